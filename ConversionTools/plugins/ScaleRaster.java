@@ -0,0 +1,352 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.IOException;
+import java.text.DecimalFormat;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool stores a raster as 16-bit integers or bytes with a scale factor
+ * and offset, reducing the size of the file, and reports the largest error in
+ * the real-world values that results.
+ */
+public class ScaleRaster implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "ScaleRaster";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Scale Raster";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Stores a raster as scaled 16-bit integers or bytes, reporting "
+                + "the largest resulting error.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "ConversionTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        boolean byteOutput = false;
+        double scale = Double.NaN;
+        double offset = Double.NaN;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        inputHeader = args[0];
+        outputHeader = args[1];
+        byteOutput = args[2].toLowerCase().contains("byte");
+        if (!args[3].toLowerCase().contains("not specified")) {
+            scale = Double.parseDouble(args[3]);
+        }
+        if (!args[4].toLowerCase().contains("not specified")) {
+            offset = Double.parseDouble(args[4]);
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (scale == 0) {
+            showFeedback("The scale factor cannot be zero.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster input = new WhiteboxRaster(inputHeader, "r");
+
+            int rows = input.getNumberRows();
+            int cols = input.getNumberColumns();
+            double inputNoData = input.getNoDataValue();
+            double[] data;
+
+            double min = Double.MAX_VALUE;
+            double max = -Double.MAX_VALUE;
+            for (int row = 0; row < rows; row++) {
+                data = input.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (data[col] != inputNoData) {
+                        if (data[col] < min) {
+                            min = data[col];
+                        }
+                        if (data[col] > max) {
+                            max = data[col];
+                        }
+                    }
+                }
+            }
+            if (max < min) {
+                // every cell is NoData
+                min = 0;
+                max = 0;
+            }
+
+            // Unless they are specified, the scale factor and offset are those
+            // that spread the range of the data over the stored values other
+            // than the NoData value, -32768 for integers and 255 for bytes.
+            DataType dataType = byteOutput ? DataType.BYTE : DataType.INTEGER;
+            double noData = byteOutput ? 255 : -32768;
+            if (Double.isNaN(scale)) {
+                scale = max > min ? (max - min) / (byteOutput ? 254 : 65534) : 1;
+                offset = byteOutput ? min : (max + min) / 2;
+            } else if (Double.isNaN(offset)) {
+                offset = 0;
+            }
+
+            WhiteboxRasterStreamWriter output = new WhiteboxRasterStreamWriter(outputHeader, inputHeader, dataType, noData);
+            output.setScaleAndOffset(scale, offset);
+            output.setPreferredPalette(input.getPreferredPalette());
+            output.setZUnits(input.getZUnits());
+
+            for (int row = 0; row < rows; row++) {
+                data = input.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (data[col] == inputNoData) {
+                        data[col] = noData;
+                    }
+                }
+                output.writeRow(row, data);
+                if (cancelOp) {
+                    input.close();
+                    output.abort();
+                    cancelOperation();
+                    return;
+                }
+                updateProgress("Writing scaled raster:", (int) (50f * (row + 1) / rows));
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.close();
+
+            // The values are read back from the output, so that the reported
+            // error is that of the file as it was written. Values beyond the
+            // range of the data type are clipped, and their error is larger
+            // than half of the scale factor.
+            double bound = Math.abs(scale) / 2;
+            double maxError = 0;
+            long numClipped = 0;
+            WhiteboxRaster scaled = new WhiteboxRaster(outputHeader, "r");
+            for (int row = 0; row < rows; row++) {
+                data = input.getRowValues(row);
+                double[] scaledData = scaled.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (data[col] != inputNoData) {
+                        double error = Math.abs(scaledData[col] - data[col]);
+                        if (error > maxError) {
+                            maxError = error;
+                        }
+                        if (error > bound * (1 + 1e-9)) {
+                            numClipped++;
+                        }
+                    }
+                }
+                updateProgress("Measuring quantization error:", (int) (50f + 50f * (row + 1) / rows));
+            }
+            scaled.close();
+            input.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+            String retstr = "SCALE RASTER\n";
+            retstr += "\nData type:\t" + (byteOutput ? "byte" : "integer");
+            retstr += "\nScale factor:\t" + scale;
+            retstr += "\nOffset:\t" + offset;
+            retstr += "\nMaximum quantization error:\t" + maxError;
+            retstr += "\nQuantization error bound:\t" + bound;
+            retstr += "\nClipped cells:\t" + new DecimalFormat("###,###,###,##0").format(numClipped);
+            returnData(retstr);
+
+        } catch (IOException e) {
+            myHost.showFeedback("The output raster could not be written. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -16,10 +16,13 @@
  */
 package plugins;
 
+import java.io.File;
 import java.util.Date;
+import java.util.PriorityQueue;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.structures.GridCell;
 
 /**
  * This tool can be used to calculate the accumulated cost of traveling from the 'source grid cell' to each other grid cell in a raster dataset. The cost-accumulation tool can be used to perform cost-distance or least-cost pathway analyses. 
@@ -165,6 +168,9 @@ public class CostAccumulation implements WhiteboxPlugin {
         String costHeader = null;
         String outputHeader = null;
         String backLinkHeader = null;
+        String destinationHeader = null;
+        int pyramidLevels = 0;
+        int corridorWidth = 10;
         WhiteboxRaster costSurface;
         WhiteboxRaster sourceImage;
         WhiteboxRaster output;
@@ -233,6 +239,18 @@ public class CostAccumulation implements WhiteboxPlugin {
                         }
                     }
                 }
+            } else if (i == 6) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    destinationHeader = args[i];
+                }
+            } else if (i == 7) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    pyramidLevels = Integer.parseInt(args[i]);
+                }
+            } else if (i == 8) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    corridorWidth = Integer.parseInt(args[i]);
+                }
             }
         }
 
@@ -251,6 +269,21 @@ public class CostAccumulation implements WhiteboxPlugin {
             }
         }
 
+        if (pyramidLevels > 0) {
+            if (destinationHeader == null) {
+                showFeedback("A destination raster must be specified to use pyramidal processing.");
+                return;
+            }
+            if (blnAnisotropicForce) {
+                showFeedback("Pyramidal processing cannot be used with an anisotropic force.");
+                return;
+            }
+            if (corridorWidth < 1) {
+                showFeedback("The corridor width must be at least one grid cell.");
+                return;
+            }
+        }
+
         try {
             sourceImage = new WhiteboxRaster(sourceHeader, "r");
             rows = sourceImage.getNumberRows();
@@ -272,7 +305,76 @@ public class CostAccumulation implements WhiteboxPlugin {
             backLink = new WhiteboxRaster(backLinkHeader, "rw", sourceHeader, WhiteboxRaster.DataType.INTEGER, noData);
             backLink.setPreferredPalette("spectrum.pal");
             backLink.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
-           
+
+            if (pyramidLevels > 0) {
+                WhiteboxRaster destinationImage = new WhiteboxRaster(destinationHeader, "r");
+                if (destinationImage.getNumberColumns() != cols
+                        || destinationImage.getNumberRows() != rows) {
+                    showFeedback("Input images must have the same dimensions");
+                    return;
+                }
+                double[][] costs = new double[rows][];
+                boolean[][] isSource = new boolean[rows][cols];
+                boolean[][] isDestination = new boolean[rows][cols];
+                double[] data;
+                for (row = 0; row < rows; row++) {
+                    costs[row] = costSurface.getRowValues(row);
+                    data = sourceImage.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        isSource[row][col] = data[col] != noData && data[col] > 0;
+                    }
+                    data = destinationImage.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        isDestination[row][col] = data[col] != noData && data[col] > 0;
+                    }
+                }
+                destinationImage.close();
+
+                double[][] accumulation = new double[rows][cols];
+                int[][] backLinkDirection = new int[rows][cols];
+                if (!pyramidalCostAccumulation(costs, isSource, isDestination, noData,
+                        pyramidLevels, corridorWidth, accumulation, backLinkDirection)) {
+                    return;
+                }
+
+                // cells that were outside of the final corridor, or that
+                // could not be reached, are NoData.
+                double[] outputData = new double[cols];
+                double[] backLinkData = new double[cols];
+                for (row = 0; row < rows; row++) {
+                    for (col = 0; col < cols; col++) {
+                        if (accumulation[row][col] < Double.POSITIVE_INFINITY) {
+                            outputData[col] = accumulation[row][col];
+                            backLinkData[col] = (backLinkDirection[row][col] >= 0)
+                                    ? backLinkDir[backLinkDirection[row][col]] : 0;
+                        } else {
+                            outputData[col] = noData;
+                            backLinkData[col] = noData;
+                        }
+                    }
+                    output.setRowValues(row, outputData);
+                    backLink.setRowValues(row, backLinkData);
+                }
+
+                for (WhiteboxRaster raster : new WhiteboxRaster[]{output, backLink}) {
+                    raster.addMetadataEntry("Created by the "
+                            + getDescriptiveName() + " tool.");
+                    raster.addMetadataEntry("Created on " + new Date());
+                    raster.addMetadataEntry("Approximate: calculated by pyramidal processing with "
+                            + pyramidLevels + " levels and a corridor width of " + corridorWidth
+                            + " cells; values are only calculated near the least-cost paths to "
+                            + new File(destinationHeader).getName());
+                }
+
+                sourceImage.close();
+                costSurface.close();
+                output.close();
+                backLink.close();
+
+                returnData(outputHeader);
+                return;
+            }
+
             updateProgress("Calculating Cost Accumulation Surface:", 0);
             double[] data;
             for (row = 0; row < rows; row++) {
@@ -636,4 +738,237 @@ public class CostAccumulation implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
-}
\ No newline at end of file
+
+    /**
+     * Calculates the accumulated cost from the source cells on a pyramid of
+     * successively finer grids. The cost surface is first decimated by a
+     * factor of 2^levels, by averaging blocks of cells, and the accumulated
+     * cost is found over the whole of this coarse grid. At each finer level,
+     * the calculation is restricted to a corridor of cells within
+     * corridorWidth cells of the least-cost paths from the destination cells
+     * found at the previous level. The result is an approximation, since the
+     * true least-cost path may leave the corridor.
+     *
+     * @return false if the operation was cancelled.
+     */
+    private boolean pyramidalCostAccumulation(double[][] costs, boolean[][] isSource,
+            boolean[][] isDestination, double noData, int levels, int corridorWidth,
+            double[][] accumulation, int[][] backLinkDirection) {
+        int rows = costs.length;
+        int cols = costs[0].length;
+
+        // Decimate the inputs, each level from the one below it. The cost of
+        // a coarse cell is the mean of the valid costs in its block, so the
+        // sums and numbers of the valid costs are carried up the pyramid.
+        double[][][] levelCosts = new double[levels + 1][][];
+        boolean[][][] levelSources = new boolean[levels + 1][][];
+        boolean[][][] levelDestinations = new boolean[levels + 1][][];
+        levelCosts[0] = costs;
+        levelSources[0] = isSource;
+        levelDestinations[0] = isDestination;
+        double[][] sums = costs;
+        int[][] counts = null;
+        for (int level = 1; level <= levels; level++) {
+            int lowerRows = levelCosts[level - 1].length;
+            int lowerCols = levelCosts[level - 1][0].length;
+            int levelRows = (lowerRows + 1) / 2;
+            int levelCols = (lowerCols + 1) / 2;
+            double[][] levelSums = new double[levelRows][levelCols];
+            int[][] levelCounts = new int[levelRows][levelCols];
+            levelSources[level] = new boolean[levelRows][levelCols];
+            levelDestinations[level] = new boolean[levelRows][levelCols];
+            for (int r = 0; r < lowerRows; r++) {
+                for (int c = 0; c < lowerCols; c++) {
+                    int count = (counts == null) ? (costs[r][c] != noData ? 1 : 0) : counts[r][c];
+                    if (count > 0) {
+                        levelSums[r / 2][c / 2] += sums[r][c];
+                        levelCounts[r / 2][c / 2] += count;
+                        levelSources[level][r / 2][c / 2] |= levelSources[level - 1][r][c];
+                        levelDestinations[level][r / 2][c / 2] |= levelDestinations[level - 1][r][c];
+                    }
+                }
+            }
+            levelCosts[level] = new double[levelRows][levelCols];
+            for (int r = 0; r < levelRows; r++) {
+                for (int c = 0; c < levelCols; c++) {
+                    levelCosts[level][r][c] = (levelCounts[r][c] > 0)
+                            ? levelSums[r][c] / levelCounts[r][c] : noData;
+                }
+            }
+            sums = levelSums;
+            counts = levelCounts;
+        }
+
+        int[][] backwardNeighbours = new int[][]{{-1, -1}, {-1, 0}, {-1, 1}, {0, -1}};
+        int[][] forwardNeighbours = new int[][]{{1, 1}, {1, 0}, {1, -1}, {0, 1}};
+        boolean[][] corridor = null;
+        for (int level = levels; level >= 0; level--) {
+            int levelRows = levelCosts[level].length;
+            int levelCols = levelCosts[level][0].length;
+            int factor = 1 << level;
+            updateProgress("Pyramid level " + level + ":", 0);
+
+            double[][] levelAccumulation = (level == 0) ? accumulation : new double[levelRows][levelCols];
+            int[][] levelBackLinks = (level == 0) ? backLinkDirection : new int[levelRows][levelCols];
+            if (!leastCostSearch(levelCosts[level], levelSources[level], corridor, noData, factor,
+                    levelAccumulation, levelBackLinks)) {
+                return false;
+            }
+            boolean[][] paths = tracePaths(levelDestinations[level], levelAccumulation, levelBackLinks);
+            if (paths == null && corridor != null) {
+                // the corridor has cut off all of the destinations, e.g. where
+                // averaging has hidden a gap in a barrier, so search the whole
+                // of this level instead.
+                if (!leastCostSearch(levelCosts[level], levelSources[level], null, noData, factor,
+                        levelAccumulation, levelBackLinks)) {
+                    return false;
+                }
+                paths = tracePaths(levelDestinations[level], levelAccumulation, levelBackLinks);
+            }
+            if (level == 0) {
+                break;
+            }
+            if (paths == null) {
+                // no destination can be reached, so there is no corridor.
+                corridor = null;
+                continue;
+            }
+
+            // The corridor at the next level contains the cells within
+            // corridorWidth cells, measured by the chessboard distance, of
+            // the cells whose parents are on a path.
+            int nextRows = levelCosts[level - 1].length;
+            int nextCols = levelCosts[level - 1][0].length;
+            int[][] distance = new int[nextRows][nextCols];
+            for (int r = 0; r < nextRows; r++) {
+                for (int c = 0; c < nextCols; c++) {
+                    distance[r][c] = paths[r / 2][c / 2] ? 0 : Integer.MAX_VALUE - 1;
+                    for (int[] d : backwardNeighbours) {
+                        int r2 = r + d[0];
+                        int c2 = c + d[1];
+                        if (r2 >= 0 && c2 >= 0 && c2 < nextCols && distance[r2][c2] + 1 < distance[r][c]) {
+                            distance[r][c] = distance[r2][c2] + 1;
+                        }
+                    }
+                }
+            }
+            corridor = new boolean[nextRows][nextCols];
+            for (int r = nextRows - 1; r >= 0; r--) {
+                for (int c = nextCols - 1; c >= 0; c--) {
+                    for (int[] d : forwardNeighbours) {
+                        int r2 = r + d[0];
+                        int c2 = c + d[1];
+                        if (r2 < nextRows && c2 >= 0 && c2 < nextCols && distance[r2][c2] + 1 < distance[r][c]) {
+                            distance[r][c] = distance[r2][c2] + 1;
+                        }
+                    }
+                    corridor[r][c] = distance[r][c] <= corridorWidth;
+                }
+            }
+        }
+        return true;
+    }
+
+    /**
+     * Finds the accumulated cost from the source cells to each cell of a grid,
+     * using Dijkstra's algorithm, and the direction index of the neighbour
+     * that each cell is reached from (-1 for sources). Cells that are outside
+     * of the corridor, if one is given, or that cannot be reached are
+     * assigned an infinite cost. The cost of a step is the mean cost of the
+     * two cells times the distance between them, in cells of the finest
+     * level, i.e. times the decimation factor.
+     *
+     * @return false if the operation was cancelled.
+     */
+    private boolean leastCostSearch(double[][] costs, boolean[][] isSource, boolean[][] corridor,
+            double noData, int factor, double[][] accumulation, int[][] backLinkDirection) {
+        int[] dX = new int[]{1, 1, 0, -1, -1, -1, 0, 1};
+        int[] dY = new int[]{0, 1, 1, 1, 0, -1, -1, -1};
+        double diagDist = Math.sqrt(2);
+        double[] dist = new double[]{1, diagDist, 1, diagDist, 1, diagDist, 1, diagDist};
+        int rows = costs.length;
+        int cols = costs[0].length;
+        PriorityQueue<GridCell> queue = new PriorityQueue<>();
+        for (int r = 0; r < rows; r++) {
+            for (int c = 0; c < cols; c++) {
+                accumulation[r][c] = Double.POSITIVE_INFINITY;
+                backLinkDirection[r][c] = -1;
+                if (isSource[r][c] && costs[r][c] != noData && (corridor == null || corridor[r][c])) {
+                    accumulation[r][c] = 0;
+                    queue.add(new GridCell(r, c, 0, noData, -1));
+                }
+            }
+        }
+        long numCells = (long) rows * cols;
+        long numDone = 0;
+        int oldProgress = -1;
+        while (!queue.isEmpty()) {
+            GridCell cell = queue.poll();
+            if (cell.z > accumulation[cell.row][cell.col]) {
+                continue;
+            }
+            double cost1 = costs[cell.row][cell.col];
+            for (int c = 0; c < 8; c++) {
+                int x = cell.col + dX[c];
+                int y = cell.row + dY[c];
+                if (y < 0 || y >= rows || x < 0 || x >= cols || costs[y][x] == noData
+                        || (corridor != null && !corridor[y][x])) {
+                    continue;
+                }
+                double newCostVal = cell.z + (cost1 + costs[y][x]) / 2 * dist[c] * factor;
+                if (newCostVal < accumulation[y][x]) {
+                    accumulation[y][x] = newCostVal;
+                    backLinkDirection[y][x] = c;
+                    queue.add(new GridCell(y, x, newCostVal, noData, -1));
+                }
+            }
+            numDone++;
+            int progress = (int) (100 * numDone / numCells);
+            if (progress != oldProgress) {
+                oldProgress = progress;
+                updateProgress(progress);
+                if (cancelOp) {
+                    cancelOperation();
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+
+    /**
+     * Follows the back-links from each reachable destination cell to a
+     * source, marking the cells on the way.
+     *
+     * @return the cells on the paths, or null if no destination was reached.
+     */
+    private static boolean[][] tracePaths(boolean[][] isDestination, double[][] accumulation,
+            int[][] backLinkDirection) {
+        int[] dX = new int[]{1, 1, 0, -1, -1, -1, 0, 1};
+        int[] dY = new int[]{0, 1, 1, 1, 0, -1, -1, -1};
+        int rows = accumulation.length;
+        int cols = accumulation[0].length;
+        boolean[][] paths = new boolean[rows][cols];
+        boolean reached = false;
+        for (int r = 0; r < rows; r++) {
+            for (int c = 0; c < cols; c++) {
+                if (isDestination[r][c] && accumulation[r][c] < Double.POSITIVE_INFINITY) {
+                    reached = true;
+                    int y = r;
+                    int x = c;
+                    while (!paths[y][x]) {
+                        paths[y][x] = true;
+                        int dir = backLinkDirection[y][x];
+                        if (dir < 0) {
+                            break;
+                        }
+                        // step back to the cell that this one was reached from
+                        y -= dY[dir];
+                        x -= dX[dir];
+                    }
+                }
+            }
+        }
+        return reached ? paths : null;
+    }
+}
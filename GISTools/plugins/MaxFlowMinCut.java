@@ -0,0 +1,677 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool finds the minimum cut separating a source region from a sink region in a cost raster, i.e. the
+ * set of grid cells with the lowest total cost that, if removed, would disconnect the two regions. It uses
+ * the push-relabel maximum flow algorithm (Goldberg and Tarjan, 1988).
+ */
+public class MaxFlowMinCut implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "MaxFlowMinCut";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Max Flow Min Cut";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Finds the lowest-cost barrier of grid cells separating a source "
+                + "region from a sink region.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "CostTools" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    // The flow network. Arcs are added in pairs, so that the reverse of arc a
+    // is arc a ^ 1, and the arcs leaving each node form a linked list.
+    private int numNodes;
+    private int numArcs;
+    private int source;
+    private int sink;
+    private int[] head;
+    private int[] nextArc;
+    private int[] arcTo;
+    private double[] residual;
+
+    // The push-relabel state. Nodes below the height numNodes that are not
+    // being discharged are kept in a bucket for their height, either in a
+    // stack of active nodes (those with excess) or a list of inactive nodes.
+    private int[] height;
+    private int[] currentArc;
+    private double[] excess;
+    private int[] activeFirst;
+    private int[] activeNext;
+    private int[] inactiveFirst;
+    private int[] inactiveNext;
+    private int[] inactivePrev;
+    private int maxActive;
+    private int maxHeight;
+    private int relabelCount;
+    private double activeExcess;
+
+    private void addArc(int u, int v, double capacity) {
+        arcTo[numArcs] = v;
+        residual[numArcs] = capacity;
+        nextArc[numArcs] = head[u];
+        head[u] = numArcs;
+        numArcs++;
+        arcTo[numArcs] = u;
+        residual[numArcs] = 0;
+        nextArc[numArcs] = head[v];
+        head[v] = numArcs;
+        numArcs++;
+    }
+
+    private void addActive(int v) {
+        int h = height[v];
+        activeNext[v] = activeFirst[h];
+        activeFirst[h] = v;
+        if (h > maxActive) {
+            maxActive = h;
+        }
+        if (h > maxHeight) {
+            maxHeight = h;
+        }
+    }
+
+    private void addInactive(int v) {
+        int h = height[v];
+        inactivePrev[v] = -1;
+        inactiveNext[v] = inactiveFirst[h];
+        if (inactiveFirst[h] >= 0) {
+            inactivePrev[inactiveFirst[h]] = v;
+        }
+        inactiveFirst[h] = v;
+        if (h > maxHeight) {
+            maxHeight = h;
+        }
+    }
+
+    private void removeInactive(int v) {
+        if (inactivePrev[v] >= 0) {
+            inactiveNext[inactivePrev[v]] = inactiveNext[v];
+        } else {
+            inactiveFirst[height[v]] = inactiveNext[v];
+        }
+        if (inactiveNext[v] >= 0) {
+            inactivePrev[inactiveNext[v]] = inactivePrev[v];
+        }
+    }
+
+    /**
+     * Sets the height of each node to its exact distance to the sink in the
+     * residual network, found by a breadth-first search backwards from the
+     * sink. Nodes that can no longer reach the sink are lifted to numNodes,
+     * beyond which they are no longer processed.
+     */
+    private void globalRelabel() {
+        for (int h = 0; h <= maxHeight; h++) {
+            activeFirst[h] = -1;
+            inactiveFirst[h] = -1;
+        }
+        maxActive = -1;
+        maxHeight = 0;
+        relabelCount = 0;
+
+        int[] queue = activeNext; // the active stacks are rebuilt below
+        for (int v = 0; v < numNodes; v++) {
+            height[v] = numNodes;
+        }
+        height[sink] = 0;
+        int queueStart = 0;
+        int queueEnd = 0;
+        queue[queueEnd++] = sink;
+        while (queueStart < queueEnd) {
+            int v = queue[queueStart++];
+            for (int a = head[v]; a >= 0; a = nextArc[a]) {
+                int u = arcTo[a];
+                if (residual[a ^ 1] > 0 && height[u] == numNodes && u != source) {
+                    height[u] = height[v] + 1;
+                    queue[queueEnd++] = u;
+                }
+            }
+        }
+
+        activeExcess = 0;
+        for (int v = 0; v < numNodes; v++) {
+            if (v != source && v != sink && height[v] < numNodes) {
+                currentArc[v] = head[v];
+                if (excess[v] > 0) {
+                    activeExcess += excess[v];
+                }
+            }
+        }
+        for (int v = 0; v < numNodes; v++) {
+            if (v != source && v != sink && height[v] < numNodes) {
+                if (excess[v] > 0) {
+                    addActive(v);
+                } else {
+                    addInactive(v);
+                }
+            }
+        }
+    }
+
+    /**
+     * Lifts all of the nodes above an empty height to numNodes, since they
+     * can no longer reach the sink.
+     */
+    private void gap(int emptyHeight) {
+        for (int h = emptyHeight + 1; h <= maxHeight; h++) {
+            for (int v = activeFirst[h]; v >= 0; v = activeNext[v]) {
+                height[v] = numNodes;
+                activeExcess -= excess[v];
+            }
+            for (int v = inactiveFirst[h]; v >= 0; v = inactiveNext[v]) {
+                height[v] = numNodes;
+            }
+            activeFirst[h] = -1;
+            inactiveFirst[h] = -1;
+        }
+        maxHeight = emptyHeight - 1;
+        maxActive = Math.min(maxActive, emptyHeight - 1);
+    }
+
+    /**
+     * Pushes the excess of a node to its neighbours, relabelling it as
+     * needed, until it has no excess or can no longer reach the sink.
+     */
+    private void discharge(int u) {
+        int h = height[u];
+        while (true) {
+            int a = currentArc[u];
+            while (a >= 0) {
+                int v = arcTo[a];
+                if (residual[a] > 0 && height[v] == h - 1) {
+                    double delta = Math.min(excess[u], residual[a]);
+                    if (v != sink && excess[v] == 0) {
+                        removeInactive(v);
+                        addActive(v);
+                    }
+                    residual[a] -= delta;
+                    residual[a ^ 1] += delta;
+                    excess[u] -= delta;
+                    excess[v] += delta;
+                    if (v == sink) {
+                        activeExcess -= delta;
+                    }
+                    if (excess[u] == 0) {
+                        break;
+                    }
+                }
+                a = nextArc[a];
+            }
+            currentArc[u] = a;
+            if (excess[u] == 0) {
+                addInactive(u);
+                return;
+            }
+
+            // relabel
+            relabelCount++;
+            if (activeFirst[h] < 0 && inactiveFirst[h] < 0) {
+                gap(h);
+                height[u] = numNodes;
+                activeExcess -= excess[u];
+                return;
+            }
+            int newHeight = numNodes;
+            for (a = head[u]; a >= 0; a = nextArc[a]) {
+                if (residual[a] > 0 && height[arcTo[a]] + 1 < newHeight) {
+                    newHeight = height[arcTo[a]] + 1;
+                }
+            }
+            height[u] = newHeight;
+            currentArc[u] = head[u];
+            if (newHeight >= numNodes) {
+                activeExcess -= excess[u];
+                return;
+            }
+            h = newHeight;
+            if (h > maxHeight) {
+                maxHeight = h;
+            }
+        }
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String costHeader = null;
+        String sourceHeader = null;
+        String sinkHeader = null;
+        String outputHeader = null;
+        int[] dX = new int[]{1, 1, 0, -1, -1, -1, 0, 1};
+        int[] dY = new int[]{0, 1, 1, 1, 0, -1, -1, -1};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                costHeader = args[i];
+            } else if (i == 1) {
+                sourceHeader = args[i];
+            } else if (i == 2) {
+                sinkHeader = args[i];
+            } else if (i == 3) {
+                outputHeader = args[i];
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((costHeader == null) || (sourceHeader == null) || (sinkHeader == null)
+                || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster costSurface = new WhiteboxRaster(costHeader, "r");
+            int rows = costSurface.getNumberRows();
+            int cols = costSurface.getNumberColumns();
+            double noData = costSurface.getNoDataValue();
+
+            WhiteboxRaster sourceImage = new WhiteboxRaster(sourceHeader, "r");
+            WhiteboxRaster sinkImage = new WhiteboxRaster(sinkHeader, "r");
+            if (sourceImage.getNumberColumns() != cols || sourceImage.getNumberRows() != rows
+                    || sinkImage.getNumberColumns() != cols || sinkImage.getNumberRows() != rows) {
+                showFeedback("Input images must have the same dimensions");
+                return;
+            }
+            double sourceNoData = sourceImage.getNoDataValue();
+            double sinkNoData = sinkImage.getNoDataValue();
+
+            // read the inputs and count the nodes and arcs of the network
+            updateProgress("Building the flow network:", 0);
+            double[][] cost = new double[rows][];
+            byte[][] terminal = new byte[rows][cols]; // 1 for sources, 2 for sinks
+            int numCells = 0;
+            int numSources = 0;
+            int numSinks = 0;
+            double totalCost = 0;
+            for (int row = 0; row < rows; row++) {
+                cost[row] = costSurface.getRowValues(row);
+                double[] sources = sourceImage.getRowValues(row);
+                double[] sinks = sinkImage.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (cost[row][col] == noData) {
+                        continue;
+                    }
+                    if (cost[row][col] < 0) {
+                        showFeedback("The cost raster must not contain negative values.");
+                        return;
+                    }
+                    numCells++;
+                    totalCost += cost[row][col];
+                    boolean isSource = sources[col] != sourceNoData && sources[col] > 0;
+                    boolean isSink = sinks[col] != sinkNoData && sinks[col] > 0;
+                    if (isSource && isSink) {
+                        showFeedback("The source and sink regions must not overlap.");
+                        return;
+                    }
+                    if (isSource) {
+                        terminal[row][col] = 1;
+                        numSources++;
+                    } else if (isSink) {
+                        terminal[row][col] = 2;
+                        numSinks++;
+                    }
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress((int) (100f * row / rows));
+            }
+            if (numSources == 0 || numSinks == 0) {
+                showFeedback("The source and sink rasters must each contain at least one "
+                        + "positive-valued cell that is not NoData in the cost raster.");
+                return;
+            }
+
+            // Each cell is split into an in-node and an out-node, joined by an
+            // arc with the cell's cost as its capacity, so that the minimum
+            // cut is a set of cells. Flow moves between neighbouring cells,
+            // from the out-node of one to the in-node of the other, along arcs
+            // that are never part of the minimum cut.
+            int[][] cellIndex = new int[rows][cols];
+            int n = 0;
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    cellIndex[row][col] = (cost[row][col] != noData) ? n++ : -1;
+                }
+            }
+            long arcCount = 2L * (numCells + numSources + numSinks);
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    if (cellIndex[row][col] >= 0) {
+                        for (int c = 0; c < 8; c++) {
+                            int x = col + dX[c];
+                            int y = row + dY[c];
+                            if (y >= 0 && y < rows && x >= 0 && x < cols && cellIndex[y][x] >= 0) {
+                                arcCount += 2;
+                            }
+                        }
+                    }
+                }
+            }
+            if (arcCount > Integer.MAX_VALUE) {
+                showFeedback("The input raster is too large for this tool.");
+                return;
+            }
+
+            numNodes = 2 * numCells + 2;
+            source = numNodes - 2;
+            sink = numNodes - 1;
+            numArcs = 0;
+            head = new int[numNodes];
+            Arrays.fill(head, -1);
+            nextArc = new int[(int) arcCount];
+            arcTo = new int[(int) arcCount];
+            residual = new double[(int) arcCount];
+
+            // an effectively infinite capacity, larger than any finite cut
+            double infinity = totalCost + 1;
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    int i = cellIndex[row][col];
+                    if (i < 0) {
+                        continue;
+                    }
+                    addArc(2 * i, 2 * i + 1, cost[row][col]);
+                    for (int c = 0; c < 8; c++) {
+                        int x = col + dX[c];
+                        int y = row + dY[c];
+                        if (y >= 0 && y < rows && x >= 0 && x < cols && cellIndex[y][x] >= 0) {
+                            addArc(2 * i + 1, 2 * cellIndex[y][x], infinity);
+                        }
+                    }
+                    // source and sink cells can't be part of the cut
+                    if (terminal[row][col] == 1) {
+                        addArc(source, 2 * i + 1, infinity);
+                    } else if (terminal[row][col] == 2) {
+                        addArc(2 * i, sink, infinity);
+                    }
+                }
+            }
+            cost = null;
+
+            // Find the maximum preflow using the highest-label push-relabel
+            // algorithm, with the gap and global relabelling heuristics. This
+            // is sufficient to find the minimum cut.
+            updateProgress("Solving the maximum flow:", 0);
+            height = new int[numNodes];
+            currentArc = new int[numNodes];
+            excess = new double[numNodes];
+            activeFirst = new int[numNodes];
+            activeNext = new int[numNodes];
+            inactiveFirst = new int[numNodes];
+            inactiveNext = new int[numNodes];
+            inactivePrev = new int[numNodes];
+            Arrays.fill(activeFirst, -1);
+            Arrays.fill(inactiveFirst, -1);
+            maxHeight = numNodes - 1;
+            for (int a = head[source]; a >= 0; a = nextArc[a]) {
+                excess[arcTo[a]] += residual[a];
+                residual[a ^ 1] += residual[a];
+                residual[a] = 0;
+            }
+            globalRelabel();
+            double initialExcess = activeExcess;
+            int iteration = 0;
+            while (maxActive >= 0) {
+                int u = activeFirst[maxActive];
+                if (u < 0) {
+                    maxActive--;
+                    continue;
+                }
+                activeFirst[maxActive] = activeNext[u];
+                discharge(u);
+                if (relabelCount >= numNodes) {
+                    globalRelabel();
+                }
+                iteration++;
+                if ((iteration & 1023) == 0) {
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    updateProgress((int) (100 * (1 - Math.max(0, activeExcess) / initialExcess)));
+                }
+            }
+
+            double maxFlow = excess[sink];
+            if (maxFlow >= infinity) {
+                showFeedback("The source and sink regions are adjacent, and so "
+                        + "cannot be separated by a cut.");
+                return;
+            }
+
+            // The sink side of the minimum cut contains the nodes that can
+            // still reach the sink in the residual network. The cut cells are
+            // those with their in-node on the source side and out-node on the
+            // sink side.
+            updateProgress("Finding the minimum cut:", 0);
+            boolean[] sinkSide = new boolean[numNodes];
+            int[] queue = activeNext;
+            int queueStart = 0;
+            int queueEnd = 0;
+            sinkSide[sink] = true;
+            queue[queueEnd++] = sink;
+            while (queueStart < queueEnd) {
+                int v = queue[queueStart++];
+                for (int a = head[v]; a >= 0; a = nextArc[a]) {
+                    int u = arcTo[a];
+                    if (!sinkSide[u] && residual[a ^ 1] > 0) {
+                        sinkSide[u] = true;
+                        queue[queueEnd++] = u;
+                    }
+                }
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", costHeader, WhiteboxRaster.DataType.INTEGER, noData);
+            output.setPreferredPalette("black_white.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.BOOLEAN);
+            int numCutCells = 0;
+            double[] data = new double[cols];
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    int i = cellIndex[row][col];
+                    if (i < 0) {
+                        data[col] = noData;
+                    } else if (!sinkSide[2 * i] && sinkSide[2 * i + 1]) {
+                        data[col] = 1;
+                        numCutCells++;
+                    } else {
+                        data[col] = 0;
+                    }
+                }
+                output.setRowValues(row, data);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress((int) (100f * row / rows));
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Minimum cut capacity: " + maxFlow);
+            output.addMetadataEntry("Number of cut cells: " + numCutCells);
+
+            costSurface.close();
+            sourceImage.close();
+            sinkImage.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            // release the network
+            head = null;
+            nextArc = null;
+            arcTo = null;
+            residual = null;
+            height = null;
+            currentArc = null;
+            excess = null;
+            activeFirst = null;
+            activeNext = null;
+            inactiveFirst = null;
+            inactiveNext = null;
+            inactivePrev = null;
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
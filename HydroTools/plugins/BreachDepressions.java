@@ -25,6 +25,7 @@ import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 import whitebox.utilities.FileUtilities;
+import whitebox.utilities.StringUtilities;
 
 /**
  * This tool can be used to pre-process a digital elevation model (DEM) prior to being used for hydrological analysis.
@@ -244,7 +245,7 @@ public class BreachDepressions implements WhiteboxPlugin {
             return;
         }
         
-        Long startTime = System.currentTimeMillis(); 
+        long startTime = System.nanoTime();
 
         try {
             WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
@@ -558,8 +559,6 @@ public class BreachDepressions implements WhiteboxPlugin {
 
             output.close();
 
-            Long endTime = System.currentTimeMillis();
-            
             // returning a header file string displays the image.
             returnData(outputHeader);
 
@@ -574,10 +573,7 @@ public class BreachDepressions implements WhiteboxPlugin {
             if (useMaxCost) {
                 results += "\nMax Elev. Decrement:\t" + maxCost;
             }
-            // format the duration
-            long sec = (endTime - startTime) / 1000;
-            String duration = String.format("%02d:%02d:%02d:%02d", sec / 86400, (sec % 86400) / 3600, (sec % 3600) / 60, (sec % 60));
-            results += "\nDuration:\t" + duration;
+            results += "\nDuration:\t" + StringUtilities.formatElapsedTime(System.nanoTime() - startTime);
             returnData(results);
         } catch (OutOfMemoryError oe) {
             myHost.showFeedback("An out-of-memory error has occurred during operation.");
@@ -24,6 +24,7 @@ import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 import whitebox.structures.BooleanBitArray2D;
 import whitebox.structures.NibbleArray2D;
+import whitebox.utilities.StringUtilities;
 
 /**
  * This tool can be used as an alternative to Breach Depression, the results will not be as optimal as the solution offered by Breach Depression, however it is considerably faster to run.
@@ -200,7 +201,7 @@ public class BreachDepressionsFast implements WhiteboxPlugin {
         amIActive = true;
 
         try {
-            Long startTime = System.currentTimeMillis();
+            long startTime = System.nanoTime();
 
             int progress, oldProgress, col, row, colN, rowN, r, c;
             int numSolvedCells = 0;
@@ -624,9 +625,7 @@ public class BreachDepressionsFast implements WhiteboxPlugin {
 
             outputRaster.addMetadataEntry("Created by the " + this.getDescriptiveName() + " tool.");
             outputRaster.addMetadataEntry("Created on " + new Date());
-            Long endTime = System.currentTimeMillis();
-            long sec = (endTime - startTime) / 1000;
-            String duration = String.format("%02d:%02d:%02d:%02d", sec / 86400, (sec % 86400) / 3600, (sec % 3600) / 60, (sec % 60));
+            String duration = StringUtilities.formatElapsedTime(System.nanoTime() - startTime);
 
             outputRaster.addMetadataEntry("Elapsed time: " + duration);
             outputRaster.addMetadataEntry("Max breach depth: " + maxDepth);
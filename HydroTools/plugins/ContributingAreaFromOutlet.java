@@ -0,0 +1,481 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.ArrayDeque;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Date;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.TreeMap;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool marks the full contributing area, i.e. all of the grid cells that
+ * drain to it, of each of a group of outlet cells, which may be located
+ * anywhere within a D8 flow pointer grid.
+ */
+public class ContributingAreaFromOutlet implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "ContributingAreaFromOutlet";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Contributing Area From Outlet";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Traces upslope from one or more outlet cells to mark the full area draining to each.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"WatershedTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    private static final int[] dX = {1, 1, 1, 0, -1, -1, -1, 0};
+    private static final int[] dY = {-1, 0, 1, 1, 1, 0, -1, -1};
+    // the pointer value of a neighbour that flows into the centre cell
+    private static final double[] inflowingVals = {16, 32, 64, 128, 1, 2, 4, 8};
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String pointerHeader = null;
+        String outletHeader = null;
+        String outputHeader = null;
+        boolean labelDownstream = true;
+        int row, col, i, k;
+        int progress;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                pointerHeader = args[i];
+            } else if (i == 1) {
+                outletHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                labelDownstream = !args[i].toLowerCase().contains("upstream");
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((pointerHeader == null) || (outletHeader == null) || (outputHeader == null)
+                || pointerHeader.isEmpty() || outletHeader.isEmpty() || outputHeader.isEmpty()) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster pntr = new WhiteboxRaster(pointerHeader, "r");
+            int rows = pntr.getNumberRows();
+            int cols = pntr.getNumberColumns();
+            double noData = pntr.getNoDataValue();
+
+            // the seed cells of each outlet, keyed by outlet ID; the cells are 
+            // stored as row * cols + col.
+            TreeMap<Double, ArrayList<Integer>> outlets = new TreeMap<>();
+            int numOffGrid = 0;
+
+            if (outletHeader.toLowerCase().endsWith(".shp")) {
+                ShapeFile input = new ShapeFile(outletHeader);
+                if (input.getShapeType().getBaseType() != ShapeType.POINT
+                        && input.getShapeType().getBaseType() != ShapeType.MULTIPOINT) {
+                    showFeedback("The input outlet vector should be \n"
+                            + "of a Point or MultiPoint ShapeType.");
+                    return;
+                }
+                // outlets are numbered in the order that they are read
+                double outletID = 1;
+                for (ShapeFileRecord record : input.records) {
+                    double[][] vertices = record.getGeometry().getPoints();
+                    for (double[] vertex : vertices) {
+                        row = pntr.getRowFromYCoordinate(vertex[1]);
+                        col = pntr.getColumnFromXCoordinate(vertex[0]);
+                        if (row < 0 || row >= rows || col < 0 || col >= cols) {
+                            numOffGrid++;
+                        } else {
+                            ArrayList<Integer> cells = new ArrayList<>();
+                            cells.add(row * cols + col);
+                            outlets.put(outletID, cells);
+                        }
+                        outletID++;
+                    }
+                }
+            } else if (outletHeader.toLowerCase().endsWith(".dep")) {
+                WhiteboxRaster outlet = new WhiteboxRaster(outletHeader, "r");
+                if (outlet.getNumberRows() != rows || outlet.getNumberColumns() != cols) {
+                    showFeedback("The input images must be of the same dimensions.");
+                    return;
+                }
+                double outletNoData = outlet.getNoDataValue();
+                double z;
+                for (row = 0; row < rows; row++) {
+                    for (col = 0; col < cols; col++) {
+                        z = outlet.getValue(row, col);
+                        if (z != 0 && z != outletNoData) {
+                            ArrayList<Integer> cells = outlets.get(z);
+                            if (cells == null) {
+                                cells = new ArrayList<>();
+                                outlets.put(z, cells);
+                            }
+                            cells.add(row * cols + col);
+                        }
+                    }
+                }
+                outlet.close();
+            } else {
+                showFeedback("Unrecognized input outlets file type.");
+                return;
+            }
+
+            if (outlets.isEmpty()) {
+                showFeedback("No outlets were located within the flow pointer grid.");
+                return;
+            }
+
+            int numOutlets = outlets.size();
+            double[] ids = new double[numOutlets];
+            HashMap<Integer, Double> outletOfCell = new HashMap<>();
+            k = 0;
+            for (Map.Entry<Double, ArrayList<Integer>> entry : outlets.entrySet()) {
+                ids[k] = entry.getKey();
+                for (int cell : entry.getValue()) {
+                    outletOfCell.put(cell, entry.getKey());
+                }
+                k++;
+            }
+
+            // stamp[cell] holds the number of the last trace or flood to visit
+            // a cell, so that each is a fresh search without clearing the array
+            // and cycles in the pointer cannot trap a search.
+            int[] stamp = new int[rows * cols];
+            int stampNum = 0;
+
+            // count the other outlets downstream of each outlet. Where one 
+            // outlet is nested within another's contributing area, it has more
+            // outlets downstream of it, which orders the floods.
+            int[] numDownstream = new int[numOutlets];
+            int r, c, cell, dir;
+            double flowDir;
+            for (k = 0; k < numOutlets; k++) {
+                ArrayList<Double> found = new ArrayList<>();
+                for (int seed : outlets.get(ids[k])) {
+                    stampNum++;
+                    r = seed / cols;
+                    c = seed % cols;
+                    while (true) {
+                        cell = r * cols + c;
+                        if (stamp[cell] == stampNum) {
+                            break;
+                        }
+                        stamp[cell] = stampNum;
+                        Double id = outletOfCell.get(cell);
+                        if (id != null && id != ids[k] && !found.contains(id)) {
+                            found.add(id);
+                        }
+                        flowDir = pntr.getValue(r, c);
+                        dir = getDirection(flowDir, noData);
+                        if (dir < 0) {
+                            break;
+                        }
+                        r += dY[dir];
+                        c += dX[dir];
+                        if (r < 0 || r >= rows || c < 0 || c >= cols) {
+                            break;
+                        }
+                    }
+                }
+                numDownstream[k] = found.size();
+            }
+
+            // the outlet that should label cells shared by nested areas is
+            // flooded last
+            Integer[] order = new Integer[numOutlets];
+            for (k = 0; k < numOutlets; k++) {
+                order[k] = k;
+            }
+            final int[] nd = numDownstream;
+            final boolean downstreamLast = labelDownstream;
+            Arrays.sort(order, (a, b) -> downstreamLast ? nd[b] - nd[a] : nd[a] - nd[b]);
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    pointerHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setDataScale(WhiteboxRasterBase.DataScale.CATEGORICAL);
+            output.setPreferredPalette("categorical1.pal");
+
+            long[] numCells = new long[numOutlets];
+            ArrayDeque<Integer> queue = new ArrayDeque<>();
+            int rN, cN;
+            for (int n = 0; n < numOutlets; n++) {
+                k = order[n];
+                stampNum++;
+                for (int seed : outlets.get(ids[k])) {
+                    if (pntr.getValue(seed / cols, seed % cols) != noData
+                            && stamp[seed] != stampNum) {
+                        stamp[seed] = stampNum;
+                        queue.add(seed);
+                    }
+                }
+                // flood upslope through the inflowing neighbours of each cell
+                while (!queue.isEmpty()) {
+                    cell = queue.poll();
+                    r = cell / cols;
+                    c = cell % cols;
+                    output.setValue(r, c, ids[k]);
+                    numCells[k]++;
+                    for (i = 0; i < 8; i++) {
+                        rN = r + dY[i];
+                        cN = c + dX[i];
+                        if (rN < 0 || rN >= rows || cN < 0 || cN >= cols
+                                || stamp[rN * cols + cN] == stampNum) {
+                            continue;
+                        }
+                        if (pntr.getValue(rN, cN) == inflowingVals[i]) {
+                            stamp[rN * cols + cN] = stampNum;
+                            queue.add(rN * cols + cN);
+                        }
+                    }
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * (n + 1) / numOutlets);
+                updateProgress("Tracing outlets:", progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Nested areas labelled with the "
+                    + (labelDownstream ? "downstream" : "upstream") + " outlet");
+
+            double cellArea = pntr.getCellSizeX() * pntr.getCellSizeY();
+            pntr.close();
+            output.close();
+
+            StringBuilder sb = new StringBuilder();
+            sb.append("CONTRIBUTING AREA FROM OUTLET\n\n");
+            sb.append("Outlet ID\tCells\tArea\tOutlets Downstream\n");
+            for (k = 0; k < numOutlets; k++) {
+                sb.append(formatID(ids[k])).append("\t").append(numCells[k]).append("\t")
+                        .append(numCells[k] * cellArea).append("\t")
+                        .append(numDownstream[k]).append("\n");
+            }
+            if (numOffGrid > 0) {
+                sb.append("\n").append(numOffGrid)
+                        .append(" outlet points lie outside of the flow pointer grid.\n");
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            returnData(sb.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Returns the index (0-7) of the neighbour that a D8 pointer value points
+     * to, or -1 if the value is NoData, zero or not a valid pointer value.
+     */
+    private static int getDirection(double value, double noData) {
+        if (value == noData) {
+            return -1;
+        }
+        for (int i = 0; i < 8; i++) {
+            if (value == (1 << i)) {
+                return i;
+            }
+        }
+        return -1;
+    }
+
+    private static String formatID(double id) {
+        if (id == Math.rint(id)) {
+            return String.valueOf((long) id);
+        }
+        return String.valueOf(id);
+    }
+}
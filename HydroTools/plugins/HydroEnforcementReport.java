@@ -0,0 +1,729 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.text.DecimalFormat;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.Date;
+import java.util.List;
+import java.util.PriorityQueue;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.shapefile.Geometry;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool audits the hydrological conditioning of a DEM. It compares a
+ * conditioned DEM with the original DEM, summarizing the number and volume of
+ * modified cells, and checks whether the D8 channels modelled on the
+ * conditioned DEM follow a mapped stream network to within a buffer distance.
+ * The results are written to an HTML report and the worst problem sites to a
+ * GeoJSON file of points.
+ */
+public class HydroEnforcementReport implements WhiteboxPlugin {
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "HydroEnforcementReport";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Hydro-Enforcement Report";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Audits a hydrologically conditioned DEM against the original DEM and a mapped stream network.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"DEMPreprocessing", "StreamAnalysis"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 8) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String originalHeader = args[0];
+        String conditionedHeader = args[1];
+        String streamsFile = null;
+        if (!args[2].toLowerCase().contains("not specified")) {
+            streamsFile = args[2];
+        }
+        double bufferDistance = Double.parseDouble(args[3]);
+        double channelThreshold = Double.parseDouble(args[4]);
+        int numSites = Integer.parseInt(args[5]);
+        String reportFile = args[6];
+        String sitesFile = null;
+        if (!args[7].toLowerCase().contains("not specified")) {
+            sitesFile = args[7];
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((originalHeader == null) || (conditionedHeader == null) || (reportFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (bufferDistance < 0 || numSites < 0) {
+            showFeedback("The buffer distance and the number of problem sites must not be negative.");
+            return;
+        }
+
+        try {
+            int row, col, r, c, i, n;
+            double z, z2, change;
+            int progress, oldProgress;
+
+            WhiteboxRaster original = new WhiteboxRaster(originalHeader, "r");
+            WhiteboxRaster conditioned = new WhiteboxRaster(conditionedHeader, "r");
+
+            int rows = conditioned.getNumberRows();
+            int cols = conditioned.getNumberColumns();
+            if (original.getNumberRows() != rows || original.getNumberColumns() != cols) {
+                showFeedback("The original and conditioned DEMs must have the same number of rows and columns.");
+                return;
+            }
+            if ((long) rows * cols > Integer.MAX_VALUE) {
+                showFeedback("The input DEMs are too large for this tool.");
+                return;
+            }
+            double noData = conditioned.getNoDataValue();
+            double originalNoData = original.getNoDataValue();
+            double cellSizeX = conditioned.getCellSizeX();
+            double cellSizeY = conditioned.getCellSizeY();
+            double cellArea = cellSizeX * cellSizeY;
+
+            // Compare the DEMs, keeping the conditioned elevations in memory
+            // for the flow modelling.
+            updateProgress("Comparing DEMs:", 0);
+            double[] elevations = new double[rows * cols];
+            long numValid = 0;
+            long numMismatchedNoData = 0;
+            long numRaised = 0;
+            long numLowered = 0;
+            double volumeRaised = 0;
+            double volumeLowered = 0;
+            double maxRaise = 0;
+            double maxLowering = 0;
+            double sumAbsChange = 0;
+            PriorityQueue<Site> changeSites = new PriorityQueue<>();
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                double[] originalData = original.getRowValues(row);
+                double[] conditionedData = conditioned.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = conditionedData[col];
+                    z2 = originalData[col];
+                    elevations[row * cols + col] = z;
+                    if ((z == noData) != (z2 == originalNoData)) {
+                        numMismatchedNoData++;
+                    } else if (z != noData) {
+                        numValid++;
+                        change = z - z2;
+                        if (change > 0) {
+                            numRaised++;
+                            volumeRaised += change * cellArea;
+                            maxRaise = Math.max(maxRaise, change);
+                        } else if (change < 0) {
+                            numLowered++;
+                            volumeLowered -= change * cellArea;
+                            maxLowering = Math.max(maxLowering, -change);
+                        }
+                        if (change != 0) {
+                            sumAbsChange += Math.abs(change);
+                            addSite(changeSites, new Site(conditioned.getXCoordinateFromColumn(col),
+                                    conditioned.getYCoordinateFromRow(row), Math.abs(change), change), numSites);
+                        }
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            long numModified = numRaised + numLowered;
+
+            String originalName = original.getShortHeaderFile();
+            String conditionedName = conditioned.getShortHeaderFile();
+            original.close();
+
+            // D8 flow directions on the conditioned DEM. Cells without a
+            // downslope neighbour, including those on flats, have no flow
+            // direction.
+            updateProgress("Calculating flow directions:", 0);
+            int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+            int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+            double[] neighbourDist = new double[8];
+            for (i = 0; i < 8; i++) {
+                neighbourDist[i] = Math.sqrt(dX[i] * cellSizeX * dX[i] * cellSizeX
+                        + dY[i] * cellSizeY * dY[i] * cellSizeY);
+            }
+            int[] downslope = new int[rows * cols];
+            int[] numInflowing = new int[rows * cols];
+            double slope, maxSlope;
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    n = row * cols + col;
+                    downslope[n] = -1;
+                    z = elevations[n];
+                    if (z != noData) {
+                        maxSlope = 0;
+                        for (i = 0; i < 8; i++) {
+                            r = row + dY[i];
+                            c = col + dX[i];
+                            if (r >= 0 && r < rows && c >= 0 && c < cols) {
+                                z2 = elevations[r * cols + c];
+                                if (z2 != noData) {
+                                    slope = (z - z2) / neighbourDist[i];
+                                    if (slope > maxSlope) {
+                                        maxSlope = slope;
+                                        downslope[n] = r * cols + c;
+                                    }
+                                }
+                            }
+                        }
+                        if (downslope[n] >= 0) {
+                            numInflowing[downslope[n]]++;
+                        }
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            // D8 flow accumulation, in grid cells, visiting each cell after
+            // all of the cells that drain to it.
+            updateProgress("Calculating flow accumulation:", 0);
+            double[] accumulation = new double[rows * cols];
+            int[] stack = new int[rows * cols];
+            int stackSize = 0;
+            for (n = 0; n < rows * cols; n++) {
+                if (elevations[n] != noData) {
+                    accumulation[n] = 1;
+                    if (numInflowing[n] == 0) {
+                        stack[stackSize] = n;
+                        stackSize++;
+                    }
+                }
+            }
+            while (stackSize > 0) {
+                stackSize--;
+                n = stack[stackSize];
+                int m = downslope[n];
+                if (m >= 0) {
+                    accumulation[m] += accumulation[n];
+                    numInflowing[m]--;
+                    if (numInflowing[m] == 0) {
+                        stack[stackSize] = m;
+                        stackSize++;
+                    }
+                }
+            }
+            stack = null;
+            numInflowing = null;
+            downslope = null;
+            elevations = null;
+
+            boolean[] isChannel = new boolean[rows * cols];
+            long numChannelCells = 0;
+            for (n = 0; n < rows * cols; n++) {
+                if (accumulation[n] >= channelThreshold) {
+                    isChannel[n] = true;
+                    numChannelCells++;
+                }
+            }
+            accumulation = null;
+
+            // Walk along the mapped streams in steps of no more than half a
+            // cell and check whether there is a modelled channel cell within
+            // the buffer distance of each step's midpoint. Runs of consecutive
+            // failing steps are the disagreements, located at their middles.
+            double mappedLength = 0;
+            double followedLength = 0;
+            double failedLength = 0;
+            double outsideLength = 0;
+            long numDisagreements = 0;
+            PriorityQueue<Site> streamSites = new PriorityQueue<>();
+            if (streamsFile != null) {
+                updateProgress("Checking the mapped streams:", 0);
+                ShapeFile streams = new ShapeFile(streamsFile);
+                if (streams.getShapeType().getBaseType() != ShapeType.POLYLINE) {
+                    showFeedback("The input streams file must be of a PolyLine ShapeType.");
+                    return;
+                }
+                double step = Math.min(cellSizeX, cellSizeY) / 2.0;
+                int searchRows = (int) Math.ceil(bufferDistance / cellSizeY);
+                int searchCols = (int) Math.ceil(bufferDistance / cellSizeX);
+                double bufferSqr = bufferDistance * bufferDistance;
+                int numRecords = streams.getNumberOfRecords();
+                int recordNum = 0;
+                oldProgress = -1;
+                for (ShapeFileRecord record : streams.records) {
+                    recordNum++;
+                    if (record.getShapeType() == ShapeType.NULLSHAPE) {
+                        continue;
+                    }
+                    Geometry geometry = record.getGeometry();
+                    double[][] vertices = geometry.getPoints();
+                    int[] parts = geometry.getParts();
+                    for (int part = 0; part < parts.length; part++) {
+                        int start = parts[part];
+                        int end = (part < parts.length - 1) ? parts[part + 1] : vertices.length;
+                        List<double[]> run = new ArrayList<>();
+                        double runLength = 0;
+                        for (int v = start + 1; v < end; v++) {
+                            double x1 = vertices[v - 1][0];
+                            double y1 = vertices[v - 1][1];
+                            double dx = vertices[v][0] - x1;
+                            double dy = vertices[v][1] - y1;
+                            double length = Math.sqrt(dx * dx + dy * dy);
+                            mappedLength += length;
+                            int numSteps = Math.max(1, (int) Math.ceil(length / step));
+                            double stepLength = length / numSteps;
+                            for (int k = 0; k < numSteps; k++) {
+                                double t = (k + 0.5) / numSteps;
+                                double x = x1 + t * dx;
+                                double y = y1 + t * dy;
+                                row = conditioned.getRowFromYCoordinate(y);
+                                col = conditioned.getColumnFromXCoordinate(x);
+                                boolean failed = false;
+                                if (row < 0 || row >= rows || col < 0 || col >= cols
+                                        || conditioned.getValue(row, col) == noData) {
+                                    outsideLength += stepLength;
+                                } else if (isChannelNearby(conditioned, isChannel, x, y, row, col,
+                                        searchRows, searchCols, bufferSqr)) {
+                                    followedLength += stepLength;
+                                } else {
+                                    failedLength += stepLength;
+                                    run.add(new double[]{x, y});
+                                    runLength += stepLength;
+                                    failed = true;
+                                }
+                                if (!failed && !run.isEmpty()) {
+                                    numDisagreements++;
+                                    addSite(streamSites, midpointSite(run, runLength), numSites);
+                                    run.clear();
+                                    runLength = 0;
+                                }
+                            }
+                        }
+                        if (!run.isEmpty()) {
+                            numDisagreements++;
+                            addSite(streamSites, midpointSite(run, runLength), numSites);
+                        }
+                    }
+                    progress = (int) (100f * recordNum / numRecords);
+                    if (progress != oldProgress) {
+                        oldProgress = progress;
+                        updateProgress(progress);
+                        if (cancelOp) {
+                            cancelOperation();
+                            return;
+                        }
+                    }
+                }
+            }
+            conditioned.close();
+
+            List<Site> sortedChangeSites = new ArrayList<>(changeSites);
+            Collections.sort(sortedChangeSites, Collections.reverseOrder());
+            List<Site> sortedStreamSites = new ArrayList<>(streamSites);
+            Collections.sort(sortedStreamSites, Collections.reverseOrder());
+
+            // write the report
+            File report;
+            if (!reportFile.toLowerCase().endsWith(".html")) {
+                reportFile += ".html";
+            }
+            report = new File(reportFile);
+
+            DecimalFormat df = new DecimalFormat("###,###,###,##0.000");
+            DecimalFormat df2 = new DecimalFormat("###,###,###,###");
+            StringBuilder sb = new StringBuilder();
+            sb.append("<!DOCTYPE html>\n");
+            sb.append("<html lang=\"en\">\n");
+            sb.append("<head>\n");
+            sb.append("<meta content=\"text/html; charset=iso-8859-1\" http-equiv=\"content-type\">");
+            sb.append("<title>Hydro-Enforcement Report</title>\n");
+            sb.append("<style type=\"text/css\">");
+            sb.append("h1 {font-size: 14pt; margin-left: 15px; margin-right: 15px; text-align: center; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;} ");
+            sb.append("h2 {font-size: 12pt; margin-left: 15px; margin-right: 15px; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;} ");
+            sb.append("p {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ");
+            sb.append("table {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px;} ");
+            sb.append("table th {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #dedede; } ");
+            sb.append("table td {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #ffffff; } ");
+            sb.append("caption {font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ");
+            sb.append(".numberCell { text-align: right; }");
+            sb.append("</style>\n</head>\n");
+            sb.append("<body><h1>Hydro-Enforcement Report</h1>\n");
+
+            sb.append("<p><b>Original DEM:</b> ").append(originalName).append("<br>\n");
+            sb.append("<b>Conditioned DEM:</b> ").append(conditionedName).append("<br>\n");
+            if (streamsFile != null) {
+                sb.append("<b>Mapped streams:</b> ").append(new File(streamsFile).getName()).append("<br>\n");
+            }
+            sb.append("<b>Created on:</b> ").append(new Date()).append("</p>\n");
+
+            sb.append("<h2>DEM Modifications</h2>\n");
+            sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+            sb.append("<tr><th></th><th>Raised</th><th>Lowered</th><th>All Modified</th></tr>\n");
+            sb.append("<tr><td>Number of Cells</td><td class=\"numberCell\">").append(df2.format(numRaised));
+            sb.append("</td><td class=\"numberCell\">").append(df2.format(numLowered));
+            sb.append("</td><td class=\"numberCell\">").append(df2.format(numModified)).append("</td></tr>\n");
+            sb.append("<tr><td>Percent of Valid Cells</td><td class=\"numberCell\">").append(df.format(percent(numRaised, numValid)));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(percent(numLowered, numValid)));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(percent(numModified, numValid))).append("</td></tr>\n");
+            sb.append("<tr><td>Volume</td><td class=\"numberCell\">").append(df.format(volumeRaised));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(volumeLowered));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(volumeRaised + volumeLowered)).append("</td></tr>\n");
+            sb.append("<tr><td>Maximum Elevation Change</td><td class=\"numberCell\">").append(df.format(maxRaise));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(maxLowering));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(Math.max(maxRaise, maxLowering))).append("</td></tr>\n");
+            sb.append("<tr><td>Mean Elevation Change</td><td class=\"numberCell\">").append(df.format(numRaised > 0 ? volumeRaised / cellArea / numRaised : 0));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(numLowered > 0 ? volumeLowered / cellArea / numLowered : 0));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(numModified > 0 ? sumAbsChange / numModified : 0)).append("</td></tr>\n");
+            sb.append("</table></p>\n");
+            sb.append("<p>Volumes are in the horizontal units squared times the vertical units. Mean elevation changes ");
+            sb.append("are the mean absolute changes of the modified cells.");
+            if (numMismatchedNoData > 0) {
+                sb.append(" <b>Warning:</b> ").append(df2.format(numMismatchedNoData));
+                sb.append(" cells are NoData in one DEM but not in the other and were excluded.");
+            }
+            sb.append("</p>\n");
+
+            sb.append("<h2>Mapped Stream Agreement</h2>\n");
+            if (streamsFile != null) {
+                double evaluatedLength = followedLength + failedLength;
+                sb.append("<p>Modelled channels are the cells of the conditioned DEM with a D8 flow accumulation of at least ");
+                sb.append(df2.format(channelThreshold)).append(" grid cells (").append(df2.format(numChannelCells));
+                sb.append(" cells). A mapped stream is followed where a modelled channel cell lies within ");
+                sb.append(df.format(bufferDistance)).append(" of it.</p>\n");
+                sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+                sb.append("<tr><th></th><th>Length</th><th>Percent</th></tr>\n");
+                sb.append("<tr><td>Mapped Streams</td><td class=\"numberCell\">").append(df.format(mappedLength));
+                sb.append("</td><td></td></tr>\n");
+                sb.append("<tr><td>Outside of the DEM</td><td class=\"numberCell\">").append(df.format(outsideLength));
+                sb.append("</td><td></td></tr>\n");
+                sb.append("<tr><td>Followed by Modelled Channels</td><td class=\"numberCell\">").append(df.format(followedLength));
+                sb.append("</td><td class=\"numberCell\">").append(df.format(evaluatedLength > 0 ? 100.0 * followedLength / evaluatedLength : 0)).append("</td></tr>\n");
+                sb.append("<tr><td>Not Followed</td><td class=\"numberCell\">").append(df.format(failedLength));
+                sb.append("</td><td class=\"numberCell\">").append(df.format(evaluatedLength > 0 ? 100.0 * failedLength / evaluatedLength : 0)).append("</td></tr>\n");
+                sb.append("</table></p>\n");
+                sb.append("<p>The streams that are not followed form ").append(df2.format(numDisagreements));
+                sb.append(" separate disagreements. Percentages are of the mapped length within the DEM.</p>\n");
+            } else {
+                sb.append("<p>No mapped streams were specified.</p>\n");
+            }
+
+            if (numSites > 0) {
+                sb.append("<h2>Problem Sites</h2>\n");
+                appendSiteTable(sb, "Largest Elevation Changes", "Change", sortedChangeSites, df);
+                if (streamsFile != null) {
+                    appendSiteTable(sb, "Longest Stream Disagreements", "Length Not Followed", sortedStreamSites, df);
+                }
+            }
+            sb.append("</body>\n</html>\n");
+
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(report, false)))) {
+                out.print(sb.toString());
+            }
+
+            // write the problem sites as GeoJSON points
+            if (sitesFile != null) {
+                if (!sitesFile.toLowerCase().endsWith(".geojson") && !sitesFile.toLowerCase().endsWith(".json")) {
+                    sitesFile += ".geojson";
+                }
+                StringBuilder json = new StringBuilder();
+                json.append("{\n\"type\": \"FeatureCollection\",\n\"features\": [");
+                boolean first = true;
+                for (int k = 0; k < sortedChangeSites.size() + sortedStreamSites.size(); k++) {
+                    boolean isStream = k >= sortedChangeSites.size();
+                    int rank = isStream ? k - sortedChangeSites.size() : k;
+                    Site site = isStream ? sortedStreamSites.get(rank) : sortedChangeSites.get(rank);
+                    json.append(first ? "\n" : ",\n");
+                    first = false;
+                    json.append("{\"type\": \"Feature\", \"geometry\": {\"type\": \"Point\", \"coordinates\": [");
+                    json.append(site.x).append(", ").append(site.y).append("]}, \"properties\": {\"SITE_TYPE\": \"");
+                    json.append(isStream ? "stream disagreement" : "elevation change");
+                    json.append("\", \"RANK\": ").append(rank + 1);
+                    json.append(", \"VALUE\": ").append(site.value).append("}}");
+                }
+                json.append("\n]\n}\n");
+                try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(sitesFile, false)))) {
+                    out.print(json.toString());
+                }
+            }
+
+            returnData(report.getAbsolutePath());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    // returns true if there is a channel cell whose centre is within the
+    // buffer distance of the point (x, y), which lies in cell (row, col).
+    private static boolean isChannelNearby(WhiteboxRaster raster, boolean[] isChannel,
+            double x, double y, int row, int col, int searchRows, int searchCols, double bufferSqr) {
+        int rows = raster.getNumberRows();
+        int cols = raster.getNumberColumns();
+        for (int r = Math.max(0, row - searchRows); r <= Math.min(rows - 1, row + searchRows); r++) {
+            double dy = raster.getYCoordinateFromRow(r) - y;
+            for (int c = Math.max(0, col - searchCols); c <= Math.min(cols - 1, col + searchCols); c++) {
+                if (isChannel[r * cols + c]) {
+                    double dx = raster.getXCoordinateFromColumn(c) - x;
+                    if (dx * dx + dy * dy <= bufferSqr) {
+                        return true;
+                    }
+                }
+            }
+        }
+        return false;
+    }
+
+    // the site of a run of failing steps is the middle step of the run.
+    private static Site midpointSite(List<double[]> run, double runLength) {
+        double[] point = run.get(run.size() / 2);
+        return new Site(point[0], point[1], runLength, runLength);
+    }
+
+    // keeps the numSites sites with the highest scores.
+    private static void addSite(PriorityQueue<Site> sites, Site site, int numSites) {
+        if (numSites <= 0) {
+            return;
+        }
+        sites.add(site);
+        if (sites.size() > numSites) {
+            sites.poll();
+        }
+    }
+
+    private static double percent(long count, long total) {
+        return (total > 0) ? 100.0 * count / total : 0;
+    }
+
+    private static void appendSiteTable(StringBuilder sb, String caption, String valueName,
+            List<Site> sites, DecimalFormat df) {
+        sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+        sb.append("<caption>").append(caption).append("</caption>\n");
+        sb.append("<tr><th>Rank</th><th>X</th><th>Y</th><th>").append(valueName).append("</th></tr>\n");
+        if (sites.isEmpty()) {
+            sb.append("<tr><td colspan=\"4\">None</td></tr>\n");
+        }
+        for (int i = 0; i < sites.size(); i++) {
+            Site site = sites.get(i);
+            sb.append("<tr><td class=\"numberCell\">").append(i + 1);
+            sb.append("</td><td class=\"numberCell\">").append(df.format(site.x));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(site.y));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(site.value)).append("</td></tr>\n");
+        }
+        sb.append("</table></p>\n");
+    }
+
+    // A problem site. Sites are ordered by their score, which is the size of
+    // the problem, and the value is reported.
+    private static class Site implements Comparable<Site> {
+
+        double x;
+        double y;
+        double score;
+        double value;
+
+        Site(double x, double y, double score, double value) {
+            this.x = x;
+            this.y = y;
+            this.score = score;
+            this.value = value;
+        }
+
+        @Override
+        public int compareTo(Site other) {
+            return Double.compare(score, other.score);
+        }
+    }
+}
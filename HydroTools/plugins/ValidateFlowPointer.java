@@ -0,0 +1,550 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.structures.BooleanBitArray2D;
+
+/**
+ * This tool is used to check a D8 flow pointer grid for cycles, invalid
+ * pointer values and, optionally, pointers that flow uphill on a DEM, and can
+ * repair the pointer at the problem cells.
+ */
+public class ValidateFlowPointer implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "ValidateFlowPointer";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Validate Flow Pointer";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Detects cycles, invalid values and uphill flow in a D8 flow pointer grid and optionally repairs them.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"FlowPointers"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    private static final int[] dX = {1, 1, 1, 0, -1, -1, -1, 0};
+    private static final int[] dY = {-1, 0, 1, 1, 1, 0, -1, -1};
+    
+    // diagnostic values, which are summed where a cell has several problems
+    private static final int INVALID_VALUE = 1;
+    private static final int CYCLE = 2;
+    private static final int UPHILL = 4;
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String pointerHeader = null;
+        String outputHeader = null;
+        String demHeader = null;
+        String repairedHeader = null;
+        double tolerance = 0;
+        int row, col, i;
+        int progress;
+        int oldProgress;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                pointerHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2 && specified) {
+                demHeader = args[i];
+            } else if (i == 3 && specified) {
+                tolerance = Double.parseDouble(args[i]);
+            } else if (i == 4 && specified) {
+                repairedHeader = args[i];
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((pointerHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (repairedHeader != null && demHeader == null) {
+            showFeedback("A DEM must be specified to repair the flow pointer.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster pointer = new WhiteboxRaster(pointerHeader, "r");
+            int rows = pointer.getNumberRows();
+            int cols = pointer.getNumberColumns();
+            double noData = pointer.getNoDataValue();
+
+            WhiteboxRaster dem = null;
+            double demNoData = 0;
+            if (demHeader != null) {
+                dem = new WhiteboxRaster(demHeader, "r");
+                if (dem.getNumberRows() != rows || dem.getNumberColumns() != cols) {
+                    showFeedback("The DEM and flow pointer must have the same number of rows and columns.");
+                    return;
+                }
+                demNoData = dem.getNoDataValue();
+            }
+
+            BooleanBitArray2D inCycle = new BooleanBitArray2D(rows, cols);
+            long numCycles = findCycles(pointer, inCycle, "Finding cycles:");
+            if (numCycles < 0) {
+                return;
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    pointerHeader, WhiteboxRaster.DataType.INTEGER, noData);
+            output.setPreferredPalette("qual.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+            output.setZUnits("dimensionless");
+
+            WhiteboxRaster repaired = null;
+            double[] distances = new double[8];
+            if (repairedHeader != null) {
+                repaired = new WhiteboxRaster(repairedHeader, "rw",
+                        pointerHeader, WhiteboxRaster.DataType.FLOAT, noData);
+                repaired.setPreferredPalette("qual.pal");
+                repaired.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+                repaired.setZUnits("dimensionless");
+                for (i = 0; i < 8; i++) {
+                    distances[i] = Math.sqrt(dX[i] * dem.getCellSizeX() * dX[i] * dem.getCellSizeX()
+                            + dY[i] * dem.getCellSizeY() * dY[i] * dem.getCellSizeY());
+                }
+            }
+
+            long numInvalid = 0;
+            long numInCycles = 0;
+            long numUphill = 0;
+            long numProblemCells = 0;
+            long numRepaired = 0;
+            double[] outputData = new double[cols];
+            double[] repairedData = new double[cols];
+            double value, z, zN;
+            int dir, rowN, colN, problems;
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    value = pointer.getValue(row, col);
+                    outputData[col] = noData;
+                    repairedData[col] = value;
+                    if (value == noData) {
+                        continue;
+                    }
+                    problems = 0;
+                    dir = getDirection(value);
+                    if (dir == -2) {
+                        problems += INVALID_VALUE;
+                        numInvalid++;
+                    }
+                    if (inCycle.getValue(row, col)) {
+                        problems += CYCLE;
+                        numInCycles++;
+                    }
+                    if (dem != null && dir >= 0) {
+                        rowN = row + dY[dir];
+                        colN = col + dX[dir];
+                        z = dem.getValue(row, col);
+                        zN = dem.getValue(rowN, colN);
+                        if (z != demNoData && zN != demNoData && rowN >= 0 && rowN < rows
+                                && colN >= 0 && colN < cols && zN - z > tolerance) {
+                            problems += UPHILL;
+                            numUphill++;
+                        }
+                    }
+                    outputData[col] = problems;
+                    if (problems > 0) {
+                        numProblemCells++;
+                        if (repaired != null) {
+                            repairedData[col] = getSteepestDescent(dem, row, col, distances, noData);
+                            numRepaired++;
+                        }
+                    }
+                }
+                output.setRowValues(row, outputData);
+                if (repaired != null) {
+                    repaired.setRowValues(row, repairedData);
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress("Checking pointer values:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            pointer.close();
+            if (dem != null) {
+                dem.close();
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Values: 1 = invalid pointer value, 2 = cycle, "
+                    + "4 = uphill pointer, summed where several apply");
+            output.close();
+
+            StringBuilder sb = new StringBuilder();
+            sb.append("VALIDATE FLOW POINTER\n\n");
+            sb.append("Invalid pointer values:\t").append(numInvalid).append("\n");
+            sb.append("Cells on cycles:\t").append(numInCycles).append(" (")
+                    .append(numCycles).append(" cycles)\n");
+            if (dem != null) {
+                sb.append("Uphill pointers (tolerance ").append(tolerance).append("):\t")
+                        .append(numUphill).append("\n");
+            }
+            sb.append("Total problem cells:\t").append(numProblemCells).append("\n");
+
+            if (repaired != null) {
+                repaired.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                repaired.addMetadataEntry("Created on " + new Date());
+                repaired.addMetadataEntry("Repaired " + numRepaired + " cells of " 
+                        + pointer.getShortHeaderFile());
+                repaired.close();
+
+                // a repaired cell flows downhill, but may join an unrepaired
+                // path that rises within the tolerance, so check it again.
+                WhiteboxRaster check = new WhiteboxRaster(repairedHeader, "r");
+                long numRemaining = findCycles(check, 
+                        new BooleanBitArray2D(rows, cols), "Checking repaired pointer:");
+                check.close();
+                if (numRemaining < 0) {
+                    return;
+                }
+                sb.append("\nRepaired cells:\t").append(numRepaired).append("\n");
+                sb.append("Cycles remaining after repair:\t").append(numRemaining).append("\n");
+                returnData(repairedHeader);
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            returnData(sb.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Returns the index (0-7) of the neighbour that a D8 pointer value points
+     * to, -1 for a value of zero (no flow), or -2 for an invalid value.
+     */
+    private static int getDirection(double value) {
+        if (value == 0) {
+            return -1;
+        }
+        for (int i = 0; i < 8; i++) {
+            if (value == (1 << i)) {
+                return i;
+            }
+        }
+        return -2;
+    }
+
+    /**
+     * Returns the index of the neighbour that a cell flows to, or -1 if the
+     * flow path ends at the cell, i.e. it has no valid pointer, or it points
+     * off the grid or to a NoData cell.
+     */
+    private static int getFlowDirection(WhiteboxRaster pointer, int row, int col,
+            double noData) {
+        double value = pointer.getValue(row, col);
+        if (value == noData) {
+            return -1;
+        }
+        int dir = getDirection(value);
+        if (dir < 0) {
+            return -1;
+        }
+        int rowN = row + dY[dir];
+        int colN = col + dX[dir];
+        if (rowN < 0 || rowN >= pointer.getNumberRows() || colN < 0
+                || colN >= pointer.getNumberColumns()
+                || pointer.getValue(rowN, colN) == noData) {
+            return -1;
+        }
+        return dir;
+    }
+
+    /**
+     * Finds the cells of a pointer grid that lie on cycles, i.e. flow paths
+     * that return to themselves, and marks them in inCycle. Each flow path is
+     * traced iteratively, stamping the cells of the current path, and then
+     * retraced to mark its cells as resolved, so that each cell is visited a
+     * fixed number of times and only three bits are needed per grid cell.
+     *
+     * @return the number of cycles, or -1 if the operation was cancelled.
+     */
+    private long findCycles(WhiteboxRaster pointer, BooleanBitArray2D inCycle,
+            String progressLabel) {
+        int rows = pointer.getNumberRows();
+        int cols = pointer.getNumberColumns();
+        double noData = pointer.getNoDataValue();
+        BooleanBitArray2D onPath = new BooleanBitArray2D(rows, cols);
+        BooleanBitArray2D resolved = new BooleanBitArray2D(rows, cols);
+        long numCycles = 0;
+        int r, c, dir;
+        int progress;
+        int oldProgress = -1;
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                if (resolved.getValue(row, col)) {
+                    continue;
+                }
+                // trace the path until it ends, joins a resolved path, or
+                // returns to one of its own cells
+                r = row;
+                c = col;
+                while (!resolved.getValue(r, c)) {
+                    if (onPath.getValue(r, c)) {
+                        numCycles++;
+                        do {
+                            inCycle.setValue(r, c, true);
+                            dir = getFlowDirection(pointer, r, c, noData);
+                            r += dY[dir];
+                            c += dX[dir];
+                        } while (!inCycle.getValue(r, c));
+                        break;
+                    }
+                    onPath.setValue(r, c, true);
+                    dir = getFlowDirection(pointer, r, c, noData);
+                    if (dir < 0) {
+                        break;
+                    }
+                    r += dY[dir];
+                    c += dX[dir];
+                }
+                // retrace the path, marking its cells as resolved
+                r = row;
+                c = col;
+                while (onPath.getValue(r, c)) {
+                    onPath.setValue(r, c, false);
+                    resolved.setValue(r, c, true);
+                    dir = getFlowDirection(pointer, r, c, noData);
+                    if (dir < 0) {
+                        break;
+                    }
+                    r += dY[dir];
+                    c += dX[dir];
+                }
+            }
+            progress = (int) (100f * row / (rows - 1));
+            if (progress > oldProgress) {
+                updateProgress(progressLabel, progress);
+                oldProgress = progress;
+                if (cancelOp) {
+                    cancelOperation();
+                    return -1;
+                }
+            }
+        }
+        return numCycles;
+    }
+
+    /**
+     * Returns the D8 pointer value of the steepest downslope neighbour of a 
+     * DEM cell, 0 if it has no downslope neighbour, or noData if the cell is
+     * NoData in the DEM.
+     */
+    private static double getSteepestDescent(WhiteboxRaster dem, int row, int col,
+            double[] distances, double noData) {
+        double demNoData = dem.getNoDataValue();
+        double z = dem.getValue(row, col);
+        if (z == demNoData) {
+            return noData;
+        }
+        double maxSlope = 0;
+        double flowDir = 0;
+        for (int i = 0; i < 8; i++) {
+            double zN = dem.getValue(row + dY[i], col + dX[i]);
+            if (zN != demNoData && (z - zN) / distances[i] > maxSlope) {
+                maxSlope = (z - zN) / distances[i];
+                flowDir = 1 << i;
+            }
+        }
+        return flowDir;
+    }
+}
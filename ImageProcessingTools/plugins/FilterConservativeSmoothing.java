@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -176,7 +177,7 @@ public class FilterConservativeSmoothing implements WhiteboxPlugin {
         int numPixelsInFilter;
         boolean filterRounded = false;
         double[] filterShape;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
     
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -188,7 +189,7 @@ public class FilterConservativeSmoothing implements WhiteboxPlugin {
         filterSizeX = Integer.parseInt(args[2]);
         filterSizeY = Integer.parseInt(args[3]);
         filterRounded = Boolean.parseBoolean(args[4]);
-        reflectAtBorders = Boolean.parseBoolean(args[5]);
+        edgeMode = EdgeMode.fromString(args[5]);
         
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
@@ -198,7 +199,7 @@ public class FilterConservativeSmoothing implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -173,7 +174,7 @@ public class FilterEdgePreservingSmoothing implements WhiteboxPlugin {
         double[] weightsI;
         int midPoint;
         int numPixelsInFilter;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
         double sigmaD = 0;
         double sigmaI = 0;
         double recipRoot2PiTimesSigmaD;
@@ -197,7 +198,7 @@ public class FilterEdgePreservingSmoothing implements WhiteboxPlugin {
             } else if (i == 3) {
                 sigmaI = Double.parseDouble(args[i]);
             } else if (i == 4) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -218,7 +219,7 @@ public class FilterEdgePreservingSmoothing implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -169,7 +170,7 @@ public class FilterEmboss implements WhiteboxPlugin {
         int[] dY;
         double[] weights;
         int numPixelsInFilter;
-        boolean reflectAtBorders = true;
+        EdgeMode edgeMode = EdgeMode.REFLECT;
         double centreValue;
         String direction = "n";
     
@@ -185,6 +186,8 @@ public class FilterEmboss implements WhiteboxPlugin {
                 outputHeader = args[i];
             } else if (i == 2) {
                 direction = args[i].toLowerCase();
+            } else if (i == 3) {
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -196,7 +199,7 @@ public class FilterEmboss implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -208,7 +209,7 @@ public class FilterLocalContrastEnhancement implements WhiteboxPlugin {
         int numPixelsInFilter;
         boolean filterRounded = false;
         double[] filterShape;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
         double alpha;
         double globalMean;
         double predefinedStdDev;
@@ -231,7 +232,7 @@ public class FilterLocalContrastEnhancement implements WhiteboxPlugin {
         globalMean = Double.parseDouble(args[5]);
         predefinedStdDev = Double.parseDouble(args[6]);
         filterRounded = Boolean.parseBoolean(args[7]);
-        reflectAtBorders = Boolean.parseBoolean(args[8]);
+        edgeMode = EdgeMode.fromString(args[8]);
 
         // check to see that the inputHeader and outputHeader are not null.
         if (inputHeader.isEmpty() || outputHeader.isEmpty()) {
@@ -241,7 +242,7 @@ public class FilterLocalContrastEnhancement implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -175,7 +176,7 @@ public class FilterMean implements WhiteboxPlugin {
         int numPixelsInFilter;
         boolean filterRounded = false;
         double[] filterShape;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
     
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -194,7 +195,7 @@ public class FilterMean implements WhiteboxPlugin {
             } else if (i == 4) {
                 filterRounded = Boolean.parseBoolean(args[i]);
             } else if (i == 5) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -206,7 +207,7 @@ public class FilterMean implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -20,6 +20,7 @@ import java.util.ArrayList;
 import java.util.Collections;
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -176,7 +177,7 @@ public class FilterPercentile implements WhiteboxPlugin {
         int numPixelsInFilter;
         boolean filterRounded = false;
         double[] filterShape;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
         ArrayList<Double> data = new ArrayList<Double>();
     
         if (args.length <= 0) {
@@ -196,7 +197,7 @@ public class FilterPercentile implements WhiteboxPlugin {
             } else if (i == 4) {
                 filterRounded = Boolean.parseBoolean(args[i]);
             } else if (i == 5) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -208,7 +209,7 @@ public class FilterPercentile implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -0,0 +1,402 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedReader;
+import java.io.File;
+import java.io.FileReader;
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Date;
+import java.util.List;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
+
+/**
+ * This tool performs a spatial filter on a raster image using a kernel of weights that is read from a text file, allowing directional filters, custom smoothing kernels, and emboss effects that are not otherwise available.
+ */
+public class FilterUserKernel implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name containing no spaces.
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "FilterUserKernel";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "User-Defined Kernel Filter";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Performs a filter on an image using a kernel read from a text file.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "Filters" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+        
+        String inputHeader = null;
+        String outputHeader = null;
+        String kernelFile = null;
+        boolean normalize = false;
+        double clipPercentage = 0;
+        EdgeMode edgeMode = EdgeMode.NODATA;
+    
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                kernelFile = args[i];
+            } else if (i == 3) {
+                normalize = Boolean.parseBoolean(args[i]);
+            } else if (i == 4) {
+                if (!args[i].toLowerCase().contains("not specified")) {
+                    clipPercentage = Double.parseDouble(args[i]);
+                }
+            } else if (i == 5) {
+                edgeMode = EdgeMode.fromString(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null) || (kernelFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (clipPercentage < 0 || clipPercentage >= 50) {
+            showFeedback("The clip percentage must be at least 0 and less than 50.");
+            return;
+        }
+        
+        double[][] kernel;
+        try {
+            kernel = readKernel(kernelFile);
+            if (normalize) {
+                normalizeKernel(kernel);
+            }
+        } catch (IOException | IllegalArgumentException e) {
+            showFeedback(e.getMessage());
+            return;
+        }
+
+        try {
+            final WhiteboxRaster inputFile = WhiteboxRaster.openMemoryMapped(inputHeader);
+            inputFile.setEdgeMode(edgeMode);
+
+            final int rows = inputFile.getNumberRows();
+            final int cols = inputFile.getNumberColumns();
+            final double noData = inputFile.getNoDataValue();
+
+            final WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            outputFile.setPreferredPalette(inputFile.getPreferredPalette());
+            
+            // the kernel is applied as it is laid out in the file, with the 
+            // first row of the file to the north of the centre cell.
+            int kernelRows = kernel.length;
+            int kernelCols = kernel[0].length;
+            final int numPixelsInFilter = kernelRows * kernelCols;
+            final int[] dX = new int[numPixelsInFilter];
+            final int[] dY = new int[numPixelsInFilter];
+            final double[] weights = new double[numPixelsInFilter];
+            int a = 0;
+            for (int row = 0; row < kernelRows; row++) {
+                for (int col = 0; col < kernelCols; col++) {
+                    dX[a] = col - kernelCols / 2;
+                    dY[a] = row - kernelRows / 2;
+                    weights[a] = kernel[row][col];
+                    a++;
+                }
+            }
+            
+            // the rows are filtered in parallel if the input can be read by
+            // several threads at once, and are written in order
+            boolean completed = Parallel.ForRows(rows, cols, inputFile.isMemoryMapped(), new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        double z = inputFile.getValue(row, col);
+                        if (z != noData) {
+                            double sum = 0;
+                            for (int a = 0; a < numPixelsInFilter; a++) {
+                                double val = inputFile.getValue(row + dY[a], col + dX[a]);
+                                if (val == noData) {
+                                    // replace it with z
+                                    val = z;
+                                }
+                                sum += weights[a] * val;
+                            }
+                            data[col] = sum;
+                        } else {
+                            data[col] = noData;
+                        }
+                    }
+                }
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        outputFile.setValue(row, col, data[col]);
+                    }
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
+                }
+            });
+
+            if (!completed) {
+                cancelOperation();
+                return;
+            }
+
+            outputFile.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            outputFile.addMetadataEntry("Created on " + new Date());
+            outputFile.addMetadataEntry("Kernel file: " + new File(kernelFile).getName());
+            
+            inputFile.close();
+            outputFile.close();
+            
+            if (clipPercentage > 0) {
+                // clip the tails of the output distribution for display. The 
+                // data themselves are unaltered.
+                WhiteboxRaster clippedFile = new WhiteboxRaster(outputHeader, "rw");
+                double[] displayRange = clippedFile.getPercentiles(clipPercentage, 100 - clipPercentage);
+                clippedFile.setDisplayMinimum(displayRange[0]);
+                clippedFile.setDisplayMaximum(displayRange[1]);
+                clippedFile.close();
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    /**
+     * Reads a kernel from a text file. Each line of the file is one row of 
+     * the kernel, with the weights separated by spaces, tabs, or commas. 
+     * Blank lines are ignored. The kernel must be rectangular and have an 
+     * odd number of rows and columns so that it has a centre cell.
+     *
+     * @param fileName The name of the kernel file.
+     * @return The kernel weights, indexed by row and column.
+     * @throws IOException if the file cannot be read.
+     * @throws IllegalArgumentException if the file does not hold a valid kernel.
+     */
+    static double[][] readKernel(String fileName) throws IOException {
+        List<double[]> kernelRows = new ArrayList<>();
+        try (BufferedReader br = new BufferedReader(new FileReader(fileName))) {
+            String line;
+            int lineNum = 0;
+            while ((line = br.readLine()) != null) {
+                lineNum++;
+                line = line.trim();
+                if (line.isEmpty()) {
+                    continue;
+                }
+                String[] str = line.split("[\\s,]+");
+                double[] weights = new double[str.length];
+                for (int i = 0; i < str.length; i++) {
+                    try {
+                        weights[i] = Double.parseDouble(str[i]);
+                    } catch (NumberFormatException e) {
+                        throw new IllegalArgumentException("The kernel file contains a value that is "
+                                + "not a number ('" + str[i] + "' on line " + lineNum + ").");
+                    }
+                }
+                if (!kernelRows.isEmpty() && weights.length != kernelRows.get(0).length) {
+                    throw new IllegalArgumentException("Each row of the kernel file must contain the "
+                            + "same number of values (line " + lineNum + " has " + weights.length
+                            + " values but the first row has " + kernelRows.get(0).length + ").");
+                }
+                kernelRows.add(weights);
+            }
+        }
+        if (kernelRows.isEmpty()) {
+            throw new IllegalArgumentException("The kernel file does not contain any values.");
+        }
+        int numRows = kernelRows.size();
+        int numCols = kernelRows.get(0).length;
+        if (numRows % 2 == 0 || numCols % 2 == 0) {
+            throw new IllegalArgumentException("The kernel must have an odd number of rows and columns "
+                    + "(the kernel file has " + numRows + " rows and " + numCols + " columns).");
+        }
+        return kernelRows.toArray(new double[numRows][]);
+    }
+    
+    /**
+     * Scales the weights of a kernel so that they sum to one.
+     *
+     * @param kernel The kernel weights, which are modified in place.
+     * @throws IllegalArgumentException if the weights sum to zero.
+     */
+    static void normalizeKernel(double[][] kernel) {
+        double sum = 0;
+        for (double[] kernelRow : kernel) {
+            for (double weight : kernelRow) {
+                sum += weight;
+            }
+        }
+        if (Math.abs(sum) < 1e-10) {
+            throw new IllegalArgumentException("The kernel cannot be normalized because its weights "
+                    + "sum to zero, as is the case for edge-detection and emboss kernels.");
+        }
+        for (double[] kernelRow : kernel) {
+            for (int i = 0; i < kernelRow.length; i++) {
+                kernelRow[i] /= sum;
+            }
+        }
+    }
+}
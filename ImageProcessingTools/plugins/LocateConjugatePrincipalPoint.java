@@ -29,6 +29,7 @@ import whitebox.structures.XYPoint;
 import whitebox.stats.PolynomialLeastSquares2DFitting;
 import whitebox.structures.KdTree;
 import whitebox.structures.RowPriorityGridCell;
+import whitebox.utilities.StringUtilities;
 
 /**
  * Can't find
@@ -66,7 +67,6 @@ public class LocateConjugatePrincipalPoint {
         maxNeighbourhoodSize = 500;
         epsilon = 1.2;
         int polyOrder = 2;
-        StringBuilder str;
 
         KdTree<Double> controlPointTree = new KdTree.SqrEuclid<>(2, new Integer(2000));
 
@@ -417,35 +417,7 @@ public class LocateConjugatePrincipalPoint {
             output2.write();
 
             System.out.println("\nOperation complete!");
-            long endTime = System.nanoTime();
-
-            double duration = (endTime - startTime);
-
-            int secs = (int) (duration / 1000000000);
-//            int days = secs / 86400;
-//            secs = secs - 86400 * days;
-            int hours = secs / 3600;
-            secs = secs - 3600 * hours;
-            int minutes = secs / 60;
-            secs = secs - minutes * 60;
-            int seconds = secs;
-
-            str = new StringBuilder();
-            str.append("Duration: ");
-//            if (days > 0) {
-//                str.append(days).append(" days, ");
-//            }
-            if (hours > 0) {
-                str.append(hours).append(" hours, ");
-            }
-            if (minutes > 0) {
-                str.append(minutes).append(" minutes, ");
-            }
-            if (seconds > 0) {
-                str.append(seconds).append(" seconds, ");
-            }
-
-            System.out.println(str.toString());
+            System.out.println("Duration: " + StringUtilities.formatElapsedTime(System.nanoTime() - startTime));
 
 
         } catch (Exception e) {
@@ -16,10 +16,8 @@
  */
 package plugins;
 
-import java.io.BufferedWriter;
-import java.io.FileWriter;
-import java.io.PrintWriter;
 import java.util.Date;
+import whitebox.algorithms.RasterResampler;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterBase;
 import whitebox.geospatialfiles.WhiteboxRasterInfo;
@@ -27,7 +25,7 @@ import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
 /**
- * This tool will create an image mosaic from one or more input image files using one of three resampling methods including, nearest neighbour, bilinear interpolation, and cubic convolution.
+ * This tool will create an image mosaic from one or more input image files using one of four resampling methods including, nearest neighbour, bilinear interpolation, cubic convolution, and Lanczos.
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
 public class Mosaic implements WhiteboxPlugin {
@@ -166,24 +164,16 @@ public class Mosaic implements WhiteboxPlugin {
         WhiteboxRasterInfo imageInfo = null;
         int nCols = 0;
         int nRows = 0;
-        double imageNoData = -32768;
         double outputNoData = -32768;
         int numImages;
         double x, y, z;
         int progress = 0;
         int col, row;
-        int a, i;
+        int a;
         String inputFilesString = null;
         String[] imageFiles;
         String resampleMethod = "nearest neighbour";
         double north, south, east, west;
-        double gridResX, gridResY;
-        int currentFile = -1;
-        
-        String str1 = null;
-        FileWriter fw = null;
-        BufferedWriter bw = null;
-        PrintWriter out = null;
 
 
         if (args.length <= 0) {
@@ -195,12 +185,20 @@ public class Mosaic implements WhiteboxPlugin {
         inputFilesString = args[0];
         destHeader = args[1];
         resampleMethod = args[2].toLowerCase().trim();
-        if (!resampleMethod.equals("nearest neighbour")
-                && !resampleMethod.equals("bilinear")
-                && !resampleMethod.equals("cubic convolution")) {
+        RasterResampler.Method method = RasterResampler.getMethodFromString(resampleMethod);
+        if (method == null) {
             showFeedback("Resample method not recognized");
             return;
         }
+        RasterResampler.NoDataPolicy noDataPolicy = RasterResampler.NoDataPolicy.RENORMALIZE;
+        if (args.length > 3 && !args[3].trim().isEmpty() 
+                && !args[3].toLowerCase().contains("not specified")) {
+            noDataPolicy = RasterResampler.getNoDataPolicyFromString(args[3]);
+            if (noDataPolicy == null) {
+                showFeedback("NoData policy not recognized");
+                return;
+            }
+        }
 
         imageFiles = inputFilesString.split(";");
 
@@ -281,162 +279,53 @@ public class Mosaic implements WhiteboxPlugin {
                     WhiteboxRasterBase.DataType.FLOAT, outputNoData, outputNoData);
             
             
-            int nColsLessOne = nCols - 1;
-            int nRowsLessOne = nRows - 1;
-            
-            // these may not be exactly the same as above now.
-            gridResX = destination.getCellSizeX();
-            gridResY = destination.getCellSizeY();
-            double northernEdge = north - gridResY;
-            double westernEdge = west + gridResX;
-            if (north < south) {
-                northernEdge = north + gridResY;
-            }
-
-            if (east < west) {
-                westernEdge = west - gridResX;
-            }
-
-            
-            double yRange = north - south - gridResY;
-            double xRange = east - west - gridResX;
-            int sourceCol, sourceRow;
-            
-
-            if (resampleMethod.equals("nearest neighbour")) {
-                for (a = 0; a < numImages; a++) {
-                    image = new WhiteboxRaster(imageFiles[a], "r");
-                    int startRow = destination.getRowFromYCoordinate(image.getNorth()) - 1;
-                    int endRow = destination.getRowFromYCoordinate(image.getSouth()) + 1;
-                    int startCol = destination.getColumnFromXCoordinate(image.getWest()) - 1;
-                    int endCol = destination.getColumnFromXCoordinate(image.getEast()) + 1;
-                    if (startRow < 0) { startRow = 0; }
-                    if (startCol < 0) { startCol = 0; }
-                    if (endRow > nRows - 1) { endRow = nRows - 1; }
-                    if (endCol > nCols - 1) { endCol = nCols - 1; }
-                    for (row = startRow; row <= endRow; row++) {
-                        y = northernEdge - (yRange * row) / nRowsLessOne;
-                        for (col = startCol; col <= endCol; col++) {
-                            x = westernEdge + (xRange * col) / nColsLessOne;
-                            if (isBetween(y, imageData[a][0], imageData[a][1])
-                                    && isBetween(x, imageData[a][2], imageData[a][3])) {
-                                 //what are the column and row of the image?
-                                sourceRow = (int) Math.round((imageData[a][0] - y) / imageData[a][10] * (imageData[a][4] - 0.5));
-                                sourceCol = (int) Math.round((x - imageData[a][3]) / imageData[a][9] * (imageData[a][5] - 0.5));
-                                z = image.getValue(sourceRow, sourceCol);
+            RasterResampler resampler;
+            for (a = 0; a < numImages; a++) {
+                image = new WhiteboxRaster(imageFiles[a], "r");
+                resampler = new RasterResampler(image, method, noDataPolicy);
+                // the range of destination cells overlapping the image
+                int r1 = destination.getRowFromYCoordinate(imageData[a][0]);
+                int r2 = destination.getRowFromYCoordinate(imageData[a][1]);
+                int c1 = destination.getColumnFromXCoordinate(imageData[a][3]);
+                int c2 = destination.getColumnFromXCoordinate(imageData[a][2]);
+                int startRow = Math.min(r1, r2) - 1;
+                int endRow = Math.max(r1, r2) + 1;
+                int startCol = Math.min(c1, c2) - 1;
+                int endCol = Math.max(c1, c2) + 1;
+                if (startRow < 0) { startRow = 0; }
+                if (startCol < 0) { startCol = 0; }
+                if (endRow > nRows - 1) { endRow = nRows - 1; }
+                if (endCol > nCols - 1) { endCol = nCols - 1; }
+                for (row = startRow; row <= endRow; row++) {
+                    y = destination.getYCoordinateFromRow(row);
+                    for (col = startCol; col <= endCol; col++) {
+                        x = destination.getXCoordinateFromColumn(col);
+                        if (isBetween(y, imageData[a][0], imageData[a][1])
+                                && isBetween(x, imageData[a][2], imageData[a][3])) {
+                            // the first image in the list with a valid value is used
+                            if (destination.getValue(row, col) == outputNoData) {
+                                z = resampler.getValue(x, y);
                                 if (z != imageData[a][8]) {
                                     destination.setValue(row, col, z);
-                                } else {
-                                    destination.setValue(row, col, outputNoData);
                                 }
                             }
                         }
                     }
-                    image.close();
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * (a + 1) / numImages);
-                    updateProgress(progress);
                 }
-                
-            } else {
-                if (destination.getDataType() != WhiteboxRaster.DataType.DOUBLE && 
-                        destination.getDataType() != WhiteboxRaster.DataType.FLOAT) {
-                    showFeedback("The destination image is not of an appropriate data"
-                            + " type (i.e. double or float) to perform this operation.");
+                image.close();
+                if (cancelOp) {
+                    cancelOperation();
                     return;
                 }
-                double dX, dY;
-                double srcRow, srcCol; 
-                double originRow, originCol;
-                double rowN, colN;
-                double sumOfDist;
-                double[] shiftX;
-                double[] shiftY;
-                int numNeighbours = 0;
-                double[][] neighbour;
-                if (resampleMethod.equals("cubic convolution")) {
-                    shiftX = new double[]{ -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2 };
-                    shiftY = new double[]{ -1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2 };
-                    numNeighbours = 16;
-                    neighbour = new double[16][2];
-                } else { // resampleMethod is "bilinear"
-                    shiftX = new double[]{ 0, 1, 0, 1 };
-                    shiftY = new double[]{ 0, 0, 1, 1 };
-                    numNeighbours = 4;
-                    neighbour = new double[4][2];
-                }
-                
-                for (a = 0; a < numImages; a++) {
-                    image = new WhiteboxRaster(imageFiles[a], "r");
-                    int startRow = destination.getRowFromYCoordinate(image.getNorth()) - 1;
-                    int endRow = destination.getRowFromYCoordinate(image.getSouth()) + 1;
-                    int startCol = destination.getColumnFromXCoordinate(image.getWest()) - 1;
-                    int endCol = destination.getColumnFromXCoordinate(image.getEast()) + 1;
-                    if (startRow < 0) { startRow = 0; }
-                    if (startCol < 0) { startCol = 0; }
-                    if (endRow > nRows - 1) { endRow = nRows - 1; }
-                    if (endCol > nCols - 1) { endCol = nCols - 1; }
-                    for (row = startRow; row <= endRow; row++) {
-                        y = northernEdge - (yRange * row) / nRowsLessOne;
-                        for (col = startCol; col <= endCol; col++) {
-                            x = westernEdge + (xRange * col) / nColsLessOne;
-                            // see if this x, y location falls within the input image
-                            if (isBetween(y, imageData[a][0], imageData[a][1])
-                                    && isBetween(x, imageData[a][2], imageData[a][3])) {
-                                imageNoData = imageData[a][8];
-                                // what are the exact col and row of the image?
-                                srcRow = (imageData[a][0] - y) / imageData[a][10] * (imageData[a][4] - 0.5);
-                                srcCol = (x - imageData[a][3]) / imageData[a][9] * (imageData[a][5] - 0.5);
-
-                                originRow = Math.floor(srcRow);
-                                originCol = Math.floor(srcCol);
-
-                                sumOfDist = 0;
-                                for (i = 0; i < numNeighbours; i++) {
-                                    rowN = originRow + shiftY[i];
-                                    colN = originCol + shiftX[i];
-                                    neighbour[i][0] = image.getValue((int) rowN, (int) colN);
-                                    dY = rowN - srcRow;
-                                    dX = colN - srcCol;
-
-                                    if ((dX + dY) != 0 && neighbour[i][0] != imageNoData) {
-                                        neighbour[i][1] = 1 / (dX * dX + dY * dY);
-                                        sumOfDist += neighbour[i][1];
-                                    } else if (neighbour[i][0] == imageNoData) {
-                                        neighbour[i][1] = 0;
-                                    } else {
-                                        destination.setValue(row, col, neighbour[0][3]);
-                                        break;
-                                    }
-
-                                }
-
-                                if (sumOfDist > 0) {
-                                    z = 0;
-                                    for (i = 0; i < numNeighbours; i++) {
-                                        z += (neighbour[i][0] * neighbour[i][1]) / sumOfDist;
-                                    }
-                                    destination.setValue(row, col, z);
-                                    //break; 
-                                } else {
-                                    destination.setValue(row, col, outputNoData);
-                                }
-                            }
-                        }
-                    }
-                    image.close();
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * (a + 1) / numImages);
-                    updateProgress(progress);
-                }
+                progress = (int) (100f * (a + 1) / numImages);
+                updateProgress(progress);
             }
 
+            destination.addMetadataEntry("Resampling method: " + resampleMethod);
+            if (method != RasterResampler.Method.NEAREST_NEIGHBOUR) {
+                destination.addMetadataEntry("NoData policy: " 
+                        + noDataPolicy.toString().toLowerCase());
+            }
             destination.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             destination.addMetadataEntry("Created on " + new Date());
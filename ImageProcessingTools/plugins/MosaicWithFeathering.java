@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import java.io.File;
+import whitebox.algorithms.RasterResampler;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterBase;
 import whitebox.geospatialfiles.WhiteboxRasterInfo;
@@ -192,7 +193,7 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
         amIActive = true;
 
         int progress;
-        int row, col, i;
+        int row, col;
         int baseCol, baseRow, appendCol, appendRow;
         double x, y, z, zN, zBase, zAppend;
         double w1, w2, dist1, dist2, sumDist;
@@ -211,9 +212,8 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
         String inputHeader = args[1];
         String outputHeader = args[2];
         String resampleMethod = args[3].toLowerCase().trim();
-        if (!resampleMethod.equals("nearest neighbour")
-                && !resampleMethod.equals("bilinear")
-                && !resampleMethod.contains("cubic")) {
+        RasterResampler.Method method = RasterResampler.getMethodFromString(resampleMethod);
+        if (method == null) {
             showFeedback("Resample method not recognized");
             return;
         }
@@ -225,6 +225,15 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
         double power = Double.parseDouble(args[5]);
         if (power > 15.0) { power = 15.0; }
         if (power < 1.0) { power = 1.0; }
+        RasterResampler.NoDataPolicy noDataPolicy = RasterResampler.NoDataPolicy.RENORMALIZE;
+        if (args.length > 6 && !args[6].trim().isEmpty() 
+                && !args[6].toLowerCase().contains("not specified")) {
+            noDataPolicy = RasterResampler.getNoDataPolicyFromString(args[6]);
+            if (noDataPolicy == null) {
+                showFeedback("NoData policy not recognized");
+                return;
+            }
+        }
 
         try {
             
@@ -259,15 +268,11 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
             double baseSouth = baseRaster.getSouth();
             double baseEast = baseRaster.getEast();
             double baseWest = baseRaster.getWest();
-            double baseNSRange = baseNorth - baseSouth;
-            double baseEWRange = baseEast - baseWest;
 
             double appendNorth = appendRaster.getNorth();
             double appendSouth = appendRaster.getSouth();
             double appendEast = appendRaster.getEast();
             double appendWest = appendRaster.getWest();
-            double appendNSRange = appendNorth - appendSouth;
-            double appendEWRange = appendEast - appendWest;
             
             double north, south, east, west;
 
@@ -410,7 +415,7 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
 
             calculateDistance(distToEdgeAppend);
             
-            if (resampleMethod.contains("nearest")) {
+            if (method == RasterResampler.Method.NEAREST_NEIGHBOUR) {
                 for (row = 0; row < nRows; row++) {
                     for (col = 0; col < nCols; col++) {
                         x = destination.getXCoordinateFromColumn(col);
@@ -475,27 +480,10 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
                             + " type (i.e. double or float) to perform this operation.");
                     return;
                 }
-                double dX, dY;
-                double srcRow, srcCol;
-                double originRow, originCol;
-                double rowN, colN;
-                double sumOfDist;
-                double[] shiftX;
-                double[] shiftY;
-                int numNeighbours = 0;
-                double[][] neighbour;
-                if (resampleMethod.contains("cubic")) {
-                    shiftX = new double[]{-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2};
-                    shiftY = new double[]{-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2};
-                    numNeighbours = 16;
-                    neighbour = new double[numNeighbours][2];
-                } else { // resampleMethod is "bilinear"
-                    shiftX = new double[]{0, 1, 0, 1};
-                    shiftY = new double[]{0, 0, 1, 1};
-                    numNeighbours = 4;
-                    neighbour = new double[numNeighbours][2];
-                }
-
+                RasterResampler baseResampler = new RasterResampler(baseRaster, 
+                        method, noDataPolicy);
+                RasterResampler appendResampler = new RasterResampler(appendRaster, 
+                        method, noDataPolicy);
 
                 for (row = 0; row < nRows; row++) {
                     for (col = 0; col < nCols; col++) {
@@ -504,81 +492,11 @@ public class MosaicWithFeathering implements WhiteboxPlugin {
 
                         baseCol = baseRaster.getColumnFromXCoordinate(x);
                         baseRow = baseRaster.getRowFromYCoordinate(y);
-
-                        // what are the exact col and row of the image?
-                        srcRow = (baseNorth - y) / baseNSRange * (baseRows - 0.5);
-                        srcCol = (x - baseWest) / baseEWRange * (baseCols - 0.5);
-
-                        originRow = Math.floor(srcRow);
-                        originCol = Math.floor(srcCol);
-
-                        sumOfDist = 0;
-                        for (i = 0; i < numNeighbours; i++) {
-                            rowN = originRow + shiftY[i];
-                            colN = originCol + shiftX[i];
-                            neighbour[i][0] = baseRaster.getValue((int) rowN, (int) colN);
-                            dY = rowN - srcRow;
-                            dX = colN - srcCol;
-
-                            if ((dX + dY) != 0 && neighbour[i][0] != baseNoData) {
-                                neighbour[i][1] = 1 / (dX * dX + dY * dY);
-                                sumOfDist += neighbour[i][1];
-                            } else if (neighbour[i][0] == baseNoData) {
-                                neighbour[i][1] = 0;
-                            } else { // dist is zero
-                                neighbour[i][1] = 99999999;
-                                sumOfDist += neighbour[i][1];
-                            }
-                        }
-
-                        if (sumOfDist > 0) {
-                            z = 0;
-                            for (i = 0; i < numNeighbours; i++) {
-                                z += neighbour[i][0] * neighbour[i][1] / sumOfDist;
-                            }
-                            zBase = z;
-                        } else {
-                            zBase = baseNoData;
-                        }
-
+                        zBase = baseResampler.getValue(x, y);
 
                         appendCol = appendRaster.getColumnFromXCoordinate(x);
                         appendRow = appendRaster.getRowFromYCoordinate(y);
-
-                        srcRow = (appendNorth - y) / appendNSRange * (appendRows - 0.5);
-                        srcCol = (x - appendWest) / appendEWRange * (appendCols - 0.5);
-
-                        originRow = Math.floor(srcRow);
-                        originCol = Math.floor(srcCol);
-
-                        sumOfDist = 0;
-                        for (i = 0; i < numNeighbours; i++) {
-                            rowN = originRow + shiftY[i];
-                            colN = originCol + shiftX[i];
-                            neighbour[i][0] = appendRaster.getValue((int) rowN, (int) colN);
-                            dY = rowN - srcRow;
-                            dX = colN - srcCol;
-
-                            if ((dX + dY) != 0 && neighbour[i][0] != appendNoData) {
-                                neighbour[i][1] = 1 / (dX * dX + dY * dY);
-                                sumOfDist += neighbour[i][1];
-                            } else if (neighbour[i][0] == appendNoData) {
-                                neighbour[i][1] = 0;
-                            } else { // dist is zero
-                                neighbour[i][1] = 99999999;
-                                sumOfDist += neighbour[i][1];
-                            }
-                        }
-
-                        if (sumOfDist > 0) {
-                            z = 0;
-                            for (i = 0; i < numNeighbours; i++) {
-                                z += (neighbour[i][0] * neighbour[i][1]) / sumOfDist;
-                            }
-                            zAppend = z;
-                        } else {
-                            zAppend = appendNoData;
-                        }
+                        zAppend = appendResampler.getValue(x, y);
 
                         if (zBase != baseNoData && zAppend == appendNoData) {
                             destination.setValue(row, col, zBase);
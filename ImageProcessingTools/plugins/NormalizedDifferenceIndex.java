@@ -0,0 +1,302 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates a normalized difference index, (A - B) / (A + B), from two band images, e.g. the NDVI,
+ * NDWI, NDSI or NBR, optionally with the soil-adjustment correction factor of the SAVI.
+ */
+public class NormalizedDifferenceIndex implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name containing no spaces.
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "NormalizedDifferenceIndex";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Normalized Difference Index";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates a normalized difference index (e.g. NDVI, NDWI, NDSI, NBR or SAVI) from two bands.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "ImageTransformations", "VegetationIndices" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String bandAHeader = null;
+        String bandBHeader = null;
+        String outputHeader = null;
+        String index = "normalized difference";
+        double correction = 0;
+        double clipPercent = 0;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                bandAHeader = args[i];
+            } else if (i == 1) {
+                bandBHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    index = args[i].trim();
+                }
+            } else if (i == 4) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    correction = Double.parseDouble(args[i]);
+                }
+            } else if (i == 5) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    clipPercent = Double.parseDouble(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((bandAHeader == null) || (bandBHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (correction < 0) {
+            showFeedback("The correction factor must not be negative.");
+            return;
+        }
+        if (clipPercent < 0 || clipPercent >= 50) {
+            showFeedback("The clip percentage must be between 0 and 50.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster bandA = new WhiteboxRaster(bandAHeader, "r");
+            int rows = bandA.getNumberRows();
+            int cols = bandA.getNumberColumns();
+            double noDataA = bandA.getNoDataValue();
+
+            WhiteboxRaster bandB = new WhiteboxRaster(bandBHeader, "r");
+            double noDataB = bandB.getNoDataValue();
+
+            if (rows != bandB.getNumberRows() || cols != bandB.getNumberColumns()) {
+                showFeedback("The two input images must have the same number of rows and columns.");
+                return;
+            }
+
+            double noData = -32768;
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    bandAHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("grey.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            double[] data = new double[cols];
+            for (int row = 0; row < rows; row++) {
+                double[] a = bandA.getRowValues(row);
+                double[] b = bandB.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    double denominator = a[col] + b[col] + correction;
+                    if (a[col] != noDataA && b[col] != noDataB && denominator != 0) {
+                        data[col] = (float) ((a[col] - b[col]) / denominator * (1 + correction));
+                    } else {
+                        data[col] = noData;
+                    }
+                }
+                output.setRowValues(row, data);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress("Calculating index:", (int) (100f * row / rows));
+            }
+
+            // clip the tails of the distribution, at percentiles found from
+            // streaming histograms of the output
+            if (clipPercent > 0) {
+                double[] clipValues = output.getPercentiles(clipPercent, 100 - clipPercent);
+                if (!Double.isNaN(clipValues[0])) {
+                    for (int row = 0; row < rows; row++) {
+                        data = output.getRowValues(row);
+                        for (int col = 0; col < cols; col++) {
+                            if (data[col] != noData) {
+                                data[col] = Math.min(Math.max(data[col], clipValues[0]), clipValues[1]);
+                            }
+                        }
+                        output.setRowValues(row, data);
+                        if (cancelOp) {
+                            cancelOperation();
+                            return;
+                        }
+                        updateProgress("Clipping tails:", (int) (100f * row / rows));
+                    }
+                }
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Index: " + index);
+            output.addMetadataEntry("Band A: " + bandA.getShortHeaderFile());
+            output.addMetadataEntry("Band B: " + bandB.getShortHeaderFile());
+            if (correction != 0) {
+                output.addMetadataEntry("Soil-adjustment correction factor (L): " + correction);
+            }
+            if (clipPercent > 0) {
+                output.addMetadataEntry("Distribution tails clipped by " + clipPercent + " percent");
+            }
+
+            bandA.close();
+            bandB.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -164,7 +164,7 @@ public class PercentageContrastStretch implements WhiteboxPlugin {
         double z;
         double noData;
         int progress;
-        int i, bin;
+        int i;
         int numImages = 0;
         double minVal, maxVal;
         int numBins = 1024;
@@ -200,105 +200,52 @@ public class PercentageContrastStretch implements WhiteboxPlugin {
                 noData = image.getNoDataValue();
                 double[] data = null;
                 
-                // first create the histogram
-                double inputImageMin = image.getMinimumValue();
-                double inputImageMax = image.getMaximumValue();
-                int inputImageBins = (int)(inputImageMax - inputImageMin + 1);
-                double inputImageRange = inputImageMax - inputImageMin;
-                double[] histo = new double[inputImageBins];
-                
-                for (row = 0; row < nRows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < nCols; col++) {
-                        if (data[col] != noData) {
-                            bin = (int) (data[col] - inputImageMin);
-                            if (bin < 0) {
-                                bin = 0;
-                            }
-                            if (bin > (inputImageBins - 1)) {
-                                bin = (inputImageBins - 1);
-                            }
-                            histo[bin]++;
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (nRows - 1));
-                    updateProgress("Calculating clip values:", progress);
-                }
-
-                // convert the histogram into a cumulative prob.
-                for (int a = 1; a < inputImageBins; a++) {
-                    histo[a] = histo[a] + histo[a - 1];
-                }
-                // convert the histogram into a cumulative prob.
-                for (int a = 0; a < inputImageBins; a++) {
-                    histo[a] = histo[a] / histo[inputImageBins - 1] * 100;
-                }
-                
-                // find the upper and lower tails.
-                boolean lowerTailFound = false;
-                boolean upperTailFound = false;
-                minVal = -1;
-                maxVal = -1;
+                // the clip values are percentiles of the image's data
                 if (whichTailsToClip.contains("both")) {
-                    for (int a = 1; a < inputImageBins; a++) {
-                        if (histo[a] >= clipPercentage && !lowerTailFound) {
-                            minVal = (a - 1 + inputImageMin) + (clipPercentage - histo[a - 1]) / (histo[a] - histo[a - 1]);
-                            lowerTailFound = true;
-                        }
-
-                        if (histo[a] >= (100 - clipPercentage) && !upperTailFound) {
-                            maxVal = (a - 1 + inputImageMin) + ((100 - clipPercentage) - histo[a - 1]) / (histo[a] - histo[a - 1]);
-                            upperTailFound = true;
-                            break;
-                        }
-                    }
+                    double[] clipValues = image.getPercentiles(clipPercentage, 100 - clipPercentage);
+                    minVal = clipValues[0];
+                    maxVal = clipValues[1];
                 } else if (whichTailsToClip.contains("lower")) {
-                    for (int a = 1; a < inputImageBins; a++) {
-                        if (histo[a] >= clipPercentage && !lowerTailFound) {
-                            minVal = (a - 1 + inputImageMin) + (clipPercentage - histo[a - 1]) / (histo[a] - histo[a - 1]);
-                            lowerTailFound = true;
-                            break;
-                        }
-                    }
-                    maxVal = inputImageMax;
+                    minVal = image.getPercentile(clipPercentage);
+                    maxVal = image.getMaximumValue();
                 } else {
-                    for (int a = 1; a < inputImageBins; a++) {
-                        if (histo[a] >= (100 - clipPercentage) && !upperTailFound) {
-                            maxVal = (a - 1 + inputImageMin) + ((100 - clipPercentage) - histo[a - 1]) / (histo[a] - histo[a - 1]);
-                            upperTailFound = true;
-                            break;
-                        }
-                    }
-                    minVal = inputImageMin;
+                    minVal = image.getMinimumValue();
+                    maxVal = image.getPercentile(100 - clipPercentage);
                 }
                 
-                double scaleFactor = numBins / (maxVal - minVal);
+                if (!(maxVal > minVal)) {
+                    showFeedback("The clip values of " + imageHeaders[i] + " are equal. The image cannot be stretched.");
+                    image.close();
+                    continue;
+                }
+                
+                StretchOutput stretch = new StretchOutput(numBins, args, 5, noData);
+                if (stretch.getError() != null) {
+                    image.close();
+                    showFeedback(stretch.getError());
+                    return;
+                }
+                double outputNoData = stretch.getNoData();
 
                 outputHeader = imageHeaders[i].replace(".dep", outputSuffix);
-                WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", imageHeaders[i], WhiteboxRaster.DataType.INTEGER, noData);
+                WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", imageHeaders[i], stretch.getDataType(), outputNoData);
+                output.setNoDataValue(outputNoData);
                 output.setPreferredPalette(image.getPreferredPalette());
 
                 for (row = 0; row < nRows; row++) {
                     data = image.getRowValues(row);
                     for (col = 0; col < nCols; col++) {
                         if (data[col] != noData) {
-                            z = (int) (data[col] - minVal) * scaleFactor;
-                            if (z < 0) {
-                                z = 0;
-                            }
-                            if (z > (numBins - 1)) {
-                                z = (numBins - 1);
-                            }
+                            z = stretch.stretch(data[col], minVal, maxVal);
                             output.setValue(row, col, z);
                         } else {
-                            output.setValue(row, col, noData);
+                            output.setValue(row, col, outputNoData);
                         }
                     }
                     if (cancelOp) {
+                        image.close();
+                        output.isTemporaryFile = true;
+                        output.close();
                         cancelOperation();
                         return;
                     }
@@ -311,6 +258,7 @@ public class PercentageContrastStretch implements WhiteboxPlugin {
                 output.addMetadataEntry("Created by the "
                         + getDescriptiveName() + " tool.");
                 output.addMetadataEntry("Created on " + new Date());
+                output.addMetadataEntry("Output: " + stretch.getDescription());
                 output.close();
             }
 
@@ -309,7 +309,8 @@ public class RGBtoIHS implements WhiteboxPlugin {
                         
                         i = r + g + b;
                         
-                        if (i == 3) { 
+                        if (i == 3 || m == Math.max(Math.max(r, g), b)) { 
+                            // white, black and grey cells have no hue
                             h = 0;
                         } else if (m == b) {
                             h = (g - b) / (i - 3 * b);
@@ -319,7 +320,9 @@ public class RGBtoIHS implements WhiteboxPlugin {
                             h = (r - g) / (i - 3 * g) + 2;
                         }
                         
-                        if (h <= 1) {
+                        if (i == 0) {
+                            s = 0;
+                        } else if (h <= 1) {
                             s = (i - 3 * b) / i;
                         } else if (h <= 2) {
                             s = (i - 3 * r) / i;
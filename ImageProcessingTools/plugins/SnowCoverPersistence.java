@@ -0,0 +1,314 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the fraction of dates on which each grid cell is snow
+ * covered from a time series of binary snow-cover images.
+ */
+public class SnowCoverPersistence implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SnowCoverPersistence";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Snow Cover Persistence";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the mean snow-cover fraction of each grid cell from a time series of binary snow-cover rasters.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "ChangeDetection" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */ 
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+        
+        String outputHeader = null;
+        String noDataTreatment = "skip";
+        WhiteboxRaster image = null;
+        int cols = 0;
+        int rows = 0;
+        double imageNoData = -32768;
+        double outputNoData = -32768;
+        int numImages;
+        float progress = 0;
+        int col, row;
+        int a, i;
+        String inputFilesString = null;
+        String[] imageFiles;
+                
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputFilesString = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                noDataTreatment = args[i].trim().toLowerCase();
+            }
+        }
+        
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFilesString == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        boolean pessimistic = noDataTreatment.startsWith("pess");
+        boolean optimistic = noDataTreatment.startsWith("opt");
+        if (!pessimistic && !optimistic && !noDataTreatment.startsWith("skip")) {
+            showFeedback("The NoData treatment must be 'skip', 'pessimistic' or 'optimistic'.");
+            return;
+        }
+        
+        imageFiles = inputFilesString.split(";");
+
+        numImages = imageFiles.length;
+        
+        if (numImages < 2) {
+            showFeedback("At least two images must be specified.");
+            return;
+        }
+
+        try {
+            double[] data;
+            // the number of dates on which each cell is snow covered, and on
+            // which it is observed, i.e. not NoData
+            int[][] numSnow = null;
+            int[][] numValid = null;
+            for (a = 0; a < numImages; a++) {
+                image = new WhiteboxRaster(imageFiles[a], "r");
+                imageNoData = image.getNoDataValue();
+                String label = "Loop " + String.valueOf(a + 1) + " of " + String.valueOf(numImages) + ":";
+                    
+                if (a == 0) {
+                    rows = image.getNumberRows();
+                    cols = image.getNumberColumns();
+                    numSnow = new int[rows][cols];
+                    numValid = new int[rows][cols];
+                } else {
+                    if (image.getNumberColumns() != cols || 
+                            image.getNumberRows() != rows) {
+                        showFeedback("All input images must have the same dimensions (rows and columns).");
+                        return;
+                    }
+                }
+                for (row = 0; row < rows; row++) {
+                    data = image.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        if (data[col] != imageNoData) {
+                            numValid[row][col]++;
+                            if (data[col] > 0) {
+                                numSnow[row][col]++;
+                            }
+                        }
+                    }
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (float) (100f * row / (rows - 1));
+                    updateProgress(label, (int) progress);
+                }
+                image.close();
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", imageFiles[0], 
+                    WhiteboxRaster.DataType.FLOAT, outputNoData);
+            output.setPreferredPalette("grey.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            
+            updateProgress("Calculating snow-cover fraction:", 0);
+            data = new double[cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    int n = numValid[row][col];
+                    if (n == 0) {
+                        // the cell is never observed
+                        data[col] = outputNoData;
+                    } else if (pessimistic) {
+                        // obscured dates are snow free
+                        data[col] = (double) numSnow[row][col] / numImages;
+                    } else if (optimistic) {
+                        // obscured dates are snow covered
+                        data[col] = (double) (numSnow[row][col] + numImages - n) / numImages;
+                    } else {
+                        data[col] = (double) numSnow[row][col] / n;
+                    }
+                }
+                output.setRowValues(row, data);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (float) (100f * row / (rows - 1));
+                updateProgress("Calculating snow-cover fraction:", (int) progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Number of dates: " + numImages);
+            output.addMetadataEntry("NoData treatment: " + (pessimistic ? "pessimistic" 
+                    : optimistic ? "optimistic" : "skip"));
+            output.setDisplayMinimum(0);
+            output.setDisplayMaximum(1);
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -225,7 +225,8 @@ public class ArcSin implements WhiteboxPlugin {
                 data = inputFile.getRowValues(row);
                 for (col = 0; col < cols; col++) {
                     z = data[col];
-                    if (z != noData) {
+                    // values outside of -1 to 1 are undefined and are NoData
+                    if (z != noData && z >= -1 && z <= 1) {
                         outputFile.setValue(row, col, Math.asin(z));
                     }
 
@@ -249,8 +249,10 @@ public class Divide implements WhiteboxPlugin {
                 double noData1 = inputFile1.getNoDataValue();
                 double noData2 = inputFile2.getNoDataValue();
 
-                // make sure that the input images have the same dimensions.
-                if ((inputFile2.getNumberRows() != rows) || (inputFile2.getNumberColumns() != cols)) {
+                // make sure that the input images have the same dimensions and coordinates.
+                if (!inputFile1.isCoregisteredWith(inputFile2)) {
+                    inputFile1.close();
+                    inputFile2.close();
                     showFeedback("The input images must have the same dimensions and coordinates. Operation cancelled.");
                     return;
                 }
@@ -310,6 +312,8 @@ public class Divide implements WhiteboxPlugin {
                         z1 = data1[col];
                         if (z1 != noData && constant2 != 0) {
                             outputFile.setValue(row, col, z1 / constant2);
+                        } else {
+                            outputFile.setValue(row, col, noData);
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -348,6 +352,8 @@ public class Divide implements WhiteboxPlugin {
                         z2 = data2[col];
                         if (z2 != noData && z2 != 0) {
                             outputFile.setValue(row, col, constant1 / z2);
+                        } else {
+                            outputFile.setValue(row, col, noData);
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -244,8 +244,10 @@ public class EqualTo implements WhiteboxPlugin {
                 double noData1 = inputFile1.getNoDataValue();
                 double noData2 = inputFile2.getNoDataValue();
 
-                // make sure that the input images have the same dimensions.
-                if ((inputFile2.getNumberRows() != rows) || (inputFile2.getNumberColumns() != cols)) {
+                // make sure that the input images have the same dimensions and coordinates.
+                if (!inputFile1.isCoregisteredWith(inputFile2)) {
+                    inputFile1.close();
+                    inputFile2.close();
                     showFeedback("The input images must have the same dimensions and coordinates. Operation cancelled.");
                     return;
                 }
@@ -311,6 +313,8 @@ public class EqualTo implements WhiteboxPlugin {
                             } else {
                                 outputFile.setValue(row, col, 0);
                             }
+                        } else {
+                            outputFile.setValue(row, col, noData);
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -348,11 +352,13 @@ public class EqualTo implements WhiteboxPlugin {
                     for (col = 0; col < cols; col++) {
                         z2 = data2[col];
                         if (z2 != noData) {
-                            if (z2 == constant1) {
+                            if (constant1 == z2) {
                                 outputFile.setValue(row, col, 1);
                             } else {
                                 outputFile.setValue(row, col, 0);
                             }
+                        } else {
+                            outputFile.setValue(row, col, noData);
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -0,0 +1,385 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool inverts the relief of a raster, so that high values become low
+ * values and vice versa, e.g. valleys in a DEM become ridges. The inversion is
+ * either linear, reflecting each value about the middle of the data range, or
+ * based on the rank of each value. The output can be rescaled to a
+ * user-specified range.
+ */
+public class InvertRaster implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "InvertRaster";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Invert Raster";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Inverts the relief of a raster so that high values become low values.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"MathTools", "TerrainAnalysis"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 3) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String inputHeader = args[0];
+        String outputHeader = args[1];
+        boolean rankMethod = args[2].toLowerCase().contains("rank");
+        boolean rescale = false;
+        double scaleMin = 0;
+        double scaleMax = 0;
+        if (args.length > 4 && !args[3].toLowerCase().contains("not specified")
+                && !args[4].toLowerCase().contains("not specified")) {
+            scaleMin = Double.parseDouble(args[3]);
+            scaleMax = Double.parseDouble(args[4]);
+            rescale = true;
+        } else if (args.length > 4 && (!args[3].toLowerCase().contains("not specified")
+                || !args[4].toLowerCase().contains("not specified"))) {
+            showFeedback("Both the output minimum and maximum must be specified to rescale the output.");
+            return;
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            int row, col, i;
+            double z;
+            int progress, oldProgress = -1;
+            double[] data;
+
+            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
+
+            int rows = inputFile.getNumberRows();
+            int cols = inputFile.getNumberColumns();
+            double noData = inputFile.getNoDataValue();
+
+            // find the range of the data, and for the rank method, the sorted
+            // valid values.
+            updateProgress("Reading data:", 0);
+            double minValue = Double.POSITIVE_INFINITY;
+            double maxValue = Double.NEGATIVE_INFINITY;
+            long numValid = 0;
+            for (row = 0; row < rows; row++) {
+                data = inputFile.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z != noData) {
+                        numValid++;
+                        if (z < minValue) {
+                            minValue = z;
+                        }
+                        if (z > maxValue) {
+                            maxValue = z;
+                        }
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            if (numValid == 0) {
+                showFeedback("The input raster does not contain any valid values.");
+                return;
+            }
+
+            double[] sortedValues = null;
+            if (rankMethod) {
+                if (numValid > Integer.MAX_VALUE) {
+                    showFeedback("The input raster contains too many valid cells for the rank method.");
+                    return;
+                }
+                sortedValues = new double[(int) numValid];
+                i = 0;
+                for (row = 0; row < rows; row++) {
+                    data = inputFile.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        if (data[col] != noData) {
+                            sortedValues[i] = data[col];
+                            i++;
+                        }
+                    }
+                }
+                Arrays.sort(sortedValues);
+            }
+
+            // the range of the inverted values before rescaling
+            double outMin = rankMethod ? 0 : minValue;
+            double outMax = rankMethod ? 1 : maxValue;
+
+            WhiteboxRaster.DataType dataType = (inputFile.getDataType() == WhiteboxRaster.DataType.DOUBLE)
+                    ? WhiteboxRaster.DataType.DOUBLE : WhiteboxRaster.DataType.FLOAT;
+            WhiteboxRasterStreamWriter outputFile = new WhiteboxRasterStreamWriter(outputHeader, inputHeader, dataType, noData);
+            outputFile.setPreferredPalette(inputFile.getPreferredPalette());
+
+            updateProgress("Inverting data:", 0);
+            oldProgress = -1;
+            double[] outputData = new double[cols];
+            for (row = 0; row < rows; row++) {
+                data = inputFile.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z != noData) {
+                        if (rankMethod) {
+                            // Tied values share the mean of their ranks. The
+                            // lowest value is given a rank of zero, so the
+                            // inverted value is one minus the normalized rank.
+                            int first = firstIndexOf(sortedValues, z);
+                            int last = firstIndexOf(sortedValues, Math.nextUp(z)) - 1;
+                            double rank = (first + last) / 2.0;
+                            z = (numValid > 1) ? 1 - rank / (numValid - 1) : 0;
+                        } else {
+                            z = (maxValue + minValue) - z;
+                        }
+                        if (rescale) {
+                            if (outMax > outMin) {
+                                z = scaleMin + (z - outMin) / (outMax - outMin) * (scaleMax - scaleMin);
+                            } else {
+                                z = scaleMin;
+                            }
+                        }
+                        outputData[col] = z;
+                    } else {
+                        outputData[col] = noData;
+                    }
+                }
+                outputFile.writeRow(row, outputData);
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        inputFile.close();
+                        outputFile.abort();
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            outputFile.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            outputFile.addMetadataEntry("Created on " + new Date());
+            outputFile.addMetadataEntry("Method: " + (rankMethod ? "rank" : "linear"));
+
+            // close all of the open Whitebox rasters.
+            inputFile.close();
+            outputFile.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    // returns the index of the first value in a sorted array that is not less
+    // than z, or the length of the array if there is no such value.
+    private static int firstIndexOf(double[] sortedValues, double z) {
+        int low = 0;
+        int high = sortedValues.length;
+        while (low < high) {
+            int mid = (low + high) >>> 1;
+            if (sortedValues[mid] < z) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        return low;
+    }
+}
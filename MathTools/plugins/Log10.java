@@ -225,7 +225,9 @@ public class Log10 implements WhiteboxPlugin {
                 data = inputFile.getRowValues(row);
                 for (col = 0; col < cols; col++) {
                     z = data[col];
-                    if (z != noData) {
+                    // the logarithm of zero or a negative value is undefined,
+                    // so these cells are left as NoData
+                    if (z != noData && z > 0) {
                         outputFile.setValue(row, col, Math.log10(z));
                     }
 
@@ -223,7 +223,8 @@ public class SqrRt implements WhiteboxPlugin {
                 data1 = inputFile1.getRowValues(row);
                 for (col = 0; col < cols; col++) {
                     z1 = data1[col];
-                    if (z1 != noData) {
+                    // negative values have no square root and are NoData
+                    if (z1 != noData && z1 >= 0) {
                         outputFile.setValue(row, col, Math.sqrt(z1));
                     } else {
                         outputFile.setValue(row, col, noData);
@@ -244,8 +244,10 @@ public class Subtract implements WhiteboxPlugin {
                 double noData1 = inputFile1.getNoDataValue();
                 double noData2 = inputFile2.getNoDataValue();
 
-                // make sure that the input images have the same dimensions.
-                if ((inputFile2.getNumberRows() != rows) || (inputFile2.getNumberColumns() != cols)) {
+                // make sure that the input images have the same dimensions and coordinates.
+                if (!inputFile1.isCoregisteredWith(inputFile2)) {
+                    inputFile1.close();
+                    inputFile2.close();
                     showFeedback("The input images must have the same dimensions and coordinates. Operation cancelled.");
                     return;
                 }
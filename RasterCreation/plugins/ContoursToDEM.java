@@ -0,0 +1,705 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase;
+import whitebox.geospatialfiles.shapefile.MultiPointZ;
+import whitebox.geospatialfiles.shapefile.PointZ;
+import whitebox.geospatialfiles.shapefile.PolyLineZ;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.ShapeTypeDimension;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool interpolates a digital elevation model (DEM) from contour lines and,
+ * optionally, spot heights, by solving Laplace's equation on the grid with the
+ * elevations of the grid cells crossed by contours held fixed.
+ */
+public class ContoursToDEM implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "ContoursToDEM";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Contours To DEM";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Interpolates a DEM from contour lines and spot heights.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "Interpolation" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+    
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String contoursFile;
+        String contourFieldName;
+        String spotsFile = "not specified";
+        String spotFieldName = "not specified";
+        String outputHeader;
+        String baseFileHeader = "not specified";
+        double cellSize = -1.0;
+        double tolerance = 0.001;
+        double noData = -32768.0;
+        int row, col, rows, cols;
+        int progress, oldProgress;
+        double north, south, east, west;
+        double[][] points;
+        int[] parts;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        contoursFile = args[0];
+        contourFieldName = args[1].trim();
+        if (args.length > 2 && !args[2].trim().isEmpty()
+                && !args[2].toLowerCase().contains("not specified")) {
+            spotsFile = args[2].trim();
+        }
+        if (args.length > 3 && !args[3].trim().isEmpty()
+                && !args[3].toLowerCase().contains("not specified")) {
+            spotFieldName = args[3].trim();
+        }
+        outputHeader = args[4];
+        if (args.length > 5 && !args[5].trim().isEmpty()
+                && !args[5].toLowerCase().contains("not specified")) {
+            cellSize = Double.parseDouble(args[5]);
+        }
+        if (args.length > 6 && !args[6].trim().isEmpty()) {
+            baseFileHeader = args[6];
+        }
+        if (args.length > 7 && !args[7].trim().isEmpty()
+                && !args[7].toLowerCase().contains("not specified")) {
+            tolerance = Double.parseDouble(args[7]);
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((contoursFile == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (tolerance <= 0) {
+            showFeedback("The convergence tolerance must be greater than zero.");
+            return;
+        }
+
+        try {
+            ShapeFile contours = new ShapeFile(contoursFile);
+            ShapeType contoursType = contours.getShapeType();
+            if (contoursType.getBaseType() != ShapeType.POLYLINE) {
+                showFeedback("The contours file must be of a 'polyline' data type.");
+                return;
+            }
+            int contourFieldNum = findField(contours, contourFieldName);
+            if (contourFieldNum < 0 && !(contourFieldName.equalsIgnoreCase("z")
+                    && contoursType.getDimension() == ShapeTypeDimension.Z)) {
+                showFeedback("The elevation field '" + contourFieldName
+                        + "' could not be located in the contours file's attribute table.");
+                return;
+            }
+            
+            ShapeFile spots = null;
+            int spotFieldNum = -1;
+            if (!spotsFile.equals("not specified")) {
+                spots = new ShapeFile(spotsFile);
+                ShapeType spotsType = spots.getShapeType();
+                if (spotsType.getBaseType() != ShapeType.POINT
+                        && spotsType.getBaseType() != ShapeType.MULTIPOINT) {
+                    showFeedback("The spot heights file must be of a 'point' data type.");
+                    return;
+                }
+                if (spotFieldName.equals("not specified")) {
+                    spotFieldName = contourFieldName;
+                }
+                spotFieldNum = findField(spots, spotFieldName);
+                if (spotFieldNum < 0 && !(spotFieldName.equalsIgnoreCase("z")
+                        && spotsType.getDimension() == ShapeTypeDimension.Z)) {
+                    showFeedback("The elevation field '" + spotFieldName
+                            + "' could not be located in the spot heights file's attribute table.");
+                    return;
+                }
+            }
+
+            // initialize the output raster
+            WhiteboxRaster output;
+            if ((cellSize > 0) || 
+                    ((cellSize < 0) & (baseFileHeader.toLowerCase().contains("not specified")))) {
+                double xMin = contours.getxMin();
+                double xMax = contours.getxMax();
+                double yMin = contours.getyMin();
+                double yMax = contours.getyMax();
+                if (spots != null) {
+                    xMin = Math.min(xMin, spots.getxMin());
+                    xMax = Math.max(xMax, spots.getxMax());
+                    yMin = Math.min(yMin, spots.getyMin());
+                    yMax = Math.max(yMax, spots.getyMax());
+                }
+                if ((cellSize < 0) & (baseFileHeader.toLowerCase().contains("not specified"))) {
+                    cellSize = Math.min((yMax - yMin) / 500.0, (xMax - xMin) / 500.0);
+                }
+                north = yMax + cellSize / 2.0;
+                south = yMin - cellSize / 2.0;
+                east = xMax + cellSize / 2.0;
+                west = xMin - cellSize / 2.0;
+                rows = (int)(Math.ceil((north - south) / cellSize));
+                cols = (int)(Math.ceil((east - west) / cellSize));
+                
+                // update west and south
+                east = west + cols * cellSize;
+                south = north - rows * cellSize;
+                
+                output = new WhiteboxRaster(outputHeader, north, south, east, west,
+                        rows, cols, WhiteboxRasterBase.DataScale.CONTINUOUS, 
+                        WhiteboxRasterBase.DataType.FLOAT, noData, noData);
+            } else {
+                output = new WhiteboxRaster(outputHeader, "rw", 
+                    baseFileHeader, WhiteboxRasterBase.DataType.FLOAT, noData);
+                rows = output.getNumberRows();
+                cols = output.getNumberColumns();
+                cellSize = output.getCellSizeX();
+            }
+            output.setPreferredPalette("high_relief.pal");
+
+            // Rasterize the contours. Each segment is sampled at half-cell
+            // intervals and the cells that it crosses are assigned the mean
+            // elevation of the samples that fall within them.
+            double[][] z = new double[rows][cols];
+            int[][] n = new int[rows][cols];
+            double step = cellSize / 2.0;
+            int numRecords = contours.getNumberOfRecords();
+            oldProgress = -1;
+            for (ShapeFileRecord record : contours.records) {
+                int recNum = record.getRecordNumber();
+                points = record.getGeometry().getPoints();
+                parts = record.getGeometry().getParts();
+                double[] zArray = null;
+                double value = 0;
+                if (contourFieldNum >= 0) {
+                    Object fieldValue = contours.getAttributeTable().getValue(recNum - 1, contourFieldNum);
+                    if (!(fieldValue instanceof Number)) {
+                        continue;
+                    }
+                    value = ((Number)fieldValue).doubleValue();
+                } else if (record.getGeometry() instanceof PolyLineZ) {
+                    zArray = ((PolyLineZ)record.getGeometry()).getzArray();
+                }
+                for (int p = 0; p < parts.length; p++) {
+                    int start = parts[p];
+                    int end = (p < parts.length - 1) ? parts[p + 1] - 1 : points.length - 1;
+                    for (int i = start; i <= end; i++) {
+                        double z1 = (zArray == null) ? value : zArray[i];
+                        if (i == end) {
+                            // a single-vertex part still marks its cell
+                            if (start == end) {
+                                addSample(output, z, n, points[i][0], points[i][1], z1);
+                            }
+                            break;
+                        }
+                        double z2 = (zArray == null) ? value : zArray[i + 1];
+                        double dx = points[i + 1][0] - points[i][0];
+                        double dy = points[i + 1][1] - points[i][1];
+                        int numSteps = Math.max(1, (int)Math.ceil(Math.sqrt(dx * dx + dy * dy) / step));
+                        for (int s = 0; s <= numSteps; s++) {
+                            double t = (double)s / numSteps;
+                            addSample(output, z, n, points[i][0] + t * dx,
+                                    points[i][1] + t * dy, z1 + t * (z2 - z1));
+                        }
+                    }
+                }
+                progress = (int)(100f * recNum / numRecords);
+                if (progress != oldProgress) {
+                    updateProgress("Rasterizing contours:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            
+            boolean[][] fixed = new boolean[rows][cols];
+            int numFixed = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (n[row][col] > 0) {
+                        z[row][col] /= n[row][col];
+                        fixed[row][col] = true;
+                        numFixed++;
+                    }
+                }
+            }
+            
+            // Spot heights replace any contour elevation in their cells.
+            int numSpotCells = 0;
+            if (spots != null) {
+                double[][] spotZ = new double[rows][cols];
+                int[][] spotN = new int[rows][cols];
+                for (ShapeFileRecord record : spots.records) {
+                    int recNum = record.getRecordNumber();
+                    points = record.getGeometry().getPoints();
+                    double[] zArray = null;
+                    double value = 0;
+                    if (spotFieldNum >= 0) {
+                        Object fieldValue = spots.getAttributeTable().getValue(recNum - 1, spotFieldNum);
+                        if (!(fieldValue instanceof Number)) {
+                            continue;
+                        }
+                        value = ((Number)fieldValue).doubleValue();
+                    } else if (record.getGeometry() instanceof PointZ) {
+                        zArray = ((PointZ)record.getGeometry()).getzArray();
+                    } else if (record.getGeometry() instanceof MultiPointZ) {
+                        zArray = ((MultiPointZ)record.getGeometry()).getzArray();
+                    }
+                    for (int i = 0; i < points.length; i++) {
+                        addSample(output, spotZ, spotN, points[i][0], points[i][1],
+                                (zArray == null) ? value : zArray[i]);
+                    }
+                }
+                for (row = 0; row < rows; row++) {
+                    for (col = 0; col < cols; col++) {
+                        if (spotN[row][col] > 0) {
+                            z[row][col] = spotZ[row][col] / spotN[row][col];
+                            if (!fixed[row][col]) {
+                                fixed[row][col] = true;
+                                numFixed++;
+                            }
+                            numSpotCells++;
+                        }
+                    }
+                }
+            }
+            
+            if (numFixed == 0) {
+                showFeedback("None of the contours or spot heights overlap the output grid.");
+                output.close();
+                return;
+            }
+
+            // Interpolate the remaining cells.
+            iterations = 0;
+            converged = true;
+            solve(z, fixed, tolerance, true);
+            if (cancelOp) {
+                cancelOperation();
+                return;
+            }
+            
+            // Find the unconstrained cells that have been left flat. A harmonic
+            // surface is flat wherever a connected area of unconstrained cells
+            // is bordered by cells of a single elevation, e.g. a hilltop or a
+            // depression enclosed by one contour, or the area between the
+            // outermost contour and the grid edge.
+            long numFlatEnclosed = 0;
+            long numFlatEdge = 0;
+            boolean[][] visited = new boolean[rows][cols];
+            int[] stack = new int[rows * cols];
+            for (int startRow = 0; startRow < rows; startRow++) {
+                for (int startCol = 0; startCol < cols; startCol++) {
+                    if (fixed[startRow][startCol] || visited[startRow][startCol]) {
+                        continue;
+                    }
+                    long areaSize = 0;
+                    boolean touchesEdge = false;
+                    double minBorder = Double.POSITIVE_INFINITY;
+                    double maxBorder = Double.NEGATIVE_INFINITY;
+                    int top = 0;
+                    stack[top++] = startRow * cols + startCol;
+                    visited[startRow][startCol] = true;
+                    while (top > 0) {
+                        int cell = stack[--top];
+                        row = cell / cols;
+                        col = cell % cols;
+                        areaSize++;
+                        for (int k = 0; k < 4; k++) {
+                            int r = row + dRow[k];
+                            int c = col + dCol[k];
+                            if (r < 0 || r >= rows || c < 0 || c >= cols) {
+                                touchesEdge = true;
+                            } else if (fixed[r][c]) {
+                                minBorder = Math.min(minBorder, z[r][c]);
+                                maxBorder = Math.max(maxBorder, z[r][c]);
+                            } else if (!visited[r][c]) {
+                                visited[r][c] = true;
+                                stack[top++] = r * cols + c;
+                            }
+                        }
+                    }
+                    if (maxBorder - minBorder <= 1.0e-6 * Math.max(1.0, Math.abs(maxBorder))) {
+                        if (touchesEdge) {
+                            numFlatEdge += areaSize;
+                        } else {
+                            numFlatEnclosed += areaSize;
+                        }
+                    }
+                }
+            }
+            stack = null;
+            visited = null;
+            
+            updateProgress("Saving data:", 0);
+            for (row = 0; row < rows; row++) {
+                output.setRowValues(row, z[row]);
+            }
+            
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Interpolation method: finite-difference (Laplace) with contour cells fixed");
+            output.addMetadataEntry("Convergence tolerance: " + tolerance);
+            output.addMetadataEntry("SOR iterations (all grid levels): " + iterations);
+            if (spots != null) {
+                output.addMetadataEntry("Spot height cells: " + numSpotCells);
+            }
+            output.close();
+            
+            if (!converged) {
+                String msg = "Warning: The interpolation did not converge to a tolerance of "
+                        + tolerance + " within " + maxIterations
+                        + " iterations. Consider using a larger tolerance or cell size.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+            if (numFlatEnclosed > 0) {
+                String msg = "Warning: " + numFlatEnclosed + " grid cells lie within "
+                        + "areas that are enclosed by a single contour, such as hilltops "
+                        + "and depressions, and have been interpolated as flat. Adding spot "
+                        + "heights for the summits and pits will remove these flat areas.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+            if (numFlatEdge > 0) {
+                String msg = "Warning: " + numFlatEdge + " grid cells between the outermost "
+                        + "contours and the edge of the grid are bordered by a single contour "
+                        + "and have been interpolated as flat.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private static final int[] dRow = {-1, 0, 1, 0};
+    private static final int[] dCol = {0, 1, 0, -1};
+    private static final int maxIterations = 20000;
+    private static final int minCoarseSize = 16;
+    private long iterations;
+    private boolean converged;
+    
+    private int findField(ShapeFile shapefile, String fieldName) {
+        String[] fieldNames = shapefile.getAttributeTableFields();
+        for (int i = 0; i < fieldNames.length; i++) {
+            if (fieldNames[i].trim().equalsIgnoreCase(fieldName)) {
+                return i;
+            }
+        }
+        return -1;
+    }
+    
+    private void addSample(WhiteboxRaster grid, double[][] sum, int[][] count,
+            double x, double y, double value) {
+        int row = grid.getRowFromYCoordinate(y);
+        int col = grid.getColumnFromXCoordinate(x);
+        if (row >= 0 && row < sum.length && col >= 0 && col < sum[0].length) {
+            sum[row][col] += value;
+            count[row][col]++;
+        }
+    }
+    
+    /**
+     * Solves Laplace's equation for the cells of z that are not fixed, using
+     * successive over-relaxation (SOR). To speed convergence, the problem is
+     * first solved on a grid of half the resolution and the result is used as
+     * the starting surface. Cells on the grid edges use only the neighbours
+     * that are available, i.e. there is no flow across the edges.
+     */
+    private void solve(double[][] z, boolean[][] fixed, double tolerance, boolean finest) {
+        int rows = z.length;
+        int cols = z[0].length;
+        int row, col;
+        
+        if (Math.min(rows, cols) >= 2 * minCoarseSize) {
+            int coarseRows = (rows + 1) / 2;
+            int coarseCols = (cols + 1) / 2;
+            double[][] coarseZ = new double[coarseRows][coarseCols];
+            boolean[][] coarseFixed = new boolean[coarseRows][coarseCols];
+            int[][] count = new int[coarseRows][coarseCols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (fixed[row][col]) {
+                        coarseZ[row / 2][col / 2] += z[row][col];
+                        count[row / 2][col / 2]++;
+                    }
+                }
+            }
+            for (row = 0; row < coarseRows; row++) {
+                for (col = 0; col < coarseCols; col++) {
+                    if (count[row][col] > 0) {
+                        coarseZ[row][col] /= count[row][col];
+                        coarseFixed[row][col] = true;
+                    }
+                }
+            }
+            solve(coarseZ, coarseFixed, tolerance, false);
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (!fixed[row][col]) {
+                        z[row][col] = coarseZ[row / 2][col / 2];
+                    }
+                }
+            }
+        } else {
+            double mean = 0;
+            int numFixed = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (fixed[row][col]) {
+                        mean += z[row][col];
+                        numFixed++;
+                    }
+                }
+            }
+            mean = (numFixed > 0) ? mean / numFixed : 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (!fixed[row][col]) {
+                        z[row][col] = mean;
+                    }
+                }
+            }
+        }
+        
+        double omega = 2.0 / (1.0 + Math.sin(Math.PI / Math.max(rows, cols)));
+        double initialChange = -1;
+        int progress, oldProgress = -1;
+        for (int iter = 0; iter < maxIterations; iter++) {
+            double maxChange = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (fixed[row][col]) {
+                        continue;
+                    }
+                    double sum = 0;
+                    int n = 0;
+                    for (int k = 0; k < 4; k++) {
+                        int r = row + dRow[k];
+                        int c = col + dCol[k];
+                        if (r >= 0 && r < rows && c >= 0 && c < cols) {
+                            sum += z[r][c];
+                            n++;
+                        }
+                    }
+                    double change = omega * (sum / n - z[row][col]);
+                    z[row][col] += change;
+                    if (Math.abs(change) > maxChange) {
+                        maxChange = Math.abs(change);
+                    }
+                }
+            }
+            iterations++;
+            if (maxChange < tolerance) {
+                return;
+            }
+            if (cancelOp) {
+                return;
+            }
+            if (finest) {
+                // progress is reported on a log scale of the remaining change
+                if (initialChange < 0) {
+                    initialChange = maxChange;
+                }
+                progress = (int)(100.0 * Math.log(initialChange / maxChange)
+                        / Math.log(initialChange / tolerance));
+                progress = Math.max(0, Math.min(100, progress));
+                if (progress != oldProgress) {
+                    updateProgress("Interpolating:", progress);
+                    oldProgress = progress;
+                }
+            }
+        }
+        converged = false;
+    }
+}
@@ -0,0 +1,363 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.File;
+import java.text.DecimalFormat;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool compares two rasters cell by cell and reports whether they are
+ * identical, to within a tolerance. The rasters must have the same dimensions
+ * and extent and their NoData cells must coincide. The locations of the first
+ * few differing cells and the maximum absolute difference are reported, which
+ * makes the tool useful for checking the outputs of other tools against
+ * reference outputs.
+ */
+public class CompareRasters implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "CompareRasters";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Compare Rasters";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Tests whether two rasters are identical to within a tolerance.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = {"StatisticalTools"};
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader1 = null;
+        String inputHeader2 = null;
+        double tolerance = 0;
+        int maxReported = 10;
+        int row, col, i;
+        int progress, oldProgress;
+        double z1, z2, diff;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader1 = args[i];
+            } else if (i == 1) {
+                inputHeader2 = args[i];
+            } else if (i == 2 && specified) {
+                tolerance = Double.parseDouble(args[i]);
+            } else if (i == 3 && specified) {
+                maxReported = Integer.parseInt(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader1 and inputHeader2 are not null.
+        if ((inputHeader1 == null) || (inputHeader2 == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (tolerance < 0 || maxReported < 0) {
+            showFeedback("The tolerance and the number of reported differences must not be negative.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster image1 = new WhiteboxRaster(inputHeader1, "r");
+            WhiteboxRaster image2 = new WhiteboxRaster(inputHeader2, "r");
+            int rows = image1.getNumberRows();
+            int cols = image1.getNumberColumns();
+            double noData1 = image1.getNoDataValue();
+            double noData2 = image2.getNoDataValue();
+            
+            StringBuilder retstr = new StringBuilder();
+            retstr.append("RASTER COMPARISON\n\n");
+            retstr.append("Input file 1:\t").append(new File(inputHeader1).getName()).append("\n");
+            retstr.append("Input file 2:\t").append(new File(inputHeader2).getName()).append("\n");
+            retstr.append("Tolerance:\t").append(tolerance).append("\n\n");
+            
+            if (rows != image2.getNumberRows() || cols != image2.getNumberColumns()) {
+                retstr.append("Result:\tDIFFERENT\n\n");
+                retstr.append("The rasters have different dimensions (")
+                        .append(rows).append(" x ").append(cols).append(" and ")
+                        .append(image2.getNumberRows()).append(" x ")
+                        .append(image2.getNumberColumns()).append(" rows x columns).\n");
+                image1.close();
+                image2.close();
+                returnData(retstr.toString());
+                return;
+            }
+            
+            // the extents are compared to within a small fraction of a cell
+            double extentTolerance = 0.001 * Math.min(image1.getCellSizeX(), image1.getCellSizeY());
+            boolean sameExtent = Math.abs(image1.getNorth() - image2.getNorth()) <= extentTolerance
+                    && Math.abs(image1.getSouth() - image2.getSouth()) <= extentTolerance
+                    && Math.abs(image1.getEast() - image2.getEast()) <= extentTolerance
+                    && Math.abs(image1.getWest() - image2.getWest()) <= extentTolerance;
+            
+            long numValueDiffs = 0;
+            long numNoDataDiffs = 0;
+            long numCompared = 0;
+            double maxDiff = 0;
+            int maxDiffRow = -1;
+            int maxDiffCol = -1;
+            StringBuilder cellList = new StringBuilder();
+            int numListed = 0;
+            double[] data1, data2;
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                data1 = image1.getRowValues(row);
+                data2 = image2.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z1 = data1[col];
+                    z2 = data2[col];
+                    boolean isNoData1 = (z1 == noData1);
+                    boolean isNoData2 = (z2 == noData2);
+                    boolean isDifferent;
+                    if (isNoData1 || isNoData2) {
+                        // the NoData masks must match
+                        isDifferent = (isNoData1 != isNoData2);
+                        if (isDifferent) {
+                            numNoDataDiffs++;
+                        }
+                    } else {
+                        numCompared++;
+                        diff = Math.abs(z1 - z2);
+                        if (diff > maxDiff) {
+                            maxDiff = diff;
+                            maxDiffRow = row;
+                            maxDiffCol = col;
+                        }
+                        isDifferent = (diff > tolerance);
+                        if (isDifferent) {
+                            numValueDiffs++;
+                        }
+                    }
+                    if (isDifferent && numListed < maxReported) {
+                        cellList.append(row).append("\t").append(col).append("\t")
+                                .append(isNoData1 ? "NoData" : String.valueOf(z1)).append("\t")
+                                .append(isNoData2 ? "NoData" : String.valueOf(z2)).append("\n");
+                        numListed++;
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    updateProgress("Comparing rasters:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            image1.close();
+            image2.close();
+            
+            boolean identical = sameExtent && numValueDiffs == 0 && numNoDataDiffs == 0;
+            DecimalFormat df = new DecimalFormat("###,###,###,##0");
+            retstr.append("Result:\t").append(identical ? "IDENTICAL" : "DIFFERENT").append("\n\n");
+            if (!sameExtent) {
+                retstr.append("The rasters have different extents.\n");
+            }
+            retstr.append("Cells compared:\t").append(df.format(numCompared)).append("\n");
+            retstr.append("Cells differing by more than the tolerance:\t")
+                    .append(df.format(numValueDiffs)).append("\n");
+            retstr.append("Cells that are NoData in only one raster:\t")
+                    .append(df.format(numNoDataDiffs)).append("\n");
+            retstr.append("Maximum absolute difference:\t").append(maxDiff);
+            if (maxDiffRow >= 0) {
+                retstr.append(" (row ").append(maxDiffRow).append(", column ")
+                        .append(maxDiffCol).append(")");
+            }
+            retstr.append("\n");
+            if (numListed > 0) {
+                retstr.append("\nFirst ").append(numListed).append(" differing cells:\n");
+                retstr.append("Row\tColumn\tValue 1\tValue 2\n");
+                retstr.append(cellList);
+            }
+            returnData(retstr.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
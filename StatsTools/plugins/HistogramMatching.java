@@ -366,6 +366,8 @@ public class HistogramMatching implements WhiteboxPlugin{
                         if (binNum > numBinsLessOne) { binNum = numBinsLessOne; }
                         pVal = cdf[binNum];
                         j = (int)(Math.floor(pVal * 10));
+                        // values at the top of the distribution take the largest reference value
+                        xVal = referenceCDF[numLines - 1][0];
                         for (i = startingVals[j]; i < numLines; i++) {
                             if (referenceCDF[i][1] > pVal) {
                                 if (i > 0) {
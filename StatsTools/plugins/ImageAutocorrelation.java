@@ -18,6 +18,7 @@ package plugins;
 
 import org.apache.commons.math3.distribution.NormalDistribution;
 import java.text.DecimalFormat;
+import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
@@ -233,6 +234,10 @@ public class ImageAutocorrelation implements WhiteboxPlugin {
             dX = new int[]{1, 0, -1, 0};
             dY = new int[]{0, 1, 0, -1};
         }
+        boolean outputLISA = false;
+        if (args.length > 2) {
+            outputLISA = Boolean.parseBoolean(args[2]);
+        }
 
         try {
 
@@ -251,6 +256,7 @@ public class ImageAutocorrelation implements WhiteboxPlugin {
             double[] zR = new double[numImages];
             double[] pValueN = new double[numImages];
             double[] pValueR = new double[numImages];
+            long[][] numClusterCells = new long[numImages][5];
             double[] data;
             NormalDistribution distribution = new NormalDistribution(0, 1);
 
@@ -321,7 +327,8 @@ public class ImageAutocorrelation implements WhiteboxPlugin {
                     updateProgress(progressMessage, progress);
                 }
                 
-                double S1 = 4 * W;
+                // for symmetric binary weights, S1 = 2W and S2 = 4 sum(wi^2)
+                double S1 = 2 * W;
                 S2 = S2 * 4;
                 
                 stdDev[a] = Math.sqrt(totalDeviation / (n[a] - 1));
@@ -329,20 +336,94 @@ public class ImageAutocorrelation implements WhiteboxPlugin {
                 I[a] = n[a] * numerator / (totalDeviation * W);
                 
                 varNormality[a] = (n[a] * n[a] * S1 - n[a] * S2 + 3 * W * W) / 
-                        ((W * W) * (n[a] * n[a] - 1));
+                        ((W * W) * (n[a] * n[a] - 1)) - E_I[a] * E_I[a];
                 
                 zN[a] = (I[a] - E_I[a]) / (Math.sqrt(varNormality[a])); 
                 pValueN[a] = 2d * (1.0 - distribution.cumulativeProbability(Math.abs(zN[a])));
                 
-                k = k / (n[a] * stdDev[a] * stdDev[a] * stdDev[a] * stdDev[a]);
+                // sample kurtosis, m4 / m2^2
+                k = n[a] * k / (totalDeviation * totalDeviation);
                 
                 varRandomization[a] = (n[a] * ((n[a] * n[a] - 3 * n[a] + 3) * S1 - n[a] * S2 + 3 * W * W) - 
-                        k * (n[a] * n[a] - n[a]) * S1 - 2 * n[a] * S1 + 6 * W * W) / 
-                        ((n[a] - 1) * (n[a] - 2) * (n[a] - 3) * W * W);
+                        k * ((n[a] * n[a] - n[a]) * S1 - 2 * n[a] * S2 + 6 * W * W)) / 
+                        ((n[a] - 1) * (n[a] - 2) * (n[a] - 3) * W * W) - E_I[a] * E_I[a];
                 
                 zR[a] = (I[a] - E_I[a]) / (Math.sqrt(varRandomization[a])); 
                 pValueR[a] = 2d * (1.0 - distribution.cumulativeProbability(Math.abs(zR[a])));
                 
+                if (outputLISA) {
+                    /* Local Moran's I (Anselin, 1995) is calculated for each
+                     * cell using the same binary contiguity weights. The 
+                     * z-score of each local I is based on the randomization 
+                     * assumption and cells that are significant at the 0.05 
+                     * level are classified as High-High (1), Low-Low (2), 
+                     * High-Low (3), or Low-High (4) clusters and outliers.
+                     */
+                    double m2 = totalDeviation / n[a];
+                    double zCritical = distribution.inverseCumulativeProbability(0.975);
+                    double lag, Ii, expectedIi, varIi, zIi;
+                    int cluster;
+                    String shortFileName = imageFiles[a].replace(".dep", "");
+                    WhiteboxRaster localI = new WhiteboxRaster(shortFileName + "_local_I.dep", "rw", imageFiles[a], WhiteboxRaster.DataType.FLOAT, noData);
+                    localI.setPreferredPalette("blue_white_red.pal");
+                    localI.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+                    WhiteboxRaster lisa = new WhiteboxRaster(shortFileName + "_LISA.dep", "rw", imageFiles[a], WhiteboxRaster.DataType.INTEGER, noData);
+                    lisa.setPreferredPalette("qual.pal");
+                    lisa.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+                    for (row = 0; row < rows; row++) {
+                        for (col = 0; col < cols; col++) {
+                            z = image.getValue(row, col);
+                            if (z != noData) {
+                                lag = 0;
+                                wij = 0;
+                                for (int i = 0; i < numNeighbours; i++) {
+                                    zn = image.getValue(row + dY[i], col + dX[i]);
+                                    if (zn != noData) {
+                                        lag += zn - mean[a];
+                                        wij += 1;
+                                    }
+                                }
+                                if (wij == 0) {
+                                    continue;
+                                }
+                                Ii = (z - mean[a]) / m2 * lag;
+                                expectedIi = -wij / (n[a] - 1);
+                                varIi = wij * (n[a] - k) / (n[a] - 1)
+                                        + (wij * wij - wij) * (2 * k - n[a]) / ((n[a] - 1) * (n[a] - 2))
+                                        - expectedIi * expectedIi;
+                                zIi = (Ii - expectedIi) / Math.sqrt(varIi);
+                                localI.setValue(row, col, zIi);
+                                cluster = 0;
+                                if (Math.abs(zIi) >= zCritical) {
+                                    if (z > mean[a]) {
+                                        cluster = (lag > 0) ? 1 : 3;
+                                    } else {
+                                        cluster = (lag < 0) ? 2 : 4;
+                                    }
+                                }
+                                lisa.setValue(row, col, cluster);
+                                numClusterCells[a][cluster]++;
+                            }
+                        }
+                        if (cancelOp) {
+                            cancelOperation();
+                            return;
+                        }
+                        progress = (int)(row * 100.0 / rows);
+                        updateProgress(progressMessage, progress);
+                    }
+                    localI.addMetadataEntry("Created by the "
+                            + getDescriptiveName() + " tool.");
+                    localI.addMetadataEntry("Created on " + new Date());
+                    localI.addMetadataEntry("Values are z-scores of local Moran's I.");
+                    localI.close();
+                    lisa.addMetadataEntry("Created by the "
+                            + getDescriptiveName() + " tool.");
+                    lisa.addMetadataEntry("Created on " + new Date());
+                    lisa.addMetadataEntry("0 = not significant, 1 = High-High, 2 = Low-Low, 3 = High-Low, 4 = Low-High");
+                    lisa.close();
+                }
+                
                 image.close();
                 
                 progress = (int) (100f * (a + 1) / numImages);
@@ -372,6 +453,13 @@ public class ImageAutocorrelation implements WhiteboxPlugin {
                 retstr.append("Variance of I (randomization assumption):\t").append(df2.format(varRandomization[a])).append("\n");
                 retstr.append("z test stat (randomization assumption):\t").append(df2.format(zR[a])).append("\n");
                 retstr.append("p-value (randomization assumption):\t").append(df2.format(pValueR[a])).append("\n");
+                if (outputLISA) {
+                    retstr.append("Local Moran's I clusters (p < 0.05):\n");
+                    retstr.append("   High-High (hot spots):\t").append(df1.format(numClusterCells[a][1])).append("\n");
+                    retstr.append("   Low-Low (cold spots):\t").append(df1.format(numClusterCells[a][2])).append("\n");
+                    retstr.append("   High-Low (outliers):\t").append(df1.format(numClusterCells[a][3])).append("\n");
+                    retstr.append("   Low-High (outliers):\t").append(df1.format(numClusterCells[a][4])).append("\n");
+                }
                 
             }
 
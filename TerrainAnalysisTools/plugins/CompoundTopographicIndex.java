@@ -0,0 +1,577 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates a compound topographic index (CTI) from a digital
+ * elevation model (DEM) using one of several formulations, which differ in the
+ * flow routing used to derive the specific catchment area.
+ */
+public class CompoundTopographicIndex implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "CompoundTopographicIndex";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Compound Topographic Index";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates a compound topographic index (CTI) from a DEM "
+                + "using the standard TWI, SAGA SWI, McNamara TWI, or Beven-Kirkby "
+                + "formulation.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SecondaryTerrainAttributes" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+    
+    // the CTI formulations, each of which combines the slope with a specific 
+    // catchment area (SCA) derived using a different flow routing scheme
+    private static final int STANDARD_TWI = 0;
+    private static final int SAGA_SWI = 1;
+    private static final int MCNAMARA_TWI = 2;
+    private static final int BEVEN_KIRKBY = 3;
+    private static final String[] methodNames = {"standard_twi", "saga_swi", 
+        "mcnamara_twi", "beven_kirkby"};
+    
+    // the exponent of the Freeman (1991) multiple flow direction algorithm
+    private static final double freemanExponent = 1.1;
+    // the slope (degrees) above which the SAGA SCA is modified, as in the
+    // default of the SAGA Wetness Index tool
+    private static final double sagaSlopeThreshold = 2.0;
+    // the range of the area-dependent exponent used by mcnamara_twi and the
+    // upslope area (grid cells) at which the exponent reaches its maximum
+    private static final double minRoutingExponent = 1.1;
+    private static final double maxRoutingExponent = 10.0;
+    private static final double routingAreaThreshold = 1000.0;
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+        
+        String demHeader = null;
+        String outputHeader = null;
+        int method = STANDARD_TWI;
+        double zConvFactor = 1;
+        int i;
+        int progress;
+        int row, col;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                demHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                method = parseMethod(args[i]);
+                if (method < 0) {
+                    showFeedback("Unrecognized method '" + args[i] + "'. The method must "
+                            + "be one of standard_twi, saga_swi, mcnamara_twi, or beven_kirkby.");
+                    return;
+                }
+            } else if (i == 3 && !args[i].trim().isEmpty() 
+                    && !args[i].toLowerCase().contains("not specified")) {
+                zConvFactor = Double.parseDouble(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((demHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster dem = new WhiteboxRaster(demHeader, "r");
+            rows = dem.getNumberRows();
+            cols = dem.getNumberColumns();
+            noData = dem.getNoDataValue();
+            cellSizeX = dem.getCellSizeX();
+            cellSizeY = dem.getCellSizeY();
+            
+            if (dem.getXYUnits().toLowerCase().contains("deg") || 
+                    dem.getProjection().toLowerCase().contains("geog")) {
+                // calculate a new z-conversion factor
+                double midLat = (dem.getNorth() + dem.getSouth()) / 2.0;
+                if (midLat <= 90 && midLat >= -90) {
+                    zConvFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)));
+                }
+            }
+            
+            for (i = 0; i < 8; i++) {
+                dist[i] = Math.sqrt(dX[i] * cellSizeX * dX[i] * cellSizeX 
+                        + dY[i] * cellSizeY * dY[i] * cellSizeY);
+                // the effective contour lengths of Quinn et al. (1991)
+                contourLength[i] = (dX[i] == 0 || dY[i] == 0) ? 0.5 * cellSizeX
+                        : 0.354 * cellSizeX;
+            }
+            
+            z = new double[rows][cols];
+            for (row = 0; row < rows; row++) {
+                double[] data = dem.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z[row][col] = data[col] != noData ? data[col] * zConvFactor : noData;
+                }
+            }
+            dem.close();
+            
+            double[][] slope = calculateSlope();
+            if (slope == null) {
+                return;
+            }
+            
+            double[][] area = calculateArea(method);
+            if (area == null) {
+                return;
+            }
+            
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", demHeader, 
+                    WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("blueyellow.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits("dimensionless");
+
+            double thresholdRad = Math.toRadians(sagaSlopeThreshold);
+            double[] outData = new double[cols];
+            double sca, s;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    s = slope[row][col];
+                    if (z[row][col] != noData && s > 0) {
+                        // the specific catchment area, i.e. the upslope area 
+                        // per unit contour width
+                        sca = area[row][col] / cellSizeX;
+                        if (method == SAGA_SWI && s > thresholdRad) {
+                            sca = sca / (2 * cellSizeX * Math.sin(s));
+                        }
+                        outData[col] = Math.log(sca / Math.tan(s));
+                    } else {
+                        outData[col] = noData;
+                    }
+                }
+                output.setRowValues(row, outData);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int)(100f * row / (rows - 1));
+                updateProgress("Saving data:", progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Method: " + methodNames[method]);
+            output.addMetadataEntry("Z conversion factor: " + zConvFactor);
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            z = null;
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private final int[] dX = {1, 1, 1, 0, -1, -1, -1, 0};
+    private final int[] dY = {-1, 0, 1, 1, 1, 0, -1, -1};
+    private final double[] dist = new double[8];
+    private final double[] contourLength = new double[8];
+    private int rows;
+    private int cols;
+    private double noData;
+    private double cellSizeX;
+    private double cellSizeY;
+    private double[][] z;
+    
+    private int parseMethod(String str) {
+        str = str.toLowerCase().trim();
+        if (str.contains("saga") || str.contains("swi")) {
+            return SAGA_SWI;
+        } else if (str.contains("mcnamara")) {
+            return MCNAMARA_TWI;
+        } else if (str.contains("beven") || str.contains("kirkby")) {
+            return BEVEN_KIRKBY;
+        } else if (str.contains("standard") || str.equals("twi")) {
+            return STANDARD_TWI;
+        }
+        return -1;
+    }
+    
+    /**
+     * Calculates the slope, in radians, using the same method as the Slope 
+     * tool. Returns null if the operation is cancelled.
+     */
+    private double[][] calculateSlope() {
+        double[][] slope = new double[rows][cols];
+        double[] N = new double[8];
+        double fx, fy;
+        int row, col, rowN, colN, i;
+        updateProgress("Calculating slope:", 0);
+        for (row = 0; row < rows; row++) {
+            for (col = 0; col < cols; col++) {
+                if (z[row][col] == noData) {
+                    continue;
+                }
+                for (i = 0; i < 8; i++) {
+                    rowN = row + dY[i];
+                    colN = col + dX[i];
+                    if (rowN >= 0 && rowN < rows && colN >= 0 && colN < cols
+                            && z[rowN][colN] != noData) {
+                        N[i] = z[rowN][colN];
+                    } else {
+                        N[i] = z[row][col];
+                    }
+                }
+                fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / (8 * cellSizeY);
+                fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / (8 * cellSizeX);
+                slope[row][col] = Math.atan(Math.sqrt(fx * fx + fy * fy));
+            }
+            if (cancelOp) {
+                cancelOperation();
+                return null;
+            }
+            updateProgress("Calculating slope:", (int)(100f * row / (rows - 1)));
+        }
+        return slope;
+    }
+    
+    /**
+     * Calculates the partitioning of the flow leaving a grid cell among its 
+     * downslope neighbours for the specified method. The weights are not 
+     * normalized and the neighbours that receive flow are flagged in 
+     * receivers, since a receiver's weight may underflow to zero under the 
+     * non-linear routing. Returns the sum of the weights.
+     */
+    private double getFlowWeights(int row, int col, int method, double upslopeCells,
+            double[] weights, boolean[] receivers) {
+        int rowN, colN, i;
+        double zN, gradient;
+        double totalWeight = 0;
+        double maxGradient = 0;
+        int steepest = -1;
+        double exponent = 1.0;
+        if (method == MCNAMARA_TWI) {
+            exponent = minRoutingExponent + (maxRoutingExponent - minRoutingExponent)
+                    * Math.min(1.0, upslopeCells / routingAreaThreshold);
+        }
+        for (i = 0; i < 8; i++) {
+            weights[i] = 0;
+            receivers[i] = false;
+            rowN = row + dY[i];
+            colN = col + dX[i];
+            if (rowN < 0 || rowN >= rows || colN < 0 || colN >= cols) {
+                continue;
+            }
+            zN = z[rowN][colN];
+            if (zN == noData || zN >= z[row][col]) {
+                continue;
+            }
+            gradient = (z[row][col] - zN) / dist[i];
+            switch (method) {
+                case STANDARD_TWI:
+                    if (gradient > maxGradient) {
+                        maxGradient = gradient;
+                        steepest = i;
+                    }
+                    break;
+                case SAGA_SWI:
+                    weights[i] = Math.pow(gradient, freemanExponent);
+                    receivers[i] = true;
+                    break;
+                case BEVEN_KIRKBY:
+                    weights[i] = gradient * contourLength[i];
+                    receivers[i] = true;
+                    break;
+                case MCNAMARA_TWI:
+                    weights[i] = gradient * contourLength[i];
+                    receivers[i] = true;
+                    if (weights[i] > maxGradient) {
+                        maxGradient = weights[i];
+                    }
+                    break;
+            }
+            totalWeight += weights[i];
+        }
+        if (method == MCNAMARA_TWI && maxGradient > 0) {
+            // the weights are scaled by the largest before raising them to 
+            // the exponent, so that they can't all underflow
+            totalWeight = 0;
+            for (i = 0; i < 8; i++) {
+                if (receivers[i]) {
+                    weights[i] = Math.pow(weights[i] / maxGradient, exponent);
+                    totalWeight += weights[i];
+                }
+            }
+        } else if (method == STANDARD_TWI && steepest >= 0) {
+            weights[steepest] = 1.0;
+            receivers[steepest] = true;
+            totalWeight = 1.0;
+        }
+        return totalWeight;
+    }
+    
+    /**
+     * Calculates the upslope contributing area of each grid cell, visiting 
+     * each cell once all of the cells that drain to it have been visited. 
+     * Returns null if the operation is cancelled.
+     */
+    private double[][] calculateArea(int method) {
+        int row, col, rowN, colN, i;
+        int progress, oldProgress = -1;
+        double[] weights = new double[8];
+        boolean[] receivers = new boolean[8];
+        double totalWeight;
+        double cellArea = cellSizeX * cellSizeY;
+        
+        // count the inflowing neighbours of each cell
+        updateProgress("Calculating catchment area:", 0);
+        byte[][] numInflowing = new byte[rows][cols];
+        for (row = 0; row < rows; row++) {
+            for (col = 0; col < cols; col++) {
+                if (z[row][col] == noData) {
+                    continue;
+                }
+                getFlowWeights(row, col, method, 0, weights, receivers);
+                for (i = 0; i < 8; i++) {
+                    if (receivers[i]) {
+                        numInflowing[row + dY[i]][col + dX[i]]++;
+                    }
+                }
+            }
+            if (cancelOp) {
+                cancelOperation();
+                return null;
+            }
+        }
+        
+        double[][] area = new double[rows][cols];
+        int[] stack = new int[rows * cols];
+        int stackSize = 0;
+        long numValidCells = 0;
+        for (row = 0; row < rows; row++) {
+            for (col = 0; col < cols; col++) {
+                if (z[row][col] != noData) {
+                    area[row][col] = cellArea;
+                    numValidCells++;
+                    if (numInflowing[row][col] == 0) {
+                        stack[stackSize] = row * cols + col;
+                        stackSize++;
+                    }
+                }
+            }
+        }
+        long numSolved = 0;
+        while (stackSize > 0) {
+            stackSize--;
+            row = stack[stackSize] / cols;
+            col = stack[stackSize] % cols;
+            totalWeight = getFlowWeights(row, col, method, area[row][col] / cellArea, 
+                    weights, receivers);
+            for (i = 0; i < 8; i++) {
+                if (receivers[i]) {
+                    rowN = row + dY[i];
+                    colN = col + dX[i];
+                    if (totalWeight > 0) {
+                        area[rowN][colN] += area[row][col] * weights[i] / totalWeight;
+                    }
+                    numInflowing[rowN][colN]--;
+                    if (numInflowing[rowN][colN] == 0) {
+                        stack[stackSize] = rowN * cols + colN;
+                        stackSize++;
+                    }
+                }
+            }
+            numSolved++;
+            progress = (int)(100f * numSolved / numValidCells);
+            if (progress != oldProgress) {
+                oldProgress = progress;
+                updateProgress("Calculating catchment area:", progress);
+                if (cancelOp) {
+                    cancelOperation();
+                    return null;
+                }
+            }
+        }
+        return area;
+    }
+}
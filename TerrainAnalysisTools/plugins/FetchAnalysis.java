@@ -17,9 +17,11 @@
 package plugins;
 
 import java.util.Date;
+import whitebox.algorithms.DirectionalRay;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
 
 /**
  * This tool creates a new raster in which each grid cell is assigned the distance, in meters, to the nearest topographic obstacle in a specified direction. 
@@ -143,7 +145,7 @@ public class FetchAnalysis implements WhiteboxPlugin {
         }
         previousProgress = progress;
     }
-    
+
     /**
      * Sets the arguments (parameters) used by the plugin.
      *
@@ -190,70 +192,41 @@ public class FetchAnalysis implements WhiteboxPlugin {
     @Override
     public void run() {
         amIActive = true;
-        
+
         String inputHeader = null;
         String outputHeader = null;
-        int i;
-        int progress;
-        int row, col;
-        double z = 0;
-        double gridRes = 0;
-        double currentVal = 0;
-        double maxValDist = 0;
-        double maxDist = 0;
-        double lineSlope = 0;
         double azimuth = 0;
-        double deltaX = 0;
-        double deltaY = 0;
-        double x = 0;
-        int x1 = 0;
-        int x2 = 0;
-        double y = 0;
-        int y1 = 0;
-        int y2 = 0;
-        double z1 = 0;
-        double z2 = 0;
-        double dist = 0;
-        double oldDist = 0;
-        double yIntercept = 0;
-        int xStep = 0;
-        int yStep = 0;
-        double noData = 0;
-        boolean flag = false;
         double heightIncrement = 0;
-        double currentMaxVal = 0;
+        double maxDist = Double.POSITIVE_INFINITY;
 
-        maxDist = Double.MAX_VALUE;
-                    
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
             return;
         }
-        
-        for (i = 0; i < args.length; i++) {
+
+        for (int i = 0; i < args.length; i++) {
             if (i == 0) {
                 inputHeader = args[i];
             } else if (i == 1) {
                 outputHeader = args[i];
             } else if (i == 2) {
-                azimuth = Double.parseDouble(args[i]);
-                if (azimuth > 360 || azimuth < 0) {
-                    azimuth = 0.1;
+                azimuth = Double.parseDouble(args[i]) % 360;
+                if (azimuth < 0) {
+                    azimuth += 360;
                 }
-                if (azimuth == 0) { azimuth = 0.1; }
-                if (azimuth == 180) { azimuth = 179.9; }
-                if (azimuth == 360) { azimuth = 359.9; }
-                if (azimuth < 180) { 
-                    lineSlope = Math.tan(Math.toRadians(90 - azimuth));
-                } else {
-                    lineSlope = Math.tan(Math.toRadians(270 - azimuth));
-                }
-                
             } else if (i == 3) {
                 heightIncrement = Double.parseDouble(args[i]);
+            } else if (i == 4) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    maxDist = Double.parseDouble(args[i]);
+                    if (maxDist <= 0) {
+                        showFeedback("The maximum search distance must be greater than zero.");
+                        return;
+                    }
+                }
             }
         }
-        
+
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
             showFeedback("One or more of the input parameters have not been set properly.");
@@ -262,153 +235,94 @@ public class FetchAnalysis implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
-            int rows = DEM.getNumberRows();
-            int cols = DEM.getNumberColumns();
-            noData = DEM.getNoDataValue();
-            gridRes = (DEM.getCellSizeX() + DEM.getCellSizeY()) / 2;
-            
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
-            output.setPreferredPalette("grey.pal");
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double cellSizeX = DEM.getCellSizeX();
+            final double cellSizeY = DEM.getCellSizeY();
+            final double rayAzimuth = azimuth;
+            final double rayMaxDist = maxDist;
+            final double increment = heightIncrement;
 
-            if (azimuth > 0 && azimuth <= 90) {
-                xStep = 1;
-                yStep = 1;
-            } else if (azimuth <= 180) {
-                xStep = 1;
-                yStep = -1;
-            } else if (azimuth <= 270) {
-                xStep = -1;
-                yStep = -1;
-            } else {
-                xStep = -1;
-                yStep = 1;
+            final double[][] elevations = new double[rows][];
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
             }
-            
-            for (row = 0; row < rows; row++) {
-                for (col = 0; col < cols; col++) {
-                    currentVal = DEM.getValue(row, col);
-                    if (currentVal != noData) {
-                        //calculate the y intercept of the line equation
-                        yIntercept = -row - lineSlope * col;
 
-                        //find all of the vertical intersections
-                        currentMaxVal = 0;
-                        maxValDist = 0;
-                        x = col;
-                        
-                        flag = true;
-                        do {
-                            x = x + xStep;
-                            if (x < 0 || x >= cols) {
-                                flag = false;
-                                break;
-                            }
-
-                            //calculate the Y value
-                            y = (lineSlope * x + yIntercept) * -1;
-                            if (y < 0 || y >= rows) {
-                                flag = false;
-                                break;
-                            }
+            final WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("blue_white_red.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits(DEM.getXYUnits());
 
-                            //calculate the distance
-                            deltaX = (x - col) * gridRes;
-                            deltaY = (y - row) * gridRes;
+            boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
 
-                            dist = Math.sqrt(deltaX * deltaX + deltaY * deltaY);
-                            if (dist > maxDist) {
-                                flag = false;
-                                break;
-                            }
-
-                            //estimate z
-                            y1 = (int)(y);
-                            y2 = y1 + yStep * -1;
-                            z1 = DEM.getValue(y1, (int)x);
-                            z2 = DEM.getValue(y2, (int)x);
-                            z = z1 + (y - y1) * (z2 - z1);
-                            if (z >= currentVal + dist * heightIncrement) {
-                                maxValDist = dist;
-                                flag = false;
-                            }
-                        } while (flag);
-                        
-                        oldDist = dist;
-                        
-                        //find all of the horizontal intersections
-                        y = -row;
-                        flag = true;
-                        do {
-                            y = y + yStep;
-                            if (-y < 0 || -y >= rows) {
-                                flag = false;
-                                break;
-                            }
-
-                            //calculate the X value
-                            x = (y - yIntercept) / lineSlope;
-                            if (x < 0 || x >= cols) {
-                                flag = false;
-                                break;
-                            }
-
-                            //calculate the distance
-                            deltaX = (x - col) * gridRes;
-                            deltaY = (-y - row) * gridRes;
-                            dist = Math.sqrt(deltaX * deltaX + deltaY * deltaY);
-                            if (dist > maxDist) {
-                                flag = false;
-                                break;
-                            }
-
-                            //estimate z
-                            x1 = (int)x;
-                            x2 = x1 + xStep;
-                            if (x2 < 0 || x2 >= cols) {
-                                flag = false;
-                                break;
-                            }
-
-                            z1 = DEM.getValue((int)-y, x1);
-                            z2 = DEM.getValue((int)y, x2);
-                            z = z1 + (x - x1) * (z2 - z1);
-                            if (z >= currentVal + dist * heightIncrement) {
-                                if (dist < maxValDist || maxValDist == 0) {
-                                    maxValDist = dist; 
+                @Override
+                public void run(int row, double[] data) {
+                    DirectionalRay ray = new DirectionalRay(elevations, noData,
+                            cellSizeX, cellSizeY, rayAzimuth, rayMaxDist);
+                    for (int col = 0; col < cols; col++) {
+                        double z = elevations[row][col];
+                        if (z != noData) {
+                            // the fetch is the distance to the first obstacle, or
+                            // the negative of the distance searched if there is none
+                            double fetch = 0;
+                            boolean foundObstacle = false;
+                            ray.start(row, col);
+                            while (ray.next()) {
+                                if (ray.getValue() >= z + ray.getDistance() * increment) {
+                                    fetch = ray.getDistance();
+                                    foundObstacle = true;
+                                    break;
                                 }
-                                flag = false;
                             }
-                        } while (flag);
-                        
-                        if (maxValDist == 0) {
-                            //find the larger of dist and olddist
-                            if (dist > oldDist) {
-                                maxValDist = -dist;
-                            } else {
-                                maxValDist = -oldDist;
+                            if (!foundObstacle && ray.getDistance() > 0) {
+                                fetch = -ray.getDistance();
                             }
+                            data[col] = fetch;
+                        } else {
+                            data[col] = noData;
                         }
-                        output.setValue(row, col, maxValDist);
                     }
                 }
-                if (cancelOp) {
-                    cancelOperation();
-                    return;
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    output.setRowValues(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
                 }
-                progress = (int)(100f * row / (rows - 1));
-                updateProgress(progress);
+            });
+
+            if (!completed) {
+                DEM.close();
+                output.close();
+                cancelOperation();
+                return;
             }
 
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Azimuth: " + azimuth + " degrees clockwise from north "
+                    + "(rays are traced towards the azimuth)");
+            output.addMetadataEntry("Height increment: " + heightIncrement);
+            if (maxDist < Double.POSITIVE_INFINITY) {
+                output.addMetadataEntry("Maximum search distance: " + maxDist);
+            }
+            if (maxDist < Double.POSITIVE_INFINITY) {
+                output.addMetadataEntry("Maximum search distance: " + maxDist);
+            }
 
             DEM.close();
             output.close();
-            
+
             // returning a header file string displays the image.
             returnData(outputHeader);
-            
+
         } catch (OutOfMemoryError oe) {
             myHost.showFeedback("An out-of-memory error has occurred during operation.");
         } catch (Exception e) {
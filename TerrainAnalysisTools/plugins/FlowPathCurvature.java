@@ -0,0 +1,474 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the profile and tangential curvatures of a digital
+ * elevation model (DEM) measured along and across the local flow direction
+ * given by a D8 or D-infinity flow pointer, as well as the number of
+ * neighbouring grid cells that drain into each grid cell.
+ *
+ * @author johnlindsay
+ */
+public class FlowPathCurvature implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "FlowPathCurvature";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Flow Path Curvature";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates profile and tangential curvature along the D8 or D-infinity flow path, and the number of inflowing neighbours.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SurfDerivatives" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String demHeader = null;
+        String pointerHeader = null;
+        boolean isDinf = false;
+        String profileHeader = null;
+        String tangentialHeader = null;
+        String convergenceHeader = null;
+        double zConvFactor = 1;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                demHeader = args[i];
+            } else if (i == 1) {
+                pointerHeader = args[i];
+            } else if (i == 2) {
+                isDinf = args[i].toLowerCase().contains("inf");
+            } else if (i == 3 && specified) {
+                profileHeader = args[i];
+            } else if (i == 4 && specified) {
+                tangentialHeader = args[i];
+            } else if (i == 5 && specified) {
+                convergenceHeader = args[i];
+            } else if (i == 6 && specified) {
+                zConvFactor = Double.parseDouble(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((demHeader == null) || (pointerHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (profileHeader == null && tangentialHeader == null && convergenceHeader == null) {
+            showFeedback("At least one output file must be specified.");
+            return;
+        }
+
+        try {
+            int row, col, i;
+            double z;
+            double[] N = new double[8];
+            float progress = 0;
+            int[] Dy = {-1, 0, 1, 1, 1, 0, -1, -1};
+            int[] Dx = {1, 1, 1, 0, -1, -1, -1, 0};
+            // the D8 pointer value of a neighbour that flows into the centre cell
+            double[] inflowingVals = {16, 32, 64, 128, 1, 2, 4, 8};
+            final double radToDeg = 180 / Math.PI;
+            final double LnOf2 = 0.693147180559945;
+            double Zx, Zy, Zxx, Zyy, Zxy, gradSquared, ex, ey, dirSlope, crossSlope;
+
+            WhiteboxRaster dem = new WhiteboxRaster(demHeader, "r");
+            dem.isReflectedAtEdges = true;
+
+            int rows = dem.getNumberRows();
+            int cols = dem.getNumberColumns();
+            double noData = dem.getNoDataValue();
+
+            WhiteboxRaster pointer = new WhiteboxRaster(pointerHeader, "r");
+            if (pointer.getNumberRows() != rows || pointer.getNumberColumns() != cols) {
+                showFeedback("The flow pointer and DEM must have the same number of rows and columns.");
+                return;
+            }
+            double pointerNoData = pointer.getNoDataValue();
+
+            double cellSizeX = dem.getCellSizeX();
+            double cellSizeY = dem.getCellSizeY();
+
+            if (dem.getXYUnits().toLowerCase().contains("deg")
+                    || dem.getProjection().toLowerCase().contains("geog")) {
+                // calculate a new z-conversion factor
+                double midLat = (dem.getNorth() + dem.getSouth()) / 2.0;
+                if (midLat <= 90 && midLat >= -90) {
+                    zConvFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)));
+                }
+            }
+
+            // the unit vector, in map coordinates (x east, y north), pointing
+            // from a cell to each of its neighbours
+            double[] unitX = new double[8];
+            double[] unitY = new double[8];
+            for (i = 0; i < 8; i++) {
+                double dist = Math.sqrt(Dx[i] * cellSizeX * Dx[i] * cellSizeX
+                        + Dy[i] * cellSizeY * Dy[i] * cellSizeY);
+                unitX[i] = Dx[i] * cellSizeX / dist;
+                unitY[i] = -Dy[i] * cellSizeY / dist;
+            }
+
+            WhiteboxRaster profile = null;
+            if (profileHeader != null) {
+                profile = new WhiteboxRaster(profileHeader, "rw", demHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                profile.setPreferredPalette("blue_white_red.pal");
+                profile.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            }
+            WhiteboxRaster tangential = null;
+            if (tangentialHeader != null) {
+                tangential = new WhiteboxRaster(tangentialHeader, "rw", demHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                tangential.setPreferredPalette("blue_white_red.pal");
+                tangential.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            }
+            WhiteboxRaster convergence = null;
+            if (convergenceHeader != null) {
+                convergence = new WhiteboxRaster(convergenceHeader, "rw", demHeader,
+                        WhiteboxRaster.DataType.INTEGER, noData);
+                convergence.setPreferredPalette("spectrum.pal");
+                convergence.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            }
+
+            double[] profileData = new double[cols];
+            double[] tangentialData = new double[cols];
+            double[] convergenceData = new double[cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    z = dem.getValue(row, col);
+                    double flowDir = pointer.getValue(row, col);
+                    profileData[col] = noData;
+                    tangentialData[col] = noData;
+                    convergenceData[col] = noData;
+                    if (z == noData || flowDir == pointerNoData) {
+                        continue;
+                    }
+
+                    // count the neighbours that drain into this cell
+                    int numInflowing = 0;
+                    for (i = 0; i < 8; i++) {
+                        int rowN = row + Dy[i];
+                        int colN = col + Dx[i];
+                        if (rowN < 0 || rowN >= rows || colN < 0 || colN >= cols) {
+                            continue;
+                        }
+                        double flowDirN = pointer.getValue(rowN, colN);
+                        if (flowDirN == pointerNoData) {
+                            continue;
+                        }
+                        if (isDinf) {
+                            if (flowDirN < 0) {
+                                continue;
+                            }
+                            // the neighbour sends a share of its flow to this
+                            // cell if its flow direction is within 45 degrees
+                            // of the direction back to this cell
+                            double azimuthToCell = Math.toDegrees(Math.atan2(-unitX[i], -unitY[i]));
+                            double diff = Math.abs(flowDirN - azimuthToCell) % 360;
+                            if (diff > 180) {
+                                diff = 360 - diff;
+                            }
+                            if (diff < 45) {
+                                numInflowing++;
+                            }
+                        } else if (flowDirN == inflowingVals[i]) {
+                            numInflowing++;
+                        }
+                    }
+                    convergenceData[col] = numInflowing;
+
+                    // the unit vector of the flow direction
+                    if (isDinf) {
+                        if (flowDir < 0) {
+                            continue;
+                        }
+                        ex = Math.sin(Math.toRadians(flowDir));
+                        ey = Math.cos(Math.toRadians(flowDir));
+                    } else {
+                        if (flowDir <= 0) {
+                            continue;
+                        }
+                        i = (int) Math.round(Math.log(flowDir) / LnOf2);
+                        if (i < 0 || i > 7) {
+                            continue;
+                        }
+                        ex = unitX[i];
+                        ey = unitY[i];
+                    }
+
+                    z = z * zConvFactor;
+                    for (i = 0; i < 8; i++) {
+                        N[i] = dem.getValue(row + Dy[i], col + Dx[i]);
+                        if (N[i] != noData) {
+                            N[i] = N[i] * zConvFactor;
+                        } else {
+                            N[i] = z;
+                        }
+                    }
+                    //calculate each of the terms
+                    Zx = (N[1] - N[5]) / (2 * cellSizeX);
+                    Zy = (N[7] - N[3]) / (2 * cellSizeY);
+                    Zxx = (N[1] - 2 * z + N[5]) / (cellSizeX * cellSizeX);
+                    Zyy = (N[7] - 2 * z + N[3]) / (cellSizeY * cellSizeY);
+                    Zxy = (-N[6] + N[0] + N[4] - N[2]) / (4 * cellSizeX * cellSizeY);
+                    gradSquared = Zx * Zx + Zy * Zy;
+
+                    /* The curvature of the normal section of the surface in
+                     * the direction of the unit vector (ex, ey) is:
+                     * 
+                     * k = (Zxx ex^2 + 2 Zxy ex ey + Zyy ey^2) / 
+                     *     (sqrt(1 + p) (1 + s^2))
+                     * 
+                     * where p is the squared gradient and s is the slope in
+                     * that direction. Profile curvature is measured along the
+                     * flow direction and tangential curvature across it, at
+                     * right angles (-ey, ex). When the flow direction is that
+                     * of steepest descent these are identical to the ProfCurv
+                     * and TangentialCurv tools.
+                     */
+                    dirSlope = Zx * ex + Zy * ey;
+                    crossSlope = -Zx * ey + Zy * ex;
+                    profileData[col] = (Zxx * ex * ex + 2 * Zxy * ex * ey + Zyy * ey * ey)
+                            / (Math.sqrt(1 + gradSquared) * (1 + dirSlope * dirSlope))
+                            * radToDeg * 100;
+                    tangentialData[col] = (Zxx * ey * ey - 2 * Zxy * ex * ey + Zyy * ex * ex)
+                            / (Math.sqrt(1 + gradSquared) * (1 + crossSlope * crossSlope))
+                            * radToDeg * 100;
+                }
+                if (profile != null) {
+                    profile.setRowValues(row, profileData);
+                }
+                if (tangential != null) {
+                    tangential.setRowValues(row, tangentialData);
+                }
+                if (convergence != null) {
+                    convergence.setRowValues(row, convergenceData);
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (float) (100f * row / (rows - 1));
+                updateProgress((int) progress);
+            }
+
+            dem.close();
+            pointer.close();
+
+            String pointerType = isDinf ? "D-infinity" : "D8";
+            WhiteboxRaster[] outputs = {profile, tangential, convergence};
+            for (WhiteboxRaster output : outputs) {
+                if (output == null) {
+                    continue;
+                }
+                output.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                output.addMetadataEntry("Created on " + new Date());
+                output.addMetadataEntry("Flow pointer: " + pointerType);
+                output.close();
+            }
+
+            // returning a header file string displays the image.
+            if (convergenceHeader != null) {
+                returnData(convergenceHeader);
+            }
+            if (tangentialHeader != null) {
+                returnData(tangentialHeader);
+            }
+            if (profileHeader != null) {
+                returnData(profileHeader);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,395 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the slope length and steepness (LS) factor of the Universal
+ * Soil Loss Equation from slope and specific contributing area rasters, using either
+ * slope-class exponents or the Desmet and Govers (1996) method.
+ */
+public class LSFactor implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "LSFactor";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "LS Factor (USLE)";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the slope length and steepness (LS) factor of the Universal Soil Loss Equation.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SecondaryTerrainAttributes" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+
+    // the upper bounds of the Riley et al. (1999) classes, in metres
+    private static final double[] rileyBreaks = {80, 116, 161, 239, 497, 958};
+    private static final String[] rileyClassNames = {"Level", "Nearly level",
+        "Slightly rugged", "Intermediately rugged", "Moderately rugged",
+        "Highly rugged", "Extremely rugged"};
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String slopeHeader = null;
+        String areaHeader = null;
+        String outputHeader = null;
+        boolean desmetGovers = false;
+        double mOverride = Double.NaN;
+        double nOverride = Double.NaN;
+        int i;
+        int progress;
+        int row, col;
+        double z, m, s, l, sinB, percentSlope;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                slopeHeader = args[i];
+            } else if (i == 1) {
+                areaHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                desmetGovers = args[i].toLowerCase().contains("desmet");
+            } else if (i == 4) {
+                if (specified) {
+                    mOverride = Double.parseDouble(args[i]);
+                }
+            } else if (i == 5) {
+                if (specified) {
+                    nOverride = Double.parseDouble(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((slopeHeader == null) || (areaHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster slopeImage = new WhiteboxRaster(slopeHeader, "r");
+            int rows = slopeImage.getNumberRows();
+            int cols = slopeImage.getNumberColumns();
+            double slopeNoData = slopeImage.getNoDataValue();
+
+            WhiteboxRaster areaImage = new WhiteboxRaster(areaHeader, "r");
+            if (areaImage.getNumberRows() != rows || areaImage.getNumberColumns() != cols) {
+                showFeedback("The input images must be of the same dimensions.");
+                return;
+            }
+            double areaNoData = areaImage.getNoDataValue();
+            double gridRes = (areaImage.getCellSizeX() + areaImage.getCellSizeY()) / 2.0;
+            double cellArea = gridRes * gridRes;
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", slopeHeader,
+                    WhiteboxRaster.DataType.FLOAT, slopeNoData);
+            output.setPreferredPalette("spectrum.pal");
+
+            long numSteep = 0;
+            double minArea = Double.POSITIVE_INFINITY;
+            double[] area;
+            double[] slope;
+            for (row = 0; row < rows; row++) {
+                area = areaImage.getRowValues(row);
+                slope = slopeImage.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    if (area[col] != areaNoData && slope[col] != slopeNoData) {
+                        if (slope[col] > 45) {
+                            numSteep++;
+                        }
+                        minArea = Math.min(minArea, area[col]);
+                        sinB = Math.sin(Math.toRadians(slope[col]));
+                        if (!desmetGovers) {
+                            // The slope-length exponent of the USLE slope classes
+                            // (Wischmeier and Smith, 1978).
+                            percentSlope = 100 * Math.tan(Math.toRadians(slope[col]));
+                            if (!Double.isNaN(mOverride)) {
+                                m = mOverride;
+                            } else if (percentSlope < 1) {
+                                m = 0.2;
+                            } else if (percentSlope < 3.5) {
+                                m = 0.3;
+                            } else if (percentSlope < 5) {
+                                m = 0.4;
+                            } else {
+                                m = 0.5;
+                            }
+                            l = Math.pow(Math.max(area[col], 0) / 22.13, m);
+                            s = Math.pow(sinB / 0.0896, Double.isNaN(nOverride) ? 1.3 : nOverride);
+                        } else {
+                            // The slope-length exponent depends on the ratio of rill
+                            // to interrill erosion (McCool et al., 1989).
+                            if (!Double.isNaN(mOverride)) {
+                                m = mOverride;
+                            } else {
+                                double beta = (sinB / 0.0896) / (3 * Math.pow(sinB, 0.8) + 0.56);
+                                m = beta / (1 + beta);
+                            }
+                            /*
+                             * The unit contributing area of Desmet and Govers
+                             * (1996). The area draining into the cell is the
+                             * contributing area of the cell, i.e. the SCA times the
+                             * contour width, less the area of the cell itself. Since
+                             * the SCA is calculated by a flow accumulation
+                             * algorithm, convergent and divergent flow is accounted
+                             * for by the routing of the contributing area.
+                             */
+                            double inflow = Math.max(area[col] * gridRes - cellArea, 0);
+                            l = (Math.pow(inflow + cellArea, m + 1) - Math.pow(inflow, m + 1))
+                                    / (Math.pow(gridRes, m + 2) * Math.pow(22.13, m));
+                            if (!Double.isNaN(nOverride)) {
+                                s = Math.pow(sinB / 0.0896, nOverride);
+                            } else if (Math.tan(Math.toRadians(slope[col])) < 0.09) {
+                                s = 10.8 * sinB + 0.03;
+                            } else {
+                                s = 16.8 * sinB - 0.50;
+                            }
+                        }
+                        z = l * s;
+                        output.setValue(row, col, z);
+                    } else {
+                        output.setValue(row, col, slopeNoData);
+                    }
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int)(100f * row / (rows - 1));
+                updateProgress(progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Method: " + (desmetGovers ? "Desmet and Govers (1996)" : "USLE slope classes"));
+            if (!Double.isNaN(mOverride)) {
+                output.addMetadataEntry("Slope-length exponent (m): " + mOverride);
+            }
+            if (!Double.isNaN(nOverride)) {
+                output.addMetadataEntry("Slope exponent (n): " + nOverride);
+            }
+
+            slopeImage.close();
+            areaImage.close();
+            output.close();
+
+            // Check for inputs that are likely in the wrong units. The SCA of a
+            // cell with no upslope area is the grid resolution.
+            StringBuilder warnings = new StringBuilder();
+            if (numSteep > 0) {
+                warnings.append(numSteep).append(" grid cells have slopes greater than 45 degrees. ")
+                        .append("Check that the slope raster is in degrees rather than percent.\n");
+            }
+            boolean geographic = areaImage.getXYUnits().toLowerCase().contains("deg");
+            if (minArea < 0) {
+                warnings.append("The SCA raster contains negative values and may be log-transformed. ")
+                        .append("The SCA must not be log-transformed.\n");
+            } else if (!geographic && gridRes != 1 && Math.abs(minArea - 1) < 1e-6) {
+                warnings.append("The SCA raster appears to contain the number of upslope grid cells ")
+                        .append("rather than the specific contributing area.\n");
+            } else if (!geographic && gridRes != 1 && Math.abs(minArea - cellArea) < 1e-6 * cellArea) {
+                warnings.append("The SCA raster appears to contain the total contributing area ")
+                        .append("rather than the specific contributing area.\n");
+            } else if (!geographic && minArea < 0.5 * gridRes) {
+                warnings.append("Some SCA values are smaller than the grid resolution. Check that the ")
+                        .append("SCA raster is the specific contributing area, in metres.\n");
+            }
+            if (warnings.length() > 0) {
+                myHost.logMessage(Level.WARNING, getDescriptiveName() + ": " + warnings.toString().trim());
+                showFeedback("Warning: " + warnings.toString().trim());
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,372 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+package plugins;
+
+import java.io.File;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the factor of safety against shallow landsliding using
+ * the infinite slope stability model.
+ */
+public class MassWastingSusceptibility implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "MassWastingSusceptibility";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Mass Wasting Susceptibility";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the infinite-slope factor of safety, where values below 1 indicate slope failure.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SecondaryTerrainAttributes" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+        
+        String slopeHeader = null;
+        String cohesionHeader = null;
+        String frictionHeader = null;
+        String depthHeader = null;
+        double depthConstant = 0;
+        double unitWeight = 0;
+        String outputHeader = null;
+        String waterTableHeader = null;
+        int i;
+        int progress;
+        int row, col;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                slopeHeader = args[i];
+            } else if (i == 1) {
+                cohesionHeader = args[i];
+            } else if (i == 2) {
+                frictionHeader = args[i];
+            } else if (i == 3) {
+                // the regolith depth may be either a raster or a constant value
+                File file = new File(args[i]);
+                if (file.exists()) {
+                    depthHeader = args[i];
+                } else {
+                    depthConstant = Double.parseDouble(file.getName().replace(".dep", ""));
+                }
+            } else if (i == 4) {
+                unitWeight = Double.parseDouble(args[i]);
+            } else if (i == 5) {
+                outputHeader = args[i];
+            } else if (i == 6 && !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified")) {
+                waterTableHeader = args[i];
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((slopeHeader == null) || (cohesionHeader == null) || (frictionHeader == null)
+                || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (unitWeight <= 0) {
+            showFeedback("The soil unit weight must be greater than zero.");
+            return;
+        }
+
+        try {
+            // unit weights in N/m^3, so that they are consistent with a
+            // cohesion measured in Pa
+            double gamma = unitWeight * 1000;
+            final double gammaWater = 9810;
+            
+            WhiteboxRaster slopeImage = new WhiteboxRaster(slopeHeader, "r");
+            int rows = slopeImage.getNumberRows();
+            int cols = slopeImage.getNumberColumns();
+            double slopeNoData = slopeImage.getNoDataValue();
+            
+            String[] inputHeaders = {cohesionHeader, frictionHeader, depthHeader, waterTableHeader};
+            WhiteboxRaster[] inputs = new WhiteboxRaster[inputHeaders.length];
+            double[] noDataValues = new double[inputHeaders.length];
+            for (i = 0; i < inputHeaders.length; i++) {
+                if (inputHeaders[i] != null) {
+                    inputs[i] = new WhiteboxRaster(inputHeaders[i], "r");
+                    if (inputs[i].getNumberRows() != rows || inputs[i].getNumberColumns() != cols) {
+                        showFeedback("The input images must be of the same dimensions.");
+                        return;
+                    }
+                    noDataValues[i] = inputs[i].getNoDataValue();
+                }
+            }
+                
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", slopeHeader, 
+                    WhiteboxRaster.DataType.FLOAT, slopeNoData);
+            output.setPreferredPalette("spectrum.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits("dimensionless");
+
+            double[] slope;
+            double[][] data = new double[inputHeaders.length][];
+            double[] outData = new double[cols];
+            double beta, phi, c, z, m, normalStress, shearStress;
+            long numValid = 0;
+            long numUnstable = 0;
+            for (row = 0; row < rows; row++) {
+                slope = slopeImage.getRowValues(row);
+                for (i = 0; i < inputHeaders.length; i++) {
+                    if (inputs[i] != null) {
+                        data[i] = inputs[i].getRowValues(row);
+                    }
+                }
+                for (col = 0; col < cols; col++) {
+                    outData[col] = slopeNoData;
+                    if (slope[col] == slopeNoData || data[0][col] == noDataValues[0]
+                            || data[1][col] == noDataValues[1]) {
+                        continue;
+                    }
+                    if (inputs[2] != null) {
+                        if (data[2][col] == noDataValues[2]) {
+                            continue;
+                        }
+                        z = data[2][col];
+                    } else {
+                        z = depthConstant;
+                    }
+                    m = 0;
+                    if (inputs[3] != null) {
+                        if (data[3][col] == noDataValues[3]) {
+                            continue;
+                        }
+                        // the saturated fraction of the regolith
+                        m = z > 0 ? Math.max(0, Math.min(1, data[3][col] / z)) : 0;
+                    }
+                    beta = Math.toRadians(slope[col]);
+                    phi = Math.toRadians(data[1][col]);
+                    c = data[0][col];
+                    shearStress = gamma * z * Math.sin(beta) * Math.cos(beta);
+                    if (shearStress <= 0) {
+                        // flat or without regolith; it cannot fail
+                        continue;
+                    }
+                    normalStress = (gamma - m * gammaWater) * z * Math.cos(beta) * Math.cos(beta);
+                    outData[col] = (c + normalStress * Math.tan(phi)) / shearStress;
+                    numValid++;
+                    if (outData[col] < 1) {
+                        numUnstable++;
+                    }
+                }
+                output.setRowValues(row, outData);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int)(100f * row / (rows - 1));
+                updateProgress(progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Soil unit weight: " + unitWeight + " kN/m^3");
+            if (depthHeader == null) {
+                output.addMetadataEntry("Regolith depth: " + depthConstant + " m");
+            }
+            output.addMetadataEntry("Unstable cells (FS < 1): " + numUnstable + " of " + numValid);
+            output.setDisplayMinimum(0);
+            output.setDisplayMaximum(2);
+
+            slopeImage.close();
+            for (WhiteboxRaster input : inputs) {
+                if (input != null) {
+                    input.close();
+                }
+            }
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,424 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the SAGA wetness index, a topographic wetness index
+ * based on a slope-modified specific catchment area, from a digital elevation
+ * model (DEM).
+ */
+public class SAGAWetnessIndex implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SAGAWetnessIndex";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "SAGA Wetness Index";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the SAGA wetness index from a DEM using a slope-modified specific catchment area.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SecondaryTerrainAttributes" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+        
+        String demHeader = null;
+        String outputHeader = null;
+        double slopeThreshold = 0;
+        double zConvFactor = 1;
+        int i;
+        int progress;
+        int row, col;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                demHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                slopeThreshold = Double.parseDouble(args[i]);
+            } else if (i == 3 && !args[i].toLowerCase().contains("not specified")) {
+                zConvFactor = Double.parseDouble(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((demHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (slopeThreshold < 0 || slopeThreshold >= 90) {
+            showFeedback("The slope threshold must be between 0 and 90 degrees.");
+            return;
+        }
+
+        try {
+            int[] dX = {1, 1, 1, 0, -1, -1, -1, 0};
+            int[] dY = {-1, 0, 1, 1, 1, 0, -1, -1};
+            // the exponent of the Freeman (1991) multiple flow direction algorithm
+            final double mfdExponent = 1.1;
+            
+            WhiteboxRaster dem = new WhiteboxRaster(demHeader, "r");
+            int rows = dem.getNumberRows();
+            int cols = dem.getNumberColumns();
+            double noData = dem.getNoDataValue();
+            double cellSizeX = dem.getCellSizeX();
+            double cellSizeY = dem.getCellSizeY();
+            
+            if (dem.getXYUnits().toLowerCase().contains("deg") || 
+                    dem.getProjection().toLowerCase().contains("geog")) {
+                // calculate a new z-conversion factor
+                double midLat = (dem.getNorth() + dem.getSouth()) / 2.0;
+                if (midLat <= 90 && midLat >= -90) {
+                    zConvFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)));
+                }
+            }
+            
+            double[] dist = new double[8];
+            for (i = 0; i < 8; i++) {
+                dist[i] = Math.sqrt(dX[i] * cellSizeX * dX[i] * cellSizeX 
+                        + dY[i] * cellSizeY * dY[i] * cellSizeY);
+            }
+            
+            double[][] z = new double[rows][cols];
+            for (row = 0; row < rows; row++) {
+                double[] data = dem.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z[row][col] = data[col] != noData ? data[col] * zConvFactor : noData;
+                }
+            }
+            dem.close();
+            
+            // Calculate the slope, in radians, using the same method as the
+            // Slope tool, and count the inflowing neighbours of each cell.
+            updateProgress("Calculating slope:", 0);
+            double[][] slope = new double[rows][cols];
+            byte[][] numInflowing = new byte[rows][cols];
+            double[] N = new double[8];
+            double fx, fy, zN;
+            int rowN, colN;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (z[row][col] == noData) {
+                        continue;
+                    }
+                    for (i = 0; i < 8; i++) {
+                        rowN = row + dY[i];
+                        colN = col + dX[i];
+                        if (rowN >= 0 && rowN < rows && colN >= 0 && colN < cols
+                                && z[rowN][colN] != noData) {
+                            N[i] = z[rowN][colN];
+                            if (N[i] > z[row][col]) {
+                                numInflowing[row][col]++;
+                            }
+                        } else {
+                            N[i] = z[row][col];
+                        }
+                    }
+                    fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / (8 * cellSizeY);
+                    fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / (8 * cellSizeX);
+                    slope[row][col] = Math.atan(Math.sqrt(fx * fx + fy * fy));
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int)(100f * row / (rows - 1));
+                updateProgress("Calculating slope:", progress);
+            }
+            
+            // Accumulate the upslope area using the multiple flow direction
+            // algorithm, visiting each cell once all of its upslope neighbours
+            // have been visited.
+            updateProgress("Calculating catchment area:", 0);
+            double[][] area = new double[rows][cols];
+            int[] stack = new int[rows * cols];
+            int stackSize = 0;
+            long numValidCells = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (z[row][col] != noData) {
+                        area[row][col] = cellSizeX * cellSizeY;
+                        numValidCells++;
+                        if (numInflowing[row][col] == 0) {
+                            stack[stackSize] = row * cols + col;
+                            stackSize++;
+                        }
+                    }
+                }
+            }
+            double[] weights = new double[8];
+            double totalWeight;
+            long numSolved = 0;
+            int oldProgress = -1;
+            while (stackSize > 0) {
+                stackSize--;
+                row = stack[stackSize] / cols;
+                col = stack[stackSize] % cols;
+                totalWeight = 0;
+                for (i = 0; i < 8; i++) {
+                    weights[i] = 0;
+                    rowN = row + dY[i];
+                    colN = col + dX[i];
+                    if (rowN >= 0 && rowN < rows && colN >= 0 && colN < cols) {
+                        zN = z[rowN][colN];
+                        if (zN != noData && zN < z[row][col]) {
+                            weights[i] = Math.pow((z[row][col] - zN) / dist[i], mfdExponent);
+                            totalWeight += weights[i];
+                        }
+                    }
+                }
+                for (i = 0; i < 8; i++) {
+                    if (weights[i] > 0) {
+                        rowN = row + dY[i];
+                        colN = col + dX[i];
+                        area[rowN][colN] += area[row][col] * weights[i] / totalWeight;
+                        numInflowing[rowN][colN]--;
+                        if (numInflowing[rowN][colN] == 0) {
+                            stack[stackSize] = rowN * cols + colN;
+                            stackSize++;
+                        }
+                    }
+                }
+                numSolved++;
+                progress = (int)(100f * numSolved / numValidCells);
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress("Calculating catchment area:", progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", demHeader, 
+                    WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("blueyellow.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits("dimensionless");
+
+            double thresholdRad = Math.toRadians(slopeThreshold);
+            double[] outData = new double[cols];
+            double sca, s;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    s = slope[row][col];
+                    if (z[row][col] != noData && s > 0) {
+                        // the specific catchment area, i.e. the upslope area 
+                        // per unit contour width
+                        sca = area[row][col] / cellSizeX;
+                        if (s > thresholdRad) {
+                            sca = sca / (2 * cellSizeX * Math.sin(s));
+                        }
+                        outData[col] = Math.log(sca / Math.tan(s));
+                    } else {
+                        outData[col] = noData;
+                    }
+                }
+                output.setRowValues(row, outData);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int)(100f * row / (rows - 1));
+                updateProgress("Saving data:", progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Slope threshold: " + slopeThreshold + " degrees");
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
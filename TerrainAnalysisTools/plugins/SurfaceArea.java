@@ -0,0 +1,407 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.text.DecimalFormat;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
+
+/**
+ * This tool calculates the surface area of each grid cell in a DEM, accounting
+ * for terrain relief, or the ratio of the surface area to the planimetric area.
+ */
+public class SurfaceArea implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SurfaceArea";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Surface Area";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the surface area of each grid cell in a DEM, accounting "
+                + "for terrain relief, or the ratio of surface area to planimetric area.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SurfDerivatives" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        boolean outputRatio = false;
+        double zFactor = 1;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        inputHeader = args[0];
+        outputHeader = args[1];
+        outputRatio = Boolean.parseBoolean(args[2]);
+        zFactor = Double.parseDouble(args[3]);
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
+
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double conversionFactor = zFactor;
+            final boolean ratio = outputRatio;
+
+            // the cell sizes of each row, in the units of the elevations. The
+            // cells of a geographic DEM are converted from degrees to metres,
+            // rather than the elevations to degrees, so that areas are in
+            // square metres.
+            final double[] gridResX = new double[rows];
+            final double[] gridResY = new double[rows];
+            boolean geographic = DEM.getXYUnits().toLowerCase().contains("deg")
+                    || DEM.getProjection().toLowerCase().contains("geog");
+            for (int row = 0; row < rows; row++) {
+                gridResX[row] = DEM.getCellSizeX();
+                gridResY[row] = DEM.getCellSizeY();
+                if (geographic) {
+                    double lat = DEM.getNorth() - (row + 0.5) * (DEM.getNorth() - DEM.getSouth()) / rows;
+                    if (lat <= 90 && lat >= -90) {
+                        gridResX[row] *= 113200 * Math.cos(Math.toRadians(lat));
+                        gridResY[row] *= 113200;
+                    }
+                }
+            }
+
+            final double[][] elevations = new double[rows][];
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
+            }
+
+            final WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("spectrum.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits(ratio ? "dimensionless" : (geographic ? "square metres" : "square " + DEM.getXYUnits()));
+
+            // the surface and planimetric areas of the valid cells of each row
+            final double[] rowSurfaceArea = new double[rows];
+            final double[] rowPlanimetricArea = new double[rows];
+
+            // the diagonal neighbours, NE, SE, SW and NW, in order around the cell
+            final int[] dX = new int[]{1, 1, -1, -1};
+            final int[] dY = new int[]{-1, 1, 1, -1};
+            boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    double[] N = new double[4];
+                    double dx = gridResX[row];
+                    double dy = gridResY[row];
+                    double planimetricArea = dx * dy;
+                    for (int col = 0; col < cols; col++) {
+                        double z = elevations[row][col];
+                        if (z != noData) {
+                            for (int i = 0; i < 4; i++) {
+                                N[i] = estimateNeighbour(elevations, row, col, dY[i], dX[i], noData) * conversionFactor;
+                            }
+                            z = z * conversionFactor;
+
+                            // the four triangular facets joining the cell
+                            // centre to each pair of adjacent diagonal
+                            // neighbours. Together they cover four times the
+                            // area of the cell.
+                            double area = 0;
+                            for (int i = 0; i < 4; i++) {
+                                int j = (i + 1) % 4;
+                                double ax = dX[i] * dx;
+                                double ay = -dY[i] * dy;
+                                double az = N[i] - z;
+                                double bx = dX[j] * dx;
+                                double by = -dY[j] * dy;
+                                double bz = N[j] - z;
+                                double cx = ay * bz - az * by;
+                                double cy = az * bx - ax * bz;
+                                double cz = ax * by - ay * bx;
+                                area += 0.5 * Math.sqrt(cx * cx + cy * cy + cz * cz);
+                            }
+                            area /= 4;
+
+                            rowSurfaceArea[row] += area;
+                            rowPlanimetricArea[row] += planimetricArea;
+                            data[col] = ratio ? area / planimetricArea : area;
+                        } else {
+                            data[col] = noData;
+                        }
+                    }
+                }
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    output.setRowValues(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
+                }
+            });
+
+            if (!completed) {
+                DEM.close();
+                output.close();
+                cancelOperation();
+                return;
+            }
+
+            double totalSurfaceArea = 0;
+            double totalPlanimetricArea = 0;
+            for (int row = 0; row < rows; row++) {
+                totalSurfaceArea += rowSurfaceArea[row];
+                totalPlanimetricArea += rowPlanimetricArea[row];
+            }
+            double totalRatio = totalPlanimetricArea > 0 ? totalSurfaceArea / totalPlanimetricArea : 1;
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Values are the " + (ratio ? "ratio of surface area to planimetric area"
+                    : "surface area") + " of each cell");
+            output.addMetadataEntry("Total surface area: " + totalSurfaceArea);
+            output.addMetadataEntry("Total planimetric area: " + totalPlanimetricArea);
+
+            DEM.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+            DecimalFormat df = new DecimalFormat("###,###,###,##0.000");
+            String retstr = "SURFACE AREA\n";
+            retstr += "\nTotal surface area:\t" + df.format(totalSurfaceArea);
+            retstr += "\nTotal planimetric area:\t" + df.format(totalPlanimetricArea);
+            retstr += "\nSurface area ratio:\t" + new DecimalFormat("0.000000").format(totalRatio);
+            returnData(retstr);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    private static double getNeighbour(double[][] elevations, int row, int col, double noData) {
+        if (row < 0 || row >= elevations.length || col < 0 || col >= elevations[row].length
+                || elevations[row][col] == noData) {
+            return Double.NaN;
+        }
+        return elevations[row][col];
+    }
+
+    /**
+     * Returns the elevation of the diagonal neighbour of a cell. A missing
+     * neighbour, beyond the edge of the DEM or NoData, is extrapolated from
+     * the opposite neighbour, or else from the two neighbours beside it, so
+     * that the facets of an edge cell on a plane lie in the plane. Where
+     * neither is possible, the neighbour is level with the cell.
+     */
+    private static double estimateNeighbour(double[][] elevations, int row, int col,
+            int dRow, int dCol, double noData) {
+        double z = elevations[row][col];
+        double value = getNeighbour(elevations, row + dRow, col + dCol, noData);
+        if (!Double.isNaN(value)) {
+            return value;
+        }
+        double opposite = getNeighbour(elevations, row - dRow, col - dCol, noData);
+        if (!Double.isNaN(opposite)) {
+            return 2 * z - opposite;
+        }
+        double zRow = getNeighbour(elevations, row + dRow, col, noData);
+        if (Double.isNaN(zRow)) {
+            opposite = getNeighbour(elevations, row - dRow, col, noData);
+            zRow = Double.isNaN(opposite) ? z : 2 * z - opposite;
+        }
+        double zCol = getNeighbour(elevations, row, col + dCol, noData);
+        if (Double.isNaN(zCol)) {
+            opposite = getNeighbour(elevations, row, col - dCol, noData);
+            zCol = Double.isNaN(opposite) ? z : 2 * z - opposite;
+        }
+        return zRow + zCol - z;
+    }
+}
@@ -0,0 +1,437 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.text.DecimalFormat;
+import java.util.ArrayList;
+import java.util.Date;
+import whitebox.algorithms.DirectionalRay;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
+
+/**
+ * This tool calculates the wind shelter index (Sx) of Winstral et al. (2002), the
+ * mean of the maximum upward angles to the terrain within a search distance across a
+ * sector of directions centred on the wind direction.
+ */
+public class WindShelterIndex implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "WindShelterIndex";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Wind Shelter Index";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the topographic shelter from, or exposure to, the wind (Winstral Sx).";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "WindRelatedTAs" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        double[] azimuths = null;
+        double halfWidth = 15;
+        double maxDist = 0;
+        double sectorStep = 5;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                String[] azimuthStrings = args[i].split(",");
+                azimuths = new double[azimuthStrings.length];
+                for (int a = 0; a < azimuthStrings.length; a++) {
+                    azimuths[a] = Double.parseDouble(azimuthStrings[a].trim()) % 360;
+                    if (azimuths[a] < 0) {
+                        azimuths[a] += 360;
+                    }
+                }
+            } else if (i == 3) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    halfWidth = Double.parseDouble(args[i]);
+                }
+            } else if (i == 4) {
+                maxDist = Double.parseDouble(args[i]);
+            } else if (i == 5) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    sectorStep = Double.parseDouble(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null) || (azimuths == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (maxDist <= 0) {
+            showFeedback("The search distance must be greater than zero.");
+            return;
+        }
+        if (halfWidth < 0 || halfWidth >= 180) {
+            showFeedback("The sector half-width must be between 0 and 180 degrees.");
+            return;
+        }
+        if (sectorStep <= 0) {
+            showFeedback("The sector increment must be greater than zero.");
+            return;
+        }
+
+        try {
+            final int numWinds = azimuths.length;
+
+            // The search directions are multiples of the sector increment
+            // that fall within each wind's sector, so that directions that are
+            // shared by the sectors of several winds are scanned only once.
+            ArrayList<Double> directions = new ArrayList<>();
+            ArrayList<boolean[]> directionWinds = new ArrayList<>();
+            int[] directionsPerWind = new int[numWinds];
+            for (int w = 0; w < numWinds; w++) {
+                ArrayList<Double> sector = new ArrayList<>();
+                long first = (long) Math.ceil((azimuths[w] - halfWidth) / sectorStep - 1e-9);
+                long last = (long) Math.floor((azimuths[w] + halfWidth) / sectorStep + 1e-9);
+                for (long k = first; k <= last; k++) {
+                    double direction = (k * sectorStep) % 360;
+                    if (direction < 0) {
+                        direction += 360;
+                    }
+                    sector.add(direction);
+                }
+                if (sector.isEmpty()) {
+                    // the sector is narrower than the increment
+                    sector.add(azimuths[w]);
+                }
+                for (double direction : sector) {
+                    int d = -1;
+                    for (int j = 0; j < directions.size(); j++) {
+                        if (Math.abs(directions.get(j) - direction) < 1e-9) {
+                            d = j;
+                            break;
+                        }
+                    }
+                    if (d < 0) {
+                        directions.add(direction);
+                        directionWinds.add(new boolean[numWinds]);
+                        d = directions.size() - 1;
+                    }
+                    if (!directionWinds.get(d)[w]) {
+                        directionWinds.get(d)[w] = true;
+                        directionsPerWind[w]++;
+                    }
+                }
+            }
+
+            WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double cellSizeX = DEM.getCellSizeX();
+            final double cellSizeY = DEM.getCellSizeY();
+            final double searchDist = maxDist;
+
+            final double[][] elevations = new double[rows][];
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
+            }
+
+            // the sum of the maximum upward angles over the sector directions,
+            // and the number of directions with at least one cell, for each wind
+            final float[][][] angleSums = new float[numWinds][rows][cols];
+            final short[][][] angleCounts = new short[numWinds][rows][cols];
+
+            final int numDirections = directions.size();
+            for (int d = 0; d < numDirections; d++) {
+                final double direction = directions.get(d);
+                final boolean[] winds = directionWinds.get(d);
+                final String label = "Direction " + (d + 1) + " of " + numDirections + ":";
+                // the rows hold the maximum upward angle in this direction,
+                // or NaN where there is no cell to compare with, and are
+                // added to the sums in row order
+                boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
+
+                    @Override
+                    public void run(int row, double[] data) {
+                        DirectionalRay ray = new DirectionalRay(elevations, noData,
+                                cellSizeX, cellSizeY, direction, searchDist);
+                        for (int col = 0; col < cols; col++) {
+                            data[col] = Double.NaN;
+                            double z = elevations[row][col];
+                            if (z == noData) {
+                                continue;
+                            }
+                            double maxSlope = Double.NEGATIVE_INFINITY;
+                            ray.start(row, col);
+                            while (ray.next()) {
+                                double slope = (ray.getValue() - z) / ray.getDistance();
+                                if (slope > maxSlope) {
+                                    maxSlope = slope;
+                                }
+                            }
+                            if (maxSlope > Double.NEGATIVE_INFINITY) {
+                                data[col] = (float) Math.toDegrees(Math.atan(maxSlope));
+                            }
+                        }
+                    }
+                }, new Parallel.RowSink() {
+
+                    @Override
+                    public boolean accept(int row, double[] data) {
+                        for (int col = 0; col < cols; col++) {
+                            if (!Double.isNaN(data[col])) {
+                                for (int w = 0; w < numWinds; w++) {
+                                    if (winds[w]) {
+                                        angleSums[w][row][col] += (float) data[col];
+                                        angleCounts[w][row][col]++;
+                                    }
+                                }
+                            }
+                        }
+                        if (cancelOp) {
+                            return false;
+                        }
+                        updateProgress(label, (int) (100f * (row + 1) / rows));
+                        return true;
+                    }
+                });
+
+                if (!completed) {
+                    DEM.close();
+                    cancelOperation();
+                    return;
+                }
+            }
+
+            // write the outputs, one per wind direction
+            DecimalFormat df = new DecimalFormat("0.###");
+            String[] outputFiles = new String[numWinds];
+            double[] data = new double[cols];
+            for (int w = 0; w < numWinds; w++) {
+                if (numWinds == 1) {
+                    outputFiles[w] = outputHeader;
+                } else {
+                    outputFiles[w] = outputHeader.replace(".dep", "_" + df.format(azimuths[w]) + ".dep");
+                }
+                WhiteboxRaster output = new WhiteboxRaster(outputFiles[w], "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+                output.setPreferredPalette("blue_white_red.pal");
+                output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+                output.setZUnits("degrees");
+                for (int row = 0; row < rows; row++) {
+                    for (int col = 0; col < cols; col++) {
+                        // cells without any upwind cells, e.g. along the
+                        // upwind edge of the DEM, are assigned NoData
+                        if (elevations[row][col] != noData && angleCounts[w][row][col] > 0) {
+                            data[col] = angleSums[w][row][col] / angleCounts[w][row][col];
+                        } else {
+                            data[col] = noData;
+                        }
+                    }
+                    output.setRowValues(row, data);
+                }
+
+                output.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                output.addMetadataEntry("Created on " + new Date());
+                output.addMetadataEntry("Wind azimuth: " + azimuths[w] + " degrees clockwise from north "
+                        + "(the direction from which the wind blows)");
+                output.addMetadataEntry("Sector half-width: " + halfWidth + " degrees, sampled "
+                        + "in " + directionsPerWind[w] + " directions");
+                output.addMetadataEntry("Search distance: " + maxDist);
+                output.addMetadataEntry("Positive values are sheltered, negative values are exposed.");
+                output.close();
+            }
+
+            DEM.close();
+
+            // returning a header file string displays the image.
+            for (String outputFile : outputFiles) {
+                returnData(outputFile);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,382 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.ArrayList;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.shapefile.Geometry;
+import whitebox.geospatialfiles.shapefile.PolyLine;
+import whitebox.geospatialfiles.shapefile.PolyLineM;
+import whitebox.geospatialfiles.shapefile.PolyLineZ;
+import whitebox.geospatialfiles.shapefile.Polygon;
+import whitebox.geospatialfiles.shapefile.PolygonM;
+import whitebox.geospatialfiles.shapefile.PolygonZ;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool inserts vertices into the lines or polygons of a vector file so 
+ * that no segment is longer than a specified maximum length. The existing 
+ * vertices and feature attributes are copied to the output unchanged.
+ */
+public class DensifyGeometry implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "DensifyGeometry";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Densify Geometry";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Inserts vertices into lines or polygons so that no segment exceeds a maximum length";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "VectorTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+  
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+   
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        
+        amIActive = true;
+        String inputFile;
+        String outputFile;
+        int progress;
+        int i, j, part, start, end, numSegments;
+        int featureNum, numFeatures;
+        double maxSegmentLength, length, t;
+        ShapeType shapeType;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        inputFile = args[0];
+        outputFile = args[1];
+        maxSegmentLength = Double.parseDouble(args[2]);
+        
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (maxSegmentLength <= 0) {
+            showFeedback("The maximum segment length must be greater than zero.");
+            return;
+        }
+
+        try {
+            // set up the input shapefile.
+            ShapeFile input = new ShapeFile(inputFile);
+            shapeType = input.getShapeType();
+            
+            // make sure that the shapetype is either a flavour of polyline or polygon.
+            if (shapeType.getBaseType() != ShapeType.POLYLINE && shapeType.getBaseType() != ShapeType.POLYGON) {
+                showFeedback("This tool only works with shapefiles of a polyline or polygon base shape type.");
+                return;
+            }
+            
+            // the attributes of each feature are copied to the output unchanged.
+            AttributeTable table = input.getAttributeTable();
+            ShapeFile output = new ShapeFile(outputFile, shapeType, table.getAllFields());
+            output.setProjectionStringFromOtherShapefile(input);
+            
+            numFeatures = input.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : input.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object[] attData = table.getRecord(record.getRecordNumber() - 1);
+                    Geometry geometry = record.getGeometry();
+                    double[][] points = geometry.getPoints();
+                    int[] parts = geometry.getParts();
+                    double[] zArray = getZArray(geometry);
+                    double[] mArray = getMArray(geometry);
+                    
+                    // new vertices are placed at equal intervals along each 
+                    // segment that is longer than the maximum length, with 
+                    // z and measure values interpolated linearly.
+                    ArrayList<double[]> outputPoints = new ArrayList<>();
+                    int[] outputParts = new int[parts.length];
+                    for (part = 0; part < parts.length; part++) {
+                        start = parts[part];
+                        end = (part < parts.length - 1) ? parts[part + 1] : points.length;
+                        outputParts[part] = outputPoints.size();
+                        for (i = start; i < end; i++) {
+                            if (i > start) {
+                                length = Math.sqrt((points[i][0] - points[i - 1][0]) * (points[i][0] - points[i - 1][0])
+                                        + (points[i][1] - points[i - 1][1]) * (points[i][1] - points[i - 1][1]));
+                                numSegments = (int) Math.ceil(length / maxSegmentLength);
+                                for (j = 1; j < numSegments; j++) {
+                                    t = (double) j / numSegments;
+                                    outputPoints.add(new double[]{
+                                        points[i - 1][0] + t * (points[i][0] - points[i - 1][0]),
+                                        points[i - 1][1] + t * (points[i][1] - points[i - 1][1]),
+                                        zArray[i - 1] + t * (zArray[i] - zArray[i - 1]),
+                                        mArray[i - 1] + t * (mArray[i] - mArray[i - 1])});
+                                }
+                            }
+                            outputPoints.add(new double[]{points[i][0], points[i][1], zArray[i], mArray[i]});
+                        }
+                    }
+                    
+                    int numPoints = outputPoints.size();
+                    double[][] xy = new double[numPoints][2];
+                    double[] z = new double[numPoints];
+                    double[] m = new double[numPoints];
+                    for (i = 0; i < numPoints; i++) {
+                        double[] p = outputPoints.get(i);
+                        xy[i][0] = p[0];
+                        xy[i][1] = p[1];
+                        z[i] = p[2];
+                        m[i] = p[3];
+                    }
+                    
+                    switch (shapeType) {
+                        case POLYLINE:
+                            output.addRecord(new PolyLine(outputParts, xy), attData);
+                            break;
+                        case POLYLINEZ:
+                            output.addRecord(new PolyLineZ(outputParts, xy, z, m), attData);
+                            break;
+                        case POLYLINEM:
+                            output.addRecord(new PolyLineM(outputParts, xy, m), attData);
+                            break;
+                        case POLYGON:
+                            output.addRecord(new Polygon(outputParts, xy), attData);
+                            break;
+                        case POLYGONZ:
+                            output.addRecord(new PolygonZ(outputParts, xy, z, m), attData);
+                            break;
+                        case POLYGONM:
+                            output.addRecord(new PolygonM(outputParts, xy, m), attData);
+                            break;
+                    }
+                }
+                
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+            
+            output.write();
+            
+            // returning a header file string displays the image.
+            returnData(outputFile);
+            
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+       
+    }
+    
+    private double[] getZArray(Geometry geometry) {
+        if (geometry instanceof PolyLineZ) {
+            return ((PolyLineZ) geometry).getzArray();
+        } else if (geometry instanceof PolygonZ) {
+            return ((PolygonZ) geometry).getzArray();
+        }
+        return new double[geometry.getPoints().length];
+    }
+    
+    private double[] getMArray(Geometry geometry) {
+        if (geometry instanceof PolyLineZ) {
+            return ((PolyLineZ) geometry).getmArray();
+        } else if (geometry instanceof PolygonZ) {
+            return ((PolygonZ) geometry).getmArray();
+        } else if (geometry instanceof PolyLineM) {
+            return ((PolyLineM) geometry).getmArray();
+        } else if (geometry instanceof PolygonM) {
+            return ((PolygonM) geometry).getmArray();
+        }
+        return new double[geometry.getPoints().length];
+    }
+    
+//    // This method is only used during testing.
+//    public static void main(String[] args) {
+//        args = new String[3];
+//        args[0] = "/Users/johnlindsay/Documents/Data/tmp1.shp";
+//        args[1] = "/Users/johnlindsay/Documents/Data/tmp3.shp";
+//        args[2] = "10.0";
+//        
+//        DensifyGeometry densify = new DensifyGeometry();
+//        densify.setArgs(args);
+//        densify.run();
+//    }
+}
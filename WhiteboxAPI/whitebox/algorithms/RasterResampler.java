@@ -0,0 +1,279 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package whitebox.algorithms;
+
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
+
+/**
+ * This class estimates the value of a raster at arbitrary map coordinates
+ * using one of several separable interpolation kernels: nearest neighbour,
+ * bilinear, cubic convolution (Keys, 1981, with a = -0.5), and Lanczos
+ * (three lobes). It is used by the tools that resample images onto a new
+ * grid, e.g. Resample and Mosaic.
+ *
+ * Kernel taps that fall outside of the image take the value of the nearest
+ * edge cell. Taps that fall on NoData cells are handled according to the
+ * NoData policy, either invalidating the interpolated value or excluding
+ * the taps and renormalizing the kernel weights of the remaining valid taps.
+ * In either case, a point that falls within a NoData cell is NoData.
+ */
+public class RasterResampler {
+
+    public enum Method {
+
+        NEAREST_NEIGHBOUR, BILINEAR, CUBIC_CONVOLUTION, LANCZOS;
+    }
+
+    public enum NoDataPolicy {
+
+        INVALIDATE, RENORMALIZE;
+    }
+
+    // the cubic convolution kernel parameter
+    private static final double CUBIC_A = -0.5;
+    // the number of lobes of the Lanczos kernel
+    private static final int LANCZOS_LOBES = 3;
+
+    private final WhiteboxRaster image;
+    private final Method method;
+    private final NoDataPolicy noDataPolicy;
+    private final int rows;
+    private final int cols;
+    private final double noData;
+    private final double north;
+    private final double west;
+    private final double cellSizeX;
+    private final double cellSizeY;
+    private final int radius;
+    private final double[] weightsX;
+    private final double[] weightsY;
+
+    public RasterResampler(WhiteboxRaster image, Method method, NoDataPolicy noDataPolicy) {
+        this.image = image;
+        this.method = method;
+        this.noDataPolicy = noDataPolicy;
+        this.rows = image.getNumberRows();
+        this.cols = image.getNumberColumns();
+        this.noData = image.getNoDataValue();
+        this.north = image.getNorth();
+        this.west = image.getWest();
+        this.cellSizeX = (image.getEast() - image.getWest()) / cols;
+        this.cellSizeY = (image.getNorth() - image.getSouth()) / rows;
+        switch (method) {
+            case BILINEAR:
+                radius = 1;
+                break;
+            case CUBIC_CONVOLUTION:
+                radius = 2;
+                break;
+            case LANCZOS:
+                radius = LANCZOS_LOBES;
+                break;
+            default:
+                radius = 0;
+        }
+        weightsX = new double[2 * radius];
+        weightsY = new double[2 * radius];
+    }
+
+    /**
+     * Converts a resampling method name, as used in the tool dialogs, into a
+     * Method.
+     *
+     * @param name The method name, e.g. "nearest neighbour", "bilinear",
+     * "cubic convolution", or "lanczos".
+     * @return The Method, or null if the name is not recognized.
+     */
+    public static Method getMethodFromString(String name) {
+        String str = name.toLowerCase().trim();
+        if (str.contains("nearest")) {
+            return Method.NEAREST_NEIGHBOUR;
+        } else if (str.contains("bilinear")) {
+            return Method.BILINEAR;
+        } else if (str.contains("cubic")) {
+            return Method.CUBIC_CONVOLUTION;
+        } else if (str.contains("lanczos")) {
+            return Method.LANCZOS;
+        }
+        return null;
+    }
+
+    /**
+     * Converts a NoData policy name into a NoDataPolicy.
+     *
+     * @param name The policy name, either "invalidate" or "renormalize".
+     * @return The NoDataPolicy, or null if the name is not recognized.
+     */
+    public static NoDataPolicy getNoDataPolicyFromString(String name) {
+        String str = name.toLowerCase().trim();
+        if (str.startsWith("invalid")) {
+            return NoDataPolicy.INVALIDATE;
+        } else if (str.startsWith("renormali")) {
+            return NoDataPolicy.RENORMALIZE;
+        }
+        return null;
+    }
+
+    /**
+     * Interpolates the value of the image at a point.
+     *
+     * @param x The x-coordinate of the point.
+     * @param y The y-coordinate of the point.
+     * @return The interpolated value, or the image's NoData value if the point
+     * is outside of the image or cannot be interpolated.
+     */
+    public double getValue(double x, double y) {
+        // the fractional row and column, where whole numbers are cell centres
+        double row = (north - y) / cellSizeY - 0.5;
+        double col = (x - west) / cellSizeX - 0.5;
+        if (row < -0.5 || row > rows - 0.5 || col < -0.5 || col > cols - 0.5) {
+            return noData;
+        }
+
+        int nearestRow = clamp((int) Math.round(row), rows);
+        int nearestCol = clamp((int) Math.round(col), cols);
+        double zNearest = image.getValue(nearestRow, nearestCol);
+        if (zNearest == noData || method == Method.NEAREST_NEIGHBOUR) {
+            return zNearest;
+        }
+
+        int originRow = (int) Math.floor(row) - radius + 1;
+        int originCol = (int) Math.floor(col) - radius + 1;
+        int numTaps = 2 * radius;
+        double sumWeightsX = 0;
+        double sumWeightsY = 0;
+        for (int i = 0; i < numTaps; i++) {
+            weightsX[i] = kernel(col - (originCol + i));
+            weightsY[i] = kernel(row - (originRow + i));
+            sumWeightsX += weightsX[i];
+            sumWeightsY += weightsY[i];
+        }
+        // the Lanczos weights do not sum exactly to one
+        for (int i = 0; i < numTaps; i++) {
+            weightsX[i] /= sumWeightsX;
+            weightsY[i] /= sumWeightsY;
+        }
+
+        double z;
+        double w;
+        double sumWeights = 0;
+        double sumValues = 0;
+        for (int i = 0; i < numTaps; i++) {
+            int r = clamp(originRow + i, rows);
+            for (int j = 0; j < numTaps; j++) {
+                w = weightsY[i] * weightsX[j];
+                if (w == 0) {
+                    continue;
+                }
+                z = image.getValue(r, clamp(originCol + j, cols));
+                if (z != noData) {
+                    sumValues += w * z;
+                    sumWeights += w;
+                } else if (noDataPolicy == NoDataPolicy.INVALIDATE) {
+                    return noData;
+                }
+            }
+        }
+
+        /* When renormalizing, the weights of the valid taps may sum to a
+         * small or negative value because of the negative lobes of the
+         * cubic and Lanczos kernels, in which case the nearest value is
+         * used instead.
+         */
+        if (sumWeights < 0.5) {
+            return zNearest;
+        }
+        return sumValues / sumWeights;
+    }
+
+    /**
+     * Rounds and clamps an interpolated value so that it can be stored in an
+     * image of the specified data type. Cubic convolution and Lanczos kernels
+     * can overshoot the range of the input values, which would otherwise wrap
+     * around when stored as an integer. The result is never equal to the
+     * NoData value unless the input is.
+     *
+     * @param z The interpolated value.
+     * @param dataType The data type of the output image.
+     * @param noData The NoData value of the output image.
+     * @return The value to store.
+     */
+    public static double fitToDataType(double z, DataType dataType, double noData) {
+        if (z == noData) {
+            return z;
+        }
+        double min, max;
+        switch (dataType) {
+            case INTEGER:
+                min = Short.MIN_VALUE;
+                max = Short.MAX_VALUE;
+                break;
+            case BYTE:
+                min = 0;
+                max = 255;
+                break;
+            case I32:
+                min = Integer.MIN_VALUE;
+                max = Integer.MAX_VALUE;
+                break;
+            case FLOAT:
+                return Math.max(Math.min(z, Float.MAX_VALUE), -Float.MAX_VALUE);
+            default:
+                return z;
+        }
+        double ret = Math.max(Math.min(Math.round(z), max), min);
+        if (ret == noData) {
+            // step away from the NoData value, towards the interpolated value
+            if ((z >= noData && ret < max) || ret == min) {
+                ret++;
+            } else {
+                ret--;
+            }
+        }
+        return ret;
+    }
+
+    private double kernel(double d) {
+        double x = Math.abs(d);
+        switch (method) {
+            case BILINEAR:
+                return x < 1 ? 1 - x : 0;
+            case CUBIC_CONVOLUTION:
+                if (x <= 1) {
+                    return ((CUBIC_A + 2) * x - (CUBIC_A + 3)) * x * x + 1;
+                } else if (x < 2) {
+                    return ((CUBIC_A * x - 5 * CUBIC_A) * x + 8 * CUBIC_A) * x - 4 * CUBIC_A;
+                }
+                return 0;
+            case LANCZOS:
+                if (x == 0) {
+                    return 1;
+                } else if (x < LANCZOS_LOBES) {
+                    double px = Math.PI * x;
+                    return LANCZOS_LOBES * Math.sin(px) * Math.sin(px / LANCZOS_LOBES) / (px * px);
+                }
+                return 0;
+            default:
+                return x < 0.5 ? 1 : 0;
+        }
+    }
+
+    private static int clamp(int i, int n) {
+        return i < 0 ? 0 : (i >= n ? n - 1 : i);
+    }
+}
@@ -37,15 +37,28 @@
  */
 package whitebox.geospatialfiles;
 
+import java.io.EOFException;
 import java.io.File;
 import java.io.IOException;
+import java.io.InterruptedIOException;
 import java.io.PrintStream;
 import java.io.RandomAccessFile;
 import java.nio.*;
 import java.nio.channels.FileChannel;
 import java.util.ArrayList;
+import java.util.Arrays;
 import java.util.Collections;
 import java.util.List;
+import java.util.concurrent.Callable;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.Executors;
+import java.util.concurrent.Future;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+import java.util.zip.Inflater;
+import whitebox.parallel.Parallel;
+import whitebox.utilities.NamedThreadFactory;
 
 /**
  * Low level read/write geotiff files.
@@ -81,6 +94,7 @@ public class GeoTiff {
      * @throws java.io.IOException on io error
      */
     public void close() throws IOException {
+        finishBands();
         if (channel != null) {
             if (!readonly) {
                 channel.force(true);
@@ -449,18 +463,7 @@ public class GeoTiff {
             parseGeoInfo();
         }
 
-        nCols = getNumberColumns();
-        nRows = getNumberRows();
-        nodata = getNoData();
-        if (findTag(Tag.TileOffsets) != null) {
-            tiledFormat = true;
-            tileWidth = findTag(Tag.TileWidth).value[0];
-            tileLength = findTag(Tag.TileLength).value[0];
-            nTilesX = (nCols + tileWidth - 1) / tileWidth;
-            nTilesY = (nRows + tileLength - 1) / tileLength;
-        } else {
-            rowsPerStrip = findTag(Tag.RowsPerStrip).value[0];
-        }
+        initLayout();
     }
 
     private IFDEntry findTag(Tag tag) {
@@ -493,7 +496,8 @@ public class GeoTiff {
     }
 
     public int getSampleFormat() {
-        return findTag(Tag.SampleFormat).value[0];
+        // unsigned integers, if the tag is absent
+        return getTagValue(Tag.SampleFormat, 1);
     }
     
     public String getFileName() {
@@ -578,6 +582,53 @@ public class GeoTiff {
         return (findTag(Tag.GDALNoData) != null);
     }
 
+    /**
+     * Retrieves the scale factor of the first band from the GDAL metadata
+     * tag, which GDAL writes as an item with the role "scale". The real-world
+     * value of a pixel is its stored value multiplied by the scale factor,
+     * plus the offset.
+     *
+     * @return The scale factor, or 1 if the file has none.
+     */
+    public double getScale() {
+        return getGdalMetadataItem("scale", 1.0);
+    }
+
+    /**
+     * Retrieves the offset of the first band from the GDAL metadata tag,
+     * which GDAL writes as an item with the role "offset".
+     *
+     * @return The offset, or 0 if the file has none.
+     */
+    public double getOffset() {
+        return getGdalMetadataItem("offset", 0.0);
+    }
+
+    private static final Pattern GDAL_METADATA_ITEM = Pattern.compile(
+            "<Item\\s([^>]*)>([^<]*)</Item>", Pattern.CASE_INSENSITIVE);
+
+    // The GDAL metadata tag holds XML such as
+    // <GDALMetadata><Item name="SCALE" sample="0" role="scale">0.01</Item></GDALMetadata>
+    private double getGdalMetadataItem(String role, double defaultValue) {
+        IFDEntry entry = findTag(Tag.GDALMetadata);
+        if (entry == null || entry.valueS == null) {
+            return defaultValue;
+        }
+        Matcher m = GDAL_METADATA_ITEM.matcher(entry.valueS);
+        while (m.find()) {
+            String attributes = m.group(1).toLowerCase();
+            if (attributes.contains("role=\"" + role + "\"")
+                    && (!attributes.contains("sample=") || attributes.contains("sample=\"0\""))) {
+                try {
+                    return Double.parseDouble(m.group(2).trim());
+                } catch (NumberFormatException e) {
+                    return defaultValue;
+                }
+            }
+        }
+        return defaultValue;
+    }
+
     public int getPhotometricInterpretation() {
         if (findTag(Tag.PhotometricInterpretation) != null) {
             return findTag(Tag.PhotometricInterpretation).value[0];
@@ -590,10 +641,18 @@ public class GeoTiff {
         return findTag(Tag.Compression).value[0];
     }
 
-    double[] tileOrStripData;
+    // The image is read in bands of rows: a strip, a row of tiles, or, for
+    // uncompressed strips, a run of rows that is read with a single call.
+    // Each band is decoded straight into a buffer that is reused for later
+    // bands, as floats when every value of the image can be held exactly in a
+    // float (8- and 16-bit integers and 32-bit floats) and as doubles
+    // otherwise. Strips and tiles are compressed independently, so the bands
+    // of a compressed file that follow the one being read are decoded ahead on
+    // a thread pool.
+    private static final long MAX_UNCOMPRESSED_BAND_BYTES = 4L << 20;
+    private static final long MAX_PREFETCH_BYTES = 256L << 20;
+
     boolean tiledFormat = false;
-    int currentTile = -1;
-    int currentStrip = -1;
     int nRows = -1;
     int nCols = -1;
     int rowsPerStrip = -1;
@@ -602,568 +661,543 @@ public class GeoTiff {
     int nTilesX = -1;
     int nTilesY = -1;
     double nodata = -32768;
+    private int compression = TiffCodec.NONE;
+    private int predictor = 1;
+    private int sampleFormat = 1;
+    private int bytesPerSample = 1;
+    private int samplesPerPixel = 1;
+    private boolean rgb = false;
+    private boolean floatSamples = false;
+    private long[] blockOffsets;
+    private long[] blockByteCounts;
+    private String unsupportedReason = null;
+    private int bandRows;
+    private int numBands;
+    private Band[] bands = new Band[0];
+
+    private void initLayout() {
+        nCols = getNumberColumns();
+        nRows = getNumberRows();
+        nodata = getNoData();
+        compression = getTagValue(Tag.Compression, TiffCodec.NONE);
+        predictor = getTagValue(Tag.Predictor, 1);
+        sampleFormat = getTagValue(Tag.SampleFormat, 1);
+        samplesPerPixel = getTagValue(Tag.SamplesPerPixel, 1);
+        int bitsPerSample = getTagValue(Tag.BitsPerSample, 1);
+        bytesPerSample = bitsPerSample / 8;
+        rgb = getPhotometricInterpretation() == 2;
+
+        IFDEntry offsets;
+        IFDEntry byteCounts;
+        int numBlocks;
+        if (findTag(Tag.TileOffsets) != null) {
+            tiledFormat = true;
+            tileWidth = findTag(Tag.TileWidth).value[0];
+            tileLength = findTag(Tag.TileLength).value[0];
+            nTilesX = (nCols + tileWidth - 1) / tileWidth;
+            nTilesY = (nRows + tileLength - 1) / tileLength;
+            numBlocks = nTilesX * nTilesY;
+            offsets = findTag(Tag.TileOffsets);
+            byteCounts = findTag(Tag.TileByteCounts);
+        } else {
+            // the default of 2^32 - 1 rows, which is read as -1, is a single strip
+            rowsPerStrip = getTagValue(Tag.RowsPerStrip, nRows);
+            if (rowsPerStrip <= 0 || rowsPerStrip > nRows) {
+                rowsPerStrip = nRows;
+            }
+            numBlocks = (nRows + rowsPerStrip - 1) / rowsPerStrip;
+            offsets = findTag(Tag.StripOffsets);
+            byteCounts = findTag(Tag.StripByteCounts);
+        }
+
+        if (offsets == null || byteCounts == null || offsets.value.length < numBlocks
+                || byteCounts.value.length < numBlocks) {
+            unsupportedReason = "The file does not contain the locations of all of its strips or tiles.";
+        } else if (!TiffCodec.isSupportedCompression(compression)) {
+            unsupportedReason = "Compression type " + compression + " is not supported.";
+        } else if (predictor < 1 || predictor > 3 || (predictor == 3 && sampleFormat != 3)) {
+            unsupportedReason = "Predictor " + predictor + " is not supported for this type of data.";
+        } else if (samplesPerPixel > 1 && getTagValue(Tag.PlanarConfiguration, 1) == 2) {
+            unsupportedReason = "Images with separate planes for each sample are not supported.";
+        } else if (rgb && (bitsPerSample != 8 || (samplesPerPixel != 3 && samplesPerPixel != 4))) {
+            unsupportedReason = "Only 24- and 32-bit RGB images are supported.";
+        } else if (!rgb && samplesPerPixel != 1) {
+            unsupportedReason = "Images with more than one sample per pixel are only supported for RGB data.";
+        } else if ((bitsPerSample != 8 && bitsPerSample != 16 && bitsPerSample != 32 && bitsPerSample != 64)
+                || (sampleFormat == 3 && bitsPerSample < 32) || (sampleFormat == 1 && bitsPerSample == 64)
+                || sampleFormat < 1 || sampleFormat > 3) {
+            unsupportedReason = bitsPerSample + "-bit data of sample format " + sampleFormat + " are not supported.";
+        }
+        if (unsupportedReason != null) {
+            return;
+        }
+
+        blockOffsets = new long[numBlocks];
+        blockByteCounts = new long[numBlocks];
+        for (int i = 0; i < numBlocks; i++) {
+            // offsets and byte counts are unsigned
+            blockOffsets[i] = offsets.value[i] & 0xFFFFFFFFL;
+            blockByteCounts[i] = byteCounts.value[i] & 0xFFFFFFFFL;
+        }
+        floatSamples = !rgb && (bytesPerSample <= 2 || (sampleFormat == 3 && bytesPerSample == 4));
+
+        long rowBytes = (long) nCols * bytesPerSample * samplesPerPixel;
+        if (tiledFormat) {
+            bandRows = tileLength;
+        } else if (compression == TiffCodec.NONE) {
+            bandRows = (int) Math.max(1, Math.min(nRows, MAX_UNCOMPRESSED_BAND_BYTES / rowBytes));
+        } else {
+            bandRows = rowsPerStrip;
+        }
+        numBands = (nRows + bandRows - 1) / bandRows;
+        long bandBytes = (long) bandRows * nCols * (floatSamples ? 4 : 8);
+        if ((long) bandRows * Math.max(nCols, tiledFormat ? tileWidth : 0) > Integer.MAX_VALUE - 8) {
+            unsupportedReason = "The strips or tiles of the image are too large to be read.";
+            return;
+        }
+
+        int numBandBuffers = 1;
+        if (compression != TiffCodec.NONE) {
+            long maxBands = Math.max(1, MAX_PREFETCH_BYTES / bandBytes);
+            numBandBuffers = (int) Math.min(Math.min(numBands, maxBands),
+                    2L * Math.max(1, Parallel.getPluginProcessors()));
+        }
+        bands = new Band[numBandBuffers];
+        for (int i = 0; i < numBandBuffers; i++) {
+            bands[i] = new Band();
+        }
+    }
+
+    private int getTagValue(Tag tag, int defaultValue) {
+        IFDEntry ifd = findTag(tag);
+        if (ifd == null || ifd.value == null || ifd.value.length == 0) {
+            return defaultValue;
+        }
+        return ifd.value[0];
+    }
+
+    /**
+     * Explains why the image data cannot be read, e.g. an unsupported type of
+     * compression.
+     *
+     * @return The reason, or null if the image data can be read.
+     */
+    public String getUnsupportedReason() {
+        return unsupportedReason;
+    }
+
+    /**
+     * Indicates whether every value of the image can be held exactly in a
+     * float, in which case getRowValues(int, float[]) reads the image without
+     * converting it to doubles.
+     *
+     * @return true for 8- and 16-bit integer and 32-bit floating point data.
+     */
+    public boolean isSinglePrecision() {
+        return floatSamples;
+    }
 
     public double getValue(int row, int col) throws Exception {
-        if (!tiledFormat) { // strip oriented
-            // figure out which strip the pixel is in
-            int stripNum = row / rowsPerStrip;
-            if (currentStrip != stripNum) {
-                tileOrStripData = getStripData(stripNum);
-                if (tileOrStripData == null) {
-                    throw new Exception("Error reading data. It is likely that the TIFF file is of an unsupported type, possibly due to data compression.");
-                }
-                currentStrip = stripNum;
-            }
-            int stripRow = row % rowsPerStrip;
-            int stripPixelNum = stripRow * nCols + col;
-            return tileOrStripData[stripPixelNum];
-        } else { // tile oriented
-            // figure out which tile the pixel is in
-            int tileNum = row / tileLength * nTilesX + col / tileWidth;
-            if (currentTile != tileNum) {
-                tileOrStripData = getTileData(tileNum);
-                if (tileOrStripData == null) {
-                    throw new Exception("Error reading data. It is likely that the TIFF file is of an unsupported type, possibly due to data compression.");
-                }
-                currentTile = tileNum;
+        Band band = getBand(row);
+        int i = (row - band.index * bandRows) * nCols + col;
+        return floatSamples ? band.floatData[i] : band.doubleData[i];
+    }
+
+    /**
+     * Reads the values of a row of the image. Rows are read most quickly in
+     * order from the top of the image.
+     *
+     * @param row The zero-based row number.
+     * @param data An array that receives one value for each column.
+     * @throws IOException if the data cannot be read.
+     */
+    public void getRowValues(int row, double[] data) throws IOException {
+        Band band = getBand(row);
+        int offset = (row - band.index * bandRows) * nCols;
+        if (floatSamples) {
+            float[] values = band.floatData;
+            for (int col = 0; col < nCols; col++) {
+                data[col] = values[offset + col];
             }
-            int tileRow = row % tileLength;
-            int tileCol = col % tileWidth;
-            int tilePixelNum = tileRow * tileLength + tileCol;
-            return tileOrStripData[tilePixelNum];
+        } else {
+            System.arraycopy(band.doubleData, offset, data, 0, nCols);
         }
     }
 
-    private double[] getTileData(int tile) {
-        try {
-            if (!tiledFormat) {
-                return null;
+    /**
+     * Reads the values of a row of the image as floats. Values that cannot be
+     * held exactly in a float (see isSinglePrecision()) are rounded.
+     *
+     * @param row The zero-based row number.
+     * @param data An array that receives one value for each column.
+     * @throws IOException if the data cannot be read.
+     */
+    public void getRowValues(int row, float[] data) throws IOException {
+        Band band = getBand(row);
+        int offset = (row - band.index * bandRows) * nCols;
+        if (floatSamples) {
+            System.arraycopy(band.floatData, offset, data, 0, nCols);
+        } else {
+            double[] values = band.doubleData;
+            for (int col = 0; col < nCols; col++) {
+                data[col] = (float) values[offset + col];
             }
-            
-            double[] data;
-                    
-            int compressionType = findTag(Tag.Compression).value[0];
-            int sampleFormat = 1; //findTag(Tag.SampleFormat).value[0];
+        }
+    }
 
-            if (findTag(Tag.SampleFormat) != null) {
-                sampleFormat = findTag(Tag.SampleFormat).value[0];
-            }
+    // A buffer for the decoded values of a band of rows, along with the
+    // buffers that are used to decode it. Each band is decoded by one thread
+    // at a time.
+    private static final class Band {
+
+        int index = -1;
+        Future<?> pending = null;
+        float[] floatData;
+        double[] doubleData;
+        float[] tileFloats = new float[0];
+        double[] tileDoubles = new double[0];
+        byte[] compressed = new byte[0];
+        byte[] decoded = new byte[0];
+        byte[] reordered = new byte[0];
+        short[] shorts = new short[0];
+        int[] ints = new int[0];
+        long[] longs = new long[0];
+        final int[][] lzwTables = new int[2][4096];
+        Inflater inflater = null;
+    }
 
-            if (getPhotometricInterpretation() != 2) {
-                int tileOffset = findTag(Tag.TileOffsets).value[tile];
-                int tileByteCount = findTag(Tag.TileByteCounts).value[tile];
-                int[] bitsPerSample = findTag(Tag.BitsPerSample).value;
-                int numPixelsInTile = tileByteCount / (bitsPerSample[0] / 8);
-                data = new double[numPixelsInTile];
-
-                channel.position(tileOffset);
-                int size = tileByteCount / 8;
-                ByteBuffer buffer = ByteBuffer.allocate(tileByteCount);
-                buffer.order(byteOrder);
-                
-                if (compressionType == 1) {
-
-                    channel.read(buffer);
-                    buffer.flip();
-
-                    if (sampleFormat == 1 && bitsPerSample[0] == 8) { // unsigned byte
-                        byte b;
-                        for (int i = 0; i < size; i++) {
-                            b = buffer.get();
-                            data[i] = (short) (0x000000FF & ((int) b));
-                        }
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 8) { // signed byte
-                        byte b;
-                        for (int i = 0; i < size; i++) {
-                            b = buffer.get();
-                            data[i] = b;
-                        }
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 16) { // unsigned 16-bit short
-                        int b1;
-                        int b2;
-                        for (int i = 0; i < numPixelsInTile; i++) {
-                            b1 = (0x000000FF & ((int) buffer.get()));
-                            b2 = (0x000000FF & ((int) buffer.get()));
-                            data[i] = (b2 << 8 | b1);
-                        }
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 16) { // signed 16-bit short
-                        ShortBuffer sb = buffer.asShortBuffer();
-                        short[] sa = new short[numPixelsInTile];
-                        sb.get(sa);
-                        sb = null;
-                        for (int j = 0; j < numPixelsInTile; j++) {
-                            data[j] = sa[j];
-                        }
-                        sa = null;
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 32) { // unsigned 32-bit int
-                        int b1, b2, b3, b4;
-                        for (int i = 0; i < numPixelsInTile; i++) {
-                            b1 = (0x000000FF & ((int) buffer.get()));
-                            b2 = (0x000000FF & ((int) buffer.get()));
-                            b3 = (0x000000FF & ((int) buffer.get()));
-                            b4 = (0x000000FF & ((int) buffer.get()));
-                            data[i] = ((long) (b1 << 24 | b2 << 16 | b3 << 8 | b4)) & 0xFFFFFFFFL;
-                        }
-
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 32) { // signed 32-bit int
-                        IntBuffer ib = buffer.asIntBuffer();
-                        int[] ia = new int[numPixelsInTile];
-                        ib.get(ia);
-                        ib = null;
-                        for (int j = 0; j < numPixelsInTile; j++) {
-                            data[j] = ia[j];
-                        }
-                        ia = null;
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 64) { // unsigned 64-bit long
-                        // I don't know what data type you could cast an unsigned long into. Perhaps a BigInteger.
-                        return null;
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 64) { // signed 64-bit long
-                        LongBuffer lb = buffer.asLongBuffer();
-                        long[] la = new long[numPixelsInTile];
-                        lb.get(la);
-                        lb = null;
-                        for (int j = 0; j < numPixelsInTile; j++) {
-                            data[j] = la[j];
-                        }
-                        la = null;
-                    } else if (sampleFormat == 3 && bitsPerSample[0] == 32) { // 32-bit single-precision float
-                        FloatBuffer fb = buffer.asFloatBuffer();
-                        float[] fa = new float[numPixelsInTile];
-                        fb.get(fa);
-                        fb = null;
-                        for (int j = 0; j < numPixelsInTile; j++) {
-                            data[j] = fa[j];
-                        }
-                        fa = null;
-                    } else if (sampleFormat == 3 && bitsPerSample[0] == 64) { // 64-bit double-precision float
-                        DoubleBuffer db = buffer.asDoubleBuffer();
-                        double[] da = new double[numPixelsInTile];
-                        db.get(da);
-                        db = null;
-                        data = da.clone();
-                        da = null;
-                    } else {
-                        return null;
-                    }
-                } else if (compressionType == 32773) {
-                    return null;
-                } else {
-                    return null;
-                }
+    private Band getBand(int row) throws IOException {
+        if (unsupportedReason != null) {
+            throw new IOException("The TIFF file cannot be read. " + unsupportedReason);
+        }
+        if (row < 0 || row >= nRows) {
+            throw new IndexOutOfBoundsException("Row " + row + " is outside of the image.");
+        }
+        int b = row / bandRows;
+        Band band = bands[b % bands.length];
+        if (band.index == b && band.pending == null) {
+            return band;
+        }
+        if (bands.length == 1) {
+            band.index = -1;
+            decodeBand(band, b);
+            band.index = b;
+            return band;
+        }
 
-            } else {
-                int tileOffset = findTag(Tag.TileOffsets).value[tile];
-                int tileByteCount = findTag(Tag.TileByteCounts).value[tile];
-                int[] bitsPerSample = findTag(Tag.BitsPerSample).value;
-                int totalBitsPerSample = 0;
-                for (int a = 0; a < bitsPerSample.length; a++) {
-                    totalBitsPerSample += bitsPerSample[a];
+        // decode this band and the ones that follow it in the buffers that
+        // don't already hold them
+        ExecutorService pool = getDecodePool();
+        for (int k = 0; k < bands.length && b + k < numBands; k++) {
+            final Band slot = bands[(b + k) % bands.length];
+            final int index = b + k;
+            if (slot.index != index) {
+                try {
+                    awaitBand(slot);
+                } catch (IOException e) {
+                    // the band is no longer needed
                 }
-                int numPixelsInTile = tileByteCount / (totalBitsPerSample / 8);
-                data = new double[numPixelsInTile];
+                slot.index = index;
+                slot.pending = pool.submit(new Callable<Void>() {
 
-                channel.position(tileOffset);
-                ByteBuffer buffer = ByteBuffer.allocate(tileByteCount);
-                buffer.order(byteOrder);
-                
-                try {
-                    if (compressionType == 1) {
-                        channel.read(buffer);
-                        buffer.flip();
-
-                        if (totalBitsPerSample == 24 && bitsPerSample.length == 3) {
-                            int r, g, b;
-                            for (int i = 0; i < nCols; i++) {
-                                r = (0x000000FF & ((int) buffer.get()));
-                                g = (0x000000FF & ((int) buffer.get()));
-                                b = (0x000000FF & ((int) buffer.get()));
-                                data[i] = (double) ((255 << 24) | (b << 16) | (g << 8) | r);
-                            }
-                        } else if (totalBitsPerSample == 32 && bitsPerSample.length == 4) {
-                            int r, g, b, a;
-                            for (int i = 0; i < nCols; i++) {
-                                r = (0x000000FF & ((int) buffer.get()));
-                                g = (0x000000FF & ((int) buffer.get()));
-                                b = (0x000000FF & ((int) buffer.get()));
-                                a = (0x000000FF & ((int) buffer.get()));
-                                data[i] = (double) ((a << 24) | (b << 16) | (g << 8) | r);
-                            }
-                        } else {
-                            return null;
-                        }
-
-                    } else if (compressionType == 32773) {
-                        return null;
-                    } else {
+                    @Override
+                    public Void call() throws IOException {
+                        decodeBand(slot, index);
                         return null;
                     }
-
-                } catch (IOException e) {
-                    // do nothing
-                }
+                });
             }
-            return data;
-        } catch (Exception e) {
-            return null;
         }
+        awaitBand(band);
+        return band;
     }
 
-    private double[] getStripData(int stripNum) {
-        double[] data;
-        int compressionType = findTag(Tag.Compression).value[0];
-        int[] bitsPerSample = findTag(Tag.BitsPerSample).value;
-        int sampleFormat = 1; //findTag(Tag.SampleFormat).value[0];
+    private void awaitBand(Band band) throws IOException {
+        Future<?> pending = band.pending;
+        if (pending == null) {
+            return;
+        }
+        band.pending = null;
+        try {
+            pending.get();
+        } catch (InterruptedException e) {
+            band.index = -1;
+            Thread.currentThread().interrupt();
+            throw new InterruptedIOException("Reading of " + filename + " was interrupted.");
+        } catch (ExecutionException e) {
+            band.index = -1;
+            Throwable cause = e.getCause();
+            if (cause instanceof IOException) {
+                throw (IOException) cause;
+            }
+            throw new IOException("Error decoding " + filename, cause);
+        }
+    }
 
-        if (findTag(Tag.SampleFormat) != null) {
-            sampleFormat = findTag(Tag.SampleFormat).value[0];
+    private void decodeBand(Band band, int b) throws IOException {
+        int startRow = b * bandRows;
+        int numRows = Math.min(bandRows, nRows - startRow);
+        int bytesPerPixel = bytesPerSample * samplesPerPixel;
+        if (floatSamples && band.floatData == null) {
+            band.floatData = new float[bandRows * nCols];
+        } else if (!floatSamples && band.doubleData == null) {
+            band.doubleData = new double[bandRows * nCols];
         }
-        if (tiledFormat) {
-            return null; 
-        }
-        int stripOffset = findTag(Tag.StripOffsets).value[stripNum];
-        int stripByteSize = findTag(Tag.StripByteCounts).value[stripNum];
-        
-        if (getPhotometricInterpretation() != 2) {
-            int numPixelsInStrip = stripByteSize / (bitsPerSample[0] / 8);
-            data = new double[numPixelsInStrip];
-            try {
-                channel.position(stripOffset);
-                ByteBuffer buffer = ByteBuffer.allocate(stripByteSize);
-                buffer.order(byteOrder);
-
-                if (compressionType == 1) {
-
-                    channel.read(buffer);
-                    buffer.flip();
-
-                    if (sampleFormat == 1 && bitsPerSample[0] == 8) { // unsigned byte
-                        byte b;
-                        for (int i = 0; i < numPixelsInStrip; i++) {
-                            b = buffer.get();
-                            data[i] = (short) (0x000000FF & ((int) b));
-                        }
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 8) { // signed byte
-                        byte b;
-                        for (int i = 0; i < numPixelsInStrip; i++) {
-                            b = buffer.get();
-                            data[i] = b;
-                        }
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 16) { // unsigned 16-bit short
-                        int b1;
-                        int b2;
-                        for (int i = 0; i < numPixelsInStrip; i++) {
-                            b1 = (0x000000FF & ((int) buffer.get()));
-                            b2 = (0x000000FF & ((int) buffer.get()));
-                            data[i] = (b2 << 8 | b1);
-                        }
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 16) { // signed 16-bit short
-                        ShortBuffer sb = buffer.asShortBuffer();
-                        short[] sa = new short[numPixelsInStrip];
-                        sb.get(sa);
-                        sb = null;
-                        for (int j = 0; j < numPixelsInStrip; j++) {
-                            data[j] = sa[j];
-                        }
-                        sa = null;
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 32) { // unsigned 32-bit int
-                        int b1, b2, b3, b4;
-                        for (int i = 0; i < numPixelsInStrip; i++) {
-                            b1 = (0x000000FF & ((int) buffer.get()));
-                            b2 = (0x000000FF & ((int) buffer.get()));
-                            b3 = (0x000000FF & ((int) buffer.get()));
-                            b4 = (0x000000FF & ((int) buffer.get()));
-                            data[i] = ((long) (b1 << 24 | b2 << 16 | b3 << 8 | b4)) & 0xFFFFFFFFL;
-                        }
-
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 32) { // signed 32-bit int
-                        IntBuffer ib = buffer.asIntBuffer();
-                        int[] ia = new int[numPixelsInStrip];
-                        ib.get(ia);
-                        ib = null;
-                        for (int j = 0; j < numPixelsInStrip; j++) {
-                            data[j] = ia[j];
-                        }
-                        ia = null;
-                    } else if (sampleFormat == 1 && bitsPerSample[0] == 64) { // unsigned 64-bit long
-                        // I don't know what data type you could cast an unsigned long into. Perhaps a BigInteger.
-                        return null;
-                    } else if (sampleFormat == 2 && bitsPerSample[0] == 64) { // signed 64-bit long
-                        LongBuffer lb = buffer.asLongBuffer();
-                        long[] la = new long[numPixelsInStrip];
-                        lb.get(la);
-                        lb = null;
-                        for (int j = 0; j < numPixelsInStrip; j++) {
-                            data[j] = la[j];
-                        }
-                        la = null;
-                    } else if (sampleFormat == 3 && bitsPerSample[0] == 32) { // 32-bit single-precision float
-                        FloatBuffer fb = buffer.asFloatBuffer();
-                        float[] fa = new float[numPixelsInStrip];
-                        fb.get(fa);
-                        fb = null;
-                        for (int j = 0; j < numPixelsInStrip; j++) {
-                            data[j] = fa[j];
-                        }
-                        fa = null;
-                    } else if (sampleFormat == 3 && bitsPerSample[0] == 64) { // 64-bit double-precision float
-                        DoubleBuffer db = buffer.asDoubleBuffer();
-                        double[] da = new double[numPixelsInStrip];
-                        db.get(da);
-                        db = null;
-                        data = da.clone();
-                        da = null;
+
+        if (!tiledFormat && compression == TiffCodec.NONE) {
+            // the rows are read directly and may span several strips
+            int row = startRow;
+            while (row < startRow + numRows) {
+                int strip = row / rowsPerStrip;
+                int n = Math.min(startRow + numRows, (strip + 1) * rowsPerStrip) - row;
+                int length = n * nCols * bytesPerPixel;
+                band.decoded = ensureCapacity(band.decoded, length);
+                readFully(blockOffsets[strip] + (long) (row - strip * rowsPerStrip) * nCols * bytesPerPixel,
+                        band.decoded, length);
+                decodeSamples(band, band.decoded, nCols, n, band.floatData, band.doubleData,
+                        (row - startRow) * nCols);
+                row += n;
+            }
+        } else if (!tiledFormat) {
+            decodeBlock(band, b, numRows * nCols * bytesPerPixel);
+            decodeSamples(band, band.decoded, nCols, numRows, band.floatData, band.doubleData, 0);
+        } else {
+            int tileSize = tileWidth * numRows;
+            if (floatSamples) {
+                band.tileFloats = ensureCapacity(band.tileFloats, tileSize);
+            } else {
+                band.tileDoubles = ensureCapacity(band.tileDoubles, tileSize);
+            }
+            for (int tx = 0; tx < nTilesX; tx++) {
+                decodeBlock(band, b * nTilesX + tx, tileSize * bytesPerPixel);
+                decodeSamples(band, band.decoded, tileWidth, numRows, band.tileFloats, band.tileDoubles, 0);
+                // tiles on the right edge extend beyond the image
+                int startCol = tx * tileWidth;
+                int width = Math.min(tileWidth, nCols - startCol);
+                for (int r = 0; r < numRows; r++) {
+                    if (floatSamples) {
+                        System.arraycopy(band.tileFloats, r * tileWidth, band.floatData, r * nCols + startCol, width);
                     } else {
-                        return null;
+                        System.arraycopy(band.tileDoubles, r * tileWidth, band.doubleData, r * nCols + startCol, width);
                     }
-                } else if (compressionType == 32773) {
-                    return null;
-                } else {
-                    return null;
                 }
-
-            } catch (IOException e) {
-                // do nothing
             }
+        }
+    }
 
+    // reads and decompresses the first length bytes of a strip or tile into
+    // band.decoded.
+    private void decodeBlock(Band band, int block, int length) throws IOException {
+        band.decoded = ensureCapacity(band.decoded, length);
+        long byteCount = blockByteCounts[block];
+        if (byteCount == 0) {
+            // a sparse file leaves out blocks of zeros
+            Arrays.fill(band.decoded, 0, length, (byte) 0);
+            return;
+        }
+        if (byteCount > Integer.MAX_VALUE) {
+            throw new IOException("Strip or tile " + block + " is too large to be read.");
+        }
+        int n;
+        if (compression == TiffCodec.NONE) {
+            n = (int) Math.min(byteCount, length);
+            readFully(blockOffsets[block], band.decoded, n);
         } else {
-            int totalBitsPerSample = 0;
-            for (int a = 0; a < bitsPerSample.length; a++) {
-                totalBitsPerSample += bitsPerSample[a];
+            band.compressed = ensureCapacity(band.compressed, (int) byteCount);
+            readFully(blockOffsets[block], band.compressed, (int) byteCount);
+            if (band.inflater == null) {
+                band.inflater = new Inflater();
             }
-            int numPixelsInStrip = stripByteSize / (totalBitsPerSample / 8);
-            data = new double[numPixelsInStrip];
-            
-            try {
-                channel.position(stripOffset);
-                ByteBuffer buffer = ByteBuffer.allocate(stripByteSize);
-                buffer.order(byteOrder);
-
-                if (compressionType == 1) {
-
-                    channel.read(buffer);
-                    buffer.flip();
-
-                    if (totalBitsPerSample == 24 && bitsPerSample.length == 3) {
-                        int r, g, b;
-                        for (int i = 0; i < nCols; i++) {
-                            r = (0x000000FF & ((int) buffer.get()));
-                            g = (0x000000FF & ((int) buffer.get()));
-                            b = (0x000000FF & ((int) buffer.get()));
-                            data[i] = (double) ((255 << 24) | (b << 16) | (g << 8) | r);
-                        }
-                    } else if (totalBitsPerSample == 32 && bitsPerSample.length == 4) {
-                        int r, g, b, a;
-                        for (int i = 0; i < nCols; i++) {
-                            r = (0x000000FF & ((int) buffer.get()));
-                            g = (0x000000FF & ((int) buffer.get()));
-                            b = (0x000000FF & ((int) buffer.get()));
-                            a = (0x000000FF & ((int) buffer.get()));
-                            data[i] = (double) ((a << 24) | (b << 16) | (g << 8) | r);
-                        }
-                    } else {
-                        return null;
-                    }
+            n = TiffCodec.decompress(compression, band.compressed, (int) byteCount,
+                    band.decoded, length, band.lzwTables, band.inflater);
+        }
+        if (n < length) {
+            throw new IOException("Strip or tile " + block + " of " + filename + " is incomplete.");
+        }
+    }
+
+    private void readFully(long position, byte[] dst, int length) throws IOException {
+        ByteBuffer buffer = ByteBuffer.wrap(dst, 0, length);
+        while (buffer.hasRemaining()) {
+            // positional reads may be made by several threads at once
+            if (channel.read(buffer, position + buffer.position()) < 0) {
+                throw new EOFException("Unexpected end of file " + filename);
+            }
+        }
+    }
+
+    // converts the decoded bytes of numRows rows of pixelsPerRow pixels to
+    // values, undoing any predictor, and writes them to either floatData or
+    // doubleData, starting at offset.
+    private void decodeSamples(Band band, byte[] bytes, int pixelsPerRow, int numRows,
+            float[] floatData, double[] doubleData, int offset) {
+        int samplesPerRow = pixelsPerRow * samplesPerPixel;
+        if (predictor == 3) {
+            band.reordered = ensureCapacity(band.reordered, samplesPerRow * numRows * bytesPerSample);
+            TiffCodec.undoFloatingPointPredictor(bytes, band.reordered, samplesPerRow, numRows,
+                    bytesPerSample, samplesPerPixel);
+            convertSamples(band, band.reordered, ByteOrder.BIG_ENDIAN, false, samplesPerRow, numRows,
+                    floatData, doubleData, offset);
+        } else {
+            convertSamples(band, bytes, byteOrder, predictor == 2, samplesPerRow, numRows,
+                    floatData, doubleData, offset);
+        }
+    }
 
-                } else if (compressionType == 32773) {
-                    return null;
+    // The samples are converted from the byte order of the file a whole block
+    // at a time, with the bulk get methods of the typed buffer views, and the
+    // horizontal differencing predictor is undone on the integer samples.
+    private void convertSamples(Band band, byte[] bytes, ByteOrder order, boolean differenced,
+            int samplesPerRow, int numRows, float[] floatData, double[] doubleData, int offset) {
+        int n = samplesPerRow * numRows;
+        boolean signed = sampleFormat == 2;
+        ByteBuffer buffer = ByteBuffer.wrap(bytes, 0, n * bytesPerSample).order(order);
+        switch (bytesPerSample) {
+            case 1:
+                if (differenced) {
+                    TiffCodec.undoHorizontalDifferencing(bytes, samplesPerRow, numRows, samplesPerPixel);
+                }
+                if (rgb) {
+                    int numPixels = n / samplesPerPixel;
+                    for (int i = 0, p = 0; i < numPixels; i++, p += samplesPerPixel) {
+                        int r = bytes[p] & 0xFF;
+                        int g = bytes[p + 1] & 0xFF;
+                        int b = bytes[p + 2] & 0xFF;
+                        int a = samplesPerPixel == 4 ? bytes[p + 3] & 0xFF : 255;
+                        doubleData[offset + i] = (double) ((a << 24) | (b << 16) | (g << 8) | r);
+                    }
+                } else if (signed) {
+                    for (int i = 0; i < n; i++) {
+                        floatData[offset + i] = bytes[i];
+                    }
                 } else {
-                    return null;
+                    for (int i = 0; i < n; i++) {
+                        floatData[offset + i] = bytes[i] & 0xFF;
+                    }
+                }
+                break;
+            case 2:
+                short[] shorts = band.shorts = ensureCapacity(band.shorts, n);
+                buffer.asShortBuffer().get(shorts, 0, n);
+                if (differenced) {
+                    TiffCodec.undoHorizontalDifferencing(shorts, samplesPerRow, numRows, samplesPerPixel);
+                }
+                if (signed) {
+                    for (int i = 0; i < n; i++) {
+                        floatData[offset + i] = shorts[i];
+                    }
+                } else {
+                    for (int i = 0; i < n; i++) {
+                        floatData[offset + i] = shorts[i] & 0xFFFF;
+                    }
+                }
+                break;
+            case 4:
+                if (sampleFormat == 3 && !differenced) {
+                    buffer.asFloatBuffer().get(floatData, offset, n);
+                    break;
+                }
+                int[] ints = band.ints = ensureCapacity(band.ints, n);
+                buffer.asIntBuffer().get(ints, 0, n);
+                if (differenced) {
+                    TiffCodec.undoHorizontalDifferencing(ints, samplesPerRow, numRows, samplesPerPixel);
+                }
+                if (sampleFormat == 3) {
+                    for (int i = 0; i < n; i++) {
+                        floatData[offset + i] = Float.intBitsToFloat(ints[i]);
+                    }
+                } else if (signed) {
+                    for (int i = 0; i < n; i++) {
+                        doubleData[offset + i] = ints[i];
+                    }
+                } else {
+                    for (int i = 0; i < n; i++) {
+                        doubleData[offset + i] = ints[i] & 0xFFFFFFFFL;
+                    }
+                }
+                break;
+            case 8:
+                if (sampleFormat == 3 && !differenced) {
+                    buffer.asDoubleBuffer().get(doubleData, offset, n);
+                    break;
+                }
+                long[] longs = band.longs = ensureCapacity(band.longs, n);
+                buffer.asLongBuffer().get(longs, 0, n);
+                if (differenced) {
+                    TiffCodec.undoHorizontalDifferencing(longs, samplesPerRow, numRows, samplesPerPixel);
+                }
+                if (sampleFormat == 3) {
+                    for (int i = 0; i < n; i++) {
+                        doubleData[offset + i] = Double.longBitsToDouble(longs[i]);
+                    }
+                } else {
+                    for (int i = 0; i < n; i++) {
+                        doubleData[offset + i] = longs[i];
+                    }
                 }
+                break;
+        }
+    }
+
+    private static byte[] ensureCapacity(byte[] array, int length) {
+        return array.length >= length ? array : new byte[length];
+    }
+
+    private static short[] ensureCapacity(short[] array, int length) {
+        return array.length >= length ? array : new short[length];
+    }
+
+    private static int[] ensureCapacity(int[] array, int length) {
+        return array.length >= length ? array : new int[length];
+    }
+
+    private static long[] ensureCapacity(long[] array, int length) {
+        return array.length >= length ? array : new long[length];
+    }
+
+    private static float[] ensureCapacity(float[] array, int length) {
+        return array.length >= length ? array : new float[length];
+    }
 
-            } catch (IOException e) {
-                return null;
+    private static double[] ensureCapacity(double[] array, int length) {
+        return array.length >= length ? array : new double[length];
+    }
+
+    private static ExecutorService decodePool = null;
+    private static int decodePoolThreads = 0;
+
+    // returns the pool on which the strips and tiles of compressed files are
+    // decoded, which is shared by all readers and sized by the number of
+    // processors that plugins may use.
+    private static synchronized ExecutorService getDecodePool() {
+        int numThreads = Math.max(1, Parallel.getPluginProcessors());
+        if (decodePool == null || numThreads != decodePoolThreads) {
+            if (decodePool != null) {
+                decodePool.shutdown();
             }
+            decodePool = Executors.newFixedThreadPool(numThreads, new NamedThreadFactory("GeoTiff"));
+            decodePoolThreads = numThreads;
         }
-        return data;
-    }
-
-//    public double[] getRowData(int row) {
-//        double[] data = new double[nCols];
-//        int offset = 0;
-//        int size = 0;
-//        int compressionType = findTag(Tag.Compression).value[0];
-//        int[] bitsPerSample = findTag(Tag.BitsPerSample).value;
-//        int sampleFormat = 1; //findTag(Tag.SampleFormat).value[0];
-//
-//        if (findTag(Tag.SampleFormat) != null) {
-//            sampleFormat = findTag(Tag.SampleFormat).value[0];
-//        }
-//        if (tiledFormat) {
-//            return null; // can't handle this type of tiff.
-//        }
-//
-//        if (getPhotometricInterpretation() != 2) {
-//
-//            // how many rows per strip?
-////                rowsPerStrip = findTag(Tag.RowsPerStrip).value[0];
-//            // which strip will contain row?
-//            int stripNum = (int) row / rowsPerStrip;
-//
-//            int stripOffset = findTag(Tag.StripOffsets).value[stripNum];
-//            //int stripSize = findTag(Tag.StripByteCounts).value[0];
-//            offset = stripOffset + (row % rowsPerStrip * nCols * (bitsPerSample[0] / 8));
-//            size = nCols * (bitsPerSample[0] / 8); //stripSize;
-//
-//            try {
-//                channel.position(offset);
-//                ByteBuffer buffer = ByteBuffer.allocate(size);
-//                buffer.order(byteOrder);
-//
-//                if (compressionType == 1) {
-//
-//                    channel.read(buffer);
-//                    buffer.flip();
-//
-//                    if (sampleFormat == 1 && bitsPerSample[0] == 8) { // unsigned byte
-//                        byte b;
-//                        for (int i = 0; i < size; i++) {
-//                            b = buffer.get();
-//                            data[i] = (short) (0x000000FF & ((int) b));
-//                        }
-//                    } else if (sampleFormat == 2 && bitsPerSample[0] == 8) { // signed byte
-//                        byte b;
-//                        for (int i = 0; i < size; i++) {
-//                            b = buffer.get();
-//                            data[i] = b;
-//                        }
-//                    } else if (sampleFormat == 1 && bitsPerSample[0] == 16) { // unsigned 16-bit short
-//                        int b1;
-//                        int b2;
-//                        for (int i = 0; i < nCols; i++) {
-//                            b1 = (0x000000FF & ((int) buffer.get()));
-//                            b2 = (0x000000FF & ((int) buffer.get()));
-//                            data[i] = (b2 << 8 | b1);
-//                        }
-//                    } else if (sampleFormat == 2 && bitsPerSample[0] == 16) { // signed 16-bit short
-//                        ShortBuffer sb = buffer.asShortBuffer();
-//                        short[] sa = new short[nCols];
-//                        sb.get(sa);
-//                        sb = null;
-//                        for (int j = 0; j < nCols; j++) {
-//                            data[j] = sa[j];
-//                        }
-//                        sa = null;
-//                    } else if (sampleFormat == 1 && bitsPerSample[0] == 32) { // unsigned 32-bit int
-//                        int b1, b2, b3, b4;
-//                        for (int i = 0; i < nCols; i++) {
-//                            b1 = (0x000000FF & ((int) buffer.get()));
-//                            b2 = (0x000000FF & ((int) buffer.get()));
-//                            b3 = (0x000000FF & ((int) buffer.get()));
-//                            b4 = (0x000000FF & ((int) buffer.get()));
-//                            data[i] = ((long) (b1 << 24 | b2 << 16 | b3 << 8 | b4)) & 0xFFFFFFFFL;
-//                        }
-//
-//                    } else if (sampleFormat == 2 && bitsPerSample[0] == 32) { // signed 32-bit int
-//                        IntBuffer ib = buffer.asIntBuffer();
-//                        int[] ia = new int[nCols];
-//                        ib.get(ia);
-//                        ib = null;
-//                        for (int j = 0; j < nCols; j++) {
-//                            data[j] = ia[j];
-//                        }
-//                        ia = null;
-//                    } else if (sampleFormat == 1 && bitsPerSample[0] == 64) { // unsigned 64-bit long
-//                        // I don't know what data type you could cast an unsigned long into. Perhaps a BigInteger.
-//                        return null;
-//                    } else if (sampleFormat == 2 && bitsPerSample[0] == 64) { // signed 64-bit long
-//                        LongBuffer lb = buffer.asLongBuffer();
-//                        long[] la = new long[nCols];
-//                        lb.get(la);
-//                        lb = null;
-//                        for (int j = 0; j < nCols; j++) {
-//                            data[j] = la[j];
-//                        }
-//                        la = null;
-//                    } else if (sampleFormat == 3 && bitsPerSample[0] == 32) { // 32-bit single-precision float
-//                        FloatBuffer fb = buffer.asFloatBuffer();
-//                        float[] fa = new float[nCols];
-//                        fb.get(fa);
-//                        fb = null;
-//                        for (int j = 0; j < nCols; j++) {
-//                            data[j] = fa[j];
-//                        }
-//                        fa = null;
-//                    } else if (sampleFormat == 3 && bitsPerSample[0] == 64) { // 64-bit double-precision float
-//                        DoubleBuffer db = buffer.asDoubleBuffer();
-//                        double[] da = new double[nCols];
-//                        db.get(da);
-//                        db = null;
-//                        data = da.clone();
-//                        da = null;
-//                    } else {
-//                        return null;
-//                    }
-//                } else if (compressionType == 32773) {
-//                    return null;
-//                } else {
-//                    return null;
-//                }
-//
-//            } catch (IOException e) {
-//                // do nothing
-//            }
-//
-//            return data;
-//        } else {
-//            // how many rows per strip?
-//            int rowsPerStrip = findTag(Tag.RowsPerStrip).value[0];
-//            // which strip will contain row?
-//            int stripNum = (int) row / rowsPerStrip;
-//
-//            int stripOffset = findTag(Tag.StripOffsets).value[stripNum];
-//            //int stripSize = findTag(Tag.StripByteCounts).value[0];
-//
-//            int totalBitsPerSample = 0;
-//            for (int a = 0; a < bitsPerSample.length; a++) {
-//                totalBitsPerSample += bitsPerSample[a];
-//            }
-//            offset = stripOffset + (row % rowsPerStrip * nCols * (totalBitsPerSample / 8));
-//            size = nCols * (totalBitsPerSample / 8); //stripSize;
-//
-//            try {
-//                channel.position(offset);
-//                ByteBuffer buffer = ByteBuffer.allocate(size);
-//                buffer.order(byteOrder);
-//
-//                if (compressionType == 1) {
-//
-//                    channel.read(buffer);
-//                    buffer.flip();
-//
-//                    if (totalBitsPerSample == 24 && bitsPerSample.length == 3) {
-//                        int r, g, b;
-//                        for (int i = 0; i < nCols; i++) {
-//                            r = (0x000000FF & ((int) buffer.get()));
-//                            g = (0x000000FF & ((int) buffer.get()));
-//                            b = (0x000000FF & ((int) buffer.get()));
-//                            data[i] = (double) ((255 << 24) | (b << 16) | (g << 8) | r);
-//                        }
-//                    } else if (totalBitsPerSample == 32 && bitsPerSample.length == 4) {
-//                        int r, g, b, a;
-//                        for (int i = 0; i < nCols; i++) {
-//                            r = (0x000000FF & ((int) buffer.get()));
-//                            g = (0x000000FF & ((int) buffer.get()));
-//                            b = (0x000000FF & ((int) buffer.get()));
-//                            a = (0x000000FF & ((int) buffer.get()));
-//                            data[i] = (double) ((a << 24) | (b << 16) | (g << 8) | r);
-//                        }
-//                    } else {
-//                        return null;
-//                    }
-//
-//                } else if (compressionType == 32773) {
-//                    return null;
-//                } else {
-//                    return null;
-//                }
-//
-//            } catch (IOException e) {
-//                // do nothing
-//            }
-//            return data;
-////            return null;
-//        }
-//    }
+        return decodePool;
+    }
+
+    // waits for the bands that are being decoded, so that the file can be closed.
+    private void finishBands() {
+        for (Band band : bands) {
+            if (band.pending != null) {
+                try {
+                    awaitBand(band);
+                } catch (IOException e) {
+                    // the band is not needed
+                }
+            }
+            band.index = -1;
+            if (band.inflater != null) {
+                band.inflater.end();
+                band.inflater = null;
+            }
+        }
+    }
 
     private int readHeader(FileChannel channel) throws IOException {
         channel.position(0);
@@ -70,6 +70,7 @@ public class LASReader {
     private PointRecColours[] pointColours;
     private boolean coloursNeedReading = true;
     private boolean pointsNeedReading = true;
+    private LasSpatialIndex spatialIndex = null;
     
     //private PointWavePacket[] pointWavePagetData;
 
@@ -91,6 +92,7 @@ public class LASReader {
         this.fileName = fileName;
         readHeaderData();
         readVariableLengthRecords();
+        spatialIndex = LasSpatialIndex.read(fileName, numPointRecords);
     }
 
     public int getFileSourceID() {
@@ -233,6 +235,33 @@ public class LASReader {
         return minZ;
     }
 
+    /**
+     * Indicates whether the file has an up-to-date spatial index, which is used
+     * to speed up bounding-box queries.
+     *
+     * @return true if the file has a spatial index.
+     */
+    public boolean hasSpatialIndex() {
+        return spatialIndex != null;
+    }
+
+    /**
+     * Finds the ranges of point records that must be searched for a bounding
+     * box query. Without a spatial index, this is the whole file.
+     */
+    private ArrayList<long[]> getPointRangesInBoundingBox(BoundingBox bb) {
+        ArrayList<long[]> ranges = new ArrayList<>();
+        if (spatialIndex == null) {
+            ranges.add(new long[]{0, numPointRecords});
+        } else {
+            for (int block : spatialIndex.getBlocksInBoundingBox(bb)) {
+                long first = spatialIndex.getFirstPoint(block);
+                ranges.add(new long[]{first, first + spatialIndex.getNumPoints(block)});
+            }
+        }
+        return ranges;
+    }
+
     // Methods
     public ArrayList<PointRecord> getPointRecordsInBoundingBox(BoundingBox bb) {
         double minXbb = bb.getMinX();
@@ -244,15 +273,17 @@ public class LASReader {
         ArrayList<PointRecord> ret = new ArrayList<>();
         PointRecord rec;
         try {
-            for (int i = 0; i < numPointRecords; i++) {
-                rec = getPointRecord(i);
-                if (rec != null) {
-                    x = rec.getX();
-                    y = rec.getY();
-                    if (maxYbb < y || maxXbb < x || minYbb > y || minXbb > x) {
-                        // do nothing it's outside the bounds
-                    } else {
-                        ret.add(rec);
+            for (long[] range : getPointRangesInBoundingBox(bb)) {
+                for (int i = (int) range[0]; i < range[1]; i++) {
+                    rec = getPointRecord(i);
+                    if (rec != null) {
+                        x = rec.getX();
+                        y = rec.getY();
+                        if (maxYbb < y || maxXbb < x || minYbb > y || minXbb > x) {
+                            // do nothing it's outside the bounds
+                        } else {
+                            ret.add(rec);
+                        }
                     }
                 }
             }
@@ -272,14 +303,16 @@ public class LASReader {
 
         ArrayList<Integer> ret = new ArrayList<>();
         PointRecord rec;
-        for (int i = 0; i < numPointRecords; i++) {
-            rec = getPointRecord(i);
-            x = rec.getX();
-            y = rec.getY();
-            if (maxYbb < y || maxXbb < x || minYbb > y || minXbb > x) {
-                // do nothing it's outside the bounds
-            } else {
-                ret.add(i);
+        for (long[] range : getPointRangesInBoundingBox(bb)) {
+            for (int i = (int) range[0]; i < range[1]; i++) {
+                rec = getPointRecord(i);
+                x = rec.getX();
+                y = rec.getY();
+                if (maxYbb < y || maxXbb < x || minYbb > y || minXbb > x) {
+                    // do nothing it's outside the bounds
+                } else {
+                    ret.add(i);
+                }
             }
         }
         return ret;
@@ -0,0 +1,242 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package whitebox.geospatialfiles;
+
+import java.io.BufferedOutputStream;
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.IOException;
+import java.io.RandomAccessFile;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.nio.channels.FileChannel;
+import java.util.ArrayList;
+import whitebox.structures.BoundingBox;
+
+/**
+ * A simple block-based spatial index for a LAS file, stored in a sidecar file
+ * with the same name as the LAS file and a .lsi extension. The point records
+ * of the LAS file are divided into blocks of consecutive records, and the
+ * bounding box of each block is stored, so that a bounding-box query only needs
+ * to read the blocks that overlap the box. The index is only useful when the
+ * points are spatially sorted, e.g. by the SortLidar tool.
+ *
+ * The index file is little-endian and has the following layout (version 1):
+ * <pre>
+ * bytes 0-7    signature "WBLASIDX"
+ * int          format version
+ * long         length of the LAS file, in bytes
+ * long         last-modified time of the LAS file, in ms
+ * long         number of point records
+ * int          nominal number of points per block
+ * int          number of blocks
+ * then, for each block:
+ * long         index of the first point record in the block
+ * int          number of point records in the block
+ * double x 4   minimum x, minimum y, maximum x, maximum y
+ * </pre>
+ * The LAS file length, modification time and number of points are used to
+ * detect an index that no longer matches its LAS file, in which case the index
+ * is ignored.
+ */
+public class LasSpatialIndex {
+
+    public static final int VERSION = 1;
+    private static final byte[] SIGNATURE = {'W', 'B', 'L', 'A', 'S', 'I', 'D', 'X'};
+    private static final int HEADER_LENGTH = 44;
+    private static final int BLOCK_RECORD_LENGTH = 44;
+
+    private int blockSize;
+    private long numPointRecords = 0;
+    private final ArrayList<Long> firstPoints = new ArrayList<>();
+    private final ArrayList<Integer> numPoints = new ArrayList<>();
+    private final ArrayList<BoundingBox> extents = new ArrayList<>();
+
+    /**
+     * Creates an empty index.
+     *
+     * @param blockSize The nominal number of point records per block.
+     */
+    public LasSpatialIndex(int blockSize) {
+        this.blockSize = blockSize;
+    }
+
+    /**
+     * Returns the name of the index file that accompanies a LAS file.
+     *
+     * @param lasFileName The name of the LAS file.
+     * @return The name of the index file.
+     */
+    public static String getIndexFileName(String lasFileName) {
+        int i = lasFileName.toLowerCase().lastIndexOf(".las");
+        if (i >= 0) {
+            return lasFileName.substring(0, i) + ".lsi";
+        }
+        return lasFileName + ".lsi";
+    }
+
+    /**
+     * Reads the index of a LAS file.
+     *
+     * @param lasFileName The name of the LAS file.
+     * @param numPointRecords The number of point records in the LAS file header.
+     * @return The index, or null if the file has no index, or if the index is
+     * of an unsupported version or does not match the LAS file.
+     */
+    public static LasSpatialIndex read(String lasFileName, long numPointRecords) {
+        File lasFile = new File(lasFileName);
+        File indexFile = new File(getIndexFileName(lasFileName));
+        if (!lasFile.exists() || !indexFile.exists()) {
+            return null;
+        }
+        RandomAccessFile rIn = null;
+        try {
+            rIn = new RandomAccessFile(indexFile, "r");
+            FileChannel inChannel = rIn.getChannel();
+            if (inChannel.size() < HEADER_LENGTH) {
+                return null;
+            }
+            ByteBuffer buf = ByteBuffer.allocate(HEADER_LENGTH);
+            buf.order(ByteOrder.LITTLE_ENDIAN);
+            inChannel.read(buf, 0);
+            buf.rewind();
+            for (int a = 0; a < SIGNATURE.length; a++) {
+                if (buf.get() != SIGNATURE[a]) {
+                    return null;
+                }
+            }
+            if (buf.getInt() != VERSION
+                    || buf.getLong() != lasFile.length()
+                    || buf.getLong() != lasFile.lastModified()
+                    || buf.getLong() != numPointRecords) {
+                return null;
+            }
+            LasSpatialIndex index = new LasSpatialIndex(buf.getInt());
+            int numBlocks = buf.getInt();
+            if (inChannel.size() != HEADER_LENGTH + (long) numBlocks * BLOCK_RECORD_LENGTH) {
+                return null;
+            }
+            buf = ByteBuffer.allocate(numBlocks * BLOCK_RECORD_LENGTH);
+            buf.order(ByteOrder.LITTLE_ENDIAN);
+            inChannel.read(buf, HEADER_LENGTH);
+            buf.rewind();
+            for (int i = 0; i < numBlocks; i++) {
+                long firstPoint = buf.getLong();
+                int n = buf.getInt();
+                double minX = buf.getDouble();
+                double minY = buf.getDouble();
+                double maxX = buf.getDouble();
+                double maxY = buf.getDouble();
+                index.addBlock(firstPoint, n, new BoundingBox(minX, minY, maxX, maxY));
+            }
+            return index;
+        } catch (IOException e) {
+            return null;
+        } finally {
+            if (rIn != null) {
+                try {
+                    rIn.close();
+                } catch (IOException e) {
+                }
+            }
+        }
+    }
+
+    /**
+     * Writes the index to the sidecar file of a LAS file. The LAS file must
+     * already have been written, since its length and modification time are
+     * recorded in the index.
+     *
+     * @param lasFileName The name of the LAS file.
+     * @throws IOException
+     */
+    public void write(String lasFileName) throws IOException {
+        File lasFile = new File(lasFileName);
+        ByteBuffer buf = ByteBuffer.allocate(HEADER_LENGTH + getNumBlocks() * BLOCK_RECORD_LENGTH);
+        buf.order(ByteOrder.LITTLE_ENDIAN);
+        buf.put(SIGNATURE);
+        buf.putInt(VERSION);
+        buf.putLong(lasFile.length());
+        buf.putLong(lasFile.lastModified());
+        buf.putLong(numPointRecords);
+        buf.putInt(blockSize);
+        buf.putInt(getNumBlocks());
+        for (int i = 0; i < getNumBlocks(); i++) {
+            BoundingBox bb = extents.get(i);
+            buf.putLong(firstPoints.get(i));
+            buf.putInt(numPoints.get(i));
+            buf.putDouble(bb.getMinX());
+            buf.putDouble(bb.getMinY());
+            buf.putDouble(bb.getMaxX());
+            buf.putDouble(bb.getMaxY());
+        }
+        try (BufferedOutputStream out = new BufferedOutputStream(
+                new FileOutputStream(getIndexFileName(lasFileName)))) {
+            out.write(buf.array());
+        }
+    }
+
+    /**
+     * Adds a block of consecutive point records to the index.
+     *
+     * @param firstPoint The index of the first point record in the block.
+     * @param n The number of point records in the block.
+     * @param extent The bounding box of the points in the block.
+     */
+    public final void addBlock(long firstPoint, int n, BoundingBox extent) {
+        firstPoints.add(firstPoint);
+        numPoints.add(n);
+        extents.add(extent);
+        numPointRecords += n;
+    }
+
+    public int getBlockSize() {
+        return blockSize;
+    }
+
+    public int getNumBlocks() {
+        return firstPoints.size();
+    }
+
+    public long getFirstPoint(int block) {
+        return firstPoints.get(block);
+    }
+
+    public int getNumPoints(int block) {
+        return numPoints.get(block);
+    }
+
+    public BoundingBox getExtent(int block) {
+        return extents.get(block);
+    }
+
+    /**
+     * Finds the blocks that may contain points within a bounding box.
+     *
+     * @param bb The bounding box.
+     * @return The block numbers, in order of their first point record.
+     */
+    public ArrayList<Integer> getBlocksInBoundingBox(BoundingBox bb) {
+        ArrayList<Integer> ret = new ArrayList<>();
+        for (int i = 0; i < getNumBlocks(); i++) {
+            if (extents.get(i).overlaps(bb)) {
+                ret.add(i);
+            }
+        }
+        return ret;
+    }
+}
@@ -195,7 +195,7 @@ public class RasterLayerInfo implements MapLayer {
     }
 
     public int getColFromXCoordinate(double x) {
-        return source.getRowFromYCoordinate(x);
+        return source.getColumnFromXCoordinate(x);
     }
 
     public double getXCoordinateFromColumn(int col) {
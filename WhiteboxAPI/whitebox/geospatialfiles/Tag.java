@@ -64,6 +64,7 @@ class Tag implements Comparable {
     static public final Tag ResolutionUnit = new Tag("ResolutionUnit", 296);
     static public final Tag PageNumber = new Tag("PageNumber", 297);
     static public final Tag Software = new Tag("Software", 305);
+    static public final Tag Predictor = new Tag("Predictor", 317);
     static public final Tag ColorMap = new Tag("ColorMap", 320);
     static public final Tag TileWidth = new Tag("TileWidth", 322);
     static public final Tag TileLength = new Tag("TileLength", 323);
@@ -81,6 +82,7 @@ class Tag implements Comparable {
     static public final Tag GeoDoubleParamsTag = new Tag("GeoDoubleParamsTag", 34736);
     static public final Tag GeoAsciiParamsTag = new Tag("GeoAsciiParamsTag", 34737);
     // Gdal tiff tags
+    static public final Tag GDALMetadata = new Tag("GDALMetadataTag", 42112);
     static public final Tag GDALNoData = new Tag("GDALNoDataTag", 42113);
 
     /**
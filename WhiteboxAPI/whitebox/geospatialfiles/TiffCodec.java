@@ -0,0 +1,264 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package whitebox.geospatialfiles;
+
+import java.io.IOException;
+import java.util.zip.DataFormatException;
+import java.util.zip.Inflater;
+
+/**
+ * Decompression and predictor decoding of the strips and tiles of a TIFF
+ * file. Each method decodes into a buffer supplied by the caller, so that a
+ * reader can reuse its buffers from one strip or tile to the next.
+ */
+final class TiffCodec {
+
+    static final int NONE = 1;
+    static final int LZW = 5;
+    static final int DEFLATE = 8;
+    static final int DEFLATE_OLD = 32946;
+    static final int PACKBITS = 32773;
+
+    private TiffCodec() {
+    }
+
+    static boolean isSupportedCompression(int compression) {
+        return compression == NONE || compression == LZW || compression == DEFLATE
+                || compression == DEFLATE_OLD || compression == PACKBITS;
+    }
+
+    /**
+     * Decompresses a strip or tile.
+     *
+     * @param compression The TIFF compression type.
+     * @param src The compressed bytes.
+     * @param srcLength The number of compressed bytes.
+     * @param dst The buffer for the decompressed bytes.
+     * @param dstLength The expected number of decompressed bytes.
+     * @param tables The string tables used by LZW decoding, of length 4096.
+     * @param inflater The inflater used by Deflate decoding.
+     * @return The number of bytes decompressed, which is less than dstLength
+     * if the data are truncated.
+     * @throws IOException if the data are not valid.
+     */
+    static int decompress(int compression, byte[] src, int srcLength, byte[] dst,
+            int dstLength, int[][] tables, Inflater inflater) throws IOException {
+        switch (compression) {
+            case NONE:
+                int n = Math.min(srcLength, dstLength);
+                System.arraycopy(src, 0, dst, 0, n);
+                return n;
+            case LZW:
+                return decodeLzw(src, srcLength, dst, dstLength, tables[0], tables[1]);
+            case DEFLATE:
+            case DEFLATE_OLD:
+                return inflate(src, srcLength, dst, dstLength, inflater);
+            case PACKBITS:
+                return unpackBits(src, srcLength, dst, dstLength);
+            default:
+                throw new IOException("Compression type " + compression + " is not supported.");
+        }
+    }
+
+    // Every string in the LZW table is a run of bytes that has already been
+    // written to the output, so the table holds only the offset and length of
+    // each string in dst and strings are copied with System.arraycopy.
+    private static int decodeLzw(byte[] src, int srcLength, byte[] dst, int dstLength,
+            int[] offsets, int[] lengths) throws IOException {
+        int srcPos = 0;
+        int pos = 0;
+        int bitBuffer = 0;
+        int numBits = 0;
+        int codeLength = 9;
+        int nextCode = 258;
+        int prevOffset = -1;
+        int prevLength = 0;
+        while (pos < dstLength) {
+            while (numBits < codeLength) {
+                if (srcPos >= srcLength) {
+                    return pos;
+                }
+                bitBuffer = (bitBuffer << 8) | (src[srcPos++] & 0xFF);
+                numBits += 8;
+            }
+            numBits -= codeLength;
+            int code = (bitBuffer >>> numBits) & ((1 << codeLength) - 1);
+            bitBuffer &= (1 << numBits) - 1;
+
+            if (code == 257) { // end of information
+                break;
+            }
+            if (code == 256) { // clear the table
+                codeLength = 9;
+                nextCode = 258;
+                prevOffset = -1;
+                continue;
+            }
+
+            int length;
+            if (code < 256) {
+                dst[pos] = (byte) code;
+                length = 1;
+            } else if (code < nextCode && prevOffset >= 0) {
+                length = Math.min(lengths[code], dstLength - pos);
+                System.arraycopy(dst, offsets[code], dst, pos, length);
+            } else if (code == nextCode && prevOffset >= 0) {
+                // the string is the previous string followed by its first byte
+                length = Math.min(prevLength, dstLength - pos);
+                System.arraycopy(dst, prevOffset, dst, pos, length);
+                if (pos + length < dstLength) {
+                    dst[pos + length] = dst[prevOffset];
+                    length++;
+                }
+            } else {
+                throw new IOException("Invalid LZW code " + code + ".");
+            }
+
+            if (prevOffset >= 0 && nextCode < 4096) {
+                // the previous string followed by the first byte of this one
+                offsets[nextCode] = prevOffset;
+                lengths[nextCode] = prevLength + 1;
+                nextCode++;
+                if (nextCode == 511) {
+                    codeLength = 10;
+                } else if (nextCode == 1023) {
+                    codeLength = 11;
+                } else if (nextCode == 2047) {
+                    codeLength = 12;
+                }
+            }
+            prevOffset = pos;
+            prevLength = length;
+            pos += length;
+        }
+        return pos;
+    }
+
+    private static int inflate(byte[] src, int srcLength, byte[] dst, int dstLength,
+            Inflater inflater) throws IOException {
+        inflater.reset();
+        inflater.setInput(src, 0, srcLength);
+        int pos = 0;
+        try {
+            while (pos < dstLength && !inflater.finished()) {
+                int n = inflater.inflate(dst, pos, dstLength - pos);
+                if (n == 0 && (inflater.needsInput() || inflater.needsDictionary())) {
+                    break;
+                }
+                pos += n;
+            }
+        } catch (DataFormatException e) {
+            throw new IOException("Invalid Deflate data: " + e.getMessage(), e);
+        }
+        return pos;
+    }
+
+    private static int unpackBits(byte[] src, int srcLength, byte[] dst, int dstLength) {
+        int srcPos = 0;
+        int pos = 0;
+        while (srcPos < srcLength && pos < dstLength) {
+            int n = src[srcPos++];
+            if (n >= 0) { // n + 1 literal bytes
+                int length = Math.min(Math.min(n + 1, srcLength - srcPos), dstLength - pos);
+                System.arraycopy(src, srcPos, dst, pos, length);
+                srcPos += n + 1;
+                pos += length;
+            } else if (n != -128 && srcPos < srcLength) { // a byte repeated 1 - n times
+                byte b = src[srcPos++];
+                int end = Math.min(pos + 1 - n, dstLength);
+                while (pos < end) {
+                    dst[pos++] = b;
+                }
+            }
+        }
+        return pos;
+    }
+
+    // The horizontal differencing predictor (Predictor = 2) stores each sample
+    // as the difference from the same sample of the previous pixel in the
+    // row. It is undone with a running sum over each row, which wraps around
+    // in the width of the sample, as the differences were taken.
+    static void undoHorizontalDifferencing(byte[] data, int rowLength, int numRows, int stride) {
+        for (int r = 0; r < numRows; r++) {
+            int start = r * rowLength;
+            int end = start + rowLength;
+            for (int i = start + stride; i < end; i++) {
+                data[i] += data[i - stride];
+            }
+        }
+    }
+
+    static void undoHorizontalDifferencing(short[] data, int rowLength, int numRows, int stride) {
+        for (int r = 0; r < numRows; r++) {
+            int start = r * rowLength;
+            int end = start + rowLength;
+            for (int i = start + stride; i < end; i++) {
+                data[i] += data[i - stride];
+            }
+        }
+    }
+
+    static void undoHorizontalDifferencing(int[] data, int rowLength, int numRows, int stride) {
+        for (int r = 0; r < numRows; r++) {
+            int start = r * rowLength;
+            int end = start + rowLength;
+            for (int i = start + stride; i < end; i++) {
+                data[i] += data[i - stride];
+            }
+        }
+    }
+
+    static void undoHorizontalDifferencing(long[] data, int rowLength, int numRows, int stride) {
+        for (int r = 0; r < numRows; r++) {
+            int start = r * rowLength;
+            int end = start + rowLength;
+            for (int i = start + stride; i < end; i++) {
+                data[i] += data[i - stride];
+            }
+        }
+    }
+
+    /**
+     * Undoes the floating point predictor (Predictor = 3). Within each row,
+     * the bytes of the samples are stored in planes, most significant byte
+     * first, and differenced byte by byte. The samples are written to dst in
+     * big-endian order, whatever the byte order of the file.
+     *
+     * @param src The decompressed bytes, which are overwritten.
+     * @param dst The buffer for the decoded samples.
+     * @param samplesPerRow The number of samples in each row.
+     * @param numRows The number of rows.
+     * @param bytesPerSample The size of each sample, 4 or 8.
+     * @param stride The number of samples in each pixel.
+     */
+    static void undoFloatingPointPredictor(byte[] src, byte[] dst, int samplesPerRow,
+            int numRows, int bytesPerSample, int stride) {
+        int rowBytes = samplesPerRow * bytesPerSample;
+        undoHorizontalDifferencing(src, rowBytes, numRows, stride);
+        for (int r = 0; r < numRows; r++) {
+            int start = r * rowBytes;
+            for (int b = 0; b < bytesPerSample; b++) {
+                int plane = start + b * samplesPerRow;
+                int out = start + b;
+                for (int i = 0; i < samplesPerRow; i++) {
+                    dst[out] = src[plane + i];
+                    out += bytesPerSample;
+                }
+            }
+        }
+    }
+}
@@ -17,6 +17,7 @@
 package whitebox.geospatialfiles;
 
 import java.io.File;
+import java.io.IOException;
 import java.io.RandomAccessFile;
 import java.nio.ByteBuffer;
 import java.nio.DoubleBuffer;
@@ -24,11 +25,14 @@ import java.nio.FloatBuffer;
 import java.nio.IntBuffer;
 import java.nio.ShortBuffer;
 import java.nio.channels.FileChannel;
+import java.nio.channels.FileChannel.MapMode;
 import java.util.Arrays;
 
 /**
  * The whiteboxRaster is used to manipulate Whitebox GAT raster files (.dep and
- * .tas).
+ * .tas). A new raster is written to temporary files that are moved into place
+ * when it is closed, so that a tool that fails part of the way through never
+ * leaves a partly written raster behind.
  *
  * @author Dr. John Lindsay <jlindsay@uoguelph.ca>
  */
@@ -43,6 +47,12 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
     private long blockEndingCell = -1;
     private double initialValue;
     private boolean isDirty = false;
+    // The data file of a memory-mapped raster is mapped in segments of 2^30
+    // bytes, the largest power of two that a single mapping can hold. Cells
+    // never straddle two segments because the cell sizes are powers of two.
+    private ByteBuffer[] mappedSegments = null;
+    private static final int SEGMENT_SHIFT = 30;
+    private static final long SEGMENT_MASK = (1L << SEGMENT_SHIFT) - 1;
     /**
      * Set to false if the header and data files (.dep and .tas) should be
      * deleted when the object is closed.
@@ -50,9 +60,75 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
     public boolean isTemporaryFile = false;
     /**
      * Set to true when the getValue function should reflect beyond the edges.
+     * This is equivalent to an edge mode of EdgeMode.REFLECT and takes
+     * precedence over the edge mode.
      */
     public boolean isReflectedAtEdges = false;
 
+    /**
+     * Determines the value that the getValue function returns for a cell
+     * beyond the edges of the grid. NODATA returns the NoData value,
+     * REPLICATE returns the value of the nearest edge cell, REFLECT returns
+     * the value of the cell mirrored about the edge (the edge cell itself
+     * is repeated), and WRAP returns the value of the cell on the opposite
+     * side of the grid, as though the grid were tiled.
+     */
+    public enum EdgeMode {
+
+        NODATA, REPLICATE, REFLECT, WRAP;
+
+        /**
+         * Converts a tool parameter into an edge mode. The parameter may be
+         * the name of an edge mode in any case, or, for compatibility with
+         * the earlier 'reflect at borders' parameters, "true" (REFLECT) or
+         * "false" (NODATA).
+         *
+         * @param str The parameter.
+         * @return The edge mode.
+         * @throws IllegalArgumentException if the parameter is not
+         * recognized.
+         */
+        public static EdgeMode fromString(String str) {
+            String s = str.trim().toLowerCase();
+            switch (s) {
+                case "true":
+                    return REFLECT;
+                case "false":
+                    return NODATA;
+                default:
+                    for (EdgeMode mode : values()) {
+                        if (mode.name().toLowerCase().equals(s)) {
+                            return mode;
+                        }
+                    }
+                    throw new IllegalArgumentException("Unrecognized edge mode: " + str);
+            }
+        }
+    }
+
+    private EdgeMode edgeMode = EdgeMode.NODATA;
+
+    /**
+     * Retrieves the edge mode, which determines the values of cells beyond
+     * the edges of the grid.
+     *
+     * @return The edge mode.
+     */
+    public EdgeMode getEdgeMode() {
+        return isReflectedAtEdges ? EdgeMode.REFLECT : edgeMode;
+    }
+
+    /**
+     * Sets the edge mode, which determines the values of cells beyond the
+     * edges of the grid. The default is EdgeMode.NODATA.
+     *
+     * @param edgeMode The edge mode.
+     */
+    public void setEdgeMode(EdgeMode edgeMode) {
+        this.edgeMode = edgeMode;
+        isReflectedAtEdges = (edgeMode == EdgeMode.REFLECT);
+    }
+
     // ************************
     // Constructors
     // ************************
@@ -113,12 +189,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
         headerFile = HeaderFile;
         dataFile = headerFile.replace(".dep", ".tas");
         statsFile = headerFile.replace(".dep", ".wstat");
-        File f1 = new File(this.headerFile);
-        f1.delete();
-        f1 = new File(this.dataFile);
-        f1.delete();
-        f1 = new File(this.statsFile);
-        f1.delete();
+        beginPendingFiles();
         initialValue = InitialValue;
         setFileAccess(FileAccess);
         setPropertiesUsingAnotherRaster(BaseRasterHeader, dataType);
@@ -146,12 +217,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
         headerFile = HeaderFile;
         dataFile = headerFile.replace(".dep", ".tas");
         statsFile = headerFile.replace(".dep", ".wstat");
-        File f1 = new File(this.headerFile);
-        f1.delete();
-        f1 = new File(this.dataFile);
-        f1.delete();
-        f1 = new File(this.statsFile);
-        f1.delete();
+        beginPendingFiles();
         initialValue = InitialValue;
         setFileAccess(FileAccess);
         setBufferSize(BufferSize);
@@ -165,12 +231,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
         headerFile = HeaderFile;
         dataFile = headerFile.replace(".dep", ".tas");
         statsFile = headerFile.replace(".dep", ".wstat");
-        File f1 = new File(this.headerFile);
-        f1.delete();
-        f1 = new File(this.dataFile);
-        f1.delete();
-        f1 = new File(this.statsFile);
-        f1.delete();
+        beginPendingFiles();
 
         this.north = north;
         this.south = south;
@@ -189,6 +250,58 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
         //createNewDataFile();
 
     }
+    /**
+     * Opens an existing raster for reading with its data file mapped into
+     * memory. Values are read from the mapped file as they are requested
+     * rather than from blocks that are copied into memory, so opening the
+     * raster is fast and only the parts of the file that are read occupy
+     * memory. This suits tools that make scattered reads of a small part of a
+     * large raster, e.g. tracing flowpaths or cost pathways. The values of a
+     * mapped raster may also be read by several threads at once, e.g. with
+     * Parallel.ForRows. If the data file cannot be mapped, the raster is
+     * opened normally; isMemoryMapped() reports which is the case.
+     *
+     * @param HeaderFile The name of the WhiteboxRaster header file.
+     * @return A read-only WhiteboxRaster.
+     */
+    public static WhiteboxRaster openMemoryMapped(String HeaderFile) {
+        WhiteboxRaster raster = new WhiteboxRaster(HeaderFile, "r");
+        raster.mapDataFile();
+        return raster;
+    }
+
+    private void mapDataFile() {
+        try (RandomAccessFile rIn = new RandomAccessFile(dataFile, "r");
+                FileChannel inChannel = rIn.getChannel()) {
+            long length = (long) numberRows * numberColumns * cellSizeInBytes;
+            if (inChannel.size() < length) {
+                return;
+            }
+            int numSegments = (int) ((length + SEGMENT_MASK) >>> SEGMENT_SHIFT);
+            ByteBuffer[] segments = new ByteBuffer[numSegments];
+            for (int i = 0; i < numSegments; i++) {
+                long start = (long) i << SEGMENT_SHIFT;
+                segments[i] = inChannel.map(MapMode.READ_ONLY, start,
+                        Math.min(length - start, 1L << SEGMENT_SHIFT)).order(byteOrder);
+            }
+            // the mappings remain valid after the channel is closed
+            mappedSegments = segments;
+        } catch (IOException e) {
+            // e.g. a 32-bit JVM without the address space for the file
+            mappedSegments = null;
+        }
+    }
+
+    /**
+     * Indicates whether the data file is mapped into memory.
+     *
+     * @return true if the raster was opened with openMemoryMapped and its data
+     * file could be mapped.
+     */
+    public boolean isMemoryMapped() {
+        return mappedSegments != null;
+    }
+
     // ***********************************
     // Property getter and setter methods.
     // ***********************************
@@ -396,6 +509,14 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
             long startingCell = row * numberColumns;
             long endingCell = startingCell + numberColumns - 1;
 
+            if (isScaled()) {
+                double[] stored = new double[numberColumns];
+                for (int i = 0; i < numberColumns; i++) {
+                    stored[i] = toStored(vals[i]);
+                }
+                vals = stored;
+            }
+
             rOut = new RandomAccessFile(dataFile, "rw");
             outChannel = rOut.getChannel();
             outChannel.position(startingCell * cellSizeInBytes);
@@ -485,6 +606,30 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
         //try {
         if (column >= 0 && column < numberColumns && row >= 0 && row < numberRows) {
 
+            if (mappedSegments != null) {
+                long offset = ((long) row * numberColumns + column) * cellSizeInBytes;
+                ByteBuffer segment = mappedSegments[(int) (offset >>> SEGMENT_SHIFT)];
+                int i = (int) (offset & SEGMENT_MASK);
+                double z;
+                switch (dataType) {
+                    case DOUBLE:
+                        z = segment.getDouble(i);
+                        break;
+                    case FLOAT:
+                        z = segment.getFloat(i);
+                        break;
+                    case I32:
+                        z = segment.getInt(i);
+                        break;
+                    case INTEGER:
+                        z = segment.getShort(i);
+                        break;
+                    default:
+                        z = segment.get(i) & 0xFF;
+                }
+                return fromStored(z);
+            }
+
             if (blockEndingCell < 0) {
                 readDataBlock();
             }
@@ -533,8 +678,17 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
             }
             return grid[(int) (cellNum - blockStartingCell)];
         } else {
-            if (!isReflectedAtEdges) {
-                return noDataValue;
+            switch (getEdgeMode()) {
+                case NODATA:
+                    return noDataValue;
+                case REPLICATE:
+                    row = Math.max(0, Math.min(numberRows - 1, row));
+                    column = Math.max(0, Math.min(numberColumns - 1, column));
+                    return getValue(row, column);
+                case WRAP:
+                    row = ((row % numberRows) + numberRows) % numberRows;
+                    column = ((column % numberColumns) + numberColumns) % numberColumns;
+                    return getValue(row, column);
             }
 
             // if you get to this point, it is reflected at the edges
@@ -565,6 +719,60 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
 //        }
     }
 
+    /**
+     * Estimates the value at a fractional grid position by bilinear
+     * interpolation between the centres of the four surrounding cells. Whole
+     * row and column numbers refer to cell centres, i.e. (0.0, 0.0) is the
+     * centre of the upper-left cell. Positions between the centres of the
+     * edge cells and the edge of the grid take the values of the edge cells.
+     *
+     * @param row The zero-based fractional row number.
+     * @param column The zero-based fractional column number.
+     * @return The interpolated value, or NoData if the position is outside of
+     * the grid or any of the surrounding cells is NoData.
+     */
+    public double getBilinearValue(double row, double column) {
+        if (!(row >= -0.5 && row <= numberRows - 0.5
+                && column >= -0.5 && column <= numberColumns - 0.5)) {
+            return noDataValue;
+        }
+        double r = Math.min(Math.max(row, 0), numberRows - 1);
+        double c = Math.min(Math.max(column, 0), numberColumns - 1);
+        int r0 = (int) Math.floor(r);
+        int c0 = (int) Math.floor(c);
+        double dr = r - r0;
+        double dc = c - c0;
+        // cells with a weight of zero are not needed
+        int r1 = dr > 0 ? r0 + 1 : r0;
+        int c1 = dc > 0 ? c0 + 1 : c0;
+        double z00 = getValue(r0, c0);
+        double z01 = getValue(r0, c1);
+        double z10 = getValue(r1, c0);
+        double z11 = getValue(r1, c1);
+        if (z00 == noDataValue || z01 == noDataValue
+                || z10 == noDataValue || z11 == noDataValue) {
+            return noDataValue;
+        }
+        return z00 * (1 - dr) * (1 - dc) + z01 * (1 - dr) * dc
+                + z10 * dr * (1 - dc) + z11 * dr * dc;
+    }
+
+    /**
+     * Estimates the value at a point in map coordinates by bilinear
+     * interpolation between the centres of the four surrounding cells.
+     *
+     * @param x The x-coordinate (easting) of the point.
+     * @param y The y-coordinate (northing) of the point.
+     * @return The interpolated value, or NoData if the point is outside of
+     * the grid or any of the surrounding cells is NoData.
+     * @see #getBilinearValue(double, double)
+     */
+    public double getValueAt(double x, double y) {
+        double row = (north - y) / getCellSizeY() - 0.5;
+        double column = (x - west) / getCellSizeX() - 0.5;
+        return getBilinearValue(row, column);
+    }
+
     /**
      * Sets the value of a specified cell in the raster grid.
      *
@@ -897,6 +1105,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                 writeLength = (int) numberCells;
             }
             long numCellsWritten = 0;
+            double initialValue = toStored(this.initialValue);
 
             rOut = new RandomAccessFile(dataFile, "rws");
 
@@ -1094,6 +1303,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                     grid[j] = whitebox.utilities.Unsigned.getUnsignedByte(buf, j);
                 }
             }
+            fromStored(grid);
 
         } catch (Exception e) {
             if (communicator != null) {
@@ -1174,6 +1384,14 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                 outChannel.position(blockStartingCell * cellSizeInBytes);
                 int writeLengthInCells = (int) (blockEndingCell - blockStartingCell + 1);
 
+                double[] stored = grid;
+                if (isScaled()) {
+                    stored = new double[grid.length];
+                    for (int j = 0; j < grid.length; j++) {
+                        stored[j] = toStored(grid[j]);
+                    }
+                }
+
                 /*long startPos = blockStartingCell * cellSizeInBytes;
                  FileChannel fc = new RandomAccessFile(dataFile, "rw").getChannel();
                  FloatBuffer fb = fc.map(FileChannel.MapMode.READ_WRITE, startPos, 
@@ -1188,13 +1406,13 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                     buf = ByteBuffer.allocate(cellSizeInBytes * writeLengthInCells);
                     buf.order(byteOrder);
                     DoubleBuffer db = buf.asDoubleBuffer();
-                    db.put(grid);
+                    db.put(stored);
                     db = null;
                     outChannel.write(buf);
                 } else if (dataType == DataType.FLOAT) { //.equals("float")) {
                     float[] fa = new float[writeLengthInCells];
                     for (int j = 0; j < writeLengthInCells; j++) {
-                        fa[j] = (float) grid[j];
+                        fa[j] = (float) stored[j];
                     }
                     buf = ByteBuffer.allocateDirect(cellSizeInBytes * writeLengthInCells);
                     buf.order(byteOrder);
@@ -1206,7 +1424,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                 } else if (dataType == DataType.INTEGER) { //.equals("integer")) {
                     short[] ia = new short[writeLengthInCells];
                     for (int j = 0; j < writeLengthInCells; j++) {
-                        ia[j] = (short) grid[j];
+                        ia[j] = (short) stored[j];
                     }
                     buf = ByteBuffer.allocate(cellSizeInBytes * writeLengthInCells);
                     buf.order(byteOrder);
@@ -1218,7 +1436,7 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
                 } else if (dataType == DataType.BYTE) { //.equals("byte")) {
                     byte[] ba = new byte[writeLengthInCells];
                     for (int j = 0; j < writeLengthInCells; j++) {
-                        ba[j] = (byte) grid[j];
+                        ba[j] = (byte) stored[j];
                     }
                     buf = ByteBuffer.wrap(ba);
                     ba = null;
@@ -1260,19 +1478,30 @@ public class WhiteboxRaster extends WhiteboxRasterBase {
     @Override
     public void close() {
         if (this.isTemporaryFile) {
-            File f1 = new File(this.headerFile);
-            f1.delete();
-            f1 = new File(this.dataFile);
-            f1.delete();
+            if (pendingHeaderFile != null) {
+                discardPendingFiles();
+            } else {
+                File f1 = new File(this.headerFile);
+                f1.delete();
+                f1 = new File(this.dataFile);
+                f1.delete();
+            }
         } else {
-            if (saveChanges) {
-                if (isDirty) {
-                    writeDataBlock();
+            try {
+                if (saveChanges) {
+                    if (isDirty) {
+                        writeDataBlock();
+                    }
+                    findMinAndMaxVals();
+                    writeHeaderFile();
                 }
-                findMinAndMaxVals();
-                writeHeaderFile();
+                commitFiles();
+            } finally {
+                // does nothing once the files are committed
+                discardPendingFiles();
             }
         }
         grid = null;
+        mappedSegments = null;
     }
 }
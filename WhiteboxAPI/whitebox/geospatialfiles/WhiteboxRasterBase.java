@@ -17,9 +17,17 @@
 package whitebox.geospatialfiles;
 
 import java.io.*;
+import java.lang.ref.WeakReference;
 import java.nio.*;
 import java.nio.channels.FileChannel;
+import java.nio.channels.FileLock;
+import java.nio.file.AtomicMoveNotSupportedException;
+import java.nio.file.Files;
+import java.nio.file.StandardCopyOption;
 import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.logging.Level;
 import whitebox.interfaces.Communicator;
 
 /**
@@ -186,6 +194,7 @@ public class WhiteboxRasterBase {
      */
     public void setNorth(float North) {
         north = North;
+        cellSizeY = 0;
     }
 
     protected double south;
@@ -206,6 +215,7 @@ public class WhiteboxRasterBase {
      */
     public void setSouth(float South) {
         south = South;
+        cellSizeY = 0;
     }
 
     protected double west;
@@ -226,6 +236,7 @@ public class WhiteboxRasterBase {
      */
     public void setWest(float West) {
         west = West;
+        cellSizeX = 0;
     }
 
     protected double east;
@@ -246,6 +257,7 @@ public class WhiteboxRasterBase {
      */
     public void setEast(float East) {
         east = East;
+        cellSizeX = 0;
     }
 
     protected int numberColumns;
@@ -535,6 +547,133 @@ public class WhiteboxRasterBase {
         noDataValue = value;
     }
 
+    protected double scaleFactor = 1.0;
+    protected double offset = 0.0;
+
+    /**
+     * Retrieves the scale factor that converts the stored values of this
+     * raster to real-world values, which are stored value * scale factor +
+     * offset. It is 1 unless the raster is a scaled raster.
+     *
+     * @return The scale factor.
+     */
+    public double getScaleFactor() {
+        return scaleFactor;
+    }
+
+    /**
+     * Retrieves the offset that is added to the scaled stored values of this
+     * raster to give real-world values. It is 0 unless the raster is a
+     * scaled raster.
+     *
+     * @return The offset.
+     */
+    public double getOffset() {
+        return offset;
+    }
+
+    /**
+     * Indicates whether the values of this raster are stored with a scale
+     * factor or offset. The values read from and written to a scaled raster
+     * are always real-world values; they are converted to and from the stored
+     * values as the file is read and written.
+     *
+     * @return true if the scale factor is not 1 or the offset is not 0.
+     */
+    public boolean isScaled() {
+        return scaleFactor != 1.0 || offset != 0.0;
+    }
+
+    /**
+     * Sets the scale factor and offset used to store the values of this
+     * raster, usually as INTEGER or BYTE data. A stored value z represents the
+     * real-world value z * scale + offset, and a value that is written is
+     * stored as the nearest whole number to (value - offset) / scale, within
+     * the range of the data type, so that the values read back differ from
+     * those written by no more than half of the scale factor. The NoData
+     * value is stored unchanged and should be a stored value that no
+     * real-world value maps to. The scale factor and offset should be set
+     * before any values are written.
+     *
+     * @param scale The scale factor, which cannot be zero.
+     * @param offset The offset.
+     */
+    public void setScaleAndOffset(double scale, double offset) {
+        if (scale == 0 || Double.isNaN(scale) || Double.isInfinite(scale)
+                || Double.isNaN(offset) || Double.isInfinite(offset)) {
+            throw new IllegalArgumentException("The scale factor must be a non-zero number and the offset a number.");
+        }
+        this.scaleFactor = scale;
+        this.offset = offset;
+    }
+
+    /**
+     * Converts a real-world value to the value stored in the file. For
+     * INTEGER, BYTE and I32 data the value is rounded and kept within the
+     * range of the data type, never taking the NoData value.
+     *
+     * @param value A real-world value.
+     * @return The stored value.
+     */
+    protected double toStored(double value) {
+        if (value == noDataValue || !isScaled()) {
+            return value;
+        }
+        double z = (value - offset) / scaleFactor;
+        double lowest;
+        double highest;
+        switch (dataType) {
+            case INTEGER:
+                lowest = Short.MIN_VALUE;
+                highest = Short.MAX_VALUE;
+                break;
+            case BYTE:
+                lowest = 0;
+                highest = 255;
+                break;
+            case I32:
+                lowest = Integer.MIN_VALUE;
+                highest = Integer.MAX_VALUE;
+                break;
+            default:
+                return z;
+        }
+        z = Math.max(lowest, Math.min(highest, Math.rint(z)));
+        if (z == noDataValue) {
+            z += (z == highest) ? -1 : 1;
+        }
+        return z;
+    }
+
+    /**
+     * Converts a value stored in the file to its real-world value.
+     *
+     * @param stored A stored value.
+     * @return The real-world value, or NoData.
+     */
+    protected double fromStored(double stored) {
+        if (stored == noDataValue || !isScaled()) {
+            return stored;
+        }
+        return stored * scaleFactor + offset;
+    }
+
+    /**
+     * Converts an array of stored values to real-world values in place.
+     *
+     * @param values The stored values.
+     */
+    protected void fromStored(double[] values) {
+        if (!isScaled()) {
+            return;
+        }
+        for (int i = 0; i < values.length; i++) {
+            if (values[i] != noDataValue) {
+                values[i] = values[i] * scaleFactor + offset;
+            }
+        }
+    }
+
     protected double cellSizeX = 0;
 
     /**
@@ -563,6 +702,27 @@ public class WhiteboxRasterBase {
         return cellSizeY;
     }
 
+    /**
+     * Tests whether another raster covers the same grid as this one, i.e.
+     * whether it has the same numbers of rows and columns and the same
+     * edges. The edges are compared to within a hundredth of a cell, since
+     * they may have been rounded when they were written.
+     *
+     * @param other The raster to compare with this one.
+     * @return true if the grid cells of the two rasters coincide.
+     */
+    public boolean isCoregisteredWith(WhiteboxRasterBase other) {
+        if (other.getNumberRows() != numberRows || other.getNumberColumns() != numberColumns) {
+            return false;
+        }
+        double toleranceX = 0.01 * Math.abs(getCellSizeX());
+        double toleranceY = 0.01 * Math.abs(getCellSizeY());
+        return Math.abs(other.getNorth() - north) <= toleranceY
+                && Math.abs(other.getSouth() - south) <= toleranceY
+                && Math.abs(other.getEast() - east) <= toleranceX
+                && Math.abs(other.getWest() - west) <= toleranceX;
+    }
+
     protected ByteOrder byteOrder = java.nio.ByteOrder.nativeOrder(); // "LITTLE_ENDIAN";
 
     /**
@@ -770,8 +930,16 @@ public class WhiteboxRasterBase {
         boolean byteOrderRead = false;
         String delimiter = "\t";
         try {
+            // A raster that is still being created by this process is read
+            // from its temporary files.
+            String file = this.headerFile;
+            WhiteboxRasterBase writer = findPendingWriter(this.headerFile);
+            if (writer != null && writer != this) {
+                file = writer.pendingHeaderFile;
+                this.dataFile = writer.dataFile;
+            }
             // Open the file that is the first command line parameter
-            FileInputStream fstream = new FileInputStream(this.headerFile);
+            FileInputStream fstream = new FileInputStream(file);
             // Get the object of DataInputStream
             in = new DataInputStream(fstream);
 
@@ -793,9 +961,9 @@ public class WhiteboxRasterBase {
                     }
                     int dataCol = str.length - 1;
                     if (str[0].toLowerCase().contains("min:") && (!str[0].toLowerCase().contains("display"))) {
-                        this.minimumValue = Float.parseFloat(str[dataCol]);
+                        this.minimumValue = Double.parseDouble(str[dataCol]);
                     } else if (str[0].toLowerCase().contains("max:") && (!str[0].toLowerCase().contains("display"))) {
-                        this.maximumValue = Float.parseFloat(str[dataCol]);
+                        this.maximumValue = Double.parseDouble(str[dataCol]);
                     } else if (str[0].toLowerCase().contains("north")) {
                         this.north = Double.parseDouble(str[dataCol]);
                     } else if (str[0].toLowerCase().contains("south")) {
@@ -843,9 +1011,9 @@ public class WhiteboxRasterBase {
                     } else if (str[0].toLowerCase().contains("projection")) {
                         this.projection = str[dataCol];
                     } else if (str[0].toLowerCase().contains("display min")) {
-                        this.displayMinimum = Float.parseFloat(str[dataCol]);
+                        this.displayMinimum = Double.parseDouble(str[dataCol]);
                     } else if (str[0].toLowerCase().contains("display max")) {
-                        this.displayMaximum = Float.parseFloat(str[dataCol]);
+                        this.displayMaximum = Double.parseDouble(str[dataCol]);
                     } else if (str[0].toLowerCase().contains("preferred palette")) {
                         this.preferredPalette = str[dataCol].replace(".plt", ".pal");
                     } else if (str[0].toLowerCase().contains("byte order")) {
@@ -862,6 +1030,10 @@ public class WhiteboxRasterBase {
                         }
                     } else if (str[0].toLowerCase().contains("palette nonlinearity")) {
                         this.nonlinearity = Double.parseDouble(str[dataCol]);
+                    } else if (str[0].trim().toLowerCase().equals("scale factor:")) {
+                        this.scaleFactor = Double.parseDouble(str[dataCol]);
+                    } else if (str[0].trim().toLowerCase().equals("offset:")) {
+                        this.offset = Double.parseDouble(str[dataCol]);
                     }
                 }
                 if (this.displayMinimum == Float.POSITIVE_INFINITY) {
@@ -905,7 +1077,7 @@ public class WhiteboxRasterBase {
      */
     public void writeHeaderFile() {
         String str1 = null;
-        File file = new File(this.headerFile);
+        File file = new File(pendingHeaderFile != null ? pendingHeaderFile : this.headerFile);
         FileWriter fw = null;
         BufferedWriter bw = null;
         PrintWriter out = null;
@@ -986,11 +1158,18 @@ public class WhiteboxRasterBase {
             out.println(str1);
             str1 = "Palette Nonlinearity:\t" + this.nonlinearity;
             out.println(str1);
+            if (isScaled()) {
+                str1 = "Scale Factor:\t" + Double.toString(this.scaleFactor);
+                out.println(str1);
+                str1 = "Offset:\t" + Double.toString(this.offset);
+                out.println(str1);
+            }
 
             // Write the metadata entries to the file
             if (metadata.size() > 0) {
                 for (int i = 0; i < metadata.size(); i++) {
-                    str1 = "Metadata Entry:\t" + metadata.get(i).replaceAll(":", ";");
+                    str1 = "Metadata Entry:\t" + metadata.get(i).replaceAll(":", ";")
+                            .replaceAll("\\s*[\\r\\n]+\\s*", " ");
                     out.println(str1);
                 }
             }
@@ -1016,7 +1195,9 @@ public class WhiteboxRasterBase {
         BufferedReader br = null;
         try {
             // Open the file that is the first command line parameter
-            FileInputStream fstream = new FileInputStream(BaseRasterHeader);
+            WhiteboxRasterBase writer = findPendingWriter(BaseRasterHeader);
+            FileInputStream fstream = new FileInputStream(writer != null
+                    ? writer.pendingHeaderFile : BaseRasterHeader);
             // Get the object of DataInputStream
             in = new DataInputStream(fstream);
 
@@ -1206,6 +1387,7 @@ public class WhiteboxRasterBase {
                     retVals[j] = whitebox.utilities.Unsigned.getUnsignedByte(buf, j); //ba[j]);
                 }
             }
+            fromStored(retVals);
 
         } catch (IOException e) {
             System.err.println("Caught exception: " + e.toString());
@@ -1300,6 +1482,7 @@ public class WhiteboxRasterBase {
                     }
                     break;
             }
+            fromStored(retVals);
 
         } catch (Exception e) {
             System.err.println("Caught exception: " + e.toString());
@@ -1399,6 +1582,7 @@ public class WhiteboxRasterBase {
                     retVals[j] = whitebox.utilities.Unsigned.getUnsignedByte(buf, j); //ba[j]);
                 }
             }
+            fromStored(retVals);
 
         } catch (IOException e) {
             System.err.println("Caught exception: " + e.toString());
@@ -1449,6 +1633,7 @@ public class WhiteboxRasterBase {
             long cellNum = (long) (row) * numberColumns + column;
             outChannel.position(cellNum * cellSizeInBytes);
             int writeLengthInCells = 1;
+            value = toStored(value);
 
             if (dataType == DataType.DOUBLE) {
                 buf = ByteBuffer.allocate(cellSizeInBytes);
@@ -1581,6 +1766,259 @@ public class WhiteboxRasterBase {
         return retVal;
     }
 
+    /**
+     * Counts the valid cells of the raster in equal-width bins spanning the
+     * range of the data. The minimum and maximum values are found first, in a
+     * pass over the data, and stored in the minimumValue and maximumValue
+     * fields, so that the edges of bin i are min + i * (max - min) /
+     * numberOfBins and min + (i + 1) * (max - min) / numberOfBins. The
+     * maximum value is counted in the last bin.
+     *
+     * @param numberOfBins The number of bins.
+     * @return The number of valid cells in each bin.
+     */
+    public long[] getHistogram(int numberOfBins) {
+        if (numberOfBins < 1) {
+            throw new IllegalArgumentException("The number of bins must be at least 1.");
+        }
+        findMinAndMaxVals();
+        long[] counts = new long[numberOfBins];
+        double min = minimumValue;
+        double range = maximumValue - minimumValue;
+        for (int stack = 0; stack < numberStacks; stack++) {
+            for (int row = 0; row < numberRows; row++) {
+                double[] data = getRowValues(row, stack);
+                for (int col = 0; col < numberColumns; col++) {
+                    double z = data[col];
+                    if (z != noDataValue) {
+                        int bin = range > 0 ? (int) ((z - min) / range * numberOfBins) : 0;
+                        counts[Math.min(bin, numberOfBins - 1)]++;
+                    }
+                }
+            }
+        }
+        return counts;
+    }
+
+    /**
+     * Finds a percentile of the valid cells of the raster. See
+     * getPercentiles.
+     *
+     * @param percentile The percentile, from 0 to 100.
+     * @return The value of the percentile, or NaN if there are no valid cells.
+     */
+    public double getPercentile(double percentile) {
+        return getPercentiles(percentile)[0];
+    }
+
+    /**
+     * Finds percentiles of the valid cells of the raster, without sorting or
+     * holding the data in memory. The p-th percentile is the value of rank p
+     * / 100 * (n - 1) among the n valid values in ascending order,
+     * interpolated linearly between the neighbouring values, so that the 0th
+     * and 100th percentiles are the minimum and maximum. Unlike
+     * getPercentileValue, which interpolates within the bins of the
+     * histogram in the statistics file, the result is exact.
+     *
+     * The values of the ranks needed are found with streaming histograms.
+     * One pass over the data finds the range of the values and a second
+     * counts them in fixed bins, which shows the bin that holds each rank.
+     * Each further pass either collects the values of that bin, if there are
+     * few enough to sort, or counts them in finer bins. All of the
+     * percentiles are found in the same passes.
+     *
+     * @param percentiles The percentiles, each from 0 to 100.
+     * @return The value of each percentile, or NaN if there are no valid
+     * cells.
+     */
+    public double[] getPercentiles(double... percentiles) {
+        for (double p : percentiles) {
+            if (!(p >= 0 && p <= 100)) {
+                throw new IllegalArgumentException("Percentiles must be from 0 to 100.");
+            }
+        }
+        double[] result = new double[percentiles.length];
+
+        // the range and number of the valid values
+        double min = Double.POSITIVE_INFINITY;
+        double max = Double.NEGATIVE_INFINITY;
+        long n = 0;
+        for (int stack = 0; stack < numberStacks; stack++) {
+            for (int row = 0; row < numberRows; row++) {
+                double[] data = getRowValues(row, stack);
+                for (int col = 0; col < numberColumns; col++) {
+                    double z = data[col];
+                    if (z != noDataValue) {
+                        if (z < min) {
+                            min = z;
+                        }
+                        if (z > max) {
+                            max = z;
+                        }
+                        n++;
+                    }
+                }
+            }
+        }
+        if (n == 0) {
+            java.util.Arrays.fill(result, Double.NaN);
+            return result;
+        }
+
+        // the ranks that each percentile falls between
+        Map<Long, RankSearch> searchesByRank = new HashMap<>();
+        for (double p : percentiles) {
+            double rank = p / 100 * (n - 1);
+            long lower = (long) Math.floor(rank);
+            for (long r : new long[]{lower, Math.min(lower + 1, n - 1)}) {
+                if (!searchesByRank.containsKey(r)) {
+                    searchesByRank.put(r, new RankSearch(r, min, max, n));
+                }
+            }
+        }
+        RankSearch[] searches = searchesByRank.values().toArray(new RankSearch[0]);
+
+        boolean done = false;
+        while (!done) {
+            for (RankSearch s : searches) {
+                s.startPass();
+            }
+            for (int stack = 0; stack < numberStacks; stack++) {
+                for (int row = 0; row < numberRows; row++) {
+                    double[] data = getRowValues(row, stack);
+                    for (int col = 0; col < numberColumns; col++) {
+                        double z = data[col];
+                        if (z != noDataValue) {
+                            for (RankSearch s : searches) {
+                                s.add(z);
+                            }
+                        }
+                    }
+                }
+            }
+            done = true;
+            for (RankSearch s : searches) {
+                done &= s.endPass();
+            }
+        }
+
+        for (int i = 0; i < percentiles.length; i++) {
+            double rank = percentiles[i] / 100 * (n - 1);
+            long lower = (long) Math.floor(rank);
+            double fraction = rank - lower;
+            double z1 = searchesByRank.get(lower).value;
+            double z2 = searchesByRank.get(Math.min(lower + 1, n - 1)).value;
+            result[i] = fraction > 0 ? z1 + fraction * (z2 - z1) : z1;
+        }
+        return result;
+    }
+
+    /**
+     * The search for the value of one rank among the valid values, narrowed
+     * by one pass over the data at a time. The values from lo to hi, of
+     * which there are count, hold the rank, and there are below values less
+     * than lo.
+     */
+    private static class RankSearch {
+
+        private static final int NUM_BINS = 4096;
+        private static final int MAX_SORTED = 65536;
+
+        private final long rank;
+        private double lo;
+        private double hi;
+        private long below = 0;
+        private long count;
+        private boolean found = false;
+        private double value = Double.NaN;
+
+        // the bins of a counting pass, with the range of the values in each,
+        // or the values collected in a sorting pass
+        private long[] binCounts;
+        private double[] binMin;
+        private double[] binMax;
+        private double[] collected;
+        private int numCollected;
+
+        RankSearch(long rank, double lo, double hi, long count) {
+            this.rank = rank;
+            this.lo = lo;
+            this.hi = hi;
+            this.count = count;
+            if (lo == hi) {
+                found = true;
+                value = lo;
+            }
+        }
+
+        void startPass() {
+            if (found) {
+                return;
+            }
+            if (count <= MAX_SORTED) {
+                collected = new double[(int) count];
+                numCollected = 0;
+            } else {
+                binCounts = new long[NUM_BINS];
+                binMin = new double[NUM_BINS];
+                binMax = new double[NUM_BINS];
+                java.util.Arrays.fill(binMin, Double.POSITIVE_INFINITY);
+                java.util.Arrays.fill(binMax, Double.NEGATIVE_INFINITY);
+            }
+        }
+
+        void add(double z) {
+            if (found || z < lo || z > hi) {
+                return;
+            }
+            if (collected != null) {
+                collected[numCollected++] = z;
+            } else {
+                int bin = Math.min((int) ((z - lo) / (hi - lo) * NUM_BINS), NUM_BINS - 1);
+                binCounts[bin]++;
+                if (z < binMin[bin]) {
+                    binMin[bin] = z;
+                }
+                if (z > binMax[bin]) {
+                    binMax[bin] = z;
+                }
+            }
+        }
+
+        // returns true once the value of the rank is known
+        boolean endPass() {
+            if (found) {
+                return true;
+            }
+            if (collected != null) {
+                java.util.Arrays.sort(collected, 0, numCollected);
+                value = collected[(int) (rank - below)];
+                collected = null;
+                found = true;
+                return true;
+            }
+            // the values of each bin lie between its smallest and largest
+            // value, which are the range of the next pass
+            for (int bin = 0; bin < NUM_BINS; bin++) {
+                if (below + binCounts[bin] > rank) {
+                    lo = binMin[bin];
+                    hi = binMax[bin];
+                    count = binCounts[bin];
+                    break;
+                }
+                below += binCounts[bin];
+            }
+            binCounts = null;
+            binMin = null;
+            binMax = null;
+            if (lo == hi) {
+                value = lo;
+                found = true;
+            }
+            return found;
+        }
+    }
+
     public void deleteStatsFile() {
         File file = new File(statsFile);
         if (file.exists()) {
@@ -1727,10 +2165,10 @@ public class WhiteboxRasterBase {
             mean = mean / n;
             numValidCells = n;
 
-            if (dataType == DataType.INTEGER) { //.equals("integer")) {
+            if (dataType == DataType.INTEGER && !isScaled()) { //.equals("integer")) {
                 numberOfBins = (int) (max - min + 1);
                 binWidth = 1;
-            } else if (dataType == DataType.FLOAT || dataType == DataType.DOUBLE) { //.equals("float") || dataType.equals("double")) {
+            } else if (dataType == DataType.FLOAT || dataType == DataType.DOUBLE || isScaled()) { //.equals("float") || dataType.equals("double")) {
                 if ((max - min) < 512) {
                     numberOfBins = 512;
                 } else if ((max - min) < 1024) {
@@ -1834,76 +2272,245 @@ public class WhiteboxRasterBase {
         }
     }
 
-    private double halfCellSizeX = -1;
-    private double EWRange = -1;
-
+    /**
+     * Converts an x-coordinate (easting) into the number of the column that
+     * contains it. The extent of a raster describes the outer edges of its
+     * cells, and so column i spans from west + i * cellSizeX to
+     * west + (i + 1) * cellSizeX. A coordinate that falls on the boundary
+     * between two columns is assigned to the eastern one.
+     *
+     * @param x The x-coordinate.
+     * @return The zero-based column number. This is less than zero or not
+     * less than the number of columns if x is outside of the grid.
+     */
     public int getColumnFromXCoordinate(double x) {
-        if (halfCellSizeX < 0 || EWRange < 0) {
-            getCellSizeX();
-            halfCellSizeX = cellSizeX / 2.0;
-            EWRange = east - west - cellSizeX;
-        }
-        return (int) (Math.round((numberColumns - 1) * (x - west - halfCellSizeX) / EWRange));
+        return (int) Math.floor((x - west) / getCellSizeX());
     }
 
-    private double halfCellSizeY = -1;
-    private double NSRange = -1;
-
+    /**
+     * Converts a y-coordinate (northing) into the number of the row that
+     * contains it. Rows are numbered from the north, and so row i spans from
+     * north - i * cellSizeY to north - (i + 1) * cellSizeY. A coordinate that
+     * falls on the boundary between two rows is assigned to the southern one.
+     *
+     * @param y The y-coordinate.
+     * @return The zero-based row number. This is less than zero or not less
+     * than the number of rows if y is outside of the grid.
+     */
     public int getRowFromYCoordinate(double y) {
-        if (halfCellSizeY < 0 || NSRange < 0) {
-            getCellSizeY();
-            halfCellSizeY = cellSizeY / 2.0;
-            NSRange = north - south - cellSizeY;
-        }
-        return (int) (Math.round((numberRows - 1) * (north - halfCellSizeY - y) / NSRange));
+        return (int) Math.floor((north - y) / getCellSizeY());
     }
 
-    private double[] xCoordsByColumn;
-
+    /**
+     * Converts a column number into the x-coordinate of the centres of the
+     * cells in the column.
+     *
+     * @param column The zero-based column number, which may be outside of
+     * the grid.
+     * @return The x-coordinate of the cell centres.
+     */
     public double getXCoordinateFromColumn(int column) {
-        if (halfCellSizeX < 0 || EWRange < 0) {
-            getCellSizeX();
-            halfCellSizeX = cellSizeX / 2.0;
-            EWRange = east - west - cellSizeX;
+        return west + (column + 0.5) * getCellSizeX();
+    }
+
+    /**
+     * Converts a row number into the y-coordinate of the centres of the cells
+     * in the row.
+     *
+     * @param row The zero-based row number, which may be outside of the grid.
+     * @return The y-coordinate of the cell centres.
+     */
+    public double getYCoordinateFromRow(int row) {
+        return north - (row + 0.5) * getCellSizeY();
+    }
+
+    public void close() {
+        if (saveChanges) {
+            if (isDirty) {
+                writeHeaderFile();
+            }
         }
-        if (xCoordsByColumn == null) {
-            xCoordsByColumn = new double[numberColumns];
-            for (int i = 0; i < numberColumns; i++) {
-                xCoordsByColumn[i] = west + halfCellSizeX + i * cellSizeX;
+    }
+
+    // ***********************************
+    // Atomic output commit
+    // ***********************************
+    /**
+     * The temporary header file that is written in place of the header file
+     * while a new raster is being created, or null if the raster's files are
+     * written directly.
+     */
+    protected String pendingHeaderFile = null;
+    private String committedDataFile = null;
+    private RasterLock rasterLock = null;
+
+    // The locks held by the rasters that are being created in this process,
+    // keyed by header file. Locks are released when a raster is closed or its
+    // files are discarded. A lock whose owner has since been garbage collected,
+    // or has finished writing, is stale: a raster that was abandoned without
+    // being closed, e.g. by a tool that failed, blocks its file until then.
+    private static final Map<String, RasterLock> rasterLocks = new HashMap<>();
+
+    private static class RasterLock {
+
+        WeakReference<WhiteboxRasterBase> owner;
+        FileChannel channel;
+        FileLock lock;
+
+        void release() {
+            try {
+                if (lock != null) {
+                    lock.release();
+                }
+                if (channel != null) {
+                    channel.close();
+                }
+            } catch (IOException e) {
             }
         }
-        if (column >= 0 && column < numberColumns) {
-            return xCoordsByColumn[column];
-        } else {
-            return west + halfCellSizeX + column * cellSizeX;
+    }
+
+    /**
+     * Starts the creation of a new raster. The header and data files are
+     * written to temporary files (.dep.tmp and .tas.tmp) in the output's
+     * directory until commitFiles is called, so that an existing raster with
+     * the same name is left untouched if the tool fails or is killed part of
+     * the way through. An advisory lock is held on a .lock file for the
+     * duration, so that two writers of the same raster fail rather than
+     * interleave their data.
+     *
+     * @throws IllegalStateException if the raster is being written by another
+     * process or by another object in this one.
+     */
+    protected void beginPendingFiles() {
+        String key = new File(headerFile).getAbsolutePath();
+        synchronized (rasterLocks) {
+            RasterLock existing = rasterLocks.get(key);
+            WhiteboxRasterBase owner = (existing != null) ? existing.owner.get() : null;
+            if (owner != null && owner.pendingHeaderFile != null) {
+                throw new IllegalStateException("The raster " + headerFile
+                        + " is already being written. Two tools cannot write the same output file at once.");
+            } else if (existing != null) {
+                existing.release();
+                rasterLocks.remove(key);
+            }
+
+            RasterLock rl = new RasterLock();
+            rl.owner = new WeakReference<>(this);
+            try {
+                rl.channel = new RandomAccessFile(headerFile + ".lock", "rw").getChannel();
+                rl.lock = rl.channel.tryLock();
+            } catch (IOException e) {
+                rl.release();
+                throw new IllegalStateException("Unable to lock " + headerFile + ": " + e.getMessage(), e);
+            }
+            if (rl.lock == null) {
+                rl.release();
+                throw new IllegalStateException("The raster " + headerFile
+                        + " is already being written by another process.");
+            }
+            rasterLocks.put(key, rl);
+            rasterLock = rl;
         }
+        committedDataFile = dataFile;
+        pendingHeaderFile = headerFile + ".tmp";
+        dataFile = dataFile + ".tmp";
+        // remove the temporary files left by a writer that failed
+        new File(pendingHeaderFile).delete();
+        new File(dataFile).delete();
     }
 
-    private double[] yCoordsByRow;
+    /**
+     * Finds the raster that is creating the specified raster in this process.
+     *
+     * @param headerFile The name of the raster's header file.
+     * @return The raster that is writing the temporary files of the specified
+     * raster, or null if the raster is not being created.
+     */
+    protected static WhiteboxRasterBase findPendingWriter(String headerFile) {
+        if (headerFile == null) {
+            return null;
+        }
+        synchronized (rasterLocks) {
+            RasterLock rl = rasterLocks.get(new File(headerFile).getAbsolutePath());
+            WhiteboxRasterBase writer = (rl != null) ? rl.owner.get() : null;
+            return (writer != null && writer.pendingHeaderFile != null) ? writer : null;
+        }
+    }
 
-    public double getYCoordinateFromRow(int row) {
-        if (halfCellSizeY < 0 || NSRange < 0) {
-            getCellSizeY();
-            halfCellSizeY = cellSizeY / 2.0;
-            NSRange = north - south - cellSizeY;
+    /**
+     * Moves the temporary header and data files of a new raster into place,
+     * replacing any existing raster of the same name, and releases the lock.
+     * The data file is moved before the header file, which completes the
+     * raster. The files are renamed within their directory, which is atomic on
+     * most file systems; where it is not, they are copied and a warning is
+     * logged. The palette and metadata are part of the header file and are
+     * replaced with it. The stats file (.wstat) is not staged: it is a cache
+     * of the data file's distribution that readers rebuild when it is missing,
+     * so that of the replaced raster is deleted once the new files are in
+     * place. This does nothing if the raster's files are written directly.
+     */
+    protected void commitFiles() {
+        if (pendingHeaderFile == null) {
+            return;
         }
-        if (yCoordsByRow == null) {
-            yCoordsByRow = new double[numberRows];
-            for (int i = 0; i < numberRows; i++) {
-                yCoordsByRow[i] = north - halfCellSizeY - i * cellSizeY;
+        try {
+            File tempDataFile = new File(dataFile);
+            if (tempDataFile.exists()) {
+                moveIntoPlace(tempDataFile, new File(committedDataFile));
             }
+            moveIntoPlace(new File(pendingHeaderFile), new File(headerFile));
+            deleteStatsFile();
+        } catch (IOException e) {
+            if (communicator != null) {
+                communicator.logException("WhiteboxRaster error", e);
+            } else {
+                System.err.println("Error: " + e.getMessage());
+            }
+        } finally {
+            dataFile = committedDataFile;
+            pendingHeaderFile = null;
+            releaseLock();
         }
-        if (row >= 0 && row < numberRows) {
-            return yCoordsByRow[row];
-        } else {
-            return north - halfCellSizeY - row * cellSizeY;
+    }
+
+    /**
+     * Deletes the temporary header and data files of a new raster without
+     * moving them into place, and releases the lock.
+     */
+    protected void discardPendingFiles() {
+        if (pendingHeaderFile == null) {
+            return;
         }
+        new File(pendingHeaderFile).delete();
+        new File(dataFile).delete();
+        dataFile = committedDataFile;
+        pendingHeaderFile = null;
+        releaseLock();
     }
 
-    public void close() {
-        if (saveChanges) {
-            if (isDirty) {
-                writeHeaderFile();
+    private void moveIntoPlace(File source, File target) throws IOException {
+        try {
+            Files.move(source.toPath(), target.toPath(), StandardCopyOption.ATOMIC_MOVE,
+                    StandardCopyOption.REPLACE_EXISTING);
+        } catch (AtomicMoveNotSupportedException e) {
+            String msg = "Warning: " + target + " could not be replaced atomically and has been copied instead.";
+            if (communicator != null) {
+                communicator.logMessage(Level.WARNING, msg);
+            } else {
+                System.err.println(msg);
+            }
+            Files.move(source.toPath(), target.toPath(), StandardCopyOption.REPLACE_EXISTING);
+        }
+    }
+
+    private void releaseLock() {
+        synchronized (rasterLocks) {
+            if (rasterLock != null) {
+                new File(headerFile + ".lock").delete();
+                rasterLock.release();
+                rasterLocks.remove(new File(headerFile).getAbsolutePath());
+                rasterLock = null;
             }
         }
     }
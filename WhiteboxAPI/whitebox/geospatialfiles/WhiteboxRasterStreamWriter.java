@@ -0,0 +1,315 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package whitebox.geospatialfiles;
+
+import java.io.FileOutputStream;
+import java.io.IOException;
+import java.nio.ByteBuffer;
+import java.nio.channels.FileChannel;
+import java.util.logging.Level;
+
+/**
+ * The WhiteboxRasterStreamWriter is used to write a new Whitebox GAT raster
+ * (.dep and .tas) one row at a time, from the top row down. Each row is
+ * written to the data file as soon as it is handed to the writer, so that
+ * only a single row of the output is ever held in memory. This differs from
+ * a WhiteboxRaster opened in "rw" mode, which buffers a large block of the
+ * grid and initializes the entire data file before the first value is
+ * written.
+ *
+ * The writer can be used by any tool that calculates each output row from
+ * the input data alone and produces the rows in order, i.e. local and
+ * neighbourhood operations such as the Sobel, Prewitt, and Laplacian filters,
+ * hillshading, slope, and raster calculator type operations. Tools that
+ * revisit output cells after they are first assigned (e.g. flow accumulation,
+ * depression filling, clumping, and distance transforms) must continue to
+ * use a WhiteboxRaster.
+ */
+public class WhiteboxRasterStreamWriter extends WhiteboxRasterBase {
+
+    private FileChannel outChannel = null;
+    private ByteBuffer buf = null;
+    private int nextRow = 0;
+
+    /**
+     * Class constructor. The header file of the new raster is written. Any
+     * existing raster with the specified header file name is replaced when
+     * the writer is closed.
+     *
+     * @param HeaderFile The name of the WhiteboxRaster header file.
+     * @param BaseRasterHeader The name of a WhiteboxRaster header file to base
+     * this new raster on.
+     * @param dataType The data type of the new raster.
+     * @param NoData The NoData value of the new raster.
+     * @throws IOException if the data file cannot be created.
+     */
+    public WhiteboxRasterStreamWriter(String HeaderFile, String BaseRasterHeader, DataType dataType, double NoData) throws IOException {
+        headerFile = HeaderFile;
+        dataFile = headerFile.replace(".dep", ".tas");
+        statsFile = headerFile.replace(".dep", ".wstat");
+        beginPendingFiles();
+        setFileAccess("rw");
+        // The minimum and maximum are unknown until the data have been
+        // written. They are zeroed so that writing the header doesn't try to
+        // scan the (not yet existing) data file for them.
+        minimumValue = 0;
+        maximumValue = 0;
+        displayMinimum = 0;
+        displayMaximum = 0;
+        setPropertiesUsingAnotherRaster(BaseRasterHeader, dataType);
+        noDataValue = NoData;
+        createDataFile();
+    }
+
+    /**
+     * Class constructor for a raster that is not based on an existing raster,
+     * e.g. one that is imported from another file format. The header file of
+     * the new raster is written. Any existing raster with the specified header
+     * file name is replaced when the writer is closed.
+     *
+     * @param HeaderFile The name of the WhiteboxRaster header file.
+     * @param north The northern edge of the raster.
+     * @param south The southern edge of the raster.
+     * @param east The eastern edge of the raster.
+     * @param west The western edge of the raster.
+     * @param rows The number of rows.
+     * @param cols The number of columns.
+     * @param dataScale The data scale of the new raster.
+     * @param dataType The data type of the new raster.
+     * @param NoData The NoData value of the new raster.
+     * @throws IOException if the data file cannot be created.
+     */
+    public WhiteboxRasterStreamWriter(String HeaderFile, double north, double south, double east,
+            double west, int rows, int cols, DataScale dataScale, DataType dataType,
+            double NoData) throws IOException {
+        headerFile = HeaderFile;
+        dataFile = headerFile.replace(".dep", ".tas");
+        statsFile = headerFile.replace(".dep", ".wstat");
+        beginPendingFiles();
+        setFileAccess("rw");
+        minimumValue = 0;
+        maximumValue = 0;
+        displayMinimum = 0;
+        displayMaximum = 0;
+        this.north = north;
+        this.south = south;
+        this.east = east;
+        this.west = west;
+        this.numberRows = rows;
+        this.numberColumns = cols;
+        this.dataScale = dataScale;
+        setDataType(dataType);
+        noDataValue = NoData;
+        createDataFile();
+    }
+
+    private void createDataFile() throws IOException {
+        writeHeaderFile();
+        minimumValue = Double.MAX_VALUE;
+        maximumValue = -Double.MAX_VALUE;
+
+        outChannel = new FileOutputStream(dataFile).getChannel();
+        buf = ByteBuffer.allocate(numberColumns * cellSizeInBytes);
+        buf.order(byteOrder);
+    }
+
+    /**
+     * Retrieves the zero-based index of the next row to be written.
+     *
+     * @return The next row.
+     */
+    public int getNextRow() {
+        return nextRow;
+    }
+
+    /**
+     * Writes a row of data to the data file. Rows must be written in order,
+     * starting with row zero. The values of a scaled raster are real-world
+     * values, which are converted to stored values as they are written.
+     *
+     * @param row The zero-based row number.
+     * @param data An array of doubles with one value for each column.
+     * @throws IOException if the data cannot be written.
+     */
+    public void writeRow(int row, double[] data) throws IOException {
+        checkRow(row, data.length);
+
+        buf.clear();
+        double z;
+        for (int col = 0; col < numberColumns; col++) {
+            z = toStored(data[col]);
+            switch (dataType) {
+                case DOUBLE:
+                    buf.putDouble(z);
+                    break;
+                case FLOAT:
+                    buf.putFloat((float) z);
+                    z = (float) z;
+                    break;
+                case INTEGER:
+                    buf.putShort((short) z);
+                    z = (short) z;
+                    break;
+                case BYTE:
+                    buf.put((byte) z);
+                    z = ((byte) z) & 0xFF;
+                    break;
+                case I32:
+                    buf.putInt((int) z);
+                    z = (int) z;
+                    break;
+            }
+            // the minimum and maximum are those of the values as they will
+            // be read back
+            z = fromStored(z);
+            if (data[col] != noDataValue) {
+                if (z < minimumValue) {
+                    minimumValue = z;
+                }
+                if (z > maximumValue) {
+                    maximumValue = z;
+                }
+            }
+        }
+        writeBuffer();
+    }
+
+    /**
+     * Writes a row of float values to the data file. Rows must be written in
+     * order, starting with row zero. If the raster is of the FLOAT data type
+     * and is not scaled, the row is written without conversion.
+     *
+     * @param row The zero-based row number.
+     * @param data An array of floats with one value for each column.
+     * @throws IOException if the data cannot be written.
+     */
+    public void writeRow(int row, float[] data) throws IOException {
+        checkRow(row, data.length);
+        if (dataType != DataType.FLOAT || isScaled()) {
+            if (doubleRow == null) {
+                doubleRow = new double[numberColumns];
+            }
+            for (int col = 0; col < numberColumns; col++) {
+                doubleRow[col] = data[col];
+            }
+            writeRow(row, doubleRow);
+            return;
+        }
+
+        for (int col = 0; col < numberColumns; col++) {
+            float z = data[col];
+            if (z != noDataValue) {
+                if (z < minimumValue) {
+                    minimumValue = z;
+                }
+                if (z > maximumValue) {
+                    maximumValue = z;
+                }
+            }
+        }
+        buf.clear();
+        buf.asFloatBuffer().put(data, 0, numberColumns);
+        buf.position(numberColumns * 4);
+        writeBuffer();
+    }
+
+    private double[] doubleRow = null;
+
+    private void checkRow(int row, int length) {
+        if (outChannel == null) {
+            throw new IllegalStateException("The raster has been closed.");
+        }
+        if (row != nextRow) {
+            throw new IllegalArgumentException("Rows must be written in order; expected row "
+                    + nextRow + " but received row " + row + ".");
+        }
+        if (length != numberColumns) {
+            throw new IllegalArgumentException("The row contains " + length
+                    + " values but the raster has " + numberColumns + " columns.");
+        }
+    }
+
+    private void writeBuffer() throws IOException {
+        buf.flip();
+        while (buf.hasRemaining()) {
+            outChannel.write(buf);
+        }
+        nextRow++;
+    }
+
+    /**
+     * Closes the data file, writes the header file with the final minimum and
+     * maximum values, and moves the new raster into place. The raster is only
+     * committed if every row has been written; otherwise, e.g. when a tool is
+     * cancelled part of the way through, it is discarded as by abort(), so
+     * that a partly computed raster never replaces an existing one.
+     */
+    @Override
+    public void close() {
+        if (outChannel == null) {
+            return;
+        }
+        if (nextRow < numberRows) {
+            if (communicator != null) {
+                communicator.logMessage(Level.WARNING, "Only " + nextRow + " of " + numberRows
+                        + " rows of " + headerFile + " were written; the raster has been discarded.");
+            }
+            abort();
+            return;
+        }
+        try {
+            outChannel.force(false);
+            outChannel.close();
+            if (maximumValue < minimumValue) {
+                // every cell is NoData
+                minimumValue = noDataValue;
+                maximumValue = noDataValue;
+            }
+            displayMinimum = minimumValue;
+            displayMaximum = maximumValue;
+            writeHeaderFile();
+            commitFiles();
+        } catch (IOException e) {
+            if (communicator != null) {
+                communicator.logException("WhiteboxRasterStreamWriter error", e);
+            }
+        } finally {
+            abort();
+        }
+    }
+
+    /**
+     * Closes the data file and deletes the new raster's temporary files
+     * without moving them into place, leaving any existing raster of the same
+     * name untouched. Tools call this instead of close() when they are
+     * cancelled or fail. It does nothing once the raster has been committed.
+     */
+    public void abort() {
+        if (outChannel != null) {
+            if (outChannel.isOpen()) {
+                try {
+                    outChannel.close();
+                } catch (IOException e) {
+                }
+            }
+            outChannel = null;
+            buf = null;
+        }
+        // releases the lock of a raster that was not committed
+        discardPendingFiles();
+    }
+}
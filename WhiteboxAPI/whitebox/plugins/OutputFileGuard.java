@@ -0,0 +1,175 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package whitebox.plugins;
+
+import java.io.File;
+import java.util.ArrayList;
+import java.util.List;
+import javax.swing.JOptionPane;
+import javax.xml.parsers.DocumentBuilder;
+import javax.xml.parsers.DocumentBuilderFactory;
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.NodeList;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * Stops a plugin tool from silently replacing output files that already exist.
+ * The output files of a tool are the 'Save File' parameters of its dialog file
+ * (resources/plugins/Dialogs/&lt;name&gt;.xml). Every host checks them before
+ * running a tool, unless overwriting has been allowed on the calling thread
+ * with setOverwrite(true).
+ */
+public class OutputFileGuard {
+
+    private static final ThreadLocal<Boolean> overwrite = new ThreadLocal<Boolean>() {
+        @Override
+        protected Boolean initialValue() {
+            return false;
+        }
+    };
+
+    private OutputFileGuard() {
+    }
+
+    /**
+     * Sets whether the tools run from the current thread may replace existing
+     * output files.
+     *
+     * @param value true to allow existing outputs to be replaced.
+     */
+    public static void setOverwrite(boolean value) {
+        overwrite.set(value);
+    }
+
+    /**
+     * Whether the tools run from the current thread may replace existing output
+     * files. False unless set with setOverwrite.
+     *
+     * @return true if existing outputs may be replaced.
+     */
+    public static boolean isOverwrite() {
+        return overwrite.get();
+    }
+
+    /**
+     * Finds the output files of a tool that already exist.
+     *
+     * @param resourcesDirectory the Whitebox resources directory.
+     * @param pluginName the name of the tool.
+     * @param args the arguments that the tool will be run with.
+     * @return the existing output files, which is empty if there are none or
+     * the tool has no dialog file.
+     */
+    public static List<String> findExistingOutputs(String resourcesDirectory, String pluginName, String[] args) {
+        List<String> ret = new ArrayList<>();
+        if (resourcesDirectory == null || args == null) {
+            return ret;
+        }
+        if (!resourcesDirectory.endsWith(File.separator)) {
+            resourcesDirectory += File.separator;
+        }
+        File dialogFile = new File(resourcesDirectory + "plugins" + File.separator
+                + "Dialogs" + File.separator + pluginName + ".xml");
+        for (int i : getOutputParameters(dialogFile)) {
+            if (i < args.length && args[i] != null && !args[i].isEmpty()
+                    && !args[i].toLowerCase().equals("not specified")
+                    && new File(args[i]).exists()) {
+                ret.add(args[i]);
+            }
+        }
+        return ret;
+    }
+
+    /**
+     * Checks that a tool may be run by a host. If any of the tool's output files
+     * already exists and overwriting hasn't been allowed, the host is told which
+     * file it is and the tool mustn't be run.
+     *
+     * @param host the host that will run the tool.
+     * @param pluginName the name of the tool.
+     * @param args the arguments that the tool will be run with.
+     * @return true if the tool may be run.
+     */
+    public static boolean canRun(WhiteboxPluginHost host, String pluginName, String[] args) {
+        if (isOverwrite()) {
+            return true;
+        }
+        List<String> existing = findExistingOutputs(host.getResourcesDirectory(), pluginName, args);
+        if (existing.isEmpty()) {
+            return true;
+        }
+        host.showFeedback(pluginName + " was not run because its output file " + existing.get(0)
+                + " already exists. Delete the file, choose another name, or allow outputs to be overwritten.",
+                JOptionPane.DEFAULT_OPTION, JOptionPane.WARNING_MESSAGE);
+        return false;
+    }
+
+    // the positions in the argument list of a tool's 'Save File' parameters
+    private static List<Integer> getOutputParameters(File dialogFile) {
+        List<Integer> ret = new ArrayList<>();
+        if (!dialogFile.exists()) {
+            return ret;
+        }
+        try {
+            DocumentBuilderFactory dbf = DocumentBuilderFactory.newInstance();
+            DocumentBuilder db = dbf.newDocumentBuilder();
+            Document doc = db.parse(dialogFile);
+            doc.getDocumentElement().normalize();
+            NodeList nl = doc.getDocumentElement().getElementsByTagName("DialogComponent");
+            int argNum = 0;
+            for (int i = 0; i < nl.getLength(); i++) {
+                Element el = (Element) nl.item(i);
+                String componentType = el.getAttribute("type");
+                switch (componentType) {
+                    case "CustomDialogProvidedByPlugin":
+                        // the arguments don't follow the dialog file
+                        return new ArrayList<>();
+                    case "DialogFile":
+                        String dialogMode = getTextValue(el, "DialogMode").toLowerCase();
+                        if (!dialogMode.contains("open")) {
+                            ret.add(argNum);
+                        }
+                        argNum++;
+                        break;
+                    case "DialogMultiFile":
+                    case "DialogCheckBox":
+                    case "DialogComboBox":
+                    case "DialogFieldSelector":
+                    case "DialogDataInput":
+                    case "DialogOption":
+                    case "DialogReclassGrid":
+                    case "DialogWeightedMultiFile":
+                        argNum++;
+                        break;
+                }
+            }
+        } catch (Exception e) {
+            // a dialog file that can't be read doesn't stop the tool
+            return new ArrayList<>();
+        }
+        return ret;
+    }
+
+    private static String getTextValue(Element ele, String tagName) {
+        NodeList nl = ele.getElementsByTagName(tagName);
+        if (nl != null && nl.getLength() > 0 && nl.item(0).getFirstChild() != null) {
+            return nl.item(0).getFirstChild().getNodeValue().trim();
+        }
+        return "";
+    }
+}
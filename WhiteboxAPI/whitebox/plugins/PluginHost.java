@@ -137,20 +137,20 @@ public class PluginHost implements WhiteboxPluginHost {
                     if (strLine.startsWith("name = \"")) {
                         containsName = true;
                         // now retreive the name
-                        String[] str2 = strLine.split("=");
-                        name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        name = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.startsWith("descriptiveName = \"")) {
                         containsDescriptiveName = true;
-                        String[] str2 = strLine.split("=");
-                        descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.startsWith("description = \"")) {
                         containsDescription = true;
-                        String[] str2 = strLine.split("=");
-                        description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        description = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.startsWith("toolboxes = [\"")) {
                         containsToolboxes = true;
-                        String[] str2 = strLine.split("=");
-                        toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                        String[] str2 = strLine.split("=", 2);
+                        toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                         for (int i = 0; i < toolboxes.length; i++) {
                             toolboxes[i] = toolboxes[i].trim();
                         }
@@ -199,20 +199,20 @@ public class PluginHost implements WhiteboxPluginHost {
                             && !strLine.toLowerCase().contains("descriptivename")) {
                         containsName = true;
                         // now retreive the name
-                        String[] str2 = strLine.split("=");
-                        name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        name = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("descriptivename = \"")) {
                         containsDescriptiveName = true;
-                        String[] str2 = strLine.split("=");
-                        descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("description = \"")) {
                         containsDescription = true;
-                        String[] str2 = strLine.split("=");
-                        description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        description = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("toolboxes = [\"")) {
                         containsToolboxes = true;
-                        String[] str2 = strLine.split("=");
-                        toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                        String[] str2 = strLine.split("=", 2);
+                        toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                         for (int i = 0; i < toolboxes.length; i++) {
                             toolboxes[i] = toolboxes[i].trim();
                         }
@@ -261,20 +261,20 @@ public class PluginHost implements WhiteboxPluginHost {
                             && !strLine.toLowerCase().contains("descriptivename")) {
                         containsName = true;
                         // now retreive the name
-                        String[] str2 = strLine.split("=");
-                        name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        name = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("descriptivename = \"")) {
                         containsDescriptiveName = true;
-                        String[] str2 = strLine.split("=");
-                        descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("description = \"")) {
                         containsDescription = true;
-                        String[] str2 = strLine.split("=");
-                        description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                        String[] str2 = strLine.split("=", 2);
+                        description = str2[1].replace("\"", "").replace("\'", "").trim();
                     } else if (strLine.toLowerCase().contains("toolboxes = [\"")) {
                         containsToolboxes = true;
-                        String[] str2 = strLine.split("=");
-                        toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                        String[] str2 = strLine.split("=", 2);
+                        toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                         for (int i = 0; i < toolboxes.length; i++) {
                             toolboxes[i] = toolboxes[i].trim();
                         }
@@ -364,12 +364,25 @@ public class PluginHost implements WhiteboxPluginHost {
     
     @Override
     public void runPlugin(String pluginName, String[] args, boolean runOnDedicatedThread) {
-        throw new UnsupportedOperationException("Not supported yet."); //To change body of generated methods, choose Tools | Templates.
+        runPlugin(pluginName, args);
     }
     
     @Override
     public void runPlugin(String pluginName, String[] args) {
-        throw new UnsupportedOperationException("Not supported yet.");
+        WhiteboxPlugin plug = pluginService.getPlugin(pluginName, StandardPluginService.SIMPLE_NAME);
+        if (plug == null) {
+            plug = pluginService.getPlugin(pluginName, StandardPluginService.DESCRIPTIVE_NAME);
+        }
+        if (plug == null) {
+            showFeedback("Unrecognized tool name " + pluginName + ".");
+            return;
+        }
+        if (!OutputFileGuard.canRun(this, plug.getName(), args)) {
+            return;
+        }
+        plug.setPluginHost(this);
+        plug.setArgs(args);
+        plug.run();
     }
 
     @Override
@@ -527,7 +540,7 @@ public class PluginHost implements WhiteboxPluginHost {
 
     @Override
     public void runPlugin(String pluginName, String[] args, boolean runOnDedicatedThread, boolean suppressReturnedData) {
-        throw new UnsupportedOperationException("Not supported yet."); //To change body of generated methods, choose Tools | Templates.
+        runPlugin(pluginName, args);
     }
 
     @Override
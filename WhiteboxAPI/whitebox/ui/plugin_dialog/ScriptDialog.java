@@ -596,9 +596,7 @@ public class ScriptDialog implements Communicator, ActionListener, HyperlinkList
     public void setHelpFile(String newHelpFile) {
         this.helpFile = newHelpFile;
 
-        if (!helpFile.contains(pathSep)) {
-            helpFile = resourcesDirectory + "Help" + pathSep + helpFile;
-        }
+        helpFile = FileUtilities.resolvePath(helpFile, resourcesDirectory + "Help" + pathSep);
         if (!helpFile.endsWith(".html")) {
             helpFile = this.helpFile + ".html";
         }
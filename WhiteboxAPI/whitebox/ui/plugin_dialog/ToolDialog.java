@@ -700,9 +700,7 @@ public class ToolDialog extends JDialog implements Communicator, ActionListener,
     public void setHelpFile(String newHelpFile) {
         this.helpFile = newHelpFile;
         
-        if (!helpFile.contains(pathSep)) {
-            helpFile = resourcesDirectory + "Help" + pathSep + helpFile;
-        }
+        helpFile = FileUtilities.resolvePath(helpFile, resourcesDirectory + "Help" + pathSep);
         if (!helpFile.endsWith(".html")) {
             helpFile = this.helpFile + ".html";
         }
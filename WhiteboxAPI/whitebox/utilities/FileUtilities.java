@@ -187,4 +187,27 @@ public class FileUtilities {
         return (new File(fileName)).exists();
     }
 
+    /**
+     * Resolves a file name entered as a tool parameter against the working
+     * directory. The file name is taken as-is, apart from surrounding white
+     * space; quotes and embedded spaces are treated as part of the name. The
+     * working directory is only prepended when the file name is not an
+     * absolute path.
+     *
+     * @param fileName the file name as entered.
+     * @param workingDirectory the directory used to resolve relative file names.
+     * @return the resolved file name, or an empty string if none was entered.
+     */
+    public static String resolvePath(String fileName, String workingDirectory) {
+        String path = fileName.trim();
+        if (path.isEmpty() || new File(path).isAbsolute()
+                || workingDirectory == null || workingDirectory.isEmpty()) {
+            return path;
+        }
+        if (workingDirectory.endsWith(File.separator)) {
+            return workingDirectory + path;
+        }
+        return workingDirectory + File.separator + path;
+    }
+
 }
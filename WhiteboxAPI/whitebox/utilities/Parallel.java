@@ -21,6 +21,7 @@
  */
 package whitebox.utilities;
 
+import java.util.ArrayList;
 import java.util.Collection;
 import java.util.LinkedList;
 import java.util.List;
@@ -94,6 +95,189 @@ public class Parallel {
         executor.shutdown();
     }
 
+    /**
+     * Computes the values of each row of an output raster with a RowTask, in
+     * parallel, and passes the rows to a RowSink one at a time in row order,
+     * on the calling thread. The rows are computed in chunks on a thread pool
+     * that is shared by all tools and has as many threads as the number of
+     * processors that plugins may use (see
+     * whitebox.parallel.Parallel.getPluginProcessors()). Only a few chunks
+     * are held in memory at a time and their row buffers are reused, so a
+     * sink can report progress, write each row as it is received, and stop
+     * the operation early.
+     *
+     * @param rows The number of rows.
+     * @param columns The number of values in each row.
+     * @param task Computes the values of a row. It is called concurrently and
+     * must only read shared data, e.g. arrays or memory-mapped rasters.
+     * @param sink Receives each row in order, and returns false to stop.
+     * @return false if the sink stopped the operation.
+     * @throws Exception if the task or the sink throws an exception.
+     */
+    public static boolean ForRows(int rows, int columns, RowTask task, RowSink sink) throws Exception {
+        return ForRows(rows, columns, true, task, sink);
+    }
+
+    /**
+     * Computes the values of each row of an output raster with a RowTask and
+     * passes them to a RowSink in row order, as ForRows(rows, columns, task,
+     * sink) does, but only in parallel if inParallel is true. Otherwise the
+     * rows are computed on the calling thread, e.g. when the task reads from
+     * a raster that can only be read by one thread at a time.
+     */
+    public static boolean ForRows(int rows, int columns, boolean inParallel,
+            RowTask task, RowSink sink) throws Exception {
+        RowPool rowPool = inParallel && rows > 1 ? acquireRowPool() : null;
+        if (rowPool == null || rowPool.threads == 1) {
+            if (rowPool != null) {
+                releaseRowPool(rowPool);
+            }
+            double[] data = new double[columns];
+            for (int row = 0; row < rows; row++) {
+                task.run(row, data);
+                if (!sink.accept(row, data)) {
+                    return false;
+                }
+            }
+            return true;
+        }
+
+        // the rows are computed in chunks, of which a fixed number are in
+        // progress at a time, each with its own set of row buffers.
+        ExecutorService pool = rowPool.executor;
+        int chunkSize = Math.max(1, Math.min(64, rows / (rowPool.threads * 4)));
+        int numChunks = (rows + chunkSize - 1) / chunkSize;
+        int window = Math.min(numChunks, rowPool.threads * 2);
+        double[][][] buffers = new double[window][chunkSize][columns];
+        List<Future<?>> futures = new ArrayList<>(window);
+        boolean completed = false;
+        try {
+            for (int c = 0; c < window; c++) {
+                futures.add(submitChunk(pool, task, c, chunkSize, rows, buffers[c]));
+            }
+            for (int c = 0; c < numChunks; c++) {
+                int slot = c % window;
+                try {
+                    futures.get(slot).get();
+                } catch (ExecutionException e) {
+                    Throwable cause = e.getCause();
+                    if (cause instanceof Exception) {
+                        throw (Exception) cause;
+                    }
+                    throw new RuntimeException(cause);
+                }
+                int startRow = c * chunkSize;
+                int endRow = Math.min(rows, startRow + chunkSize);
+                for (int row = startRow; row < endRow; row++) {
+                    if (!sink.accept(row, buffers[slot][row - startRow])) {
+                        return false;
+                    }
+                }
+                if (c + window < numChunks) {
+                    futures.set(slot, submitChunk(pool, task, c + window, chunkSize, rows, buffers[slot]));
+                }
+            }
+            completed = true;
+            return true;
+        } finally {
+            if (!completed) {
+                // wait for the chunks that have already started, so that the
+                // task's data are no longer in use when this method returns.
+                for (Future<?> future : futures) {
+                    future.cancel(false);
+                    try {
+                        future.get();
+                    } catch (CancellationException | ExecutionException e) {
+                    }
+                }
+            }
+            releaseRowPool(rowPool);
+        }
+    }
+
+    private static Future<?> submitChunk(ExecutorService pool, final RowTask task,
+            final int chunk, final int chunkSize, final int rows, final double[][] buffers) {
+        return pool.submit(new Callable<Void>() {
+
+            @Override
+            public Void call() throws Exception {
+                int startRow = chunk * chunkSize;
+                int endRow = Math.min(rows, startRow + chunkSize);
+                for (int row = startRow; row < endRow; row++) {
+                    task.run(row, buffers[row - startRow]);
+                }
+                return null;
+            }
+        });
+    }
+
+    // The pool shared by the ForRows calls, with the number of calls that are
+    // using it. The pool is created when it is first needed and replaced if
+    // the number of plugin processors changes, but a replaced pool is only
+    // shut down once the calls that are still using it have finished.
+    private static class RowPool {
+
+        final ExecutorService executor;
+        final int threads;
+        int users = 0;
+        boolean replaced = false;
+
+        RowPool(int threads) {
+            this.threads = threads;
+            executor = Executors.newFixedThreadPool(threads, new NamedThreadFactory("Parallel.ForRows"));
+        }
+    }
+
+    private static RowPool currentRowPool = null;
+
+    private static synchronized RowPool acquireRowPool() {
+        int numThreads = Math.max(1, whitebox.parallel.Parallel.getPluginProcessors());
+        if (currentRowPool == null || numThreads != currentRowPool.threads) {
+            if (currentRowPool != null) {
+                currentRowPool.replaced = true;
+                if (currentRowPool.users == 0) {
+                    currentRowPool.executor.shutdown();
+                }
+            }
+            currentRowPool = new RowPool(numThreads);
+        }
+        currentRowPool.users++;
+        return currentRowPool;
+    }
+
+    private static synchronized void releaseRowPool(RowPool rowPool) {
+        rowPool.users--;
+        if (rowPool.replaced && rowPool.users == 0) {
+            rowPool.executor.shutdown();
+        }
+    }
+
+    /**
+     * Computes the values of one row of an output raster.
+     */
+    public interface RowTask {
+
+        /**
+         * @param row The row number.
+         * @param data The buffer for the row's values, which may hold the
+         * values of an earlier row and must be overwritten.
+         */
+        void run(int row, double[] data) throws Exception;
+    }
+
+    /**
+     * Receives the rows computed by ForRows, in order.
+     */
+    public interface RowSink {
+
+        /**
+         * @param row The row number.
+         * @param data The row's values, which are only valid until this
+         * method returns.
+         * @return false to stop the operation.
+         */
+        boolean accept(int row, double[] data) throws Exception;
+    }
     
     // this is only used for testing the tool
     public static void main(String[] args) {
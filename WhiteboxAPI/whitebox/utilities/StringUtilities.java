@@ -1,6 +1,11 @@
 package whitebox.utilities;
 
 import java.text.DecimalFormatSymbols;
+import java.util.ArrayList;
+import java.util.Collection;
+import java.util.Collections;
+import java.util.Comparator;
+import java.util.List;
 
 /**
  *
@@ -82,4 +87,89 @@ public abstract class StringUtilities {
 
         return ret.toString();
     }
+
+    /**
+     * Formats an elapsed time as HH:MM:SS.mmm. The elapsed time should be
+     * measured using System.nanoTime(), which unlike the system clock is not
+     * affected by changes to the time of day.
+     *
+     * @param elapsedNanos the elapsed time, in nanoseconds.
+     * @return the formatted elapsed time.
+     */
+    public static String formatElapsedTime(long elapsedNanos) {
+        long millis = Math.max(0, elapsedNanos / 1000000L);
+        return String.format("%02d:%02d:%02d.%03d", millis / 3600000L,
+                (millis / 60000L) % 60, (millis / 1000L) % 60, millis % 1000);
+    }
+
+    /**
+     * Calculates the Levenshtein (edit) distance between two strings, i.e. the
+     * minimum number of single-character insertions, deletions and
+     * substitutions needed to change one string into the other.
+     *
+     * @param a the first string.
+     * @param b the second string.
+     * @return the edit distance.
+     */
+    public static int levenshteinDistance(String a, String b) {
+        int[] previous = new int[b.length() + 1];
+        int[] current = new int[b.length() + 1];
+        for (int j = 0; j <= b.length(); j++) {
+            previous[j] = j;
+        }
+        for (int i = 1; i <= a.length(); i++) {
+            current[0] = i;
+            for (int j = 1; j <= b.length(); j++) {
+                int cost = a.charAt(i - 1) == b.charAt(j - 1) ? 0 : 1;
+                current[j] = Math.min(Math.min(current[j - 1] + 1, previous[j] + 1),
+                        previous[j - 1] + cost);
+            }
+            int[] tmp = previous;
+            previous = current;
+            current = tmp;
+        }
+        return previous[b.length()];
+    }
+
+    /**
+     * Finds the candidates that are closest to a string, ignoring case, for
+     * suggesting corrections to a misspelled name. Only candidates within an
+     * edit distance of a third of the string's length (and at least two) are
+     * returned, closest first.
+     *
+     * @param str the string to match.
+     * @param candidates the possible matches.
+     * @param maxMatches the maximum number of matches to return.
+     * @return the closest candidates.
+     */
+    public static List<String> findClosestMatches(String str, Collection<String> candidates,
+            int maxMatches) {
+        final String target = str.trim().toLowerCase();
+        int maxDistance = Math.max(2, target.length() / 3);
+        final ArrayList<String> matches = new ArrayList<>();
+        final ArrayList<Integer> distances = new ArrayList<>();
+        for (String candidate : candidates) {
+            int d = levenshteinDistance(target, candidate.toLowerCase());
+            if (d <= maxDistance && !matches.contains(candidate)) {
+                matches.add(candidate);
+                distances.add(d);
+            }
+        }
+        ArrayList<Integer> order = new ArrayList<>();
+        for (int i = 0; i < matches.size(); i++) {
+            order.add(i);
+        }
+        Collections.sort(order, new Comparator<Integer>() {
+            @Override
+            public int compare(Integer i, Integer j) {
+                int c = distances.get(i).compareTo(distances.get(j));
+                return c != 0 ? c : matches.get(i).compareToIgnoreCase(matches.get(j));
+            }
+        });
+        List<String> ret = new ArrayList<>();
+        for (int i = 0; i < order.size() && i < maxMatches; i++) {
+            ret.add(matches.get(order.get(i)));
+        }
+        return ret;
+    }
 }
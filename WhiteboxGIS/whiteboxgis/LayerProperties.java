@@ -42,6 +42,7 @@ import whitebox.geospatialfiles.VectorLayerInfo;
 import whitebox.geospatialfiles.LasLayerInfo;
 import whitebox.geospatialfiles.shapefile.ShapeTypeDimension;
 import whitebox.ui.plugin_dialog.DialogFile;
+import whitebox.utilities.FileUtilities;
 
 /**
  *
@@ -2094,10 +2095,8 @@ public class LayerProperties extends JDialog implements ActionListener, Adjustme
 //            if (!hillshadeText.getText().isEmpty()) {
             if (!"not specified".equals(hillshadeText.getValue())) {
                 String value = hillshadeText.getValue(); // .getText();
-                if (!value.contains(File.separator)) {
-                    // add the working directory to the file name
-                    value = host.getWorkingDirectory() + value;
-                }
+                // add the working directory to a relative file name
+                value = FileUtilities.resolvePath(value, host.getWorkingDirectory());
                 if (!value.contains(".dep")) {
                     value += ".dep";
                 }
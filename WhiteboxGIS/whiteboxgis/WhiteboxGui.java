@@ -98,6 +98,7 @@ import whitebox.internationalization.WhiteboxInternationalizationTools;
 import whitebox.structures.InteroperableGeospatialDataFormat;
 import whitebox.interfaces.InteropPlugin.InteropPluginType;
 import whitebox.utilities.StringUtilities;
+import whitebox.plugins.OutputFileGuard;
 import whitebox.plugins.ReturnedDataEvent;
 import whiteboxgis.user_interfaces.ViewTextDialog;
 import whitebox.geospatialfiles.LasLayerInfo;
@@ -864,43 +865,43 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                                 && !strLine.toLowerCase().contains("filetypename")) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.startsWith("toolName = \"") && name.isEmpty()) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.startsWith("descriptiveName = \"")) {
                             containsDescriptiveName = true;
-                            String[] str2 = strLine.split("=");
-                            descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.startsWith("description = \"")) {
                             containsDescription = true;
-                            String[] str2 = strLine.split("=");
-                            description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            description = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.startsWith("toolboxes = [\"")) {
                             containsToolboxes = true;
-                            String[] str2 = strLine.split("=");
-                            toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                            String[] str2 = strLine.split("=", 2);
+                            toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                             for (int i = 0; i < toolboxes.length; i++) {
                                 toolboxes[i] = toolboxes[i].trim();
                             }
                         } else if (strLine.startsWith("extensions = [")) {
                             containsExtensions = true;
-                            String[] str2 = strLine.split("=");
-                            extensions = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                            String[] str2 = strLine.split("=", 2);
+                            extensions = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                             for (int i = 0; i < extensions.length; i++) {
                                 extensions[i] = extensions[i].trim();
                             }
                         } else if (strLine.startsWith("fileTypeName = \"")) {
                             containsFileTypeName = true;
-                            String[] str2 = strLine.split("=");
-                            fileTypeName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            fileTypeName = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.startsWith("isRasterFormat = ")) {
                             containsIsRasterFormat = true;
-                            String[] str2 = strLine.split("=");
-                            isRasterFormat = Boolean.parseBoolean(str2[str2.length - 1].replace("\"", "").replace("\'", "").trim());
+                            String[] str2 = strLine.split("=", 2);
+                            isRasterFormat = Boolean.parseBoolean(str2[1].replace("\"", "").replace("\'", "").trim());
                         } else if (strLine.startsWith("interopPluginType = InteropPluginType")) {
                             containsPluginType = true;
                             if (strLine.toLowerCase().contains("import")) {
@@ -910,16 +911,16 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                             }
                         } else if (strLine.toLowerCase().contains("parentmenu = \"")) {
                             containsParentMenu = true;
-                            String[] str2 = strLine.split("=");
-                            parentMenu = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            parentMenu = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("menulabel = \"")) {
                             containsMenuLabel = true;
-                            String[] str2 = strLine.split("=");
-                            menuLabel = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            menuLabel = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("acceleratorkey = \"")) {
                             containsKeyStroke = true;
-                            String[] str2 = strLine.split("=");
-                            String str3 = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            String str3 = str2[1].replace("\"", "").replace("\'", "").trim();
                             keyStroke = str3.charAt(0);
                         }
                     }
@@ -1005,25 +1006,25 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                                 && !strLine.toLowerCase().contains("toolname")) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("toolname = \"") && name.isEmpty()) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("descriptivename = \"")) {
                             containsDescriptiveName = true;
-                            String[] str2 = strLine.split("=");
-                            descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("description = \"")) {
                             containsDescription = true;
-                            String[] str2 = strLine.split("=");
-                            description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            description = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("toolboxes = [\"")) {
                             containsToolboxes = true;
-                            String[] str2 = strLine.split("=");
-                            toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                            String[] str2 = strLine.split("=", 2);
+                            toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                             for (int i = 0; i < toolboxes.length; i++) {
                                 toolboxes[i] = toolboxes[i].trim();
                             }
@@ -1099,43 +1100,43 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                                 && !strLine.toLowerCase().contains("filetypename")) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("toolname = \"") && name.isEmpty()) {
                             containsName = true;
                             // now retreive the name
-                            String[] str2 = strLine.split("=");
-                            name = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            name = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("descriptivename = \"")) {
                             containsDescriptiveName = true;
-                            String[] str2 = strLine.split("=");
-                            descriptiveName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            descriptiveName = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("description = \"")) {
                             containsDescription = true;
-                            String[] str2 = strLine.split("=");
-                            description = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            description = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("toolboxes = [\"")) {
                             containsToolboxes = true;
-                            String[] str2 = strLine.split("=");
-                            toolboxes = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                            String[] str2 = strLine.split("=", 2);
+                            toolboxes = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                             for (int i = 0; i < toolboxes.length; i++) {
                                 toolboxes[i] = toolboxes[i].trim();
                             }
                         } else if (strLine.toLowerCase().contains("extensions = [")) {
                             containsExtensions = true;
-                            String[] str2 = strLine.split("=");
-                            extensions = str2[str2.length - 1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
+                            String[] str2 = strLine.split("=", 2);
+                            extensions = str2[1].replace("\"", "").replace("\'", "").replace("[", "").replace("]", "").trim().split(",");
                             for (int i = 0; i < extensions.length; i++) {
                                 extensions[i] = extensions[i].trim();
                             }
                         } else if (strLine.toLowerCase().contains("filetypename = \"")) {
                             containsFileTypeName = true;
-                            String[] str2 = strLine.split("=");
-                            fileTypeName = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            fileTypeName = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("israsterformat = ")) {
                             containsIsRasterFormat = true;
-                            String[] str2 = strLine.split("=");
-                            isRasterFormat = Boolean.parseBoolean(str2[str2.length - 1].replace("\"", "").replace("\'", "").trim());
+                            String[] str2 = strLine.split("=", 2);
+                            isRasterFormat = Boolean.parseBoolean(str2[1].replace("\"", "").replace("\'", "").trim());
                         } else if (strLine.toLowerCase().contains("interopplugintype = interopplugintype")) {
                             containsPluginType = true;
                             if (strLine.toLowerCase().contains("import")) {
@@ -1145,16 +1146,16 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                             }
                         } else if (strLine.toLowerCase().contains("parentmenu = \"")) {
                             containsParentMenu = true;
-                            String[] str2 = strLine.split("=");
-                            parentMenu = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            parentMenu = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("menulabel = \"")) {
                             containsMenuLabel = true;
-                            String[] str2 = strLine.split("=");
-                            menuLabel = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            menuLabel = str2[1].replace("\"", "").replace("\'", "").trim();
                         } else if (strLine.toLowerCase().contains("acceleratorkey = \"")) {
                             containsKeyStroke = true;
-                            String[] str2 = strLine.split("=");
-                            String str3 = str2[str2.length - 1].replace("\"", "").replace("\'", "").trim();
+                            String[] str2 = strLine.split("=", 2);
+                            String str3 = str2[1].replace("\"", "").replace("\'", "").trim();
                             keyStroke = str3.charAt(0);
                         }
                     }
@@ -1266,7 +1267,11 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                     requestForOperationCancel = false;
                     WhiteboxPlugin plug = pluginService.getPlugin(pluginName, StandardPluginService.SIMPLE_NAME);
                     if (plug == null) {
-                        throw new Exception("Plugin not located.");
+                        showUnknownToolFeedback(pluginName);
+                        return;
+                    }
+                    if (!OutputFileGuard.canRun(this, plug.getName(), args)) {
+                        return;
                     }
                     plug.setPluginHost(this);
                     plug.setArgs(args);
@@ -1369,7 +1374,11 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
                 requestForOperationCancel = false;
                 WhiteboxPlugin plug = pluginService.getPlugin(pluginName, StandardPluginService.SIMPLE_NAME);
                 if (plug == null) {
-                    throw new Exception("Plugin not located.");
+                    showUnknownToolFeedback(pluginName);
+                    return;
+                }
+                if (!OutputFileGuard.canRun(this, plug.getName(), args)) {
+                    return;
                 }
                 plug.setPluginHost(this);
                 plug.setArgs(args);
@@ -1642,6 +1651,37 @@ public class WhiteboxGui extends JFrame implements ThreadListener, ActionListene
         }
     }
 
+    /**
+     * Tells the user that a tool name wasn't recognized, e.g. when a script
+     * misspells the name of a tool, and suggests the closest tool names.
+     */
+    private void showUnknownToolFeedback(String pluginName) {
+        ArrayList<String> candidates = new ArrayList<>();
+        HashMap<String, String> toolNames = new HashMap<>();
+        for (PluginInfo pi : plugInfo) {
+            candidates.add(pi.getName());
+            candidates.add(pi.getDescriptiveName());
+            toolNames.put(pi.getName(), pi.getName());
+            toolNames.put(pi.getDescriptiveName(), pi.getName());
+        }
+        LinkedHashSet<String> suggestions = new LinkedHashSet<>();
+        for (String match : StringUtilities.findClosestMatches(pluginName, candidates, 10)) {
+            if (suggestions.size() < 5) {
+                suggestions.add(toolNames.get(match));
+            }
+        }
+        StringBuilder sb = new StringBuilder();
+        sb.append("No tool named '").append(pluginName).append("' was found.");
+        if (!suggestions.isEmpty()) {
+            sb.append(" Did you mean:");
+            for (String suggestion : suggestions) {
+                sb.append("\n    ").append(suggestion);
+            }
+        }
+        LOGGER.log(Level.WARNING, sb.toString());
+        showFeedback(sb.toString());
+    }
+
     private boolean isToolAScript(String pluginName) {
         boolean isScript = false;
         for (int i = 0; i < plugInfo.size(); i++) {
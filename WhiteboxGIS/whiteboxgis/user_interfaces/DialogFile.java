@@ -269,10 +269,8 @@ public class DialogFile extends JPanel implements ActionListener, DialogComponen
         if (!value.equals(text.getText())) {
             value = text.getText();
         }
-        // see if there is a directory attached to the value
-        if (!value.contains(pathSep) && !value.equals("")) {
-            value = workingDirectory + value;
-        }
+        // relative file names are resolved against the working directory
+        value = FileUtilities.resolvePath(value, workingDirectory);
         // see if there is a file extension attached to the value
         boolean flag = false;
         for (int i = 0; i < filters.size(); i++) {
@@ -1013,7 +1013,7 @@ public class Scripter extends JDialog implements ActionListener, KeyListener {
                     } else if (line.contains("=") && !line.contains("==") && !line.contains("!=")) {
                         // variable definition
                         // first find the name of the variable
-                        String[] s1 = line.split("=");
+                        String[] s1 = line.split("=", 2);
                         if (s1.length == 2) {
                             String[] s2 = s1[0].split(" ");
                             String variableName = s2[s2.length - 1].trim();
@@ -28,6 +28,7 @@ import java.io.File;
 import java.io.IOException;
 import java.net.URL;
 import java.util.ArrayList;
+import java.util.List;
 import java.util.ResourceBundle;
 import java.util.logging.Level;
 import javax.swing.*;
@@ -43,6 +44,7 @@ import org.w3c.dom.NodeList;
 import org.xml.sax.SAXException;
 import whitebox.interfaces.Communicator;
 import whitebox.interfaces.DialogComponent;
+import whitebox.plugins.OutputFileGuard;
 import whitebox.utilities.FileUtilities;
 
 /**
@@ -628,7 +630,23 @@ public class ToolDialog extends JDialog implements Communicator, ActionListener,
         }
 
         if (!containsNull) {
-            runPlugin(pluginName, args);
+            List<String> existing = OutputFileGuard.findExistingOutputs(host.getResourcesDirectory(), pluginName, args);
+            if (existing.isEmpty() || OutputFileGuard.isOverwrite()) {
+                runPlugin(pluginName, args);
+                return;
+            }
+            // ask before replacing an existing output
+            int n = showFeedback("The file " + existing.get(0) + " already exists.\n"
+                    + "Would you like to overwrite it?", JOptionPane.YES_NO_OPTION,
+                    JOptionPane.QUESTION_MESSAGE);
+            if (n == JOptionPane.YES_OPTION) {
+                OutputFileGuard.setOverwrite(true);
+                try {
+                    runPlugin(pluginName, args);
+                } finally {
+                    OutputFileGuard.setOverwrite(false);
+                }
+            }
         }
     }
 
@@ -0,0 +1,139 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import whitebox.geospatialfiles.GeoTiff
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter
+
+// Measures the throughput of the GeoTiff reader on a float32 DEM in striped
+// and tiled layouts, uncompressed and compressed with the floating point
+// predictor. Each file is converted to a Whitebox raster, as the ImportGeoTiff
+// tool does, and, if gdal_translate is on the path, to an ENVI raster with
+// GDAL, so that both read the whole file and write it uncompressed. The
+// target is for the GeoTiff reader to take no more than 1.5 times as long as
+// gdal_translate. Each conversion is run once to warm the file cache and then
+// timed three times, keeping the quickest.
+//
+// Run it from the top directory of the project, after building, with
+//
+//     java -cp "bin/*:lib/*" groovy.ui.GroovyMain benches/GeoTiffReadBenchmark.groovy [rows cols | file.tif ...]
+//
+// By default a synthetic DEM of 4000 x 4000 cells (61 MB) is written in
+// each layout; existing GeoTIFF files may be named instead.
+
+def TestTiff = new GroovyClassLoader(getClass().getClassLoader()).parseClass(
+    new File("tests" + File.separator + "TestTiff.groovy"))
+def workDir = Files.createTempDirectory("whitebox_bench").toString() + File.separator
+
+List<List> files = []
+if (args.length > 0 && args[0].toLowerCase().endsWith(".tif")) {
+    args.each { files << [new File(it).getName(), it] }
+} else {
+    int rows = args.length >= 2 ? Integer.parseInt(args[0]) : 4000
+    int cols = args.length >= 2 ? Integer.parseInt(args[1]) : 4000
+    // a smooth surface with some noise, like a DEM
+    double[] values = new double[rows * cols]
+    Random random = new Random(1)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            values[row * cols + col] = 250 + 80 * Math.sin(row / 400.0) * Math.cos(col / 300.0) +
+                0.02 * (row + col) + 0.1 * random.nextGaussian()
+        }
+    }
+    def layouts = [
+        ["striped", [rowsPerStrip: 1]],
+        ["striped_big_endian", [rowsPerStrip: 16, littleEndian: false]],
+        ["tiled", [tileWidth: 256, tileLength: 256]],
+        ["striped_deflate_predictor3", [rowsPerStrip: 16, compression: 8, predictor: 3]],
+        ["tiled_lzw_predictor3", [tileWidth: 256, tileLength: 256, compression: 5, predictor: 3]],
+        ["tiled_deflate", [tileWidth: 256, tileLength: 256, compression: 8]]
+    ]
+    layouts.each { layout ->
+        String file = workDir + layout[0] + ".tif"
+        println "Writing " + file
+        TestTiff.write(file, rows, cols, values, [bits: 32, sampleFormat: 3] + layout[1])
+        files << [layout[0], file]
+    }
+    values = null
+}
+
+boolean hasGdal = false
+try {
+    hasGdal = ["gdal_translate", "--version"].execute().waitFor() == 0
+} catch (IOException e) {
+    println "gdal_translate was not found, so only the GeoTiff reader is timed."
+}
+
+def bestOf = { Closure run ->
+    run()
+    (1..3).collect {
+        long start = System.nanoTime()
+        run()
+        (System.nanoTime() - start) / 1e9
+    }.min()
+}
+
+def importWithGeoTiff = { String file ->
+    GeoTiff gt = new GeoTiff(file)
+    gt.read()
+    int rows = gt.getNumberRows()
+    int cols = gt.getNumberColumns()
+    WhiteboxRasterStreamWriter output = new WhiteboxRasterStreamWriter(workDir + "output.dep",
+        gt.getNorth(), gt.getSouth(), gt.getEast(), gt.getWest(), rows, cols,
+        DataScale.CONTINUOUS, DataType.FLOAT, gt.getNoData())
+    float[] data = new float[cols]
+    for (int row = 0; row < rows; row++) {
+        gt.getRowValues(row, data)
+        output.writeRow(row, data)
+    }
+    output.close()
+    gt.close()
+}
+
+def translateWithGdal = { String file ->
+    def process = ["gdal_translate", "-q", "-of", "ENVI", file, workDir + "gdal_output.bil"].execute()
+    process.consumeProcessOutput(System.out, System.err)
+    if (process.waitFor() != 0) {
+        throw new RuntimeException("gdal_translate failed on " + file)
+    }
+}
+
+println ""
+println String.format("%-28s %10s %12s %12s %8s", "File", "MB", "GeoTiff (s)", "GDAL (s)", "Ratio")
+boolean allWithinTarget = true
+files.each { entry ->
+    GeoTiff gt = new GeoTiff(entry[1])
+    gt.read()
+    double megabytes = (double) gt.getNumberRows() * gt.getNumberColumns() * gt.getNumberBitsPerSample() / 8 / 1048576
+    gt.close()
+    double geoTiffTime = bestOf { importWithGeoTiff(entry[1]) }
+    if (hasGdal) {
+        double gdalTime = bestOf { translateWithGdal(entry[1]) }
+        double ratio = geoTiffTime / gdalTime
+        allWithinTarget &= ratio <= 1.5
+        println String.format("%-28s %10.1f %12.3f %12.3f %8.2f", entry[0], megabytes, geoTiffTime, gdalTime, ratio)
+    } else {
+        println String.format("%-28s %10.1f %12.3f %12s %8s", entry[0], megabytes, geoTiffTime, "-", "-")
+    }
+}
+if (hasGdal) {
+    println "\n" + (allWithinTarget ? "Every file was read within 1.5 times the time taken by gdal_translate."
+        : "Some files took more than 1.5 times as long to read as with gdal_translate.")
+}
+new File(workDir).deleteDir()
@@ -187,6 +187,29 @@ def WhiteboxTools():
     print("This will take some time, especially on slower computers.\nGo make yourself a cup of coffee (or have a nap if your computer is slow).")
     subprocess.call(cd  + ' whitebox_tools' + slash + ' && cargo build --release', shell=True); 
 
+#generates the python interface to the plugin tools from their dialog files
+def PythonAPI():
+    import python_api
+    python_api.generate()
+
+#exports a JSON manifest of the plugin tools, their toolboxes and their parameters
+def ToolManifest():
+    import tool_manifest
+    tool_manifest.generate()
+
+#runs the tests in tests/ against the compiled plugins in bin/, which come
+#first on the classpath so that they take precedence over lib/MathTools.jar
+def RunTests():
+    print("Running tests")
+    classpath = 'bin' + slash + '*' + (';' if windows else ':') + 'lib' + slash + '*'
+    failed = 0
+    for script in ['FilterTests.groovy', 'VectorTests.groovy', 'HydroTests.groovy', 'MathTests.groovy', 'CostTests.groovy', 'RasterTests.groovy', 'TerrainTests.groovy']:
+        result = subprocess.call('java -cp "' + classpath + '" groovy.ui.GroovyMain tests' + slash + script, shell=True)
+        if (result != 0):
+            failed = result
+    if (failed != 0):
+        sys.exit(failed)
+
 def clean():
     print("Cleaning bin/ and release/")
     if (windows):
@@ -228,6 +251,8 @@ def makeRelease():
     isRelease = True
     makeTest()
     WhiteboxTools()
+    PythonAPI()
+    ToolManifest()
     release()
 
 def help():
@@ -235,7 +260,7 @@ def help():
     print("Make whole project:")
     print("[release][test]")
     print("Make individual packages")
-    print("[clean][whiteboxapi][conversiontools][fileoperations][geasytools, gistools, hydrotools, imageprocessingtools, importexport, lidartools, mathtools, photogrammetry, rastercalculator, rastercreation, statstools, streamnetworkanalysistools, terrainanalysistools, vectortools][whiteboxgis]")
+    print("[clean][whiteboxapi][conversiontools][fileoperations][geasytools, gistools, hydrotools, imageprocessingtools, importexport, lidartools, mathtools, photogrammetry, rastercalculator, rastercreation, statstools, streamnetworkanalysistools, terrainanalysistools, vectortools][whiteboxgis][pythonapi][manifest][runtests]")
     print("If no argument is provided, the test argument is assumed")
     print("\nExamples:")
     print("python build.py release\npython build.py WhiteboxAPI GIStools\npython build.py clean GIStools vectorTools")
@@ -274,7 +299,7 @@ if (windows):
 apiWin = "WhiteboxAPI/whitebox/algorithms/*.java WhiteboxAPI/whitebox/cartographic/*.java WhiteboxAPI/whitebox/georeference/*.java WhiteboxAPI/whitebox/geospatialfiles/*.java WhiteboxAPI/whitebox/geospatialfiles/shapefile/*.java WhiteboxAPI/whitebox/geospatialfiles/shapefile/attributes/*.java WhiteboxAPI/whitebox/interfaces/*.java WhiteboxAPI/whitebox/internationalization/*.java WhiteboxAPI/whitebox/parallel/*.java WhiteboxAPI/whitebox/plugins/*.java WhiteboxAPI/whitebox/projections/*.java WhiteboxAPI/whitebox/serialization/*.java WhiteboxAPI/whitebox/stats/*.java WhiteboxAPI/whitebox/structures/*.java WhiteboxAPI/whitebox/ui/*.java WhiteboxAPI/whitebox/ui/carto_properties/*.java WhiteboxAPI/whitebox/ui/plugin_dialog/*.java WhiteboxAPI/whitebox/utilities/*.java"
 
 #dictionart of functions
-functions = {"clean":clean, "whiteboxapi":WhiteboxAPI, "conversiontools":ConversionTools, "fileoperations":FIleOperations, "geasytools":GeasyTools, "gistools":GISTools, "hydrotools":HydroTools, "imageprocessingtools":ImageProcessingTools, "importexport":ImportExport, "lidartools":LidarTools, "mathtools":MathTools, "photogrammetry":Photogrammetry, "rastercalculator":RasterCalculator, "rastercreation":RasterCreation, "statstools":StatsTools, "streamnetworkanalysistools":StreamNetworkAnalysisTools, "terrainanalysistools":TerrainAnalysisTools, "vectortools":VectorTools, "whiteboxgis":WhiteboxGIS, "whiteboxtools":WhiteboxTools}
+functions = {"clean":clean, "whiteboxapi":WhiteboxAPI, "conversiontools":ConversionTools, "fileoperations":FIleOperations, "geasytools":GeasyTools, "gistools":GISTools, "hydrotools":HydroTools, "imageprocessingtools":ImageProcessingTools, "importexport":ImportExport, "lidartools":LidarTools, "mathtools":MathTools, "photogrammetry":Photogrammetry, "rastercalculator":RasterCalculator, "rastercreation":RasterCreation, "statstools":StatsTools, "streamnetworkanalysistools":StreamNetworkAnalysisTools, "terrainanalysistools":TerrainAnalysisTools, "vectortools":VectorTools, "whiteboxgis":WhiteboxGIS, "whiteboxtools":WhiteboxTools, "pythonapi":PythonAPI, "manifest":ToolManifest, "runtests":RunTests}
 
 #no args
 if (len(sys.argv) == 1):
@@ -0,0 +1,350 @@
+# Generates a Python interface to the Whitebox GAT plugin tools
+#
+# Copyright (C) 2026 Whitebox GAT contributors
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+# Each plugin registered in a META-INF/services file becomes a method of the
+# WhiteboxTools class in the generated whitebox_tools.py. The parameters of a
+# tool are read from its dialog file (resources/plugins/Dialogs/<name>.xml),
+# which lists the tool's arguments in the order in which the plugin expects
+# them. Run with 'python build.py pythonapi' or 'python python_api.py'.
+
+import glob
+import io
+import keyword
+import os
+import re
+import sys
+import textwrap
+import xml.etree.ElementTree as ET
+
+serviceFiles = os.path.join('*', 'META-INF', 'services', 'whitebox.interfaces.WhiteboxPlugin')
+dialogsDir = os.path.join('resources', 'plugins', 'Dialogs')
+outputFile = os.path.join('resources', 'plugins', 'whitebox_tools.py')
+
+header = '''# -*- coding: utf-8 -*-
+\'\'\'
+Python interface to the Whitebox GAT plugin tools.
+
+This file was generated by python_api.py from the tool dialog files. Do not
+edit it by hand; run 'python build.py pythonapi' to regenerate it.
+
+Each tool is a method of the WhiteboxTools class. Parameters that have a
+default value in the tool's dialog are optional, and optional parameters
+with a default of None are passed to the tool as 'not specified'. Required
+parameters come before optional ones, so it is best to call the methods
+with keyword arguments. For example, within the Whitebox Scripter:
+
+    import sys
+    sys.path.append(pluginHost.getResourcesDirectory() + "plugins")
+    from whitebox_tools import WhiteboxTools
+
+    wbt = WhiteboxTools(pluginHost)
+    wd = pluginHost.getWorkingDirectory()
+    wbt.fill_depressions(input_file=wd + "DEM.dep", output_file=wd + "filled.dep")
+
+A tool won't run if any of its output files already exists; instead, an
+OSError with errno EEXIST is raised, so that finished results aren't
+silently replaced during long batch runs. The output files are found by
+whitebox.plugins.OutputFileGuard, which makes the same check for tools run
+from the Whitebox user interface. Create the WhiteboxTools object with
+overwrite=True, or set its overwrite attribute, to replace existing outputs.
+\'\'\'
+
+import errno
+
+from whitebox.plugins import OutputFileGuard
+
+NOT_SPECIFIED = "not specified"
+
+
+def _num(value):
+    value = float(value)
+    if value == int(value):
+        return str(int(value))
+    return repr(value)
+
+
+def _bool(value):
+    if value:
+        return "true"
+    return "false"
+
+
+def _files(value):
+    if isinstance(value, (list, tuple)):
+        return ";".join([str(v) for v in value])
+    return str(value)
+
+
+def _opt(value, convert):
+    if value is None:
+        return NOT_SPECIFIED
+    return convert(value)
+
+
+class WhiteboxTools(object):
+    \'\'\'Runs the Whitebox GAT plugin tools through a plugin host.\'\'\'
+
+    def __init__(self, plugin_host, run_on_dedicated_thread=False, suppress_returned_data=False,
+                 overwrite=False):
+        self.plugin_host = plugin_host
+        self.run_on_dedicated_thread = run_on_dedicated_thread
+        self.suppress_returned_data = suppress_returned_data
+        self.overwrite = overwrite
+
+    def run_tool(self, tool_name, args):
+        \'\'\'Runs a tool by name with a list of string arguments.
+
+        Raises OSError (errno EEXIST) if any of the tool's output files already
+        exists and overwrite is not set.
+        \'\'\'
+        args = [str(a) for a in args]
+        if not self.overwrite:
+            existing = OutputFileGuard.findExistingOutputs(self.plugin_host.getResourcesDirectory(),
+                                                           tool_name, args)
+            if not existing.isEmpty():
+                raise OSError(errno.EEXIST, tool_name + " output file already exists "
+                              "(use overwrite=True to replace it)", existing.get(0))
+        previous = OutputFileGuard.isOverwrite()
+        OutputFileGuard.setOverwrite(self.overwrite)
+        try:
+            self.plugin_host.runPlugin(tool_name, args, self.run_on_dedicated_thread,
+                                       self.suppress_returned_data)
+        finally:
+            OutputFileGuard.setOverwrite(previous)
+'''
+
+
+class Parameter:
+    def __init__(self, name, pyType, description, default=None, hasDefault=False, choices=None, convert='str'):
+        self.name = name
+        self.pyType = pyType
+        self.description = description
+        self.default = default
+        self.hasDefault = hasDefault
+        self.choices = choices
+        self.convert = convert
+
+
+# reads the literal returned by a method such as getName() in a plugin's source file
+def getReturnedString(source, method):
+    m = re.search(r'public\s+String\s+' + method + r'\s*\(\s*\)\s*\{\s*return\s+"((?:[^"\\]|\\.)*)"\s*;', source)
+    if m:
+        return m.group(1).replace('\\"', '"').replace('\\n', ' ')
+    return None
+
+
+# finds the source code of the registered plugins, keyed on the tool name
+def findPluginSources():
+    sources = {}
+    for services in sorted(glob.glob(serviceFiles)):
+        folder = services.split(os.sep)[0]
+        with io.open(services, encoding='latin-1') as f:
+            classNames = [line.strip() for line in f if line.strip() and not line.strip().startswith('#')]
+        for className in classNames:
+            sourceFile = os.path.join(folder, *className.split('.')) + '.java'
+            if not os.path.exists(sourceFile):
+                continue
+            with io.open(sourceFile, encoding='latin-1') as f:
+                # ignore commented-out methods
+                source = re.sub(r'//[^\n]*', '', f.read())
+            name = getReturnedString(source, 'getName') or className.split('.')[-1]
+            sources[name] = source
+    return sources
+
+
+# finds the registered plugins, returning (name, descriptive name, description) tuples
+def findTools():
+    tools = []
+    for name, source in sorted(findPluginSources().items()):
+        descriptiveName = getReturnedString(source, 'getDescriptiveName') or name
+        description = getReturnedString(source, 'getToolDescription') or ''
+        tools.append((name, descriptiveName, description))
+    return tools
+
+
+def snakeCase(name):
+    s = re.sub(r'([A-Z]+)([A-Z][a-z])', r'\1_\2', name)
+    s = re.sub(r'([a-z0-9])([A-Z])', r'\1_\2', s)
+    s = re.sub(r'[^0-9A-Za-z]+', '_', s).strip('_').lower()
+    s = re.sub(r'_+', '_', s)
+    if not s or s[0].isdigit():
+        s = 'tool_' + s
+    return s
+
+
+def getText(el, tag, default=''):
+    child = el.find(tag)
+    if child is None or child.text is None:
+        return default
+    return child.text.strip()
+
+
+def isTrue(el, tag):
+    return getText(el, tag).lower() == 'true'
+
+
+# converts a dialog component into a parameter, or None for components that aren't arguments
+def readComponent(el):
+    componentType = el.get('type')
+    name = snakeCase(getText(el, 'Name', 'arg'))
+    description = getText(el, 'Description') or getText(el, 'LabelText')
+    if componentType == 'DialogFile':
+        if isTrue(el, 'MakeOptional'):
+            return Parameter(name, 'str', description, None, True, convert='str')
+        return Parameter(name, 'str', description)
+    elif componentType == 'DialogMultiFile':
+        return Parameter(name, 'list of str', description, convert='_files')
+    elif componentType == 'DialogCheckBox':
+        return Parameter(name, 'bool', description, isTrue(el, 'InitialState'), True, convert='_bool')
+    elif componentType == 'DialogComboBox':
+        choices = [c.strip() for c in getText(el, 'ListItems').split(',')]
+        try:
+            default = choices[int(getText(el, 'DefaultItem', '0'))]
+        except (ValueError, IndexError):
+            default = choices[0]
+        return Parameter(name, 'str', description, default, True, choices)
+    elif componentType == 'DialogOption':
+        choices = [getText(el, 'Button1Label'), getText(el, 'Button2Label')]
+        return Parameter(name, 'str', description, choices[0], True, choices)
+    elif componentType == 'DialogDataInput':
+        numerical = isTrue(el, 'NumericalInputOnly')
+        pyType = 'float' if numerical else 'str'
+        convert = '_num' if numerical else 'str'
+        initialText = getText(el, 'InitialText')
+        if isTrue(el, 'MakeOptional'):
+            return Parameter(name, pyType, description, None, True, convert=convert)
+        if initialText:
+            if numerical:
+                try:
+                    return Parameter(name, pyType, description, float(initialText), True, convert=convert)
+                except ValueError:
+                    pass
+            else:
+                return Parameter(name, pyType, description, initialText, True, convert=convert)
+        return Parameter(name, pyType, description, convert=convert)
+    elif componentType in ('DialogFieldSelector', 'DialogWeightedMultiFile', 'DialogReclassGrid'):
+        return Parameter(name, 'str', description)
+    return None
+
+
+# reads the parameters of a tool from its dialog file, or returns None if the tool has no usable dialog
+def readParameters(toolName):
+    dialogFile = os.path.join(dialogsDir, toolName + '.xml')
+    if not os.path.exists(dialogFile):
+        return None
+    try:
+        root = ET.parse(dialogFile).getroot()
+    except ET.ParseError as e:
+        print("Unable to read " + dialogFile + ": " + str(e))
+        return None
+    params = []
+    for el in root.iter('DialogComponent'):
+        if el.get('type') == 'CustomDialogProvidedByPlugin':
+            return None
+        p = readComponent(el)
+        if p is not None:
+            params.append(p)
+    # make the names valid and unique
+    used = set(['self'])
+    for p in params:
+        name = pyName(p.name)
+        n = 2
+        base = name
+        while name in used:
+            name = base + str(n)
+            n += 1
+        used.add(name)
+        p.name = name
+    return params
+
+
+def pyName(name):
+    if keyword.iskeyword(name) or name in ('print', 'exec', 'None', 'True', 'False'):
+        return name + '_'
+    return name
+
+
+def sentence(text):
+    text = ' '.join(text.split())
+    if text and text[-1] not in '.?!:':
+        text += '.'
+    return text
+
+
+def docText(text):
+    return text.replace('\\', '\\\\').replace('"""', '\\"\\"\\"')
+
+
+def writeTool(out, tool, params):
+    name, descriptiveName, description = tool
+    # Python requires the optional parameters to follow the required ones;
+    # the argument list passed to the tool keeps the dialog's order
+    ordered = [p for p in params if not p.hasDefault] + [p for p in params if p.hasDefault]
+    signature = ['self']
+    for p in ordered:
+        if p.hasDefault:
+            signature.append(p.name + '=' + repr(p.default))
+        else:
+            signature.append(p.name)
+    out.append('\n    def ' + pyName(snakeCase(name)) + '(' + ', '.join(signature) + '):\n')
+    out.append('        """' + docText(descriptiveName) + '.\n')
+    if description and description != descriptiveName:
+        out.append('\n')
+        for line in textwrap.wrap(docText(sentence(description)), 80):
+            out.append('        ' + line + '\n')
+    if ordered:
+        out.append('\n        Args:\n')
+        for p in ordered:
+            text = p.name + ' (' + p.pyType + '): ' + docText(sentence(p.description))
+            if p.choices:
+                text += ' One of: ' + ', '.join(['"' + docText(c) + '"' for c in p.choices]) + '.'
+            for i, line in enumerate(textwrap.wrap(text, 80)):
+                out.append(('            ' if i == 0 else '                ') + line + '\n')
+    out.append('        """\n')
+    args = []
+    for p in params:
+        if p.hasDefault and p.default is None:
+            args.append('_opt(' + p.name + ', ' + p.convert + ')')
+        else:
+            args.append(p.convert + '(' + p.name + ')')
+    out.append('        args = [' + ', '.join(args) + ']\n')
+    out.append('        self.run_tool(' + repr(name) + ', args)\n')
+
+
+def generate(fileName=outputFile):
+    print("Generating Python API: " + fileName)
+    count = 0
+    out = [header]
+    methods = set()
+    for tool in findTools():
+        params = readParameters(tool[0])
+        method = pyName(snakeCase(tool[0]))
+        if params is None or method in methods:
+            continue
+        methods.add(method)
+        writeTool(out, tool, params)
+        count += 1
+    with open(fileName, 'wb') as f:
+        f.write(''.join(out).encode('utf-8'))
+    print("Wrote " + str(count) + " tools")
+
+
+if __name__ == '__main__':
+    if len(sys.argv) > 1:
+        generate(sys.argv[1])
+    else:
+        generate()
@@ -45,8 +45,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -106,7 +104,7 @@ public class ConditionalEvaluation implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-			//Date start = new Date()
+			//long start = System.nanoTime()
 			double value, trueValue = 0, falseValue = 0
 			boolean ret
 			int progress, oldProgress
@@ -459,8 +457,7 @@ public class ConditionalEvaluation implements ActionListener {
 	        output.addMetadataEntry("Created on " + new Date())
 			output.close()
 
-//			Date stop = new Date()
-//			TimeDuration td = TimeCategory.minus(stop, start)
+//			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 //			pluginHost.showFeedback("Elapsed time: $td")
 	
 			// display the output image
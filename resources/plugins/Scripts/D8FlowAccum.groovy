@@ -36,8 +36,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -97,7 +95,7 @@ public class D8FlowAccum implements ActionListener {
 	private void execute(String[] args) {
 		try {
 
-			Date start = new Date()
+			long start = System.nanoTime()
 			
 			int progress, oldProgress, i, row, col, rN, cN, dir, n
 	  		int[] dX = [ 1, 1, 1, 0, -1, -1, -1, 0 ]
@@ -335,8 +333,7 @@ public class D8FlowAccum implements ActionListener {
 	        output.addMetadataEntry("Created on " + new Date())
 			output.close()
 
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus( stop, start )
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			pluginHost.showFeedback("Elapsed time: $td")
 			
 			// display the output image
@@ -0,0 +1,547 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "DarkObjectSubtraction"
+def descriptiveName = "Dark Object Subtraction"
+def description = "Performs a dark object subtraction (DOS) atmospheric correction on image bands."
+def toolboxes = ["ImageProc"]
+
+public class DarkObjectSubtraction implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public DarkObjectSubtraction(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogMultiFile("Select the input image bands", "Input Image Bands:", "Raster Files (*.dep), DEP")
+			sd.addDialogDataInput("Output file suffix; output files are named after the input files with this suffix", "Output File Suffix:", "dos", false, false)
+			sd.addDialogComboBox("Correction method; DOS2 and DOS4 require top-of-atmosphere reflectance, either as input or by converting DN", "Method:", ["simple DOS", "DOS2", "DOS4"], 0)
+			sd.addDialogDataInput("Percentage of non-zero pixels assumed to be truly dark", "Percent Dark (%):", "0.01", true, false)
+			sd.addDialogDataInput("Solar zenith angle (degrees); required by DOS2 and DOS4 unless a sun elevation is given", "Solar Zenith Angle (degrees):", "", true, true)
+			sd.addDialogComboBox("Method used to estimate the dark-object value of each band", "Dark-Object Value:", ["percentile", "minimum", "user-specified"], 0)
+			sd.addDialogDataInput("User-specified dark-object values, in input units; a comma-separated list with one value per band, or a single value for all bands", "User-Specified Dark-Object Values:", "", false, true)
+			sd.addDialogCheckBox("Convert digital numbers (DN) to top-of-atmosphere reflectance before the correction?", "Convert DN to Top-of-Atmosphere Reflectance", false)
+			sd.addDialogDataInput("Reflectance gains; a comma-separated list with one value per band, or a single value for all bands", "Reflectance Gains:", "", false, true)
+			sd.addDialogDataInput("Reflectance offsets; a comma-separated list with one value per band, or a single value for all bands", "Reflectance Offsets:", "", false, true)
+			sd.addDialogDataInput("Sun elevation angle (degrees)", "Sun Elevation (degrees):", "", true, true)
+			sd.addDialogFile("Image metadata file (e.g. a Landsat MTL file) from which missing gains, offsets and sun elevation are read", "Input Metadata File (optional):", "open", "Text Files (*.txt), TXT", true, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 4) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			List<String> inputFiles = new ArrayList<>()
+			for (String s : args[0].split("[;,]")) {
+				if (!s.trim().isEmpty()) {
+					inputFiles.add(s.trim())
+				}
+			}
+			int numBands = inputFiles.size()
+			if (numBands == 0) {
+				pluginHost.showFeedback("No input images were specified.")
+				return
+			}
+			String suffix = args[1].trim()
+			if (suffix.isEmpty()) {
+				suffix = "dos"
+			}
+			String method = args[2].trim().toLowerCase().replace("_", " ")
+			if (method.contains("simple") || method.equals("dos") || method.equals("dos1")) {
+				method = "simple DOS"
+			} else if (method.equals("dos2")) {
+				method = "DOS2"
+			} else if (method.equals("dos4")) {
+				method = "DOS4"
+			} else {
+				pluginHost.showFeedback("Unrecognized method '${args[2]}'. The method must be one of simple DOS, DOS2, or DOS4.")
+				return
+			}
+			double percentDark = Double.parseDouble(args[3])
+			if (percentDark <= 0 || percentDark >= 100) {
+				pluginHost.showFeedback("The percent dark value must be between 0 and 100.")
+				return
+			}
+			double solarZenith = -1.0
+			if (args.length > 4 && isSpecified(args[4])) {
+				solarZenith = Double.parseDouble(args[4])
+			}
+			String darkMode = "percentile"
+			if (args.length > 5 && isSpecified(args[5])) {
+				darkMode = args[5].trim().toLowerCase()
+				if (darkMode.startsWith("min")) {
+					darkMode = "minimum"
+				} else if (darkMode.startsWith("user")) {
+					darkMode = "user-specified"
+				} else if (!darkMode.equals("percentile")) {
+					pluginHost.showFeedback("Unrecognized dark-object value method '${args[5]}'. It must be one of percentile, minimum, or user-specified.")
+					return
+				}
+			}
+			double[] userDarkValues = null
+			if (darkMode == "user-specified") {
+				userDarkValues = parseBandList(args.length > 6 ? args[6] : null, numBands, "user-specified dark-object values")
+				if (userDarkValues == null) {
+					return
+				}
+			}
+			boolean convertToReflectance = args.length > 7 && Boolean.parseBoolean(args[7])
+
+			/* Reflectance conversion parameters given as arguments take
+			 * precedence over those read from the metadata file.
+			 */
+			double[] gains = null
+			double[] offsets = null
+			double sunElevation = -1.0
+			if (convertToReflectance) {
+				Map<String, String> metadata = new HashMap<>()
+				if (args.length > 11 && isSpecified(args[11])) {
+					metadata = readMetadataFile(args[11])
+				}
+				if (args.length > 8 && isSpecified(args[8])) {
+					gains = parseBandList(args[8], numBands, "reflectance gains")
+				} else {
+					gains = readBandValues(metadata, inputFiles, ["REFLECTANCE_MULT_BAND_", "GAIN_BAND_"], "gain")
+				}
+				if (gains == null) {
+					return
+				}
+				if (args.length > 9 && isSpecified(args[9])) {
+					offsets = parseBandList(args[9], numBands, "reflectance offsets")
+				} else {
+					offsets = readBandValues(metadata, inputFiles, ["REFLECTANCE_ADD_BAND_", "OFFSET_BAND_"], "offset")
+				}
+				if (offsets == null) {
+					return
+				}
+				if (args.length > 10 && isSpecified(args[10])) {
+					sunElevation = Double.parseDouble(args[10])
+				} else if (metadata.containsKey("SUN_ELEVATION")) {
+					sunElevation = Double.parseDouble(metadata.get("SUN_ELEVATION"))
+				} else {
+					pluginHost.showFeedback("The sun elevation must be specified to convert DN to top-of-atmosphere reflectance.")
+					return
+				}
+				if (sunElevation <= 0 || sunElevation > 90) {
+					pluginHost.showFeedback("The sun elevation must be between 0 and 90 degrees.")
+					return
+				}
+				if (solarZenith < 0) {
+					solarZenith = 90.0 - sunElevation
+				}
+			}
+
+			double cosZenith = 1.0
+			if (method != "simple DOS") {
+				if (solarZenith < 0) {
+					pluginHost.showFeedback("The solar zenith angle must be specified for the ${method} method.")
+					return
+				}
+				if (solarZenith >= 90) {
+					pluginHost.showFeedback("The solar zenith angle must be between 0 and 90 degrees.")
+					return
+				}
+				cosZenith = Math.cos(Math.toRadians(solarZenith))
+			}
+			double sinElevation = convertToReflectance ? Math.sin(Math.toRadians(sunElevation)) : 1.0d
+
+			StringBuilder summary = new StringBuilder()
+			summary.append("Dark-object values (${method}):")
+			List<String> outputFiles = new ArrayList<>()
+			for (int b = 0; b < numBands; b++) {
+				String inputFile = inputFiles.get(b)
+				WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+				double nodata = image.getNoDataValue()
+				int rows = image.getNumberRows()
+				int cols = image.getNumberColumns()
+				String label = "Band ${b + 1} of ${numBands}:"
+				double gain = convertToReflectance ? gains[b] : 1.0d
+				double bias = convertToReflectance ? offsets[b] : 0.0d
+
+				/* Find the dark-object value in input units. For the
+				 * percentile method, this is the darkest value that is
+				 * exceeded by all but percentDark of the non-zero pixels.
+				 * Zero is excluded because it is the fill value around the
+				 * edges of most scenes. Integer-valued images, e.g. 16-bit
+				 * DNs, are binned exactly; otherwise a fine histogram is used.
+				 */
+				double darkValue
+				double[] data
+				double z
+				if (darkMode == "user-specified") {
+					darkValue = userDarkValues[b]
+				} else {
+					double minValue = Double.POSITIVE_INFINITY
+					double maxValue = Double.NEGATIVE_INFINITY
+					boolean isInteger = true
+					long numCells = 0
+					for (int row = 0; row < rows; row++) {
+						data = image.getRowValues(row)
+						for (int col = 0; col < cols; col++) {
+							z = data[col]
+							if (z != nodata && z != 0) {
+								if (z < minValue) { minValue = z }
+								if (z > maxValue) { maxValue = z }
+								if (isInteger && z != Math.floor(z)) {
+									isInteger = false
+								}
+								numCells++
+							}
+						}
+					}
+					if (numCells == 0) {
+						pluginHost.showFeedback("${inputFile} does not contain any non-zero valid pixels.")
+						image.close()
+						return
+					}
+
+					darkValue = minValue
+					if (darkMode == "percentile") {
+						int numBins
+						double binWidth
+						if (isInteger && maxValue - minValue < 1000000) {
+							numBins = (int)(maxValue - minValue) + 1
+							binWidth = 1.0
+						} else {
+							numBins = 100000
+							binWidth = (maxValue - minValue) / numBins
+							if (binWidth <= 0) {
+								binWidth = 1.0
+							}
+						}
+						long[] histo = new long[numBins]
+						int bin
+						for (int row = 0; row < rows; row++) {
+							data = image.getRowValues(row)
+							for (int col = 0; col < cols; col++) {
+								z = data[col]
+								if (z != nodata && z != 0) {
+									bin = (int)Math.floor((z - minValue) / binWidth)
+									if (bin >= numBins) { bin = numBins - 1 }
+									histo[bin]++
+								}
+							}
+							updateProgress(label, (int)(50f * row / rows))
+							if (pluginHost.isRequestForOperationCancelSet()) {
+								pluginHost.showFeedback("Operation cancelled")
+								return
+							}
+						}
+						long darkCount = Math.max(1L, (long)Math.ceil(numCells * percentDark / 100.0))
+						long cumulative = 0
+						for (int i = 0; i < numBins; i++) {
+							cumulative += histo[i]
+							if (cumulative >= darkCount) {
+								darkValue = minValue + i * binWidth
+								break
+							}
+						}
+					}
+				}
+
+				// the haze value is expressed in the units of the corrected image
+				double haze = (gain * darkValue + bias) / sinElevation
+
+				/* For the simple DOS, the haze value is subtracted from
+				 * every pixel. DOS2 and DOS4 instead assume that the dark
+				 * object has a surface reflectance of 1%, so the path
+				 * reflectance is the haze value less the reflectance of a 1%
+				 * target seen through the atmosphere, i.e.
+				 * rho_p = haze - 0.01 Tv (Tz + Ed), and surface reflectance
+				 * is (rho - rho_p) / (Tv (Tz + Ed)). DOS2 uses Tv = 1,
+				 * Tz = cos(zenith) and Ed = 0. DOS4 uses Tv = exp(-tau),
+				 * for a nadir view, Tz = exp(-tau / cos(zenith)) and a
+				 * diffuse sky irradiance equal to the path reflectance, with
+				 * the optical thickness, tau, solved for iteratively.
+				 */
+				double offset = haze
+				double divisor = 1.0
+				double tau = 0.0
+				if (method != "simple DOS") {
+					if (haze > 1.0) {
+						pluginHost.showFeedback("The ${method} method requires top-of-atmosphere reflectance, but the haze value of ${inputFile} is greater than one. Convert the DN to reflectance first.")
+						image.close()
+						return
+					}
+					double tv = 1.0
+					double tz = cosZenith
+					double ed = 0.0
+					double pathRefl = haze - 0.01 * tv * (tz + ed)
+					if (method == "DOS4") {
+						tz = 1.0
+						for (int iter = 0; iter < 100; iter++) {
+							pathRefl = haze - 0.01 * tv * (tz + ed)
+							if (pathRefl <= 0) {
+								pathRefl = 0
+								tau = 0
+								tv = 1.0
+								tz = 1.0
+								ed = 0.0
+								break
+							}
+							if (4.0 * pathRefl >= 1.0) {
+								pluginHost.showFeedback("The optical thickness for ${inputFile} could not be solved; the haze value is too large for the DOS4 method.")
+								image.close()
+								return
+							}
+							double newTau = -cosZenith * Math.log(1.0 - 4.0 * pathRefl)
+							tv = Math.exp(-newTau)
+							tz = Math.exp(-newTau / cosZenith)
+							ed = pathRefl
+							if (Math.abs(newTau - tau) < 1e-8) {
+								tau = newTau
+								break
+							}
+							tau = newTau
+						}
+					} else if (pathRefl < 0) {
+						// the dark object is darker than a 1% target, so there is no haze
+						pathRefl = 0
+					}
+					offset = pathRefl
+					divisor = tv * (tz + ed)
+				}
+
+				String outputFile = inputFile.replace(".dep", "_" + suffix + ".dep")
+				WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw",
+				  inputFile, DataType.FLOAT, nodata)
+				output.setPreferredPalette(image.getPreferredPalette())
+				output.setDataScale(DataScale.CONTINUOUS)
+
+				// zero-valued fill pixels in the scene margins become NoData
+				double zOut
+				for (int row = 0; row < rows; row++) {
+					data = image.getRowValues(row)
+					for (int col = 0; col < cols; col++) {
+						z = data[col]
+						if (z != nodata && z != 0) {
+							zOut = ((gain * z + bias) / sinElevation - offset) / divisor
+							if (zOut < 0) {
+								zOut = 0
+							}
+							output.setValue(row, col, zOut)
+						} else {
+							output.setValue(row, col, nodata)
+						}
+					}
+					updateProgress(label, (int)(50f + 50f * row / rows))
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+
+				image.close()
+
+				output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+				output.addMetadataEntry("Created on " + new Date())
+				output.addMetadataEntry("Method: " + method)
+				if (darkMode == "percentile") {
+					output.addMetadataEntry("Dark-object value: " + darkValue + " (" + percentDark + " percentile)")
+				} else {
+					output.addMetadataEntry("Dark-object value: " + darkValue + " (" + darkMode + ")")
+				}
+				if (convertToReflectance) {
+					output.addMetadataEntry("Converted to top-of-atmosphere reflectance; gain: " + gain + ", offset: " + bias + ", sun elevation: " + sunElevation + " degrees")
+				}
+				output.addMetadataEntry("Haze value: " + haze)
+				if (method != "simple DOS") {
+					output.addMetadataEntry("Solar zenith angle: " + solarZenith + " degrees")
+					output.addMetadataEntry("Path reflectance: " + offset)
+				}
+				if (method == "DOS4") {
+					output.addMetadataEntry("Optical thickness: " + tau)
+				}
+				output.close()
+
+				outputFiles.add(outputFile)
+				summary.append("\n").append(new File(inputFile).getName()).append(": ").append(darkValue)
+			}
+
+			pluginHost.showFeedback(summary.toString())
+
+			// display the output images
+			for (String outputFile : outputFiles) {
+				pluginHost.returnData(outputFile)
+			}
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	/* Parses a comma-separated list of per-band values. A single value
+	 * is applied to every band. Returns null, after notifying the user,
+	 * if the list is missing or has the wrong number of entries.
+	 */
+	@CompileStatic
+	private double[] parseBandList(String list, int numBands, String description) {
+		if (list == null || !isSpecified(list)) {
+			pluginHost.showFeedback("The ${description} must be specified.")
+			return null
+		}
+		String[] vals = list.trim().split("[,;]")
+		if (vals.length != 1 && vals.length != numBands) {
+			pluginHost.showFeedback("The number of ${description} (${vals.length}) does not match the number of input images (${numBands}).")
+			return null
+		}
+		double[] ret = new double[numBands]
+		for (int i = 0; i < numBands; i++) {
+			ret[i] = Double.parseDouble(vals[vals.length == 1 ? 0 : i].trim())
+		}
+		return ret
+	}
+
+	/* Reads 'KEY = value' lines from a metadata text file, such as a
+	 * Landsat MTL file. Keys are upper-cased and quotes are removed from
+	 * values; lines without an equals sign are ignored.
+	 */
+	@CompileStatic
+	private Map<String, String> readMetadataFile(String fileName) {
+		Map<String, String> ret = new HashMap<>()
+		new File(fileName).eachLine { String line ->
+			int i = line.indexOf("=")
+			if (i > 0) {
+				String key = line.substring(0, i).trim().toUpperCase()
+				String value = line.substring(i + 1).trim().replace("\"", "")
+				ret.put(key, value)
+			}
+		}
+		return ret
+	}
+
+	/* Looks up a per-band value in the metadata using the band number
+	 * in each input file's name, e.g. 'LC08_..._B4.dep' or 'band4.dep'.
+	 * If a file name does not end in a band number, the band's position
+	 * in the input list is used.
+	 */
+	@CompileStatic
+	private double[] readBandValues(Map<String, String> metadata, List<String> inputFiles,
+	  List<String> keyPrefixes, String description) {
+		int numBands = inputFiles.size()
+		double[] ret = new double[numBands]
+		for (int b = 0; b < numBands; b++) {
+			String shortName = new File(inputFiles.get(b)).getName().replace(".dep", "")
+			java.util.regex.Matcher m = shortName =~ /(?i)(?:b|band)_?(\d+)$/
+			String bandNum = m.find() ? String.valueOf(Integer.parseInt(m.group(1))) : String.valueOf(b + 1)
+			String value = null
+			for (String prefix : keyPrefixes) {
+				if (metadata.containsKey(prefix + bandNum)) {
+					value = metadata.get(prefix + bandNum)
+					break
+				}
+			}
+			if (value == null) {
+				pluginHost.showFeedback("The reflectance ${description} for band ${bandNum} (${shortName}) was not specified and could not be found in the metadata file.")
+				return null
+			}
+			ret[b] = Double.parseDouble(value)
+		}
+		return ret
+	}
+
+	private int previousProgress = -1
+
+	@CompileStatic
+	private void updateProgress(String label, int progress) {
+		if (progress != previousProgress) {
+			pluginHost.updateProgress(label, progress)
+			previousProgress = progress
+		}
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new DarkObjectSubtraction(pluginHost, args, name, descriptiveName)
+}
@@ -32,8 +32,6 @@ import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
 import whitebox.structures.BooleanBitArray2D
 import whitebox.structures.DoubleArray2D
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -102,7 +100,7 @@ public class DenoiseDEM implements ActionListener {
 			long sum
 			int sumN, numCells
 			
-			Date start = new Date();
+			long start = System.nanoTime();
 			
 			// read the input parameters
 			String inputFile = args[0];
@@ -515,8 +513,7 @@ public class DenoiseDEM implements ActionListener {
 			output.setDisplayMinimum(image.getDisplayMinimum())
 			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
 	        output.addMetadataEntry("Created on " + new Date())
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			output.addMetadataEntry("Elapsed time: $td")
 			output.addMetadataEntry("Input DEM: $demShortName")
 			output.addMetadataEntry("Filter Half-size: $filterSize")
@@ -30,8 +30,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -89,7 +87,7 @@ public class ElevationPercentileImageHisto implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-			Date start = new Date()
+			long start = System.nanoTime()
 			
 			int row, col, bin, progress, oldProgress, N, numLess, numInBin
 			int x1, x2, y1, y2
@@ -230,8 +228,7 @@ public class ElevationPercentileImageHisto implements ActionListener {
 	        output.addMetadataEntry("Created on " + new Date())
 	        output.addMetadataEntry("Window size (D): ${neighbourhoodSize * 2 + 1}")
 			output.addMetadataEntry("Histogram bins: $numBins")
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			output.addMetadataEntry("Elapsed time: $td")
 			
 			output.close()
@@ -0,0 +1,433 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+ /*
+  * This tool propagates the uncertainty of a set of input rasters
+  * through an expression, using a first-order Taylor expansion and,
+  * optionally, Monte Carlo simulation.
+  */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import java.util.Random
+import java.util.function.ToDoubleFunction
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.ui.plugin_dialog.ScriptDialog
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "ErrorPropagation"
+def descriptiveName = "Error Propagation"
+def description = "Propagates the standard errors of input rasters through an expression."
+def toolboxes = ["MathTools", "StatisticalTools"]
+
+public class ErrorPropagation implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public ErrorPropagation(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogMultiFile("Select the input raster files", "Input Raster Files:", "Raster Files (*.dep), DEP")
+			sd.addDialogDataInput("Standard error of each input, as a raster file or constant value, separated by semicolons", "Standard Errors (e.g. dem_error.dep; 0.5):", "", false, false)
+			sd.addDialogDataInput("<html>Expression.\nInputs are referred to as [name] or x1, x2, etc. in the order of the input files, e.g. [slope] * [area] ^ 2.</html>", "Expression:", "", false, false)
+			sd.addDialogFile("Output mean file", "Output Mean File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output standard error file", "Output Standard Error File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Number of Monte Carlo samples (optional)", "Number of Monte Carlo Samples (optional):", "", true, true)
+			sd.addDialogFile("Output Monte Carlo standard error file (optional)", "Output Monte Carlo Standard Error File (optional):", "save", "Raster Files (*.dep), DEP", true, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			int progress, oldProgress = -1
+
+			if (args.length < 5) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String[] inputFiles = args[0].split(";").findAll { String s -> !s.trim().isEmpty() } as String[]
+			String[] stdErrorStrings = args[1].split(";").findAll { String s -> !s.trim().isEmpty() } as String[]
+			String expression = args[2]
+			String outputMeanFile = args[3]
+			String outputStdErrorFile = args[4]
+			int numSamples = 0
+			if (args.length > 5 && !args[5].trim().isEmpty() && !args[5].toLowerCase().contains("not specified")) {
+				numSamples = Integer.parseInt(args[5].trim())
+			}
+			String outputMonteCarloFile = null
+			if (args.length > 6 && !args[6].trim().isEmpty() && !args[6].toLowerCase().contains("not specified")) {
+				outputMonteCarloFile = args[6]
+			}
+			int n = inputFiles.length
+			if (n == 0) {
+				pluginHost.showFeedback("At least one input raster must be specified.")
+				return
+			}
+			if (stdErrorStrings.length != n && stdErrorStrings.length != 1) {
+				pluginHost.showFeedback("A standard error must be given for each input raster, or a single value for all of them.")
+				return
+			}
+
+			// read the inputs and their standard errors, which are either
+			// rasters or constant values
+			WhiteboxRaster[] inputs = new WhiteboxRaster[n]
+			WhiteboxRaster[] stdErrorRasters = new WhiteboxRaster[n]
+			double[] stdErrorConstants = new double[n]
+			for (int i = 0; i < n; i++) {
+				inputs[i] = new WhiteboxRaster(inputFiles[i].trim(), "r")
+				if (i > 0 && (inputs[i].getNumberRows() != inputs[0].getNumberRows() ||
+				  inputs[i].getNumberColumns() != inputs[0].getNumberColumns())) {
+					pluginHost.showFeedback("Each input raster must have the same dimensions.")
+					return
+				}
+				String stdError = stdErrorStrings[stdErrorStrings.length == 1 ? 0 : i].trim()
+				if (new File(stdError).exists()) {
+					stdErrorRasters[i] = new WhiteboxRaster(stdError, "r")
+					if (stdErrorRasters[i].getNumberRows() != inputs[0].getNumberRows() ||
+					  stdErrorRasters[i].getNumberColumns() != inputs[0].getNumberColumns()) {
+						pluginHost.showFeedback("Each standard error raster must have the same dimensions as the inputs.")
+						return
+					}
+				} else {
+					try {
+						stdErrorConstants[i] = Double.parseDouble(stdError)
+					} catch (NumberFormatException e) {
+						pluginHost.showFeedback("The standard error '" + stdError + "' is neither a raster file nor a number.")
+						return
+					}
+				}
+			}
+			int rows = inputs[0].getNumberRows()
+			int cols = inputs[0].getNumberColumns()
+			double nodata = inputs[0].getNoDataValue()
+
+			ToDoubleFunction<double[]> function = compileExpression(expression, inputFiles)
+			if (function == null) {
+				return
+			}
+
+			WhiteboxRaster outputMean = new WhiteboxRaster(outputMeanFile, "rw",
+  		  	     inputFiles[0].trim(), DataType.FLOAT, nodata)
+  		  	outputMean.setPreferredPalette(inputs[0].getPreferredPalette())
+			WhiteboxRaster outputStdError = new WhiteboxRaster(outputStdErrorFile, "rw",
+  		  	     inputFiles[0].trim(), DataType.FLOAT, nodata)
+  		  	outputStdError.setPreferredPalette("spectrum.pal")
+			WhiteboxRaster outputMonteCarlo = null
+			if (numSamples > 1 && outputMonteCarloFile != null) {
+				outputMonteCarlo = new WhiteboxRaster(outputMonteCarloFile, "rw",
+	  		  	     inputFiles[0].trim(), DataType.FLOAT, nodata)
+	  		  	outputMonteCarlo.setPreferredPalette("spectrum.pal")
+			}
+
+			// a fixed seed makes the Monte Carlo estimates repeatable
+			Random random = new Random(1L)
+			double[][] data = new double[n][]
+			double[][] stdErrorData = new double[n][]
+			double[] x = new double[n]
+			double[] sigma = new double[n]
+			double[] sample = new double[n]
+			double[] meanData = new double[cols]
+			double[] stdErrorOut = new double[cols]
+			double[] monteCarloOut = new double[cols]
+			long numCompared = 0
+			double sumFirstOrder = 0, sumMonteCarlo = 0, maxDifference = 0
+			for (int row = 0; row < rows; row++) {
+				for (int i = 0; i < n; i++) {
+					data[i] = inputs[i].getRowValues(row)
+					if (stdErrorRasters[i] != null) {
+						stdErrorData[i] = stdErrorRasters[i].getRowValues(row)
+					}
+				}
+				for (int col = 0; col < cols; col++) {
+					meanData[col] = nodata
+					stdErrorOut[col] = nodata
+					monteCarloOut[col] = nodata
+					boolean valid = true
+					for (int i = 0; i < n && valid; i++) {
+						x[i] = data[i][col]
+						if (x[i] == inputs[i].getNoDataValue()) {
+							valid = false
+						} else if (stdErrorRasters[i] != null) {
+							sigma[i] = stdErrorData[i][col]
+							valid = (sigma[i] != stdErrorRasters[i].getNoDataValue())
+						} else {
+							sigma[i] = stdErrorConstants[i]
+						}
+					}
+					if (!valid) {
+						continue
+					}
+					double z = evaluate(function, x)
+					if (Double.isNaN(z) || Double.isInfinite(z)) {
+						continue
+					}
+					meanData[col] = z
+
+					// the first-order variance is the sum of the squared partial
+					// derivatives, each estimated by a central difference, times
+					// the variances of the inputs
+					double variance = 0
+					for (int i = 0; i < n && valid; i++) {
+						if (sigma[i] == 0) {
+							continue
+						}
+						double h = 1e-4 * Math.max(Math.abs(x[i]), Math.abs(sigma[i]))
+						x[i] += h
+						double zUp = evaluate(function, x)
+						x[i] -= 2 * h
+						double zDown = evaluate(function, x)
+						x[i] += h
+						double derivative
+						boolean upValid = !Double.isNaN(zUp) && !Double.isInfinite(zUp)
+						boolean downValid = !Double.isNaN(zDown) && !Double.isInfinite(zDown)
+						if (upValid && downValid) {
+							derivative = (zUp - zDown) / (2 * h)
+						} else if (upValid) {
+							derivative = (zUp - z) / h
+						} else if (downValid) {
+							derivative = (z - zDown) / h
+						} else {
+							valid = false
+							continue
+						}
+						variance += derivative * derivative * sigma[i] * sigma[i]
+					}
+					if (!valid) {
+						continue
+					}
+					stdErrorOut[col] = Math.sqrt(variance)
+
+					// Monte Carlo simulation with normally distributed, independent
+					// input errors; samples for which the expression is undefined
+					// are ignored
+					if (numSamples > 1) {
+						int count = 0
+						double mean = 0, m2 = 0
+						for (int s = 0; s < numSamples; s++) {
+							for (int i = 0; i < n; i++) {
+								sample[i] = x[i] + sigma[i] * random.nextGaussian()
+							}
+							double zSample = evaluate(function, sample)
+							if (Double.isNaN(zSample) || Double.isInfinite(zSample)) {
+								continue
+							}
+							count++
+							double delta = zSample - mean
+							mean += delta / count
+							m2 += delta * (zSample - mean)
+						}
+						if (count > 1) {
+							monteCarloOut[col] = Math.sqrt(m2 / (count - 1))
+							numCompared++
+							sumFirstOrder += stdErrorOut[col]
+							sumMonteCarlo += monteCarloOut[col]
+							maxDifference = Math.max(maxDifference, Math.abs(monteCarloOut[col] - stdErrorOut[col]))
+						}
+					}
+				}
+				outputMean.setRowValues(row, meanData)
+				outputStdError.setRowValues(row, stdErrorOut)
+				if (outputMonteCarlo != null) {
+					outputMonteCarlo.setRowValues(row, monteCarloOut)
+				}
+  				progress = (int)(100f * row / rows)
+				if (progress != oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			for (int i = 0; i < n; i++) {
+				inputs[i].close()
+				if (stdErrorRasters[i] != null) {
+					stdErrorRasters[i].close()
+				}
+			}
+
+			for (WhiteboxRaster output : [outputMean, outputStdError, outputMonteCarlo]) {
+				if (output == null) {
+					continue
+				}
+				output.addMetadataEntry("Created by the "
+	                    + descriptiveName + " tool.")
+	        	output.addMetadataEntry("Created on " + new Date())
+	        	output.addMetadataEntry("Expression: " + expression)
+				output.close()
+			}
+
+			if (numSamples > 1) {
+				StringBuilder sb = new StringBuilder()
+				sb.append("Error Propagation: First-order and Monte Carlo Standard Errors\n\n")
+				sb.append("Number of Monte Carlo samples: " + numSamples + "\n")
+				sb.append("Number of cells compared: " + numCompared + "\n")
+				if (numCompared > 0) {
+					sb.append("Mean first-order standard error: " + String.format("%.6g", sumFirstOrder / numCompared) + "\n")
+					sb.append("Mean Monte Carlo standard error: " + String.format("%.6g", sumMonteCarlo / numCompared) + "\n")
+					sb.append("Maximum absolute difference: " + String.format("%.6g", maxDifference) + "\n")
+				}
+				pluginHost.returnData(sb.toString())
+			}
+
+			// display the output images
+			pluginHost.returnData(outputStdErrorFile)
+			pluginHost.returnData(outputMeanFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	// Compiles the expression into a new class that is a function of the
+	// input values, given as an array. Input names in square brackets and the
+	// Raster Calculator's operators are translated into Groovy.
+	private ToDoubleFunction<double[]> compileExpression(String expression, String[] inputFiles) {
+		String expr = expression.replace("^", "**").replace("×", "*")
+			.replace("÷", "/").replace("−", "-").replace("π", "PI")
+		StringBuilder variables = new StringBuilder()
+		for (int i = 0; i < inputFiles.length; i++) {
+			File file = new File(inputFiles[i].trim())
+			String shortName = file.getName().replace(".dep", "")
+			expr = expr.replace("[" + inputFiles[i].trim() + "]", "x" + (i + 1))
+			expr = expr.replace("[" + file.getName() + "]", "x" + (i + 1))
+			expr = expr.replace("[" + shortName + "]", "x" + (i + 1))
+			variables.append("double x" + (i + 1) + " = x[" + i + "]\n")
+		}
+		if (expr.contains("[")) {
+			pluginHost.showFeedback("The expression refers to a raster that is not one of the inputs.")
+			return null
+		}
+		String source = """
+			@groovy.transform.CompileStatic
+			class PropagatedExpression implements java.util.function.ToDoubleFunction<double[]> {
+				static final double PI = Math.PI
+				static final double E = Math.E
+				static double sin(double v) { Math.sin(v) }
+				static double cos(double v) { Math.cos(v) }
+				static double tan(double v) { Math.tan(v) }
+				static double arcsin(double v) { Math.asin(v) }
+				static double arccos(double v) { Math.acos(v) }
+				static double arctan(double v) { Math.atan(v) }
+				static double sinh(double v) { Math.sinh(v) }
+				static double cosh(double v) { Math.cosh(v) }
+				static double tanh(double v) { Math.tanh(v) }
+				static double log(double v) { Math.log10(v) }
+				static double ln(double v) { Math.log(v) }
+				static double log2(double v) { Math.log(v) / Math.log(2) }
+				static double exp(double v) { Math.exp(v) }
+				static double abs(double v) { Math.abs(v) }
+				static double sqr(double v) { v * v }
+				static double sqrt(double v) { Math.sqrt(v) }
+				static double min(double a, double b) { Math.min(a, b) }
+				static double max(double a, double b) { Math.max(a, b) }
+				static double pow(double a, double b) { Math.pow(a, b) }
+
+				double applyAsDouble(double[] x) {
+					$variables
+					return (($expr) as Number).doubleValue()
+				}
+			}
+			"""
+		try {
+			Class c = new GroovyClassLoader(getClass().getClassLoader()).parseClass(source)
+			return (ToDoubleFunction<double[]>)c.newInstance()
+		} catch (Exception e) {
+			pluginHost.showFeedback("The expression could not be compiled: " + e.getMessage())
+			return null
+		}
+	}
+
+	// returns NaN where the expression cannot be evaluated
+	@CompileStatic
+	private static double evaluate(ToDoubleFunction<double[]> function, double[] x) {
+		try {
+			return function.applyAsDouble(x)
+		} catch (Exception e) {
+			return Double.NaN
+		}
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new ErrorPropagation(pluginHost, args, name, descriptiveName)
+}
@@ -34,8 +34,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -93,7 +91,7 @@ public class FD8FlowAccum2 implements ActionListener {
 	private void execute(String[] args) {
 		try {
 
-			Date start = new Date()
+			long start = System.nanoTime()
 			
 			int progress, oldProgress, i, row, col, rN, cN, dir, n
 	  		int[] dX = [ 1, 1, 1, 0, -1, -1, -1, 0 ]
@@ -260,8 +258,7 @@ public class FD8FlowAccum2 implements ActionListener {
             
             output.flush();
 
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus( stop, start )
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			
 			output.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
@@ -43,8 +43,6 @@ import whitebox.structures.BooleanBitArray2D
 import whitebox.structures.NibbleArray2D
 import whitebox.structures.BoundingBox;
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -97,7 +95,7 @@ public class FillBurn implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-	  		Date start = new Date()
+	  		long start = System.nanoTime()
 			
 			int progress, oldProgress, col, row, colN, rowN, numPits, r, c
 	  		int numSolvedCells = 0;
@@ -515,8 +513,7 @@ public class FillBurn implements ActionListener {
 			outputRasterFD.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        outputRasterFD.addMetadataEntry("Created on " + new Date())
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			outputRasterFD.addMetadataEntry("Elapsed time: $td")
 			outputRasterFD.close()
 
@@ -583,8 +580,7 @@ public class FillBurn implements ActionListener {
 			outputFA.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        outputFA.addMetadataEntry("Created on " + new Date())
-			stop = new Date()
-			td = TimeCategory.minus(stop, start)
+			td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			outputFA.addMetadataEntry("Elapsed time: $td")
 			outputFA.close()
 
@@ -596,8 +592,7 @@ public class FillBurn implements ActionListener {
 			outputRaster.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        outputRaster.addMetadataEntry("Created on " + new Date())
-			stop = new Date()
-			td = TimeCategory.minus(stop, start)
+			td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			outputRaster.addMetadataEntry("Elapsed time: $td")
 			outputRaster.close();
 
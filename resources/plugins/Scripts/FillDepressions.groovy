@@ -28,7 +28,6 @@ import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.*
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -90,7 +89,7 @@ public class FillDepressions implements ActionListener {
 			return
 		}
 		
-		def timeStart = new Date()
+		long timeStart = System.nanoTime()
 		
 		int progress
 		int oldProgress = -1
@@ -142,7 +141,7 @@ public class FillDepressions implements ActionListener {
         }
         image.close();
 
-        //TimeDuration duration1 = TimeCategory.minus(new Date(), timeStart)
+        //String duration1 = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
 		
         
         // initialize and fill the priority queue.
@@ -183,7 +182,7 @@ public class FillDepressions implements ActionListener {
 			}
         }
 		
-		//TimeDuration duration2 = TimeCategory.minus(new Date(), timeStart)
+		//String duration2 = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
 		
         // now fill!
         pluginHost.updateProgress("Loop 2: ", 0);
@@ -218,7 +217,7 @@ public class FillDepressions implements ActionListener {
 			}
         }
 
-        //TimeDuration duration3 = TimeCategory.minus(new Date(), timeStart)
+        //String duration3 = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
 		
 
         pluginHost.updateProgress("Saving Data: ", 0);
@@ -241,7 +240,7 @@ public class FillDepressions implements ActionListener {
 			}
         }
 
-        //TimeDuration duration4 = TimeCategory.minus(new Date(), timeStart)
+        //String duration4 = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
 		
             
 		outputFile.addMetadataEntry("Created by the "
@@ -249,11 +248,11 @@ public class FillDepressions implements ActionListener {
         outputFile.addMetadataEntry("Created on " + new Date())
 		outputFile.close()
 
-		//TimeDuration duration5 = TimeCategory.minus(new Date(), timeStart)
+		//String duration5 = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
 		//pluginHost.showFeedback("${duration1} ${duration2} ${duration3} ${duration4}")
 
-		TimeDuration duration = TimeCategory.minus(new Date(), timeStart)
-		pluginHost.showFeedback("Operation completed in ${duration} seconds.")
+		String duration = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
+		pluginHost.showFeedback("Operation completed in ${duration}.")
 	
 
 		// display the output image
@@ -0,0 +1,408 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import java.util.Random
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "GenerateSyntheticTerrain"
+def descriptiveName = "Generate Synthetic Terrain"
+def description = "Creates a fractal DEM using the diamond-square or Perlin noise method."
+def toolboxes = ["RasterCreation"]
+
+public class GenerateSyntheticTerrain implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public GenerateSyntheticTerrain(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Output DEM file", "Output DEM File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Number of rows in the output DEM", "Number Of Rows:", "500", true, false)
+			sd.addDialogDataInput("Number of columns in the output DEM", "Number Of Columns:", "500", true, false)
+			sd.addDialogDataInput("Grid resolution (cell size) of the output DEM", "Cell Size:", "10.0", true, false)
+			sd.addDialogComboBox("Method used to generate the fractal surface", "Method:", ["diamond-square", "perlin noise"], 0)
+			sd.addDialogDataInput("Roughness, as the Hurst exponent (0-1); smaller values produce rougher terrain", "Roughness (Hurst Exponent, 0-1):", "0.8", true, false)
+			sd.addDialogDataInput("Seed of the random number generator; the same seed reproduces the same DEM", "Random Seed (optional):", "", true, true)
+			sd.addDialogDataInput("Minimum elevation of the output DEM", "Minimum Elevation:", "0.0", true, false)
+			sd.addDialogDataInput("Maximum elevation of the output DEM", "Maximum Elevation:", "1000.0", true, false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 5) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String outputFile = args[0]
+			int rows = Integer.parseInt(args[1].trim())
+			int cols = Integer.parseInt(args[2].trim())
+			double cellSize = Double.parseDouble(args[3].trim())
+			if (rows < 2 || cols < 2) {
+				pluginHost.showFeedback("The output DEM must have at least two rows and columns.")
+				return
+			}
+			if (cellSize <= 0) {
+				pluginHost.showFeedback("The cell size must be greater than zero.")
+				return
+			}
+			String method = args[4].toLowerCase()
+			boolean diamondSquare
+			if (method.contains("diamond")) {
+				diamondSquare = true
+			} else if (method.contains("perlin")) {
+				diamondSquare = false
+			} else {
+				pluginHost.showFeedback("Unrecognized method '${args[4]}'. The method must be either diamond-square or perlin noise.")
+				return
+			}
+			double hurst = 0.8
+			if (args.length > 5 && isSpecified(args[5])) {
+				hurst = Double.parseDouble(args[5].trim())
+			}
+			if (hurst <= 0 || hurst > 1) {
+				pluginHost.showFeedback("The roughness (Hurst exponent) must be greater than 0 and no greater than 1.")
+				return
+			}
+			long seed
+			if (args.length > 6 && isSpecified(args[6])) {
+				seed = Long.parseLong(args[6].trim())
+			} else {
+				// record a seed so that the output can be reproduced
+				seed = new Random().nextInt()
+			}
+			double minElev = 0
+			if (args.length > 7 && isSpecified(args[7])) {
+				minElev = Double.parseDouble(args[7].trim())
+			}
+			double maxElev = 1000
+			if (args.length > 8 && isSpecified(args[8])) {
+				maxElev = Double.parseDouble(args[8].trim())
+			}
+			if (maxElev <= minElev) {
+				pluginHost.showFeedback("The maximum elevation must be greater than the minimum elevation.")
+				return
+			}
+
+			Random generator = new Random(seed)
+			double[][] z = diamondSquare ? diamondSquareSurface(rows, cols, hurst, generator) :
+			  perlinSurface(rows, cols, hurst, generator)
+			if (z == null) {
+				// the operation was cancelled
+				return
+			}
+
+			// rescale the surface to the elevation range
+			double zMin = Double.POSITIVE_INFINITY
+			double zMax = Double.NEGATIVE_INFINITY
+			for (int row = 0; row < rows; row++) {
+				for (int col = 0; col < cols; col++) {
+					if (z[row][col] < zMin) { zMin = z[row][col] }
+					if (z[row][col] > zMax) { zMax = z[row][col] }
+				}
+			}
+			double scale = zMax > zMin ? (maxElev - minElev) / (zMax - zMin) : 0d
+
+			double nodata = -32768d
+			if (minElev <= nodata) {
+				nodata = minElev - 1
+			}
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, rows * cellSize, 0d,
+			  cols * cellSize, 0d, rows, cols, WhiteboxRasterBase.DataScale.CONTINUOUS,
+			  WhiteboxRasterBase.DataType.FLOAT, nodata, nodata)
+			output.setPreferredPalette("high_relief.pal")
+
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				for (int col = 0; col < cols; col++) {
+					output.setValue(row, col, minElev + (z[row][col] - zMin) * scale)
+				}
+				int progress = (int)(50f + 50f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Saving DEM:", progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Method: " + (diamondSquare ? "diamond-square" : "perlin noise"))
+			output.addMetadataEntry("Hurst exponent: " + hurst)
+			output.addMetadataEntry("Random seed: " + seed)
+			output.close()
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	/* Generates a surface using the diamond-square algorithm on the
+	 * smallest (2^n + 1) x (2^n + 1) grid that covers the DEM. The
+	 * standard deviation of the random displacements is reduced by a
+	 * factor of 2^-H at each level of subdivision.
+	 */
+	@CompileStatic
+	private double[][] diamondSquareSurface(int rows, int cols, double hurst, Random generator) {
+		int size = 2
+		while (size + 1 < Math.max(rows, cols)) {
+			size *= 2
+		}
+		int n = size + 1
+		double[][] grid = new double[n][n]
+		double sd = 1.0
+		grid[0][0] = generator.nextGaussian() * sd
+		grid[0][size] = generator.nextGaussian() * sd
+		grid[size][0] = generator.nextGaussian() * sd
+		grid[size][size] = generator.nextGaussian() * sd
+
+		double reduction = Math.pow(2d, -hurst)
+		int numLevels = Integer.numberOfTrailingZeros(size)
+		int level = 0
+		int oldProgress = -1
+		for (int step = size; step > 1; step >>= 1) {
+			int half = step >> 1
+			sd *= reduction
+			// diamond step: the centre of each square
+			for (int row = half; row < n; row += step) {
+				for (int col = half; col < n; col += step) {
+					double avg = (grid[row - half][col - half] + grid[row - half][col + half] +
+					  grid[row + half][col - half] + grid[row + half][col + half]) / 4d
+					grid[row][col] = avg + generator.nextGaussian() * sd
+				}
+			}
+			// square step: the midpoint of each edge, averaging the
+			// available neighbours along the grid edges
+			for (int row = 0; row < n; row += half) {
+				int startCol = ((int)(row / half)) % 2 == 0 ? half : 0
+				for (int col = startCol; col < n; col += step) {
+					double sum = 0
+					int count = 0
+					if (row - half >= 0) { sum += grid[row - half][col]; count++ }
+					if (row + half < n) { sum += grid[row + half][col]; count++ }
+					if (col - half >= 0) { sum += grid[row][col - half]; count++ }
+					if (col + half < n) { sum += grid[row][col + half]; count++ }
+					grid[row][col] = sum / count + generator.nextGaussian() * sd
+				}
+			}
+			level++
+			int progress = (int)(50f * level / numLevels)
+			if (progress != oldProgress) {
+				pluginHost.updateProgress("Generating surface:", progress)
+				oldProgress = progress
+				if (pluginHost.isRequestForOperationCancelSet()) {
+					pluginHost.showFeedback("Operation cancelled")
+					return null
+				}
+			}
+		}
+
+		double[][] z = new double[rows][cols]
+		for (int row = 0; row < rows; row++) {
+			System.arraycopy(grid[row], 0, z[row], 0, cols)
+		}
+		return z
+	}
+
+	/* Generates a surface by summing octaves of Perlin gradient noise.
+	 * The lowest octave has a wavelength of half of the larger grid
+	 * dimension and each subsequent octave halves the wavelength, down
+	 * to two grid cells, with amplitudes reduced by a factor of 2^-H.
+	 */
+	@CompileStatic
+	private double[][] perlinSurface(int rows, int cols, double hurst, Random generator) {
+		// the permutation table, shuffled using the seeded generator
+		int[] perm = new int[512]
+		for (int i = 0; i < 256; i++) {
+			perm[i] = i
+		}
+		for (int i = 255; i > 0; i--) {
+			int j = generator.nextInt(i + 1)
+			int tmp = perm[i]
+			perm[i] = perm[j]
+			perm[j] = tmp
+		}
+		for (int i = 0; i < 256; i++) {
+			perm[256 + i] = perm[i]
+		}
+
+		double wavelength = Math.max(rows, cols) / 2d
+		int numOctaves = 0
+		for (double w = wavelength; w >= 2; w /= 2) {
+			numOctaves++
+		}
+		double[] frequency = new double[numOctaves]
+		double[] amplitude = new double[numOctaves]
+		double[] offsetX = new double[numOctaves]
+		double[] offsetY = new double[numOctaves]
+		double reduction = Math.pow(2d, -hurst)
+		for (int k = 0; k < numOctaves; k++) {
+			frequency[k] = Math.pow(2d, k) / wavelength
+			amplitude[k] = Math.pow(reduction, k)
+			// offsets keep the lattice points of the octaves from coinciding
+			offsetX[k] = generator.nextDouble() * 256
+			offsetY[k] = generator.nextDouble() * 256
+		}
+
+		double[][] z = new double[rows][cols]
+		int oldProgress = -1
+		for (int row = 0; row < rows; row++) {
+			for (int col = 0; col < cols; col++) {
+				double sum = 0
+				for (int k = 0; k < numOctaves; k++) {
+					sum += amplitude[k] * noise(perm, (col + 0.5) * frequency[k] + offsetX[k],
+					  (row + 0.5) * frequency[k] + offsetY[k])
+				}
+				z[row][col] = sum
+			}
+			int progress = (int)(50f * row / (rows - 1))
+			if (progress != oldProgress) {
+				pluginHost.updateProgress("Generating surface:", progress)
+				oldProgress = progress
+				if (pluginHost.isRequestForOperationCancelSet()) {
+					pluginHost.showFeedback("Operation cancelled")
+					return null
+				}
+			}
+		}
+		return z
+	}
+
+	// Two-dimensional Perlin gradient noise (Perlin, 2002).
+	@CompileStatic
+	private static double noise(int[] perm, double x, double y) {
+		int xi = ((int)Math.floor(x)) & 255
+		int yi = ((int)Math.floor(y)) & 255
+		double xf = x - Math.floor(x)
+		double yf = y - Math.floor(y)
+		double u = fade(xf)
+		double v = fade(yf)
+		int aa = perm[perm[xi] + yi]
+		int ab = perm[perm[xi] + yi + 1]
+		int ba = perm[perm[xi + 1] + yi]
+		int bb = perm[perm[xi + 1] + yi + 1]
+		double x1 = lerp(grad(aa, xf, yf), grad(ba, xf - 1, yf), u)
+		double x2 = lerp(grad(ab, xf, yf - 1), grad(bb, xf - 1, yf - 1), u)
+		return lerp(x1, x2, v)
+	}
+
+	@CompileStatic
+	private static double fade(double t) {
+		return t * t * t * (t * (t * 6 - 15) + 10)
+	}
+
+	@CompileStatic
+	private static double lerp(double a, double b, double t) {
+		return a + t * (b - a)
+	}
+
+	@CompileStatic
+	private static double grad(int hash, double x, double y) {
+		switch (hash & 7) {
+			case 0: return x + y
+			case 1: return -x + y
+			case 2: return x - y
+			case 3: return -x - y
+			case 4: return x
+			case 5: return -x
+			case 6: return y
+			default: return -y
+		}
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new GenerateSyntheticTerrain(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,439 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "GramSchmidtPanSharpen"
+def descriptiveName = "Gram-Schmidt Pan-Sharpening"
+def description = "Sharpens multispectral image bands using a panchromatic image and the Gram-Schmidt transform."
+def toolboxes = ["ImageEnhancement"]
+
+public class GramSchmidtPanSharpen implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public GramSchmidtPanSharpen(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input panchromatic image", "Input Panchromatic Image:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogMultiFile("Select the input multispectral image bands", "Input Multispectral Bands:", "Raster Files (*.dep), DEP")
+			sd.addDialogDataInput("Output file prefix; output files are named after the input bands with this prefix", "Output File Prefix:", "gs_", false, false)
+			sd.addDialogDataInput("Weights used to simulate the panchromatic image; a comma-separated list with one value per band. If unspecified, the weights are estimated by regression", "Band Weights (optional):", "", false, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 3) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String panFile = args[0]
+			List<String> inputFiles = new ArrayList<>()
+			for (String s : args[1].split("[;,]")) {
+				if (!s.trim().isEmpty()) {
+					inputFiles.add(s.trim())
+				}
+			}
+			int numBands = inputFiles.size()
+			if (numBands < 2) {
+				pluginHost.showFeedback("At least two multispectral bands must be specified.")
+				return
+			}
+			String prefix = args[2].trim()
+			if (prefix.isEmpty()) {
+				prefix = "gs_"
+			}
+			double[] weights = null
+			if (args.length > 3 && isSpecified(args[3])) {
+				String[] str = args[3].split(",")
+				if (str.length != numBands) {
+					pluginHost.showFeedback("The number of band weights (${str.length}) does not match the number of multispectral bands (${numBands}).")
+					return
+				}
+				weights = new double[numBands]
+				for (int b = 0; b < numBands; b++) {
+					weights[b] = Double.parseDouble(str[b].trim())
+				}
+			}
+
+			WhiteboxRaster pan = new WhiteboxRaster(panFile, "r")
+			int rows = pan.getNumberRows()
+			int cols = pan.getNumberColumns()
+			double panNodata = pan.getNoDataValue()
+			double panNorth = pan.getNorth()
+			double panWest = pan.getWest()
+			double panCellX = pan.getCellSizeX()
+			double panCellY = pan.getCellSizeY()
+
+			WhiteboxRaster[] bands = new WhiteboxRaster[numBands]
+			for (int b = 0; b < numBands; b++) {
+				bands[b] = new WhiteboxRaster(inputFiles.get(b), "r")
+			}
+
+			/* The multispectral bands are resampled to the pan grid by
+			 * bilinear interpolation at the centre of each pan cell. The
+			 * first pass accumulates the means and covariances of the
+			 * resampled bands and the pan image over the cells that are
+			 * valid in every image.
+			 */
+			double[] z = new double[numBands]
+			double[] meanB = new double[numBands]
+			double[][] covBB = new double[numBands][numBands]
+			double[] covBP = new double[numBands]
+			double meanP = 0
+			double varP = 0
+			long n = 0
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				double y = panNorth - (row + 0.5) * panCellY
+				for (int col = 0; col < cols; col++) {
+					double p = pan.getValue(row, col)
+					if (p == panNodata) {
+						continue
+					}
+					double x = panWest + (col + 0.5) * panCellX
+					if (!resample(bands, x, y, z)) {
+						continue
+					}
+					// Welford-style updating of the means and co-moments
+					n++
+					double dp = p - meanP
+					for (int i = 0; i < numBands; i++) {
+						double di = (z[i] - meanB[i]) * (n - 1) / n
+						for (int j = 0; j <= i; j++) {
+							covBB[i][j] += di * (z[j] - meanB[j])
+						}
+						covBP[i] += di * dp
+					}
+					varP += dp * dp * (n - 1) / n
+					for (int i = 0; i < numBands; i++) {
+						meanB[i] += (z[i] - meanB[i]) / n
+					}
+					meanP += dp / n
+				}
+				int progress = (int)(50f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Calculating statistics:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+			if (n < numBands + 2) {
+				pluginHost.showFeedback("The panchromatic image and the multispectral bands do not sufficiently overlap.")
+				return
+			}
+			double df = n - 1
+			for (int i = 0; i < numBands; i++) {
+				for (int j = 0; j <= i; j++) {
+					covBB[i][j] = covBB[i][j] / df
+					covBB[j][i] = covBB[i][j]
+				}
+				covBP[i] = covBP[i] / df
+			}
+			varP /= df
+			if (varP <= 0) {
+				pluginHost.showFeedback("The panchromatic image has no variation.")
+				return
+			}
+
+			/* The simulated pan is a weighted sum of the multispectral
+			 * bands. Unless the weights are specified, they are the
+			 * least-squares regression coefficients of the pan on the
+			 * resampled bands, such that the simulated pan best matches
+			 * the spectral response of the pan sensor.
+			 */
+			if (weights == null) {
+				weights = solve(covBB, covBP)
+				if (weights == null) {
+					pluginHost.showFeedback("The band weights could not be estimated because two or more bands are perfectly correlated. Please specify the weights.")
+					return
+				}
+			}
+			double[] covBS = new double[numBands]
+			double varS = 0
+			for (int i = 0; i < numBands; i++) {
+				for (int j = 0; j < numBands; j++) {
+					covBS[i] += covBB[i][j] * weights[j]
+				}
+				varS += weights[i] * covBS[i]
+			}
+			if (varS <= 0) {
+				pluginHost.showFeedback("The simulated panchromatic image has no variation.")
+				return
+			}
+			double meanS = 0
+			for (int i = 0; i < numBands; i++) {
+				meanS += weights[i] * meanB[i]
+			}
+
+			/* The first Gram-Schmidt component, GS1, is the mean-centred
+			 * simulated pan. Each band is then orthogonalized against the
+			 * preceding components, GS(k+1) = B(k) - mean(k) - sum of
+			 * phi(k, j) * GS(j), where phi(k, j) = cov(B(k), GS(j)) / var(GS(j)).
+			 * The real pan, matched to the mean and standard deviation of GS1,
+			 * replaces GS1 and the transform is inverted. Since only GS1 is
+			 * replaced, the inverse transform reduces to
+			 * B'(k) = B(k) + phi(k, 1) * (GS1' - GS1).
+			 */
+			double[] phi = new double[numBands]
+			for (int i = 0; i < numBands; i++) {
+				phi[i] = covBS[i] / varS
+			}
+			double panGain = Math.sqrt(varS / varP)
+
+			WhiteboxRaster[] outputs = new WhiteboxRaster[numBands]
+			String[] outputFiles = new String[numBands]
+			for (int b = 0; b < numBands; b++) {
+				File inFile = new File(inputFiles.get(b))
+				String dir = inFile.getParent() != null ? inFile.getParent() + File.separator : ""
+				outputFiles[b] = dir + prefix + inFile.getName()
+				outputs[b] = new WhiteboxRaster(outputFiles[b], "rw",
+				  panFile, DataType.FLOAT, panNodata)
+				outputs[b].setPreferredPalette(bands[b].getPreferredPalette())
+				outputs[b].setDataScale(DataScale.CONTINUOUS)
+			}
+
+			oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				double y = panNorth - (row + 0.5) * panCellY
+				for (int col = 0; col < cols; col++) {
+					double p = pan.getValue(row, col)
+					double x = panWest + (col + 0.5) * panCellX
+					if (p != panNodata && resample(bands, x, y, z)) {
+						double gs1 = -meanS
+						for (int i = 0; i < numBands; i++) {
+							gs1 += weights[i] * z[i]
+						}
+						double delta = (p - meanP) * panGain - gs1
+						for (int i = 0; i < numBands; i++) {
+							outputs[i].setValue(row, col, z[i] + phi[i] * delta)
+						}
+					} else {
+						for (int i = 0; i < numBands; i++) {
+							outputs[i].setValue(row, col, panNodata)
+						}
+					}
+				}
+				int progress = (int)(50f + 50f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Sharpening bands:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			pan.close()
+			StringBuilder weightStr = new StringBuilder()
+			for (int b = 0; b < numBands; b++) {
+				bands[b].close()
+				if (b > 0) {
+					weightStr.append(", ")
+				}
+				weightStr.append(weights[b])
+			}
+
+			for (int b = 0; b < numBands; b++) {
+				outputs[b].addMetadataEntry("Created by the " + descriptiveName + " tool.")
+				outputs[b].addMetadataEntry("Created on " + new Date())
+				outputs[b].addMetadataEntry("Panchromatic image: " + new File(panFile).getName())
+				outputs[b].addMetadataEntry("Simulated pan band weights: " + weightStr.toString())
+				outputs[b].addMetadataEntry("Gram-Schmidt coefficient: " + phi[b])
+				outputs[b].close()
+			}
+
+			// display the output images
+			for (int b = 0; b < numBands; b++) {
+				pluginHost.returnData(outputFiles[b])
+			}
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	/* Bilinearly interpolates each band at the point (x, y), storing the
+	 * values in z. Where the four neighbouring cells are not all valid,
+	 * the value of the nearest cell is used. Returns false if any band
+	 * is NoData at the point.
+	 */
+	@CompileStatic
+	private static boolean resample(WhiteboxRaster[] bands, double x, double y, double[] z) {
+		for (int b = 0; b < bands.length; b++) {
+			WhiteboxRaster band = bands[b]
+			double nodata = band.getNoDataValue()
+			int rows = band.getNumberRows()
+			int cols = band.getNumberColumns()
+			double c = (x - band.getWest()) / band.getCellSizeX() - 0.5
+			double r = (band.getNorth() - y) / band.getCellSizeY() - 0.5
+			int nearRow = (int)Math.round(r)
+			int nearCol = (int)Math.round(c)
+			if (nearRow < 0 || nearRow >= rows || nearCol < 0 || nearCol >= cols) {
+				return false
+			}
+			int r0 = (int)Math.floor(r)
+			int c0 = (int)Math.floor(c)
+			// clamp to the edge cells at the margins of the band
+			int r1 = Math.min(r0 + 1, rows - 1)
+			int c1 = Math.min(c0 + 1, cols - 1)
+			r0 = Math.max(r0, 0)
+			c0 = Math.max(c0, 0)
+			double fr = Math.min(Math.max(r - r0, 0d), 1d)
+			double fc = Math.min(Math.max(c - c0, 0d), 1d)
+			double z00 = band.getValue(r0, c0)
+			double z01 = band.getValue(r0, c1)
+			double z10 = band.getValue(r1, c0)
+			double z11 = band.getValue(r1, c1)
+			if (z00 != nodata && z01 != nodata && z10 != nodata && z11 != nodata) {
+				z[b] = (z00 * (1 - fc) + z01 * fc) * (1 - fr) + (z10 * (1 - fc) + z11 * fc) * fr
+			} else {
+				double zn = band.getValue(nearRow, nearCol)
+				if (zn == nodata) {
+					return false
+				}
+				z[b] = zn
+			}
+		}
+		return true
+	}
+
+	/* Solves the linear system a x = b by Gaussian elimination with
+	 * partial pivoting. Returns null if the system is singular.
+	 */
+	@CompileStatic
+	private static double[] solve(double[][] a, double[] b) {
+		int n = b.length
+		double[][] m = new double[n][n + 1]
+		for (int i = 0; i < n; i++) {
+			for (int j = 0; j < n; j++) {
+				m[i][j] = a[i][j]
+			}
+			m[i][n] = b[i]
+		}
+		for (int k = 0; k < n; k++) {
+			int pivot = k
+			for (int i = k + 1; i < n; i++) {
+				if (Math.abs(m[i][k]) > Math.abs(m[pivot][k])) {
+					pivot = i
+				}
+			}
+			if (Math.abs(m[pivot][k]) <= 1e-12 * Math.abs(a[k][k])) {
+				return null
+			}
+			double[] tmp = m[k]
+			m[k] = m[pivot]
+			m[pivot] = tmp
+			for (int i = k + 1; i < n; i++) {
+				double f = m[i][k] / m[k][k]
+				for (int j = k; j <= n; j++) {
+					m[i][j] -= f * m[k][j]
+				}
+			}
+		}
+		double[] x = new double[n]
+		for (int i = n - 1; i >= 0; i--) {
+			double s = m[i][n]
+			for (int j = i + 1; j < n; j++) {
+				s -= m[i][j] * x[j]
+			}
+			x[i] = s / m[i][i]
+		}
+		return x
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new GramSchmidtPanSharpen(pluginHost, args, name, descriptiveName)
+}
@@ -42,8 +42,6 @@ import whitebox.structures.DoubleArray2D
 import whitebox.ui.plugin_dialog.*
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -88,8 +86,8 @@ public class ImpoundmentIndex implements ActionListener {
 			// add some components to the dialog
 			sd.addDialogFile("Input DEM raster", "Input DEM Raster:", "open", "Raster Files (*.dep), DEP", true, false)
             sd.addDialogFile("Output impoundment index raster file", "Output Impoundment Size Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
-			sd.addDialogComboBox("What measure of impoundment size should be output?", "Output impoundment", ["area", "volume"], 0)
-			sd.addDialogDataInput("Max. dam height (in z-units)", "Max. dam height (in z-units)", "", true, false)
+			sd.addDialogComboBox("What measure of impoundment size should be output?", "Output impoundment", ["area", "volume", "volume/area ratio"], 0)
+			sd.addDialogDataInput("Max. dam height (in z-units)", "Max. dam height (in z-units)", "10.0", true, false)
             sd.addDialogDataInput("Max. dam length (in grid cells)", "Max. dam length (in grid cells)", "", true, false)
             
 //			DialogCheckBox outDEM = sd.addDialogCheckBox("Output dammed DEM?", "Output dammed DEM?", false)
@@ -129,7 +127,7 @@ public class ImpoundmentIndex implements ActionListener {
 	private void execute(String[] args) {
 		try {
 
-			//Date start = new Date()
+			//long start = System.nanoTime()
 			
 			int progress, oldProgress, i, row, col, rN, cN, rN2, cN2, dir, perpDir1, perpDir2, n
 
@@ -155,11 +153,16 @@ public class ImpoundmentIndex implements ActionListener {
 			// read the input parameters
 			String inputFile = args[0]
 			String outputFile = args[1]
-			int mode = 1 // 0 = output volume; 1 = output area
-			if (args[2].toLowerCase().contains("vol")) {
+			int mode = 1 // 0 = output volume; 1 = output area; 2 = output volume/area ratio
+			if (args[2].toLowerCase().contains("ratio")) {
+				mode = 2
+			} else if (args[2].toLowerCase().contains("vol")) {
 				mode = 0
 			}
-			double maxDamHeight = Double.parseDouble(args[3])
+			double maxDamHeight = 10.0
+			if (!args[3].trim().isEmpty() && !args[3].toLowerCase().equals("not specified")) {
+				maxDamHeight = Double.parseDouble(args[3])
+			}
 			int damLength = Integer.parseInt(args[4])
 			damLength = (int)(Math.floor(damLength / 2))
 			boolean createDEM = false
@@ -538,6 +541,9 @@ public class ImpoundmentIndex implements ActionListener {
 							vals = upslopeVals[r][c].getValues()
 							if (mode == 0) { // volume
 								outVal = upslopeVals[r][c].volumeBelow(height, cellArea)
+							} else if (mode == 2) { // volume/area ratio, i.e. the mean depth
+								n = upslopeVals[r][c].numLessThan(height)
+								outVal = n > 0 ? upslopeVals[r][c].volumeBelow(height, cellArea) / (n * cellArea) : 0
 							} else { // area
 								outVal = upslopeVals[r][c].numLessThan(height) * cellArea
 							}
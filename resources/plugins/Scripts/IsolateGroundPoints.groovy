@@ -39,8 +39,6 @@ import whitebox.geospatialfiles.shapefile.attributes.DBFField;
 import whitebox.geospatialfiles.shapefile.attributes.*;
 import whitebox.ui.plugin_dialog.*
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -134,7 +132,7 @@ public class IsolateGroundPoints implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 //		long start = System.currentTimeMillis()
-		Date start = new Date();
+		long start = System.nanoTime();
 		int progress = 0
 	    int oldProgress = -1
 	    double x, y, z, zN, dist, slope, maxDist, maxSlope
@@ -687,8 +685,7 @@ public class IsolateGroundPoints implements ActionListener {
 				}
 			}
 
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			pluginHost.showFeedback("Elapsed time: $td")
 
 			if (displayOutput) {
@@ -0,0 +1,301 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "LandSurfaceTemperature"
+def descriptiveName = "Land Surface Temperature"
+def description = "Estimates land surface temperature from a thermal infrared radiance image."
+def toolboxes = ["ImageProc"]
+
+public class LandSurfaceTemperature implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	// Landsat 8 TIRS band 10 thermal conversion constants
+	private static final double DEFAULT_K1 = 774.8853
+	private static final double DEFAULT_K2 = 1321.0789
+	private static final double DEFAULT_EMISSIVITY = 0.95
+
+	// brightness temperatures (K) bounding physically meaningful radiances
+	private static final double MIN_TEMPERATURE = 150.0
+	private static final double MAX_TEMPERATURE = 450.0
+
+	public LandSurfaceTemperature(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input thermal infrared band (spectral radiance, W / (m2 sr um))", "Input Thermal Band (Radiance):", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output land surface temperature image", "Output Temperature File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Surface emissivity (raster or constant value)", "Emissivity (Raster File Or Constant Value):", "open", "Raster Files (*.dep), DEP", true, true)
+			sd.addDialogDataInput("Thermal conversion constant K1 (W / (m2 sr um)); default is Landsat 8 band 10", "K1:", "774.8853", true, false)
+			sd.addDialogDataInput("Thermal conversion constant K2 (K); default is Landsat 8 band 10", "K2:", "1321.0789", true, false)
+			sd.addDialogCheckBox("Output temperature in degrees Celsius (otherwise Kelvin)?", "Output In Celsius", false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 2) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			String emissivityFile = null
+			double emissivityConst = DEFAULT_EMISSIVITY
+			if (args.length > 2 && isSpecified(args[2])) {
+				File file = new File(args[2])
+				if (file.exists()) {
+					emissivityFile = args[2]
+				} else {
+					emissivityConst = Double.parseDouble(file.getName().replace(".dep", ""))
+					if (emissivityConst <= 0 || emissivityConst > 1) {
+						pluginHost.showFeedback("The emissivity must be greater than 0 and no greater than 1.")
+						return
+					}
+				}
+			}
+			double k1 = DEFAULT_K1
+			if (args.length > 3 && isSpecified(args[3])) {
+				k1 = Double.parseDouble(args[3])
+			}
+			double k2 = DEFAULT_K2
+			if (args.length > 4 && isSpecified(args[4])) {
+				k2 = Double.parseDouble(args[4])
+			}
+			if (k1 <= 0 || k2 <= 0) {
+				pluginHost.showFeedback("The thermal constants K1 and K2 must be positive.")
+				return
+			}
+			boolean celsius = args.length > 5 && Boolean.parseBoolean(args[5])
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+
+			WhiteboxRaster emissivityImage = null
+			double emissivityNodata = nodata
+			if (emissivityFile != null) {
+				emissivityImage = new WhiteboxRaster(emissivityFile, "r")
+				if (emissivityImage.getNumberRows() != rows || emissivityImage.getNumberColumns() != cols) {
+					pluginHost.showFeedback("The thermal band and emissivity image must have the same dimensions.")
+					return
+				}
+				emissivityNodata = emissivityImage.getNoDataValue()
+			}
+
+			/* Radiances outside of the range emitted by black bodies at
+			 * MIN_TEMPERATURE to MAX_TEMPERATURE are not physically
+			 * meaningful for land surfaces. These cells are assigned
+			 * NoData. If most of the image is out of range, the input is
+			 * probably not radiance, e.g. it contains digital numbers.
+			 */
+			double minRadiance = k1 / (Math.exp(k2 / MIN_TEMPERATURE) - 1)
+			double maxRadiance = k1 / (Math.exp(k2 / MAX_TEMPERATURE) - 1)
+
+			double[] data
+			double radiance
+			long numInRange = 0
+			long numOutOfRange = 0
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				data = image.getRowValues(row)
+				for (int col = 0; col < cols; col++) {
+					radiance = data[col]
+					if (radiance != nodata) {
+						if (radiance < minRadiance || radiance > maxRadiance) {
+							numOutOfRange++
+						} else {
+							numInRange++
+						}
+					}
+				}
+				int progress = (int)(50f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Validating radiance:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+			String radianceRange = String.format("%.4f", minRadiance) + " to " + String.format("%.4f", maxRadiance) + " W/(m2 sr um)"
+			if (numOutOfRange > numInRange) {
+				pluginHost.showFeedback("Most of the thermal band's values are outside of the physically meaningful range of radiance (" +
+				  radianceRange + "). \nThe input image may contain digital numbers (DN), which must first be converted to radiance, " +
+				  "or the thermal constants may be incorrect.")
+				return
+			}
+
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw",
+			  inputFile, DataType.FLOAT, nodata)
+			output.setPreferredPalette("spectrum.pal")
+			output.setDataScale(DataScale.CONTINUOUS)
+
+			double[] emissivityData = null
+			double emissivity, temperature
+			long numBadEmissivity = 0
+			oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				data = image.getRowValues(row)
+				if (emissivityImage != null) {
+					emissivityData = emissivityImage.getRowValues(row)
+				}
+				for (int col = 0; col < cols; col++) {
+					radiance = data[col]
+					emissivity = emissivityData != null ? emissivityData[col] : emissivityConst
+					if (radiance == nodata || emissivity == emissivityNodata) {
+						output.setValue(row, col, nodata)
+					} else if (radiance < minRadiance || radiance > maxRadiance) {
+						output.setValue(row, col, nodata)
+					} else if (emissivity <= 0 || emissivity > 1) {
+						numBadEmissivity++
+						output.setValue(row, col, nodata)
+					} else {
+						temperature = k2 / Math.log(emissivity * k1 / radiance + 1)
+						if (celsius) {
+							temperature -= 273.15
+						}
+						output.setValue(row, col, temperature)
+					}
+				}
+				int progress = (int)(50f + 50f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Calculating temperature:", progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			image.close()
+			if (emissivityImage != null) {
+				emissivityImage.close()
+			}
+
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Thermal band: " + new File(inputFile).getName())
+			output.addMetadataEntry("Emissivity: " + (emissivityImage != null ? new File(emissivityFile).getName() : String.valueOf(emissivityConst)))
+			output.addMetadataEntry("K1: " + k1 + ", K2: " + k2)
+			output.addMetadataEntry("Units: " + (celsius ? "degrees Celsius" : "Kelvin"))
+			output.close()
+
+			if (numOutOfRange > 0 || numBadEmissivity > 0) {
+				StringBuilder sb = new StringBuilder()
+				if (numOutOfRange > 0) {
+					sb.append(numOutOfRange).append(" grid cells with radiance values outside of the physically meaningful range (")
+					sb.append(radianceRange).append(") were assigned NoData.")
+				}
+				if (numBadEmissivity > 0) {
+					if (sb.length() > 0) {
+						sb.append("\n")
+					}
+					sb.append(numBadEmissivity).append(" grid cells with emissivity values outside of the range 0 to 1 were assigned NoData.")
+				}
+				pluginHost.showFeedback(sb.toString())
+			}
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new LandSurfaceTemperature(pluginHost, args, name, descriptiveName)
+}
@@ -108,7 +108,7 @@ public class LiDAR_Adaptive_Min_Interpolation implements ActionListener {
 	// that of native Java code.
 	@CompileStatic
 	private void execute(String[] args) {
-		long start = System.currentTimeMillis()  
+		long start = System.nanoTime()
 	  try {
 	  	if (args.length != 19) {
 			pluginHost.showFeedback("Incorrect number of arguments given to tool.")
@@ -247,9 +247,8 @@ public class LiDAR_Adaptive_Min_Interpolation implements ActionListener {
         String outputHeader = inputFiles[0].replace(".${inputFileExtension}", suffix + ".dep");
         pluginHost.returnData(outputHeader);
 
-		long end = System.currentTimeMillis()  
-		double duration = (end - start) / 1000.0
-		pluginHost.showFeedback("Interpolation completed in " + duration + " seconds.\n" + 
+		String duration = StringUtilities.formatElapsedTime(System.nanoTime() - start)
+		pluginHost.showFeedback("Interpolation completed in " + duration + ".\n" + 
 		  numSuccessfulInterpolations + " tiles were successfully interpolated.\n" + 
 		  "One has been displayed on the map.")
 		
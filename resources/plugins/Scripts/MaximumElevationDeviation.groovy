@@ -33,8 +33,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.*
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -116,7 +114,7 @@ public class MaximumElevationDeviation implements ActionListener {
 				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
 				return
 			}
-			Date start = new Date()
+			long start = System.nanoTime()
 			
 			// read the input parameters
 			String inputFile = args[0]
@@ -330,8 +328,7 @@ public class MaximumElevationDeviation implements ActionListener {
 			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
 	        output.addMetadataEntry("Created on " + new Date())
 			output.addMetadataEntry("MinNeighbourHood: $minNeighbourhood MaxNeighbourhood: $actualMaxNeighbourhood Step: $neighbourhoodStep")
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			output.addMetadataEntry("Elapsed time: $td")
 			output.close()
 
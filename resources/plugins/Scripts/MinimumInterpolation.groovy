@@ -130,7 +130,7 @@ public class MinimumInterpolation implements ActionListener {
 	// that of native Java code.
 	@CompileStatic
 	private void execute(String[] args) {
-		long start = System.currentTimeMillis()  
+		long start = System.nanoTime()
 		  try {
 		  	int progress, oldProgress, rows, cols, row, col
 	    	double x, y, z
@@ -355,9 +355,8 @@ public class MinimumInterpolation implements ActionListener {
 		    
 			pluginHost.returnData(outputFile);
 	
-			long end = System.currentTimeMillis()  
-			double duration = (end - start) / 1000.0
-			pluginHost.showFeedback("Interpolation completed in " + duration + " seconds.")
+			String duration = StringUtilities.formatElapsedTime(System.nanoTime() - start)
+			pluginHost.showFeedback("Interpolation completed in " + duration + ".")
 		
 	  	} catch (OutOfMemoryError oe) {
             pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
@@ -0,0 +1,477 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import java.util.ArrayDeque
+import java.util.ArrayList
+import java.util.Collection
+import java.util.TreeMap
+import java.text.DecimalFormat
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "PatchShapeMetrics"
+def descriptiveName = "Patch Shape Metrics"
+def description = "Calculates several shape metrics for each patch or class in a categorical raster."
+def toolboxes = ["PatchShapeTools"]
+
+public class PatchShapeMetrics implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public PatchShapeMetrics(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input categorical (class) raster", "Input Class Raster:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output text file", "Output Text File:", "save", "Text Files (*.csv), CSV", true, false)
+			sd.addDialogCheckBox("Treat zero-valued cells as background?", "Treat zero values as background?", true)
+			sd.addDialogCheckBox("Output a raster for each shape metric?", "Output a raster for each metric?", false)
+			sd.addDialogComboBox("Report metrics for each patch or for each class?", "Reporting Level:", ["patch", "class"], 0)
+			sd.addDialogDataInput("Edge width used to calculate core area (cells)", "Core Area Edge Width (cells):", "1", true, false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 2) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			boolean zeroBackground = true
+			if (args.length > 2) {
+				zeroBackground = Boolean.parseBoolean(args[2])
+			}
+			boolean outputRasters = false
+			if (args.length > 3) {
+				outputRasters = Boolean.parseBoolean(args[3])
+			}
+			boolean classLevel = false
+			if (args.length > 4) {
+				classLevel = args[4].toLowerCase().contains("class")
+			}
+			int edgeWidth = 1
+			if (args.length > 5 && !args[5].isEmpty()) {
+				edgeWidth = (int)Double.parseDouble(args[5])
+				if (edgeWidth < 0) {
+					edgeWidth = 0
+				}
+			}
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+			double cellSizeX = image.getCellSizeX()
+			double cellSizeY = image.getCellSizeY()
+			double cellArea = cellSizeX * cellSizeY
+			String[] metricNames = ["area", "perimeter", "perim_area_ratio", "shape_index",
+				"compactness", "elongation", "fractal_dim", "core_area"]
+
+			double[][] z = new double[rows][]
+			for (int row = 0; row < rows; row++) {
+				z[row] = image.getRowValues(row)
+			}
+			// the grid is held in memory from here on
+			image.close()
+
+			/* The perimeter is measured along cell edges. An edge is
+			 * part of a patch's boundary if the neighbouring cell
+			 * belongs to another patch, is background, or lies beyond
+			 * the edge of the grid. North and south edges have a
+			 * length equal to the cell width, east and west edges a
+			 * length equal to the cell height.
+			 */
+			int[] dX = [ 0, 1, 0, -1, 1, 1, -1, -1 ]
+			int[] dY = [ -1, 0, 1, 0, -1, 1, 1, -1 ]
+			double[] edgeLengths = [ cellSizeX, cellSizeY, cellSizeX, cellSizeY ]
+
+			/* A cell is part of the core area if no cell belonging to
+			 * another patch, and no part of the grid edge, lies within
+			 * the edge width, measured as the Euclidean distance in
+			 * cells between cell centres.
+			 */
+			ArrayList<int[]> coreOffsets = new ArrayList<int[]>()
+			for (int dy = -edgeWidth; dy <= edgeWidth; dy++) {
+				for (int dx = -edgeWidth; dx <= edgeWidth; dx++) {
+					if ((dx != 0 || dy != 0) && dx * dx + dy * dy <= edgeWidth * edgeWidth) {
+						coreOffsets.add([dx, dy] as int[])
+					}
+				}
+			}
+
+			/* Patches are groups of connected cells sharing the same
+			 * class value, using the eight-neighbour rule. Each patch
+			 * is labelled by flood filling from its first cell.
+			 */
+			int[][] patchIDs = new int[rows][cols]
+			ArrayList<PatchStats> patches = new ArrayList<PatchStats>()
+			ArrayDeque<Integer> stack = new ArrayDeque<Integer>()
+			PatchStats ps
+			double zValue
+			int id, n, r, c, rN, cN, cell, classValue
+			int progress, oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				for (int col = 0; col < cols; col++) {
+					zValue = z[row][col]
+					if (zValue == nodata || (zeroBackground && zValue == 0) || patchIDs[row][col] != 0) {
+						continue
+					}
+					classValue = (int)zValue
+					ps = new PatchStats()
+					ps.classValue = classValue
+					patches.add(ps)
+					id = patches.size()
+					patchIDs[row][col] = id
+					stack.push(row * cols + col)
+					while (!stack.isEmpty()) {
+						cell = stack.pop()
+						r = (int)(cell / cols)
+						c = cell % cols
+						for (n = 0; n < 8; n++) {
+							rN = r + dY[n]
+							cN = c + dX[n]
+							if (rN >= 0 && rN < rows && cN >= 0 && cN < cols && patchIDs[rN][cN] == 0) {
+								zValue = z[rN][cN]
+								if (zValue != nodata && !(zeroBackground && zValue == 0) && (int)zValue == classValue) {
+									patchIDs[rN][cN] = id
+									stack.push(rN * cols + cN)
+								}
+							}
+						}
+					}
+				}
+				progress = (int)(100f * row / rows)
+				if (progress > oldProgress) {
+					pluginHost.updateProgress("Loop 1 of 3:", progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			if (patches.isEmpty()) {
+				pluginHost.showFeedback("The input raster does not contain any patches.")
+				return
+			}
+
+			boolean isCore
+			double x, y
+			oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				for (int col = 0; col < cols; col++) {
+					id = patchIDs[row][col]
+					if (id == 0) {
+						continue
+					}
+					ps = patches.get(id - 1)
+					// cell centre coordinates relative to the grid origin
+					x = (col + 0.5) * cellSizeX
+					y = (row + 0.5) * cellSizeY
+					ps.addCell(x, y)
+					for (n = 0; n < 4; n++) {
+						rN = row + dY[n]
+						cN = col + dX[n]
+						if (rN < 0 || rN >= rows || cN < 0 || cN >= cols || patchIDs[rN][cN] != id) {
+							ps.perimeter += edgeLengths[n]
+						}
+					}
+					isCore = true
+					for (int[] offset : coreOffsets) {
+						rN = row + offset[1]
+						cN = col + offset[0]
+						if (rN < 0 || rN >= rows || cN < 0 || cN >= cols || patchIDs[rN][cN] != id) {
+							isCore = false
+							break
+						}
+					}
+					if (isCore) {
+						ps.numCoreCells++
+					}
+				}
+				progress = (int)(100f * row / rows)
+				if (progress > oldProgress) {
+					pluginHost.updateProgress("Loop 2 of 3:", progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			// class-level statistics pool the cells of all patches in a class
+			TreeMap<Integer, PatchStats> classes = new TreeMap<Integer, PatchStats>()
+			if (classLevel) {
+				for (PatchStats p : patches) {
+					ps = classes.get(p.classValue)
+					if (ps == null) {
+						ps = new PatchStats()
+						ps.classValue = p.classValue
+						ps.numPatches = 0
+						classes.put(p.classValue, ps)
+					}
+					ps.merge(p)
+				}
+			}
+
+			// the second moments of a single cell about its own centre
+			double cellMomentX = cellSizeX * cellSizeX / 12.0
+			double cellMomentY = cellSizeY * cellSizeY / 12.0
+			Collection<PatchStats> records = classLevel ? classes.values() : patches
+			for (PatchStats p : records) {
+				p.calculateMetrics(cellArea, cellMomentX, cellMomentY)
+			}
+
+			File outFile = new File(outputFile)
+			if (outFile.exists()) {
+				outFile.delete()
+			}
+			DecimalFormat df = new DecimalFormat("0.0######")
+			outFile.withWriter { BufferedWriter out ->
+				StringBuilder header = new StringBuilder()
+				header.append(classLevel ? "CLASS,NUM_PATCHES,NUM_CELLS" : "ID,CLASS,NUM_CELLS")
+				for (int m = 0; m < metricNames.length; m++) {
+					header.append(",").append(metricNames[m].toUpperCase())
+				}
+				out.writeLine(header.toString())
+				int patchNum = 0
+				for (PatchStats p : records) {
+					patchNum++
+					StringBuilder sb = new StringBuilder()
+					if (classLevel) {
+						sb.append(p.classValue).append(",").append(p.numPatches)
+					} else {
+						sb.append(patchNum).append(",").append(p.classValue)
+					}
+					sb.append(",").append(p.numCells)
+					for (int m = 0; m < metricNames.length; m++) {
+						sb.append(",")
+						if (!Double.isNaN(p.metrics[m])) {
+							sb.append(df.format(p.metrics[m]))
+						}
+					}
+					out.writeLine(sb.toString())
+				}
+			}
+
+			// per-metric rasters are named after the output text file
+			String baseName = outputFile
+			if (baseName.toLowerCase().endsWith(".csv")) {
+				baseName = baseName.substring(0, baseName.length() - 4)
+			}
+			if (outputRasters) {
+				WhiteboxRaster[] outputs = new WhiteboxRaster[metricNames.length]
+				for (int m = 0; m < metricNames.length; m++) {
+					outputs[m] = new WhiteboxRaster(baseName + "_" + metricNames[m] + ".dep",
+					  "rw", inputFile, DataType.FLOAT, nodata)
+					outputs[m].setPreferredPalette("spectrum.pal")
+					outputs[m].setDataScale(DataScale.CONTINUOUS)
+				}
+				oldProgress = -1
+				for (int row = 0; row < rows; row++) {
+					for (int col = 0; col < cols; col++) {
+						id = patchIDs[row][col]
+						if (id != 0) {
+							ps = patches.get(id - 1)
+							if (classLevel) {
+								ps = classes.get(ps.classValue)
+							}
+							for (int m = 0; m < metricNames.length; m++) {
+								if (!Double.isNaN(ps.metrics[m])) {
+									outputs[m].setValue(row, col, ps.metrics[m])
+								}
+							}
+						}
+					}
+					progress = (int)(100f * row / rows)
+					if (progress > oldProgress) {
+						pluginHost.updateProgress("Loop 3 of 3:", progress)
+						oldProgress = progress
+						// check to see if the user has requested a cancellation
+						if (pluginHost.isRequestForOperationCancelSet()) {
+							pluginHost.showFeedback("Operation cancelled")
+							return
+						}
+					}
+				}
+				for (int m = 0; m < metricNames.length; m++) {
+					outputs[m].addMetadataEntry("Created by the " + descriptiveName + " tool.")
+	        		outputs[m].addMetadataEntry("Created on " + new Date())
+					outputs[m].close()
+				}
+			}
+
+			if (classLevel) {
+				pluginHost.showFeedback("Shape metrics for ${classes.size()} classes were written to ${outFile.getName()}.")
+			} else {
+				pluginHost.showFeedback("Shape metrics for ${patches.size()} patches were written to ${outFile.getName()}.")
+			}
+			if (outputRasters) {
+				// display the perimeter-area ratio raster
+				pluginHost.returnData(baseName + "_" + metricNames[2] + ".dep")
+			}
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+
+    @CompileStatic
+    class PatchStats {
+    	int classValue
+    	int numPatches = 1
+    	long numCells = 0
+    	long numCoreCells = 0
+    	double perimeter = 0
+    	double sumX = 0, sumY = 0, sumXX = 0, sumYY = 0, sumXY = 0
+    	double[] metrics
+
+    	void addCell(double x, double y) {
+    		numCells++
+    		sumX += x
+    		sumY += y
+    		sumXX += x * x
+    		sumYY += y * y
+    		sumXY += x * y
+    	}
+
+    	void merge(PatchStats other) {
+    		numPatches += other.numPatches
+    		numCells += other.numCells
+    		numCoreCells += other.numCoreCells
+    		perimeter += other.perimeter
+    		sumX += other.sumX
+    		sumY += other.sumY
+    		sumXX += other.sumXX
+    		sumYY += other.sumYY
+    		sumXY += other.sumXY
+    	}
+
+    	void calculateMetrics(double cellArea, double cellMomentX, double cellMomentY) {
+    		metrics = new double[8]
+    		double area = numCells * cellArea
+    		metrics[0] = area
+    		metrics[1] = perimeter
+    		metrics[2] = perimeter / area
+    		// shape index, equal to one for a circle
+    		metrics[3] = perimeter / (2.0 * Math.sqrt(Math.PI * area))
+    		// ratio of the patch area to the area of a circle with the same perimeter
+    		metrics[4] = 4.0 * Math.PI * area / (perimeter * perimeter)
+
+    		/* Elongation is based on the eigenvalues of the covariance
+    		 * matrix of cell-centre coordinates, i.e. the second moments
+    		 * about the principal axes. Each cell contributes its own
+    		 * moment of inertia so that a single cell or a square
+    		 * patch has an elongation of zero.
+    		 */
+    		double meanX = sumX / numCells
+    		double meanY = sumY / numCells
+    		double varX = sumXX / numCells - meanX * meanX + cellMomentX
+    		double varY = sumYY / numCells - meanY * meanY + cellMomentY
+    		double covXY = sumXY / numCells - meanX * meanY
+    		double halfTrace = (varX + varY) / 2.0
+    		double d = Math.sqrt(((varX - varY) / 2.0) * ((varX - varY) / 2.0) + covXY * covXY)
+    		double lambda1 = halfTrace + d
+    		double lambda2 = Math.max(halfTrace - d, 0.0)
+    		metrics[5] = 1.0 - Math.sqrt(lambda2 / lambda1)
+
+    		// perimeter-area fractal dimension (McGarigal and Marks, 1995)
+    		if (area > 1.0) {
+    			metrics[6] = 2.0 * Math.log(0.25 * perimeter) / Math.log(area)
+    		} else {
+    			metrics[6] = Double.NaN
+    		}
+
+    		metrics[7] = numCoreCells * cellArea
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new PatchShapeMetrics(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "PiecewiseContrastStretch"
+def descriptiveName = "Piecewise Contrast Stretch"
+def description = "Performs a piecewise linear contrast stretch using user-defined breakpoints."
+def toolboxes = ["ImageEnhancement"]
+
+public class PiecewiseContrastStretch implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public PiecewiseContrastStretch(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input raster file", "Input Raster File:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output raster file", "Output Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Breakpoints as semicolon-separated input,output pairs, e.g. 0,0; 100,30; 500,200; 4000,255", "Stretch Function (in,out; in,out; ...):", "", false, false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 3) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			String functionString = args[2]
+
+			// parse and validate the breakpoints
+			String[] pairs = functionString.trim().split(";")
+			int numBreakpoints = pairs.length
+			if (numBreakpoints < 2) {
+				pluginHost.showFeedback("At least two breakpoints must be specified.")
+				return
+			}
+			double[] xs = new double[numBreakpoints]
+			double[] ys = new double[numBreakpoints]
+			for (int i = 0; i < numBreakpoints; i++) {
+				String[] vals = pairs[i].trim().split(",")
+				if (vals.length != 2) {
+					pluginHost.showFeedback("Breakpoint ${i + 1} ('${pairs[i].trim()}') is not a pair of input,output values.")
+					return
+				}
+				try {
+					xs[i] = Double.parseDouble(vals[0].trim())
+					ys[i] = Double.parseDouble(vals[1].trim())
+				} catch (NumberFormatException nfe) {
+					pluginHost.showFeedback("Breakpoint ${i + 1} ('${pairs[i].trim()}') contains a non-numeric value.")
+					return
+				}
+				if (i > 0 && xs[i] <= xs[i - 1]) {
+					pluginHost.showFeedback("The input values of the breakpoints must be increasing. Breakpoint ${i + 1} (${pairs[i].trim()}) does not have a greater input value than breakpoint ${i} (${pairs[i - 1].trim()}).")
+					return
+				}
+			}
+			double outMin = Double.POSITIVE_INFINITY
+			double outMax = Double.NEGATIVE_INFINITY
+			for (int i = 0; i < numBreakpoints; i++) {
+				outMin = Math.min(outMin, ys[i])
+				outMax = Math.max(outMax, ys[i])
+			}
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw",
+			  inputFile, DataType.FLOAT, nodata)
+			output.setPreferredPalette(image.getPreferredPalette())
+
+			/* Values are linearly interpolated between the two
+			 * bracketing breakpoints. Values beyond the first and last
+			 * breakpoints are clamped to their output values.
+			 */
+			double[] data
+			double z, zOut
+			int lo, hi, mid
+			int progress, oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				data = image.getRowValues(row)
+				for (int col = 0; col < cols; col++) {
+					z = data[col]
+					if (z != nodata) {
+						if (z <= xs[0]) {
+							zOut = ys[0]
+						} else if (z >= xs[numBreakpoints - 1]) {
+							zOut = ys[numBreakpoints - 1]
+						} else {
+							// binary search for the segment containing z
+							lo = 0
+							hi = numBreakpoints - 1
+							while (hi - lo > 1) {
+								mid = (int)((lo + hi) / 2)
+								if (xs[mid] <= z) {
+									lo = mid
+								} else {
+									hi = mid
+								}
+							}
+							zOut = ys[lo] + (z - xs[lo]) / (xs[hi] - xs[lo]) * (ys[hi] - ys[lo])
+						}
+						output.setValue(row, col, zOut)
+					}
+				}
+				progress = (int)(100f * row / rows)
+				if (progress > oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			image.close()
+
+			if (outMax > outMin) {
+				output.setDisplayMinimum(outMin)
+				output.setDisplayMaximum(outMax)
+			}
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Stretch function: " + functionString.trim())
+			output.close()
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new PiecewiseContrastStretch(pluginHost, args, name, descriptiveName)
+}
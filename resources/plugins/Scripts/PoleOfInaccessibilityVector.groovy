@@ -0,0 +1,356 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+ 
+import com.vividsolutions.jts.geom.Coordinate
+import com.vividsolutions.jts.geom.Geometry
+import com.vividsolutions.jts.geom.GeometryCollection
+import com.vividsolutions.jts.geom.GeometryFactory
+import com.vividsolutions.jts.geom.LineString
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.io.File
+import java.util.ArrayList
+import java.util.PriorityQueue
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.ShapeFile
+import whitebox.geospatialfiles.shapefile.*
+import whitebox.geospatialfiles.shapefile.attributes.*
+import whitebox.ui.plugin_dialog.ScriptDialog
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this 
+// script to be integrated into the tool tree panel. 
+// Comment them out if you want to remove the script.
+def name = "PoleOfInaccessibilityVector"
+def descriptiveName = "Pole Of Inaccessibility (Vector)"
+def description = "Identifies the interior point of polygons that is most distant from their boundaries."
+def toolboxes = ["VectorTools"]
+
+public class PoleOfInaccessibilityVector implements ActionListener {
+    private WhiteboxPluginHost pluginHost
+    private ScriptDialog sd;
+    private String descriptiveName
+	
+    public PoleOfInaccessibilityVector(WhiteboxPluginHost pluginHost, 
+        String[] args, def name, def descriptiveName) {
+        this.pluginHost = pluginHost
+        this.descriptiveName = descriptiveName
+			
+        if (args.length > 0) {
+            execute(args)
+        } else {
+            // Create a dialog for this tool to collect user-specified
+            // tool parameters.
+            sd = new ScriptDialog(pluginHost, descriptiveName, this)	
+		
+            // Specifying the help file will display the html help
+            // file in the help pane. This file should be be located 
+            // in the help directory and have the same name as the 
+            // class, with an html extension.
+            sd.setHelpFile(name)
+		
+            // Specifying the source file allows the 'view code' 
+            // button on the tool dialog to be displayed.
+            def pathSep = File.separator
+            def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+            sd.setSourceFile(scriptFile)
+			
+            // add some components to the dialog
+            sd.addDialogFile("Input polygon vector file", "Input Polygon Vector File:", "open", "Vector Files (*.shp), SHP", true, false)
+            sd.addDialogFile("Output points file", "Output Points Vector File:", "save", "Vector Files (*.shp), SHP", true, false)
+            sd.addDialogDataInput("Precision of the pole location, in map units (optional)", "Precision (map units; optional):", "", true, true)
+            sd.addDialogCheckBox("Create one point for each part in a multipart feature?", "Create multiple points for multipart features?", false)
+			
+            // resize the dialog to the standard size and display it
+            sd.setSize(800, 400)
+            sd.visible = true
+        }
+    }
+
+    // The CompileStatic annotation can be used to significantly
+    // improve the performance of a Groovy script to nearly 
+    // that of native Java code.
+    @CompileStatic
+    private void execute(String[] args) {
+        try {
+            int i, n, progress, oldProgress
+            
+            if (args.length != 4) {
+                pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+                return
+            }
+            
+            // read the input parameters
+            String inputFile = args[0]
+            String outputFile = args[1]
+            double precision = -1.0
+            if (!args[2].trim().isEmpty() && !args[2].toLowerCase().equals("not specified")) {
+            	precision = Double.parseDouble(args[2])
+            	if (precision <= 0) {
+            		pluginHost.showFeedback("The precision must be greater than zero.")
+            		return
+            	}
+            }
+            boolean breakApartMultipart = Boolean.parseBoolean(args[3])
+            
+           	def input = new ShapeFile(inputFile)
+			def shapeType = input.getShapeType()
+			if (shapeType.getBaseType() != ShapeType.POLYGON) {
+			    pluginHost.showFeedback("The input shapefile must have a POLYGON shape type.");
+			    return;
+			}
+			
+			def numRecs = input.getNumberOfRecords();
+			AttributeTable table = input.getAttributeTable()
+			DBFField[] inFields = table.getAllFields()
+			int numInFields = inFields.length
+			
+			// the output carries the input attributes plus the distance 
+			// from each pole to the nearest polygon boundary
+			DBFField[] fields = new DBFField[numInFields + 1]
+			for (n = 0; n < numInFields; n++) {
+				fields[n] = inFields[n]
+			}
+			DBFField field = new DBFField()
+			field.setName("DIST")
+            field.setDataType(DBFField.DBFDataType.NUMERIC)
+            field.setFieldLength(14)
+            field.setDecimalCount(5)
+            fields[numInFields] = field
+			
+			def output = new ShapeFile(outputFile, ShapeType.POINT, fields);
+			
+			Geometry[] jtsGeometries = null;
+			GeometryFactory factory = new GeometryFactory()
+			oldProgress = -1
+			progress = 0;
+			for (i = 0; i < numRecs; i++) {
+				ShapeFileRecord record = input.getRecord(i)
+			    if (record.getShapeType() != ShapeType.NULLSHAPE) {
+			        jtsGeometries = record.getGeometry().getJTSGeometries();
+			        Object[] inRowData = table.getRecord(i)
+			        List<Geometry> targets = new ArrayList<>()
+			        if (breakApartMultipart) {
+			            for (n = 0; n < jtsGeometries.length; n++) {
+			            	targets.add(jtsGeometries[n])
+			            }
+			        } else {
+			        	targets.add(new GeometryCollection(jtsGeometries, factory))
+			        }
+			        for (Geometry g : targets) {
+			        	double[] pole = findPole(g, precision)
+			        	if (pole == null) {
+			        		continue
+			        	}
+			            whitebox.geospatialfiles.shapefile.Point wbGeometry = new whitebox.geospatialfiles.shapefile.Point(pole[0], pole[1]);
+			            Object[] rowData = new Object[numInFields + 1]
+			            for (n = 0; n < numInFields; n++) {
+			            	rowData[n] = inRowData[n]
+			            }
+			            rowData[numInFields] = new Double(pole[2])
+					    output.addRecord(wbGeometry, rowData);
+			        }
+			    }
+			    progress = (int)(100f * i / (numRecs - 1))
+			    if (progress > oldProgress) {
+			        pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+			    }
+			}
+			
+			output.write()
+			
+			// display the output image
+            pluginHost.returnData(outputFile)
+        } catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+    }
+    
+    // Finds the pole of inaccessibility of a polygon geometry using the 
+    // quadtree refinement of the polylabel algorithm (Agafonkin, 2016). 
+    // The bounding box is covered by square cells that are searched in 
+    // order of the greatest distance to the boundary that any point within 
+    // them could have, and cells that cannot improve on the best point 
+    // found by more than the precision are discarded. Returns the x and y 
+    // coordinates of the pole and its distance to the boundary.
+    @CompileStatic
+    private double[] findPole(Geometry g, double precision) {
+    	// gather the rings of all of the polygons in the geometry
+    	List<Coordinate[]> rings = new ArrayList<>()
+    	addRings(g, rings)
+    	if (rings.isEmpty()) {
+    		return null
+    	}
+    	
+    	def env = g.getEnvelopeInternal()
+    	double minX = env.getMinX()
+    	double minY = env.getMinY()
+    	double width = env.getWidth()
+    	double height = env.getHeight()
+    	double cellSize = Math.min(width, height)
+    	if (cellSize == 0) {
+    		return [minX, minY, 0.0] as double[]
+    	}
+    	if (precision <= 0) {
+    		precision = Math.max(width, height) / 1000.0
+    	}
+    	double h = cellSize / 2.0
+    	
+    	PriorityQueue<double[]> queue = new PriorityQueue<double[]>(16, new Comparator<double[]>() {
+    		@Override
+    		public int compare(double[] a, double[] b) {
+    			return Double.compare(b[3], a[3])
+    		}
+    	})
+    	for (double x = minX; x < minX + width; x += cellSize) {
+    		for (double y = minY; y < minY + height; y += cellSize) {
+    			queue.add(makeCell(x + h, y + h, h, rings))
+    		}
+    	}
+    	
+    	// start with the centroid, which is often close to the pole, and the 
+    	// centre of the bounding box
+    	Coordinate c = g.getCentroid().getCoordinate()
+    	double[] best = makeCell(c.x, c.y, 0, rings)
+    	double[] bboxCell = makeCell(minX + width / 2.0, minY + height / 2.0, 0, rings)
+    	if (bboxCell[2] > best[2]) {
+    		best = bboxCell
+    	}
+    	
+    	while (!queue.isEmpty()) {
+    		double[] cell = queue.poll()
+    		if (cell[2] > best[2]) {
+    			best = cell
+    		}
+    		// the cell's potential distance (cell[3]) can't improve on the best by enough
+    		if (cell[3] - best[2] <= precision) {
+    			continue
+    		}
+    		double ch = cell[4] / 2.0
+    		queue.add(makeCell(cell[0] - ch, cell[1] - ch, ch, rings))
+    		queue.add(makeCell(cell[0] + ch, cell[1] - ch, ch, rings))
+    		queue.add(makeCell(cell[0] - ch, cell[1] + ch, ch, rings))
+    		queue.add(makeCell(cell[0] + ch, cell[1] + ch, ch, rings))
+    	}
+    	
+    	return [best[0], best[1], best[2]] as double[]
+    }
+    
+    // Returns a cell as {x, y, distance, maximum potential distance, half size}.
+    @CompileStatic
+    private double[] makeCell(double x, double y, double h, List<Coordinate[]> rings) {
+    	double d = signedDistance(x, y, rings)
+    	return [x, y, d, d + h * Math.sqrt(2.0), h] as double[]
+    }
+    
+    @CompileStatic
+    private void addRings(Geometry g, List<Coordinate[]> rings) {
+    	for (int k = 0; k < g.getNumGeometries(); k++) {
+    		Geometry part = g.getGeometryN(k)
+    		if (part instanceof com.vividsolutions.jts.geom.Polygon) {
+    			com.vividsolutions.jts.geom.Polygon poly = (com.vividsolutions.jts.geom.Polygon)part
+    			rings.add(poly.getExteriorRing().getCoordinates())
+    			for (int r = 0; r < poly.getNumInteriorRing(); r++) {
+    				rings.add(poly.getInteriorRingN(r).getCoordinates())
+    			}
+    		} else if (part instanceof GeometryCollection) {
+    			addRings(part, rings)
+    		}
+    	}
+    }
+    
+    // Returns the distance from a point to the nearest ring, which is 
+    // positive inside the polygon and negative outside of it. Insideness is 
+    // determined by the even-odd rule over all rings, so that holes are 
+    // outside.
+    @CompileStatic
+    private double signedDistance(double x, double y, List<Coordinate[]> rings) {
+    	boolean inside = false
+    	double minDistSq = Double.POSITIVE_INFINITY
+    	for (Coordinate[] ring : rings) {
+    		int numPoints = ring.length
+    		for (int k = 0; k < numPoints; k++) {
+    			Coordinate a = ring[k]
+    			Coordinate b = ring[k > 0 ? k - 1 : numPoints - 1]
+    			if ((a.y > y) != (b.y > y) && (x < (b.x - a.x) * (y - a.y) / (b.y - a.y) + a.x)) {
+    				inside = !inside
+    			}
+    			double distSq = segmentDistanceSq(x, y, a, b)
+    			if (distSq < minDistSq) {
+    				minDistSq = distSq
+    			}
+    		}
+    	}
+    	double dist = Math.sqrt(minDistSq)
+    	return inside ? dist : -dist
+    }
+    
+    @CompileStatic
+    private double segmentDistanceSq(double px, double py, Coordinate a, Coordinate b) {
+    	double x = a.x
+    	double y = a.y
+    	double dx = b.x - x
+    	double dy = b.y - y
+    	if (dx != 0 || dy != 0) {
+    		double t = ((px - x) * dx + (py - y) * dy) / (dx * dx + dy * dy)
+    		if (t > 1) {
+    			x = b.x
+    			y = b.y
+    		} else if (t > 0) {
+    			x += dx * t
+    			y += dy * t
+    		}
+    	}
+    	dx = px - x
+    	dy = py - y
+    	return dx * dx + dy * dy
+    }
+
+    @Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+            final def args = sd.collectParameters()
+            sd.dispose()
+            final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                    execute(args)
+            	}
+            }
+            final Thread t = new Thread(r)
+            t.start()
+    	}
+    }
+}
+
+if (args == null) {
+    pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+    def f = new PoleOfInaccessibilityVector(pluginHost, args, name, descriptiveName)
+}
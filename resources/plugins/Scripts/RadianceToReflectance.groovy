@@ -0,0 +1,230 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "RadianceToReflectance"
+def descriptiveName = "Radiance To Reflectance"
+def description = "Converts satellite image digital numbers to top-of-atmosphere reflectance."
+def toolboxes = ["ImageProc"]
+
+public class RadianceToReflectance implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public RadianceToReflectance(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input image (digital numbers)", "Input Image (DN):", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output reflectance image", "Output Reflectance File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Band number (recorded in the output metadata)", "Band Number:", "", true, true)
+			sd.addDialogDataInput("Solar zenith angle (degrees)", "Solar Zenith Angle (degrees):", "", true, false)
+			sd.addDialogDataInput("Mean solar exo-atmospheric irradiance for the band (W / (m2 um))", "ESUN (W/(m2 um)):", "", true, false)
+			sd.addDialogDataInput("Radiance gain (W / (m2 sr um) per DN)", "Gain:", "", true, false)
+			sd.addDialogDataInput("Radiance bias (W / (m2 sr um))", "Bias:", "", true, false)
+			sd.addDialogDataInput("Earth-Sun distance (astronomical units); leave blank to calculate from the day of year", "Earth-Sun Distance (AU):", "", true, true)
+			sd.addDialogDataInput("Day of year of image acquisition (1-366)", "Day of Year:", "", true, true)
+			sd.addDialogDataInput("Saturated DN value; leave blank to use the image maximum", "Saturated DN Value:", "", true, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 7) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			String bandNumber = isSpecified(args[2]) ? args[2].trim() : ""
+			double solarZenith = Double.parseDouble(args[3])
+			double esun = Double.parseDouble(args[4])
+			double gain = Double.parseDouble(args[5])
+			double bias = Double.parseDouble(args[6])
+			double earthSunDist = -1.0
+			if (args.length > 7 && isSpecified(args[7])) {
+				earthSunDist = Double.parseDouble(args[7])
+			}
+			if (earthSunDist <= 0) {
+				if (args.length > 8 && isSpecified(args[8])) {
+					int doy = (int)Double.parseDouble(args[8])
+					if (doy < 1 || doy > 366) {
+						pluginHost.showFeedback("The day of year must be between 1 and 366.")
+						return
+					}
+					// the eccentricity of the Earth's orbit gives the distance in AU
+					earthSunDist = 1.0 - 0.01672 * Math.cos(Math.toRadians(0.9856 * (doy - 4)))
+				} else {
+					pluginHost.showFeedback("Either the Earth-Sun distance or the day of year must be specified.")
+					return
+				}
+			}
+			if (solarZenith < 0 || solarZenith >= 90) {
+				pluginHost.showFeedback("The solar zenith angle must be between 0 and 90 degrees.")
+				return
+			}
+			if (esun <= 0) {
+				pluginHost.showFeedback("The solar exo-atmospheric irradiance (ESUN) must be greater than zero.")
+				return
+			}
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+			double saturatedDN = image.getMaximumValue()
+			if (args.length > 9 && isSpecified(args[9])) {
+				saturatedDN = Double.parseDouble(args[9])
+			}
+
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw",
+			  inputFile, DataType.FLOAT, nodata)
+			output.setPreferredPalette("grey.pal")
+			output.setDataScale(DataScale.CONTINUOUS)
+
+			// rho = pi L d^2 / (ESUN cos(theta))
+			double multiplier = Math.PI * earthSunDist * earthSunDist / (esun * Math.cos(Math.toRadians(solarZenith)))
+			double[] data
+			double dn, radiance
+			long numSaturated = 0
+			int progress, oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				data = image.getRowValues(row)
+				for (int col = 0; col < cols; col++) {
+					dn = data[col]
+					if (dn != nodata) {
+						if (dn == 0 || dn >= saturatedDN) {
+							numSaturated++
+						} else {
+							radiance = gain * dn + bias
+							output.setValue(row, col, radiance * multiplier)
+						}
+					}
+				}
+				progress = (int)(100f * row / rows)
+				if (progress > oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			image.close()
+
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			if (!bandNumber.isEmpty()) {
+				output.addMetadataEntry("Band number: " + bandNumber)
+			}
+			output.addMetadataEntry("Solar zenith angle: " + solarZenith + " degrees")
+			output.addMetadataEntry("ESUN: " + esun)
+			output.addMetadataEntry("Gain: " + gain + ", bias: " + bias)
+			output.addMetadataEntry("Earth-Sun distance: " + earthSunDist + " AU")
+			output.close()
+
+			if (numSaturated > 0) {
+				pluginHost.showFeedback("${numSaturated} cells with a DN of zero or a saturated DN were assigned NoData.")
+			}
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new RadianceToReflectance(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,459 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.text.DecimalFormat
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "RasterFrequencyAnalysis"
+def descriptiveName = "Raster Frequency Analysis"
+def description = "Calculates the value frequency distribution or the spatial power spectrum of a raster."
+def toolboxes = ["StatisticalTools"]
+
+public class RasterFrequencyAnalysis implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public RasterFrequencyAnalysis(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input raster file", "Input Raster File:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output text file", "Output Text File:", "save", "Text Files (*.csv), CSV", true, false)
+			sd.addDialogDataInput("Number of bins, of values or, for the spatial spectrum, of log-spaced frequencies used in the power-law fit", "Number Of Bins:", "50", true, false)
+			sd.addDialogCheckBox("Calculate the spatial power spectrum (otherwise the value histogram)?", "Spatial Frequency Analysis", false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 2) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			int numBins = 50
+			if (args.length > 2 && isSpecified(args[2])) {
+				numBins = Integer.parseInt(args[2].trim())
+			}
+			if (numBins < 2) {
+				pluginHost.showFeedback("The number of bins must be at least two.")
+				return
+			}
+			boolean spatialFreq = args.length > 3 && Boolean.parseBoolean(args[3])
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+			double[][] z = new double[rows][cols]
+			long numValid = 0
+			double min = Double.POSITIVE_INFINITY
+			double max = Double.NEGATIVE_INFINITY
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				double[] data = image.getRowValues(row)
+				for (int col = 0; col < cols; col++) {
+					z[row][col] = data[col]
+					if (data[col] != nodata) {
+						numValid++
+						if (data[col] < min) { min = data[col] }
+						if (data[col] > max) { max = data[col] }
+					}
+				}
+				int progress = (int)(20f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Reading data:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+			image.close()
+
+			if (numValid == 0) {
+				pluginHost.showFeedback("The input image does not contain any valid data.")
+				return
+			}
+
+			File outFile = new File(outputFile)
+			if (outFile.exists()) {
+				outFile.delete()
+			}
+			String message
+			if (!spatialFreq) {
+				message = valueHistogram(z, nodata, numValid, min, max, numBins, outFile)
+			} else {
+				message = spatialSpectrum(z, nodata, numBins, outFile)
+			}
+			if (message != null) {
+				pluginHost.showFeedback(message)
+			}
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	// Writes the histogram of the raster's values, with equal-width bins
+	// spanning the data range.
+	@CompileStatic
+	private String valueHistogram(double[][] z, double nodata, long numValid,
+	  double min, double max, int numBins, File outFile) {
+		int rows = z.length
+		int cols = z[0].length
+		double binSize = (max - min) / numBins
+		long[] counts = new long[numBins]
+		for (int row = 0; row < rows; row++) {
+			for (int col = 0; col < cols; col++) {
+				if (z[row][col] != nodata) {
+					int bin = binSize > 0 ? (int)((z[row][col] - min) / binSize) : 0
+					if (bin >= numBins) { bin = numBins - 1 }
+					counts[bin] = counts[bin] + 1
+				}
+			}
+		}
+
+		DecimalFormat df = new DecimalFormat("0.0######")
+		outFile.withWriter { BufferedWriter out ->
+			out.writeLine("BIN,LOWER,UPPER,CENTRE,COUNT,FREQUENCY,CUMULATIVE")
+			long cumulative = 0
+			for (int i = 0; i < numBins; i++) {
+				cumulative += counts[i]
+				double lower = min + i * binSize
+				StringBuilder sb = new StringBuilder()
+				sb.append(i + 1).append(",")
+				sb.append(df.format(lower)).append(",")
+				sb.append(df.format(lower + binSize)).append(",")
+				sb.append(df.format(lower + binSize / 2)).append(",")
+				sb.append(counts[i]).append(",")
+				sb.append(df.format((double)counts[i] / numValid)).append(",")
+				sb.append(df.format((double)cumulative / numValid))
+				out.writeLine(sb.toString())
+			}
+		}
+		return "The histogram of ${numValid} grid cells was written to ${outFile.getName()}."
+	}
+
+	/* Writes the spatial power spectrum of the raster, calculated as the
+	 * average periodogram of each of its rows and columns. Each line is
+	 * detrended, tapered with a Hann window, and zero-padded to a common
+	 * power-of-two length so that the periodograms share frequencies.
+	 * NoData cells are set to the line's trend. A power law,
+	 * P = a f^-beta, is fitted to the spectrum after averaging it within
+	 * logarithmically spaced frequency bins, so that each scale carries
+	 * similar weight in the fit.
+	 */
+	@CompileStatic
+	private String spatialSpectrum(double[][] z, double nodata, int numBins, File outFile) {
+		int rows = z.length
+		int cols = z[0].length
+		int n = 1
+		while (n < Math.max(rows, cols)) {
+			n *= 2
+		}
+		int numFreqs = (int)(n / 2)
+		double[] power = new double[numFreqs + 1]
+		double[] re = new double[n]
+		double[] im = new double[n]
+		int numLines = 0
+		int numSkipped = 0
+		int totalLines = rows + cols
+		int oldProgress = -1
+		for (int line = 0; line < totalLines; line++) {
+			int len = line < rows ? cols : rows
+			double[] values = new double[len]
+			for (int i = 0; i < len; i++) {
+				values[i] = line < rows ? z[line][i] : z[i][line - rows]
+			}
+			if (prepareLine(values, nodata, re, im)) {
+				fft(re, im)
+				double sumWindowSq = 0
+				for (int i = 0; i < len; i++) {
+					double w = hann(i, len)
+					sumWindowSq += w * w
+				}
+				for (int k = 1; k <= numFreqs; k++) {
+					double p = (re[k] * re[k] + im[k] * im[k]) / sumWindowSq
+					if (k < numFreqs) {
+						// one-sided spectrum
+						p = p * 2
+					}
+					power[k] = power[k] + p
+				}
+				numLines++
+			} else {
+				numSkipped++
+			}
+			int progress = (int)(20f + 70f * line / (totalLines - 1))
+			if (progress != oldProgress) {
+				pluginHost.updateProgress("Calculating power spectrum:", progress)
+				oldProgress = progress
+				if (pluginHost.isRequestForOperationCancelSet()) {
+					pluginHost.showFeedback("Operation cancelled")
+					return null
+				}
+			}
+		}
+		if (numLines == 0) {
+			return "None of the image's rows or columns contain enough valid data to calculate a power spectrum."
+		}
+		for (int k = 1; k <= numFreqs; k++) {
+			power[k] = power[k] / numLines
+		}
+
+		// average log power within log-spaced frequency bins
+		double logMinF = Math.log10(1d / n)
+		double logMaxF = Math.log10(0.5d)
+		double binWidth = (logMaxF - logMinF) / numBins
+		double[] sumLogF = new double[numBins]
+		double[] sumLogP = new double[numBins]
+		int[] binCount = new int[numBins]
+		for (int k = 1; k <= numFreqs; k++) {
+			if (power[k] <= 0) {
+				continue
+			}
+			double logF = Math.log10((double)k / n)
+			int bin = (int)((logF - logMinF) / binWidth)
+			if (bin >= numBins) { bin = numBins - 1 }
+			sumLogF[bin] = sumLogF[bin] + logF
+			sumLogP[bin] = sumLogP[bin] + Math.log10(power[k])
+			binCount[bin] = binCount[bin] + 1
+		}
+		// least-squares fit of log P on log f
+		double sx = 0, sy = 0, sxx = 0, sxy = 0, syy = 0
+		int m = 0
+		for (int b = 0; b < numBins; b++) {
+			if (binCount[b] > 0) {
+				double x = sumLogF[b] / binCount[b]
+				double y = sumLogP[b] / binCount[b]
+				sx += x
+				sy += y
+				sxx += x * x
+				sxy += x * y
+				syy += y * y
+				m++
+			}
+		}
+		double slope = Double.NaN
+		double intercept = Double.NaN
+		double rSquared = Double.NaN
+		if (m >= 2) {
+			double ssx = sxx - sx * sx / m
+			double ssy = syy - sy * sy / m
+			double ssxy = sxy - sx * sy / m
+			slope = ssxy / ssx
+			intercept = (sy - slope * sx) / m
+			rSquared = ssy > 0 ? ssxy * ssxy / (ssx * ssy) : 1d
+		}
+		// for profiles of a fractional Brownian surface, beta = 2H + 1
+		double beta = -slope
+		double hurst = (beta - 1) / 2
+
+		DecimalFormat df = new DecimalFormat("0.0######")
+		DecimalFormat dfe = new DecimalFormat("0.######E0")
+		outFile.withWriter { BufferedWriter out ->
+			out.writeLine("FREQUENCY,WAVELENGTH,POWER,FITTED_POWER")
+			for (int k = 1; k <= numFreqs; k++) {
+				double f = (double)k / n
+				StringBuilder sb = new StringBuilder()
+				sb.append(df.format(f)).append(",")
+				sb.append(df.format(1d / f)).append(",")
+				sb.append(dfe.format(power[k])).append(",")
+				if (!Double.isNaN(slope)) {
+					sb.append(dfe.format(Math.pow(10, intercept + slope * Math.log10(f))))
+				}
+				out.writeLine(sb.toString())
+			}
+			out.writeLine("")
+			out.writeLine("SLOPE," + df.format(slope))
+			out.writeLine("INTERCEPT," + df.format(intercept))
+			out.writeLine("R_SQUARED," + df.format(rSquared))
+			out.writeLine("SPECTRAL_EXPONENT," + df.format(beta))
+			out.writeLine("HURST_EXPONENT," + df.format(hurst))
+		}
+
+		StringBuilder sb = new StringBuilder()
+		sb.append("The power spectrum of ${numLines} rows and columns was written to ${outFile.getName()}.\n")
+		if (numSkipped > 0) {
+			sb.append("${numSkipped} rows and columns with fewer than half of their cells containing valid data were excluded.\n")
+		}
+		sb.append("Power-law slope: ").append(df.format(slope))
+		sb.append(" (r-squared = ").append(df.format(rSquared)).append(")\n")
+		sb.append("Hurst exponent: ").append(df.format(hurst))
+		return sb.toString()
+	}
+
+	/* Detrends and tapers a row or column into the FFT buffers, returning
+	 * false if fewer than half of its cells (or fewer than four cells)
+	 * are valid.
+	 */
+	@CompileStatic
+	private static boolean prepareLine(double[] values, double nodata, double[] re, double[] im) {
+		int len = values.length
+		int count = 0
+		double sx = 0, sy = 0, sxx = 0, sxy = 0
+		for (int i = 0; i < len; i++) {
+			if (values[i] != nodata) {
+				sx += i
+				sy += values[i]
+				sxx += (double)i * i
+				sxy += i * values[i]
+				count++
+			}
+		}
+		if (count < 4 || count < len / 2d) {
+			return false
+		}
+		double denom = count * sxx - sx * sx
+		double b = denom != 0 ? (count * sxy - sx * sy) / denom : 0d
+		double a = (sy - b * sx) / count
+		Arrays.fill(re, 0d)
+		Arrays.fill(im, 0d)
+		for (int i = 0; i < len; i++) {
+			if (values[i] != nodata) {
+				re[i] = (values[i] - (a + b * i)) * hann(i, len)
+			}
+		}
+		return true
+	}
+
+	@CompileStatic
+	private static double hann(int i, int len) {
+		return 0.5 - 0.5 * Math.cos(2 * Math.PI * i / (len - 1))
+	}
+
+	// In-place radix-2 Cooley-Tukey FFT; the length must be a power of two.
+	@CompileStatic
+	private static void fft(double[] re, double[] im) {
+		int n = re.length
+		int j = 0
+		for (int i = 1; i < n; i++) {
+			int bit = n >> 1
+			while ((j & bit) != 0) {
+				j ^= bit
+				bit >>= 1
+			}
+			j ^= bit
+			if (i < j) {
+				double tr = re[i]
+				re[i] = re[j]
+				re[j] = tr
+				double ti = im[i]
+				im[i] = im[j]
+				im[j] = ti
+			}
+		}
+		for (int len = 2; len <= n; len <<= 1) {
+			double angle = -2 * Math.PI / len
+			double wRe = Math.cos(angle)
+			double wIm = Math.sin(angle)
+			int half = len >> 1
+			for (int i = 0; i < n; i += len) {
+				double curRe = 1
+				double curIm = 0
+				for (int k = 0; k < half; k++) {
+					int p = i + k
+					int q = p + half
+					double tRe = re[q] * curRe - im[q] * curIm
+					double tIm = re[q] * curIm + im[q] * curRe
+					re[q] = re[p] - tRe
+					im[q] = im[p] - tIm
+					re[p] = re[p] + tRe
+					im[p] = im[p] + tIm
+					double nextRe = curRe * wRe - curIm * wIm
+					curIm = curRe * wIm + curIm * wRe
+					curRe = nextRe
+				}
+			}
+		}
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new RasterFrequencyAnalysis(pluginHost, args, name, descriptiveName)
+}
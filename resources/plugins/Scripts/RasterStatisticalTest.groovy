@@ -0,0 +1,508 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.text.DecimalFormat
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.ui.plugin_dialog.*
+import org.apache.commons.math3.distribution.NormalDistribution
+import org.apache.commons.math3.distribution.TDistribution
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "RasterStatisticalTest"
+def descriptiveName = "Raster Statistical Test"
+def description = "Performs a two-sample or paired-sample test for differences between two rasters."
+def toolboxes = ["StatisticalTools"]
+
+public class RasterStatisticalTest implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public RasterStatisticalTest(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input raster 1 file", "Input Raster 1:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Input raster 2 file", "Input Raster 2:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogComboBox("Compare the two rasters' value distributions (two-sample) or their co-located grid cells (paired)", "Test Design:", ["two-sample", "paired"], 0)
+			sd.addDialogCheckBox("Use a non-parametric test (Mann-Whitney U or Wilcoxon signed-rank) rather than a t-test?", "Use a non-parametric test", false)
+			sd.addDialogDataInput("Random sample size; leave blank to use all valid grid cells", "Sample Size (blank for whole image):", "", true, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 4) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile1 = args[0]
+			String inputFile2 = args[1]
+			boolean paired = args[2].toLowerCase().contains("paired")
+			boolean nonparametric = Boolean.parseBoolean(args[3])
+			int sampleSize = 0
+			if (args.length > 4 && !args[4].trim().isEmpty() && !args[4].toLowerCase().equals("not specified")) {
+				sampleSize = (int)Double.parseDouble(args[4])
+			}
+
+			WhiteboxRaster image1 = new WhiteboxRaster(inputFile1, "r")
+			int rows1 = image1.getNumberRows()
+			int cols1 = image1.getNumberColumns()
+			double nodata1 = image1.getNoDataValue()
+
+			WhiteboxRaster image2 = new WhiteboxRaster(inputFile2, "r")
+			int rows2 = image2.getNumberRows()
+			int cols2 = image2.getNumberColumns()
+			double nodata2 = image2.getNoDataValue()
+
+			if (paired && (rows1 != rows2 || cols1 != cols2)) {
+				pluginHost.showFeedback("The input images must have the same dimensions (rows and columns) for a paired test.")
+				return
+			}
+
+			/* In paired mode, a grid cell is used only if it is valid in
+			 * both images. In two-sample mode, each image's valid cells
+			 * form an independent sample and the images need not overlap.
+			 */
+			double[] x, y
+			double[] data1, data2
+			int progress, oldProgress = -1
+			if (paired) {
+				int n = 0
+				for (int row = 0; row < rows1; row++) {
+					data1 = image1.getRowValues(row)
+					data2 = image2.getRowValues(row)
+					for (int col = 0; col < cols1; col++) {
+						if (data1[col] != nodata1 && data2[col] != nodata2) {
+							n++
+						}
+					}
+				}
+				x = new double[n]
+				y = new double[n]
+				int i = 0
+				for (int row = 0; row < rows1; row++) {
+					data1 = image1.getRowValues(row)
+					data2 = image2.getRowValues(row)
+					for (int col = 0; col < cols1; col++) {
+						if (data1[col] != nodata1 && data2[col] != nodata2) {
+							x[i] = data1[col]
+							y[i] = data2[col]
+							i++
+						}
+					}
+					progress = (int)(100f * row / rows1)
+					if (progress > oldProgress) {
+						pluginHost.updateProgress("Reading data:", progress)
+						oldProgress = progress
+						// check to see if the user has requested a cancellation
+						if (pluginHost.isRequestForOperationCancelSet()) {
+							pluginHost.showFeedback("Operation cancelled")
+							return
+						}
+					}
+				}
+				if (sampleSize > 0 && sampleSize < n) {
+					int[] indices = randomSample(n, sampleSize)
+					double[] xs = new double[sampleSize]
+					double[] ys = new double[sampleSize]
+					for (int k = 0; k < sampleSize; k++) {
+						xs[k] = x[indices[k]]
+						ys[k] = y[indices[k]]
+					}
+					x = xs
+					y = ys
+				}
+			} else {
+				x = readValidValues(image1, sampleSize)
+				y = readValidValues(image2, sampleSize)
+			}
+			String shortName1 = image1.getShortHeaderFile()
+			String shortName2 = image2.getShortHeaderFile()
+			image1.close()
+			image2.close()
+
+			int n1 = x.length
+			int n2 = y.length
+			if (n1 < 2 || n2 < 2) {
+				pluginHost.showFeedback("Each sample must contain at least two valid grid cells.")
+				return
+			}
+
+			double mean1 = mean(x)
+			double mean2 = mean(y)
+			double sd1 = Math.sqrt(variance(x, mean1))
+			double sd2 = Math.sqrt(variance(y, mean2))
+			double median1 = median(x)
+			double median2 = median(y)
+
+			String testName
+			String statName
+			double statistic
+			String dfString = null
+			String zString = null
+			double pValue
+			String nullHypothesis
+			DecimalFormat df = new DecimalFormat("###,###,###,##0.000")
+			DecimalFormat df2 = new DecimalFormat("###,###,###,###")
+			NormalDistribution normal = new NormalDistribution()
+
+			if (paired && !nonparametric) {
+				testName = "Paired-sample t-test"
+				statName = "t"
+				double[] d = new double[n1]
+				for (int i = 0; i < n1; i++) {
+					d[i] = x[i] - y[i]
+				}
+				double meanD = mean(d)
+				double sdD = Math.sqrt(variance(d, meanD))
+				if (sdD == 0) {
+					pluginHost.showFeedback("The t-test cannot be performed because the differences between the paired grid cells are constant.")
+					return
+				}
+				statistic = meanD / (sdD / Math.sqrt(n1))
+				double dof = n1 - 1
+				pValue = 2.0 * (1.0 - new TDistribution(dof).cumulativeProbability(Math.abs(statistic)))
+				dfString = df2.format(dof)
+				nullHypothesis = "the mean difference between the co-located grid cells of the two images is zero"
+			} else if (!paired && !nonparametric) {
+				// Welch's t-test, which does not assume equal variances
+				testName = "Two-sample t-test (Welch)"
+				statName = "t"
+				double v1 = sd1 * sd1 / n1
+				double v2 = sd2 * sd2 / n2
+				if (v1 + v2 == 0) {
+					pluginHost.showFeedback("The t-test cannot be performed because both samples are constant.")
+					return
+				}
+				statistic = (mean1 - mean2) / Math.sqrt(v1 + v2)
+				double dof = (v1 + v2) * (v1 + v2) / (v1 * v1 / (n1 - 1) + v2 * v2 / (n2 - 1))
+				pValue = 2.0 * (1.0 - new TDistribution(dof).cumulativeProbability(Math.abs(statistic)))
+				dfString = df.format(dof)
+				nullHypothesis = "the two images have equal mean values"
+			} else if (paired) {
+				/* Wilcoxon signed-rank test. Zero differences are dropped
+				 * and the absolute differences are ranked, with tied
+				 * values assigned their average rank. W is the sum of the
+				 * ranks of the positive differences.
+				 */
+				testName = "Wilcoxon signed-rank test"
+				statName = "W"
+				int numPos = 0, numNeg = 0
+				for (int i = 0; i < n1; i++) {
+					if (x[i] > y[i]) {
+						numPos++
+					} else if (x[i] < y[i]) {
+						numNeg++
+					}
+				}
+				double[] pos = new double[numPos]
+				double[] neg = new double[numNeg]
+				int a = 0, b = 0
+				for (int i = 0; i < n1; i++) {
+					if (x[i] > y[i]) {
+						pos[a++] = x[i] - y[i]
+					} else if (x[i] < y[i]) {
+						neg[b++] = y[i] - x[i]
+					}
+				}
+				long n = numPos + numNeg
+				if (n < 2) {
+					pluginHost.showFeedback("The test cannot be performed because fewer than two of the paired grid cells differ.")
+					return
+				}
+				double[] rankResult = rankSum(pos, neg)
+				statistic = rankResult[0]
+				double expected = n * (n + 1) / 4.0
+				double var = n * (n + 1) * (2 * n + 1) / 24.0 - rankResult[1] / 48.0
+				double z = (Math.abs(statistic - expected) - 0.5) / Math.sqrt(var)
+				z = Math.max(z, 0) * Math.signum(statistic - expected)
+				pValue = 2.0 * (1.0 - normal.cumulativeProbability(Math.abs(z)))
+				zString = df.format(z)
+				nullHypothesis = "the differences between the co-located grid cells of the two images are symmetrically distributed about zero"
+			} else {
+				/* Mann-Whitney U test. The two samples are ranked
+				 * together, with tied values assigned their average rank,
+				 * and U is calculated from the rank sum of sample 1.
+				 */
+				testName = "Mann-Whitney U test"
+				statName = "U"
+				double[] rankResult = rankSum(x, y)
+				statistic = rankResult[0] - n1 * (n1 + 1.0) / 2.0
+				double n = (double)n1 + n2
+				double expected = n1 * (double)n2 / 2.0
+				double var = n1 * (double)n2 / 12.0 * ((n + 1) - rankResult[1] / (n * (n - 1)))
+				if (var <= 0) {
+					pluginHost.showFeedback("The test cannot be performed because all of the values are identical.")
+					return
+				}
+				double z = (Math.abs(statistic - expected) - 0.5) / Math.sqrt(var)
+				z = Math.max(z, 0) * Math.signum(statistic - expected)
+				pValue = 2.0 * (1.0 - normal.cumulativeProbability(Math.abs(z)))
+				zString = df.format(z)
+				nullHypothesis = "values drawn from the two images are equally likely to be larger than one another"
+			}
+
+			StringBuilder ret = new StringBuilder()
+			ret.append("<!DOCTYPE html>")
+			ret.append('<html lang="en">')
+
+			ret.append("<head>")
+			ret.append("<title>Raster Statistical Test</title>").append("\n")
+
+			ret.append("<style  type=\"text/css\">")
+			ret.append("table {margin-left: 15px;} ")
+			ret.append("h1 {font-size: 14pt; margin-left: 15px; margin-right: 15px; text-align: center; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;} ")
+			ret.append("p {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ")
+			ret.append("table {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;}")
+			ret.append("table th {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #dedede; }")
+			ret.append("table td {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #ffffff; }")
+			ret.append("caption {font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ")
+			ret.append(".numberCell { text-align: right; }")
+			ret.append("</style></head>").append("\n")
+			ret.append("<body><h1>${testName}</h1>").append("\n")
+
+			ret.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">").append("\n")
+			ret.append("<caption>Sample Summary</caption>")
+			ret.append("<tr><th>Image</th><th>N</th><th>Mean</th><th>Std. Dev.</th><th>Median</th></tr>")
+			ret.append("<tr><td>${shortName1}</td><td class=\"numberCell\">${df2.format(n1)}</td>")
+			ret.append("<td class=\"numberCell\">${df.format(mean1)}</td><td class=\"numberCell\">${df.format(sd1)}</td>")
+			ret.append("<td class=\"numberCell\">${df.format(median1)}</td></tr>").append("\n")
+			ret.append("<tr><td>${shortName2}</td><td class=\"numberCell\">${df2.format(n2)}</td>")
+			ret.append("<td class=\"numberCell\">${df.format(mean2)}</td><td class=\"numberCell\">${df.format(sd2)}</td>")
+			ret.append("<td class=\"numberCell\">${df.format(median2)}</td></tr>").append("\n")
+			ret.append("</table></p>")
+
+			ret.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">").append("\n")
+			ret.append("<caption>Test Results</caption>")
+			ret.append("<tr><td>Test Statistic (${statName})</td><td class=\"numberCell\">${df.format(statistic)}</td></tr>")
+			if (dfString != null) {
+				ret.append("<tr><td>Degrees of Freedom</td><td class=\"numberCell\">${dfString}</td></tr>")
+			}
+			if (zString != null) {
+				ret.append("<tr><td>Normal Approximation (z)</td><td class=\"numberCell\">${zString}</td></tr>")
+			}
+			String pString = pValue >= 0.001 ? df.format(pValue) : "&lt;0.001"
+			ret.append("<tr><td>Significance (two-tailed p-value)</td><td class=\"numberCell\">${pString}</td></tr>")
+			ret.append("</table></p>").append("\n")
+
+			if (pValue < 0.05) {
+				ret.append("<p>The test REJECTS the null hypothesis that ${nullHypothesis} (at the 0.05 level).</p>")
+			} else {
+				ret.append("<p>The test FAILS TO REJECT the null hypothesis that ${nullHypothesis} (at the 0.05 level).</p>")
+			}
+			if (sampleSize > 0) {
+				ret.append("<p>The test was performed on a random sample of up to ${df2.format(sampleSize)} grid cells")
+				ret.append(paired ? ".</p>" : " from each image.</p>")
+			}
+			ret.append("<p>Caveat: Given a sufficiently large sample, extremely small and non-notable differences can be found to be statistically significant, ")
+			ret.append("and statistical significance says nothing about the practical significance of a difference. ")
+			ret.append("Spatial autocorrelation also means that neighbouring grid cells are not independent observations.</p>")
+			ret.append("</body></html>")
+
+			pluginHost.returnData(ret.toString())
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@CompileStatic
+	private double[] readValidValues(WhiteboxRaster image, int sampleSize) {
+		int rows = image.getNumberRows()
+		int cols = image.getNumberColumns()
+		double nodata = image.getNoDataValue()
+		double[] data
+		int n = 0
+		for (int row = 0; row < rows; row++) {
+			data = image.getRowValues(row)
+			for (int col = 0; col < cols; col++) {
+				if (data[col] != nodata) {
+					n++
+				}
+			}
+		}
+		double[] ret = new double[n]
+		int i = 0
+		for (int row = 0; row < rows; row++) {
+			data = image.getRowValues(row)
+			for (int col = 0; col < cols; col++) {
+				if (data[col] != nodata) {
+					ret[i++] = data[col]
+				}
+			}
+		}
+		if (sampleSize > 0 && sampleSize < n) {
+			int[] indices = randomSample(n, sampleSize)
+			double[] sample = new double[sampleSize]
+			for (int k = 0; k < sampleSize; k++) {
+				sample[k] = ret[indices[k]]
+			}
+			return sample
+		}
+		return ret
+	}
+
+	// selects sampleSize of the indices 0 to n - 1, without replacement
+	@CompileStatic
+	private static int[] randomSample(int n, int sampleSize) {
+		Random generator = new Random()
+		int[] indices = new int[n]
+		for (int i = 0; i < n; i++) {
+			indices[i] = i
+		}
+		for (int i = 0; i < sampleSize; i++) {
+			int j = i + generator.nextInt(n - i)
+			int tmp = indices[i]
+			indices[i] = indices[j]
+			indices[j] = tmp
+		}
+		return Arrays.copyOf(indices, sampleSize)
+	}
+
+	/* Ranks the values of a and b together, assigning tied values their
+	 * average rank. Returns the sum of the ranks of a and the tie
+	 * correction term, sum(t^3 - t), over all groups of t tied values.
+	 */
+	@CompileStatic
+	private static double[] rankSum(double[] a, double[] b) {
+		double[] sa = Arrays.copyOf(a, a.length)
+		double[] sb = Arrays.copyOf(b, b.length)
+		Arrays.sort(sa)
+		Arrays.sort(sb)
+		int i = 0, j = 0
+		long rank = 0
+		double sumA = 0
+		double tieSum = 0
+		while (i < sa.length || j < sb.length) {
+			double v
+			if (j >= sb.length || (i < sa.length && sa[i] <= sb[j])) {
+				v = sa[i]
+			} else {
+				v = sb[j]
+			}
+			long countA = 0, countB = 0
+			while (i < sa.length && sa[i] == v) {
+				countA++
+				i++
+			}
+			while (j < sb.length && sb[j] == v) {
+				countB++
+				j++
+			}
+			long t = countA + countB
+			double averageRank = rank + (t + 1) / 2.0
+			sumA += countA * averageRank
+			tieSum += (double)t * t * t - t
+			rank += t
+		}
+		return [sumA, tieSum] as double[]
+	}
+
+	@CompileStatic
+	private static double mean(double[] values) {
+		double sum = 0
+		for (double v : values) {
+			sum += v
+		}
+		return sum / values.length
+	}
+
+	@CompileStatic
+	private static double variance(double[] values, double mean) {
+		double sum = 0
+		for (double v : values) {
+			sum += (v - mean) * (v - mean)
+		}
+		return sum / (values.length - 1)
+	}
+
+	@CompileStatic
+	private static double median(double[] values) {
+		double[] sorted = Arrays.copyOf(values, values.length)
+		Arrays.sort(sorted)
+		int n = sorted.length
+		if (n % 2 == 1) {
+			return sorted[(int)(n / 2)]
+		}
+		return (sorted[(int)(n / 2) - 1] + sorted[(int)(n / 2)]) / 2.0
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new RasterStatisticalTest(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,258 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.FileSystems
+import java.nio.file.PathMatcher
+import java.nio.file.Paths
+import java.util.logging.Level
+import whitebox.interfaces.WhiteboxPluginHost
+import whiteboxgis.WhiteboxGui
+import whitebox.ui.plugin_dialog.*
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+
+def name = "RunPluginOnFiles"
+def descriptiveName = "Run Plugin On Files"
+def description = "Runs a plugin tool once for each file matching a wildcard pattern"
+def toolboxes = ["topmost"]
+
+public class RunPluginOnFiles implements ActionListener {
+    private WhiteboxPluginHost pluginHost
+    private ScriptDialog sd
+    private String descriptiveName
+
+    public RunPluginOnFiles(WhiteboxPluginHost pluginHost,
+        String[] args, def name, def descriptiveName) {
+        this.pluginHost = pluginHost
+        this.descriptiveName = descriptiveName
+
+        if (args.length > 0) {
+            execute(args)
+        } else {
+            // Create a dialog for this tool to collect user-specified
+            // tool parameters.
+            sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+            // Specifying the help file will display the html help
+            // file in the help pane. This file should be be located
+            // in the help directory and have the same name as the
+            // class, with an html extension.
+            sd.setHelpFile(name)
+
+            // Specifying the source file allows the 'view code'
+            // button on the tool dialog to be displayed.
+            def pathSep = File.separator
+            def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+            sd.setSourceFile(scriptFile)
+
+            List plugNames = pluginHost.returnPluginList()
+            String[] plugs = new String[plugNames.size()]
+            int p = 0
+            plugNames.each() {
+                plugs[p] = it
+                p++
+            }
+            // add some components to the dialog
+            sd.addDialogComboBox("Which plugin tool should be run?", "Plugin:", plugs, 0)
+            sd.addDialogDataInput("Enter the input files as a directory and a wildcard file name, e.g. /data/tiles/*.dep", "Input Files (wildcard pattern):", "", false, false)
+            sd.addDialogDataInput("Enter the suffix that is appended to each input file name to create the output file name, e.g. _filtered", "Output File Suffix:", "_output", false, false)
+            sd.addDialogDataInput("Enter the plugin's parameters, separated by commas, using {input} and {output} in place of the input and output file names. Leave blank if the plugin's only parameters are the input and output files.", "Plugin Parameters (optional):", "{input}, {output}", false, true)
+            sd.addDialogCheckBox("Suppress return data", "Suppress return data?", true)
+
+            // resize the dialog to the standard size and display it
+            sd.setSize(800, 400)
+            sd.visible = true
+        }
+    }
+
+    @Override
+    public void actionPerformed(ActionEvent event) {
+        if (event.getActionCommand().equals("ok")) {
+            final def args = sd.collectParameters()
+            sd.dispose()
+            final Runnable r = new Runnable() {
+                @Override
+                public void run() {
+                    execute(args)
+                }
+            }
+            final Thread t = new Thread(r)
+            t.start()
+        }
+    }
+
+    private void execute(String[] args) {
+        if (args.length < 3) {
+            pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+            return
+        }
+        String pluginName = args[0]
+        String pattern = args[1].trim()
+        String suffix = args[2].trim()
+        String paramString = "{input}, {output}"
+        if (args.length > 3 && !args[3].trim().isEmpty() &&
+                !args[3].toLowerCase().contains("not specified")) {
+            paramString = args[3]
+        }
+        boolean suppressReturns = true
+        if (args.length > 4) {
+            suppressReturns = Boolean.parseBoolean(args[4])
+        }
+
+        if (suffix.isEmpty()) {
+            pluginHost.showFeedback("An output file suffix must be specified so that the input files are not overwritten.")
+            return
+        }
+        String[] params = paramString.replace("\"", "").split(",")
+        for (int j in 0..(params.length - 1)) {
+            params[j] = params[j].trim()
+        }
+        if (!params.any { it.contains("{input}") }) {
+            pluginHost.showFeedback("The plugin parameters must contain {input}.")
+            return
+        }
+        boolean hasOutput = params.any { it.contains("{output}") }
+
+        try {
+            List<File> files = findFiles(pattern)
+            // don't process the outputs of an earlier run with the same suffix
+            files = files.findAll { !baseName(it.getName()).endsWith(suffix) }
+            if (files.isEmpty()) {
+                pluginHost.showFeedback("No files match the pattern ${pattern}.")
+                return
+            }
+
+            // Suppressing returns also suppresses the feedback messages of the
+            // individual runs, which would otherwise interrupt the batch.
+            if (suppressReturns && pluginHost instanceof WhiteboxGui) {
+                ((WhiteboxGui)pluginHost).isPluginReturnDataSuppressed(true)
+            }
+
+            int numFiles = files.size()
+            List<String> failures = new ArrayList<>()
+            StringBuilder report = new StringBuilder()
+            report.append("RUN PLUGIN ON FILES\n\n")
+            report.append("Plugin:\t${pluginName}\n")
+            report.append("Input pattern:\t${pattern}\n")
+            report.append("Files matched:\t${numFiles}\n\n")
+            for (int i in 0..(numFiles - 1)) {
+                File inputFile = files.get(i)
+                String inputName = inputFile.getAbsolutePath()
+                String name = inputFile.getName()
+                String outputName = new File(inputFile.getParentFile(),
+                    baseName(name) + suffix + extension(name)).getAbsolutePath()
+                String[] pluginArgs = new String[params.length]
+                for (int j in 0..(params.length - 1)) {
+                    pluginArgs[j] = params[j].replace("{input}", inputName).replace("{output}", outputName)
+                }
+
+                pluginHost.updateProgress("Processing file ${i + 1} of ${numFiles} (${name}):", (int)(100f * i / numFiles))
+
+                long startTime = System.currentTimeMillis()
+                boolean success = true
+                String message = ""
+                try {
+                    pluginHost.runPlugin(pluginName, pluginArgs, false)
+                    if (hasOutput) {
+                        File outFile = new File(outputName)
+                        // allow for file systems that record modification
+                        // times with a resolution of a second or two
+                        if (!outFile.exists() || outFile.lastModified() < startTime - 2000) {
+                            success = false
+                            message = "no output was created"
+                        }
+                    }
+                } catch (Exception e) {
+                    success = false
+                    message = e.getMessage()
+                    pluginHost.logException("Error in " + descriptiveName + " processing " + inputName, e)
+                }
+                if (success) {
+                    report.append("${name}:\tsucceeded\n")
+                } else {
+                    failures.add(name)
+                    report.append("${name}:\tFAILED (${message})\n")
+                    pluginHost.logMessage(Level.WARNING, "${descriptiveName}: ${pluginName} failed for ${inputName} (${message})")
+                }
+
+                // check to see if the user has requested a cancellation
+                if (pluginHost.isRequestForOperationCancelSet()) {
+                    restoreReturns()
+                    report.append("\nOperation cancelled after ${i + 1} of ${numFiles} files.\n")
+                    pluginHost.returnData(report.toString())
+                    pluginHost.showFeedback("Operation cancelled")
+                    return
+                }
+            }
+
+            restoreReturns()
+            int numSuccessful = numFiles - failures.size()
+            report.append("\n${numSuccessful} of ${numFiles} files were processed successfully.\n")
+            pluginHost.returnData(report.toString())
+            if (failures.isEmpty()) {
+                pluginHost.showFeedback("Operations Complete. All of the operations were successful.")
+            } else {
+                pluginHost.showFeedback("Operations Complete. ${numSuccessful} of the ${numFiles} operations were successful. Failed: " + failures.join(", "))
+            }
+        } catch (Exception e) {
+            restoreReturns()
+            pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+            pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+            restoreReturns()
+            pluginHost.updateProgress("Progress:", 0)
+        }
+    }
+
+    private void restoreReturns() {
+        if (pluginHost instanceof WhiteboxGui) {
+            ((WhiteboxGui)pluginHost).isPluginReturnDataSuppressed(false)
+        }
+    }
+
+    // Returns the files in the pattern's directory whose names match its
+    // final component, which may contain the wildcards * and ?, sorted by name.
+    private List<File> findFiles(String pattern) {
+        File patternFile = new File(pattern)
+        File dir = patternFile.getParentFile()
+        if (dir == null) {
+            dir = new File(pluginHost.getWorkingDirectory())
+        }
+        if (!dir.isDirectory()) {
+            return []
+        }
+        PathMatcher matcher = FileSystems.getDefault().getPathMatcher("glob:" + patternFile.getName())
+        List<File> files = dir.listFiles().findAll { it.isFile() && matcher.matches(Paths.get(it.getName())) }
+        return files.sort { it.getName() }
+    }
+
+    private static String baseName(String fileName) {
+        int dot = fileName.lastIndexOf('.')
+        return dot > 0 ? fileName.substring(0, dot) : fileName
+    }
+
+    private static String extension(String fileName) {
+        int dot = fileName.lastIndexOf('.')
+        return dot > 0 ? fileName.substring(dot) : ""
+    }
+}
+
+if (args == null) {
+    pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+    def f = new RunPluginOnFiles(pluginHost, args, name, descriptiveName)
+}
@@ -30,8 +30,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.ScriptDialog
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -88,7 +86,7 @@ public class StandardDeviationOfElevation implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-			Date start1 = new Date()
+			long start1 = System.nanoTime()
 		
 			if (args.length < 4) {
 				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
@@ -327,8 +325,7 @@ public class StandardDeviationOfElevation implements ActionListener {
 			image.close()
 			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
 	        output.addMetadataEntry("Created on " + new Date())
-			Date stop1 = new Date()
-			TimeDuration td1 = TimeCategory.minus(stop1, start1)
+			String td1 = StringUtilities.formatElapsedTime(System.nanoTime() - start1)
 			output.addMetadataEntry("Total elapsed time: $td1")
 			output.close()
 	
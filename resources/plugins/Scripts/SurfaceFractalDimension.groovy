@@ -0,0 +1,261 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "SurfaceFractalDimension"
+def descriptiveName = "Surface Fractal Dimension"
+def description = "Estimates the local surface fractal dimension (roughness) of a DEM using the variogram method."
+def toolboxes = ["SecondaryTerrainAttributes"]
+
+public class SurfaceFractalDimension implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public SurfaceFractalDimension(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input DEM file", "Input DEM:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output file", "Output Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Window size (cells); must be an odd number of at least 5", "Window Size (cells):", "11", true, false)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 3) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			int windowSize = (int)Double.parseDouble(args[2])
+			if (windowSize < 5) {
+				windowSize = 5
+			}
+			if (windowSize % 2 == 0) {
+				windowSize++
+			}
+			int radius = (int)(windowSize / 2)
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+
+			double[][] z = new double[rows][]
+			for (int row = 0; row < rows; row++) {
+				z[row] = image.getRowValues(row)
+			}
+
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw",
+			  inputFile, DataType.FLOAT, nodata)
+			output.setPreferredPalette("spectrum.pal")
+			output.setDataScale(DataScale.CONTINUOUS)
+
+			/* The fractal dimension is estimated from the slope (b) of the
+			 * log-log plot of semivariance against lag distance, for lags
+			 * of 1 to radius cells measured along rows and columns within
+			 * the window, such that D = 3 - b / 2. The slope is unaffected
+			 * by a constant scaling of the lags, so lags are measured in
+			 * cells. Windows with fewer than half of their cells valid,
+			 * or with too few non-zero semivariances to fit a line, are
+			 * assigned NoData.
+			 */
+			int minValidCells = (int)Math.ceil(windowSize * windowSize / 2.0)
+			double[] lnLag = new double[radius + 1]
+			for (int h = 1; h <= radius; h++) {
+				lnLag[h] = Math.log(h)
+			}
+			double zc, z1, z2, diff, sumSqrDiff, gamma
+			double sumX, sumY, sumXY, sumXX, slope
+			int numValid, numPairs, numLags, numFlatLags
+			int r1, r2, c1, c2, row, col, r, c, h
+			int progress, oldProgress = -1
+			for (row = 0; row < rows; row++) {
+				for (col = 0; col < cols; col++) {
+					zc = z[row][col]
+					if (zc == nodata) {
+						continue
+					}
+					r1 = Math.max(row - radius, 0)
+					r2 = Math.min(row + radius, rows - 1)
+					c1 = Math.max(col - radius, 0)
+					c2 = Math.min(col + radius, cols - 1)
+					numValid = 0
+					for (r = r1; r <= r2; r++) {
+						for (c = c1; c <= c2; c++) {
+							if (z[r][c] != nodata) {
+								numValid++
+							}
+						}
+					}
+					if (numValid < minValidCells) {
+						continue
+					}
+
+					sumX = 0; sumY = 0; sumXY = 0; sumXX = 0
+					numLags = 0
+					numFlatLags = 0
+					for (h = 1; h <= radius; h++) {
+						sumSqrDiff = 0
+						numPairs = 0
+						for (r = r1; r <= r2; r++) {
+							for (c = c1; c <= c2; c++) {
+								z1 = z[r][c]
+								if (z1 == nodata) {
+									continue
+								}
+								if (c + h <= c2) {
+									z2 = z[r][c + h]
+									if (z2 != nodata) {
+										diff = z2 - z1
+										sumSqrDiff += diff * diff
+										numPairs++
+									}
+								}
+								if (r + h <= r2) {
+									z2 = z[r + h][c]
+									if (z2 != nodata) {
+										diff = z2 - z1
+										sumSqrDiff += diff * diff
+										numPairs++
+									}
+								}
+							}
+						}
+						if (numPairs == 0) {
+							continue
+						}
+						gamma = sumSqrDiff / (2.0 * numPairs)
+						if (gamma > 0) {
+							sumX += lnLag[h]
+							sumY += Math.log(gamma)
+							sumXY += lnLag[h] * Math.log(gamma)
+							sumXX += lnLag[h] * lnLag[h]
+							numLags++
+						} else {
+							numFlatLags++
+						}
+					}
+
+					if (numLags >= 2) {
+						slope = (numLags * sumXY - sumX * sumY) / (numLags * sumXX - sumX * sumX)
+						output.setValue(row, col, 3.0 - slope / 2.0)
+					} else if (numLags == 0 && numFlatLags > 0) {
+						// a perfectly flat window is a smooth surface
+						output.setValue(row, col, 2.0)
+					}
+				}
+				progress = (int)(100f * row / rows)
+				if (progress > oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			image.close()
+
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Window size: " + windowSize)
+			output.close()
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new SurfaceFractalDimension(pluginHost, args, name, descriptiveName)
+}
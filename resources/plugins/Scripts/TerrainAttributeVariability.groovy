@@ -0,0 +1,402 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "TerrainAttributeVariability"
+def descriptiveName = "Terrain Attribute Variability"
+def description = "Calculates the local coefficient of variation of a terrain attribute within a circular window."
+def toolboxes = ["TerrainAnalysis"]
+
+public class TerrainAttributeVariability implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	private static final String[] ATTRIBUTES = ["elevation", "slope", "aspect", "curvature"]
+
+	public TerrainAttributeVariability(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input DEM file", "Input DEM File:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output raster file", "Output Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Diameter of the circular window, in grid cells (an odd number)", "Filter Size (cells):", "11", true, false)
+			sd.addDialogComboBox("Terrain attribute whose variability is measured", "Terrain Attribute:", ["elevation", "slope", "aspect", "curvature"], 1)
+			sd.addDialogDataInput("Z conversion factor, used when the vertical and horizontal units differ", "Z Conversion Factor:", "1.0", true, true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 4) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			int filterSize = 11
+			if (isSpecified(args[2])) {
+				filterSize = (int)Math.round(Double.parseDouble(args[2].trim()))
+			}
+			if (filterSize < 3) {
+				pluginHost.showFeedback("The filter size must be at least three grid cells.")
+				return
+			}
+			// the window must have a central cell
+			if (filterSize % 2 == 0) {
+				filterSize++
+			}
+			String attribute = args[3].trim().toLowerCase()
+			if (!ATTRIBUTES.contains(attribute)) {
+				pluginHost.showFeedback("Unrecognized terrain attribute. It must be one of elevation, slope, aspect, or curvature.")
+				return
+			}
+			double zFactor = 1.0
+			if (args.length > 4 && isSpecified(args[4])) {
+				zFactor = Double.parseDouble(args[4].trim())
+			}
+
+			WhiteboxRaster dem = new WhiteboxRaster(inputFile, "r")
+			int rows = dem.getNumberRows()
+			int cols = dem.getNumberColumns()
+			double nodata = dem.getNoDataValue()
+			double cellSizeX = dem.getCellSizeX()
+			double cellSizeY = dem.getCellSizeY()
+
+			if (dem.getXYUnits().toLowerCase().contains("deg") ||
+					dem.getProjection().toLowerCase().contains("geog")) {
+				// calculate a new z-conversion factor
+				double midLat = (dem.getNorth() + dem.getSouth()) / 2.0
+				if (midLat <= 90 && midLat >= -90) {
+					zFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)))
+				}
+			}
+
+			double[][] z = new double[rows][]
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				z[row] = dem.getRowValues(row)
+				int progress = (int)(20f * row / Math.max(rows - 1, 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Reading data:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			double[][] values = z
+			if (!attribute.equals("elevation")) {
+				values = calculateAttribute(z, nodata, attribute, cellSizeX, cellSizeY, zFactor)
+				if (values == null) {
+					return
+				}
+			}
+
+			// Aspect is summarized by the sines and cosines of its values,
+			// other attributes by their values and squared values. The values
+			// are offset by their overall mean, which reduces round-off error
+			// in the variance.
+			boolean isCircular = attribute.equals("aspect")
+			double ref = 0
+			if (!isCircular) {
+				double total = 0
+				long n = 0
+				for (int row = 0; row < rows; row++) {
+					for (int col = 0; col < cols; col++) {
+						if (values[row][col] != nodata) {
+							total += values[row][col]
+							n++
+						}
+					}
+				}
+				if (n > 0) {
+					ref = total / n
+				}
+			}
+
+			// the half-width, in columns, of each row of the circular window
+			int radius = filterSize >> 1
+			int[] halfWidths = new int[2 * radius + 1]
+			for (int dy = -radius; dy <= radius; dy++) {
+				halfWidths[dy + radius] = (int)Math.floor(Math.sqrt((double)(radius * radius - dy * dy)))
+			}
+
+			// the window sums of each row are calculated from cumulative sums
+			// along the row, which are kept for the rows within the window
+			int numBuffered = 2 * radius + 1
+			double[][] sumA = new double[numBuffered][cols + 1]
+			double[][] sumB = new double[numBuffered][cols + 1]
+			int[][] counts = new int[numBuffered][cols + 1]
+			int nextRow = 0
+
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw", inputFile, DataType.FLOAT, nodata)
+			output.setPreferredPalette("spectrum.pal")
+			output.setDataScale(DataScale.CONTINUOUS)
+			output.setZUnits("percent")
+
+			double[] outData = new double[cols]
+			for (int row = 0; row < rows; row++) {
+				// calculate the cumulative sums of the rows entering the window
+				while (nextRow < rows && nextRow <= row + radius) {
+					int b = nextRow % numBuffered
+					double[] a = sumA[b]
+					double[] s = sumB[b]
+					int[] c = counts[b]
+					for (int col = 0; col < cols; col++) {
+						double v = values[nextRow][col]
+						if (v != nodata) {
+							double va, vb
+							if (isCircular) {
+								va = Math.sin(Math.toRadians(v))
+								vb = Math.cos(Math.toRadians(v))
+							} else {
+								va = v - ref
+								vb = va * va
+							}
+							a[col + 1] = a[col] + va
+							s[col + 1] = s[col] + vb
+							c[col + 1] = c[col] + 1
+						} else {
+							a[col + 1] = a[col]
+							s[col + 1] = s[col]
+							c[col + 1] = c[col]
+						}
+					}
+					nextRow++
+				}
+
+				for (int col = 0; col < cols; col++) {
+					if (z[row][col] == nodata) {
+						outData[col] = nodata
+						continue
+					}
+					double totalA = 0
+					double totalB = 0
+					int n = 0
+					for (int dy = -radius; dy <= radius; dy++) {
+						int r = row + dy
+						if (r < 0 || r >= rows) {
+							continue
+						}
+						int b = r % numBuffered
+						int w = halfWidths[dy + radius]
+						int c0 = Math.max(col - w, 0)
+						int c1 = Math.min(col + w, cols - 1) + 1
+						totalA += sumA[b][c1] - sumA[b][c0]
+						totalB += sumB[b][c1] - sumB[b][c0]
+						n += counts[b][c1] - counts[b][c0]
+					}
+					if (n == 0) {
+						outData[col] = nodata
+					} else if (isCircular) {
+						// the circular variance, one minus the mean resultant length
+						double meanResultantLength = Math.sqrt(totalA * totalA + totalB * totalB) / n
+						outData[col] = (1.0 - Math.min(meanResultantLength, 1.0)) * 100.0
+					} else {
+						double meanOffset = totalA / n
+						double mean = meanOffset + ref
+						double variance = totalB / n - meanOffset * meanOffset
+						if (variance < 0) {
+							variance = 0
+						}
+						if (variance == 0) {
+							outData[col] = 0
+						} else if (mean != 0) {
+							outData[col] = Math.sqrt(variance) / Math.abs(mean) * 100.0
+						} else {
+							outData[col] = nodata
+						}
+					}
+				}
+				output.setRowValues(row, outData)
+
+				int progress = (int)(40 + 60f * row / Math.max(rows - 1, 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress("Calculating variability:", progress)
+					oldProgress = progress
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			dem.close()
+
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Terrain attribute: " + attribute)
+			output.addMetadataEntry("Filter size: " + filterSize + " cells (circular)")
+			if (isCircular) {
+				output.addMetadataEntry("Values are the circular variance of aspect, as a percentage.")
+			} else {
+				output.addMetadataEntry("Values are the coefficient of variation (standard deviation / mean), as a percentage.")
+			}
+			output.close()
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	// Calculates slope (degrees), aspect (degrees clockwise from north) or
+	// total curvature from the 3 x 3 neighbourhood of each cell. Neighbours
+	// that are outside of the DEM or NoData take the value of the centre
+	// cell. Flat cells have no aspect and are assigned NoData.
+	@CompileStatic
+	private double[][] calculateAttribute(double[][] z, double nodata, String attribute,
+			double cellSizeX, double cellSizeY, double zFactor) {
+		int rows = z.length
+		int cols = z[0].length
+		int[] dX = [1, 1, 1, 0, -1, -1, -1, 0] as int[]
+		int[] dY = [-1, 0, 1, 1, 1, 0, -1, -1] as int[]
+		double[] N = new double[8]
+		double eightGridResX = 8 * cellSizeX
+		double eightGridResY = 8 * cellSizeY
+		double cellSizeXSquared = cellSizeX * cellSizeX
+		double cellSizeYSquared = cellSizeY * cellSizeY
+		double fourCellSizeXY = 4 * cellSizeX * cellSizeY
+		double[][] result = new double[rows][cols]
+		int oldProgress = -1
+		for (int row = 0; row < rows; row++) {
+			for (int col = 0; col < cols; col++) {
+				if (z[row][col] == nodata) {
+					result[row][col] = nodata
+					continue
+				}
+				double zc = z[row][col] * zFactor
+				for (int i = 0; i < 8; i++) {
+					int rn = row + dY[i]
+					int cn = col + dX[i]
+					if (rn >= 0 && rn < rows && cn >= 0 && cn < cols && z[rn][cn] != nodata) {
+						N[i] = z[rn][cn] * zFactor
+					} else {
+						N[i] = zc
+					}
+				}
+				if (attribute.equals("curvature")) {
+					double zxx = (N[1] - 2 * zc + N[5]) / cellSizeXSquared
+					double zyy = (N[7] - 2 * zc + N[3]) / cellSizeYSquared
+					double zxy = (-N[6] + N[0] + N[4] - N[2]) / fourCellSizeXY
+					result[row][col] = zxx * zxx + 2 * zxy * zxy + zyy * zyy
+				} else {
+					double fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / eightGridResY
+					double fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / eightGridResX
+					if (attribute.equals("slope")) {
+						result[row][col] = Math.toDegrees(Math.atan(Math.sqrt(fx * fx + fy * fy)))
+					} else if (fx != 0 || fy != 0) {
+						double aspect = Math.toDegrees(Math.atan2(-fx, -fy))
+						result[row][col] = aspect < 0 ? aspect + 360.0d : aspect
+					} else {
+						result[row][col] = nodata
+					}
+				}
+			}
+			int progress = (int)(20 + 20f * row / Math.max(rows - 1, 1))
+			if (progress != oldProgress) {
+				pluginHost.updateProgress("Calculating " + attribute + ":", progress)
+				oldProgress = progress
+				if (pluginHost.isRequestForOperationCancelSet()) {
+					pluginHost.showFeedback("Operation cancelled")
+					return null
+				}
+			}
+		}
+		return result
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new TerrainAttributeVariability(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,331 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "Threshold"
+def descriptiveName = "Threshold"
+def description = "Creates a Boolean mask by comparing a raster against a constant, a second raster, or a range."
+def toolboxes = ["ComparisonOps"]
+
+public class Threshold implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	public Threshold(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input raster file", "Input Raster File:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Output raster file", "Output Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogComboBox("Comparison operator", "Operator:", ["<", "<=", ">", ">=", "==", "!=", "between"], 2)
+			sd.addDialogFile("Threshold value, or the lower bound for 'between' (raster or constant value)", "Threshold / Lower Bound (Raster File Or Constant Value):", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogFile("Upper bound, used only by 'between' (raster or constant value)", "Upper Bound (Raster File Or Constant Value):", "open", "Raster Files (*.dep), DEP", true, true)
+			sd.addDialogCheckBox("Is the lower bound included in the range?", "Include Lower Bound", true)
+			sd.addDialogCheckBox("Is the upper bound included in the range?", "Include Upper Bound", true)
+			sd.addDialogCheckBox("Assign false cells a value of zero (otherwise NoData)?", "Zero Background", true)
+			sd.addDialogComboBox("Output data type", "Output Data Type:", ["smallest integer", "float"], 0)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		WhiteboxRaster image = null
+		WhiteboxRaster lowerImage = null
+		WhiteboxRaster upperImage = null
+		WhiteboxRaster output = null
+		try {
+			if (args.length < 4) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputFile = args[1]
+			String operator = args[2].trim().toLowerCase()
+			if (operator.equals("=")) {
+				operator = "=="
+			} else if (operator.equals("<>")) {
+				operator = "!="
+			}
+			if (!(operator in ["<", "<=", ">", ">=", "==", "!=", "between"])) {
+				pluginHost.showFeedback("Unrecognized operator '${args[2]}'. The operator must be one of <, <=, >, >=, ==, !=, or between.")
+				return
+			}
+			boolean between = operator == "between"
+			if (between && (args.length < 5 || !isSpecified(args[4]))) {
+				pluginHost.showFeedback("An upper bound must be specified for the 'between' operator.")
+				return
+			}
+			boolean lowerInclusive = args.length <= 5 || Boolean.parseBoolean(args[5])
+			boolean upperInclusive = args.length <= 6 || Boolean.parseBoolean(args[6])
+			boolean zeroBack = args.length <= 7 || Boolean.parseBoolean(args[7])
+			boolean floatOutput = args.length > 8 && args[8].toLowerCase().contains("float")
+
+			image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+
+			/* Each bound is either a raster, which must have the same
+			 * dimensions as the input, or a constant value.
+			 */
+			double lowerConst = 0
+			double upperConst = 0
+			if (new File(args[3]).exists()) {
+				lowerImage = new WhiteboxRaster(args[3], "r")
+				if (lowerImage.getNumberRows() != rows || lowerImage.getNumberColumns() != cols) {
+					pluginHost.showFeedback("The input images must have the same dimensions.")
+					return
+				}
+			} else {
+				Double value = parseConstant(args[3])
+				if (value == null) {
+					pluginHost.showFeedback("'${args[3]}' is neither an existing raster nor a number.")
+					return
+				}
+				lowerConst = value
+			}
+			if (between) {
+				if (new File(args[4]).exists()) {
+					upperImage = new WhiteboxRaster(args[4], "r")
+					if (upperImage.getNumberRows() != rows || upperImage.getNumberColumns() != cols) {
+						pluginHost.showFeedback("The input images must have the same dimensions.")
+						return
+					}
+				} else {
+					Double value = parseConstant(args[4])
+					if (value == null) {
+						pluginHost.showFeedback("'${args[4]}' is neither an existing raster nor a number.")
+						return
+					}
+					upperConst = value
+				}
+			}
+
+			/* The output NoData value is that of the input, unless it
+			 * would be confused with a Boolean value. The output is
+			 * stored using the smallest integer type that can hold it;
+			 * non-integer or very large NoData values require floats.
+			 */
+			double outNodata = (nodata == 0 || nodata == 1) ? -32768d : nodata
+			DataType dataType = floatOutput ? DataType.FLOAT : smallestIntegerType(outNodata)
+			double background = zeroBack ? 0d : outNodata
+
+			output = new WhiteboxRaster(outputFile, "rw",
+			  inputFile, dataType, outNodata)
+			output.setNoDataValue(outNodata)
+			output.setPreferredPalette("black_white.pal")
+			output.setDataScale(DataScale.BOOLEAN)
+
+			double[] data, lowerData, upperData
+			double z, lower, upper
+			boolean result
+			int oldProgress = -1
+			for (int row = 0; row < rows; row++) {
+				data = image.getRowValues(row)
+				lowerData = lowerImage != null ? lowerImage.getRowValues(row) : null
+				upperData = upperImage != null ? upperImage.getRowValues(row) : null
+				for (int col = 0; col < cols; col++) {
+					z = data[col]
+					lower = lowerConst
+					upper = upperConst
+					if (lowerData != null) {
+						lower = lowerData[col]
+						if (lower == lowerImage.getNoDataValue()) {
+							z = nodata
+						}
+					}
+					if (upperData != null) {
+						upper = upperData[col]
+						if (upper == upperImage.getNoDataValue()) {
+							z = nodata
+						}
+					}
+					if (z == nodata) {
+						output.setValue(row, col, outNodata)
+						continue
+					}
+					switch (operator) {
+						case "<":
+							result = z < lower
+							break
+						case "<=":
+							result = z <= lower
+							break
+						case ">":
+							result = z > lower
+							break
+						case ">=":
+							result = z >= lower
+							break
+						case "==":
+							result = z == lower
+							break
+						case "!=":
+							result = z != lower
+							break
+						default: // between
+							result = (lowerInclusive ? z >= lower : z > lower) &&
+							  (upperInclusive ? z <= upper : z < upper)
+							break
+					}
+					output.setValue(row, col, result ? 1d : background)
+				}
+				int progress = (int)(100f * row / (rows - 1))
+				if (progress != oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+
+			String condition
+			String lowerStr = lowerImage != null ? new File(args[3]).getName() : String.valueOf(lowerConst)
+			if (between) {
+				String upperStr = upperImage != null ? new File(args[4]).getName() : String.valueOf(upperConst)
+				condition = lowerStr + (lowerInclusive ? " <= " : " < ") + "value" + (upperInclusive ? " <= " : " < ") + upperStr
+			} else {
+				condition = "value " + operator + " " + lowerStr
+			}
+			output.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+			output.addMetadataEntry("Created on " + new Date())
+			output.addMetadataEntry("Condition: " + condition)
+			output.addMetadataEntry("Background: " + (zeroBack ? "zero" : "NoData"))
+			output.close()
+			output = null
+
+			// display the output image
+			pluginHost.returnData(outputFile)
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	for (WhiteboxRaster input : [image, lowerImage, upperImage]) {
+        		if (input != null) {
+        			input.close()
+        		}
+        	}
+        	if (output != null) {
+        		// an output that was not completed is discarded, leaving
+        		// any existing raster of the same name untouched
+        		output.isTemporaryFile = true
+        		output.close()
+        	}
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	// Returns the smallest integer type that can store 0, 1 and the NoData value.
+	@CompileStatic
+	private static DataType smallestIntegerType(double nodata) {
+		if (nodata != Math.floor(nodata)) {
+			return DataType.FLOAT
+		} else if (nodata >= 0 && nodata <= 255) {
+			return DataType.BYTE
+		} else if (nodata >= Short.MIN_VALUE && nodata <= Short.MAX_VALUE) {
+			return DataType.INTEGER
+		}
+		return DataType.FLOAT
+	}
+
+	// Returns the constant value of a bound that is not a raster, or null if
+	// it is not a number. The dialog may have prefixed the working directory.
+	@CompileStatic
+	private static Double parseConstant(String arg) {
+		try {
+			return Double.parseDouble(new File(arg).getName().replace(".dep", "").trim())
+		} catch (NumberFormatException e) {
+			return null
+		}
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new Threshold(pluginHost, args, name, descriptiveName)
+}
@@ -0,0 +1,355 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.awt.event.ActionListener
+import java.awt.event.ActionEvent
+import java.text.DecimalFormat
+import java.util.Date
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.ui.plugin_dialog.*
+import groovy.transform.CompileStatic
+
+// The following four variables are required for this
+// script to be integrated into the tool tree panel.
+// Comment them out if you want to remove the script.
+def name = "TileRaster"
+def descriptiveName = "Tile Raster"
+def description = "Splits a raster into a regular grid of overlapping tiles."
+def toolboxes = ["GISTools"]
+
+public class TileRaster implements ActionListener {
+	private WhiteboxPluginHost pluginHost
+	private ScriptDialog sd;
+	private String descriptiveName
+
+	private static final String DEFAULT_TEMPLATE = "{name}_{row}_{col}"
+
+	public TileRaster(WhiteboxPluginHost pluginHost,
+		String[] args, def name, def descriptiveName) {
+		this.pluginHost = pluginHost
+		this.descriptiveName = descriptiveName
+
+		if (args.length > 0) {
+			execute(args)
+		} else {
+			// Create a dialog for this tool to collect user-specified
+			// tool parameters.
+			sd = new ScriptDialog(pluginHost, descriptiveName, this)
+
+			// Specifying the help file will display the html help
+			// file in the help pane. This file should be be located
+			// in the help directory and have the same name as the
+			// class, with an html extension.
+			sd.setHelpFile(name)
+
+			// Specifying the source file allows the 'view code'
+			// button on the tool dialog to be displayed.
+			def pathSep = File.separator
+			def scriptFile = pluginHost.getResourcesDirectory() + "plugins" + pathSep + "Scripts" + pathSep + name + ".groovy"
+			sd.setSourceFile(scriptFile)
+
+			// add some components to the dialog
+			sd.addDialogFile("Input raster file", "Input Raster File:", "open", "Raster Files (*.dep), DEP", true, false)
+			sd.addDialogDataInput("Output directory for the tiles and tile index (blank for the working directory)", "Output Directory (optional):", "", false, true)
+			sd.addDialogDataInput("Tile width, excluding the overlap", "Tile Width:", "1000", true, false)
+			sd.addDialogDataInput("Tile height, excluding the overlap", "Tile Height:", "1000", true, false)
+			sd.addDialogComboBox("Units of the tile width and height", "Tile Size Units:", ["cells", "map units"], 0)
+			sd.addDialogDataInput("Width of the overlap added to each side of a tile, in cells", "Overlap Width (cells):", "0", true, false)
+			sd.addDialogDataInput("Output file name template; {name}, {row} and {col} are replaced by the input name and the tile row and column", "File Name Template:", DEFAULT_TEMPLATE, false, true)
+			sd.addDialogCheckBox("Skip tiles that contain only NoData values?", "Skip NoData Tiles", true)
+
+			// resize the dialog to the standard size and display it
+			sd.setSize(800, 400)
+			sd.visible = true
+		}
+	}
+
+	// The CompileStatic annotation can be used to significantly
+	// improve the performance of a Groovy script to nearly
+	// that of native Java code.
+	@CompileStatic
+	private void execute(String[] args) {
+		try {
+			if (args.length < 4) {
+				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
+				return
+			}
+			// read the input parameters
+			String inputFile = args[0]
+			String outputDir = isSpecified(args[1]) ? args[1].trim() : pluginHost.getWorkingDirectory()
+			if (!outputDir.endsWith(File.separator)) {
+				outputDir += File.separator
+			}
+			double tileWidth = Double.parseDouble(args[2].trim())
+			double tileHeight = Double.parseDouble(args[3].trim())
+			boolean mapUnits = args.length > 4 && args[4].toLowerCase().contains("map")
+			int overlap = 0
+			if (args.length > 5 && isSpecified(args[5])) {
+				overlap = Integer.parseInt(args[5].trim())
+			}
+			String template = DEFAULT_TEMPLATE
+			if (args.length > 6 && isSpecified(args[6])) {
+				template = args[6].trim()
+			}
+			boolean skipNoData = args.length <= 7 || Boolean.parseBoolean(args[7])
+			if (tileWidth <= 0 || tileHeight <= 0) {
+				pluginHost.showFeedback("The tile width and height must be greater than zero.")
+				return
+			}
+			if (overlap < 0) {
+				pluginHost.showFeedback("The overlap width must not be negative.")
+				return
+			}
+			if (!template.contains("{row}") || !template.contains("{col}")) {
+				pluginHost.showFeedback("The file name template must contain both the {row} and {col} placeholders.")
+				return
+			}
+			File dir = new File(outputDir)
+			if (!dir.exists() && !dir.mkdirs()) {
+				pluginHost.showFeedback("The output directory could not be created.")
+				return
+			}
+
+			WhiteboxRaster image = new WhiteboxRaster(inputFile, "r")
+			double nodata = image.getNoDataValue()
+			int rows = image.getNumberRows()
+			int cols = image.getNumberColumns()
+			double north = image.getNorth()
+			double west = image.getWest()
+			// signed cell dimensions, so that the orientation of the grid is preserved
+			double resX = (image.getEast() - west) / cols
+			double resY = (north - image.getSouth()) / rows
+
+			int tileCols, tileRows
+			if (mapUnits) {
+				tileCols = (int)Math.max(1, Math.round(tileWidth / Math.abs(resX)))
+				tileRows = (int)Math.max(1, Math.round(tileHeight / Math.abs(resY)))
+			} else {
+				tileCols = (int)tileWidth
+				tileRows = (int)tileHeight
+				if (tileCols != tileWidth || tileRows != tileHeight || tileCols < 1 || tileRows < 1) {
+					pluginHost.showFeedback("Tile sizes in cells must be positive whole numbers.")
+					return
+				}
+			}
+			int numTileRows = (int)Math.ceil((double)rows / tileRows)
+			int numTileCols = (int)Math.ceil((double)cols / tileCols)
+
+			DataType dataType = image.getDataType()
+			if (dataType == DataType.I32) {
+				// 32-bit integer rasters cannot be written
+				dataType = DataType.FLOAT
+			}
+			String baseName = new File(inputFile).getName().replace(".dep", "")
+			int rowDigits = String.valueOf(numTileRows).length()
+			int colDigits = String.valueOf(numTileCols).length()
+
+			StringBuilder csv = new StringBuilder()
+			csv.append("FILE,TILE_ROW,TILE_COL,START_ROW,START_COL,ROWS,COLS,")
+			csv.append("NORTH,SOUTH,EAST,WEST,CORE_NORTH,CORE_SOUTH,CORE_EAST,CORE_WEST\n")
+			StringBuilder json = new StringBuilder()
+			json.append("{\n\"type\": \"FeatureCollection\",\n\"features\": [\n")
+			DecimalFormat df = new DecimalFormat("0.0##########")
+
+			int numTiles = 0
+			int numSkipped = 0
+			int oldProgress = -1
+			for (int tr = 0; tr < numTileRows; tr++) {
+				// the core and buffered rows of this band of tiles
+				int coreRow0 = tr * tileRows
+				int coreRow1 = Math.min(coreRow0 + tileRows, rows)
+				int row0 = Math.max(coreRow0 - overlap, 0)
+				int row1 = Math.min(coreRow1 + overlap, rows)
+
+				// read only the rows of the source that this band requires
+				double[][] strip = new double[row1 - row0][]
+				for (int row = row0; row < row1; row++) {
+					strip[row - row0] = image.getRowValues(row)
+				}
+
+				for (int tc = 0; tc < numTileCols; tc++) {
+					int coreCol0 = tc * tileCols
+					int coreCol1 = Math.min(coreCol0 + tileCols, cols)
+					int col0 = Math.max(coreCol0 - overlap, 0)
+					int col1 = Math.min(coreCol1 + overlap, cols)
+
+					if (skipNoData && !hasValidData(strip, col0, col1, nodata)) {
+						numSkipped++
+						continue
+					}
+
+					String tileName = template.replace("{name}", baseName)
+					  .replace("{row}", pad(tr + 1, rowDigits))
+					  .replace("{col}", pad(tc + 1, colDigits))
+					if (!tileName.toLowerCase().endsWith(".dep")) {
+						tileName += ".dep"
+					}
+					String tileFile = outputDir + tileName
+
+					/* The edges are calculated from the integer cell
+					 * offsets rather than accumulated tile sizes, so
+					 * that tiles align exactly with the source grid.
+					 */
+					double tileNorth = north - row0 * resY
+					double tileSouth = north - row1 * resY
+					double tileWest = west + col0 * resX
+					double tileEast = west + col1 * resX
+					int nr = row1 - row0
+					int nc = col1 - col0
+					WhiteboxRaster tile = new WhiteboxRaster(tileFile, tileNorth, tileSouth,
+					  tileEast, tileWest, nr, nc, image.getDataScale(), dataType, nodata, nodata)
+					tile.setPreferredPalette(image.getPreferredPalette())
+					tile.setZUnits(image.getZUnits())
+					tile.setXYUnits(image.getXYUnits())
+					tile.setProjection(image.getProjection())
+					for (int row = row0; row < row1; row++) {
+						double[] data = new double[nc]
+						System.arraycopy(strip[row - row0], col0, data, 0, nc)
+						tile.setRowValues(row - row0, data)
+					}
+					tile.addMetadataEntry("Created by the " + descriptiveName + " tool.")
+					tile.addMetadataEntry("Created on " + new Date())
+					tile.addMetadataEntry("Source: " + new File(inputFile).getName() +
+					  ", rows " + row0 + " to " + (row1 - 1) + ", columns " + col0 + " to " + (col1 - 1))
+					tile.close()
+
+					double coreNorth = north - coreRow0 * resY
+					double coreSouth = north - coreRow1 * resY
+					double coreWest = west + coreCol0 * resX
+					double coreEast = west + coreCol1 * resX
+					csv.append(tileName).append(",").append(tr + 1).append(",").append(tc + 1).append(",")
+					csv.append(row0).append(",").append(col0).append(",").append(nr).append(",").append(nc).append(",")
+					csv.append(df.format(tileNorth)).append(",").append(df.format(tileSouth)).append(",")
+					csv.append(df.format(tileEast)).append(",").append(df.format(tileWest)).append(",")
+					csv.append(df.format(coreNorth)).append(",").append(df.format(coreSouth)).append(",")
+					csv.append(df.format(coreEast)).append(",").append(df.format(coreWest)).append("\n")
+
+					if (numTiles > 0) {
+						json.append(",\n")
+					}
+					json.append("{\"type\": \"Feature\", \"properties\": {")
+					json.append("\"file\": \"").append(tileName.replace("\\", "\\\\").replace("\"", "\\\"")).append("\", ")
+					json.append("\"tile_row\": ").append(tr + 1).append(", \"tile_col\": ").append(tc + 1).append(", ")
+					json.append("\"start_row\": ").append(row0).append(", \"start_col\": ").append(col0).append(", ")
+					json.append("\"rows\": ").append(nr).append(", \"cols\": ").append(nc).append(", ")
+					json.append("\"core_north\": ").append(df.format(coreNorth)).append(", ")
+					json.append("\"core_south\": ").append(df.format(coreSouth)).append(", ")
+					json.append("\"core_east\": ").append(df.format(coreEast)).append(", ")
+					json.append("\"core_west\": ").append(df.format(coreWest)).append("}, ")
+					json.append("\"geometry\": {\"type\": \"Polygon\", \"coordinates\": [[")
+					json.append("[").append(df.format(tileWest)).append(", ").append(df.format(tileNorth)).append("], ")
+					json.append("[").append(df.format(tileEast)).append(", ").append(df.format(tileNorth)).append("], ")
+					json.append("[").append(df.format(tileEast)).append(", ").append(df.format(tileSouth)).append("], ")
+					json.append("[").append(df.format(tileWest)).append(", ").append(df.format(tileSouth)).append("], ")
+					json.append("[").append(df.format(tileWest)).append(", ").append(df.format(tileNorth)).append("]")
+					json.append("]]}}")
+
+					numTiles++
+				}
+
+				int progress = (int)(100f * (tr + 1) / numTileRows)
+				if (progress != oldProgress) {
+					pluginHost.updateProgress(progress)
+					oldProgress = progress
+					// check to see if the user has requested a cancellation
+					if (pluginHost.isRequestForOperationCancelSet()) {
+						pluginHost.showFeedback("Operation cancelled")
+						return
+					}
+				}
+			}
+			json.append("\n]\n}\n")
+			image.close()
+
+			File csvFile = new File(outputDir + baseName + "_tiles.csv")
+			csvFile.text = csv.toString()
+			File jsonFile = new File(outputDir + baseName + "_tiles.geojson")
+			jsonFile.text = json.toString()
+
+			StringBuilder sb = new StringBuilder()
+			sb.append("${numTiles} tiles were created (${numTileRows} rows by ${numTileCols} columns of ${tileRows} x ${tileCols} cells")
+			if (overlap > 0) {
+				sb.append(", with an overlap of ${overlap} cells")
+			}
+			sb.append(").")
+			if (numSkipped > 0) {
+				sb.append("\n${numSkipped} tiles containing only NoData values were skipped.")
+			}
+			sb.append("\nThe tile index was written to ${csvFile.getName()} and ${jsonFile.getName()}.")
+			pluginHost.showFeedback(sb.toString())
+
+		} catch (OutOfMemoryError oe) {
+            pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
+	    } catch (Exception e) {
+	        pluginHost.showFeedback("An error has occurred during operation. See log file for details.")
+	        pluginHost.logException("Error in " + descriptiveName, e)
+        } finally {
+        	// reset the progress bar
+        	pluginHost.updateProgress(0)
+        }
+	}
+
+	@CompileStatic
+	private static boolean hasValidData(double[][] strip, int col0, int col1, double nodata) {
+		for (int i = 0; i < strip.length; i++) {
+			for (int col = col0; col < col1; col++) {
+				if (strip[i][col] != nodata) {
+					return true
+				}
+			}
+		}
+		return false
+	}
+
+	@CompileStatic
+	private static String pad(int value, int digits) {
+		String str = String.valueOf(value)
+		while (str.length() < digits) {
+			str = "0" + str
+		}
+		return str
+	}
+
+	@CompileStatic
+	private static boolean isSpecified(String arg) {
+		return arg != null && !arg.trim().isEmpty() && !arg.toLowerCase().equals("not specified")
+	}
+
+	@Override
+    public void actionPerformed(ActionEvent event) {
+    	if (event.getActionCommand().equals("ok")) {
+    		final def args = sd.collectParameters()
+			sd.dispose()
+			final Runnable r = new Runnable() {
+            	@Override
+            	public void run() {
+                	execute(args)
+            	}
+        	}
+        	final Thread t = new Thread(r)
+        	t.start()
+    	}
+    }
+}
+
+if (args == null) {
+	pluginHost.showFeedback("Plugin arguments not set.")
+} else {
+	def f = new TileRaster(pluginHost, args, name, descriptiveName)
+}
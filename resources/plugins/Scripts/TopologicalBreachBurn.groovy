@@ -40,8 +40,6 @@ import whitebox.structures.IntArray2D
 import whitebox.structures.BoundingBox;
 import whitebox.structures.KdTree;
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -256,7 +254,7 @@ public class TopologicalBreachBurn implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-			Date start = new Date()
+			long start = System.nanoTime()
 			
 	  		int progress, oldProgress, col, row, colN, rowN, numPits, r, c
 	  		int numSolvedCells = 0
@@ -1309,8 +1307,7 @@ public class TopologicalBreachBurn implements ActionListener {
 			outputRaster.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        outputRaster.addMetadataEntry("Created on " + new Date())
-			Date stop = new Date()
-			TimeDuration td = TimeCategory.minus(stop, start)
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			outputRaster.addMetadataEntry("Elapsed time: $td")
 			outputRaster.close()
 	
@@ -1380,8 +1377,7 @@ public class TopologicalBreachBurn implements ActionListener {
 			outputFA.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        outputFA.addMetadataEntry("Created on " + new Date())
-			stop = new Date()
-			td = TimeCategory.minus(stop, start)
+			td = StringUtilities.formatElapsedTime(System.nanoTime() - start)
 			outputFA.addMetadataEntry("Elapsed time: $td")
 			outputFA.close()
 
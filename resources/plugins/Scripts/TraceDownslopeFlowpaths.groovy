@@ -24,6 +24,7 @@ import java.util.ArrayList
 import whitebox.interfaces.WhiteboxPluginHost
 import whitebox.geospatialfiles.WhiteboxRaster
 import whitebox.geospatialfiles.WhiteboxRasterInfo
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
 import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
 import whitebox.geospatialfiles.ShapeFile
 import whitebox.geospatialfiles.shapefile.*
@@ -36,7 +37,7 @@ import groovy.transform.CompileStatic
 // Comment them out if you want to remove the script.
 def name = "TraceDownslopeFlowpaths"
 def descriptiveName = "Trace Downslope Flowpaths"
-def description = "Traces downslope flowpaths from one or more target sites."
+def description = "Traces downslope flowpaths from one or more target sites to the grid edge, an outlet or a stream."
 def toolboxes = ["HydroTools", "FlowpathTAs"]
 
 public class TraceDownslopeFlowpaths implements ActionListener {
@@ -73,6 +74,7 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 			sd.addDialogFile("Input seed points file", "Input Seed Points File:", "open", "Whitebox Files (*.dep; *.shp), DEP, SHP", true, false)
             sd.addDialogFile("Output file", "Output Raster File:", "save", "Raster Files (*.dep), DEP", true, false)
 			sd.addDialogCheckBox("Set the background value in the output image to NoData?", "Set background to NoData?", true)
+			sd.addDialogFile("Input streams raster (optional); flowpaths end at the first stream cell", "Input Streams Raster (optional):", "open", "Raster Files (*.dep), DEP", true, true)
 			
 			// resize the dialog to the standard size and display it
 			sd.setSize(800, 400)
@@ -86,15 +88,10 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 	@CompileStatic
 	private void execute(String[] args) {
 		try {
-	  		int progress, oldProgress, colN, rowN, c
-	  		int[] dX = [ 1, 1, 1, 0, -1, -1, -1, 0 ]
-			int[] dY = [ -1, 0, 1, 1, 1, 0, -1, -1 ]
-        	double flowDir
-        	boolean flag
-        	double outputValue = 1.0
-        	final double LnOf2 = 0.693147180559945;
+	  		int progress, oldProgress
+        	double outputValue
         	
-			if (args.length != 4) {
+			if (args.length != 4 && args.length != 5) {
 				pluginHost.showFeedback("Incorrect number of arguments given to tool.")
 				return
 			}
@@ -103,13 +100,25 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 			String inputSeedFile = args[1]
 			String outputFile = args[2]
 			boolean backgroundNoData = Boolean.parseBoolean(args[3])
+			String streamsFile = args.length > 4 ? args[4] : "not specified"
 			
-			// read the input image
-			WhiteboxRaster pntr = new WhiteboxRaster(inputFile, "r")
+			// read the input image; the pointer is read along the flowpaths, in scattered order
+			WhiteboxRaster pntr = WhiteboxRaster.openMemoryMapped(inputFile)
 			double nodata = pntr.getNoDataValue()
 			int rows = pntr.getNumberRows()
 			int cols = pntr.getNumberColumns()
 
+			// read the optional stream network, at which flowpaths terminate
+			WhiteboxRaster streams = null
+			if (streamsFile != null && !streamsFile.toLowerCase().contains("not specified")) {
+				streams = new WhiteboxRaster(streamsFile, "r")
+				if (rows != streams.getNumberRows() ||
+				  cols != streams.getNumberColumns()) {
+					pluginHost.showFeedback("Error: The streams file must have the same dimensions as the D8 pointer file.")
+					return;
+				}
+			}
+
 			double backgroundValue
 			if (backgroundNoData) {
 				backgroundValue = nodata
@@ -117,14 +126,10 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 				backgroundValue = 0.0
 			}
 
-			WhiteboxRaster output
-			if (backgroundNoData) {
-				output = new WhiteboxRaster(outputFile, "rw", 
-  		  	     inputFile, DataType.FLOAT, nodata)
-			} else {
-				output = new WhiteboxRaster(outputFile, "rw", 
-  		  	     inputFile, DataType.FLOAT, 0.0)
-			}
+			WhiteboxRaster output = new WhiteboxRaster(outputFile, "rw", 
+  		  	     inputFile, DataType.INTEGER, backgroundValue)
+  		  	output.setPreferredPalette("qual.pal")
+  		  	output.setDataScale(DataScale.CATEGORICAL)
 
 			if (inputSeedFile.toLowerCase().endsWith(".dep")) {
 				// does the seed file have the same dimensions as the pointer?
@@ -142,29 +147,8 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 	  				for (int col in 0..(cols - 1)) {
 	  					if (data[col] != seedNoData && data[col] > 0) {
 	  						// it's a seed point
-	  						outputValue = data[col]
-	  						output.setValue(row, col, outputValue)
-	  						flag = false;
-	                        colN = col;
-	                        rowN = row;
-	                        while (!flag) {
-	                            // find its downslope neighbour
-	                            flowDir = pntr.getValue(rowN, colN);
-	                            if (flowDir > 0 && flowDir != nodata) {
-	                            	//move x and y accordingly
-	                                c = (int) (Math.log(flowDir) / LnOf2);
-	                                colN += dX[c];
-	                                rowN += dY[c];
-	                                //if the new cell already has a value in the output, use that as the outletID
-	                                if (output.getValue(rowN, colN) != backgroundValue) {
-	                                    flag = true;
-	                                } else {
-	                                	output.setValue(rowN, colN, outputValue)
-	                                }
-	                            } else {
-	                                flag = true;
-	                            }
-	                        }
+	  						outputValue = Math.round(data[col])
+	  						traceFlowpath(row, col, outputValue, pntr, streams, output, backgroundValue)
 	  					}
 	  				}
 	  				progress = (int)(100f * row / rows)
@@ -193,7 +177,6 @@ public class TraceDownslopeFlowpaths implements ActionListener {
             	oldProgress = -1;
 				int row, col;
             	int numPoints = input.getNumberOfRecords();
-//            	double[][] points = new double[numPoints][2]
             	double[][] point
 
             	for (ShapeFileRecord record : input.records) {
@@ -206,30 +189,7 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 
 					if (row >= 0 && row < rows && col >= 0 && col < cols) {
 						// it's a seed point
-						output.setValue(row, col, outputValue)
-  						flag = false;
-                        colN = col;
-                        rowN = row;
-                        
-						while (!flag) {
-                            // find its downslope neighbour
-                            flowDir = pntr.getValue(rowN, colN);
-                            if (flowDir > 0 && flowDir != nodata) {
-                            	//move x and y accordingly
-                                c = (int) (Math.log(flowDir) / LnOf2);
-                                colN += dX[c];
-                                rowN += dY[c];
-
-                                //if the new cell already has a value in the output, use that as the outletID
-                                if (output.getValue(rowN, colN) != backgroundValue) {
-                                    flag = true;
-                                } else {
-                                	output.setValue(rowN, colN, outputValue)
-                                }
-                            } else {
-                                flag = true;
-                            }
-                        }
+						traceFlowpath(row, col, outputValue, pntr, streams, output, backgroundValue)
 					}
 					
 					progress = (int)(100f * featureNum / (numPoints - 1))
@@ -256,6 +216,10 @@ public class TraceDownslopeFlowpaths implements ActionListener {
 			output.addMetadataEntry("Created by the "
 	                    + descriptiveName + " tool.")
 	        output.addMetadataEntry("Created on " + new Date())
+	        if (streams != null) {
+	        	output.addMetadataEntry("Streams: " + streams.getShortHeaderFile())
+	        	streams.close()
+	        }
 			output.close()
 	
 			// display the output image
@@ -271,6 +235,53 @@ public class TraceDownslopeFlowpaths implements ActionListener {
         	pluginHost.updateProgress(0)
         }
 	}
+
+	// Marks the flowpath from a seed cell with the seed's value. The path 
+	// ends at a cell with an undefined flow direction, at the edge of the 
+	// grid, at the first stream cell that it reaches, or where it joins a 
+	// flowpath that has already been traced.
+	@CompileStatic
+	private void traceFlowpath(int row, int col, double outputValue, 
+		WhiteboxRaster pntr, WhiteboxRaster streams, WhiteboxRaster output, 
+		double backgroundValue) {
+		int[] dX = [ 1, 1, 1, 0, -1, -1, -1, 0 ]
+		int[] dY = [ -1, 0, 1, 1, 1, 0, -1, -1 ]
+		final double LnOf2 = 0.693147180559945
+		int rows = pntr.getNumberRows()
+		int cols = pntr.getNumberColumns()
+		double nodata = pntr.getNoDataValue()
+		double streamsNoData = streams != null ? streams.getNoDataValue() : 0.0d
+		double flowDir, streamValue
+		int c
+		int colN = col
+		int rowN = row
+		output.setValue(rowN, colN, outputValue)
+		while (true) {
+			if (streams != null) {
+				streamValue = streams.getValue(rowN, colN)
+				if (streamValue > 0 && streamValue != streamsNoData) {
+					break
+				}
+			}
+			// find its downslope neighbour
+			flowDir = pntr.getValue(rowN, colN)
+			if (flowDir <= 0 || flowDir == nodata) {
+				break
+			}
+			//move x and y accordingly
+			c = (int) (Math.log(flowDir) / LnOf2)
+			colN += dX[c]
+			rowN += dY[c]
+			if (rowN < 0 || rowN >= rows || colN < 0 || colN >= cols) {
+				break
+			}
+			//if the new cell already has a value in the output, the flowpath has been traced from here
+			if (output.getValue(rowN, colN) != backgroundValue) {
+				break
+			}
+			output.setValue(rowN, colN, outputValue)
+		}
+	}
 	
 	@Override
     public void actionPerformed(ActionEvent event) {
@@ -47,8 +47,6 @@ import whitebox.geospatialfiles.shapefile.*
 import whitebox.ui.plugin_dialog.*
 import whitebox.utilities.StringUtilities
 import groovy.transform.CompileStatic
-import groovy.time.TimeDuration
-import groovy.time.TimeCategory
 
 // The following four variables are required for this 
 // script to be integrated into the tool tree panel. 
@@ -136,7 +134,7 @@ public class TestDepFill implements ActionListener {
 			def paletteName = dem.getPreferredPalette()
 			output.setPreferredPalette(paletteName)
 
-			Date start = new Date()
+			long start = System.nanoTime()
   		  	
 			PriorityQueue<GridCell> pq = new PriorityQueue<GridCell>((2 * rows + 2 * cols) * 2);
 			//Deque<GridCell> q = new LinkedList<GridCell>();
@@ -413,8 +411,7 @@ public class TestDepFill implements ActionListener {
 				}
 			}
 			
-			Date stop = new Date();
-			TimeDuration td = TimeCategory.minus(stop, start);
+			String td = StringUtilities.formatElapsedTime(System.nanoTime() - start);
 			
 			// output the data
             dem.close();
@@ -96,7 +96,7 @@ public class LiDAR_BareEarthDEM implements ActionListener {
 	// that of native Java code.
 	@CompileStatic
 	private void execute(String[] args) {
-		long start = System.currentTimeMillis()  
+		long start = System.nanoTime()
 	  try {
 	  	if (args.length != 7) {
 			pluginHost.showFeedback("Incorrect number of arguments given to tool.")
@@ -326,9 +326,8 @@ public class LiDAR_BareEarthDEM implements ActionListener {
 	    
 		pluginHost.returnData(outputFile);
 
-		long end = System.currentTimeMillis()  
-		double duration = (end - start) / 1000.0
-		pluginHost.showFeedback("Interpolation completed in " + duration + " seconds.")
+		String duration = StringUtilities.formatElapsedTime(System.nanoTime() - start)
+		pluginHost.showFeedback("Interpolation completed in " + duration + ".")
 		
 	  } catch (OutOfMemoryError oe) {
             pluginHost.showFeedback("An out-of-memory error has occurred during operation.")
@@ -9,7 +9,7 @@ import java.net.URL;
 import javax.swing.*;
 import whiteboxgis.WhiteboxGui
 import org.apache.commons.io.FilenameUtils
-import groovy.time.*
+import whitebox.utilities.StringUtilities
 
 // The following lines are necessary for the script 
 // to be recognized as a menu extension.
@@ -17,7 +17,7 @@ parentMenu = "Help"
 menuLabel = "Check For Update"
 
 try {
-	def timeStart = new Date()
+	long timeStart = System.nanoTime()
 	WhiteboxGui wg = (WhiteboxGui)(pluginHost)
 
 	if (wg.currentVersionNumber == null || wg.currentVersionNumber.isEmpty()) {
@@ -66,8 +66,8 @@ try {
 				zipFile.delete()
 			}
 			
-			TimeDuration duration = TimeCategory.minus(new Date(), timeStart)
-			wg.showFeedback("Operation completed in ${duration} seconds.")
+			String duration = StringUtilities.formatElapsedTime(System.nanoTime() - timeStart)
+			wg.showFeedback("Operation completed in ${duration}.")
 	
 			wg.updateProgress("Progress:", 0)
 		} else {
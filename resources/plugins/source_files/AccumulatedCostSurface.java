@@ -0,0 +1,387 @@
+/*
+ * Copyright (C) 2011-2012 Dr. John Lindsay <jlindsay@uoguelph.ca>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.PriorityQueue;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.structures.GridCell;
+
+/**
+ * This tool calculates the least accumulated cost of travelling from any of a group of source cells to each grid cell of a cost surface, with optional impassable barriers and a maximum cost beyond which cells are not reached.
+ * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
+ */
+public class AccumulatedCostSurface implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "AccumulatedCostSurface";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Accumulated Cost Surface";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the least accumulated cost from a group of source "
+                + "cells, around impassable barriers.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "CostTools" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String costHeader = null;
+        String sourceHeader = null;
+        String barrierHeader = null;
+        String outputHeader = null;
+        double maxCost = Double.POSITIVE_INFINITY;
+        boolean costPerUnitDistance = false;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                costHeader = args[i];
+            } else if (i == 1) {
+                sourceHeader = args[i];
+            } else if (i == 2) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    barrierHeader = args[i];
+                }
+            } else if (i == 3) {
+                outputHeader = args[i];
+            } else if (i == 4) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    maxCost = Double.parseDouble(args[i]);
+                }
+            } else if (i == 5) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    costPerUnitDistance = Boolean.parseBoolean(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((costHeader == null) || (sourceHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (!(maxCost > 0)) {
+            showFeedback("The maximum cost must be greater than zero.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster costSurface = new WhiteboxRaster(costHeader, "r");
+            int rows = costSurface.getNumberRows();
+            int cols = costSurface.getNumberColumns();
+            double noData = costSurface.getNoDataValue();
+
+            WhiteboxRaster sourceImage = new WhiteboxRaster(sourceHeader, "r");
+            WhiteboxRaster barrierImage = null;
+            if (barrierHeader != null) {
+                barrierImage = new WhiteboxRaster(barrierHeader, "r");
+            }
+            if (!costSurface.isCoregisteredWith(sourceImage)
+                    || (barrierImage != null && !costSurface.isCoregisteredWith(barrierImage))) {
+                showFeedback("The input images must have the same dimensions and coordinates. Operation cancelled.");
+                costSurface.close();
+                sourceImage.close();
+                if (barrierImage != null) {
+                    barrierImage.close();
+                }
+                return;
+            }
+
+            // The costs are held in memory, with barrier cells and cells of
+            // unknown or negative cost marked as impassable by NaN.
+            double[][] costs = new double[rows][];
+            boolean[][] isSource = new boolean[rows][cols];
+            double sourceNoData = sourceImage.getNoDataValue();
+            double barrierNoData = (barrierImage != null) ? barrierImage.getNoDataValue() : 0;
+            int numSources = 0;
+            int numBarriers = 0;
+            for (int row = 0; row < rows; row++) {
+                costs[row] = costSurface.getRowValues(row);
+                double[] sources = sourceImage.getRowValues(row);
+                double[] barriers = (barrierImage != null) ? barrierImage.getRowValues(row) : null;
+                for (int col = 0; col < cols; col++) {
+                    if (barriers != null && barriers[col] != barrierNoData && barriers[col] > 0) {
+                        costs[row][col] = Double.NaN;
+                        numBarriers++;
+                    } else if (costs[row][col] == noData || costs[row][col] < 0) {
+                        costs[row][col] = Double.NaN;
+                    }
+                    if (sources[col] != sourceNoData && sources[col] > 0 && !Double.isNaN(costs[row][col])) {
+                        isSource[row][col] = true;
+                        numSources++;
+                    }
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress("Reading inputs:", (int) (100f * row / rows));
+            }
+            sourceImage.close();
+            if (barrierImage != null) {
+                barrierImage.close();
+            }
+            if (numSources == 0) {
+                showFeedback("There are no source cells outside of the barriers. Operation cancelled.");
+                costSurface.close();
+                return;
+            }
+
+            // The cost of a step between neighbouring cells is the mean of
+            // their costs, times the distance between their centres in map
+            // units if the costs are per unit distance.
+            int[] dX = new int[]{1, 1, 0, -1, -1, -1, 0, 1};
+            int[] dY = new int[]{0, 1, 1, 1, 0, -1, -1, -1};
+            double[] dist = new double[8];
+            double cellSizeX = costSurface.getCellSizeX();
+            double cellSizeY = costSurface.getCellSizeY();
+            for (int c = 0; c < 8; c++) {
+                dist[c] = costPerUnitDistance
+                        ? Math.sqrt(dX[c] * dX[c] * cellSizeX * cellSizeX + dY[c] * dY[c] * cellSizeY * cellSizeY)
+                        : 1;
+            }
+
+            // Dijkstra's algorithm, starting from every source cell at once.
+            // Cells are only added to the queue if their cost is within the
+            // maximum, so the search stops at the maximum cost.
+            double[][] accumulation = new double[rows][cols];
+            PriorityQueue<GridCell> queue = new PriorityQueue<>();
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    accumulation[row][col] = Double.POSITIVE_INFINITY;
+                    if (isSource[row][col]) {
+                        accumulation[row][col] = 0;
+                        queue.add(new GridCell(row, col, 0, noData, -1));
+                    }
+                }
+            }
+            isSource = null;
+            long numCells = (long) rows * cols;
+            long numDone = 0;
+            int oldProgress = -1;
+            updateProgress("Accumulating cost:", 0);
+            while (!queue.isEmpty()) {
+                GridCell cell = queue.poll();
+                if (cell.z > accumulation[cell.row][cell.col]) {
+                    continue;
+                }
+                double cost1 = costs[cell.row][cell.col];
+                for (int c = 0; c < 8; c++) {
+                    int x = cell.col + dX[c];
+                    int y = cell.row + dY[c];
+                    if (y < 0 || y >= rows || x < 0 || x >= cols || Double.isNaN(costs[y][x])) {
+                        continue;
+                    }
+                    double newCostVal = cell.z + (cost1 + costs[y][x]) / 2 * dist[c];
+                    if (newCostVal < accumulation[y][x] && newCostVal <= maxCost) {
+                        accumulation[y][x] = newCostVal;
+                        queue.add(new GridCell(y, x, newCostVal, noData, -1));
+                    }
+                }
+                numDone++;
+                int progress = (int) (100 * numDone / numCells);
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            // barriers and cells that could not be reached within the
+            // maximum cost are NoData
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", costHeader,
+                    WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("spectrum.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            double[] data = new double[cols];
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    data[col] = (accumulation[row][col] < Double.POSITIVE_INFINITY)
+                            ? accumulation[row][col] : noData;
+                }
+                output.setRowValues(row, data);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress("Saving data:", (int) (100f * row / rows));
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Cost surface: " + costSurface.getShortHeaderFile());
+            output.addMetadataEntry("Number of source cells: " + numSources);
+            if (barrierHeader != null) {
+                output.addMetadataEntry("Number of barrier cells: " + numBarriers);
+            }
+            if (maxCost < Double.POSITIVE_INFINITY) {
+                output.addMetadataEntry("Maximum cost: " + maxCost);
+            }
+            output.addMetadataEntry(costPerUnitDistance ? "Costs are per unit distance"
+                    : "Costs are per cell traversed");
+
+            costSurface.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
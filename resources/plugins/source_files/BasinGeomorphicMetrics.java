@@ -0,0 +1,552 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.util.Map;
+import java.util.TreeMap;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates a suite of geomorphic metrics for each of the drainage
+ * basins in a basins raster, i.e. the drainage density, basin relief, relief
+ * ratio, ruggedness number, mean slope, mean topographic position index,
+ * elongation ratio and circularity ratio. The streams, slopes and TPI values
+ * that the metrics require are calculated internally from the DEM and D8 flow
+ * pointer, in a single pass over each grid, and the metrics are written to a
+ * CSV file with one line per basin.
+ */
+public class BasinGeomorphicMetrics implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    // Constants
+    private static final double LnOf2 = 0.693147180559945;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "BasinGeomorphicMetrics";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Basin Geomorphic Metrics";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Calculates drainage density, relief, shape and other geomorphic metrics for each drainage basin.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"WatershedTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 4) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String demHeader = args[0];
+        String pointerHeader = args[1];
+        String basinsHeader = args[2];
+        String csvFile = args[3];
+        double channelThreshold = 1000;
+        if (args.length > 4 && !args[4].toLowerCase().contains("not specified")) {
+            channelThreshold = Double.parseDouble(args[4]);
+        }
+        int tpiRadius = 5;
+        if (args.length > 5 && !args[5].toLowerCase().contains("not specified")) {
+            tpiRadius = Integer.parseInt(args[5]);
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((demHeader == null) || (pointerHeader == null) || (basinsHeader == null)
+                || (csvFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (channelThreshold <= 0 || tpiRadius < 1) {
+            showFeedback("The channel threshold and TPI radius must be greater than zero.");
+            return;
+        }
+
+        try {
+            int row, col, r, c, i, n, m;
+            double z;
+            int progress, oldProgress = -1;
+            int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+            int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+
+            WhiteboxRaster dem = new WhiteboxRaster(demHeader, "r");
+            int rows = dem.getNumberRows();
+            int cols = dem.getNumberColumns();
+            double noData = dem.getNoDataValue();
+            double cellSizeX = dem.getCellSizeX();
+            double cellSizeY = dem.getCellSizeY();
+            double[] neighbourDist = new double[8];
+            for (i = 0; i < 8; i++) {
+                neighbourDist[i] = Math.sqrt(dX[i] * cellSizeX * dX[i] * cellSizeX
+                        + dY[i] * cellSizeY * dY[i] * cellSizeY);
+            }
+
+            WhiteboxRaster pntr = new WhiteboxRaster(pointerHeader, "r");
+            WhiteboxRaster basins = new WhiteboxRaster(basinsHeader, "r");
+            if (pntr.getNumberRows() != rows || pntr.getNumberColumns() != cols
+                    || basins.getNumberRows() != rows || basins.getNumberColumns() != cols) {
+                showFeedback("The input files must have the same dimensions, i.e. number of "
+                        + "rows and columns.");
+                dem.close();
+                pntr.close();
+                basins.close();
+                return;
+            }
+            double pntrNoData = pntr.getNoDataValue();
+            double basinsNoData = basins.getNoDataValue();
+
+            // Read the inputs into memory. Cells outside of the basins, i.e.
+            // those that are NoData or not positive in the basins raster,
+            // have a basin ID of zero.
+            updateProgress("Reading data:", 0);
+            double[] elevations = new double[rows * cols];
+            int[] basinID = new int[rows * cols];
+            byte[] flowDir = new byte[rows * cols];
+            double[] data, pntrData, basinsData;
+            for (row = 0; row < rows; row++) {
+                data = dem.getRowValues(row);
+                pntrData = pntr.getRowValues(row);
+                basinsData = basins.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    n = row * cols + col;
+                    elevations[n] = data[col];
+                    flowDir[n] = -1;
+                    if (pntrData[col] != pntrNoData && pntrData[col] > 0) {
+                        i = (int) Math.round(Math.log(pntrData[col]) / LnOf2);
+                        if (i > 7 || (1 << i) != pntrData[col]) {
+                            showFeedback("An unexpected value has "
+                                    + "been identified in the pointer "
+                                    + "image. This tool requires a "
+                                    + "pointer grid that has been "
+                                    + "created using either the D8 "
+                                    + "or Rho8 tools.");
+                            return;
+                        }
+                        flowDir[n] = (byte) i;
+                    }
+                    if (data[col] != noData && basinsData[col] != basinsNoData && basinsData[col] > 0) {
+                        basinID[n] = (int) Math.round(basinsData[col]);
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            String demName = dem.getShortHeaderFile();
+            String basinsName = basins.getShortHeaderFile();
+            dem.close();
+            pntr.close();
+            basins.close();
+
+            // D8 flow accumulation, in grid cells, visiting each cell after
+            // all of the cells that drain to it. The streams are the cells
+            // with an accumulation of at least the channel threshold.
+            updateProgress("Calculating flow accumulation:", 0);
+            int[] downslope = new int[rows * cols];
+            int[] numInflowing = new int[rows * cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    n = row * cols + col;
+                    downslope[n] = -1;
+                    if (flowDir[n] >= 0 && elevations[n] != noData) {
+                        r = row + dY[flowDir[n]];
+                        c = col + dX[flowDir[n]];
+                        if (r >= 0 && r < rows && c >= 0 && c < cols && elevations[r * cols + c] != noData) {
+                            downslope[n] = r * cols + c;
+                            numInflowing[downslope[n]]++;
+                        }
+                    }
+                }
+            }
+            double[] accumulation = new double[rows * cols];
+            int[] stack = new int[rows * cols];
+            int stackSize = 0;
+            for (n = 0; n < rows * cols; n++) {
+                if (elevations[n] != noData) {
+                    accumulation[n] = 1;
+                    if (numInflowing[n] == 0) {
+                        stack[stackSize] = n;
+                        stackSize++;
+                    }
+                }
+            }
+            while (stackSize > 0) {
+                stackSize--;
+                n = stack[stackSize];
+                m = downslope[n];
+                if (m >= 0) {
+                    accumulation[m] += accumulation[n];
+                    numInflowing[m]--;
+                    if (numInflowing[m] == 0) {
+                        stack[stackSize] = m;
+                        stackSize++;
+                    }
+                }
+            }
+            stack = null;
+            numInflowing = null;
+            downslope = null;
+
+            // Summed-area tables of the valid elevations and of their number,
+            // for the mean elevation of the TPI neighbourhoods.
+            updateProgress("Calculating TPI:", 0);
+            double[] sumTable = new double[(rows + 1) * (cols + 1)];
+            int[] countTable = new int[(rows + 1) * (cols + 1)];
+            for (row = 0; row < rows; row++) {
+                double rowSum = 0;
+                int rowCount = 0;
+                for (col = 0; col < cols; col++) {
+                    z = elevations[row * cols + col];
+                    if (z != noData) {
+                        rowSum += z;
+                        rowCount++;
+                    }
+                    sumTable[(row + 1) * (cols + 1) + col + 1] = sumTable[row * (cols + 1) + col + 1] + rowSum;
+                    countTable[(row + 1) * (cols + 1) + col + 1] = countTable[row * (cols + 1) + col + 1] + rowCount;
+                }
+            }
+
+            // Accumulate the metrics of each basin.
+            updateProgress("Calculating basin metrics:", 0);
+            oldProgress = -1;
+            TreeMap<Integer, Basin> basinMap = new TreeMap<>();
+            double[] N = new double[8];
+            double fx, fy;
+            final double radToDeg = 180 / Math.PI;
+            int id, row1, row2, col1, col2, count;
+            double sum;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    n = row * cols + col;
+                    id = basinID[n];
+                    if (id == 0) {
+                        continue;
+                    }
+                    Basin basin = basinMap.get(id);
+                    if (basin == null) {
+                        basin = new Basin();
+                        basinMap.put(id, basin);
+                    }
+                    z = elevations[n];
+                    basin.numCells++;
+                    if (z < basin.minZ) {
+                        basin.minZ = z;
+                    }
+                    if (z > basin.maxZ) {
+                        basin.maxZ = z;
+                    }
+                    if (accumulation[n] > basin.outletAccumulation) {
+                        basin.outletAccumulation = accumulation[n];
+                        basin.outletRow = row;
+                        basin.outletCol = col;
+                    }
+
+                    // the length of the stream's flowpath through the cell
+                    if (accumulation[n] >= channelThreshold && flowDir[n] >= 0) {
+                        basin.streamLength += neighbourDist[flowDir[n]];
+                    }
+
+                    // the cell edges that are shared with other basins or
+                    // with cells outside of any basin
+                    for (i = 1; i < 8; i += 2) {
+                        r = row + dY[i];
+                        c = col + dX[i];
+                        if (r < 0 || r >= rows || c < 0 || c >= cols || basinID[r * cols + c] != id) {
+                            basin.perimeter += (dX[i] != 0) ? cellSizeY : cellSizeX;
+                        }
+                    }
+
+                    // slope (Horn's method), with missing neighbours given
+                    // the elevation of the centre cell
+                    for (i = 0; i < 8; i++) {
+                        r = row + dY[i];
+                        c = col + dX[i];
+                        N[i] = z;
+                        if (r >= 0 && r < rows && c >= 0 && c < cols && elevations[r * cols + c] != noData) {
+                            N[i] = elevations[r * cols + c];
+                        }
+                    }
+                    fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / (8 * cellSizeY);
+                    fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / (8 * cellSizeX);
+                    basin.slopeSum += Math.atan(Math.sqrt(fx * fx + fy * fy)) * radToDeg;
+
+                    // topographic position index, the difference between the
+                    // elevation and the mean elevation of the neighbourhood
+                    row1 = Math.max(row - tpiRadius, 0);
+                    row2 = Math.min(row + tpiRadius, rows - 1) + 1;
+                    col1 = Math.max(col - tpiRadius, 0);
+                    col2 = Math.min(col + tpiRadius, cols - 1) + 1;
+                    sum = sumTable[row2 * (cols + 1) + col2] - sumTable[row1 * (cols + 1) + col2]
+                            - sumTable[row2 * (cols + 1) + col1] + sumTable[row1 * (cols + 1) + col1];
+                    count = countTable[row2 * (cols + 1) + col2] - countTable[row1 * (cols + 1) + col2]
+                            - countTable[row2 * (cols + 1) + col1] + countTable[row1 * (cols + 1) + col1];
+                    basin.tpiSum += z - sum / count;
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            sumTable = null;
+            countTable = null;
+            accumulation = null;
+
+            if (basinMap.isEmpty()) {
+                showFeedback("The basins raster does not contain any basins.");
+                return;
+            }
+
+            // The basin length is the greatest distance from the outlet, the
+            // cell with the largest flow accumulation, to any cell in the
+            // basin.
+            double dx, dy;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    id = basinID[row * cols + col];
+                    if (id != 0) {
+                        Basin basin = basinMap.get(id);
+                        dx = (col - basin.outletCol) * cellSizeX;
+                        dy = (row - basin.outletRow) * cellSizeY;
+                        basin.length = Math.max(basin.length, Math.sqrt(dx * dx + dy * dy));
+                    }
+                }
+            }
+
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                out.println("Basin,Area,Perimeter,Stream Length,Drainage Density,Relief,Basin Length,"
+                        + "Relief Ratio,Ruggedness Number,Mean Slope (degrees),Mean TPI,"
+                        + "Elongation Ratio,Circularity Ratio");
+                for (Map.Entry<Integer, Basin> e : basinMap.entrySet()) {
+                    Basin basin = e.getValue();
+                    double area = basin.numCells * cellSizeX * cellSizeY;
+                    double relief = basin.maxZ - basin.minZ;
+                    double drainageDensity = basin.streamLength / area;
+                    out.println(e.getKey() + "," + area + "," + basin.perimeter + ","
+                            + basin.streamLength + "," + drainageDensity + "," + relief + ","
+                            + basin.length + ","
+                            + (basin.length > 0 ? String.valueOf(relief / basin.length) : "") + ","
+                            + drainageDensity * Math.sqrt(relief) + ","
+                            + basin.slopeSum / basin.numCells + ","
+                            + basin.tpiSum / basin.numCells + ","
+                            + (basin.length > 0 ? String.valueOf(2 * Math.sqrt(area / Math.PI) / basin.length) : "") + ","
+                            + 4 * Math.PI * area / (basin.perimeter * basin.perimeter));
+                }
+            }
+
+            StringBuilder retstr = new StringBuilder();
+            retstr.append("BASIN GEOMORPHIC METRICS\n\n");
+            retstr.append("DEM:\t").append(demName).append("\n");
+            retstr.append("Basins:\t").append(basinsName).append("\n");
+            retstr.append("Channel threshold (grid cells):\t").append(channelThreshold).append("\n");
+            retstr.append("TPI radius (grid cells):\t").append(tpiRadius).append("\n");
+            retstr.append("Number of basins:\t").append(basinMap.size()).append("\n");
+            retstr.append("Output file:\t").append(new File(csvFile).getName()).append("\n");
+            returnData(retstr.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    // the running totals of the metrics of a basin
+    private static class Basin {
+
+        long numCells = 0;
+        double minZ = Double.POSITIVE_INFINITY;
+        double maxZ = Double.NEGATIVE_INFINITY;
+        double streamLength = 0;
+        double perimeter = 0;
+        double slopeSum = 0;
+        double tpiSum = 0;
+        double outletAccumulation = 0;
+        int outletRow;
+        int outletCol;
+        double length = 0;
+    }
+}
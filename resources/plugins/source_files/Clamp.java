@@ -0,0 +1,296 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool limits the values in an input raster to a range, replacing values
+ * below a minimum with the minimum and values above a maximum with the maximum.
+ */
+public class Clamp implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "Clamp";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Clamp Values";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Limits the values in a raster to a range between a minimum and a maximum.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"MathTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 4) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String inputHeader = args[0];
+        String outputHeader = args[1];
+        // either bound may be left unspecified, but not both.
+        double minValue = Double.NEGATIVE_INFINITY;
+        double maxValue = Double.POSITIVE_INFINITY;
+        if (!args[2].toLowerCase().contains("not specified")) {
+            minValue = Double.parseDouble(args[2].trim());
+        }
+        if (!args[3].toLowerCase().contains("not specified")) {
+            maxValue = Double.parseDouble(args[3].trim());
+        }
+        if (minValue == Double.NEGATIVE_INFINITY && maxValue == Double.POSITIVE_INFINITY) {
+            showFeedback("Specify a minimum value, a maximum value, or both.");
+            return;
+        }
+        if (minValue > maxValue) {
+            showFeedback("The minimum value must not be greater than the maximum value.");
+            return;
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            int row, col;
+            double z;
+            int progress, oldProgress = -1;
+            double[] data;
+
+            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
+
+            int rows = inputFile.getNumberRows();
+            int cols = inputFile.getNumberColumns();
+            double noData = inputFile.getNoDataValue();
+
+            WhiteboxRaster.DataType dataType = (inputFile.getDataType() == WhiteboxRaster.DataType.DOUBLE)
+                    ? WhiteboxRaster.DataType.DOUBLE : WhiteboxRaster.DataType.FLOAT;
+            WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw",
+                    inputHeader, dataType, noData);
+            outputFile.setPreferredPalette(inputFile.getPreferredPalette());
+
+            int numClamped = 0;
+            for (row = 0; row < rows; row++) {
+                data = inputFile.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z != noData) {
+                        if (z < minValue) {
+                            z = minValue;
+                            numClamped++;
+                        } else if (z > maxValue) {
+                            z = maxValue;
+                            numClamped++;
+                        }
+                        outputFile.setValue(row, col, z);
+                    } else {
+                        outputFile.setValue(row, col, noData);
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress((int) progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            outputFile.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            outputFile.addMetadataEntry("Created on " + new Date());
+            outputFile.addMetadataEntry("Minimum value: "
+                    + (minValue > Double.NEGATIVE_INFINITY ? String.valueOf(minValue) : "not specified"));
+            outputFile.addMetadataEntry("Maximum value: "
+                    + (maxValue < Double.POSITIVE_INFINITY ? String.valueOf(maxValue) : "not specified"));
+            outputFile.addMetadataEntry("Number of clamped cells: " + numClamped);
+
+            // close all of the open Whitebox rasters.
+            inputFile.close();
+            outputFile.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -209,7 +209,8 @@ public class CostPathway implements WhiteboxPlugin {
             double noData = destImage.getNoDataValue();
             gridRes = (destImage.getCellSizeX() + destImage.getCellSizeY()) / 2;
 
-            backLink = new WhiteboxRaster(backLinkHeader, "r");
+            // the back-links are read in scattered order along each pathway
+            backLink = WhiteboxRaster.openMemoryMapped(backLinkHeader);
             if (backLink.getNumberColumns() != cols || 
                     backLink.getNumberRows() != rows) {
                 showFeedback("Input images must have the same dimensions");
@@ -199,6 +199,7 @@ public class CreateColourComposite implements WhiteboxPlugin {
         String outputHeader = null;
         boolean alphaChannelSpecified = true;
         boolean performContrastEnhancement = true;
+        boolean stretchBands = true;
 
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -213,10 +214,15 @@ public class CreateColourComposite implements WhiteboxPlugin {
             alphaChannelSpecified = false;
         }
         outputHeader = args[4];
-        String doEnhancement = args[5];
-        if (doEnhancement.toLowerCase().contains("not specified")
-                || doEnhancement.toLowerCase().contains("f")) {
-            performContrastEnhancement = false;
+        if (args.length > 5) {
+            String doEnhancement = args[5];
+            if (doEnhancement.toLowerCase().contains("not specified")
+                    || doEnhancement.toLowerCase().contains("f")) {
+                performContrastEnhancement = false;
+            }
+        }
+        if (args.length > 6 && !args[6].toLowerCase().contains("not specified")) {
+            stretchBands = Boolean.parseBoolean(args[6]);
         }
 
         // check to see that the inputHeader and outputHeader are not null.
@@ -254,8 +260,15 @@ public class CreateColourComposite implements WhiteboxPlugin {
 
             double noData = red.getNoDataValue();
 
+            /* Colours are packed into a single 32-bit integer value, i.e.
+             * (alpha << 24) | (blue << 16) | (green << 8) | red. With an
+             * opaque alpha, these values are exactly representable as 
+             * floats but other alpha values require double precision.
+             */
+            WhiteboxRaster.DataType outputDataType = alphaChannelSpecified 
+                    ? WhiteboxRaster.DataType.DOUBLE : WhiteboxRaster.DataType.FLOAT;
             WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw",
-                    inputHeaderRed, WhiteboxRaster.DataType.FLOAT, noData);
+                    inputHeaderRed, outputDataType, noData);
             outputFile.setPreferredPalette("rgb.pal");
             outputFile.setDataScale(WhiteboxRaster.DataScale.RGB);
 
@@ -266,6 +279,16 @@ public class CreateColourComposite implements WhiteboxPlugin {
             redRange = red.getDisplayMaximum() - redMin;
             greenRange = green.getDisplayMaximum() - greenMin;
             blueRange = blue.getDisplayMaximum() - blueMin;
+            
+            if (!stretchBands) {
+                // band values are used directly as 8-bit colour values
+                redMin = 0;
+                greenMin = 0;
+                blueMin = 0;
+                redRange = 255;
+                greenRange = 255;
+                blueRange = 255;
+            }
 
             if (!alphaChannelSpecified) {
                 double[] dataRed, dataGreen, dataBlue;
@@ -279,21 +302,21 @@ public class CreateColourComposite implements WhiteboxPlugin {
                         greenVal = dataGreen[col];
                         blueVal = dataBlue[col];
                         if ((redVal != noData) && (greenVal != noData) && (blueVal != noData)) {
-                            r = (int) ((redVal - redMin) / redRange * 255);
+                            r = (int) Math.round((redVal - redMin) / redRange * 255);
                             if (r < 0) {
                                 r = 0;
                             }
                             if (r > 255) {
                                 r = 255;
                             }
-                            g = (int) ((greenVal - greenMin) / greenRange * 255);
+                            g = (int) Math.round((greenVal - greenMin) / greenRange * 255);
                             if (g < 0) {
                                 g = 0;
                             }
                             if (g > 255) {
                                 g = 255;
                             }
-                            b = (int) ((blueVal - blueMin) / blueRange * 255);
+                            b = (int) Math.round((blueVal - blueMin) / blueRange * 255);
                             if (b < 0) {
                                 b = 0;
                             }
@@ -328,6 +351,10 @@ public class CreateColourComposite implements WhiteboxPlugin {
                 double alphaMin, alphaRange;
                 alphaMin = alpha.getDisplayMinimum();
                 alphaRange = alpha.getDisplayMaximum() - alphaMin;
+                if (!stretchBands) {
+                    alphaMin = 0;
+                    alphaRange = 255;
+                }
                 oldProgress = -1;
                 for (row = 0; row < rows; row++) {
                     dataRed = red.getRowValues(row);
@@ -340,28 +367,28 @@ public class CreateColourComposite implements WhiteboxPlugin {
                         blueVal = dataBlue[col];
                         alphaVal = dataAlpha[col];
                         if ((redVal != noData) && (greenVal != noData) && (blueVal != noData)) {
-                            r = (int) ((redVal - redMin) / redRange * 255);
+                            r = (int) Math.round((redVal - redMin) / redRange * 255);
                             if (r < 0) {
                                 r = 0;
                             }
                             if (r > 255) {
                                 r = 255;
                             }
-                            g = (int) ((greenVal - greenMin) / greenRange * 255);
+                            g = (int) Math.round((greenVal - greenMin) / greenRange * 255);
                             if (g < 0) {
                                 g = 0;
                             }
                             if (g > 255) {
                                 g = 255;
                             }
-                            b = (int) ((blueVal - blueMin) / blueRange * 255);
+                            b = (int) Math.round((blueVal - blueMin) / blueRange * 255);
                             if (b < 0) {
                                 b = 0;
                             }
                             if (b > 255) {
                                 b = 255;
                             }
-                            a = (int) ((alphaVal - alphaMin) / alphaRange * 255);
+                            a = (int) Math.round((alphaVal - alphaMin) / alphaRange * 255);
                             if (a < 0) {
                                 a = 0;
                             }
@@ -0,0 +1,325 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool converts a D8 flow pointer raster between the direction encodings
+ * used by Whitebox, ESRI ArcGIS, TauDEM and GRASS GIS.
+ */
+public class D8PointerConversion implements WhiteboxPlugin {
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "D8PointerConversion";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "D8 Pointer Conversion";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Converts a D8 flow pointer raster between the direction encodings of different software.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"FlowPointers"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    // The codes of each encoding scheme for the flow directions northeast,
+    // east, southeast, south, southwest, west, northwest, and north.
+    private static final int[] WHITEBOX_CODES = {1, 2, 4, 8, 16, 32, 64, 128};
+    private static final int[] ESRI_CODES = {128, 1, 2, 4, 8, 16, 32, 64};
+    private static final int[] TAUDEM_CODES = {2, 1, 8, 7, 6, 5, 4, 3};
+    private static final int[] GRASS_CODES = {1, 8, 7, 6, 5, 4, 3, 2};
+
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 4) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String inputHeader = args[0];
+        String outputHeader = args[1];
+        int[] fromCodes = getCodes(args[2]);
+        int[] toCodes = getCodes(args[3]);
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (fromCodes == null || toCodes == null) {
+            showFeedback("The pointer encoding must be one of Whitebox, ESRI, TauDEM, or GRASS.");
+            return;
+        }
+
+        try {
+            int row, col, i;
+            double z;
+            int progress, oldProgress = -1;
+            long numUndefined = 0;
+
+            // the direction index of each code of the input scheme, or -1
+            int[] directionOfCode = new int[256];
+            Arrays.fill(directionOfCode, -1);
+            for (i = 0; i < 8; i++) {
+                directionOfCode[fromCodes[i]] = i;
+            }
+
+            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
+            int rows = inputFile.getNumberRows();
+            int cols = inputFile.getNumberColumns();
+            double noData = inputFile.getNoDataValue();
+
+            WhiteboxRasterStreamWriter outputFile = new WhiteboxRasterStreamWriter(outputHeader,
+                    inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            outputFile.setPreferredPalette("qual.pal");
+            outputFile.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+            outputFile.setZUnits("dimensionless");
+
+            double[] data;
+            double[] outputData = new double[cols];
+            for (row = 0; row < rows; row++) {
+                data = inputFile.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z == noData) {
+                        outputData[col] = noData;
+                        continue;
+                    }
+                    // GRASS marks cells that drain off of the map with
+                    // negative directions.
+                    if (z < 0 && fromCodes == GRASS_CODES) {
+                        z = -z;
+                    }
+                    if (z == 0) {
+                        outputData[col] = 0;
+                    } else if (z == Math.floor(z) && z < 256 && directionOfCode[(int) z] >= 0) {
+                        outputData[col] = toCodes[directionOfCode[(int) z]];
+                    } else {
+                        // codes that are not single directions, e.g. the sums
+                        // of directions that ESRI assigns to some sinks, are
+                        // undefined.
+                        outputData[col] = 0;
+                        numUndefined++;
+                    }
+                }
+                outputFile.writeRow(row, outputData);
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        inputFile.close();
+                        outputFile.abort();
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            outputFile.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            outputFile.addMetadataEntry("Created on " + new Date());
+            outputFile.addMetadataEntry("Converted from " + args[2] + " to " + args[3] + " encoding.");
+            if (numUndefined > 0) {
+                outputFile.addMetadataEntry(numUndefined + " cells with undefined directions were set to 0.");
+            }
+
+            inputFile.close();
+            outputFile.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    // returns the codes of the named encoding scheme, or null if the name is
+    // not recognized.
+    private static int[] getCodes(String scheme) {
+        String s = scheme.toLowerCase();
+        if (s.contains("whitebox")) {
+            return WHITEBOX_CODES;
+        } else if (s.contains("esri") || s.contains("arcgis")) {
+            return ESRI_CODES;
+        } else if (s.contains("taudem")) {
+            return TAUDEM_CODES;
+        } else if (s.contains("grass")) {
+            return GRASS_CODES;
+        }
+        return null;
+    }
+}
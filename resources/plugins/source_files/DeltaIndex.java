@@ -0,0 +1,464 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the difference between pre-event and post-event index images, e.g. the dNBR used to map
+ * burn severity, and optionally classifies the difference and summarizes the area of each class.
+ */
+public class DeltaIndex implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name containing no spaces.
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "DeltaIndex";
+    }
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer name (containing spaces) and is used in the interface to list the tool.
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Delta Index (dNBR, dNDVI)";
+    }
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates and classifies the change in an index between two dates, e.g. the dNBR.";
+    }
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "ChangeDetection", "VegetationIndices" };
+    	return ret;
+    }
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the class
+     * that the plugin will send all feedback messages, progress updates, and return objects.
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */  
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and the main Whitebox user-interface.
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+    /**
+     * Used to communicate a return object from a plugin tool to the main Whitebox user-interface.
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+    /**
+     * Used to communicate a progress update between a plugin tool and the main Whitebox user interface.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     * @return a boolean describing whether or not the plugin is actively being used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    // the dNBR severity classes of Key and Benson (2006), with their lower
+    // bounds in the conventional units of dNBR x 1000
+    private static final String[] dNBRClassNames = {"Enhanced regrowth, high",
+        "Enhanced regrowth, low", "Unburned", "Low severity", "Moderate-low severity",
+        "Moderate-high severity", "High severity"};
+    private static final double[] dNBRBreaks = {-250, -100, 100, 270, 440, 660};
+
+    /**
+     * Reads an index raster, or calculates a normalized difference index from
+     * two band rasters, into an array in which NoData cells are NaN.
+     */
+    private double[][] readIndex(String indexHeader, String bandAHeader,
+            String bandBHeader, double scale, int rows, int cols) throws Exception {
+        double[][] index = new double[rows][cols];
+        if (indexHeader != null) {
+            WhiteboxRaster image = new WhiteboxRaster(indexHeader, "r");
+            if (image.getNumberRows() != rows || image.getNumberColumns() != cols) {
+                image.close();
+                return null;
+            }
+            double noData = image.getNoDataValue();
+            for (int row = 0; row < rows; row++) {
+                double[] data = image.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    index[row][col] = data[col] != noData ? data[col] : Double.NaN;
+                }
+            }
+            image.close();
+        } else {
+            WhiteboxRaster bandA = new WhiteboxRaster(bandAHeader, "r");
+            WhiteboxRaster bandB = new WhiteboxRaster(bandBHeader, "r");
+            if (bandA.getNumberRows() != rows || bandA.getNumberColumns() != cols
+                    || bandB.getNumberRows() != rows || bandB.getNumberColumns() != cols) {
+                bandA.close();
+                bandB.close();
+                return null;
+            }
+            double noDataA = bandA.getNoDataValue();
+            double noDataB = bandB.getNoDataValue();
+            for (int row = 0; row < rows; row++) {
+                double[] a = bandA.getRowValues(row);
+                double[] b = bandB.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (a[col] != noDataA && b[col] != noDataB && a[col] + b[col] != 0) {
+                        index[row][col] = (a[col] - b[col]) / (a[col] + b[col]) * scale;
+                    } else {
+                        index[row][col] = Double.NaN;
+                    }
+                }
+            }
+            bandA.close();
+            bandB.close();
+        }
+        return index;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String[] inputHeaders = new String[6];
+        String outputHeader = null;
+        String classification = "none";
+        String breaksString = null;
+        String classifiedHeader = null;
+        String csvFile = null;
+        String maskHeader = null;
+        boolean scaled = false;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i < 6) {
+                // pre- and post-event indices, then pre- and post-event bands
+                inputHeaders[i] = specified ? args[i] : null;
+            } else if (i == 6) {
+                outputHeader = args[i];
+            } else if (i == 7 && specified) {
+                classification = args[i].toLowerCase();
+            } else if (i == 8 && specified) {
+                breaksString = args[i];
+            } else if (i == 9 && specified) {
+                classifiedHeader = args[i];
+            } else if (i == 10 && specified) {
+                csvFile = args[i];
+            } else if (i == 11 && specified) {
+                maskHeader = args[i];
+            } else if (i == 12) {
+                scaled = Boolean.parseBoolean(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        boolean useIndices = inputHeaders[0] != null && inputHeaders[1] != null;
+        boolean useBands = inputHeaders[2] != null && inputHeaders[3] != null
+                && inputHeaders[4] != null && inputHeaders[5] != null;
+        if (outputHeader == null || (!useIndices && !useBands)) {
+            showFeedback("Either the pre- and post-event index images, or the four "
+                    + "pre- and post-event band images, must be specified.");
+            return;
+        }
+
+        // the class breaks, in the same units as the difference image
+        double[] breaks = null;
+        String[] classNames = null;
+        if (classification.startsWith("dnbr")) {
+            breaks = new double[dNBRBreaks.length];
+            for (int i = 0; i < breaks.length; i++) {
+                breaks[i] = scaled ? dNBRBreaks[i] : dNBRBreaks[i] / 1000;
+            }
+            classNames = dNBRClassNames;
+        } else if (classification.startsWith("user")) {
+            if (breaksString == null) {
+                showFeedback("The class breaks must be specified for a user-defined classification.");
+                return;
+            }
+            String[] str = breaksString.split(",");
+            breaks = new double[str.length];
+            for (int i = 0; i < str.length; i++) {
+                breaks[i] = Double.parseDouble(str[i].trim());
+            }
+            Arrays.sort(breaks);
+            classNames = new String[breaks.length + 1];
+            for (int i = 0; i < classNames.length; i++) {
+                classNames[i] = "Class " + (i + 1);
+            }
+        }
+        if (breaks != null && classifiedHeader == null) {
+            showFeedback("The classified output file must be specified to classify the difference image.");
+            return;
+        }
+        if (breaks == null && (classifiedHeader != null || csvFile != null)) {
+            showFeedback("A classification must be selected to create a classified image or class area summary.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster base = new WhiteboxRaster(useIndices ? inputHeaders[0] : inputHeaders[2], "r");
+            int rows = base.getNumberRows();
+            int cols = base.getNumberColumns();
+            String baseHeader = base.getHeaderFile();
+            double cellArea = Math.abs(base.getCellSizeX() * base.getCellSizeY());
+            base.close();
+
+            updateProgress("Reading data:", 0);
+            double scale = scaled ? 1000 : 1;
+            double[][] pre = useIndices ? readIndex(inputHeaders[0], null, null, scale, rows, cols)
+                    : readIndex(null, inputHeaders[2], inputHeaders[3], scale, rows, cols);
+            updateProgress("Reading data:", 50);
+            double[][] post = useIndices ? readIndex(inputHeaders[1], null, null, scale, rows, cols)
+                    : readIndex(null, inputHeaders[4], inputHeaders[5], scale, rows, cols);
+            if (pre == null || post == null) {
+                showFeedback("The input images must have the same number of rows and columns.");
+                return;
+            }
+
+            WhiteboxRaster mask = null;
+            double maskNoData = 0;
+            if (maskHeader != null) {
+                mask = new WhiteboxRaster(maskHeader, "r");
+                if (mask.getNumberRows() != rows || mask.getNumberColumns() != cols) {
+                    showFeedback("The mask image must have the same number of rows and columns as the input images.");
+                    return;
+                }
+                maskNoData = mask.getNoDataValue();
+            }
+
+            double noData = -32768;
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", baseHeader,
+                    scaled ? WhiteboxRaster.DataType.INTEGER : WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("blue_white_red.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+
+            WhiteboxRaster classified = null;
+            long[] classCounts = null;
+            if (breaks != null) {
+                classified = new WhiteboxRaster(classifiedHeader, "rw", baseHeader,
+                        WhiteboxRaster.DataType.INTEGER, noData);
+                classified.setPreferredPalette(classNames == dNBRClassNames ? "burn_severity.pal" : "qual.pal");
+                classified.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+                classCounts = new long[breaks.length + 1];
+            }
+
+            // allows for the round-off of indices that are stored as floats,
+            // e.g. so that 0.5 - 0.6 falls in a class with a lower bound of -0.1
+            double tolerance = scaled ? 0 : 1e-6;
+            double[] outData = new double[cols];
+            double[] classData = new double[cols];
+            for (int row = 0; row < rows; row++) {
+                double[] maskData = mask != null ? mask.getRowValues(row) : null;
+                for (int col = 0; col < cols; col++) {
+                    double diff = pre[row][col] - post[row][col];
+                    boolean valid = !Double.isNaN(diff)
+                            && (maskData == null || (maskData[col] != maskNoData && maskData[col] != 0));
+                    if (valid) {
+                        if (scaled) {
+                            diff = Math.round(diff);
+                        }
+                        outData[col] = diff;
+                        if (breaks != null) {
+                            int c = 0;
+                            while (c < breaks.length && diff >= breaks[c] - tolerance) {
+                                c++;
+                            }
+                            classData[col] = c + 1;
+                            classCounts[c]++;
+                        }
+                    } else {
+                        outData[col] = noData;
+                        classData[col] = noData;
+                    }
+                }
+                output.setRowValues(row, outData);
+                if (classified != null) {
+                    classified.setRowValues(row, classData);
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress("Calculating difference:", (int) (100f * row / rows));
+            }
+
+            String units = scaled ? " (scaled by 1000)" : "";
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Difference of the pre-event and post-event indices" + units);
+            if (maskHeader != null) {
+                output.addMetadataEntry("Mask: " + new File(maskHeader).getName());
+            }
+            output.close();
+
+            if (classified != null) {
+                // the palette entries are assigned to the classes in order
+                classified.setDisplayMinimum(1);
+                classified.setDisplayMaximum(classCounts.length);
+                classified.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                classified.addMetadataEntry("Created on " + new Date());
+                for (int c = 0; c < classCounts.length; c++) {
+                    classified.addMetadataEntry("Class " + (c + 1) + ": " + classNames[c]
+                            + " (" + getClassRange(breaks, c) + ")" + units);
+                }
+                classified.close();
+            }
+
+            if (mask != null) {
+                mask.close();
+            }
+
+            if (csvFile != null) {
+                long totalCount = 0;
+                for (long count : classCounts) {
+                    totalCount += count;
+                }
+                try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                    out.println("Class,Name,Lower Bound,Upper Bound,Cells,Area,Percent");
+                    for (int c = 0; c < classCounts.length; c++) {
+                        out.println((c + 1) + ",\"" + classNames[c] + "\","
+                                + (c > 0 ? breaks[c - 1] : "") + ","
+                                + (c < breaks.length ? breaks[c] : "") + ","
+                                + classCounts[c] + "," + classCounts[c] * cellArea + ","
+                                + (totalCount > 0 ? 100.0 * classCounts[c] / totalCount : 0));
+                    }
+                    out.println("Total,,,," + totalCount + "," + totalCount * cellArea + ","
+                            + (totalCount > 0 ? 100.0 : 0));
+                }
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+            if (classifiedHeader != null) {
+                returnData(classifiedHeader);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    private static String getClassRange(double[] breaks, int c) {
+        if (c == 0) {
+            return "less than " + breaks[0];
+        } else if (c == breaks.length) {
+            return breaks[c - 1] + " or more";
+        }
+        return breaks[c - 1] + " to less than " + breaks[c];
+    }
+}
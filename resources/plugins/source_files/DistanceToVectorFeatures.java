@@ -0,0 +1,740 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import com.vividsolutions.jts.geom.Envelope;
+import com.vividsolutions.jts.index.strtree.ItemBoundable;
+import com.vividsolutions.jts.index.strtree.ItemDistance;
+import com.vividsolutions.jts.index.strtree.STRtree;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Date;
+import java.util.List;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
+import whitebox.geospatialfiles.shapefile.Geometry;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.geospatialfiles.shapefile.attributes.DBFField;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the Euclidean distance from each grid cell to the 
+ * nearest feature in a vector file of points, lines, or polygons. It can also 
+ * output the direction to the nearest feature and the value of an attribute 
+ * of the nearest feature (allocation). Distances are either measured exactly 
+ * to the feature geometries or estimated from a rasterized copy of the 
+ * features using a distance transform.
+ */
+public class DistanceToVectorFeatures implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "DistanceToVectorFeatures";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Distance To Vector Features";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the distance, direction, and allocation to the nearest vector feature";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "DistanceTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+  
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+   
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+
+        amIActive = true;
+        String inputFile;
+        String distanceHeader;
+        String directionHeader = null;
+        String allocationHeader = null;
+        String fieldName = null;
+        String baseFileHeader = null;
+        double cellSize = -1.0;
+        boolean exact = false;
+        double noData = -32768.0;
+        int progress;
+        int row, col, i, part, start, end;
+        int featureNum, numFeatures;
+        double x, y, dist, direction;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        inputFile = args[0];
+        distanceHeader = args[1];
+        if (!args[2].toLowerCase().contains("not specified")) {
+            directionHeader = args[2];
+        }
+        if (!args[3].toLowerCase().contains("not specified")) {
+            allocationHeader = args[3];
+        }
+        if (!args[4].toLowerCase().contains("not specified")) {
+            fieldName = args[4].trim();
+        }
+        if (!args[5].toLowerCase().contains("not specified")) {
+            cellSize = Double.parseDouble(args[5]);
+        }
+        if (!args[6].toLowerCase().contains("not specified")) {
+            baseFileHeader = args[6];
+        }
+        exact = Boolean.parseBoolean(args[7]);
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (distanceHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        if (cellSize <= 0 && baseFileHeader == null) {
+            showFeedback("Either a cell size or a base raster must be specified.");
+            return;
+        }
+
+        try {
+            ShapeFile input = new ShapeFile(inputFile);
+            ShapeType baseType = input.getShapeType().getBaseType();
+            boolean isPolygon = (baseType == ShapeType.POLYGON);
+
+            AttributeTable table = input.getAttributeTable();
+            int fieldNum = -1;
+            if (fieldName != null) {
+                fieldNum = table.getFieldColumnNumberFromName(fieldName);
+                if (fieldNum < 0) {
+                    showFeedback("The field '" + fieldName + "' could not be found in the attribute table.");
+                    return;
+                }
+                DBFField.DBFDataType fieldType = table.getField(fieldNum).getDataType();
+                if (fieldType != DBFField.DBFDataType.NUMERIC && fieldType != DBFField.DBFDataType.FLOAT) {
+                    showFeedback("The allocation field must be numeric.");
+                    return;
+                }
+            }
+
+            // Read the features. Every feature is broken into segments, with
+            // points stored as segments of zero length, and polygons are also
+            // kept whole so that cells inside of them can be found.
+            updateProgress("Reading features:", 0);
+            List<Double> featureValues = new ArrayList<>();
+            List<Segment> segments = new ArrayList<>();
+            List<Polygon> polygons = new ArrayList<>();
+            numFeatures = input.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : input.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    int feature = featureValues.size();
+                    if (fieldNum < 0) {
+                        featureValues.add((double) record.getRecordNumber());
+                    } else {
+                        Object value = table.getValue(record.getRecordNumber() - 1, fieldNum);
+                        featureValues.add(value == null ? noData : Double.valueOf(value.toString()));
+                    }
+                    Geometry geometry = record.getGeometry();
+                    double[][] vertices = geometry.getPoints();
+                    int[] parts = geometry.getParts();
+                    if (baseType == ShapeType.POINT || baseType == ShapeType.MULTIPOINT) {
+                        for (double[] vertex : vertices) {
+                            segments.add(new Segment(feature, vertex[0], vertex[1], vertex[0], vertex[1]));
+                        }
+                    } else {
+                        for (part = 0; part < parts.length; part++) {
+                            start = parts[part];
+                            end = (part < parts.length - 1) ? parts[part + 1] : vertices.length;
+                            for (i = start + 1; i < end; i++) {
+                                segments.add(new Segment(feature, vertices[i - 1][0], vertices[i - 1][1],
+                                        vertices[i][0], vertices[i][1]));
+                            }
+                        }
+                        if (isPolygon) {
+                            polygons.add(new Polygon(feature, vertices, parts));
+                        }
+                    }
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+
+            if (segments.isEmpty()) {
+                showFeedback("The input vector file does not contain any features.");
+                return;
+            }
+
+            // initialize the outputs
+            WhiteboxRaster distance;
+            if (cellSize > 0) {
+                double north = input.getyMax() + cellSize / 2.0;
+                double south = input.getyMin() - cellSize / 2.0;
+                double east = input.getxMax() + cellSize / 2.0;
+                double west = input.getxMin() - cellSize / 2.0;
+                int numRows = (int) (Math.ceil((north - south) / cellSize));
+                int numCols = (int) (Math.ceil((east - west) / cellSize));
+
+                // update east and south
+                east = west + numCols * cellSize;
+                south = north - numRows * cellSize;
+
+                distance = new WhiteboxRaster(distanceHeader, north, south, east, west,
+                        numRows, numCols, DataScale.CONTINUOUS, DataType.FLOAT, noData, noData);
+            } else {
+                distance = new WhiteboxRaster(distanceHeader, "rw", baseFileHeader, DataType.FLOAT, noData);
+                distance.setNoDataValue(noData);
+            }
+            distance.setPreferredPalette("spectrum.pal");
+
+            WhiteboxRaster directionRaster = null;
+            if (directionHeader != null) {
+                directionRaster = new WhiteboxRaster(directionHeader, "rw", distanceHeader, DataType.FLOAT, noData);
+                directionRaster.setPreferredPalette("circular_bw.pal");
+            }
+            WhiteboxRaster allocation = null;
+            if (allocationHeader != null) {
+                allocation = new WhiteboxRaster(allocationHeader, "rw", distanceHeader, DataType.FLOAT, noData);
+                allocation.setPreferredPalette("qual.pal");
+                allocation.setDataScale(DataScale.CATEGORICAL);
+            }
+
+            int rows = distance.getNumberRows();
+            int cols = distance.getNumberColumns();
+            double cellSizeX = distance.getCellSizeX();
+            double cellSizeY = distance.getCellSizeY();
+            double[] distData = new double[cols];
+            double[] dirData = new double[cols];
+            double[] allocData = new double[cols];
+
+            if (exact) {
+
+                // index the segments and the polygons in R-trees
+                STRtree segmentIndex = new STRtree();
+                for (Segment segment : segments) {
+                    segmentIndex.insert(new Envelope(segment.x1, segment.x2, segment.y1, segment.y2), segment);
+                }
+                segmentIndex.build();
+                STRtree polygonIndex = new STRtree();
+                for (Polygon polygon : polygons) {
+                    polygonIndex.insert(polygon.envelope, polygon);
+                }
+                polygonIndex.build();
+                ItemDistance segmentDistance = new SegmentDistance();
+
+                updateProgress("Measuring distances:", 0);
+                for (row = 0; row < rows; row++) {
+                    y = distance.getYCoordinateFromRow(row);
+                    for (col = 0; col < cols; col++) {
+                        x = distance.getXCoordinateFromColumn(col);
+
+                        // cells inside of a polygon are a distance of zero from it
+                        int feature = -1;
+                        if (isPolygon) {
+                            List<Polygon> candidates = polygonIndex.query(new Envelope(x, x, y, y));
+                            for (Polygon polygon : candidates) {
+                                if (polygon.contains(x, y)) {
+                                    feature = polygon.feature;
+                                    break;
+                                }
+                            }
+                        }
+
+                        if (feature >= 0) {
+                            dist = 0;
+                            direction = noData;
+                        } else {
+                            Segment nearest = (Segment) segmentIndex.nearestNeighbour(new Envelope(x, x, y, y),
+                                    new double[]{x, y}, segmentDistance);
+                            double t = nearest.closestFraction(x, y);
+                            double dx = nearest.x1 + t * (nearest.x2 - nearest.x1) - x;
+                            double dy = nearest.y1 + t * (nearest.y2 - nearest.y1) - y;
+                            feature = nearest.feature;
+                            dist = Math.sqrt(dx * dx + dy * dy);
+                            direction = (dist > 0) ? azimuth(dx, dy) : noData;
+                        }
+                        distData[col] = dist;
+                        dirData[col] = direction;
+                        allocData[col] = featureValues.get(feature);
+                    }
+                    distance.setRowValues(row, distData);
+                    if (directionRaster != null) {
+                        directionRaster.setRowValues(row, dirData);
+                    }
+                    if (allocation != null) {
+                        allocation.setRowValues(row, allocData);
+                    }
+
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (100f * row / (rows - 1));
+                    updateProgress(progress);
+                }
+
+            } else {
+
+                // Rasterize the features. Each cell records the feature that
+                // it belongs to, or -1 if it is a background cell. Segments
+                // are sampled at intervals of half a cell.
+                updateProgress("Rasterizing features:", 0);
+                int[] cellFeature = new int[rows * cols];
+                Arrays.fill(cellFeature, -1);
+                double step = Math.min(cellSizeX, cellSizeY) / 2.0;
+                for (Segment segment : segments) {
+                    int numSteps = (int) Math.ceil(segment.length / step);
+                    for (i = 0; i <= numSteps; i++) {
+                        double t = (numSteps > 0) ? (double) i / numSteps : 0;
+                        row = distance.getRowFromYCoordinate(segment.y1 + t * (segment.y2 - segment.y1));
+                        col = distance.getColumnFromXCoordinate(segment.x1 + t * (segment.x2 - segment.x1));
+                        if (row >= 0 && row < rows && col >= 0 && col < cols) {
+                            cellFeature[row * cols + col] = segment.feature;
+                        }
+                    }
+                }
+                // cells with centres inside of a polygon are added by scanning
+                // the polygon's rows
+                for (Polygon polygon : polygons) {
+                    int topRow = Math.max(0, distance.getRowFromYCoordinate(polygon.envelope.getMaxY()));
+                    int bottomRow = Math.min(rows - 1, distance.getRowFromYCoordinate(polygon.envelope.getMinY()));
+                    for (row = topRow; row <= bottomRow; row++) {
+                        y = distance.getYCoordinateFromRow(row);
+                        double[] crossings = polygon.crossings(y);
+                        for (i = 0; i < crossings.length - 1; i += 2) {
+                            int startCol = Math.max(0, (int) Math.ceil((crossings[i] - distance.getWest()) / cellSizeX - 0.5));
+                            int endCol = Math.min(cols - 1, (int) Math.floor((crossings[i + 1] - distance.getWest()) / cellSizeX - 0.5));
+                            for (col = startCol; col <= endCol; col++) {
+                                cellFeature[row * cols + col] = polygon.feature;
+                            }
+                        }
+                    }
+                }
+
+                boolean anyFeatureCells = false;
+                for (i = 0; i < rows * cols; i++) {
+                    if (cellFeature[i] >= 0) {
+                        anyFeatureCells = true;
+                        break;
+                    }
+                }
+                if (!anyFeatureCells) {
+                    showFeedback("None of the features lie within the grid. Use the exact "
+                            + "distance option for features outside of the grid.");
+                    return;
+                }
+
+                // Find the nearest feature cell to each cell by propagating
+                // the nearest cells of the neighbours, first down the grid and
+                // then back up it (the 8SSEDT of Danielsson, 1980).
+                int[] nearest = new int[rows * cols];
+                for (i = 0; i < rows * cols; i++) {
+                    nearest[i] = (cellFeature[i] >= 0) ? i : -1;
+                }
+                Propagator propagator = new Propagator(nearest, rows, cols, cellSizeX, cellSizeY);
+                updateProgress("Calculating distances:", 0);
+                for (row = 0; row < rows; row++) {
+                    for (col = 0; col < cols; col++) {
+                        propagator.update(row, col, 0, -1);
+                        propagator.update(row, col, -1, -1);
+                        propagator.update(row, col, -1, 0);
+                        propagator.update(row, col, -1, 1);
+                    }
+                    for (col = cols - 1; col >= 0; col--) {
+                        propagator.update(row, col, 0, 1);
+                    }
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (50f * row / (rows - 1));
+                    updateProgress(progress);
+                }
+                for (row = rows - 1; row >= 0; row--) {
+                    for (col = cols - 1; col >= 0; col--) {
+                        propagator.update(row, col, 0, 1);
+                        propagator.update(row, col, 1, 1);
+                        propagator.update(row, col, 1, 0);
+                        propagator.update(row, col, 1, -1);
+                    }
+                    for (col = 0; col < cols; col++) {
+                        propagator.update(row, col, 0, -1);
+                    }
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (50f + 50f * (rows - 1 - row) / (rows - 1));
+                    updateProgress(progress);
+                }
+
+                // distances and directions are measured between cell centres
+                updateProgress("Saving data:", 0);
+                for (row = 0; row < rows; row++) {
+                    for (col = 0; col < cols; col++) {
+                        int target = nearest[row * cols + col];
+                        double dx = (target % cols - col) * cellSizeX;
+                        double dy = (row - target / cols) * cellSizeY;
+                        dist = Math.sqrt(dx * dx + dy * dy);
+                        distData[col] = dist;
+                        dirData[col] = (dist > 0) ? azimuth(dx, dy) : noData;
+                        allocData[col] = featureValues.get(cellFeature[target]);
+                    }
+                    distance.setRowValues(row, distData);
+                    if (directionRaster != null) {
+                        directionRaster.setRowValues(row, dirData);
+                    }
+                    if (allocation != null) {
+                        allocation.setRowValues(row, allocData);
+                    }
+
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (100f * row / (rows - 1));
+                    updateProgress(progress);
+                }
+            }
+
+            String method = exact ? "exact" : "rasterized";
+            distance.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool (" + method + " distances).");
+            distance.addMetadataEntry("Created on " + new Date());
+            distance.close();
+            if (directionRaster != null) {
+                directionRaster.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool (" + method + " distances).");
+                directionRaster.addMetadataEntry("Created on " + new Date());
+                directionRaster.close();
+            }
+            if (allocation != null) {
+                allocation.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool (" + method + " distances).");
+                allocation.addMetadataEntry("Created on " + new Date());
+                allocation.close();
+            }
+
+            // returning a header file string displays the image.
+            returnData(distanceHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+
+    }
+
+    // returns the azimuth (degrees clockwise from north) of the offset (dx, dy)
+    private static double azimuth(double dx, double dy) {
+        double az = Math.toDegrees(Math.atan2(dx, dy));
+        return (az < 0) ? az + 360 : az;
+    }
+
+    private class Segment {
+
+        int feature;
+        double x1, y1, x2, y2;
+        double length;
+
+        Segment(int feature, double x1, double y1, double x2, double y2) {
+            this.feature = feature;
+            this.x1 = x1;
+            this.y1 = y1;
+            this.x2 = x2;
+            this.y2 = y2;
+            this.length = Math.hypot(x2 - x1, y2 - y1);
+        }
+
+        // returns the position of the point on the segment nearest to (x, y)
+        // as a fraction of the segment length.
+        double closestFraction(double x, double y) {
+            if (length == 0) {
+                return 0;
+            }
+            double t = ((x - x1) * (x2 - x1) + (y - y1) * (y2 - y1)) / (length * length);
+            return Math.max(0, Math.min(1, t));
+        }
+
+        double distance(double x, double y) {
+            double t = closestFraction(x, y);
+            return Math.hypot(x1 + t * (x2 - x1) - x, y1 + t * (y2 - y1) - y);
+        }
+    }
+
+    // The distance between a segment in the R-tree and the query point,
+    // which is stored as a double[] {x, y}.
+    private static class SegmentDistance implements ItemDistance {
+
+        @Override
+        public double distance(ItemBoundable item1, ItemBoundable item2) {
+            Object segment = item1.getItem();
+            Object point = item2.getItem();
+            if (point instanceof Segment) {
+                segment = item2.getItem();
+                point = item1.getItem();
+            }
+            double[] xy = (double[]) point;
+            return ((Segment) segment).distance(xy[0], xy[1]);
+        }
+    }
+
+    private class Polygon {
+
+        int feature;
+        double[][] vertices;
+        int[] parts;
+        Envelope envelope = new Envelope();
+
+        Polygon(int feature, double[][] vertices, int[] parts) {
+            this.feature = feature;
+            this.vertices = vertices;
+            this.parts = parts;
+            for (double[] vertex : vertices) {
+                envelope.expandToInclude(vertex[0], vertex[1]);
+            }
+        }
+
+        // returns the sorted x-coordinates at which the rings of the polygon
+        // cross the horizontal line through y. Holes are handled by the
+        // even-odd rule.
+        double[] crossings(double y) {
+            List<Double> xs = new ArrayList<>();
+            for (int part = 0; part < parts.length; part++) {
+                int start = parts[part];
+                int end = (part < parts.length - 1) ? parts[part + 1] : vertices.length;
+                for (int i = start + 1; i < end; i++) {
+                    double[] p1 = vertices[i - 1];
+                    double[] p2 = vertices[i];
+                    if ((p1[1] > y) != (p2[1] > y)) {
+                        xs.add(p1[0] + (y - p1[1]) / (p2[1] - p1[1]) * (p2[0] - p1[0]));
+                    }
+                }
+            }
+            double[] ret = new double[xs.size()];
+            for (int i = 0; i < ret.length; i++) {
+                ret[i] = xs.get(i);
+            }
+            Arrays.sort(ret);
+            return ret;
+        }
+
+        boolean contains(double x, double y) {
+            int numLeft = 0;
+            for (double crossing : crossings(y)) {
+                if (crossing < x) {
+                    numLeft++;
+                }
+            }
+            return (numLeft % 2 == 1);
+        }
+    }
+
+    // Updates the nearest feature cell of a cell from that of one of its
+    // neighbours during the distance transform.
+    private static class Propagator {
+
+        int[] nearest;
+        int rows, cols;
+        double cellSizeX, cellSizeY;
+
+        Propagator(int[] nearest, int rows, int cols, double cellSizeX, double cellSizeY) {
+            this.nearest = nearest;
+            this.rows = rows;
+            this.cols = cols;
+            this.cellSizeX = cellSizeX;
+            this.cellSizeY = cellSizeY;
+        }
+
+        double squaredDistance(int row, int col, int target) {
+            double dx = (target % cols - col) * cellSizeX;
+            double dy = (target / cols - row) * cellSizeY;
+            return dx * dx + dy * dy;
+        }
+
+        void update(int row, int col, int dRow, int dCol) {
+            int r = row + dRow;
+            int c = col + dCol;
+            if (r < 0 || r >= rows || c < 0 || c >= cols) {
+                return;
+            }
+            int candidate = nearest[r * cols + c];
+            if (candidate < 0) {
+                return;
+            }
+            int cell = row * cols + col;
+            if (nearest[cell] < 0 || squaredDistance(row, col, candidate) < squaredDistance(row, col, nearest[cell])) {
+                nearest[cell] = candidate;
+            }
+        }
+    }
+}
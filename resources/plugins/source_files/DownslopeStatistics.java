@@ -0,0 +1,438 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the maximum, minimum, or mean of an attribute along the
+ * D8 flowpath leading from each grid cell to the edge of the grid or a pit.
+ */
+public class DownslopeStatistics implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    // Constants
+    private static final double LnOf2 = 0.693147180559945;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "DownslopeStatistics";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Downslope Statistics";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Calculates statistics of an attribute along the downslope "
+                + "flowpath of each grid cell.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"HydroTools", "FlowpathTAs"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String pointerHeader = null;
+        String attributeHeader = null;
+        String outputHeader = null;
+        String statistic = "max";
+        int row, col, r, c, i, n;
+        int progress, oldProgress;
+        double z, val;
+        int count;
+        int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+        int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                pointerHeader = args[i];
+            } else if (i == 1) {
+                attributeHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                statistic = args[i].toLowerCase().trim();
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((pointerHeader == null) || (attributeHeader == null) 
+                || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        boolean isMax = statistic.startsWith("max");
+        boolean isMin = statistic.startsWith("min");
+        boolean isMean = statistic.startsWith("mean") || statistic.startsWith("average");
+        if (!isMax && !isMin && !isMean) {
+            showFeedback("The statistic must be one of 'max', 'min', or 'mean'.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster pointer = new WhiteboxRaster(pointerHeader, "r");
+            int rows = pointer.getNumberRows();
+            int cols = pointer.getNumberColumns();
+            double noData = pointer.getNoDataValue();
+
+            WhiteboxRaster attribute = new WhiteboxRaster(attributeHeader, "r");
+            if (attribute.getNumberRows() != rows 
+                    || attribute.getNumberColumns() != cols) {
+                showFeedback("The input files must have the same dimensions, i.e. number of "
+                        + "rows and columns.");
+                pointer.close();
+                attribute.close();
+                return;
+            }
+            double attNoData = attribute.getNoDataValue();
+            
+            // Read the flow directions and the attribute into memory.
+            byte[][] flowDir = new byte[rows][cols];
+            double[][] attValue = new double[rows][cols];
+            // 0 = unsolved, 1 = on the current path, 2 = solved, 3 = drains to a loop
+            byte[][] state = new byte[rows][cols];
+            updateProgress("Reading data:", 0);
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                double[] pntrRow = pointer.getRowValues(row);
+                double[] attRow = attribute.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = pntrRow[col];
+                    attValue[row][col] = attRow[col];
+                    if (z == noData) {
+                        flowDir[row][col] = -2;
+                        state[row][col] = 2;
+                    } else {
+                        flowDir[row][col] = (byte) (z > 0 ? (int) (Math.log(z) / LnOf2) : -1);
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            attribute.close();
+
+            // Walk down the flowpath from each unsolved cell until the edge of 
+            // the grid, a pit, or an already solved cell is reached, and then 
+            // work back up the path, combining the attribute value of each 
+            // cell with the statistic of the cell that it drains to. Cells 
+            // with NoData in the attribute are passed over but don't stop the 
+            // flowpath. The statistic of each cell includes its own value.
+            double[][] value = new double[rows][cols];
+            int[][] numValues = new int[rows][cols];
+            int[] path = new int[rows * cols];
+            long numLoopCells = 0;
+            updateProgress("Tracing flowpaths:", 0);
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (state[row][col] >= 2) {
+                        continue;
+                    }
+                    n = 0;
+                    r = row;
+                    c = col;
+                    val = 0;
+                    count = 0;
+                    boolean isLoop = false;
+                    while (true) {
+                        if (r < 0 || r >= rows || c < 0 || c >= cols) {
+                            break;
+                        }
+                        if (state[r][c] == 2) {
+                            val = value[r][c];
+                            count = numValues[r][c];
+                            break;
+                        }
+                        if (state[r][c] == 1 || state[r][c] == 3) {
+                            isLoop = true;
+                            break;
+                        }
+                        state[r][c] = 1;
+                        path[n++] = r * cols + c;
+                        i = flowDir[r][c];
+                        if (i < 0) {
+                            break;
+                        }
+                        r += dY[i];
+                        c += dX[i];
+                    }
+                    if (isLoop) {
+                        numLoopCells += n;
+                        for (i = 0; i < n; i++) {
+                            state[path[i] / cols][path[i] % cols] = 3;
+                        }
+                        continue;
+                    }
+                    for (i = n - 1; i >= 0; i--) {
+                        r = path[i] / cols;
+                        c = path[i] % cols;
+                        z = attValue[r][c];
+                        if (z != attNoData) {
+                            if (count == 0) {
+                                val = z;
+                            } else if (isMax) {
+                                val = Math.max(val, z);
+                            } else if (isMin) {
+                                val = Math.min(val, z);
+                            } else {
+                                val += z;
+                            }
+                            count++;
+                        }
+                        value[r][c] = val;
+                        numValues[r][c] = count;
+                        state[r][c] = 2;
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            path = null;
+            
+            if (numLoopCells > 0) {
+                String msg = "Warning: The flowpaths of " + numLoopCells + " grid cells "
+                        + "drain to loops in the flow pointer. These cells have been assigned "
+                        + "NoData.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    pointerHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette(attribute.getPreferredPalette());
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits(attribute.getZUnits());
+            
+            updateProgress("Saving data:", 0);
+            oldProgress = -1;
+            double[] outRow = new double[cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (state[row][col] != 2 || numValues[row][col] == 0) {
+                        outRow[col] = noData;
+                    } else if (isMean) {
+                        outRow[col] = value[row][col] / numValues[row][col];
+                    } else {
+                        outRow[col] = value[row][col];
+                    }
+                }
+                output.setRowValues(row, outRow);
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Attribute: " + attribute.getShortHeaderFile());
+            output.addMetadataEntry("Statistic: " + (isMax ? "max" : isMin ? "min" : "mean"));
+
+            pointer.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
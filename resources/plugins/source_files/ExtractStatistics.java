@@ -18,7 +18,13 @@ package plugins;
 
 import java.io.PrintWriter;
 import java.text.DecimalFormat;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Collections;
 import java.util.Date;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.logging.Level;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterInfo;
 import whitebox.interfaces.WhiteboxPlugin;
@@ -202,14 +208,9 @@ public class ExtractStatistics implements WhiteboxPlugin {
         WhiteboxRasterInfo featureImage;
         WhiteboxRaster output;
         int cols, rows;
-        double imageTotal = 0;
-        long imageN = 0;
-        double imageAverage = 0;
-        double imageTotalDeviation = 0;
-        double stdDeviation = 0;
         float progress = 0;
         int col, row;
-        int i;
+        int id;
         String statType = null;
         boolean textOutput = false;
                 
@@ -221,7 +222,7 @@ public class ExtractStatistics implements WhiteboxPlugin {
         dataImageHeader = args[0];
         featureImageHeader = args[1];
         outputHeader = args[2];
-        statType = args[3].toLowerCase();
+        statType = args[3].toLowerCase().trim();
         textOutput = Boolean.parseBoolean(args[4]);
         String outputTextFile = "not specified";
         if (args.length >= 6) {
@@ -234,6 +235,33 @@ public class ExtractStatistics implements WhiteboxPlugin {
             showFeedback("One or more of the input parameters have not been set properly.");
             return;
         }
+        
+        int stat;
+        if (statType.equals("average") || statType.equals("mean")) {
+            stat = AVERAGE;
+            statType = "average";
+        } else if (statType.startsWith("min")) {
+            stat = MINIMUM;
+            statType = "minimum";
+        } else if (statType.startsWith("max")) {
+            stat = MAXIMUM;
+            statType = "maximum";
+        } else if (statType.equals("range")) {
+            stat = RANGE;
+        } else if (statType.equals("standard deviation") || statType.equals("stdev")) {
+            stat = STANDARD_DEVIATION;
+            statType = "standard deviation";
+        } else if (statType.equals("total") || statType.equals("sum")) {
+            stat = TOTAL;
+            statType = "total";
+        } else if (statType.equals("median")) {
+            stat = MEDIAN;
+        } else if (statType.equals("majority")) {
+            stat = MAJORITY;
+        } else {
+            showFeedback("Specified statistic type not recognized");
+            return;
+        }
 
         try {
             dataImage = new WhiteboxRasterInfo(dataImageHeader);
@@ -252,52 +280,14 @@ public class ExtractStatistics implements WhiteboxPlugin {
             String featureImageShortName = featureImage.getShortHeaderFile();
             String dataImageShortName = dataImage.getShortHeaderFile();
             
-            //see how many features there are
-            int numFeatures = 0;
+            // The features are keyed on their IDs, which need not be 
+            // contiguous. Non-integer IDs are truncated.
+            HashMap<Integer, Feature> features = new HashMap<>();
+            boolean nonIntegerIDs = false;
             double[] featureData;
             double[] data;
-            int minFeatureID = 99999999;
-            int maxFeatureID = -99999999;
-            for (row = 0; row < rows; row++) {
-                featureData = featureImage.getRowValues(row);
-                for (col = 0; col < cols; col++) {
-                    if (featureData[col] != featureNoData) {
-                        //make sure that the feature ID is an integer value
-                        if (Math.rint(featureData[col]) != featureData[col]) {
-                            showFeedback("The feature definition image should "
-                                    + "contain integer values only.");
-                            return;
-                        }
-                        if ((int)featureData[col] < minFeatureID) { 
-                            minFeatureID = (int)featureData[col];
-                        } 
-                        if ((int)featureData[col] > maxFeatureID) { 
-                            maxFeatureID = (int)featureData[col];
-                        } 
-                    }
-                }
-                if (cancelOp) { cancelOperation(); return; }
-                progress = (float) (100f * row / (rows - 1));
-                updateProgress((int)progress);
-            }
-
-            numFeatures = maxFeatureID - minFeatureID + 1;
-            // in reality this is only the number of features if there are no 
-            // unused feature IDs between the min and max values
-
-            double[] featureTotal = new double[numFeatures];
-            long[] featureN = new long[numFeatures];
-            double[] featureAverage = new double[numFeatures];
-            double[] featureTotalDeviation = new double[numFeatures];
-            double[] featureStdDeviation = new double[numFeatures];
-            double[] featureMins = new double[numFeatures];
-            double[] featureMaxs = new double[numFeatures];
-            boolean[] featurePresent = new boolean[numFeatures];
-
-            for (i = 0; i < numFeatures; i++) {
-                featureMins[i] = 99999999;
-                featureMaxs[i] = -99999999;
-            }
+            Feature feature = null;
+            int lastID = 0;
                     
             updateProgress("Loop 1 of 2:", 0);
             for (row = 0; row < rows; row++) {
@@ -305,17 +295,20 @@ public class ExtractStatistics implements WhiteboxPlugin {
                 featureData = featureImage.getRowValues(row);
                 for (col = 0; col < cols; col++) {
                     if (featureData[col] != featureNoData) {
-                        i = (int) (featureData[col] - minFeatureID);
-                        featurePresent[i] = true;
-                        if (data[col] != noData) {
-                            featureTotal[i] += data[col];
-                            featureN[i]++;
-                            if (data[col] < featureMins[i]) {
-                                featureMins[i] = data[col];
-                            }
-                            if (data[col] > featureMaxs[i]) {
-                                featureMaxs[i] = data[col];
+                        id = (int) featureData[col];
+                        if (id != featureData[col]) {
+                            nonIntegerIDs = true;
+                        }
+                        if (feature == null || id != lastID) {
+                            feature = features.get(id);
+                            if (feature == null) {
+                                feature = new Feature(stat);
+                                features.put(id, feature);
                             }
+                            lastID = id;
+                        }
+                        if (data[col] != noData) {
+                            feature.add(data[col]);
                         }
                     }
                 }
@@ -325,32 +318,40 @@ public class ExtractStatistics implements WhiteboxPlugin {
                 updateProgress("Loop 1 of 2:", (int)progress);
             }
             
-            for (i = 0; i < numFeatures; i++) {
-                if (featureN[i] > 0) {
-                    featureAverage[i] = featureTotal[i] / featureN[i];
-                }
+            if (nonIntegerIDs) {
+                String msg = "Warning: The feature definition image should contain "
+                        + "integer values only. Non-integer feature IDs have been truncated.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
             }
-               
-            updateProgress("Loop 2 of 2:", (int)progress);
-            for (row = 0; row < rows; row++) {
-                data = dataImage.getRowValues(row);
-                featureData = featureImage.getRowValues(row);
-                for (col = 0; col < cols; col++) {
-                    if (featureData[col] != featureNoData && data[col] != noData) {
-                        i = (int)(featureData[col] - minFeatureID);
-                        featureTotalDeviation[i] += (data[col] - featureAverage[i]) * 
-                             (data[col] - featureAverage[i]);
+            
+            if (stat == STANDARD_DEVIATION) {
+                // the deviations from each feature's average are found in a 
+                // second pass through the data
+                feature = null;
+                updateProgress("Loop 2 of 2:", 0);
+                for (row = 0; row < rows; row++) {
+                    data = dataImage.getRowValues(row);
+                    featureData = featureImage.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        if (featureData[col] != featureNoData && data[col] != noData) {
+                            id = (int) featureData[col];
+                            if (feature == null || id != lastID) {
+                                feature = features.get(id);
+                                lastID = id;
+                            }
+                            double dev = data[col] - feature.total / feature.n;
+                            feature.totalDeviation += dev * dev;
+                        }
                     }
+                    if (cancelOp) { cancelOperation(); return; }
+                    progress = (float) (100f * row / (rows - 1));
+                    updateProgress("Loop 2 of 2:", (int)progress);
                 }
-                if (cancelOp) { cancelOperation(); return; }
-                progress = (float) (100f * row / (rows - 1));
-                updateProgress("Loop 2 of 2:", (int)progress);
             }
             
-            for (i = 0; i < numFeatures; i++) {
-                if (featureN[i] > 0) {
-                    featureStdDeviation[i] = Math.sqrt(featureTotalDeviation[i] / (featureN[i] - 1));
-                }
+            for (Feature f : features.values()) {
+                f.calculate(stat, noData);
             }
             
             dataImage.close();
@@ -359,112 +360,37 @@ public class ExtractStatistics implements WhiteboxPlugin {
                 output = new WhiteboxRaster(outputHeader, "rw", dataImageHeader, 
                         WhiteboxRaster.DataType.FLOAT, noData);
                 output.setPreferredPalette(dataImage.getPreferredPalette());
-                output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
-                updateProgress("Outputing image data:", (int)progress);
-                if (statType.equals("average")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureAverage[i]);
-                            }
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
-                    }
-                } else if (statType.equals("minimum")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureMins[i]);
-                            }
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
-                    }
-                } else if (statType.equals("maximum")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureMaxs[i]);
-                            }
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
-                    }
-                } else if (statType.equals("range")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureMaxs[i] - featureMins[i]);
-                            }
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
-                    }
-                } else if (statType.equals("standard deviation")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureStdDeviation[i]);
+                if (stat == MAJORITY) {
+                    output.setDataScale(dataImage.getDataScale());
+                } else {
+                    output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+                }
+                feature = null;
+                updateProgress("Outputing image data:", 0);
+                for (row = 0; row < rows; row++) {
+                    featureData = featureImage.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        if (featureData[col] != featureNoData) {
+                            id = (int) featureData[col];
+                            if (feature == null || id != lastID) {
+                                feature = features.get(id);
+                                lastID = id;
                             }
+                            output.setValue(row, col, feature.value);
                         }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
                     }
-                } else if (statType.equals("total")) {
-                    for (row = 0; row < rows; row++) {
-                        featureData = featureImage.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            if (featureData[col] != featureNoData) {
-                                i = (int) (featureData[col] - minFeatureID);
-                                output.setValue(row, col, featureTotal[i]);
-                            }
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress("Outputing image data:", (int) progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
                     }
-                } else {
-                    showFeedback("Specified statistic type not recognized");
-                    return;
+                    progress = (float) (100f * row / (rows - 1));
+                    updateProgress("Outputing image data:", (int) progress);
                 }
     
                 output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
                 output.addMetadataEntry("Created on " + new Date());
+                output.addMetadataEntry("Statistic: " + statType);
                 output.close();
                 
                 returnData(outputHeader);
@@ -476,73 +402,26 @@ public class ExtractStatistics implements WhiteboxPlugin {
                 DecimalFormat df;
                 df = new DecimalFormat("0.000");
 
-                String retstr = null;
-                retstr = "EXTRACT STATISTICS\n\n";
-                retstr += "Data Image:\t" + dataImageShortName + "\n";
-                retstr += "Feature Image:\t" + featureImageShortName + "\n";
-                retstr += "NoData Value:\t" + noData + "\n";
-                retstr += "Output Stat:\t" + statType + "\n\n";
-                retstr += "ID\t" + "Value" + "\n";
+                StringBuilder retstr = new StringBuilder();
+                retstr.append("EXTRACT STATISTICS\n\n");
+                retstr.append("Data Image:\t").append(dataImageShortName).append("\n");
+                retstr.append("Feature Image:\t").append(featureImageShortName).append("\n");
+                retstr.append("NoData Value:\t").append(noData).append("\n");
+                retstr.append("Output Stat:\t").append(statType).append("\n\n");
+                retstr.append("ID\t").append("Value").append("\n");
                 
-                if (statType.equals("average")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(featureAverage[i]) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else if (statType.equals("minimum")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(featureMins[i]) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else if (statType.equals("maximum")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(featureMaxs[i]) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else if (statType.equals("range")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format((featureMaxs[i] - featureMins[i])) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else if (statType.equals("standard deviation")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(featureStdDeviation[i]) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else if (statType.equals("total")) {
-                    for (i = 0; i < numFeatures; i++) {
-                        if (featureN[i] > 0) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(featureTotal[i]) + "\n";
-                        } else if (featurePresent[i]) {
-                            retstr += (i + minFeatureID) + "\t" + df.format(noData) + "\n";
-                        }
-                    }
-                } else {
-                    showFeedback("Specified statistic type not recognized");
-                    return;
+                ArrayList<Integer> ids = new ArrayList<>(features.keySet());
+                Collections.sort(ids);
+                for (int i : ids) {
+                    retstr.append(i).append("\t").append(df.format(features.get(i).value)).append("\n");
                 }
                 if (!outputTextFile.toLowerCase().equals("not specified")) {
                     // output the text to a file
                     try(PrintWriter out = new PrintWriter(outputTextFile)){
-                        out.println(retstr);
+                        out.println(retstr.toString());
                     }
                 } else {
-                    returnData(retstr);
+                    returnData(retstr.toString());
                 }
 
             }
@@ -562,4 +441,103 @@ public class ExtractStatistics implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
+    
+    private static final int AVERAGE = 0;
+    private static final int MINIMUM = 1;
+    private static final int MAXIMUM = 2;
+    private static final int RANGE = 3;
+    private static final int STANDARD_DEVIATION = 4;
+    private static final int TOTAL = 5;
+    private static final int MEDIAN = 6;
+    private static final int MAJORITY = 7;
+    
+    /**
+     * Accumulates the data values within a feature. The values themselves are
+     * only stored for the median, and their frequencies for the majority.
+     */
+    private static class Feature {
+        long n = 0;
+        double total = 0;
+        double min = Double.POSITIVE_INFINITY;
+        double max = Double.NEGATIVE_INFINITY;
+        double totalDeviation = 0;
+        double[] values;
+        HashMap<Double, Long> frequencies;
+        double value;
+        
+        Feature(int stat) {
+            if (stat == MEDIAN) {
+                values = new double[16];
+            } else if (stat == MAJORITY) {
+                frequencies = new HashMap<>();
+            }
+        }
+        
+        void add(double z) {
+            if (values != null) {
+                if (n == values.length) {
+                    values = Arrays.copyOf(values, values.length * 2);
+                }
+                values[(int) n] = z;
+            }
+            if (frequencies != null) {
+                Long count = frequencies.get(z);
+                frequencies.put(z, count == null ? 1L : count + 1);
+            }
+            n++;
+            total += z;
+            if (z < min) {
+                min = z;
+            }
+            if (z > max) {
+                max = z;
+            }
+        }
+        
+        void calculate(int stat, double noData) {
+            if (n == 0) {
+                value = noData;
+                return;
+            }
+            switch (stat) {
+                case AVERAGE:
+                    value = total / n;
+                    break;
+                case MINIMUM:
+                    value = min;
+                    break;
+                case MAXIMUM:
+                    value = max;
+                    break;
+                case RANGE:
+                    value = max - min;
+                    break;
+                case STANDARD_DEVIATION:
+                    value = n > 1 ? Math.sqrt(totalDeviation / (n - 1)) : 0;
+                    break;
+                case TOTAL:
+                    value = total;
+                    break;
+                case MEDIAN:
+                    int m = (int) n;
+                    Arrays.sort(values, 0, m);
+                    value = (m % 2 == 1) ? values[m / 2] 
+                            : (values[m / 2 - 1] + values[m / 2]) / 2.0;
+                    values = null;
+                    break;
+                case MAJORITY:
+                    // ties are resolved in favour of the smallest value
+                    long highestCount = 0;
+                    for (Map.Entry<Double, Long> e : frequencies.entrySet()) {
+                        if (e.getValue() > highestCount 
+                                || (e.getValue() == highestCount && e.getKey() < value)) {
+                            highestCount = e.getValue();
+                            value = e.getKey();
+                        }
+                    }
+                    frequencies = null;
+                    break;
+            }
+        }
+    }
 }
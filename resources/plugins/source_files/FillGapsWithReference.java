@@ -0,0 +1,442 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.List;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.structures.KdTree;
+
+/**
+ * This tool fills the NoData voids in a raster, e.g. a lidar DSM, using the
+ * shape of a reference surface, e.g. a coarser DEM, adjusted by the offsets
+ * between the two rasters measured around the rim of each void.
+ */
+public class FillGapsWithReference implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "FillGapsWithReference";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Fill Gaps With Reference Surface";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Fills NoData voids in a raster using a reference surface and the offsets around each void.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"ImageEnhancement", "TerrainAnalysis", "LidarTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String referenceHeader = null;
+        String outputHeader = null;
+        int maxVoidSize = Integer.MAX_VALUE;
+        int numNeighbours = 12;
+        int row, col, x, y;
+        int progress = 0;
+        double z, ref;
+        int i, n;
+        int[] dX = {1, 1, 1, 0, -1, -1, -1, 0};
+        int[] dY = {-1, 0, 1, 1, 1, 0, -1, -1};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                referenceHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                if (!args[i].trim().isEmpty() && !args[i].toLowerCase().equals("not specified")) {
+                    maxVoidSize = Integer.parseInt(args[i].trim());
+                }
+            } else if (i == 4) {
+                if (!args[i].trim().isEmpty() && !args[i].toLowerCase().equals("not specified")) {
+                    numNeighbours = Integer.parseInt(args[i].trim());
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (referenceHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (maxVoidSize < 1 || numNeighbours < 1) {
+            showFeedback("The maximum void size and the number of rim neighbours must be greater than zero.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
+            int rows = image.getNumberRows();
+            int cols = image.getNumberColumns();
+            double noData = image.getNoDataValue();
+
+            // the reference surface need not share the grid of the input; it
+            // is sampled at the input cell centres.
+            WhiteboxRaster reference = new WhiteboxRaster(referenceHeader, "r");
+            double refNoData = reference.getNoDataValue();
+
+            double[][] data = new double[rows][];
+            for (row = 0; row < rows; row++) {
+                data[row] = image.getRowValues(row);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * row / (rows - 1));
+                updateProgress("Loop 1 of 2: ", progress);
+            }
+
+            // Each void is labelled in turn by a flood fill. Valid cells are
+            // given the label of the last void that they border, so that each
+            // rim cell is only counted once per void.
+            int[][] voidID = new int[rows][cols];
+            int[] stack = new int[rows * cols];
+            int stackSize;
+            int[] voidCells = new int[rows * cols];
+            int numVoidCells;
+            int[] rimCells = new int[rows * cols];
+            int numRimCells, numRimOffsets;
+            int currentID = 0;
+            int numVoids = 0;
+            int numFilledVoids = 0;
+            int numLargeVoids = 0;
+            long numFilledCells = 0;
+            KdTree<Double> tree;
+            List<KdTree.Entry<Double>> results;
+            double sumWeights, offset;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (data[row][col] != noData || voidID[row][col] != 0) {
+                        continue;
+                    }
+                    currentID++;
+                    numVoids++;
+                    numVoidCells = 0;
+                    numRimCells = 0;
+                    voidID[row][col] = currentID;
+                    stack[0] = row * cols + col;
+                    stackSize = 1;
+                    while (stackSize > 0) {
+                        stackSize--;
+                        voidCells[numVoidCells] = stack[stackSize];
+                        numVoidCells++;
+                        y = stack[stackSize] / cols;
+                        x = stack[stackSize] % cols;
+                        for (n = 0; n < 8; n++) {
+                            int rowN = y + dY[n];
+                            int colN = x + dX[n];
+                            // voids touching the edge of the grid simply have a one-sided rim
+                            if (rowN < 0 || rowN >= rows || colN < 0 || colN >= cols
+                                    || voidID[rowN][colN] == currentID) {
+                                continue;
+                            }
+                            if (data[rowN][colN] == noData) {
+                                if (voidID[rowN][colN] == 0) {
+                                    voidID[rowN][colN] = currentID;
+                                    stack[stackSize] = rowN * cols + colN;
+                                    stackSize++;
+                                }
+                            } else {
+                                voidID[rowN][colN] = currentID;
+                                rimCells[numRimCells] = rowN * cols + colN;
+                                numRimCells++;
+                            }
+                        }
+                    }
+
+                    if (numVoidCells > maxVoidSize) {
+                        numLargeVoids++;
+                        continue;
+                    }
+
+                    // interpolate the rim offsets across the void by inverse-distance weighting
+                    tree = new KdTree.SqrEuclid<Double>(2, null);
+                    numRimOffsets = 0;
+                    for (i = 0; i < numRimCells; i++) {
+                        y = rimCells[i] / cols;
+                        x = rimCells[i] % cols;
+                        ref = sampleReference(reference, image.getXCoordinateFromColumn(x),
+                                image.getYCoordinateFromRow(y), refNoData);
+                        if (ref != refNoData) {
+                            double[] entry = {y, x};
+                            tree.addPoint(entry, data[y][x] - ref);
+                            numRimOffsets++;
+                        }
+                    }
+                    if (numRimOffsets == 0) {
+                        continue;
+                    }
+                    n = Math.min(numNeighbours, numRimOffsets);
+                    for (i = 0; i < numVoidCells; i++) {
+                        y = voidCells[i] / cols;
+                        x = voidCells[i] % cols;
+                        ref = sampleReference(reference, image.getXCoordinateFromColumn(x),
+                                image.getYCoordinateFromRow(y), refNoData);
+                        if (ref == refNoData) {
+                            continue;
+                        }
+                        double[] entry = {y, x};
+                        results = tree.nearestNeighbor(entry, n, false);
+                        // the distances are squared, giving an IDW power of 2
+                        sumWeights = 0;
+                        offset = 0;
+                        for (KdTree.Entry<Double> result : results) {
+                            sumWeights += 1 / result.distance;
+                            offset += result.value / result.distance;
+                        }
+                        data[y][x] = ref + offset / sumWeights;
+                        numFilledCells++;
+                    }
+                    numFilledVoids++;
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * row / (rows - 1));
+                updateProgress("Loop 2 of 2: ", progress);
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            for (row = 0; row < rows; row++) {
+                output.setRowValues(row, data[row]);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Reference surface: " + referenceHeader);
+            output.addMetadataEntry("Voids filled: " + numFilledVoids + " of " + numVoids
+                    + " (" + numFilledCells + " grid cells)");
+            if (numLargeVoids > 0) {
+                output.addMetadataEntry("Voids larger than " + maxVoidSize
+                        + " grid cells left unfilled: " + numLargeVoids);
+            }
+
+            image.close();
+            reference.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Samples the reference surface at a point by bilinear interpolation of
+     * the four surrounding cell centres. NoData cells are excluded and the
+     * weights of the remaining cells renormalized.
+     */
+    private double sampleReference(WhiteboxRaster reference, double x, double y, double refNoData) {
+        double c = (x - reference.getWest()) / reference.getCellSizeX() - 0.5;
+        double r = (reference.getNorth() - y) / reference.getCellSizeY() - 0.5;
+        int c0 = (int) Math.floor(c);
+        int r0 = (int) Math.floor(r);
+        double fc = c - c0;
+        double fr = r - r0;
+        double[] weights = {(1 - fr) * (1 - fc), (1 - fr) * fc, fr * (1 - fc), fr * fc};
+        int[] rowOffsets = {0, 0, 1, 1};
+        int[] colOffsets = {0, 1, 0, 1};
+        double sumWeights = 0;
+        double sum = 0;
+        double z;
+        for (int a = 0; a < 4; a++) {
+            if (weights[a] > 0) {
+                z = reference.getValue(r0 + rowOffsets[a], c0 + colOffsets[a]);
+                if (z != refNoData) {
+                    sum += weights[a] * z;
+                    sumWeights += weights[a];
+                }
+            }
+        }
+        if (sumWeights > 0) {
+            return sum / sumWeights;
+        }
+        return refNoData;
+    }
+}
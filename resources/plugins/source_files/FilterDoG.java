@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -177,7 +178,7 @@ public class FilterDoG implements WhiteboxPlugin {
         int midPoint;
         int numPixelsInFilter1;
         int numPixelsInFilter2;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
         double sigma1 = 0;
         double sigma2 = 0;
         double recipRoot2PiTimesSigma1;
@@ -201,7 +202,7 @@ public class FilterDoG implements WhiteboxPlugin {
             } else if (i == 3) {
                 sigma2 = Double.parseDouble(args[i]);
             } else if (i == 4) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -230,7 +231,7 @@ public class FilterDoG implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -172,7 +173,7 @@ public class FilterGaussian implements WhiteboxPlugin {
         double[] weights;
         int midPoint;
         int numPixelsInFilter;
-        boolean reflectAtBorders = false;
+        EdgeMode edgeMode = EdgeMode.NODATA;
         double sigmaD = 0;
         double recipRoot2PiTimesSigmaD;
         double twoSigmaSqrD;
@@ -191,7 +192,7 @@ public class FilterGaussian implements WhiteboxPlugin {
             } else if (i == 2) {
                 sigmaD = Double.parseDouble(args[i]);
             } else if (i == 3) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -209,7 +210,7 @@ public class FilterGaussian implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,8 +18,10 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
 
 /**
  * This tool can be used to perform a Laplacian filter on a raster image, which can be used to emphasize the edges in an image.
@@ -163,17 +165,10 @@ public class FilterLaplacian implements WhiteboxPlugin {
         
         String inputHeader = null;
         String outputHeader = null;
-        int row, col, x, y;
-        double z;
-        float progress = 0;
-        int a;
-        double sum;
-        int[] dX;
-        int[] dY;
-        double[] weights;
-        int numPixelsInFilter;
-        boolean reflectAtBorders = true;
-        double centreValue;
+        final int[] dX;
+        final int[] dY;
+        final double[] weights;
+        EdgeMode edgeMode = EdgeMode.REFLECT;
         String filterSize = "3 x 3 (1)";
     
         if (args.length <= 0) {
@@ -184,6 +179,9 @@ public class FilterLaplacian implements WhiteboxPlugin {
         inputHeader = args[0];
         outputHeader = args[1];
         filterSize = args[2].toLowerCase().replace("\u00D7", "x");
+        if (args.length > 3) {
+            edgeMode = EdgeMode.fromString(args[3]);
+        }
         
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
@@ -192,14 +190,14 @@ public class FilterLaplacian implements WhiteboxPlugin {
         }
 
         try {
-            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            final WhiteboxRaster inputFile = WhiteboxRaster.openMemoryMapped(inputHeader);
+            inputFile.setEdgeMode(edgeMode);
 
-            int rows = inputFile.getNumberRows();
-            int cols = inputFile.getNumberColumns();
-            double noData = inputFile.getNoDataValue();
+            final int rows = inputFile.getNumberRows();
+            final int cols = inputFile.getNumberColumns();
+            final double noData = inputFile.getNoDataValue();
 
-            WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            final WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
             outputFile.setPreferredPalette("grey.pal");
             
             if (filterSize.equals("3 x 3 (1)")) {
@@ -234,33 +232,47 @@ public class FilterLaplacian implements WhiteboxPlugin {
                     0, 0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2};
             }
 
-            numPixelsInFilter = dX.length;
-            
-            for (row = 0; row < rows; row++) {
-                for (col = 0; col < cols; col++) {
-                    centreValue = inputFile.getValue(row, col);
-                    if (centreValue != noData) {
-                        sum = 0;
-                        for (a = 0; a < numPixelsInFilter; a++) {
-                            x = col + dX[a];
-                            y = row + dY[a];
-                            z = inputFile.getValue(y, x);
-                            if (z == noData) { z = centreValue; }
-                            sum += z * weights[a];
-                        }
-                        outputFile.setValue(row, col, sum);
+            final int numPixelsInFilter = dX.length;
 
-                    } else {
-                        outputFile.setValue(row, col, noData);
-                    }
+            // the rows are filtered in parallel if the input can be read by
+            // several threads at once, and are written in order
+            boolean completed = Parallel.ForRows(rows, cols, inputFile.isMemoryMapped(), new Parallel.RowTask() {
 
+                @Override
+                public void run(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        double centreValue = inputFile.getValue(row, col);
+                        if (centreValue != noData) {
+                            double sum = 0;
+                            for (int a = 0; a < numPixelsInFilter; a++) {
+                                double z = inputFile.getValue(row + dY[a], col + dX[a]);
+                                if (z == noData) { z = centreValue; }
+                                sum += z * weights[a];
+                            }
+                            data[col] = sum;
+                        } else {
+                            data[col] = noData;
+                        }
+                    }
                 }
-                if (cancelOp) {
-                    cancelOperation();
-                    return;
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        outputFile.setValue(row, col, data[col]);
+                    }
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
                 }
-                progress = (float) (100f * row / (rows - 1));
-                updateProgress((int) progress);
+            });
+
+            if (!completed) {
+                cancelOperation();
+                return;
             }
 
             outputFile.addMetadataEntry("Created by the "
@@ -18,6 +18,7 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
@@ -170,7 +171,7 @@ public class FilterLineDetection implements WhiteboxPlugin {
         double[] weights;
         int numPixelsInFilter;
         boolean absValuesOnly = false;
-        boolean reflectAtBorders = true;
+        EdgeMode edgeMode = EdgeMode.REFLECT;
         String direction = "vertical";
         double centreValue;
     
@@ -199,6 +200,8 @@ public class FilterLineDetection implements WhiteboxPlugin {
                 }
             } else if (i == 3) {
                 absValuesOnly = Boolean.parseBoolean(args[i]);
+            } else if (i == 4) {
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -210,7 +213,7 @@ public class FilterLineDetection implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            inputFile.setEdgeMode(edgeMode);
 
             int rows = inputFile.getNumberRows();
             int cols = inputFile.getNumberColumns();
@@ -18,8 +18,11 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
 
 /**
  * This tool performs a 3x3 or 5x5 Sobel edge-detection filter on a raster image, which is similar to the Prewitt filter in that it identifies areas of high slope in the input image through the calculation of slopes in the x and y directions.
@@ -160,20 +163,12 @@ public class FilterSobel implements WhiteboxPlugin {
         
         String inputHeader = null;
         String outputHeader = null;
-        int row, col, x, y;
-        double z;
-        float progress = 0;
-        int a;
-        double slopeX;
-        double slopeY;
-        double val;
         String filterSize = "3 x 3";
-        int numPixelsInFilter;
-        boolean reflectAtBorders = false;
-        int[] dX;
-        int[] dY;
-        double[] maskX;
-        double[] maskY;
+        EdgeMode edgeMode = EdgeMode.NODATA;
+        final int[] dX;
+        final int[] dY;
+        final double[] maskX;
+        final double[] maskY;
     
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -188,7 +183,7 @@ public class FilterSobel implements WhiteboxPlugin {
             } else if (i == 2) {
                 filterSize = args[i];
             } else if (i == 3) {
-                reflectAtBorders = Boolean.parseBoolean(args[i]);
+                edgeMode = EdgeMode.fromString(args[i]);
             }
         }
 
@@ -199,14 +194,14 @@ public class FilterSobel implements WhiteboxPlugin {
         }
 
         try {
-            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
-            inputFile.isReflectedAtEdges = reflectAtBorders;
+            final WhiteboxRaster inputFile = WhiteboxRaster.openMemoryMapped(inputHeader);
+            inputFile.setEdgeMode(edgeMode);
 
-            int rows = inputFile.getNumberRows();
-            int cols = inputFile.getNumberColumns();
-            double noData = inputFile.getNoDataValue();
+            final int rows = inputFile.getNumberRows();
+            final int cols = inputFile.getNumberColumns();
+            final double noData = inputFile.getNoDataValue();
 
-            WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            final WhiteboxRasterStreamWriter outputFile = new WhiteboxRasterStreamWriter(outputHeader, inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
             outputFile.setPreferredPalette("grey.pal");
            
             if (filterSize.endsWith("3 x 3")) {
@@ -221,41 +216,54 @@ public class FilterSobel implements WhiteboxPlugin {
                 maskY = new double[]{2, 3, 4, 3, 2, 1, 2, 3, 2, 1, 0, 0, 0, 0, 0, -1, -2, -3, -2, -1, -2, -3, -4, -3, -2};
             }
             
-            numPixelsInFilter = dX.length;
-        
-            for (row = 0; row < rows; row++) {
-                for (col = 0; col < cols; col++) {
-                    z = inputFile.getValue(row, col);
-                    if (z != noData) {
-                        slopeX = 0;
-                        slopeY = 0;
-                        for (a = 0; a < numPixelsInFilter; a++) {
-                            x = col + dX[a];
-                            y = row + dY[a];
-                            val = inputFile.getValue(y, x);
-                            if (val == noData) {
-                                // replace it with z
-                                val = z;
+            final int numPixelsInFilter = dX.length;
+
+            // the rows are filtered in parallel if the input can be read by
+            // several threads at once, and are written in order
+            boolean completed = Parallel.ForRows(rows, cols, inputFile.isMemoryMapped(), new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        double z = inputFile.getValue(row, col);
+                        if (z != noData) {
+                            double slopeX = 0;
+                            double slopeY = 0;
+                            for (int a = 0; a < numPixelsInFilter; a++) {
+                                double val = inputFile.getValue(row + dY[a], col + dX[a]);
+                                if (val == noData) {
+                                    // replace it with z
+                                    val = z;
+                                }
+                                slopeX += val * maskX[a];
+                                slopeY += val * maskY[a];
                             }
-                            slopeX += val * maskX[a];
-                            slopeY += val * maskY[a];
-                        }
 
-                        val = Math.sqrt(slopeX * slopeX + slopeY * slopeY);
-                        outputFile.setValue(row, col, val);
-                    } else {
-                        outputFile.setValue(row, col, noData);
+                            data[col] = Math.sqrt(slopeX * slopeX + slopeY * slopeY);
+                        } else {
+                            data[col] = noData;
+                        }
                     }
-
                 }
-                if (cancelOp) {
-                    cancelOperation();
-                    return;
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) throws Exception {
+                    outputFile.writeRow(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
                 }
-                progress = (float) (100f * row / (rows - 1));
-                updateProgress((int) progress);
-            }
+            });
 
+            if (!completed) {
+                inputFile.close();
+                outputFile.abort();
+                cancelOperation();
+                return;
+            }
 
             outputFile.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
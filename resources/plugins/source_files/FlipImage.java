@@ -18,10 +18,11 @@ package plugins;
 
 import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 /**
- * This tool will flip (i.e., reflect) an image either vertically, horizontally, or both.
+ * This tool will flip (i.e., reflect) an image either vertically, horizontally, or both, rotate it by 90, 180, or 270 degrees, or transpose it.
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
 public class FlipImage implements WhiteboxPlugin {
@@ -50,7 +51,7 @@ public class FlipImage implements WhiteboxPlugin {
      */
     @Override
     public String getToolDescription() {
-    	return "Reflects an image in the vertical or horizontal axis.";
+    	return "Reflects, rotates, or transposes an image.";
     }
     /**
      * Used to identify which toolboxes this plugin tool should be listed in.
@@ -174,12 +175,24 @@ public class FlipImage implements WhiteboxPlugin {
             } else if (i == 1) {
                 outputHeader = args[i];
             } else if (i == 2) {
-                if (args[i].toLowerCase().contains("v")) {
+                String str = args[i].toLowerCase();
+                if (str.contains("270")) {
+                    reflectionAxis = "rotate 270";
+                } else if (str.contains("180")) {
+                    reflectionAxis = "rotate 180";
+                } else if (str.contains("90")) {
+                    reflectionAxis = "rotate 90";
+                } else if (str.contains("trans")) {
+                    reflectionAxis = "transpose";
+                } else if (str.contains("v")) {
                     reflectionAxis = "vertical";
-                } else if (args[i].toLowerCase().contains("ho")) {
+                } else if (str.contains("ho")) {
                     reflectionAxis = "horizontal";
-                } else if (args[i].toLowerCase().contains("b")) {
+                } else if (str.contains("b")) {
                     reflectionAxis = "both";
+                } else {
+                    showFeedback("Flip method not recognized.");
+                    return;
                 }
             }
         }
@@ -196,49 +209,75 @@ public class FlipImage implements WhiteboxPlugin {
             numCols = image.getNumberColumns();
             int rowsLessOne = numRows - 1;
             int colsLessOne = numCols - 1;
+            double noData = image.getNoDataValue();
             
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, 0);
+            DataType dataType = image.getDataType();
+            if (dataType == DataType.I32) {
+                dataType = DataType.FLOAT;
+            }
+            
+            // rotations and transposition swap the rows and columns about the
+            // centre of the image; reflections do not change the extent.
+            boolean swapAxes = reflectionAxis.equals("rotate 90") 
+                    || reflectionAxis.equals("rotate 270") 
+                    || reflectionAxis.equals("transpose");
+            WhiteboxRaster output;
+            if (swapAxes) {
+                double centreX = (image.getEast() + image.getWest()) / 2.0;
+                double centreY = (image.getNorth() + image.getSouth()) / 2.0;
+                double halfWidth = (image.getEast() - image.getWest()) / 2.0;
+                double halfHeight = (image.getNorth() - image.getSouth()) / 2.0;
+                output = new WhiteboxRaster(outputHeader, centreY + halfWidth, 
+                        centreY - halfWidth, centreX + halfHeight, centreX - halfHeight, 
+                        numCols, numRows, image.getDataScale(), dataType, noData, noData);
+                output.setPreferredPalette(image.getPreferredPalette());
+                output.setZUnits(image.getZUnits());
+                output.setXYUnits(image.getXYUnits());
+                output.setProjection(image.getProjection());
+            } else {
+                output = new WhiteboxRaster(outputHeader, "rw", inputHeader, dataType, noData);
+            }
             
             double[] data;
-            if (reflectionAxis.equals("vertical")) {
-                for (row = 0; row < numRows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < numCols; col++) {
-                        output.setValue(rowsLessOne - row, col, data[col]);
+            int outRow, outCol;
+            for (row = 0; row < numRows; row++) {
+                data = image.getRowValues(row);
+                for (col = 0; col < numCols; col++) {
+                    switch (reflectionAxis) {
+                        case "vertical":
+                            outRow = rowsLessOne - row;
+                            outCol = col;
+                            break;
+                        case "horizontal":
+                            outRow = row;
+                            outCol = colsLessOne - col;
+                            break;
+                        case "both":
+                        case "rotate 180":
+                            outRow = rowsLessOne - row;
+                            outCol = colsLessOne - col;
+                            break;
+                        case "rotate 90": // clockwise
+                            outRow = col;
+                            outCol = rowsLessOne - row;
+                            break;
+                        case "rotate 270":
+                            outRow = colsLessOne - col;
+                            outCol = row;
+                            break;
+                        default: // transpose
+                            outRow = col;
+                            outCol = row;
+                            break;
                     }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (numRows - 1));
-                    updateProgress("Finding patch min row and columns:", progress);
+                    output.setValue(outRow, outCol, data[col]);
                 }
-            } else if (reflectionAxis.equals("horizontal")) {
-                for (row = 0; row < numRows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < numCols; col++) {
-                        output.setValue(row, colsLessOne - col, data[col]);
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (numRows - 1));
-                    updateProgress("Finding patch min row and columns:", progress);
-                }
-            } else if (reflectionAxis.equals("both")) {
-                for (row = 0; row < numRows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < numCols; col++) {
-                        output.setValue(rowsLessOne - row, colsLessOne - col, data[col]);
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (numRows - 1));
-                    updateProgress("Finding patch min row and columns:", progress);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
                 }
+                progress = (int) (100f * row / (numRows - 1));
+                updateProgress(progress);
             }
             
             output.setDisplayMinimum(image.getDisplayMinimum());
@@ -246,6 +285,7 @@ public class FlipImage implements WhiteboxPlugin {
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Operation: " + reflectionAxis);
 
             image.close();
             output.close();
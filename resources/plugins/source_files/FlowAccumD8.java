@@ -238,8 +238,13 @@ public class FlowAccumD8 implements WhiteboxPlugin {
             double noData = pntr.getNoDataValue();
             gridRes = pntr.getCellSizeX();
 
+            // The accumulated values are stored as doubles. A float output 
+            // cannot represent cell counts above 2^24 (16,777,216) exactly and 
+            // adding one cell to such a value leaves it unchanged whenever 
+            // the grid is paged to disc, whereas a double counts exactly up 
+            // to 2^53.
             WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
-                    inputHeader, WhiteboxRaster.DataType.FLOAT, 1);
+                    inputHeader, WhiteboxRaster.DataType.DOUBLE, 1);
             output.setPreferredPalette("blueyellow.pal");
             output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
             output.setZUnits("dimensionless");
@@ -0,0 +1,470 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.ArrayList;
+import java.util.Date;
+import java.util.List;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates geographically weighted statistics of a raster, i.e.
+ * the mean, standard deviation, and skewness of the values around each cell,
+ * weighted by a Gaussian kernel of distance, and the geographically weighted
+ * correlation between two rasters. These local statistics reveal spatial
+ * non-stationarity that is hidden by global statistics.
+ */
+public class GeographicallyWeightedStatistics implements WhiteboxPlugin {
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "GeographicallyWeightedStatistics";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Geographically Weighted Statistics";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Calculates local statistics of a raster weighted by a Gaussian kernel of distance.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"StatisticalTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 8) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String inputHeader = args[0];
+        String inputHeader2 = null;
+        if (!args[1].toLowerCase().contains("not specified")) {
+            inputHeader2 = args[1];
+        }
+        String outputPrefix = args[2];
+        double bandwidth = Double.parseDouble(args[3]);
+        boolean outputMean = Boolean.parseBoolean(args[4]);
+        boolean outputStdDev = Boolean.parseBoolean(args[5]);
+        boolean outputSkewness = Boolean.parseBoolean(args[6]);
+        boolean outputCorrelation = Boolean.parseBoolean(args[7]);
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputPrefix == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (!(bandwidth > 0)) {
+            showFeedback("The bandwidth must be greater than zero.");
+            return;
+        }
+        if (!outputMean && !outputStdDev && !outputSkewness && !outputCorrelation) {
+            showFeedback("At least one statistic must be selected.");
+            return;
+        }
+        if (outputCorrelation && inputHeader2 == null) {
+            showFeedback("A second input raster is required to calculate the correlation.");
+            return;
+        }
+        if (outputPrefix.toLowerCase().endsWith(".dep")) {
+            outputPrefix = outputPrefix.substring(0, outputPrefix.length() - 4);
+        }
+
+        try {
+            int row, col, r, c, k;
+            double z, z2, w;
+            int progress, oldProgress = -1;
+
+            WhiteboxRaster inputFile = new WhiteboxRaster(inputHeader, "r");
+            int rows = inputFile.getNumberRows();
+            int cols = inputFile.getNumberColumns();
+            double noData = inputFile.getNoDataValue();
+
+            // read the input values into memory, with the second raster's
+            // values aligned to the first's.
+            updateProgress("Reading data:", 0);
+            double[][] data = new double[rows][];
+            for (row = 0; row < rows; row++) {
+                data[row] = inputFile.getRowValues(row);
+            }
+            double[][] data2 = null;
+            double noData2 = noData;
+            if (outputCorrelation) {
+                WhiteboxRaster inputFile2 = new WhiteboxRaster(inputHeader2, "r");
+                if (inputFile2.getNumberRows() != rows || inputFile2.getNumberColumns() != cols) {
+                    showFeedback("The input rasters must have the same number of rows and columns.");
+                    return;
+                }
+                noData2 = inputFile2.getNoDataValue();
+                data2 = new double[rows][];
+                for (row = 0; row < rows; row++) {
+                    data2[row] = inputFile2.getRowValues(row);
+                }
+                inputFile2.close();
+            }
+
+            // The kernel is a list of the offsets of the cells within the
+            // bandwidth of a cell and their Gaussian weights.
+            double cellSizeX = inputFile.getCellSizeX();
+            double cellSizeY = inputFile.getCellSizeY();
+            int radiusRows = (int) Math.floor(bandwidth / cellSizeY);
+            int radiusCols = (int) Math.floor(bandwidth / cellSizeX);
+            List<int[]> offsets = new ArrayList<>();
+            List<Double> weightList = new ArrayList<>();
+            for (r = -radiusRows; r <= radiusRows; r++) {
+                for (c = -radiusCols; c <= radiusCols; c++) {
+                    double dist = Math.sqrt(r * cellSizeY * r * cellSizeY + c * cellSizeX * c * cellSizeX);
+                    if (dist <= bandwidth) {
+                        offsets.add(new int[]{r, c});
+                        weightList.add(Math.exp(-0.5 * (dist / bandwidth) * (dist / bandwidth)));
+                    }
+                }
+            }
+            int numOffsets = offsets.size();
+            int[] dY = new int[numOffsets];
+            int[] dX = new int[numOffsets];
+            double[] weights = new double[numOffsets];
+            for (k = 0; k < numOffsets; k++) {
+                dY[k] = offsets.get(k)[0];
+                dX[k] = offsets.get(k)[1];
+                weights[k] = weightList.get(k);
+            }
+
+            WhiteboxRasterStreamWriter meanFile = null;
+            WhiteboxRasterStreamWriter stdDevFile = null;
+            WhiteboxRasterStreamWriter skewnessFile = null;
+            WhiteboxRasterStreamWriter correlationFile = null;
+            List<String> outputHeaders = new ArrayList<>();
+            List<WhiteboxRasterStreamWriter> outputFiles = new ArrayList<>();
+            if (outputMean) {
+                outputHeaders.add(outputPrefix + "_mean.dep");
+                meanFile = new WhiteboxRasterStreamWriter(outputPrefix + "_mean.dep", inputHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                meanFile.setPreferredPalette(inputFile.getPreferredPalette());
+                outputFiles.add(meanFile);
+            }
+            if (outputStdDev) {
+                outputHeaders.add(outputPrefix + "_std_dev.dep");
+                stdDevFile = new WhiteboxRasterStreamWriter(outputPrefix + "_std_dev.dep", inputHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                stdDevFile.setPreferredPalette("spectrum.pal");
+                outputFiles.add(stdDevFile);
+            }
+            if (outputSkewness) {
+                outputHeaders.add(outputPrefix + "_skewness.dep");
+                skewnessFile = new WhiteboxRasterStreamWriter(outputPrefix + "_skewness.dep", inputHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                skewnessFile.setPreferredPalette("blue_white_red.pal");
+                outputFiles.add(skewnessFile);
+            }
+            if (outputCorrelation) {
+                outputHeaders.add(outputPrefix + "_correlation.dep");
+                correlationFile = new WhiteboxRasterStreamWriter(outputPrefix + "_correlation.dep", inputHeader,
+                        WhiteboxRaster.DataType.FLOAT, noData);
+                correlationFile.setPreferredPalette("blue_white_red.pal");
+                outputFiles.add(correlationFile);
+            }
+
+            // The weighted moments are accumulated about the value of the
+            // centre cell, which avoids the loss of precision that comes from
+            // summing the squares and cubes of large values.
+            updateProgress("Calculating statistics:", 0);
+            double[] meanData = new double[cols];
+            double[] stdDevData = new double[cols];
+            double[] skewnessData = new double[cols];
+            double[] correlationData = new double[cols];
+            double sumW, sumX, sumXX, sumXXX, sumY, sumYY, sumXY, x, y, x0, y0;
+            double mean, variance, stdDev, meanY, varianceY, covariance;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    meanData[col] = noData;
+                    stdDevData[col] = noData;
+                    skewnessData[col] = noData;
+                    correlationData[col] = noData;
+                    x0 = data[row][col];
+                    if (x0 == noData) {
+                        continue;
+                    }
+                    sumW = 0;
+                    sumX = 0;
+                    sumXX = 0;
+                    sumXXX = 0;
+                    for (k = 0; k < numOffsets; k++) {
+                        r = row + dY[k];
+                        c = col + dX[k];
+                        if (r >= 0 && r < rows && c >= 0 && c < cols) {
+                            z = data[r][c];
+                            if (z != noData) {
+                                w = weights[k];
+                                x = z - x0;
+                                sumW += w;
+                                sumX += w * x;
+                                sumXX += w * x * x;
+                                sumXXX += w * x * x * x;
+                            }
+                        }
+                    }
+                    mean = sumX / sumW;
+                    variance = Math.max(sumXX / sumW - mean * mean, 0);
+                    stdDev = Math.sqrt(variance);
+                    meanData[col] = x0 + mean;
+                    stdDevData[col] = stdDev;
+                    if (stdDev > 0) {
+                        skewnessData[col] = (sumXXX / sumW - 3 * mean * sumXX / sumW
+                                + 2 * mean * mean * mean) / (variance * stdDev);
+                    }
+
+                    if (outputCorrelation) {
+                        y0 = data2[row][col];
+                        if (y0 == noData2) {
+                            continue;
+                        }
+                        sumW = 0;
+                        sumX = 0;
+                        sumXX = 0;
+                        sumY = 0;
+                        sumYY = 0;
+                        sumXY = 0;
+                        for (k = 0; k < numOffsets; k++) {
+                            r = row + dY[k];
+                            c = col + dX[k];
+                            if (r >= 0 && r < rows && c >= 0 && c < cols) {
+                                z = data[r][c];
+                                z2 = data2[r][c];
+                                if (z != noData && z2 != noData2) {
+                                    w = weights[k];
+                                    x = z - x0;
+                                    y = z2 - y0;
+                                    sumW += w;
+                                    sumX += w * x;
+                                    sumXX += w * x * x;
+                                    sumY += w * y;
+                                    sumYY += w * y * y;
+                                    sumXY += w * x * y;
+                                }
+                            }
+                        }
+                        mean = sumX / sumW;
+                        meanY = sumY / sumW;
+                        variance = sumXX / sumW - mean * mean;
+                        varianceY = sumYY / sumW - meanY * meanY;
+                        covariance = sumXY / sumW - mean * meanY;
+                        if (variance > 0 && varianceY > 0) {
+                            correlationData[col] = Math.max(-1, Math.min(1,
+                                    covariance / Math.sqrt(variance * varianceY)));
+                        }
+                    }
+                }
+                if (meanFile != null) {
+                    meanFile.writeRow(row, meanData);
+                }
+                if (stdDevFile != null) {
+                    stdDevFile.writeRow(row, stdDevData);
+                }
+                if (skewnessFile != null) {
+                    skewnessFile.writeRow(row, skewnessData);
+                }
+                if (correlationFile != null) {
+                    correlationFile.writeRow(row, correlationData);
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        for (WhiteboxRasterStreamWriter outputFile : outputFiles) {
+                            outputFile.abort();
+                        }
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            for (WhiteboxRasterStreamWriter outputFile : outputFiles) {
+                outputFile.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                outputFile.addMetadataEntry("Created on " + new Date());
+                outputFile.addMetadataEntry("Bandwidth: " + bandwidth);
+                outputFile.close();
+            }
+            inputFile.close();
+
+            // returning a header file string displays the image.
+            for (String outputHeader : outputHeaders) {
+                returnData(outputHeader);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
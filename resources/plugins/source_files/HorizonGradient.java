@@ -0,0 +1,447 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
+
+/**
+ * This tool calculates the gradient of a digital elevation model (DEM) in a
+ * specified direction, i.e. the directional derivative of the surface.
+ */
+public class HorizonGradient implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "HorizonGradient";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Horizon Gradient";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the gradient of a digital elevation model (DEM) in a "
+                + "specified direction.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SurfDerivatives" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        double azimuth = 0;
+        double weight = 1.0;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                azimuth = Double.parseDouble(args[i]) % 360;
+                if (azimuth < 0) {
+                    azimuth += 360;
+                }
+            } else if (i == 3) {
+                if (!args[i].toLowerCase().equals("not specified")) {
+                    weight = Double.parseDouble(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double gradientWeight = weight;
+
+            // the cell sizes of each row, in the units of the elevations, as
+            // in the Surface Area tool
+            final double[] gridResX = new double[rows];
+            final double[] gridResY = new double[rows];
+            boolean geographic = DEM.getXYUnits().toLowerCase().contains("deg")
+                    || DEM.getProjection().toLowerCase().contains("geog");
+            for (int row = 0; row < rows; row++) {
+                gridResX[row] = DEM.getCellSizeX();
+                gridResY[row] = DEM.getCellSizeY();
+                if (geographic) {
+                    double lat = DEM.getNorth() - (row + 0.5) * (DEM.getNorth() - DEM.getSouth()) / rows;
+                    if (lat <= 90 && lat >= -90) {
+                        gridResX[row] *= 113200 * Math.cos(Math.toRadians(lat));
+                        gridResY[row] *= 113200;
+                    }
+                }
+            }
+
+            final double[][] elevations = new double[rows][];
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
+            }
+
+            final WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("blue_white_red.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits("dimensionless");
+
+            // the unit vector towards the azimuth; rows increase towards the south
+            double sinAz = Math.sin(Math.toRadians(azimuth));
+            double cosAz = Math.cos(Math.toRadians(azimuth));
+            if (Math.abs(sinAz) < 1e-12) {
+                sinAz = 0;
+            }
+            if (Math.abs(cosAz) < 1e-12) {
+                cosAz = 0;
+            }
+            final double dirX = sinAz;
+            final double dirY = cosAz;
+
+            boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    // the step of one cell width along the azimuth, in map
+                    // units and in rows and columns
+                    double step = gridResX[row];
+                    double dCol = step * dirX / gridResX[row];
+                    double dRow = -step * dirY / gridResY[row];
+                    double[] z = new double[5];
+                    for (int col = 0; col < cols; col++) {
+                        z[2] = elevations[row][col];
+                        if (z[2] != noData) {
+                            // the elevations at -2, -1, +1 and +2 cell widths
+                            for (int k = -2; k <= 2; k++) {
+                                if (k != 0) {
+                                    z[k + 2] = getBilinearValue(elevations, noData,
+                                            row + k * dRow, col + k * dCol);
+                                }
+                            }
+                            double gradient = getGradient(z, step, noData);
+                            if (Double.isNaN(gradient)) {
+                                gradient = getGradientFromComponents(elevations, row, col,
+                                        noData, gridResX[row], gridResY[row], dirX, dirY);
+                            }
+                            data[col] = gradientWeight * gradient;
+                        } else {
+                            data[col] = noData;
+                        }
+                    }
+                }
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    output.setRowValues(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
+                }
+            });
+
+            if (!completed) {
+                DEM.close();
+                output.close();
+                cancelOperation();
+                return;
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Azimuth: " + azimuth + " degrees clockwise from north "
+                    + "(positive values rise towards the azimuth)");
+            output.addMetadataEntry("Weight: " + weight);
+
+            DEM.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Estimates the gradient at a cell from the elevations at -2, -1, 0, +1
+     * and +2 steps along the azimuth. The five-point central difference is
+     * used where all of the elevations are known. Near the edges of the DEM
+     * and NoData cells, the gradient falls back on one-sided differences of
+     * the second order, and then of the first, all of which are exact on a
+     * plane.
+     *
+     * @return the gradient, or NaN if no elevation along the azimuth is known.
+     */
+    private static double getGradient(double[] z, double step, double noData) {
+        boolean back2 = z[0] != noData;
+        boolean back1 = z[1] != noData;
+        boolean ahead1 = z[3] != noData;
+        boolean ahead2 = z[4] != noData;
+        if (back2 && back1 && ahead1 && ahead2) {
+            return (z[0] - 8 * z[1] + 8 * z[3] - z[4]) / (12 * step);
+        } else if (back1 && ahead1) {
+            return (z[3] - z[1]) / (2 * step);
+        } else if (ahead1 && ahead2) {
+            return (-3 * z[2] + 4 * z[3] - z[4]) / (2 * step);
+        } else if (back1 && back2) {
+            return (3 * z[2] - 4 * z[1] + z[0]) / (2 * step);
+        } else if (ahead1) {
+            return (z[3] - z[2]) / step;
+        } else if (back1) {
+            return (z[2] - z[1]) / step;
+        }
+        return Double.NaN;
+    }
+
+    /**
+     * Estimates the gradient at a cell from its components towards the east
+     * and the north, for cells such as those in the corners of the DEM that
+     * have no known elevation along the azimuth. Each component is found from
+     * the neighbours in the row or column of the cell, as in getGradient, and
+     * is zero if neither neighbour is known.
+     */
+    private static double getGradientFromComponents(double[][] elevations, int row, int col,
+            double noData, double cellSizeX, double cellSizeY, double dirX, double dirY) {
+        double[] z = new double[5];
+        Arrays.fill(z, noData);
+        z[2] = elevations[row][col];
+        z[1] = col > 0 ? elevations[row][col - 1] : noData;
+        z[3] = col < elevations[row].length - 1 ? elevations[row][col + 1] : noData;
+        double east = getGradient(z, cellSizeX, noData);
+        // rows increase towards the south
+        z[1] = row < elevations.length - 1 ? elevations[row + 1][col] : noData;
+        z[3] = row > 0 ? elevations[row - 1][col] : noData;
+        double north = getGradient(z, cellSizeY, noData);
+        return (Double.isNaN(east) ? 0 : east * dirX) + (Double.isNaN(north) ? 0 : north * dirY);
+    }
+
+    /**
+     * Estimates the value at a fractional grid position by bilinear
+     * interpolation between the centres of the four surrounding cells. Unlike
+     * WhiteboxRaster.getBilinearValue, positions beyond the centres of the
+     * edge cells are not clamped to the edge, so that every sample lies on
+     * the surface between known cells.
+     *
+     * @return the interpolated value, or NoData if the position is outside of
+     * the cell centres or any of the surrounding cells is NoData.
+     */
+    private static double getBilinearValue(double[][] data, double noData,
+            double row, double column) {
+        int rows = data.length;
+        int cols = data[0].length;
+        // allow for round-off in positions that fall on the edge cells
+        if (Math.abs(row - Math.rint(row)) < 1e-9) {
+            row = Math.rint(row);
+        }
+        if (Math.abs(column - Math.rint(column)) < 1e-9) {
+            column = Math.rint(column);
+        }
+        if (!(row >= 0 && row <= rows - 1 && column >= 0 && column <= cols - 1)) {
+            return noData;
+        }
+        int r0 = (int) Math.floor(row);
+        int c0 = (int) Math.floor(column);
+        double dr = row - r0;
+        double dc = column - c0;
+        // cells with a weight of zero are not needed
+        int r1 = dr > 0 ? r0 + 1 : r0;
+        int c1 = dc > 0 ? c0 + 1 : c0;
+        double z00 = data[r0][c0];
+        double z01 = data[r0][c1];
+        double z10 = data[r1][c0];
+        double z11 = data[r1][c1];
+        if (z00 == noData || z01 == noData || z10 == noData || z11 == noData) {
+            return noData;
+        }
+        return z00 * (1 - dr) * (1 - dc) + z01 * (1 - dr) * dc
+                + z10 * dr * (1 - dc) + z11 * dr * dc;
+    }
+}
@@ -16,7 +16,21 @@
  */
 package plugins;
 
+import java.awt.BasicStroke;
+import java.awt.Color;
+import java.awt.Font;
+import java.awt.FontMetrics;
+import java.awt.Graphics2D;
+import java.awt.RenderingHints;
+import java.awt.geom.AffineTransform;
+import java.awt.image.BufferedImage;
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
 import java.text.DecimalFormat;
+import java.util.Date;
+import javax.imageio.ImageIO;
 import org.apache.commons.math3.distribution.FDistribution;
 import org.apache.commons.math3.distribution.TDistribution;
 import whitebox.geospatialfiles.WhiteboxRaster;
@@ -192,20 +206,21 @@ public class ImageRegression implements WhiteboxPlugin {
     @Override
     public void run() {
         amIActive = true;
-        
+
         String inputHeader1 = null;
         String inputHeader2 = null;
         String outputHeader = null;
+        String reportFile = null;
         boolean outputResidualImage = false;
         double yEstimate;
         double residual;
         boolean standardizeResiduals = false;
-                
+
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
             return;
         }
-        
+
         inputHeader1 = args[0];
         inputHeader2 = args[1];
         if (!args[2].toLowerCase().equals("not specified")) {
@@ -213,7 +228,10 @@ public class ImageRegression implements WhiteboxPlugin {
             outputResidualImage = true;
             standardizeResiduals = Boolean.parseBoolean(args[3]);
         }
-        
+        if (args.length > 4 && !args[4].toLowerCase().equals("not specified")) {
+            reportFile = args[4];
+        }
+
         // check to see that the inputHeader1 and outputHeader are not null.
         if (inputHeader1 == null || inputHeader2 == null) {
             showFeedback("One or more of the input parameters have not been set properly.");
@@ -224,7 +242,7 @@ public class ImageRegression implements WhiteboxPlugin {
             int row, col;
             double x, y;
             float progress = 0;
-            
+
             WhiteboxRaster image1 = new WhiteboxRaster(inputHeader1, "r");
             int rows = image1.getNumberRows();
             int cols = image1.getNumberColumns();
@@ -238,16 +256,15 @@ public class ImageRegression implements WhiteboxPlugin {
             double noData2 = image2.getNoDataValue();
 
             double sumX = 0, sumY = 0, sumXY = 0, sumXX = 0, sumYY = 0;
+            double xMin = Double.POSITIVE_INFINITY, xMax = Double.NEGATIVE_INFINITY;
+            double yMin = Double.POSITIVE_INFINITY, yMax = Double.NEGATIVE_INFINITY;
             long N = 0;
-            
+
             double[] data1, data2;
             for (row = 0; row < rows; row++) {
                 data1 = image1.getRowValues(row);
                 data2 = image2.getRowValues(row);
                 for (col = 0; col < cols; col++) {
-                    if (col == 655) {
-                        col = 655;
-                    }
                     x = data1[col];
                     y = data2[col];
                     if (x != noData1 && y != noData2) {
@@ -256,6 +273,10 @@ public class ImageRegression implements WhiteboxPlugin {
                         sumXY += x * y;
                         sumXX += x * x;
                         sumYY += y * y;
+                        if (x < xMin) { xMin = x; }
+                        if (x > xMax) { xMax = x; }
+                        if (y < yMin) { yMin = y; }
+                        if (y > yMax) { yMax = y; }
                         N++;
                     }
                 }
@@ -264,9 +285,18 @@ public class ImageRegression implements WhiteboxPlugin {
                     return;
                 }
                 progress = (float) (100f * row / (rows - 1));
-                updateProgress((int) progress);
+                updateProgress("Calculating sums:", (int) progress);
+            }
+
+            if (N < 3) {
+                showFeedback("The input images must have at least three co-located grid cells that are not NoData.");
+                return;
             }
-            
+            if (xMax == xMin || yMax == yMin) {
+                showFeedback("The regression cannot be calculated because one of the input images is constant over the co-located valid grid cells.");
+                return;
+            }
+
             double slope = (N * sumXY - (sumX * sumY)) / (N * sumXX - (sumX * sumX));
             double intercept = (sumY - slope * sumX) / N;
             double r = (N * sumXY - (sumX * sumY)) / ((Math.sqrt(N * sumXX - (sumX * sumX)) * (Math.sqrt(N * sumYY - (sumY * sumY)))));
@@ -278,6 +308,21 @@ public class ImageRegression implements WhiteboxPlugin {
             double SSerror = 0;
             int dfReg = 1;
             int dfError = (int)(N - 2);
+
+            /* The scatter plot is summarized by counting the number of
+             * grid cells falling within hexagonal bins in plot space. This
+             * is done during the same pass as the sums of squares.
+             */
+            double hexSize = HEX_RADIUS;
+            double hexWidth = Math.sqrt(3.0) * hexSize;
+            int numHexRows = (int) (PLOT_HEIGHT / (1.5 * hexSize)) + 3;
+            int qOffset = numHexRows / 2 + 2;
+            int numHexCols = (int) (PLOT_WIDTH / hexWidth) + qOffset + 3;
+            long[][] hexCounts = new long[numHexRows][numHexCols];
+            double xRange = xMax - xMin;
+            double yRange = yMax - yMin;
+            double px, py, qf, rf, sf, rq, rr, rs;
+            int q, hr;
             for (row = 0; row < rows; row++) {
                 data1 = image1.getRowValues(row);
                 data2 = image2.getRowValues(row);
@@ -288,6 +333,26 @@ public class ImageRegression implements WhiteboxPlugin {
                         yEstimate = slope * x + intercept;
                         SSerror += (y - yEstimate) * (y - yEstimate);
                         SStotal += (y - yMean) * (y - yMean);
+
+                        // find the axial coordinates of the containing hexagon
+                        px = (x - xMin) / xRange * PLOT_WIDTH;
+                        py = (yMax - y) / yRange * PLOT_HEIGHT;
+                        qf = (Math.sqrt(3.0) / 3.0 * px - py / 3.0) / hexSize;
+                        rf = (2.0 / 3.0 * py) / hexSize;
+                        sf = -qf - rf;
+                        rq = Math.round(qf);
+                        rr = Math.round(rf);
+                        rs = Math.round(sf);
+                        if (Math.abs(rq - qf) > Math.abs(rr - rf) && Math.abs(rq - qf) > Math.abs(rs - sf)) {
+                            rq = -rr - rs;
+                        } else if (Math.abs(rr - rf) > Math.abs(rs - sf)) {
+                            rr = -rq - rs;
+                        }
+                        hr = (int) rr + 1;
+                        q = (int) rq + qOffset;
+                        if (hr >= 0 && hr < numHexRows && q >= 0 && q < numHexCols) {
+                            hexCounts[hr][q]++;
+                        }
                     }
                 }
                 if (cancelOp) {
@@ -295,14 +360,14 @@ public class ImageRegression implements WhiteboxPlugin {
                     return;
                 }
                 progress = (float) (100f * row / (rows - 1));
-                updateProgress((int) progress);
+                updateProgress("Calculating residuals:", (int) progress);
             }
             SSreg =  SStotal - SSerror;
             double MSreg = SSreg / dfReg;
             double MSerror = SSerror / dfError;
             double Fstat = MSreg / MSerror;
             double SEofEstimate = Math.sqrt(MSerror);
-            
+
             FDistribution f = new FDistribution(1, dfError);
             double pValue = 1.0 - f.cumulativeProbability(Fstat);
             double msse = (Math.max(0d, sumYY - sumXY * sumXY / sumXX)) / (N - 2);
@@ -310,98 +375,154 @@ public class ImageRegression implements WhiteboxPlugin {
             double interceptT = intercept / interceptSE;
             TDistribution distribution = new TDistribution(N - 2);
             double interceptPValue =  2d * (1.0 - distribution.cumulativeProbability(Math.abs(intercept) / interceptSE));
-            
+
             double slopeSE = Math.sqrt(msse / sumXX);
             double slopeT = slope / slopeSE;
             double slopePValue =  2d * (1.0 - distribution.cumulativeProbability(Math.abs(slope) / slopeSE));
-            
+
             if (outputResidualImage) {
                 WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader1, WhiteboxRaster.DataType.FLOAT, noData1);
                 output.setPreferredPalette("blue_white_red.pal");
-                if (standardizeResiduals) {
-                    for (row = 0; row < rows; row++) {
-                        data1 = image1.getRowValues(row);
-                        data2 = image2.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            x = data1[col];
-                            y = data2[col];
+                double scale = standardizeResiduals ? SEofEstimate : 1.0;
+                for (row = 0; row < rows; row++) {
+                    data1 = image1.getRowValues(row);
+                    data2 = image2.getRowValues(row);
+                    for (col = 0; col < cols; col++) {
+                        x = data1[col];
+                        y = data2[col];
+                        if (x != noData1 && y != noData2) {
                             yEstimate = slope * x + intercept;
-                            residual = (y - yEstimate) / SEofEstimate;
+                            residual = (y - yEstimate) / scale;
                             output.setValue(row, col, residual);
+                        } else {
+                            output.setValue(row, col, noData1);
                         }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress((int) progress);
                     }
-                } else {
-                    for (row = 0; row < rows; row++) {
-                        data1 = image1.getRowValues(row);
-                        data2 = image2.getRowValues(row);
-                        for (col = 0; col < cols; col++) {
-                            x = data1[col];
-                            y = data2[col];
-                            yEstimate = slope * x + intercept;
-                            residual = y - yEstimate;
-                            output.setValue(row, col, residual);
-                        }
-                        if (cancelOp) {
-                            cancelOperation();
-                            return;
-                        }
-                        progress = (float) (100f * row / (rows - 1));
-                        updateProgress((int) progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
                     }
+                    progress = (float) (100f * row / (rows - 1));
+                    updateProgress("Outputting residuals:", (int) progress);
                 }
-                
+
+                output.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                output.addMetadataEntry("Created on " + new Date());
+                output.addMetadataEntry("Regression equation: Y = " + slope + " * X + " + intercept);
+                output.addMetadataEntry("Standardized residuals: " + standardizeResiduals);
                 output.close();
             }
-            
+
+            String shortName1 = image1.getShortHeaderFile();
+            String shortName2 = image2.getShortHeaderFile();
+            image1.close();
+            image2.close();
+
+            // write the scatter plot image alongside the report
+            File report;
+            if (reportFile != null) {
+                if (!reportFile.toLowerCase().endsWith(".html")) {
+                    reportFile += ".html";
+                }
+                report = new File(reportFile);
+            } else {
+                report = File.createTempFile("ImageRegression", ".html");
+                report.deleteOnExit();
+            }
+            String plotName = report.getName().replace(".html", "") + "_plot.png";
+            File plotFile = new File(report.getParentFile(), plotName);
+            BufferedImage plot = drawHexbinPlot(hexCounts, qOffset, xMin, xMax, yMin, yMax,
+                    slope, intercept, shortName1, shortName2);
+            ImageIO.write(plot, "png", plotFile);
+            if (reportFile == null) {
+                plotFile.deleteOnExit();
+            }
+
             DecimalFormat df = new DecimalFormat("###,###,###,##0.000");
             DecimalFormat df2 = new DecimalFormat("###,###,###,###");
-            String retstr = null;
-            retstr = "IMAGE REGRESSION REPORT\n\n";
-            retstr += "Input Image 1 (X):\t\t" + image1.getShortHeaderFile() + "\n";
-            retstr += "Input Image 2 (Y):\t\t" + image2.getShortHeaderFile() + "\n\n";
-            
-            retstr += "Model Summary:\n";
-            retstr += "R\tR Square\tStd. Error of the Estimate\n";
-            retstr += df.format(r) + "\t" + df.format(rSqr) + "\t" + df.format(SEofEstimate) + "\n\n";
-            
-            String ANOVA = "\nAnalysis of Variance (ANOVA):\n";
-            ANOVA += "Source\tSS\tdf\tMS\tF\tP\n";
-            ANOVA += "Regression\t" + df.format(SSreg) + "\t" + df2.format(dfReg) + "\t" + df.format(MSreg) + "\t" + df.format(Fstat) + "\t" + df.format(pValue) + "\n";
-            ANOVA += "Residual\t" + df.format(SSerror) + "\t" + df2.format(dfError) + "\t" + df.format(MSerror) + "\n";
-            ANOVA += "Total\t" + df.format(SStotal) + "\n\n";
-            retstr += ANOVA;
-            
-            String coefficents = "Coefficients:\n";
-            coefficents += "Variable\tB\tStd. Error\tt\tSig.\n"; 
-            coefficents += "Constant\t" + df.format(intercept) + "\t" + df.format(interceptSE) + "\t" + df.format(interceptT) + "\t" + df.format(interceptPValue) + "\n";
-            coefficents += "Slope\t" + df.format(slope) + "\t" + df.format(slopeSE) + "\t" + df.format(slopeT) + "\t" + df.format(slopePValue) + "\n\n";
-            retstr += coefficents;
-            
+            StringBuilder sb = new StringBuilder();
+            sb.append("<!DOCTYPE html>\n");
+            sb.append("<html lang=\"en\">\n");
+            sb.append("<head>\n");
+            sb.append("<meta content=\"text/html; charset=iso-8859-1\" http-equiv=\"content-type\">");
+            sb.append("<title>Image Regression Report</title>\n");
+            sb.append("<style type=\"text/css\">");
+            sb.append("h1 {font-size: 14pt; margin-left: 15px; margin-right: 15px; text-align: center; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;} ");
+            sb.append("p {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ");
+            sb.append("table {font-size: 12pt; font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px;} ");
+            sb.append("table th {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #dedede; } ");
+            sb.append("table td {border-width: 1px; padding: 8px; border-style: solid; border-color: #666666; background-color: #ffffff; } ");
+            sb.append("caption {font-family: Helvetica, Verdana, Geneva, Arial, sans-serif; margin-left: 15px; margin-right: 15px;} ");
+            sb.append(".numberCell { text-align: right; }");
+            sb.append("</style>\n</head>\n");
+            sb.append("<body><h1>Image Regression Report</h1>\n");
+
+            sb.append("<p><b>Input Image 1 (X):</b> ").append(shortName1).append("<br>\n");
+            sb.append("<b>Input Image 2 (Y):</b> ").append(shortName2).append("<br>\n");
+            sb.append("<b>Number of Grid Cells:</b> ").append(df2.format(N)).append("</p>\n");
+
+            sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+            sb.append("<caption>Model Summary</caption>\n");
+            sb.append("<tr><th>R</th><th>R Square</th><th>Std. Error of the Estimate</th></tr>\n");
+            sb.append("<tr><td class=\"numberCell\">").append(df.format(r)).append("</td>");
+            sb.append("<td class=\"numberCell\">").append(df.format(rSqr)).append("</td>");
+            sb.append("<td class=\"numberCell\">").append(df.format(SEofEstimate)).append("</td></tr>\n");
+            sb.append("</table></p>\n");
+
+            sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+            sb.append("<caption>Analysis of Variance (ANOVA)</caption>\n");
+            sb.append("<tr><th>Source</th><th>SS</th><th>df</th><th>MS</th><th>F</th><th>P</th></tr>\n");
+            sb.append("<tr><td>Regression</td><td class=\"numberCell\">").append(df.format(SSreg));
+            sb.append("</td><td class=\"numberCell\">").append(df2.format(dfReg));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(MSreg));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(Fstat));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(pValue)).append("</td></tr>\n");
+            sb.append("<tr><td>Residual</td><td class=\"numberCell\">").append(df.format(SSerror));
+            sb.append("</td><td class=\"numberCell\">").append(df2.format(dfError));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(MSerror));
+            sb.append("</td><td></td><td></td></tr>\n");
+            sb.append("<tr><td>Total</td><td class=\"numberCell\">").append(df.format(SStotal));
+            sb.append("</td><td></td><td></td><td></td><td></td></tr>\n");
+            sb.append("</table></p>\n");
+
+            sb.append("<p><table border=\"1\" cellspacing=\"0\" cellpadding=\"3\">\n");
+            sb.append("<caption>Coefficients</caption>\n");
+            sb.append("<tr><th>Variable</th><th>B</th><th>Std. Error</th><th>t</th><th>Sig.</th></tr>\n");
+            sb.append("<tr><td>Constant</td><td class=\"numberCell\">").append(df.format(intercept));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(interceptSE));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(interceptT));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(interceptPValue)).append("</td></tr>\n");
+            sb.append("<tr><td>Slope</td><td class=\"numberCell\">").append(df.format(slope));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(slopeSE));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(slopeT));
+            sb.append("</td><td class=\"numberCell\">").append(df.format(slopePValue)).append("</td></tr>\n");
+            sb.append("</table></p>\n");
+
+            sb.append("<p><b>Regression Equation:</b> ").append(shortName2).append(" = ");
+            sb.append(df.format(slope)).append(" &times; ").append(shortName1);
             if (intercept >= 0) {
-                retstr += "Regression Equation:\t\t" + image2.getShortHeaderFile() + " = " 
-                    + df.format(slope) + " \u00D7 " + image1.getShortHeaderFile() + " + " + df.format(intercept) + "\n";
-            
+                sb.append(" + ").append(df.format(intercept));
             } else {
-                retstr += "Regression Equation:\t\t" + image2.getShortHeaderFile() + " = " 
-                    + df.format(slope) + " \u00D7 " + image1.getShortHeaderFile() + " - " + df.format(-intercept) + "\n";
-            
+                sb.append(" - ").append(df.format(-intercept));
             }
-            
-            returnData(retstr);
-            
+            sb.append("</p>\n");
+
+            sb.append("<p><img src=\"").append(plotName).append("\" alt=\"Scatter plot\"><br>\n");
+            sb.append("Hexbin scatter plot of the co-located grid cells; shading shows the number of cells in each bin ");
+            sb.append("and the line is the fitted regression.</p>\n");
+            sb.append("</body>\n</html>\n");
+
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(report, false)))) {
+                out.print(sb.toString());
+            }
+
+            returnData(report.getAbsolutePath());
+
             if (outputResidualImage) {
                 returnData(outputHeader);
             }
 
-            image1.close();
-            image2.close();
-            
         } catch (OutOfMemoryError oe) {
             myHost.showFeedback("An out-of-memory error has occurred during operation.");
         } catch (Exception e) {
@@ -414,8 +535,175 @@ public class ImageRegression implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
-    
-    
+
+    private static final int PLOT_WIDTH = 480;
+    private static final int PLOT_HEIGHT = 400;
+    private static final double HEX_RADIUS = 6.0;
+
+    /**
+     * Draws the hexagonal bin counts, the fitted regression line and the
+     * plot axes to an image.
+     */
+    private BufferedImage drawHexbinPlot(long[][] hexCounts, int qOffset,
+            double xMin, double xMax, double yMin, double yMax, double slope,
+            double intercept, String xLabel, String yLabel) {
+        int left = 90;
+        int top = 25;
+        int right = 110;
+        int bottom = 65;
+        int width = left + PLOT_WIDTH + right;
+        int height = top + PLOT_HEIGHT + bottom;
+        BufferedImage bi = new BufferedImage(width, height, BufferedImage.TYPE_INT_RGB);
+        Graphics2D g2d = bi.createGraphics();
+        g2d.setRenderingHint(RenderingHints.KEY_ANTIALIASING, RenderingHints.VALUE_ANTIALIAS_ON);
+        g2d.setRenderingHint(RenderingHints.KEY_TEXT_ANTIALIASING, RenderingHints.VALUE_TEXT_ANTIALIAS_ON);
+        g2d.setColor(Color.WHITE);
+        g2d.fillRect(0, 0, width, height);
+
+        long maxCount = 0;
+        for (long[] hexRow : hexCounts) {
+            for (long count : hexRow) {
+                if (count > maxCount) {
+                    maxCount = count;
+                }
+            }
+        }
+        double logMax = Math.log(1.0 + maxCount);
+
+        // bins are shaded on a logarithmic scale, since counts are often highly skewed
+        double hexWidth = Math.sqrt(3.0) * HEX_RADIUS;
+        g2d.setClip(left, top, PLOT_WIDTH + 1, PLOT_HEIGHT + 1);
+        int[] xPoints = new int[6];
+        int[] yPoints = new int[6];
+        double cx, cy, angle;
+        for (int hr = 0; hr < hexCounts.length; hr++) {
+            for (int q = 0; q < hexCounts[hr].length; q++) {
+                if (hexCounts[hr][q] > 0) {
+                    cx = left + hexWidth * ((q - qOffset) + (hr - 1) / 2.0);
+                    cy = top + 1.5 * HEX_RADIUS * (hr - 1);
+                    for (int k = 0; k < 6; k++) {
+                        angle = Math.toRadians(60 * k + 30);
+                        xPoints[k] = (int) Math.round(cx + (HEX_RADIUS + 0.5) * Math.cos(angle));
+                        yPoints[k] = (int) Math.round(cy + (HEX_RADIUS + 0.5) * Math.sin(angle));
+                    }
+                    g2d.setColor(getRampColour(Math.log(1.0 + hexCounts[hr][q]) / logMax));
+                    g2d.fillPolygon(xPoints, yPoints, 6);
+                }
+            }
+        }
+
+        // regression line
+        double xRange = xMax - xMin;
+        double yRange = yMax - yMin;
+        int lx1 = left;
+        int ly1 = (int) Math.round(top + (yMax - (slope * xMin + intercept)) / yRange * PLOT_HEIGHT);
+        int lx2 = left + PLOT_WIDTH;
+        int ly2 = (int) Math.round(top + (yMax - (slope * xMax + intercept)) / yRange * PLOT_HEIGHT);
+        g2d.setColor(Color.RED);
+        g2d.setStroke(new BasicStroke(2f));
+        g2d.drawLine(lx1, ly1, lx2, ly2);
+        g2d.setClip(null);
+
+        // axes, ticks and labels
+        g2d.setStroke(new BasicStroke(1f));
+        g2d.setColor(Color.BLACK);
+        g2d.drawRect(left, top, PLOT_WIDTH, PLOT_HEIGHT);
+        Font font = new Font("SansSerif", Font.PLAIN, 11);
+        g2d.setFont(font);
+        FontMetrics metrics = g2d.getFontMetrics(font);
+
+        double spacing = getTickSpacing(xRange);
+        DecimalFormat df = getTickFormat(spacing);
+        String label;
+        double tick;
+        int pos;
+        for (long k = (long) Math.ceil(xMin / spacing); k * spacing <= xMax; k++) {
+            tick = k * spacing;
+            pos = (int) Math.round(left + (tick - xMin) / xRange * PLOT_WIDTH);
+            g2d.drawLine(pos, top + PLOT_HEIGHT, pos, top + PLOT_HEIGHT + 5);
+            label = df.format(tick);
+            g2d.drawString(label, pos - metrics.stringWidth(label) / 2, top + PLOT_HEIGHT + 8 + metrics.getAscent());
+        }
+        spacing = getTickSpacing(yRange);
+        df = getTickFormat(spacing);
+        for (long k = (long) Math.ceil(yMin / spacing); k * spacing <= yMax; k++) {
+            tick = k * spacing;
+            pos = (int) Math.round(top + (yMax - tick) / yRange * PLOT_HEIGHT);
+            g2d.drawLine(left - 5, pos, left, pos);
+            label = df.format(tick);
+            g2d.drawString(label, left - 8 - metrics.stringWidth(label), pos + metrics.getAscent() / 2);
+        }
+
+        Font labelFont = new Font("SansSerif", Font.BOLD, 12);
+        g2d.setFont(labelFont);
+        FontMetrics labelMetrics = g2d.getFontMetrics(labelFont);
+        g2d.drawString(xLabel, left + (PLOT_WIDTH - labelMetrics.stringWidth(xLabel)) / 2, height - 15);
+        AffineTransform at = g2d.getTransform();
+        g2d.rotate(-Math.PI / 2.0);
+        g2d.drawString(yLabel, -(top + (PLOT_HEIGHT + labelMetrics.stringWidth(yLabel)) / 2), 20);
+        g2d.setTransform(at);
+
+        // legend
+        int legendLeft = left + PLOT_WIDTH + 30;
+        int legendTop = top + 20;
+        int legendHeight = 200;
+        int legendWidth = 18;
+        for (int i = 0; i < legendHeight; i++) {
+            g2d.setColor(getRampColour(1.0 - (double) i / (legendHeight - 1)));
+            g2d.drawLine(legendLeft, legendTop + i, legendLeft + legendWidth, legendTop + i);
+        }
+        g2d.setColor(Color.BLACK);
+        g2d.drawRect(legendLeft, legendTop, legendWidth, legendHeight);
+        g2d.drawString("Count", legendLeft, legendTop - 8);
+        g2d.setFont(font);
+        g2d.drawString(String.valueOf(maxCount), legendLeft + legendWidth + 5, legendTop + metrics.getAscent() / 2);
+        g2d.drawString("1", legendLeft + legendWidth + 5, legendTop + legendHeight + metrics.getAscent() / 2);
+
+        g2d.dispose();
+        return bi;
+    }
+
+    /**
+     * Returns a colour from a light-yellow to dark-blue ramp for a value
+     * between 0 and 1.
+     */
+    private Color getRampColour(double value) {
+        int[][] stops = {{255, 255, 204}, {65, 182, 196}, {8, 29, 88}};
+        value = Math.max(0.0, Math.min(1.0, value)) * (stops.length - 1);
+        int i = Math.min((int) value, stops.length - 2);
+        double t = value - i;
+        int red = (int) Math.round(stops[i][0] + t * (stops[i + 1][0] - stops[i][0]));
+        int green = (int) Math.round(stops[i][1] + t * (stops[i + 1][1] - stops[i][1]));
+        int blue = (int) Math.round(stops[i][2] + t * (stops[i + 1][2] - stops[i][2]));
+        return new Color(red, green, blue);
+    }
+
+    private double getTickSpacing(double range) {
+        double rough = range / 5.0;
+        double magnitude = Math.pow(10, Math.floor(Math.log10(rough)));
+        double fraction = rough / magnitude;
+        if (fraction < 1.5) {
+            return magnitude;
+        } else if (fraction < 3.0) {
+            return 2.0 * magnitude;
+        } else if (fraction < 7.0) {
+            return 5.0 * magnitude;
+        }
+        return 10.0 * magnitude;
+    }
+
+    private DecimalFormat getTickFormat(double spacing) {
+        if (spacing >= 1.0) {
+            return new DecimalFormat("###,###,###,##0");
+        }
+        int decimals = (int) Math.ceil(-Math.log10(spacing) - 1e-9);
+        StringBuilder pattern = new StringBuilder("0.");
+        for (int i = 0; i < decimals; i++) {
+            pattern.append("0");
+        }
+        return new DecimalFormat(pattern.toString());
+    }
+
 //    // this is only used for debugging the tool
 //    public static void main(String[] args) {
 //        ImageRegression ir = new ImageRegression();
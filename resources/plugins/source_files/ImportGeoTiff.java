@@ -16,12 +16,12 @@
  */
 package plugins;
 
-import java.nio.ByteOrder;
 import java.io.*;
 import java.util.Date;
 import whitebox.geospatialfiles.GeoTiff;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterBase;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 import whitebox.interfaces.InteropPlugin;
@@ -234,9 +234,8 @@ public class ImportGeoTiff implements WhiteboxPlugin, InteropPlugin {
                 GeoTiff gt = new GeoTiff(imageFiles[i]);
                 gt.read();
 
-                int compressionType = gt.getCompressionType();
-                if (compressionType != 1) {
-                    showFeedback("GeoTiff import does not currently support compressed files.");
+                if (gt.getUnsupportedReason() != null) {
+                    showFeedback("GeoTiff import does not support this file. " + gt.getUnsupportedReason());
                     return;
                 }
 
@@ -263,31 +262,78 @@ public class ImportGeoTiff implements WhiteboxPlugin, InteropPlugin {
                 (new File(whiteboxHeaderFile)).delete();
                 (new File(whiteboxDataFile)).delete();
 
-                ByteOrder byteOrder = gt.getByteOrder();
-
                 WhiteboxRasterBase.DataScale myDataScale = WhiteboxRasterBase.DataScale.CONTINUOUS;
                 if (gt.getPhotometricInterpretation() == 2) {
                     myDataScale = WhiteboxRasterBase.DataScale.RGB;
                 }
-                final WhiteboxRaster wbr = new WhiteboxRaster(whiteboxHeaderFile, gt.getNorth(), gt.getSouth(), gt.getEast(),
-                        gt.getWest(), nRows, nCols, myDataScale,
-                        WhiteboxRasterBase.DataType.FLOAT, nodata, nodata);
+                // A file with a scale factor or offset in its GDAL metadata
+                // holds stored values rather than real-world values. 8-bit
+                // unsigned and 16-bit signed data are imported as scaled BYTE
+                // and INTEGER rasters, keeping the stored values; other data
+                // are converted to real-world values.
+                double scale = gt.getScale();
+                double offset = gt.getOffset();
+                boolean isScaled = scale != 1.0 || offset != 0.0;
+                WhiteboxRasterBase.DataType dataType = WhiteboxRasterBase.DataType.FLOAT;
+                if (isScaled && myDataScale != WhiteboxRasterBase.DataScale.RGB) {
+                    int bits = gt.getNumberBitsPerSample();
+                    int sampleFormat = gt.getSampleFormat();
+                    if (bits == 8 && sampleFormat == 1) {
+                        dataType = WhiteboxRasterBase.DataType.BYTE;
+                    } else if (bits == 16 && sampleFormat == 2) {
+                        dataType = WhiteboxRasterBase.DataType.INTEGER;
+                    }
+                }
 
-                wbr.setByteOrder(byteOrder.toString());
+                // the rows are written as they are read, so that neither the
+                // GeoTiff nor the output raster is held in memory
+                final WhiteboxRasterStreamWriter wbr = new WhiteboxRasterStreamWriter(whiteboxHeaderFile,
+                        gt.getNorth(), gt.getSouth(), gt.getEast(), gt.getWest(), nRows, nCols,
+                        myDataScale, dataType, nodata);
+                if (dataType != WhiteboxRasterBase.DataType.FLOAT) {
+                    wbr.setScaleAndOffset(scale, offset);
+                }
 
-                double z;
+                // 32-bit floating point and smaller integer data are copied
+                // to the output as floats, without conversion to doubles
+                boolean singlePrecision = gt.isSinglePrecision() && !isScaled;
+                float[] floatData = new float[nCols];
+                double[] data = new double[nCols];
                 int oldProgress = -1;
                 for (int row = 0; row < nRows; row++) {
-                    for (int col = 0; col < nCols; col++) {
-                        z = gt.getValue(row, col);
-                        if (!hasNoDataValue && (z == -32768 || z == -Float.MAX_VALUE)) {
-                            nodata = z;
-                            hasNoDataValue = true;
-                            wbr.setNoDataValue(nodata);
+                    if (singlePrecision) {
+                        gt.getRowValues(row, floatData);
+                        if (!hasNoDataValue) {
+                            for (int col = 0; col < nCols; col++) {
+                                if (floatData[col] == -32768 || floatData[col] == -Float.MAX_VALUE) {
+                                    nodata = floatData[col];
+                                    hasNoDataValue = true;
+                                    wbr.setNoDataValue(nodata);
+                                    break;
+                                }
+                            }
+                        }
+                        wbr.writeRow(row, floatData);
+                    } else {
+                        gt.getRowValues(row, data);
+                        if (!hasNoDataValue) {
+                            for (int col = 0; col < nCols; col++) {
+                                if (data[col] == -32768 || data[col] == -Float.MAX_VALUE) {
+                                    nodata = data[col];
+                                    hasNoDataValue = true;
+                                    wbr.setNoDataValue(nodata);
+                                    break;
+                                }
+                            }
+                        }
+                        if (isScaled) {
+                            for (int col = 0; col < nCols; col++) {
+                                if (data[col] != nodata) {
+                                    data[col] = data[col] * scale + offset;
+                                }
+                            }
                         }
-                        //if (z != nodata) {
-                        wbr.setValue(row, col, z);
-                        //}
+                        wbr.writeRow(row, data);
                     }
                     progress = (int) (100f * row / (nRows - 1));
                     if (progress != oldProgress) {
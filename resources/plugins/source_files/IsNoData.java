@@ -222,8 +222,13 @@ public class IsNoData implements WhiteboxPlugin {
             
             double noData = inputFile.getNoDataValue();
 
-            WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            // The output only contains 0 and 1, so it is stored as bytes. Its
+            // NoData value must not be confused with either of these values.
+            double outputNoData = (noData == 0 || noData == 1) ? -32768 : noData;
+            WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.BYTE, outputNoData);
+            outputFile.setNoDataValue(outputNoData);
             outputFile.setPreferredPalette("black_white.pal");
+            outputFile.setDataScale(WhiteboxRaster.DataScale.BOOLEAN);
 
             for (row = 0; row < rows; row++) {
                 data = inputFile.getRowValues(row);
@@ -0,0 +1,455 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.File;
+import java.util.Date;
+import whitebox.geospatialfiles.LASReader;
+import whitebox.geospatialfiles.LASReader.PointRecord;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool grids quality assurance diagnostics for LAS files, including the
+ * scan angle, the proportion of ground returns, the ratio of single-return to
+ * multiple-return pulses, and the range of GPS times within each grid cell.
+ * 
+ */
+public class LiDAR_QualityRasters implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "LiDAR_QualityRasters";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Quality Assurance Rasters (LiDAR)";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Grids scan angle, ground ratio, return ratio and GPS time range diagnostics for LAS files.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */    
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"LidarTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */    
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+
+    // the file name suffixes and descriptions of the output products
+    private static final String[] productSuffixes = {"_mean_scan_angle",
+        "_max_scan_angle", "_ground_ratio", "_only_return_ratio", "_gps_time_range"};
+    private static final String[] productNames = {
+        "Mean absolute scan angle (degrees)",
+        "Maximum absolute scan angle (degrees)",
+        "Ratio of ground (class 2) returns to all returns",
+        "Ratio of single-return pulses to multiple-return pulses",
+        "GPS time range"};
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputFilesString = null;
+        double resolution = 0;
+        // scan angle (mean and maximum), ground ratio, return ratio, GPS time range
+        boolean[] requested = new boolean[4];
+        int progress, oldProgress;
+        double noData = -32768;
+
+        // get the arguments
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        for (int i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputFilesString = args[i];
+            } else if (i == 1) {
+                resolution = Double.parseDouble(args[i]);
+            } else if (i <= 5) {
+                requested[i - 2] = Boolean.parseBoolean(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if (inputFilesString == null || inputFilesString.trim().isEmpty()) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (resolution <= 0) {
+            showFeedback("The grid resolution must be greater than zero.");
+            return;
+        }
+        if (!requested[0] && !requested[1] && !requested[2] && !requested[3]) {
+            showFeedback("At least one output product must be selected.");
+            return;
+        }
+
+        // the products that are output, in the order of productSuffixes
+        boolean[] outputProducts = {requested[0], requested[0], requested[1],
+            requested[2], requested[3]};
+
+        try {
+            String[] pointFiles = inputFilesString.split(";");
+            int numPointFiles = pointFiles.length;
+            for (int j = 0; j < numPointFiles; j++) {
+                LASReader las = new LASReader(pointFiles[j]);
+                String shortName = new File(pointFiles[j]).getName();
+                short format = las.getPointDataFormatID();
+                if (requested[3] && format != 1 && format != 3 && format != 4 && format != 5) {
+                    showFeedback("The points in " + shortName + " do not contain GPS time.");
+                    return;
+                }
+
+                QualityGrid grid = new QualityGrid(las.getMinX(), las.getMaxX(),
+                        las.getMinY(), las.getMaxY(), resolution, requested);
+                if ((long) grid.rows * grid.cols > Integer.MAX_VALUE) {
+                    showFeedback("The grid resolution is too fine for the extent of " + shortName + ".");
+                    return;
+                }
+                grid.allocate();
+
+                // bin the points in a single pass through the file
+                long numPointsInFile = las.getNumPointRecords();
+                long numWithheld = 0;
+                oldProgress = -1;
+                for (int a = 0; a < numPointsInFile; a++) {
+                    PointRecord point = las.getPointRecord(a);
+                    if (!point.isPointWithheld()) {
+                        grid.addPoint(point);
+                    } else {
+                        numWithheld++;
+                    }
+                    progress = (int) (100d * (a + 1) / numPointsInFile);
+                    if (progress != oldProgress) {
+                        updateProgress("Loop " + (j + 1) + " of " + numPointFiles + " Binning points:", progress);
+                        oldProgress = progress;
+                        if (cancelOp) {
+                            cancelOperation();
+                            return;
+                        }
+                    }
+                }
+
+                int dot = pointFiles[j].toLowerCase().lastIndexOf(".las");
+                String baseName = dot >= 0 ? pointFiles[j].substring(0, dot) : pointFiles[j];
+                double[] data = new double[grid.cols];
+                for (int p = 0; p < productSuffixes.length; p++) {
+                    if (!outputProducts[p]) {
+                        continue;
+                    }
+                    String outputHeader = baseName + productSuffixes[p] + ".dep";
+                    WhiteboxRaster output = new WhiteboxRaster(outputHeader, grid.north,
+                            grid.north - grid.rows * resolution, grid.west + grid.cols * resolution,
+                            grid.west, grid.rows, grid.cols, WhiteboxRaster.DataScale.CONTINUOUS,
+                            WhiteboxRaster.DataType.FLOAT, noData, noData);
+                    output.setPreferredPalette("spectrum.pal");
+                    for (int row = 0; row < grid.rows; row++) {
+                        for (int col = 0; col < grid.cols; col++) {
+                            data[col] = grid.getValue(p, row * grid.cols + col, noData);
+                        }
+                        output.setRowValues(row, data);
+                    }
+                    output.addMetadataEntry("Created by the "
+                            + getDescriptiveName() + " tool.");
+                    output.addMetadataEntry("Created on " + new Date());
+                    output.addMetadataEntry("Product: " + productNames[p]);
+                    output.addMetadataEntry("Input LAS file: " + shortName);
+                    output.addMetadataEntry("Grid resolution: " + resolution);
+                    output.addMetadataEntry("Points binned: " + grid.numBinned);
+                    output.addMetadataEntry("Withheld points excluded: " + numWithheld);
+                    if (grid.numOutside > 0) {
+                        output.addMetadataEntry("Points outside of the LAS header extent (excluded): "
+                                + grid.numOutside);
+                    }
+                    output.close();
+
+                    returnData(outputHeader);
+                }
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Accumulates the statistics of the points within each grid cell. Only the
+     * accumulators that are needed for the requested products are allocated,
+     * and the points are binned in the order in which they are read, so the
+     * results are the same each time a file is processed.
+     */
+    private static class QualityGrid {
+
+        final double north;
+        final double west;
+        final double resolution;
+        final int rows;
+        final int cols;
+        final boolean[] requested;
+        int[] numPoints;
+        int[] sumScanAngle;
+        short[] maxScanAngle;
+        int[] numGround;
+        int[] numSinglePulses;
+        int[] numMultiplePulses;
+        double[] minTime;
+        double[] maxTime;
+        long numBinned = 0;
+        long numOutside = 0;
+
+        QualityGrid(double minX, double maxX, double minY, double maxY,
+                double resolution, boolean[] requested) {
+            // the grid is aligned to multiples of the resolution so that the
+            // grids of adjacent tiles line up
+            this.resolution = resolution;
+            this.requested = requested;
+            west = Math.floor(minX / resolution) * resolution;
+            north = Math.ceil(maxY / resolution) * resolution;
+            cols = (int) Math.floor((maxX - west) / resolution) + 1;
+            rows = (int) Math.floor((north - minY) / resolution) + 1;
+        }
+
+        void allocate() {
+            int n = rows * cols;
+            numPoints = new int[n];
+            if (requested[0]) {
+                sumScanAngle = new int[n];
+                maxScanAngle = new short[n];
+            }
+            if (requested[1]) {
+                numGround = new int[n];
+            }
+            if (requested[2]) {
+                numSinglePulses = new int[n];
+                numMultiplePulses = new int[n];
+            }
+            if (requested[3]) {
+                minTime = new double[n];
+                maxTime = new double[n];
+            }
+        }
+
+        void addPoint(PointRecord point) {
+            int col = (int) Math.floor((point.getX() - west) / resolution);
+            int row = (int) Math.floor((north - point.getY()) / resolution);
+            if (row < 0 || row >= rows || col < 0 || col >= cols) {
+                numOutside++;
+                return;
+            }
+            int i = row * cols + col;
+            numPoints[i]++;
+            numBinned++;
+            if (sumScanAngle != null) {
+                short angle = (short) Math.abs(point.getScanAngle());
+                sumScanAngle[i] += angle;
+                if (angle > maxScanAngle[i]) {
+                    maxScanAngle[i] = angle;
+                }
+            }
+            if (numGround != null && point.getClassification() == 2) {
+                numGround[i]++;
+            }
+            // each pulse is counted once, at its first return
+            if (numSinglePulses != null && point.getReturnNumber() == 1) {
+                if (point.getNumberOfReturns() > 1) {
+                    numMultiplePulses[i]++;
+                } else {
+                    numSinglePulses[i]++;
+                }
+            }
+            if (minTime != null) {
+                double t = point.getGPSTime();
+                if (numPoints[i] == 1 || t < minTime[i]) {
+                    minTime[i] = t;
+                }
+                if (numPoints[i] == 1 || t > maxTime[i]) {
+                    maxTime[i] = t;
+                }
+            }
+        }
+
+        double getValue(int product, int i, double noData) {
+            if (numPoints[i] == 0) {
+                return noData;
+            }
+            switch (product) {
+                case 0:
+                    return (double) sumScanAngle[i] / numPoints[i];
+                case 1:
+                    return maxScanAngle[i];
+                case 2:
+                    return (double) numGround[i] / numPoints[i];
+                case 3:
+                    if (numMultiplePulses[i] == 0) {
+                        return noData;
+                    }
+                    return (double) numSinglePulses[i] / numMultiplePulses[i];
+                default:
+                    return maxTime[i] - minTime[i];
+            }
+        }
+    }
+}
@@ -0,0 +1,722 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedOutputStream;
+import java.io.BufferedReader;
+import java.io.ByteArrayOutputStream;
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.FileReader;
+import java.io.IOException;
+import java.io.RandomAccessFile;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.nio.MappedByteBuffer;
+import java.nio.channels.FileChannel;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.StandardCopyOption;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.Comparator;
+import java.util.HashMap;
+import java.util.Map;
+import whitebox.geospatialfiles.LASReader;
+import whitebox.geospatialfiles.LasSpatialIndex;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool can be used to remove the striping between flightlines that is
+ * commonly seen in rasters of LiDAR intensity. The intensity of each point can
+ * be normalized to a reference range and each flightline's intensity
+ * distribution can be matched to that of the whole file.
+ */
+public class LidarIntensityCorrection implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "LidarIntensityCorrection";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "LiDAR Intensity Correction";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Normalizes LiDAR intensity for range and matches the intensity of each flightline.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"LidarTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */    
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputFile = null;
+        String outputFile = null;
+        boolean useScanAngle = false;
+        boolean useTrajectory = false;
+        double flyingAltitude = Double.NaN;
+        String trajectoryFile = null;
+        double refRange = Double.NaN;
+        double exponent = 2.0;
+        boolean matchFlightlines = false;
+        boolean keepOriginal = false;
+        int i, p, progress, value;
+        double x, y, z, r;
+        RandomAccessFile rIn = null;
+        BufferedOutputStream out = null;
+
+        // get the arguments
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputFile = args[i];
+            } else if (i == 1) {
+                outputFile = args[i];
+            } else if (i == 2) {
+                useScanAngle = args[i].toLowerCase().contains("scan angle");
+                useTrajectory = args[i].toLowerCase().contains("trajectory");
+            } else if (i == 3) {
+                if (specified) {
+                    flyingAltitude = Double.parseDouble(args[i]);
+                }
+            } else if (i == 4) {
+                if (specified) {
+                    trajectoryFile = args[i];
+                }
+            } else if (i == 5) {
+                if (specified) {
+                    refRange = Double.parseDouble(args[i]);
+                }
+            } else if (i == 6) {
+                if (specified) {
+                    exponent = Double.parseDouble(args[i]);
+                }
+            } else if (i == 7) {
+                matchFlightlines = Boolean.parseBoolean(args[i]);
+            } else if (i == 8) {
+                keepOriginal = Boolean.parseBoolean(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (inputFile.equals(outputFile)) {
+            showFeedback("The output file must be different from the input file.");
+            return;
+        }
+        if (!useScanAngle && !useTrajectory && !matchFlightlines) {
+            showFeedback("Either range normalization or flightline matching must be selected.");
+            return;
+        }
+        if (useScanAngle && Double.isNaN(flyingAltitude)) {
+            showFeedback("The flying altitude must be specified to estimate range from the scan angle.");
+            return;
+        }
+        if (useTrajectory && trajectoryFile == null) {
+            showFeedback("A trajectory file must be specified to calculate range from the trajectory.");
+            return;
+        }
+        if (!Double.isNaN(refRange) && refRange <= 0) {
+            showFeedback("The reference range must be greater than zero.");
+            return;
+        }
+
+        try {
+            LASReader las = new LASReader(inputFile);
+            if (las.getNumPointRecords() > Integer.MAX_VALUE) {
+                showFeedback("The LAS file contains too many points to be processed.");
+                return;
+            }
+            int numPoints = (int) las.getNumPointRecords();
+            int recLength = las.getPointDataRecLength();
+            long offset = las.getOffsetToPointData();
+            short format = las.getPointDataFormatID();
+            double xScale = las.getXScale();
+            double yScale = las.getYScale();
+            double zScale = las.getZScale();
+            double xOffset = las.getXOffset();
+            double yOffset = las.getYOffset();
+            double zOffset = las.getZOffset();
+            if (format < 0 || format > 5) {
+                showFeedback("Only LAS point data formats 0 to 5 are supported.");
+                return;
+            }
+            if (useTrajectory && format != 1 && format != 3 && format != 4 && format != 5) {
+                showFeedback("The points in this LAS file do not contain the GPS time needed "
+                        + "to locate the sensor on the trajectory.");
+                return;
+            }
+
+            rIn = new RandomAccessFile(inputFile, "r");
+            FileChannel inChannel = rIn.getChannel();
+            long fileLength = inChannel.size();
+            long endOfPoints = offset + (long) numPoints * recLength;
+            if (endOfPoints > fileLength) {
+                showFeedback("The LAS file appears to be truncated.");
+                return;
+            }
+
+            int recsPerChunk = Integer.MAX_VALUE / recLength;
+            int numChunks = (int) (((long) numPoints + recsPerChunk - 1) / recsPerChunk);
+            MappedByteBuffer[] chunks = new MappedByteBuffer[numChunks];
+            for (i = 0; i < numChunks; i++) {
+                long n = Math.min(recsPerChunk, numPoints - (long) i * recsPerChunk);
+                chunks[i] = inChannel.map(FileChannel.MapMode.READ_ONLY,
+                        offset + (long) i * recsPerChunk * recLength, n * recLength);
+                chunks[i].order(ByteOrder.LITTLE_ENDIAN);
+            }
+            MappedByteBuffer buf;
+            int pos;
+
+            /*
+             * The range from the sensor to each point is either approximated
+             * from the flying altitude and the scan angle, i.e. assuming level
+             * flight with the scan plane perpendicular to the flight direction,
+             * or measured from the sensor position interpolated from the
+             * trajectory at the point's GPS time. A range of zero marks the
+             * points for which the range can't be determined; these keep their
+             * original intensity.
+             */
+            float[] ranges = null;
+            int numUncorrected = 0;
+            if (useScanAngle || useTrajectory) {
+                double[][] trajectory = null;
+                if (useTrajectory) {
+                    trajectory = readTrajectory(trajectoryFile);
+                    if (trajectory.length < 2) {
+                        showFeedback("The trajectory file must contain at least two sensor positions.");
+                        return;
+                    }
+                }
+                ranges = new float[numPoints];
+                double sumRange = 0;
+                int numRanges = 0;
+                for (p = 0; p < numPoints; p++) {
+                    buf = chunks[p / recsPerChunk];
+                    pos = (p % recsPerChunk) * recLength;
+                    z = buf.getInt(pos + 8) * zScale + zOffset;
+                    if (useScanAngle) {
+                        r = (flyingAltitude - z) / Math.cos(Math.toRadians(buf.get(pos + 16)));
+                    } else {
+                        x = buf.getInt(pos) * xScale + xOffset;
+                        y = buf.getInt(pos + 4) * yScale + yOffset;
+                        r = getRange(trajectory, buf.getDouble(pos + 20), x, y, z);
+                    }
+                    if (r > 0) {
+                        ranges[p] = (float) r;
+                        sumRange += r;
+                        numRanges++;
+                    } else {
+                        numUncorrected++;
+                    }
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (100f * p / numPoints);
+                    updateProgress("Loop 1 of 3:", progress);
+                }
+                if (numRanges == 0) {
+                    showFeedback("The range could not be determined for any of the points. "
+                            + "Check that the flying altitude or trajectory matches the LAS file.");
+                    return;
+                }
+                if (Double.isNaN(refRange)) {
+                    refRange = sumRange / numRanges;
+                }
+            }
+
+            // The range-normalized intensities are stored along with a
+            // histogram of intensity for each flightline, i.e. point source ID.
+            char[] intensity = new char[numPoints];
+            HashMap<Integer, int[]> histograms = new HashMap<>();
+            int[] overallHisto = new int[65536];
+            for (p = 0; p < numPoints; p++) {
+                buf = chunks[p / recsPerChunk];
+                pos = (p % recsPerChunk) * recLength;
+                value = buf.getShort(pos + 12) & 0xFFFF;
+                if (ranges != null && ranges[p] > 0) {
+                    value = (int) Math.round(value * Math.pow(ranges[p] / refRange, exponent));
+                    value = Math.max(0, Math.min(65535, value));
+                }
+                intensity[p] = (char) value;
+                if (matchFlightlines) {
+                    int sourceID = buf.getShort(pos + 18) & 0xFFFF;
+                    int[] histo = histograms.get(sourceID);
+                    if (histo == null) {
+                        histo = new int[65536];
+                        histograms.put(sourceID, histo);
+                    }
+                    histo[value]++;
+                    overallHisto[value]++;
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * p / numPoints);
+                updateProgress("Loop 2 of 3:", progress);
+            }
+
+            HashMap<Integer, char[]> lookupTables = new HashMap<>();
+            for (Map.Entry<Integer, int[]> entry : histograms.entrySet()) {
+                lookupTables.put(entry.getKey(), matchHistogram(entry.getValue(), overallHisto, numPoints));
+            }
+
+            out = new BufferedOutputStream(new FileOutputStream(outputFile), 1 << 20);
+
+            // the header and variable length records are copied, adding an
+            // extra bytes descriptor for the original intensity if needed
+            ByteBuffer headerBuf = ByteBuffer.allocate((int) offset);
+            inChannel.read(headerBuf, 0);
+            byte[] header = headerBuf.array();
+            if (keepOriginal) {
+                header = addIntensityDescriptor(header, recLength - POINT_RECORD_LENGTHS[format], numPoints);
+            }
+            out.write(header);
+
+            byte[] rec = new byte[recLength];
+            ByteBuffer recBuf = ByteBuffer.wrap(rec).order(ByteOrder.LITTLE_ENDIAN);
+            ByteBuffer extraBuf = ByteBuffer.allocate(2).order(ByteOrder.LITTLE_ENDIAN);
+            for (p = 0; p < numPoints; p++) {
+                buf = chunks[p / recsPerChunk];
+                buf.position((p % recsPerChunk) * recLength);
+                buf.get(rec);
+                short original = recBuf.getShort(12);
+                value = intensity[p];
+                if (matchFlightlines) {
+                    value = lookupTables.get(recBuf.getShort(18) & 0xFFFF)[value];
+                }
+                recBuf.putShort(12, (short) value);
+                out.write(rec);
+                if (keepOriginal) {
+                    extraBuf.putShort(0, original);
+                    out.write(extraBuf.array());
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * p / numPoints);
+                updateProgress("Loop 3 of 3:", progress);
+            }
+
+            // anything following the point records, e.g. waveform data, is
+            // copied unchanged
+            ByteBuffer tailBuf = ByteBuffer.allocate(1 << 20);
+            long tailPos = endOfPoints;
+            while (tailPos < fileLength) {
+                tailBuf.clear();
+                int n = inChannel.read(tailBuf, tailPos);
+                if (n <= 0) {
+                    break;
+                }
+                out.write(tailBuf.array(), 0, n);
+                tailPos += n;
+            }
+
+            out.close();
+            out = null;
+
+            // the points keep their order, so a spatial index remains valid
+            File indexFile = new File(LasSpatialIndex.getIndexFileName(inputFile));
+            if (indexFile.exists()) {
+                Files.copy(indexFile.toPath(), new File(LasSpatialIndex.getIndexFileName(outputFile)).toPath(),
+                        StandardCopyOption.REPLACE_EXISTING);
+            }
+
+            StringBuilder sb = new StringBuilder();
+            sb.append("Corrected the intensity of ").append(numPoints).append(" points.");
+            if (ranges != null) {
+                sb.append("\nReference range: ").append(String.format("%.2f", refRange));
+                if (numUncorrected > 0) {
+                    sb.append("\nThe range could not be determined for ").append(numUncorrected)
+                            .append(" points, which were not range-normalized.");
+                }
+            }
+            if (matchFlightlines) {
+                sb.append("\nFlightlines matched: ").append(histograms.size());
+            }
+            showFeedback(sb.toString());
+
+            returnData(outputFile);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            if (rIn != null) {
+                try {
+                    rIn.close();
+                } catch (Exception e) {
+                }
+            }
+            if (out != null) {
+                try {
+                    out.close();
+                } catch (Exception e) {
+                }
+            }
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * The lengths of the standard point data records of formats 0 to 5. Any
+     * additional bytes in a record are extra bytes.
+     */
+    private static final int[] POINT_RECORD_LENGTHS = {20, 28, 26, 34, 57, 63};
+
+    /**
+     * Reads a trajectory file of time-stamped sensor positions, one per line as
+     * time, x, y and z separated by commas or white space. Lines that can't be
+     * read as four numbers, such as a header line, are skipped. The positions
+     * are returned sorted by time.
+     */
+    private static double[][] readTrajectory(String fileName) throws IOException {
+        ArrayList<double[]> positions = new ArrayList<>();
+        try (BufferedReader br = new BufferedReader(new FileReader(fileName))) {
+            String line;
+            while ((line = br.readLine()) != null) {
+                String[] s = line.trim().split("[,;\\s]+");
+                if (s.length < 4) {
+                    continue;
+                }
+                try {
+                    positions.add(new double[]{Double.parseDouble(s[0]), Double.parseDouble(s[1]),
+                        Double.parseDouble(s[2]), Double.parseDouble(s[3])});
+                } catch (NumberFormatException e) {
+                    // not a sensor position
+                }
+            }
+        }
+        Collections.sort(positions, new Comparator<double[]>() {
+            @Override
+            public int compare(double[] a, double[] b) {
+                return Double.compare(a[0], b[0]);
+            }
+        });
+        return positions.toArray(new double[positions.size()][]);
+    }
+
+    /**
+     * Calculates the distance from a point to the sensor position at time t,
+     * which is interpolated linearly between the trajectory positions. Returns
+     * NaN if t falls outside of the time span of the trajectory.
+     */
+    private static double getRange(double[][] trajectory, double t, double x, double y, double z) {
+        int lo = 0;
+        int hi = trajectory.length - 1;
+        if (t < trajectory[lo][0] || t > trajectory[hi][0]) {
+            return Double.NaN;
+        }
+        while (hi - lo > 1) {
+            int mid = (lo + hi) >>> 1;
+            if (trajectory[mid][0] <= t) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        double dt = trajectory[hi][0] - trajectory[lo][0];
+        double w = dt > 0 ? (t - trajectory[lo][0]) / dt : 0;
+        double dx = trajectory[lo][1] + w * (trajectory[hi][1] - trajectory[lo][1]) - x;
+        double dy = trajectory[lo][2] + w * (trajectory[hi][2] - trajectory[lo][2]) - y;
+        double dz = trajectory[lo][3] + w * (trajectory[hi][3] - trajectory[lo][3]) - z;
+        return Math.sqrt(dx * dx + dy * dy + dz * dz);
+    }
+
+    /**
+     * Creates a lookup table that maps each intensity value of a flightline to
+     * the value with the same cumulative proportion in the overall intensity
+     * distribution. Values are matched at the middle of their histogram bin,
+     * so that a flightline with the overall distribution is left unchanged.
+     */
+    private static char[] matchHistogram(int[] histo, int[] overallHisto, long numOverall) {
+        long numSource = 0;
+        for (int count : histo) {
+            numSource += count;
+        }
+        char[] lookup = new char[histo.length];
+        long cumSource = 0;
+        long cumOverall = overallHisto[0];
+        int w = 0;
+        for (int v = 0; v < histo.length; v++) {
+            if (histo[v] > 0) {
+                double target = (cumSource + histo[v] / 2.0) / numSource;
+                while (w < overallHisto.length - 1 && (double) cumOverall / numOverall < target) {
+                    w++;
+                    cumOverall += overallHisto[w];
+                }
+                cumSource += histo[v];
+            }
+            lookup[v] = (char) w;
+        }
+        return lookup;
+    }
+
+    /**
+     * Adds an extra bytes descriptor (LAS 1.4 extra bytes VLR) for the original
+     * intensity, which is appended to each point record as an unsigned short.
+     * The descriptor is added to an existing extra bytes VLR, or a new VLR is
+     * created, in which case any existing extra bytes are first described as
+     * undocumented. The offsets in the public header block are updated.
+     *
+     * @param header The public header block and variable length records.
+     * @param extraBytes The number of extra bytes in the input point records.
+     * @param numPoints The number of point records.
+     * @return The updated header block and variable length records.
+     */
+    private static byte[] addIntensityDescriptor(byte[] header, int extraBytes, long numPoints)
+            throws IOException {
+        ByteBuffer in = ByteBuffer.wrap(header).order(ByteOrder.LITTLE_ENDIAN);
+        int headerSize = in.getShort(94) & 0xFFFF;
+        long numVLRs = in.getInt(100) & 0xFFFFFFFFL;
+
+        byte[] descriptor = new byte[192];
+        ByteBuffer d = ByteBuffer.wrap(descriptor).order(ByteOrder.LITTLE_ENDIAN);
+        d.put(2, (byte) 3); // unsigned short
+        putString(descriptor, 4, 32, "original intensity");
+        putString(descriptor, 160, 32, "Intensity before correction");
+
+        ByteArrayOutputStream vlrs = new ByteArrayOutputStream();
+        boolean found = false;
+        int pos = headerSize;
+        for (long v = 0; v < numVLRs && pos + 54 <= header.length; v++) {
+            String userID = new String(header, pos + 2, 16, StandardCharsets.US_ASCII).trim();
+            int recordID = in.getShort(pos + 18) & 0xFFFF;
+            int length = in.getShort(pos + 20) & 0xFFFF;
+            if (!found && userID.equals("LASF_Spec") && recordID == 4) {
+                if (length + 192 > 65535) {
+                    throw new IOException("The extra bytes VLR can't hold another descriptor.");
+                }
+                byte[] vlr = new byte[54 + length];
+                System.arraycopy(header, pos, vlr, 0, vlr.length);
+                ByteBuffer.wrap(vlr).order(ByteOrder.LITTLE_ENDIAN).putShort(20, (short) (length + 192));
+                vlrs.write(vlr);
+                vlrs.write(descriptor);
+                found = true;
+            } else {
+                vlrs.write(header, pos, 54 + length);
+            }
+            pos += 54 + length;
+        }
+        if (!found) {
+            byte[] undocumented = new byte[0];
+            if (extraBytes > 0) {
+                undocumented = new byte[192];
+                undocumented[3] = (byte) extraBytes; // data type 0, options hold the byte count
+            }
+            byte[] vlrHeader = new byte[54];
+            ByteBuffer h = ByteBuffer.wrap(vlrHeader).order(ByteOrder.LITTLE_ENDIAN);
+            putString(vlrHeader, 2, 16, "LASF_Spec");
+            h.putShort(18, (short) 4);
+            h.putShort(20, (short) (undocumented.length + descriptor.length));
+            putString(vlrHeader, 22, 32, "Extra bytes");
+            vlrs.write(vlrHeader);
+            vlrs.write(undocumented);
+            vlrs.write(descriptor);
+            numVLRs++;
+        }
+
+        ByteArrayOutputStream result = new ByteArrayOutputStream();
+        result.write(header, 0, headerSize);
+        vlrs.writeTo(result);
+        // any padding between the VLRs and the point data is kept
+        result.write(header, pos, header.length - pos);
+        byte[] ret = result.toByteArray();
+
+        ByteBuffer o = ByteBuffer.wrap(ret).order(ByteOrder.LITTLE_ENDIAN);
+        o.putInt(96, ret.length);
+        o.putInt(100, (int) numVLRs);
+        o.putShort(105, (short) ((in.getShort(105) & 0xFFFF) + 2));
+        // the start of the waveform data (LAS 1.3) and of the extended VLRs
+        // (LAS 1.4) move with the larger point records
+        long shift = ret.length - header.length + 2 * numPoints;
+        int versionMinor = header[25];
+        if (versionMinor >= 3 && headerSize >= 235 && o.getLong(227) != 0) {
+            o.putLong(227, o.getLong(227) + shift);
+        }
+        if (versionMinor >= 4 && headerSize >= 243 && o.getLong(235) != 0) {
+            o.putLong(235, o.getLong(235) + shift);
+        }
+        return ret;
+    }
+
+    private static void putString(byte[] b, int offset, int length, String s) {
+        byte[] chars = s.getBytes(StandardCharsets.US_ASCII);
+        System.arraycopy(chars, 0, b, offset, Math.min(length, chars.length));
+    }
+}
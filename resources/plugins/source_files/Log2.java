@@ -226,8 +226,10 @@ public class Log2 implements WhiteboxPlugin {
                 data = inputFile.getRowValues(row);
                 for (col = 0; col < cols; col++) {
                     z = data[col];
-                    if (z != noData) {
-                        outputFile.setValue(row, col, Math.log(z) / log2);
+                    // the logarithm of zero or a negative value is undefined,
+                    // so these cells are left as NoData
+                    if (z != noData && z > 0) {
+                        outputFile.setValue(row, col, Math.log10(z) / log2);
                     }
 
                 }
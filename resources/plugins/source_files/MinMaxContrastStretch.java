@@ -176,7 +176,6 @@ public class MinMaxContrastStretch implements WhiteboxPlugin {
         minVal = Double.parseDouble(args[2]);
         maxVal = Double.parseDouble(args[3]);
         numBins = Integer.parseInt(args[4]);
-        int numBinsLessOne = numBins - 1;
         
         // check to see that the inputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
@@ -184,15 +183,27 @@ public class MinMaxContrastStretch implements WhiteboxPlugin {
             return;
         }
         
+        if (maxVal <= minVal) {
+            showFeedback("The upper-tail clip value must be greater than the lower-tail clip value.");
+            return;
+        }
+        
         try {
             
             WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
             int nRows = image.getNumberRows();
             int nCols = image.getNumberColumns();
             noData = image.getNoDataValue();
-            double scaleFactor = numBins / (maxVal - minVal);
+            StretchOutput stretch = new StretchOutput(numBins, args, 5, noData);
+            if (stretch.getError() != null) {
+                image.close();
+                showFeedback(stretch.getError());
+                return;
+            }
+            double outputNoData = stretch.getNoData();
             
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.INTEGER, noData);
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, stretch.getDataType(), outputNoData);
+            output.setNoDataValue(outputNoData);
             output.setPreferredPalette(image.getPreferredPalette());
             
             double[] data = null;
@@ -200,15 +211,16 @@ public class MinMaxContrastStretch implements WhiteboxPlugin {
                 data = image.getRowValues(row);
                 for (col = 0; col < nCols; col++) {
                     if (data[col] != noData) {
-                        z = (int)(data[col] - minVal) * scaleFactor;
-                        if (z < 0) { z = 0; }
-                        if (z > numBinsLessOne) { z = numBinsLessOne; }
+                        z = stretch.stretch(data[col], minVal, maxVal);
                         output.setValue(row, col, z);
                     } else {
-                        output.setValue(row, col, noData);
+                        output.setValue(row, col, outputNoData);
                     }
                 }
                 if (cancelOp) {
+                    image.close();
+                    output.isTemporaryFile = true;
+                    output.close();
                     cancelOperation();
                     return;
                 }
@@ -221,6 +233,7 @@ public class MinMaxContrastStretch implements WhiteboxPlugin {
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Output: " + stretch.getDescription());
             output.close();
 
             // returning a header file string displays the image.
@@ -0,0 +1,384 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.Map;
+import java.util.TreeMap;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool identifies the pole of inaccessibility of each patch in a raster,
+ * i.e. the patch cell that is farthest from the patch's edge, as measured by
+ * a Euclidean distance transform of the patch. Unlike the centroid, which can
+ * lie outside of a concave patch, the pole is always a cell of the patch and
+ * is therefore a good representative point for labelling and sampling.
+ */
+public class PoleOfInaccessibility implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "PoleOfInaccessibility";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Pole Of Inaccessibility (Raster)";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Identifies the cell in each patch that is farthest from the patch edge.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = {"PatchShapeTools"};
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        boolean blnTextOutput = false;
+        int row, col, i, r, c;
+        int progress, oldProgress;
+        double z, dist;
+        // the neighbours visited in the forward (0-3) and backward (4-7) passes
+        int[] dX = {-1, -1, 0, 1, 1, 1, 0, -1};
+        int[] dY = {0, -1, -1, -1, 0, 1, 1, 1};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                blnTextOutput = Boolean.parseBoolean(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
+            int rows = image.getNumberRows();
+            int cols = image.getNumberColumns();
+            double noData = image.getNoDataValue();
+            double cellSizeX = image.getCellSizeX();
+            double cellSizeY = image.getCellSizeY();
+            
+            // Patch cells are those with positive values, as in the Centroid 
+            // tool. Each patch cell records the row and column of the nearest 
+            // cell that is not part of its patch, which may be a cell of 
+            // another patch, a background or NoData cell, or a cell beyond 
+            // the edge of the grid. These are initialized with the nearest 
+            // cell beyond the grid edge and then propagated through the grid 
+            // in a forward and a backward pass, in the manner of the 
+            // Euclidean Distance tool.
+            int[][] patch = new int[rows][cols];
+            int[][] nearestRow = new int[rows][cols];
+            int[][] nearestCol = new int[rows][cols];
+            double[][] distSquared = new double[rows][cols];
+            double[] data;
+            for (row = 0; row < rows; row++) {
+                data = image.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z > 0 && z != noData) {
+                        patch[row][col] = (int) z;
+                        r = row;
+                        c = -1;
+                        if (cols - col < col + 1) {
+                            c = cols;
+                        }
+                        double dC = (c - col) * cellSizeX;
+                        double d = dC * dC;
+                        int edgeRow = (rows - row < row + 1) ? rows : -1;
+                        double dR = (edgeRow - row) * cellSizeY;
+                        if (dR * dR < d) {
+                            r = edgeRow;
+                            c = col;
+                            d = dR * dR;
+                        }
+                        nearestRow[row][col] = r;
+                        nearestCol[row][col] = c;
+                        distSquared[row][col] = d;
+                    }
+                }
+            }
+            image.close();
+            
+            oldProgress = -1;
+            for (int pass = 0; pass < 2; pass++) {
+                int start = (pass == 0) ? 0 : 4;
+                for (int k = 0; k < rows; k++) {
+                    row = (pass == 0) ? k : rows - 1 - k;
+                    for (int m = 0; m < cols; m++) {
+                        col = (pass == 0) ? m : cols - 1 - m;
+                        int id = patch[row][col];
+                        if (id == 0) {
+                            continue;
+                        }
+                        for (i = start; i < start + 4; i++) {
+                            int y = row + dY[i];
+                            int x = col + dX[i];
+                            if (y < 0 || y >= rows || x < 0 || x >= cols) {
+                                continue;
+                            }
+                            if (patch[y][x] != id) {
+                                // the neighbour itself is outside of the patch
+                                r = y;
+                                c = x;
+                            } else {
+                                r = nearestRow[y][x];
+                                c = nearestCol[y][x];
+                            }
+                            double dC = (c - col) * cellSizeX;
+                            double dR = (r - row) * cellSizeY;
+                            double d = dC * dC + dR * dR;
+                            if (d < distSquared[row][col]) {
+                                distSquared[row][col] = d;
+                                nearestRow[row][col] = r;
+                                nearestCol[row][col] = c;
+                            }
+                        }
+                    }
+                    progress = (int) (100f * (pass * rows + k) / (2 * rows - 1));
+                    if (progress != oldProgress) {
+                        updateProgress("Calculating distances:", progress);
+                        oldProgress = progress;
+                        if (cancelOp) {
+                            cancelOperation();
+                            return;
+                        }
+                    }
+                }
+            }
+            
+            // find the most distant cell of each patch; ties are resolved in 
+            // favour of the first cell in row-major order
+            TreeMap<Integer, double[]> poles = new TreeMap<>();
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    int id = patch[row][col];
+                    if (id == 0) {
+                        continue;
+                    }
+                    double[] pole = poles.get(id);
+                    if (pole == null) {
+                        pole = new double[]{row, col, -1};
+                        poles.put(id, pole);
+                    }
+                    if (distSquared[row][col] > pole[2]) {
+                        pole[0] = row;
+                        pole[1] = col;
+                        pole[2] = distSquared[row][col];
+                    }
+                }
+            }
+            
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette(image.getPreferredPalette());
+            for (Map.Entry<Integer, double[]> e : poles.entrySet()) {
+                double[] pole = e.getValue();
+                output.setValue((int) pole[0], (int) pole[1], e.getKey());
+            }
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.close();
+
+            if (blnTextOutput) {
+                StringBuilder retstr = new StringBuilder("Patch Pole Of Inaccessibility\nPatch ID\tColumn\tRow\tDistance");
+                for (Map.Entry<Integer, double[]> e : poles.entrySet()) {
+                    double[] pole = e.getValue();
+                    dist = Math.sqrt(pole[2]);
+                    retstr.append("\n").append(e.getKey()).append("\t").append((int) pole[1])
+                            .append("\t").append((int) pole[0]).append("\t").append((float) dist);
+                }
+                returnData(retstr.toString());
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
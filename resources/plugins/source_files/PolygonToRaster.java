@@ -0,0 +1,636 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Arrays;
+import java.util.Date;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterBase;
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
+import whitebox.geospatialfiles.shapefile.attributes.DBFField;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.geospatialfiles.shapefile.PolygonM;
+import whitebox.geospatialfiles.shapefile.PolygonZ;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool can be used to convert a vector polygons file (shapefile) into a 
+ * raster grid, assigning each cell to the polygon that contains its centre, 
+ * that covers the largest part of its area, or any polygon that it intersects.
+ * Unlike the Vector Polygons To Raster tool, cells are assigned according to 
+ * their exact geometry, and overlapping polygons are resolved by a rule.
+ */
+public class PolygonToRaster implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "PolygonToRaster";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Polygon To Raster";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Converts vector polygons into a raster by cell centre, majority area, or any overlap.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"RasterVectorConversions", "RasterCreation"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputFile;
+        String outputHeader;
+        String assignmentFieldName;
+        int assignmentFieldNum = -1;
+        String baseFileHeader = "not specified";
+        String method = "centroid";
+        String overlapRule = "last";
+        int subSampleFactor = 5;
+        double backgroundValue;
+        double cellSize = -1.0;
+        double noData = -32768.0;
+        DataType dataType = WhiteboxRasterBase.DataType.INTEGER;
+        boolean useRecID = false;
+        int row, col, i, progress, oldProgress;
+        double value;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        inputFile = args[0];
+        outputHeader = args[1];
+        assignmentFieldName = args[2];
+        if (args[3].toLowerCase().contains("nodata")) {
+            backgroundValue = noData;
+        } else {
+            backgroundValue = Double.parseDouble(args[3]);
+        }
+        if (!args[4].toLowerCase().contains("not specified") && !args[4].trim().isEmpty()) {
+            cellSize = Double.parseDouble(args[4]);
+        }
+        baseFileHeader = args[5];
+        if (args.length > 6) {
+            method = args[6].toLowerCase().trim().replace(" ", "_").replace("-", "_");
+        }
+        if (args.length > 7 && !args[7].toLowerCase().contains("not specified")
+                && !args[7].trim().isEmpty()) {
+            subSampleFactor = Integer.parseInt(args[7].trim());
+        }
+        if (args.length > 8) {
+            overlapRule = args[8].toLowerCase().trim();
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (!method.equals("centroid") && !method.equals("majority_area")
+                && !method.equals("any_part")) {
+            showFeedback("The method must be one of 'centroid', 'majority_area', or 'any_part'.");
+            return;
+        }
+        if (!overlapRule.equals("first") && !overlapRule.equals("last")
+                && !overlapRule.equals("sum")) {
+            showFeedback("The overlap rule must be one of 'first', 'last', or 'sum'.");
+            return;
+        }
+        if (subSampleFactor < 1) {
+            showFeedback("The sub-sample factor must be at least 1.");
+            return;
+        }
+
+        try {
+
+            // initialize the shapefile input
+            ShapeFile input = new ShapeFile(inputFile);
+            int numRecs = input.getNumberOfRecords();
+
+            if (input.getShapeType().getBaseType() != ShapeType.POLYGON) {
+                showFeedback("The input shapefile must be of a 'polygon' data type.");
+                return;
+            }
+
+            // what type of data is contained in assignmentFieldName?
+            AttributeTable reader = input.getAttributeTable();
+            int numberOfFields = reader.getFieldCount();
+
+            for (i = 0; i < numberOfFields; i++) {
+                DBFField field = reader.getField(i);
+
+                if (field.getName().equals(assignmentFieldName)) {
+                    assignmentFieldNum = i;
+                    if (field.getDataType() == DBFField.DBFDataType.NUMERIC
+                            || field.getDataType() == DBFField.DBFDataType.FLOAT) {
+                        if (field.getDecimalCount() == 0) {
+                            dataType = WhiteboxRasterBase.DataType.INTEGER;
+                        } else {
+                            dataType = WhiteboxRasterBase.DataType.FLOAT;
+                        }
+                    } else {
+                        useRecID = true;
+                    }
+                }
+            }
+
+            if (assignmentFieldNum < 0) {
+                useRecID = true;
+            }
+
+            // initialize the output raster
+            WhiteboxRaster output;
+            if ((cellSize > 0) || baseFileHeader.toLowerCase().contains("not specified")
+                    || baseFileHeader.trim().isEmpty()) {
+                if (cellSize <= 0) {
+                    cellSize = Math.min((input.getyMax() - input.getyMin()) / 500.0,
+                            (input.getxMax() - input.getxMin()) / 500.0);
+                }
+                // the grid is aligned with the north-west corner of the 
+                // shapefile's bounding box
+                double north = input.getyMax();
+                double west = input.getxMin();
+                int rows = Math.max(1, (int) (Math.ceil((north - input.getyMin()) / cellSize)));
+                int cols = Math.max(1, (int) (Math.ceil((input.getxMax() - west) / cellSize)));
+                double east = west + cols * cellSize;
+                double south = north - rows * cellSize;
+
+                output = new WhiteboxRaster(outputHeader, north, south, east, west,
+                        rows, cols, WhiteboxRasterBase.DataScale.CONTINUOUS,
+                        dataType, backgroundValue, noData);
+            } else {
+                output = new WhiteboxRaster(outputHeader, "rw",
+                        baseFileHeader, dataType, backgroundValue);
+                output.setNoDataValue(noData);
+            }
+            
+            int rows = output.getNumberRows();
+            int cols = output.getNumberColumns();
+            double north = output.getNorth();
+            double west = output.getWest();
+            double cellSizeX = (output.getEast() - west) / cols;
+            double cellSizeY = (north - output.getSouth()) / rows;
+            
+            // Cells that have not been assigned to any polygon are NaN. When 
+            // the majority-area method is used, the fraction of each cell 
+            // that is covered by the polygon that it is assigned to is also 
+            // tracked, so that later polygons only replace it if they cover 
+            // more of the cell. The overlap rule applies to the polygons that 
+            // would be assigned to the same cell.
+            double[][] values = new double[rows][cols];
+            for (row = 0; row < rows; row++) {
+                Arrays.fill(values[row], Double.NaN);
+            }
+            float[][] coverage = null;
+            if (method.equals("majority_area")) {
+                coverage = new float[rows][cols];
+            }
+            
+            oldProgress = -1;
+            for (int r = 0; r < numRecs; r++) {
+                ShapeFileRecord record = input.getRecord(r);
+                if (record.getShapeType() == ShapeType.NULLSHAPE) {
+                    continue;
+                }
+                if (!useRecID) {
+                    Object obj = reader.getRecord(r)[assignmentFieldNum];
+                    if (obj == null) {
+                        continue;
+                    }
+                    value = Double.valueOf(obj.toString());
+                } else {
+                    value = record.getRecordNumber();
+                }
+                
+                double[][] points = getXYFromShapefileRecord(record);
+                if (points == null || points.length < 3) {
+                    continue;
+                }
+                
+                // the extent of the polygon in rows and columns
+                double minX = Double.POSITIVE_INFINITY, maxX = Double.NEGATIVE_INFINITY;
+                double minY = Double.POSITIVE_INFINITY, maxY = Double.NEGATIVE_INFINITY;
+                for (double[] p : points) {
+                    minX = Math.min(minX, p[0]);
+                    maxX = Math.max(maxX, p[0]);
+                    minY = Math.min(minY, p[1]);
+                    maxY = Math.max(maxY, p[1]);
+                }
+                int topRow = Math.max(0, (int) Math.floor((north - maxY) / cellSizeY));
+                int bottomRow = Math.min(rows - 1, (int) Math.floor((north - minY) / cellSizeY));
+                int leftCol = Math.max(0, (int) Math.floor((minX - west) / cellSizeX));
+                int rightCol = Math.min(cols - 1, (int) Math.floor((maxX - west) / cellSizeX));
+                if (topRow > bottomRow || leftCol > rightCol) {
+                    continue; // the polygon is outside of the grid
+                }
+                int numRows = bottomRow - topRow + 1;
+                int numCols = rightCol - leftCol + 1;
+                
+                if (method.equals("majority_area")) {
+                    // count the sub-cell centres of each cell that are in the polygon
+                    int[][] counts = new int[numRows][numCols];
+                    int n = subSampleFactor;
+                    for (int sr = topRow * n; sr < (bottomRow + 1) * n; sr++) {
+                        double y = north - (sr + 0.5) * cellSizeY / n;
+                        double[] crossings = scanlineCrossings(points, y);
+                        for (int k = 0; k + 1 < crossings.length; k += 2) {
+                            int stSubCol = Math.max(leftCol * n, (int) Math.ceil((crossings[k] - west) / (cellSizeX / n) - 0.5));
+                            int endSubCol = Math.min((rightCol + 1) * n - 1, (int) Math.ceil((crossings[k + 1] - west) / (cellSizeX / n) - 0.5) - 1);
+                            for (int sc = stSubCol; sc <= endSubCol; sc++) {
+                                counts[sr / n - topRow][sc / n - leftCol]++;
+                            }
+                        }
+                    }
+                    float totalSubCells = n * n;
+                    for (row = 0; row < numRows; row++) {
+                        for (col = 0; col < numCols; col++) {
+                            if (counts[row][col] == 0) {
+                                continue;
+                            }
+                            float cov = counts[row][col] / totalSubCells;
+                            int gr = row + topRow;
+                            int gc = col + leftCol;
+                            if (Double.isNaN(values[gr][gc]) || cov > coverage[gr][gc]) {
+                                values[gr][gc] = value;
+                                coverage[gr][gc] = cov;
+                            } else if (cov == coverage[gr][gc]) {
+                                values[gr][gc] = applyOverlapRule(values[gr][gc], value, overlapRule);
+                            }
+                        }
+                    }
+                } else {
+                    boolean[][] inPolygon = new boolean[numRows][numCols];
+                    // scanline fill of the cells whose centres are in the polygon
+                    for (row = topRow; row <= bottomRow; row++) {
+                        double y = north - (row + 0.5) * cellSizeY;
+                        double[] crossings = scanlineCrossings(points, y);
+                        for (int k = 0; k + 1 < crossings.length; k += 2) {
+                            int stCol = Math.max(leftCol, (int) Math.ceil((crossings[k] - west) / cellSizeX - 0.5));
+                            int endCol = Math.min(rightCol, (int) Math.ceil((crossings[k + 1] - west) / cellSizeX - 0.5) - 1);
+                            for (col = stCol; col <= endCol; col++) {
+                                inPolygon[row - topRow][col - leftCol] = true;
+                            }
+                        }
+                    }
+                    if (method.equals("any_part")) {
+                        // A cell that is intersected by the polygon but whose 
+                        // centre is outside of it must have part of the 
+                        // polygon's boundary passing through its interior. Add 
+                        // each of the cells whose interior is crossed by an 
+                        // edge, by clipping the edge to each row. Cells that 
+                        // only touch the polygon along their borders are not 
+                        // included.
+                        for (int p = 0; p < points.length - 1; p++) {
+                            if (partStart[p + 1]) {
+                                continue;
+                            }
+                            double x1 = points[p][0], y1 = points[p][1];
+                            double x2 = points[p + 1][0], y2 = points[p + 1][1];
+                            int r1 = Math.max(topRow, (int) Math.floor((north - Math.max(y1, y2)) / cellSizeY));
+                            int r2 = Math.min(bottomRow, (int) Math.ceil((north - Math.min(y1, y2)) / cellSizeY) - 1);
+                            for (row = r1; row <= r2; row++) {
+                                double yTop = north - row * cellSizeY;
+                                double yBottom = yTop - cellSizeY;
+                                double xa, xb;
+                                if (y1 == y2) {
+                                    xa = Math.min(x1, x2);
+                                    xb = Math.max(x1, x2);
+                                } else {
+                                    double t1 = (Math.max(yBottom, Math.min(y1, y2)) - y1) / (y2 - y1);
+                                    double t2 = (Math.min(yTop, Math.max(y1, y2)) - y1) / (y2 - y1);
+                                    xa = Math.min(x1 + t1 * (x2 - x1), x1 + t2 * (x2 - x1));
+                                    xb = Math.max(x1 + t1 * (x2 - x1), x1 + t2 * (x2 - x1));
+                                }
+                                int stCol = (int) Math.floor((xa - west) / cellSizeX);
+                                int endCol = (int) Math.ceil((xb - west) / cellSizeX) - 1;
+                                if (xa == xb && endCol < stCol) {
+                                    continue; // a vertical edge along a column border
+                                }
+                                stCol = Math.max(leftCol, stCol);
+                                endCol = Math.min(rightCol, endCol);
+                                for (col = stCol; col <= endCol; col++) {
+                                    inPolygon[row - topRow][col - leftCol] = true;
+                                }
+                            }
+                        }
+                    }
+                    for (row = 0; row < numRows; row++) {
+                        for (col = 0; col < numCols; col++) {
+                            if (inPolygon[row][col]) {
+                                int gr = row + topRow;
+                                int gc = col + leftCol;
+                                if (Double.isNaN(values[gr][gc])) {
+                                    values[gr][gc] = value;
+                                } else {
+                                    values[gr][gc] = applyOverlapRule(values[gr][gc], value, overlapRule);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                progress = (int) (100f * r / (numRecs - 1));
+                if (progress != oldProgress) {
+                    updateProgress("Rasterizing polygons:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            
+            double[] outRow = new double[cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    outRow[col] = Double.isNaN(values[row][col]) ? backgroundValue : values[row][col];
+                }
+                output.setRowValues(row, outRow);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Method: " + method);
+            output.addMetadataEntry("Overlap rule: " + overlapRule);
+            output.addMetadataEntry("Created on " + new Date());
+
+            output.flush();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private static double applyOverlapRule(double currentValue, double newValue, String overlapRule) {
+        switch (overlapRule) {
+            case "first":
+                return currentValue;
+            case "sum":
+                return currentValue + newValue;
+            default: // last
+                return newValue;
+        }
+    }
+    
+    // partStart[i] is true if point i is the first point of a part, in which 
+    // case points i - 1 and i are not joined by an edge.
+    private boolean[] partStart;
+    
+    /**
+     * Returns the sorted x coordinates at which the edges of a polygon cross 
+     * a horizontal line. Edges are treated as half-open in y, so that a vertex 
+     * on the line is counted once, and the even-odd rule over all of the 
+     * polygon's parts means that the crossings can be taken in pairs, with 
+     * holes excluded.
+     */
+    private double[] scanlineCrossings(double[][] points, double y) {
+        double[] crossings = new double[points.length];
+        int n = 0;
+        for (int p = 0; p < points.length - 1; p++) {
+            if (partStart[p + 1]) {
+                continue;
+            }
+            double y1 = points[p][1];
+            double y2 = points[p + 1][1];
+            if ((y1 > y) != (y2 > y)) {
+                double x1 = points[p][0];
+                double x2 = points[p + 1][0];
+                crossings[n] = x1 + (y - y1) / (y2 - y1) * (x2 - x1);
+                n++;
+            }
+        }
+        crossings = Arrays.copyOf(crossings, n);
+        Arrays.sort(crossings);
+        return crossings;
+    }
+
+    /**
+     * Returns the points of a polygon record, with each part closed, and sets 
+     * the partStart array.
+     */
+    private double[][] getXYFromShapefileRecord(ShapeFileRecord record) {
+        double[][] points;
+        int[] parts;
+        ShapeType shapeType = record.getShapeType();
+        switch (shapeType) {
+            case POLYGON:
+                whitebox.geospatialfiles.shapefile.Polygon recPolygon
+                        = (whitebox.geospatialfiles.shapefile.Polygon) (record.getGeometry());
+                points = recPolygon.getPoints();
+                parts = recPolygon.getParts();
+                break;
+            case POLYGONZ:
+                PolygonZ recPolygonZ = (PolygonZ) (record.getGeometry());
+                points = recPolygonZ.getPoints();
+                parts = recPolygonZ.getParts();
+                break;
+            case POLYGONM:
+                PolygonM recPolygonM = (PolygonM) (record.getGeometry());
+                points = recPolygonM.getPoints();
+                parts = recPolygonM.getParts();
+                break;
+            default:
+                return null;
+        }
+        
+        // copy the points, closing any part whose last point isn't its first
+        double[][] ret = new double[points.length + parts.length][];
+        boolean[] starts = new boolean[points.length + parts.length];
+        int n = 0;
+        for (int part = 0; part < parts.length; part++) {
+            int start = parts[part];
+            int end = (part < parts.length - 1) ? parts[part + 1] : points.length;
+            if (end <= start) {
+                continue;
+            }
+            starts[n] = true;
+            for (int i = start; i < end; i++) {
+                ret[n] = points[i];
+                n++;
+            }
+            if (points[end - 1][0] != points[start][0] || points[end - 1][1] != points[start][1]) {
+                ret[n] = points[start];
+                n++;
+            }
+        }
+        partStart = Arrays.copyOf(starts, n + 1);
+        partStart[n] = true;
+        return Arrays.copyOf(ret, n);
+    }
+}
@@ -261,7 +261,7 @@ public class Power implements WhiteboxPlugin {
                         z1 = data1[col];
                         z2 = data2[col];
                         if ((z1 != noData1) && (z2 != noData2)) {
-                            outputFile.setValue(row, col, Math.pow(z1, z2));
+                            outputFile.setValue(row, col, power(z1, z2, noData1));
                         } else {
                             outputFile.setValue(row, col, noData1);
                         }
@@ -301,7 +301,7 @@ public class Power implements WhiteboxPlugin {
                     for (col = 0; col < cols; col++) {
                         z1 = data1[col];
                         if (z1 != noData) {
-                            outputFile.setValue(row, col, Math.pow(z1, constant2));
+                            outputFile.setValue(row, col, power(z1, constant2, noData));
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -338,7 +338,7 @@ public class Power implements WhiteboxPlugin {
                     for (col = 0; col < cols; col++) {
                         z2 = data2[col];
                         if (z2 != noData) {
-                            outputFile.setValue(row, col, Math.pow(constant1, z2));
+                            outputFile.setValue(row, col, power(constant1, z2, noData));
                         }
                     }
                     progress = (int) (100f * row / (rows - 1));
@@ -379,4 +379,11 @@ public class Power implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
+
+    // A negative base with a fractional exponent, or zero with a negative
+    // exponent, has no real result, and the cell is NoData.
+    private static double power(double base, double exponent, double noData) {
+        double z = Math.pow(base, exponent);
+        return Double.isNaN(z) || Double.isInfinite(z) ? noData : z;
+    }
 }
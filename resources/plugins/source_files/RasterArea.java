@@ -0,0 +1,416 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.text.DecimalFormat;
+import java.util.Map;
+import java.util.TreeMap;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.georeference.Ellipsoid;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the area covered by each class, i.e. unique value, in a
+ * classified raster, in grid cells, square metres, hectares, square kilometres,
+ * or acres, along with each class's percentage of the total area. Areas of
+ * rasters in geographic coordinates can be calculated cell by cell using the
+ * latitude of each cell centre.
+ */
+public class RasterArea implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "RasterArea";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Raster Area";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the area of each class in a classified raster.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = {"GISTools", "StatisticalTools"};
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String csvFile = null;
+        String units = "ha";
+        boolean zeroBackground = false;
+        boolean useGeodesic = false;
+        int row, col, i;
+        int progress, oldProgress;
+        double z;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                csvFile = args[i];
+            } else if (i == 2 && specified) {
+                units = args[i].toLowerCase().trim();
+            } else if (i == 3 && specified) {
+                zeroBackground = Boolean.parseBoolean(args[i]);
+            } else if (i == 4 && specified) {
+                useGeodesic = Boolean.parseBoolean(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (csvFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        // the multiplier that converts square metres into the output units
+        double unitsMultiplier;
+        String unitsLabel;
+        boolean isCells = false;
+        if (units.startsWith("cell")) {
+            isCells = true;
+            unitsMultiplier = 1.0;
+            unitsLabel = "cells";
+        } else if (units.equals("m2")) {
+            unitsMultiplier = 1.0;
+            unitsLabel = "m2";
+        } else if (units.startsWith("ha") || units.startsWith("hect")) {
+            unitsMultiplier = 0.0001;
+            unitsLabel = "ha";
+        } else if (units.equals("km2")) {
+            unitsMultiplier = 0.000001;
+            unitsLabel = "km2";
+        } else if (units.startsWith("ac")) {
+            unitsMultiplier = 1.0 / 4046.8564224;
+            unitsLabel = "acres";
+        } else {
+            showFeedback("The units must be one of 'cells', 'm2', 'ha', 'km2', or 'acres'.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
+            int rows = image.getNumberRows();
+            int cols = image.getNumberColumns();
+            double noData = image.getNoDataValue();
+            double cellSizeX = image.getCellSizeX();
+            double cellSizeY = image.getCellSizeY();
+            String xyUnits = image.getXYUnits().toLowerCase();
+            boolean isGeographic = xyUnits.contains("deg");
+            
+            // The area of a cell in square metres. For projected rasters, map 
+            // units other than feet are assumed to be metres. For geographic 
+            // rasters, the lengths of a degree of latitude and longitude on the 
+            // WGS 84 ellipsoid are found either at the latitude of each row's 
+            // cell centres or, as an approximation, at the raster's mid-latitude.
+            double[] rowCellArea = new double[rows];
+            if (!isGeographic) {
+                double cellArea = cellSizeX * cellSizeY;
+                if (xyUnits.contains("feet") || xyUnits.contains("foot")) {
+                    cellArea *= 0.09290304;
+                }
+                for (row = 0; row < rows; row++) {
+                    rowCellArea[row] = cellArea;
+                }
+            } else {
+                double midLat = (image.getNorth() + image.getSouth()) / 2.0;
+                for (row = 0; row < rows; row++) {
+                    double lat = useGeodesic ? image.getNorth() - (row + 0.5) * cellSizeY : midLat;
+                    rowCellArea[row] = degreeLengthLat(lat) * cellSizeY
+                            * degreeLengthLon(lat) * cellSizeX;
+                }
+                if (!useGeodesic && !isCells) {
+                    String msg = "Warning: The input raster is in geographic coordinates. The "
+                            + "areas are approximate because they are based on the cell size at "
+                            + "the raster's mid-latitude. Use the geodesic option to calculate "
+                            + "the area of each cell at its own latitude.";
+                    showFeedback(msg);
+                    myHost.logMessage(Level.WARNING, msg);
+                }
+            }
+            
+            // Total the number of cells and the area of each class.
+            TreeMap<Double, double[]> classes = new TreeMap<>();
+            double totalCells = 0;
+            double totalArea = 0;
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                double[] data = image.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    z = data[col];
+                    if (z == noData || (zeroBackground && z == 0)) {
+                        continue;
+                    }
+                    double[] entry = classes.get(z);
+                    if (entry == null) {
+                        entry = new double[2];
+                        classes.put(z, entry);
+                    }
+                    entry[0]++;
+                    entry[1] += rowCellArea[row];
+                    totalCells++;
+                    totalArea += rowCellArea[row];
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    updateProgress("Calculating areas:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            image.close();
+            
+            if (classes.isEmpty()) {
+                showFeedback("The input raster doesn't contain any valid class values.");
+                return;
+            }
+
+            double total = isCells ? totalCells : totalArea * unitsMultiplier;
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                out.println("Value,Area (" + unitsLabel + "),Percent");
+                for (Map.Entry<Double, double[]> e : classes.entrySet()) {
+                    double[] entry = e.getValue();
+                    double area = isCells ? entry[0] : entry[1] * unitsMultiplier;
+                    double percent = isCells ? 100.0 * entry[0] / totalCells 
+                            : 100.0 * entry[1] / totalArea;
+                    out.println(valueString(e.getKey()) + "," + area + "," + percent);
+                }
+            }
+            
+            DecimalFormat df = new DecimalFormat("###,###,###,##0.000");
+            StringBuilder retstr = new StringBuilder();
+            retstr.append("RASTER AREA\n\n");
+            retstr.append("Input file:\t").append(new File(inputHeader).getName()).append("\n");
+            retstr.append("Number of classes:\t").append(classes.size()).append("\n");
+            retstr.append("Total area (").append(unitsLabel).append("):\t")
+                    .append(isCells ? String.valueOf((long) total) : df.format(total)).append("\n");
+            if (isGeographic && !isCells) {
+                retstr.append("Cell areas:\t").append(useGeodesic 
+                        ? "calculated at the latitude of each cell centre" 
+                        : "approximated at the mid-latitude").append("\n");
+            }
+            if (zeroBackground) {
+                retstr.append("Zero-valued cells were treated as background.\n");
+            }
+            retstr.append("Output file:\t").append(csvFile).append("\n");
+            returnData(retstr.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private static final double A = Ellipsoid.WGS_84.majorAxis();
+    private static final double E2 = Ellipsoid.WGS_84.firstEccentricitySquared();
+    
+    /**
+     * Returns the length in metres of one degree of latitude at the given 
+     * latitude (in degrees).
+     */
+    private static double degreeLengthLat(double lat) {
+        double phi = Math.toRadians(lat);
+        return 111132.954 - 559.822 * Math.cos(2.0 * phi) + 1.175 * Math.cos(4.0 * phi);
+    }
+    
+    /**
+     * Returns the length in metres of one degree of longitude at the given 
+     * latitude (in degrees).
+     */
+    private static double degreeLengthLon(double lat) {
+        double phi = Math.toRadians(lat);
+        double sinPhi = Math.sin(phi);
+        return Math.PI * A * Math.cos(phi) / (180.0 * Math.sqrt(1.0 - E2 * sinPhi * sinPhi));
+    }
+    
+    private static String valueString(double value) {
+        if (value == Math.rint(value) && Math.abs(value) < 1e15) {
+            return String.valueOf((long) value);
+        }
+        return String.valueOf(value);
+    }
+}
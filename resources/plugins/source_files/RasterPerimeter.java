@@ -0,0 +1,477 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.text.DecimalFormat;
+import java.util.Arrays;
+import java.util.Date;
+import java.util.Map;
+import java.util.TreeMap;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.georeference.Ellipsoid;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the total length of the boundaries of each class, i.e.
+ * unique value, in a classified raster, either by counting the edges of the
+ * grid cells along the boundaries or by cutting the corners of the boundaries
+ * diagonally, along with each class's perimeter-to-area ratio. It can also
+ * output a raster of the cells on the class boundaries.
+ */
+public class RasterPerimeter implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "RasterPerimeter";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Raster Perimeter";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Calculates the perimeter of each class in a classified raster.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = {"GISTools", "StatisticalTools"};
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String csvFile = null;
+        String outputHeader = null;
+        String units = "m";
+        boolean useDiagonals = false;
+        int row, col, i;
+        int progress, oldProgress;
+        double z;
+        // the neighbours to the north, east, south, and west
+        int[] dX = {0, 1, 0, -1};
+        int[] dY = {-1, 0, 1, 0};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                csvFile = args[i];
+            } else if (i == 2 && specified) {
+                units = args[i].toLowerCase().trim();
+            } else if (i == 3 && specified) {
+                useDiagonals = args[i].toLowerCase().contains("diag");
+            } else if (i == 4 && specified) {
+                outputHeader = args[i];
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (csvFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        // the multiplier that converts metres into the output units
+        double unitsMultiplier;
+        String unitsLabel;
+        boolean isCells = false;
+        if (units.startsWith("cell")) {
+            isCells = true;
+            unitsMultiplier = 1.0;
+            unitsLabel = "cells";
+        } else if (units.equals("m") || units.startsWith("met")) {
+            unitsMultiplier = 1.0;
+            unitsLabel = "m";
+        } else if (units.equals("km") || units.startsWith("kilo")) {
+            unitsMultiplier = 0.001;
+            unitsLabel = "km";
+        } else {
+            showFeedback("The units must be one of 'cells', 'm', or 'km'.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
+            int rows = image.getNumberRows();
+            int cols = image.getNumberColumns();
+            double noData = image.getNoDataValue();
+            double cellSizeX = image.getCellSizeX();
+            double cellSizeY = image.getCellSizeY();
+            String xyUnits = image.getXYUnits().toLowerCase();
+            boolean isGeographic = xyUnits.contains("deg");
+            
+            WhiteboxRaster output = null;
+            if (outputHeader != null) {
+                output = new WhiteboxRaster(outputHeader, "rw", inputHeader,
+                        WhiteboxRaster.DataType.INTEGER, noData);
+                output.setPreferredPalette("black_white.pal");
+                output.setDataScale(WhiteboxRaster.DataScale.BOOLEAN);
+            }
+            
+            // Each cell is visited with the rows above and below it. An edge 
+            // of a cell is on a boundary if the neighbour across the edge 
+            // belongs to another class, is NoData, or is beyond the edge of the 
+            // grid. The perimeter is accumulated corner by corner: each corner 
+            // of a cell is met by a half-edge from each of two sides, and if 
+            // both half-edges are on boundaries and the diagonal method is 
+            // used, they are replaced by a half-diagonal that cuts the corner.
+            TreeMap<Double, double[]> classes = new TreeMap<>();
+            double[] prevRow = new double[cols];
+            double[] curRow = image.getRowValues(0);
+            double[] nextRow;
+            double[] outRow = new double[cols];
+            boolean[] isBoundary = new boolean[4];
+            Arrays.fill(prevRow, noData);
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                if (row < rows - 1) {
+                    nextRow = image.getRowValues(row + 1);
+                } else {
+                    nextRow = new double[cols];
+                    Arrays.fill(nextRow, noData);
+                }
+                
+                // The lengths of the edges and the area of the cells in the 
+                // output units. Cells in geographic coordinates are measured 
+                // at the latitude of the row's cell centres on the WGS 84 
+                // ellipsoid, and other map units are assumed to be metres 
+                // unless they are feet.
+                double edgeX, edgeY, cellArea;
+                if (isCells) {
+                    edgeX = 1.0;
+                    edgeY = 1.0;
+                } else if (isGeographic) {
+                    double lat = image.getNorth() - (row + 0.5) * cellSizeY;
+                    edgeX = degreeLengthLon(lat) * cellSizeX * unitsMultiplier;
+                    edgeY = degreeLengthLat(lat) * cellSizeY * unitsMultiplier;
+                } else {
+                    double toMetres = (xyUnits.contains("feet") || xyUnits.contains("foot")) ? 0.3048 : 1.0;
+                    edgeX = cellSizeX * toMetres * unitsMultiplier;
+                    edgeY = cellSizeY * toMetres * unitsMultiplier;
+                }
+                cellArea = edgeX * edgeY;
+                double halfDiagonal = Math.sqrt(edgeX * edgeX + edgeY * edgeY) / 2.0;
+                
+                for (col = 0; col < cols; col++) {
+                    z = curRow[col];
+                    if (z == noData) {
+                        outRow[col] = noData;
+                        continue;
+                    }
+                    int numBoundaryEdges = 0;
+                    for (i = 0; i < 4; i++) {
+                        int c = col + dX[i];
+                        double zn;
+                        if (c < 0 || c >= cols) {
+                            zn = noData;
+                        } else if (dY[i] < 0) {
+                            zn = prevRow[c];
+                        } else if (dY[i] > 0) {
+                            zn = nextRow[c];
+                        } else {
+                            zn = curRow[c];
+                        }
+                        isBoundary[i] = (zn != z);
+                        if (isBoundary[i]) {
+                            numBoundaryEdges++;
+                        }
+                    }
+                    
+                    double perimeter = 0;
+                    if (numBoundaryEdges > 0) {
+                        // the north and south edges have a length of edgeX and 
+                        // the east and west edges a length of edgeY
+                        for (i = 0; i < 4; i++) {
+                            int j = (i + 1) % 4;
+                            if (useDiagonals && isBoundary[i] && isBoundary[j]) {
+                                perimeter += halfDiagonal;
+                            } else {
+                                if (isBoundary[i]) {
+                                    perimeter += (i % 2 == 0 ? edgeX : edgeY) / 2.0;
+                                }
+                                if (isBoundary[j]) {
+                                    perimeter += (j % 2 == 0 ? edgeX : edgeY) / 2.0;
+                                }
+                            }
+                        }
+                    }
+                    
+                    double[] entry = classes.get(z);
+                    if (entry == null) {
+                        entry = new double[2];
+                        classes.put(z, entry);
+                    }
+                    entry[0] += perimeter;
+                    entry[1] += isCells ? 1.0 : cellArea;
+                    outRow[col] = numBoundaryEdges > 0 ? 1 : 0;
+                }
+                if (output != null) {
+                    output.setRowValues(row, outRow);
+                }
+                
+                prevRow = curRow;
+                curRow = nextRow;
+                
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    updateProgress("Calculating perimeters:", progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            image.close();
+            
+            if (output != null) {
+                output.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                output.addMetadataEntry("Created on " + new Date());
+                output.close();
+            }
+            
+            if (classes.isEmpty()) {
+                showFeedback("The input raster doesn't contain any valid class values.");
+                return;
+            }
+
+            String areaLabel = isCells ? "cells" : unitsLabel + "2";
+            double totalPerimeter = 0;
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                out.println("Value,Perimeter (" + unitsLabel + "),Perimeter-Area Ratio (" 
+                        + unitsLabel + "/" + areaLabel + ")");
+                for (Map.Entry<Double, double[]> e : classes.entrySet()) {
+                    double[] entry = e.getValue();
+                    out.println(valueString(e.getKey()) + "," + entry[0] + "," + entry[0] / entry[1]);
+                    totalPerimeter += entry[0];
+                }
+            }
+            
+            DecimalFormat df = new DecimalFormat("###,###,###,##0.000");
+            StringBuilder retstr = new StringBuilder();
+            retstr.append("RASTER PERIMETER\n\n");
+            retstr.append("Input file:\t").append(new File(inputHeader).getName()).append("\n");
+            retstr.append("Method:\t").append(useDiagonals ? "diagonals" : "pixel").append("\n");
+            retstr.append("Number of classes:\t").append(classes.size()).append("\n");
+            // each shared boundary is counted once for each of the two classes
+            retstr.append("Total of the class perimeters (").append(unitsLabel).append("):\t")
+                    .append(df.format(totalPerimeter)).append("\n");
+            retstr.append("Output file:\t").append(csvFile).append("\n");
+            returnData(retstr.toString());
+            if (output != null) {
+                returnData(outputHeader);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private static final double A = Ellipsoid.WGS_84.majorAxis();
+    private static final double E2 = Ellipsoid.WGS_84.firstEccentricitySquared();
+    
+    /**
+     * Returns the length in metres of one degree of latitude at the given 
+     * latitude (in degrees).
+     */
+    private static double degreeLengthLat(double lat) {
+        double phi = Math.toRadians(lat);
+        return 111132.954 - 559.822 * Math.cos(2.0 * phi) + 1.175 * Math.cos(4.0 * phi);
+    }
+    
+    /**
+     * Returns the length in metres of one degree of longitude at the given 
+     * latitude (in degrees).
+     */
+    private static double degreeLengthLon(double lat) {
+        double phi = Math.toRadians(lat);
+        double sinPhi = Math.sin(phi);
+        return Math.PI * A * Math.cos(phi) / (180.0 * Math.sqrt(1.0 - E2 * sinPhi * sinPhi));
+    }
+    
+    private static String valueString(double value) {
+        if (value == Math.rint(value) && Math.abs(value) < 1e15) {
+            return String.valueOf((long) value);
+        }
+        return String.valueOf(value);
+    }
+}
@@ -20,6 +20,7 @@ import java.util.Date;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
 
 /**
  * This tool creates a new raster in which each grid cell is assigned the terrain aspect relative to a user-specified wind direction.
@@ -144,7 +145,7 @@ public class RelativeAspect implements WhiteboxPlugin {
         }
         previousProgress = progress;
     }
-    
+
     /**
      * Sets the arguments (parameters) used by the plugin.
      *
@@ -191,32 +192,22 @@ public class RelativeAspect implements WhiteboxPlugin {
     @Override
     public void run() {
         amIActive = true;
-        
+
         String inputHeader = null;
         String outputHeader = null;
-        int row, col, x, y;
-        float progress = 0;
-        int i;
-        double radToDeg = 180 / Math.PI;
-        double degToRad = Math.PI / 180;
         double zFactor = 1;
-        double z;
-        int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
-        int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
-        double fx, fy;
-        double gridRes, eightGridRes;
         double azimuth = 0;
-        
+
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
             return;
         }
-        
+
         inputHeader = args[0];
         outputHeader = args[1];
-        azimuth = Double.parseDouble(args[2]);
-        if (azimuth < 0 || azimuth > 360) {
-            azimuth = 0;
+        azimuth = Double.parseDouble(args[2]) % 360;
+        if (azimuth < 0) {
+            azimuth += 360;
         }
         zFactor = Double.parseDouble(args[3]);
 
@@ -228,71 +219,106 @@ public class RelativeAspect implements WhiteboxPlugin {
 
         try {
             WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
-            
-            int rows = DEM.getNumberRows();
-            int cols = DEM.getNumberColumns();
-            double noData = DEM.getNoDataValue();
-            gridRes = DEM.getCellSizeX();
-            eightGridRes = 8 * gridRes;
-            
-            if (DEM.getXYUnits().toLowerCase().contains("deg") || 
-                    DEM.getProjection().toLowerCase().contains("geog")) {
+
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double eightGridResX = 8 * DEM.getCellSizeX();
+            final double eightGridResY = 8 * DEM.getCellSizeY();
+            final double windAzimuth = azimuth;
+
+            if (DEM.getXYUnits().toLowerCase().contains("deg")
+                    || DEM.getProjection().toLowerCase().contains("geog")) {
                 // calculate a new z-conversion factor
-                double midLat = (DEM.getNorth() - DEM.getSouth()) / 2.0;
+                double midLat = (DEM.getNorth() + DEM.getSouth()) / 2.0;
                 if (midLat <= 90 && midLat >= -90) {
                     zFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)));
                 }
             }
-            
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            final double conversionFactor = zFactor;
+
+            final double[][] elevations = new double[rows][];
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
+            }
+
+            final WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
             output.setPreferredPalette("grey.pal");
             output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
             output.setZUnits("degrees");
-            
-            double[] N = new double[8];
-            double rad180 = 180 * degToRad;
-            double rad90 = 90 * degToRad;
 
-            for (row = 0; row < rows; row++) {
-                for (col = 0; col < cols; col++) {
-                    z = DEM.getValue(row, col);
-                    if (z != noData) {
-                        z = z * zFactor;
-                        //get the neighbouring cell Z values
-                        for (i = 0; i < 8; i++) {
-                            N[i] = DEM.getValue(row + dY[i], col + dX[i]);
-                            if (N[i] != noData) {
-                                N[i] = N[i] * zFactor;
+            final int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+            final int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+            boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    double[] N = new double[8];
+                    for (int col = 0; col < cols; col++) {
+                        double z = elevations[row][col];
+                        if (z != noData) {
+                            z = z * conversionFactor;
+                            //get the neighbouring cell Z values
+                            for (int i = 0; i < 8; i++) {
+                                int rowN = row + dY[i];
+                                int colN = col + dX[i];
+                                if (rowN >= 0 && rowN < rows && colN >= 0 && colN < cols
+                                        && elevations[rowN][colN] != noData) {
+                                    N[i] = elevations[rowN][colN] * conversionFactor;
+                                } else {
+                                    N[i] = z;
+                                }
+                            }
+
+                            //calculate the slope gradients and the aspect, the
+                            //downslope direction measured clockwise from north
+                            double fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / eightGridResY;
+                            double fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / eightGridResX;
+                            if (fx != 0 || fy != 0) {
+                                double aspect = Math.toDegrees(Math.atan2(-fx, -fy));
+                                if (aspect < 0) {
+                                    aspect += 360;
+                                }
+                                double relativeAspect = Math.abs(aspect - windAzimuth);
+                                if (relativeAspect > 180) {
+                                    relativeAspect = 360 - relativeAspect;
+                                }
+                                data[col] = relativeAspect;
                             } else {
-                                N[i] = z;
+                                data[col] = -1;
                             }
-                        }
-                        
-                        //calculate slope and aspect
-                        fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / eightGridRes;
-                        fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / eightGridRes;
-                        if (fx != 0) {
-                            z = 180 - Math.atan(fy / fx) * radToDeg + 90 * (fx / Math.abs(fx));
-                            z = Math.abs(z - azimuth);
-                            if (z > 180) { z = 360 - z; }
-                            output.setValue(row, col, z);
                         } else {
-                            output.setValue(row, col, -1);
+                            data[col] = noData;
                         }
                     }
                 }
-                if (cancelOp) {
-                    cancelOperation();
-                    return;
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    output.setRowValues(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
                 }
-                progress = (float) (100f * row / (rows - 1));
-                updateProgress((int) progress);
+            });
+
+            if (!completed) {
+                DEM.close();
+                output.close();
+                cancelOperation();
+                return;
             }
 
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
-            
+            output.addMetadataEntry("Azimuth: " + azimuth + " degrees clockwise from north "
+                    + "(values are the angle between the aspect and the azimuth, "
+                    + "from 0 to 180 degrees, and -1 for flat cells)");
+
             DEM.close();
             output.close();
 
@@ -311,4 +337,4 @@ public class RelativeAspect implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
-}
\ No newline at end of file
+}
@@ -17,13 +17,14 @@
 package plugins;
 
 import java.util.Date;
+import whitebox.algorithms.RasterResampler;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterInfo;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
 /**
- * This tool will resample the values from one or more source image files into a destination image file using one of three resampling methods including, nearest neighbour, bilinear interpolation, and cubic convolution.
+ * This tool will resample the values from one or more source image files into a destination image file using one of four resampling methods including, nearest neighbour, bilinear interpolation, cubic convolution, and Lanczos.
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
 public class Resample implements WhiteboxPlugin {
@@ -162,18 +163,15 @@ public class Resample implements WhiteboxPlugin {
         WhiteboxRasterInfo imageInfo = null;
         int nCols = 0;
         int nRows = 0;
-        double imageNoData = -32768;
         double outputNoData = -32768;
         int numImages;
         double x, y, z;
         int progress = 0;
         int col, row;
-        int a, i;
+        int a;
         String inputFilesString = null;
         String[] imageFiles;
         String resampleMethod = "nearest neighbour";
-        double north, south, east, west;
-        double gridResX, gridResY;
         int currentFile = -1;
 
         if (args.length <= 0) {
@@ -185,12 +183,20 @@ public class Resample implements WhiteboxPlugin {
         inputFilesString = args[0];
         destHeader = args[1];
         resampleMethod = args[2].toLowerCase().trim();
-        if (!resampleMethod.equals("nearest neighbour")
-                && !resampleMethod.equals("bilinear")
-                && !resampleMethod.equals("cubic convolution")) {
+        RasterResampler.Method method = RasterResampler.getMethodFromString(resampleMethod);
+        if (method == null) {
             showFeedback("Resample method not recognized");
             return;
         }
+        RasterResampler.NoDataPolicy noDataPolicy = RasterResampler.NoDataPolicy.RENORMALIZE;
+        if (args.length > 3 && !args[3].trim().isEmpty() 
+                && !args[3].toLowerCase().contains("not specified")) {
+            noDataPolicy = RasterResampler.getNoDataPolicyFromString(args[3]);
+            if (noDataPolicy == null) {
+                showFeedback("NoData policy not recognized");
+                return;
+            }
+        }
 
         imageFiles = inputFilesString.split(";");
 
@@ -207,183 +213,63 @@ public class Resample implements WhiteboxPlugin {
             destination = new WhiteboxRaster(destHeader, "rw");
             nCols = destination.getNumberColumns();
             nRows = destination.getNumberRows();
-            int nColsLessOne = nCols - 1;
-            int nRowsLessOne = nRows - 1;
-            north = destination.getNorth();
-            south = destination.getSouth();
-            east = destination.getEast();
-            west = destination.getWest();
-            gridResX = destination.getCellSizeX();
-            gridResY = destination.getCellSizeY();
-            double northernEdge = north - gridResY;
-            double westernEdge = west + gridResX;
-            if (north < south) {
-                northernEdge = north + gridResY;
-            }
-
-            if (east < west) {
-                westernEdge = west - gridResX;
-            }
-
             outputNoData = destination.getNoDataValue();
-
-            double yRange = north - south - gridResY;
-            double xRange = east - west - gridResX;
-            int sourceCol, sourceRow;
+            WhiteboxRaster.DataType outputDataType = destination.getDataType();
             
             // retrieve data about each image.
-            double[][] imageData = new double[numImages][11];
+            double[][] imageData = new double[numImages][4];
             for (a = 0; a < numImages; a++) {
                 imageInfo = new WhiteboxRasterInfo(imageFiles[a]);
                 imageData[a][0] = imageInfo.getNorth();
                 imageData[a][1] = imageInfo.getSouth();
                 imageData[a][2] = imageInfo.getEast();
                 imageData[a][3] = imageInfo.getWest();
-                imageData[a][4] = imageInfo.getNumberRows();
-                imageData[a][5] = imageInfo.getNumberColumns();
-                imageData[a][6] = imageInfo.getCellSizeX();
-                imageData[a][7] = imageInfo.getCellSizeY();
-                imageData[a][8] = imageInfo.getNoDataValue();
-                imageData[a][9] = imageInfo.getEast() - imageInfo.getWest();
-                imageData[a][10] = imageInfo.getNorth() - imageInfo.getSouth();
             }
 
-            if (resampleMethod.equals("nearest neighbour")) {
-                for (row = 0; row < nRows; row++) {
-                    y = northernEdge - (yRange * row) / nRowsLessOne;
-                    for (col = 0; col < nCols; col++) {
-                        x = westernEdge + (xRange * col) / nColsLessOne;
-                        // see if this x, y location falls within any of the input images
-                        for (a = 0; a < numImages; a++) {
-                            if (isBetween(y, imageData[a][0], imageData[a][1])
-                                    && isBetween(x, imageData[a][2], imageData[a][3])) {
-                                if (a != currentFile) {
-                                    if (currentFile >= 0) {
-                                        image.close();
-                                    }
-                                    image = new WhiteboxRaster(imageFiles[a], "r");
-                                    currentFile = a;
-                                }
-                                // what are the col and row of the image?
-                                //row = (int) ((top - northing) / (top - bottom) * (rows - 0.5));
-                                sourceRow = (int)Math.round((imageData[a][0] - y) / imageData[a][10] * (imageData[a][4] - 0.5));
-                                //col = (int) ((easting - left) / (right - left) * (columns - 0.5));
-                                sourceCol = (int)Math.round((x - imageData[a][3]) / imageData[a][9] * (imageData[a][5] - 0.5));
-                                z = image.getValue(sourceRow, sourceCol);
-                                if (z != imageData[a][8]) { 
-                                    destination.setValue(row, col, z);
-                                    break; 
-                                } else {
-                                    destination.setValue(row, col, outputNoData);
+            RasterResampler resampler = null;
+            for (row = 0; row < nRows; row++) {
+                y = destination.getYCoordinateFromRow(row);
+                for (col = 0; col < nCols; col++) {
+                    x = destination.getXCoordinateFromColumn(col);
+                    z = outputNoData;
+                    // see if this x, y location falls within any of the input images
+                    for (a = 0; a < numImages; a++) {
+                        if (isBetween(y, imageData[a][0], imageData[a][1])
+                                && isBetween(x, imageData[a][2], imageData[a][3])) {
+                            if (a != currentFile) {
+                                if (currentFile >= 0) {
+                                    image.close();
                                 }
+                                image = new WhiteboxRaster(imageFiles[a], "r");
+                                resampler = new RasterResampler(image, method, noDataPolicy);
+                                currentFile = a;
+                            }
+                            z = resampler.getValue(x, y);
+                            if (z != image.getNoDataValue()) {
+                                break;
                             }
+                            z = outputNoData;
                         }
                     }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (nRows - 1));
-                    updateProgress(progress);
+                    destination.setValue(row, col, 
+                            RasterResampler.fitToDataType(z, outputDataType, outputNoData));
                 }
-                image.close();
-                
-            } else {
-                if (destination.getDataType() != WhiteboxRaster.DataType.DOUBLE && 
-                        destination.getDataType() != WhiteboxRaster.DataType.FLOAT) {
-                    showFeedback("The destination image is not of an appropriate data"
-                            + " type (i.e. double or float) to perform this operation.");
+                if (cancelOp) {
+                    cancelOperation();
                     return;
                 }
-                double dX, dY;
-                double srcRow, srcCol; 
-                double originRow, originCol;
-                double rowN, colN;
-                double sumOfDist;
-                double[] shiftX;
-                double[] shiftY;
-                int numNeighbours = 0;
-                double[][] neighbour;
-                if (resampleMethod.equals("cubic convolution")) {
-                    shiftX = new double[]{ -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2 };
-                    shiftY = new double[]{ -1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2 };
-                    numNeighbours = 16;
-                    neighbour = new double[16][2];
-                } else { // resampleMethod is "bilinear"
-                    shiftX = new double[]{ 0, 1, 0, 1 };
-                    shiftY = new double[]{ 0, 0, 1, 1 };
-                    numNeighbours = 4;
-                    neighbour = new double[4][2];
-                }
-                
-                for (row = 0; row < nRows; row++) {
-                    y = northernEdge - (yRange * row) / nRowsLessOne;
-                    for (col = 0; col < nCols; col++) {
-                        x = westernEdge + (xRange * col) / nColsLessOne;
-                        // see if this x, y location falls within any of the input images
-                        for (a = 0; a < numImages; a++) {
-                            if (isBetween(y, imageData[a][0], imageData[a][1])
-                                    && isBetween(x, imageData[a][2], imageData[a][3])) {
-                                if (a != currentFile) {
-                                    if (currentFile >= 0) {
-                                        image.close();
-                                    }
-                                    image = new WhiteboxRaster(imageFiles[a], "r");
-                                    currentFile = a;
-                                }
-                                imageNoData = imageData[a][8];
-                                // what are the col and row of the image?
-                                //row = ((top - northing) / (top - bottom) * (rows - 0.5));
-                                srcRow = (imageData[a][0] - y) / imageData[a][10] * (imageData[a][4] - 0.5);
-                                //col = ((easting - left) / (right - left) * (columns - 0.5));
-                                srcCol = (x - imageData[a][3]) / imageData[a][9] * (imageData[a][5] - 0.5);
-                                
-                                originRow = Math.floor(srcRow);
-                                originCol = Math.floor(srcCol);
-                                
-                                sumOfDist = 0;
-                                for (i = 0; i < numNeighbours; i++) {
-                                    rowN = originRow + shiftY[i];
-                                    colN = originCol + shiftX[i];
-                                    neighbour[i][0] = image.getValue((int)rowN, (int)colN);
-                                    dY = rowN - srcRow;
-                                    dX = colN - srcCol;
-                                    
-                                    if ((dX + dY) != 0 && neighbour[i][0] != imageNoData) {
-                                        neighbour[i][1] = 1 /(dX * dX + dY * dY);
-                                        sumOfDist += neighbour[i][1];
-                                    } else if (neighbour[i][0] == imageNoData) {
-                                        neighbour[i][1] = 0;
-                                    } else {
-                                        destination.setValue(row, col, neighbour[i][0]);
-                                        break;
-                                    }
-                                    
-                                }               
-                                
-                                if (sumOfDist > 0) { 
-                                    z = 0;
-                                    for (i = 0; i < numNeighbours; i++) {
-                                        z += (neighbour[i][0] * neighbour[i][1]) / sumOfDist;
-                                    }
-                                    destination.setValue(row, col, z);
-                                    break; 
-                                } else {
-                                    destination.setValue(row, col, outputNoData);
-                                }
-                            }
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (int) (100f * row / (nRows - 1));
-                    updateProgress(progress);
-                }
+                progress = (int) (100f * row / (nRows - 1));
+                updateProgress(progress);
+            }
+            if (image != null) {
                 image.close();
             }
 
+            destination.addMetadataEntry("Resampling method: " + resampleMethod);
+            if (method != RasterResampler.Method.NEAREST_NEIGHBOUR) {
+                destination.addMetadataEntry("NoData policy: " 
+                        + noDataPolicy.toString().toLowerCase());
+            }
             destination.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             destination.addMetadataEntry("Created on " + new Date());
@@ -0,0 +1,473 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.File;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.text.DecimalFormat;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+import java.util.Random;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.structures.KdTree;
+
+/**
+ * This tool calculates Ripley's K function and the linearized L function for a
+ * point pattern, with Monte Carlo envelopes from realizations of complete
+ * spatial randomness, to test whether the points are clustered, random, or
+ * dispersed at a range of spatial scales.
+ */
+public class RipleyKFunction implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "RipleyKFunction";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Ripley\'s K Function";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Tests a point pattern for clustering or dispersion "
+                + "at multiple scales using Ripley\'s K function.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = {"StatisticalTools"};
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputFile = null;
+        String csvFile = null;
+        double maxDist = -1;
+        int numIntervals = 50;
+        int numSimulations = 99;
+        long seed = System.nanoTime();
+        int i, j;
+        int progress, oldProgress;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputFile = args[i];
+            } else if (i == 1) {
+                csvFile = args[i];
+            } else if (i == 2 && specified) {
+                maxDist = Double.parseDouble(args[i]);
+            } else if (i == 3 && specified) {
+                numIntervals = Integer.parseInt(args[i].trim());
+            } else if (i == 4 && specified) {
+                numSimulations = Integer.parseInt(args[i].trim());
+            } else if (i == 5 && specified) {
+                seed = Long.parseLong(args[i].trim());
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (csvFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (numIntervals < 1 || numSimulations < 0) {
+            showFeedback("The number of intervals must be at least one and the number "
+                    + "of simulations can't be negative.");
+            return;
+        }
+
+        try {
+            ShapeFile input = new ShapeFile(inputFile);
+            ShapeType shapeType = input.getShapeType().getBaseType();
+            if (shapeType != ShapeType.POINT && shapeType != ShapeType.MULTIPOINT) {
+                showFeedback("The input shapefile must be of a 'point' data type.");
+                return;
+            }
+            
+            ArrayList<double[]> pointList = new ArrayList<>();
+            for (ShapeFileRecord record : input.records) {
+                if (record.getShapeType() == ShapeType.NULLSHAPE) {
+                    continue;
+                }
+                for (double[] p : record.getGeometry().getPoints()) {
+                    pointList.add(new double[]{p[0], p[1]});
+                }
+            }
+            int n = pointList.size();
+            if (n < 2) {
+                showFeedback("The input shapefile must contain at least two points.");
+                return;
+            }
+            double[][] points = pointList.toArray(new double[n][]);
+            
+            // the study area is taken to be the bounding box of the points
+            xMin = Double.POSITIVE_INFINITY;
+            yMin = Double.POSITIVE_INFINITY;
+            xMax = Double.NEGATIVE_INFINITY;
+            yMax = Double.NEGATIVE_INFINITY;
+            for (double[] p : points) {
+                xMin = Math.min(xMin, p[0]);
+                xMax = Math.max(xMax, p[0]);
+                yMin = Math.min(yMin, p[1]);
+                yMax = Math.max(yMax, p[1]);
+            }
+            double width = xMax - xMin;
+            double height = yMax - yMin;
+            double area = width * height;
+            if (area <= 0) {
+                showFeedback("The points are collinear and don't define a study area.");
+                return;
+            }
+            
+            if (maxDist <= 0) {
+                maxDist = Math.min(width, height) / 4.0;
+            } else if (maxDist > Math.min(width, height) / 2.0) {
+                String msg = "Warning: The maximum distance exceeds half of the shorter side "
+                        + "of the study area, where the edge correction becomes unreliable.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+            double interval = maxDist / numIntervals;
+            
+            updateProgress("Calculating K function:", 0);
+            double[] k = calculateK(points, area, maxDist, numIntervals);
+            if (k == null) {
+                return;
+            }
+            
+            // Monte Carlo envelopes from realizations of complete spatial 
+            // randomness (CSR) with the same number of points in the same area
+            double[] kLower = new double[numIntervals];
+            double[] kUpper = new double[numIntervals];
+            Arrays.fill(kLower, Double.POSITIVE_INFINITY);
+            Arrays.fill(kUpper, Double.NEGATIVE_INFINITY);
+            Random rand = new Random(seed);
+            double[][] simPoints = new double[n][2];
+            oldProgress = -1;
+            for (int s = 0; s < numSimulations; s++) {
+                for (i = 0; i < n; i++) {
+                    simPoints[i][0] = xMin + rand.nextDouble() * width;
+                    simPoints[i][1] = yMin + rand.nextDouble() * height;
+                }
+                double[] simK = calculateK(simPoints, area, maxDist, numIntervals);
+                if (simK == null) {
+                    return;
+                }
+                for (j = 0; j < numIntervals; j++) {
+                    kLower[j] = Math.min(kLower[j], simK[j]);
+                    kUpper[j] = Math.max(kUpper[j], simK[j]);
+                }
+                progress = (int) (100f * (s + 1) / numSimulations);
+                if (progress != oldProgress) {
+                    updateProgress("Simulating CSR envelopes:", progress);
+                    oldProgress = progress;
+                }
+            }
+            
+            int numClustered = 0;
+            int numDispersed = 0;
+            try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                out.println("Distance,K,Expected K,L,Expected L,K Lower Envelope,K Upper Envelope,"
+                        + "L Lower Envelope,L Upper Envelope");
+                for (j = 0; j < numIntervals; j++) {
+                    double t = (j + 1) * interval;
+                    StringBuilder sb = new StringBuilder();
+                    sb.append(t).append(",").append(k[j]).append(",").append(Math.PI * t * t);
+                    sb.append(",").append(lFunction(k[j], t)).append(",").append(0.0);
+                    if (numSimulations > 0) {
+                        sb.append(",").append(kLower[j]).append(",").append(kUpper[j]);
+                        sb.append(",").append(lFunction(kLower[j], t));
+                        sb.append(",").append(lFunction(kUpper[j], t));
+                        if (k[j] > kUpper[j]) {
+                            numClustered++;
+                        } else if (k[j] < kLower[j]) {
+                            numDispersed++;
+                        }
+                    } else {
+                        sb.append(",,,,");
+                    }
+                    out.println(sb.toString());
+                }
+            }
+            
+            DecimalFormat df = new DecimalFormat("0.0000");
+            StringBuilder retstr = new StringBuilder();
+            retstr.append("RIPLEY'S K FUNCTION\n\n");
+            retstr.append("Input file:\t").append(new File(inputFile).getName()).append("\n");
+            retstr.append("Number of points:\t").append(n).append("\n");
+            retstr.append("Study area (bounding box):\t").append(df.format(area)).append("\n");
+            retstr.append("Maximum distance:\t").append(df.format(maxDist)).append("\n");
+            retstr.append("Number of intervals:\t").append(numIntervals).append("\n");
+            retstr.append("Number of CSR simulations:\t").append(numSimulations).append("\n");
+            if (numSimulations > 0) {
+                retstr.append("Distances with K above the envelope (clustered):\t")
+                        .append(numClustered).append("\n");
+                retstr.append("Distances with K below the envelope (dispersed):\t")
+                        .append(numDispersed).append("\n");
+            }
+            retstr.append("Output file:\t").append(csvFile).append("\n");
+            returnData(retstr.toString());
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+    
+    private double xMin, xMax, yMin, yMax;
+    
+    private static double lFunction(double k, double t) {
+        return Math.sqrt(k / Math.PI) - t;
+    }
+    
+    /**
+     * Calculates the edge-corrected K function, i.e. (A / n^2) times the 
+     * weighted count of ordered pairs of points within each distance. Returns 
+     * null if the operation is cancelled.
+     */
+    private double[] calculateK(double[][] points, double area, double maxDist,
+            int numIntervals) {
+        int n = points.length;
+        double interval = maxDist / numIntervals;
+        KdTree<Integer> tree = new KdTree.SqrEuclid<>(2, new Integer(n));
+        for (int i = 0; i < n; i++) {
+            tree.addPoint(points[i], i);
+        }
+        double[] counts = new double[numIntervals];
+        for (int i = 0; i < n; i++) {
+            List<KdTree.Entry<Integer>> neighbours = tree.neighborsWithinRange(points[i], maxDist);
+            for (KdTree.Entry<Integer> entry : neighbours) {
+                if (entry.value == i) {
+                    continue;
+                }
+                double d = Math.sqrt(entry.distance);
+                if (d > maxDist) {
+                    continue;
+                }
+                int bin = Math.max(0, (int) Math.ceil(d / interval) - 1);
+                counts[bin] += edgeCorrection(points[i][0], points[i][1], d);
+            }
+            if (cancelOp) {
+                cancelOperation();
+                return null;
+            }
+        }
+        double[] k = new double[numIntervals];
+        double cumulative = 0;
+        for (int j = 0; j < numIntervals; j++) {
+            cumulative += counts[j];
+            k[j] = area / ((double) n * n) * cumulative;
+        }
+        return k;
+    }
+    
+    /**
+     * Ripley's isotropic edge correction for a rectangular study area, i.e.
+     * the reciprocal of the proportion of the circumference of the circle of 
+     * radius r centred on (x, y) that lies within the study area.
+     */
+    private double edgeCorrection(double x, double y, double r) {
+        if (r <= 0) {
+            return 1.0;
+        }
+        // distances to the left, bottom, right, and top edges, in order 
+        // around the rectangle so that consecutive edges are adjacent
+        double[] d = {x - xMin, y - yMin, xMax - x, yMax - y};
+        double[] alpha = new double[4];
+        double outside = 0;
+        for (int e = 0; e < 4; e++) {
+            alpha[e] = d[e] < r ? Math.acos(d[e] / r) : 0;
+            outside += 2 * alpha[e];
+        }
+        // the arcs beyond two adjacent edges overlap where the circle 
+        // contains the corner between them
+        for (int e = 0; e < 4; e++) {
+            int e2 = (e + 1) % 4;
+            if (d[e] * d[e] + d[e2] * d[e2] < r * r) {
+                outside -= alpha[e] + alpha[e2] - Math.PI / 2;
+            }
+        }
+        double inside = 1.0 - outside / (2 * Math.PI);
+        // limit the correction where little of the circle lies within the area
+        return 1.0 / Math.max(inside, 0.25);
+    }
+}
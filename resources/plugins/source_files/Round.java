@@ -23,7 +23,8 @@ import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 
 /**
- * This tool rounds the values in an input image.
+ * This tool rounds the values in an input image to the nearest integer, to a
+ * number of decimal places, or to the nearest multiple of a value.
  *
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
@@ -61,7 +62,8 @@ public class Round implements WhiteboxPlugin {
      */
     @Override
     public String getToolDescription() {
-        return "Rounds the values in a raster to the nearest integer.";
+        return "Rounds the values in a raster to the nearest integer, to a number "
+                + "of decimal places, or to the nearest multiple of a value.";
     }
 
     /**
@@ -196,6 +198,24 @@ public class Round implements WhiteboxPlugin {
 
         String inputHeader = args[0];
         String outputHeader = args[1];
+        // the optional number of decimal places or multiple, of which at
+        // most one may be specified.
+        int decimals = 0;
+        double multiple = 0;
+        if (args.length > 2 && !args[2].toLowerCase().contains("not specified")) {
+            decimals = Integer.parseInt(args[2].trim());
+        }
+        if (args.length > 3 && !args[3].toLowerCase().contains("not specified")) {
+            if (args.length > 2 && !args[2].toLowerCase().contains("not specified")) {
+                showFeedback("Specify either the number of decimal places or the multiple to round to, but not both.");
+                return;
+            }
+            multiple = Double.parseDouble(args[3].trim());
+            if (multiple <= 0) {
+                showFeedback("The multiple to round to must be greater than zero.");
+                return;
+            }
+        }
         
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
@@ -215,8 +235,10 @@ public class Round implements WhiteboxPlugin {
             int cols = inputFile1.getNumberColumns();
             double noData = inputFile1.getNoDataValue();
 
+            WhiteboxRaster.DataType dataType = (inputFile1.getDataType() == WhiteboxRaster.DataType.DOUBLE)
+                    ? WhiteboxRaster.DataType.DOUBLE : WhiteboxRaster.DataType.FLOAT;
             WhiteboxRaster outputFile = new WhiteboxRaster(outputHeader, "rw", 
-                    inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+                    inputHeader, dataType, noData);
             outputFile.setPreferredPalette(inputFile1.getPreferredPalette());
 
             for (row = 0; row < rows; row++) {
@@ -224,7 +246,7 @@ public class Round implements WhiteboxPlugin {
                 for (col = 0; col < cols; col++) {
                     z1 = data1[col];
                     if (z1 != noData) {
-                        outputFile.setValue(row, col, (double)Math.round(z1));
+                        outputFile.setValue(row, col, round(z1, decimals, multiple));
                     } else {
                         outputFile.setValue(row, col, noData);
                     }
@@ -243,6 +265,11 @@ public class Round implements WhiteboxPlugin {
             outputFile.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             outputFile.addMetadataEntry("Created on " + new Date());
+            if (multiple > 0) {
+                outputFile.addMetadataEntry("Rounded to the nearest multiple of " + multiple);
+            } else {
+                outputFile.addMetadataEntry("Rounded to " + decimals + " decimal places");
+            }
 
             // close all of the open Whitebox rasters.
             inputFile1.close();
@@ -263,4 +290,19 @@ public class Round implements WhiteboxPlugin {
             myHost.pluginComplete();
         }
     }
+
+    // rounds a value to the nearest multiple, if one is given, or otherwise
+    // to a number of decimal places, which may be negative. Values halfway
+    // between are rounded to the even neighbour.
+    private static double round(double z, int decimals, double multiple) {
+        if (multiple > 0) {
+            return Math.rint(z / multiple) * multiple;
+        } else if (decimals >= 0) {
+            double factor = Math.pow(10, decimals);
+            return Math.rint(z * factor) / factor;
+        } else {
+            double factor = Math.pow(10, -decimals);
+            return Math.rint(z / factor) * factor;
+        }
+    }
 }
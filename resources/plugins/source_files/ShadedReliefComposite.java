@@ -0,0 +1,503 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import com.google.gson.JsonArray;
+import com.google.gson.JsonElement;
+import com.google.gson.JsonObject;
+import com.google.gson.JsonParseException;
+import com.google.gson.JsonParser;
+import java.io.FileReader;
+import java.io.IOException;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool creates a shaded relief image from a DEM by blending a hillshade 
+ * with hypsometric tinting, i.e. a colour ramp of elevation percentiles, using 
+ * multiply compositing. The output is a colour composite raster.
+ */
+public class ShadedReliefComposite implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "ShadedReliefComposite";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Shaded Relief Composite";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Blends a hillshade with hypsometric tinting to create a colour shaded relief image.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "SurfDerivatives" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /* The named palettes. Each colour stop is an elevation percentile 
+     * followed by the red, green and blue values of its colour.
+     */
+    private static final double[][] swissTopoPalette = {
+        {0, 120, 160, 100}, {15, 160, 190, 120}, {35, 205, 215, 155},
+        {55, 230, 215, 165}, {75, 215, 190, 150}, {90, 200, 185, 170},
+        {100, 250, 250, 250}};
+    private static final double[][] imhofPalette = {
+        {0, 100, 140, 120}, {25, 150, 175, 125}, {50, 205, 200, 145},
+        {75, 235, 220, 170}, {100, 255, 245, 220}};
+    private static final double[][] naturalEarthPalette = {
+        {0, 85, 130, 85}, {30, 150, 170, 105}, {55, 195, 180, 130},
+        {80, 165, 135, 105}, {100, 240, 240, 235}};
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        String paletteName = "swiss_topo";
+        String paletteFile = null;
+        final double degToRad = Math.PI / 180;
+        final double radToDeg = 180 / Math.PI;
+        double azimuth = (315 - 90) * degToRad;
+        double altitude = 30 * degToRad;
+        double zFactor = 1;
+        int[] Dy = {-1, 0, 1, 1, 1, 0, -1, -1};
+        int[] Dx = {1, 1, 1, 0, -1, -1, -1, 0};
+        double[] N = new double[8];
+        double z, fx, fy, tanSlope, aspect, shade, percentile;
+        int i, row, col, bin;
+        int progress;
+        int numBins = 10000;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2) {
+                if (specified) {
+                    paletteName = args[i].trim().toLowerCase();
+                }
+            } else if (i == 3) {
+                azimuth = (Double.parseDouble(args[i]) - 90) * degToRad;
+            } else if (i == 4) {
+                altitude = Double.parseDouble(args[i]) * degToRad;
+            } else if (i == 5) {
+                zFactor = Double.parseDouble(args[i]);
+            } else if (i == 6) {
+                if (specified) {
+                    paletteFile = args[i];
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            double[][] palette;
+            if (paletteFile != null) {
+                palette = readPaletteFile(paletteFile);
+                if (palette == null) {
+                    return;
+                }
+            } else if (paletteName.equals("swiss_topo")) {
+                palette = swissTopoPalette;
+            } else if (paletteName.equals("imhof")) {
+                palette = imhofPalette;
+            } else if (paletteName.equals("natural_earth")) {
+                palette = naturalEarthPalette;
+            } else {
+                showFeedback("Unrecognized palette '" + paletteName + "'. The palette must be "
+                        + "one of swiss_topo, imhof or natural_earth.");
+                return;
+            }
+
+            double sinTheta = Math.sin(altitude);
+            double cosTheta = Math.cos(altitude);
+
+            WhiteboxRaster dem = new WhiteboxRaster(inputHeader, "r");
+            dem.isReflectedAtEdges = true;
+            int rows = dem.getNumberRows();
+            int cols = dem.getNumberColumns();
+            double noData = dem.getNoDataValue();
+            double eightGridRes = 8 * dem.getCellSizeX();
+
+            if (dem.getXYUnits().toLowerCase().contains("deg")
+                    || dem.getProjection().toLowerCase().contains("geog")) {
+                // calculate a new z-conversion factor
+                double midLat = (dem.getNorth() - dem.getSouth()) / 2.0;
+                if (midLat <= 90 && midLat >= -90) {
+                    midLat = Math.toRadians(midLat);
+                    zFactor = 1.0 / (113200 * Math.cos(midLat));
+                }
+            }
+
+            // find the elevation of each percentile from a histogram of the DEM
+            double minValue = Double.POSITIVE_INFINITY;
+            double maxValue = Double.NEGATIVE_INFINITY;
+            double[] data;
+            for (row = 0; row < rows; row++) {
+                data = dem.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    if (data[col] != noData) {
+                        minValue = Math.min(minValue, data[col]);
+                        maxValue = Math.max(maxValue, data[col]);
+                    }
+                }
+            }
+            if (minValue > maxValue) {
+                showFeedback("The input DEM does not contain any valid cells.");
+                dem.close();
+                return;
+            }
+            double binSize = (maxValue - minValue) / numBins;
+            long[] histo = new long[numBins];
+            long numCells = 0;
+            for (row = 0; row < rows; row++) {
+                data = dem.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    if (data[col] != noData) {
+                        bin = binSize > 0 ? (int) ((data[col] - minValue) / binSize) : 0;
+                        histo[Math.min(bin, numBins - 1)]++;
+                        numCells++;
+                    }
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * row / (rows - 1));
+                updateProgress("Calculating elevation percentiles:", progress);
+            }
+            double[] cdf = new double[numBins];
+            long sum = 0;
+            for (bin = 0; bin < numBins; bin++) {
+                sum += histo[bin];
+                cdf[bin] = 100.0 * sum / numCells;
+            }
+
+            /* The output is a colour composite, which stores the red, green 
+             * and blue bands of each cell as byte values packed into a single 
+             * value, (alpha << 24) | (blue << 16) | (green << 8) | red.
+             */
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setNoDataValue(noData);
+            output.setPreferredPalette("rgb.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.RGB);
+
+            int r, g, b;
+            double[] colour = new double[3];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    z = dem.getValue(row, col);
+                    if (z != noData) {
+                        // the hillshade intensity, calculated as in the Hillshade tool
+                        for (int c = 0; c < 8; c++) {
+                            N[c] = dem.getValue(row + Dy[c], col + Dx[c]);
+                            if (N[c] != noData) {
+                                N[c] = N[c] * zFactor;
+                            } else {
+                                N[c] = z * zFactor;
+                            }
+                        }
+                        fy = (N[6] - N[4] + 2 * (N[7] - N[3]) + N[0] - N[2]) / eightGridRes;
+                        fx = (N[2] - N[4] + 2 * (N[1] - N[5]) + N[0] - N[6]) / eightGridRes;
+                        if (fx != 0) {
+                            tanSlope = Math.sqrt(fx * fx + fy * fy);
+                            aspect = (180 - Math.atan(fy / fx) * radToDeg + 90 * (fx / Math.abs(fx))) * degToRad;
+                            shade = tanSlope / Math.sqrt(1 + tanSlope * tanSlope)
+                                    * (sinTheta / tanSlope - cosTheta * Math.sin(azimuth - aspect));
+                        } else {
+                            shade = 0.5;
+                        }
+                        shade = Math.max(0, Math.min(1, shade));
+
+                        bin = binSize > 0 ? (int) ((z - minValue) / binSize) : 0;
+                        percentile = cdf[Math.min(bin, numBins - 1)];
+                        getColour(palette, percentile, colour);
+
+                        // multiply blending of the tint with the hillshade
+                        r = (int) Math.round(colour[0] * shade);
+                        g = (int) Math.round(colour[1] * shade);
+                        b = (int) Math.round(colour[2] * shade);
+                        output.setValue(row, col, (double) ((255 << 24) | (b << 16) | (g << 8) | r));
+                    } else {
+                        output.setValue(row, col, noData);
+                    }
+                }
+
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * row / (rows - 1));
+                updateProgress("Creating shaded relief:", progress);
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Palette: " + (paletteFile != null ? paletteFile : paletteName));
+
+            dem.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Interpolates the colour of an elevation percentile linearly between the
+     * two palette stops that bracket it.
+     */
+    private static void getColour(double[][] palette, double percentile, double[] colour) {
+        int n = palette.length;
+        if (percentile <= palette[0][0]) {
+            System.arraycopy(palette[0], 1, colour, 0, 3);
+            return;
+        }
+        for (int i = 1; i < n; i++) {
+            if (percentile <= palette[i][0]) {
+                double t = (percentile - palette[i - 1][0]) / (palette[i][0] - palette[i - 1][0]);
+                for (int j = 0; j < 3; j++) {
+                    colour[j] = palette[i - 1][j + 1] + t * (palette[i][j + 1] - palette[i - 1][j + 1]);
+                }
+                return;
+            }
+        }
+        System.arraycopy(palette[n - 1], 1, colour, 0, 3);
+    }
+
+    /**
+     * Reads a custom palette from a JSON file of the form 
+     * {"stops": [{"percentile": 0, "rgb": [r, g, b]}, ...]}. Returns null, 
+     * after notifying the user, if the file is not a valid palette.
+     */
+    private double[][] readPaletteFile(String fileName) throws IOException {
+        JsonElement root;
+        try (FileReader reader = new FileReader(fileName)) {
+            root = new JsonParser().parse(reader);
+        } catch (JsonParseException e) {
+            showFeedback("The palette file could not be read: " + e.getMessage());
+            return null;
+        }
+        if (!root.isJsonObject() || !root.getAsJsonObject().has("stops")
+                || !root.getAsJsonObject().get("stops").isJsonArray()) {
+            showFeedback("The palette file must contain a 'stops' array.");
+            return null;
+        }
+        JsonArray stops = root.getAsJsonObject().getAsJsonArray("stops");
+        if (stops.size() < 2) {
+            showFeedback("The palette file must contain at least two colour stops.");
+            return null;
+        }
+        double[][] palette = new double[stops.size()][4];
+        for (int i = 0; i < stops.size(); i++) {
+            JsonObject stop = stops.get(i).isJsonObject() ? stops.get(i).getAsJsonObject() : null;
+            if (stop == null || !stop.has("percentile") || !stop.has("rgb")
+                    || !stop.get("rgb").isJsonArray() || stop.getAsJsonArray("rgb").size() != 3) {
+                showFeedback("Each colour stop in the palette file must have a 'percentile' "
+                        + "and an 'rgb' array of three values.");
+                return null;
+            }
+            palette[i][0] = stop.get("percentile").getAsDouble();
+            for (int j = 0; j < 3; j++) {
+                palette[i][j + 1] = stop.getAsJsonArray("rgb").get(j).getAsDouble();
+                if (palette[i][j + 1] < 0 || palette[i][j + 1] > 255) {
+                    showFeedback("The colour values in the palette file must be between 0 and 255.");
+                    return null;
+                }
+            }
+            if (palette[i][0] < 0 || palette[i][0] > 100 || (i > 0 && palette[i][0] <= palette[i - 1][0])) {
+                showFeedback("The percentiles in the palette file must be in increasing order "
+                        + "and between 0 and 100.");
+                return null;
+            }
+        }
+        return palette;
+    }
+}
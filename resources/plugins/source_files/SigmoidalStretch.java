@@ -189,12 +189,18 @@ public class SigmoidalStretch implements WhiteboxPlugin {
             
             double noData = input.getNoDataValue();
 
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
-            output.setPreferredPalette(input.getPreferredPalette());
-
             double minValue = input.getMinimumValue();
             double maxValue = input.getMaximumValue();
             double range = maxValue - minValue;
+            if (range <= 0) {
+                showFeedback("The input image has no variation and cannot be stretched.");
+                input.close();
+                return;
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette(input.getPreferredPalette());
+
             double[] data;
             
             double a = 1/(1+Math.exp(gain * cutoff));
@@ -216,6 +222,8 @@ public class SigmoidalStretch implements WhiteboxPlugin {
                 updateProgress(progress);
             }
             
+            output.setDisplayMinimum(0);
+            output.setDisplayMaximum(1);
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
@@ -16,23 +16,31 @@
  */
 package plugins;
 
+import com.vividsolutions.jts.algorithm.CGAlgorithms;
+import com.vividsolutions.jts.algorithm.LineIntersector;
+import com.vividsolutions.jts.algorithm.RobustLineIntersector;
 import com.vividsolutions.jts.geom.Coordinate;
+import com.vividsolutions.jts.geom.Envelope;
+import com.vividsolutions.jts.geom.GeometryCollection;
 import com.vividsolutions.jts.geom.GeometryFactory;
-import com.vividsolutions.jts.geom.LineString;
-import com.vividsolutions.jts.simplify.DouglasPeuckerSimplifier;
+import com.vividsolutions.jts.index.quadtree.Quadtree;
+import com.vividsolutions.jts.simplify.TopologyPreservingSimplifier;
 import java.util.ArrayList;
+import java.util.PriorityQueue;
 import whitebox.geospatialfiles.ShapeFile;
 import whitebox.geospatialfiles.shapefile.PointsList;
+import whitebox.geospatialfiles.shapefile.PolyLine;
+import whitebox.geospatialfiles.shapefile.Polygon;
 import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
 import whitebox.geospatialfiles.shapefile.ShapeType;
-import whitebox.geospatialfiles.shapefile.ShapefilePoint;
-import whitebox.geospatialfiles.shapefile.attributes.DBFField;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
-import whitebox.utilities.Topology;
 
 /**
- * This uses the Douglas and Peucker (1973) algorithm to simplify vector line or polygon features.
+ * This uses either the Douglas and Peucker (1973) or the Visvalingam and Whyatt (1993) algorithm to
+ * simplify vector line or polygon features without collapsing polygon rings or creating
+ * self-intersections.
  *
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
@@ -40,6 +48,8 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
 
     private WhiteboxPluginHost myHost = null;
     private String[] args;
+    private GeometryFactory factory = new GeometryFactory();
+    private LineIntersector lineIntersector = new RobustLineIntersector();
 
     /**
      * Used to retrieve the plugin tool's name. This is a short, unique name
@@ -196,22 +206,15 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
      */
     @Override
     public void run() {
-        /*
-         * This tool places the nodes (vertices) from a shapefile of polygons or
-         * lines into a shapefile of Point ShapeType.
-         */
-
         amIActive = true;
         String inputFile;
         String outputFile;
         int progress;
-        int i, n;
-        int numFeatures;
-        int oneHundredthTotal;
+        int i, part, start, end;
+        int featureNum, numFeatures;
         ShapeType shapeType, outputShapeType;
-        GeometryFactory factory = new GeometryFactory();
-        double distTolerance = 10;
-        boolean loseNoFeatures = false;
+        double distTolerance;
+        boolean useVisvalingam = false;
 
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -221,7 +224,13 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
         inputFile = args[0];
         outputFile = args[1];
         distTolerance = Double.parseDouble(args[2]);
-        loseNoFeatures = Boolean.parseBoolean(args[3]);
+        // Scripts written for earlier versions of this tool pass an 'ensure
+        // no features are lost' flag in place of the method. Rings are no
+        // longer collapsed by either method, so the flag is not needed and
+        // these scripts get the Douglas-Peucker method.
+        if (args.length > 3 && args[3].toLowerCase().contains("visvalingam")) {
+            useVisvalingam = true;
+        }
 
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputFile == null) || (outputFile == null)) {
@@ -235,12 +244,6 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
             shapeType = input.getShapeType();
 
             // make sure that the shapetype is either a flavour of polyline or polygon.
-            if (shapeType.getBaseType() != ShapeType.POLYGON && shapeType.getBaseType() != ShapeType.POLYLINE) {
-                showFeedback("This tool only works with shapefiles of a polygon or line base shape type.");
-                return;
-            }
-
-            // set up the output files of the shapefile and the dbf
             if (shapeType.getBaseType() == ShapeType.POLYGON) {
                 outputShapeType = ShapeType.POLYGON;
             } else if (shapeType.getBaseType() == ShapeType.POLYLINE) {
@@ -249,124 +252,60 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
                 showFeedback("This tool only works with shapefiles of a polygon or line base shape type.");
                 return;
             }
+            boolean isPolygon = (outputShapeType == ShapeType.POLYGON);
 
-            int numOutputFields = input.getAttributeTable().getFieldCount() + 1;
-            int numInputFields = input.getAttributeTable().getFieldCount();
-            DBFField[] inputFields = input.getAttributeTable().getAllFields();
-            DBFField fields[] = new DBFField[numOutputFields];
-            
-            fields[0] = new DBFField();
-            fields[0].setName("PARENT_ID");
-            fields[0].setDataType(DBFField.DBFDataType.NUMERIC);
-            fields[0].setFieldLength(10);
-            fields[0].setDecimalCount(0);
-
-            System.arraycopy(inputFields, 0, fields, 1, numInputFields);
-            
-            ShapeFile output = new ShapeFile(outputFile, outputShapeType, fields);
+            // the attributes of each feature are copied to the output unchanged.
+            AttributeTable table = input.getAttributeTable();
+            ShapeFile output = new ShapeFile(outputFile, outputShapeType, table.getAllFields());
             output.setProjectionStringFromOtherShapefile(input);
-            
+
             numFeatures = input.getNumberOfRecords();
-            oneHundredthTotal = numFeatures / 100;
-            n = 0;
-            progress = 0;
-            com.vividsolutions.jts.geom.Geometry[] recJTS = null;
-            int recordNum;
+            featureNum = 0;
             for (ShapeFileRecord record : input.records) {
-                recordNum = record.getRecordNumber();
-                Object[] attData = input.getAttributeTable().getRecord(recordNum - 1);
-                //featureNum++;
-                recJTS = record.getGeometry().getJTSGeometries();
-
-                ArrayList<com.vividsolutions.jts.geom.Geometry> geomList = new ArrayList<>();
-                for (int a = 0; a < recJTS.length; a++) {
-                    geomList.add(recJTS[a]);
-                }
-
-                DouglasPeuckerSimplifier dps = new DouglasPeuckerSimplifier(factory.buildGeometry(geomList));
-                dps.setDistanceTolerance(distTolerance);
-                com.vividsolutions.jts.geom.Geometry outputGeom = dps.getResultGeometry();
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object[] attData = table.getRecord(record.getRecordNumber() - 1);
+                    double[][] points = record.getGeometry().getPoints();
+                    int[] parts = record.getGeometry().getParts();
+
+                    Coordinate[][] lines = new Coordinate[parts.length][];
+                    for (part = 0; part < parts.length; part++) {
+                        start = parts[part];
+                        end = (part < parts.length - 1) ? parts[part + 1] : points.length;
+                        lines[part] = new Coordinate[end - start];
+                        for (i = start; i < end; i++) {
+                            lines[part][i - start] = new Coordinate(points[i][0], points[i][1]);
+                        }
+                    }
 
-                if (outputGeom.isEmpty() && loseNoFeatures) {
-                    outputGeom = factory.buildGeometry(geomList);
-                }
-                if (!outputGeom.isEmpty()) {
-                    for (int a = 0; a < outputGeom.getNumGeometries(); a++) {
-                        //parentRecNum = 0;
-                        com.vividsolutions.jts.geom.Geometry g = outputGeom.getGeometryN(a);
-                        if (g instanceof com.vividsolutions.jts.geom.Polygon && !g.isEmpty()) {
-                            com.vividsolutions.jts.geom.Polygon p = (com.vividsolutions.jts.geom.Polygon) g;
-                            ArrayList<ShapefilePoint> pnts = new ArrayList<>();
-                            int[] parts = new int[p.getNumInteriorRing() + 1];
-
-                            Coordinate[] buffCoords = p.getExteriorRing().getCoordinates();
-                            if (!Topology.isLineClosed(buffCoords)) {
-                                System.out.println("Exterior ring not closed.");
-                            }
-                            if (Topology.isClockwisePolygon(buffCoords)) {
-                                for (i = 0; i < buffCoords.length; i++) {
-                                    pnts.add(new ShapefilePoint(buffCoords[i].x, buffCoords[i].y));
-                                }
-                            } else {
-                                for (i = buffCoords.length - 1; i >= 0; i--) {
-                                    pnts.add(new ShapefilePoint(buffCoords[i].x, buffCoords[i].y));
-                                }
-                            }
-
-                            for (int b = 0; b < p.getNumInteriorRing(); b++) {
-                                parts[b + 1] = pnts.size();
-                                buffCoords = p.getInteriorRingN(b).getCoordinates();
-                                if (!Topology.isLineClosed(buffCoords)) {
-                                    System.out.println("Interior ring not closed.");
-                                }
-                                if (Topology.isClockwisePolygon(buffCoords)) {
-                                    for (i = buffCoords.length - 1; i >= 0; i--) {
-                                        pnts.add(new ShapefilePoint(buffCoords[i].x, buffCoords[i].y));
-                                    }
-                                } else {
-                                    for (i = 0; i < buffCoords.length; i++) {
-                                        pnts.add(new ShapefilePoint(buffCoords[i].x, buffCoords[i].y));
-                                    }
-                                }
-                            }
-
-                            PointsList pl = new PointsList(pnts);
-                            whitebox.geospatialfiles.shapefile.Polygon wbPoly = new whitebox.geospatialfiles.shapefile.Polygon(parts, pl.getPointsArray());
-                            Object[] rowData = new Object[numOutputFields];
-                            rowData[0] = new Double(recordNum - 1);
-                            System.arraycopy(attData, 0, rowData, 1, numInputFields);
-                            output.addRecord(wbPoly, rowData);
-                        } else if (g instanceof com.vividsolutions.jts.geom.LineString && !g.isEmpty()) {
-                            LineString ls = (LineString) g;
-                            ArrayList<ShapefilePoint> pnts = new ArrayList<>();
-
-                            int[] parts = {0};
-
-                            Coordinate[] coords = ls.getCoordinates();
-                            for (i = 0; i < coords.length; i++) {
-                                pnts.add(new ShapefilePoint(coords[i].x, coords[i].y));
-                            }
-
-                            PointsList pl = new PointsList(pnts);
-                            whitebox.geospatialfiles.shapefile.PolyLine wbGeometry = new whitebox.geospatialfiles.shapefile.PolyLine(parts, pl.getPointsArray());
-                            Object[] rowData = new Object[numOutputFields];
-                            rowData[0] = new Double(recordNum - 1);
-                            System.arraycopy(attData, 0, rowData, 1, numInputFields);
-                            output.addRecord(wbGeometry, rowData);
+                    if (useVisvalingam) {
+                        lines = simplifyVisvalingamWhyatt(lines, isPolygon, distTolerance * distTolerance);
+                    } else {
+                        lines = simplifyDouglasPeucker(lines, isPolygon, distTolerance);
+                    }
 
+                    PointsList pl = new PointsList();
+                    int[] outputParts = new int[lines.length];
+                    for (part = 0; part < lines.length; part++) {
+                        outputParts[part] = pl.size();
+                        for (Coordinate c : lines[part]) {
+                            pl.addPoint(c.x, c.y);
                         }
                     }
-                }
-                n++;
-                if (n >= oneHundredthTotal) {
-                    n = 0;
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
+
+                    if (isPolygon) {
+                        output.addRecord(new Polygon(outputParts, pl.getPointsArray()), attData);
+                    } else {
+                        output.addRecord(new PolyLine(outputParts, pl.getPointsArray()), attData);
                     }
-                    progress++;
-                    updateProgress(progress);
                 }
+
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
             }
 
             output.write();
@@ -389,6 +328,253 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
         }
 
     }
+
+    /**
+     * Simplifies the parts of a feature using the JTS topology-preserving
+     * Douglas-Peucker simplifier. Polygon rings keep at least four points and
+     * the simplified parts do not cross one another.
+     */
+    private Coordinate[][] simplifyDouglasPeucker(Coordinate[][] lines, boolean isPolygon,
+            double tolerance) {
+        ArrayList<Integer> partNums = new ArrayList<>();
+        ArrayList<com.vividsolutions.jts.geom.Geometry> geomList = new ArrayList<>();
+        for (int a = 0; a < lines.length; a++) {
+            int n = lines[a].length;
+            if (isPolygon && n >= 4 && lines[a][0].equals2D(lines[a][n - 1])) {
+                geomList.add(factory.createLinearRing(lines[a]));
+                partNums.add(a);
+            } else if (n >= 2) {
+                geomList.add(factory.createLineString(lines[a]));
+                partNums.add(a);
+            }
+        }
+        if (geomList.isEmpty()) {
+            return lines;
+        }
+
+        com.vividsolutions.jts.geom.Geometry[] geoms = new com.vividsolutions.jts.geom.Geometry[geomList.size()];
+        GeometryCollection collection = factory.createGeometryCollection(geomList.toArray(geoms));
+        com.vividsolutions.jts.geom.Geometry simplified = TopologyPreservingSimplifier.simplify(collection, tolerance);
+
+        Coordinate[][] ret = lines.clone();
+        for (int a = 0; a < simplified.getNumGeometries(); a++) {
+            ret[partNums.get(a)] = simplified.getGeometryN(a).getCoordinates();
+        }
+        return ret;
+    }
+
+    /**
+     * Simplifies the parts of a feature using the Visvalingam and Whyatt
+     * (1993) algorithm, which repeatedly removes the vertex forming the
+     * triangle of smallest area with its neighbours until every remaining
+     * triangle is at least as large as the area threshold. Line end points
+     * are never removed, polygon rings keep at least four points, and a
+     * vertex is retained if removing it would make the feature's linework
+     * cross itself or pass over another vertex.
+     */
+    private Coordinate[][] simplifyVisvalingamWhyatt(Coordinate[][] lines, boolean isPolygon,
+            double areaThreshold) {
+        int numParts = lines.length;
+        int numVertices = 0;
+        for (Coordinate[] line : lines) {
+            numVertices += line.length;
+        }
+
+        // the vertices of all parts are held in one set of linked lists so
+        // that a removal can be checked against the whole feature.
+        Coordinate[] coords = new Coordinate[numVertices];
+        int[] prev = new int[numVertices];
+        int[] next = new int[numVertices];
+        int[] partOf = new int[numVertices];
+        int[] version = new int[numVertices];
+        boolean[] isFixed = new boolean[numVertices];
+        boolean[] isRemoved = new boolean[numVertices];
+        Segment[] segmentFrom = new Segment[numVertices];
+        int[] partStart = new int[numParts];
+        int[] partEnd = new int[numParts];
+        boolean[] isRing = new boolean[numParts];
+        int[] remaining = new int[numParts];
+        int[] minVertices = new int[numParts];
+        Quadtree segments = new Quadtree();
+        PriorityQueue<Candidate> queue = new PriorityQueue<>();
+
+        int k = 0;
+        for (int a = 0; a < numParts; a++) {
+            int n = lines[a].length;
+            partStart[a] = k;
+            partEnd[a] = k + n;
+            boolean isClosed = n >= 4 && lines[a][0].equals2D(lines[a][n - 1]);
+            isRing[a] = isPolygon && isClosed;
+            for (int i = 0; i < n; i++) {
+                coords[k + i] = lines[a][i];
+                partOf[k + i] = a;
+                prev[k + i] = k + i - 1;
+                next[k + i] = k + i + 1;
+            }
+            if (isRing[a]) {
+                // the closing point duplicates the first and is added back
+                // to the ring after simplification.
+                isRemoved[k + n - 1] = true;
+                prev[k] = k + n - 2;
+                next[k + n - 2] = k;
+                remaining[a] = n - 1;
+                minVertices[a] = 3;
+            } else {
+                if (n > 0) {
+                    isFixed[k] = true;
+                    isFixed[k + n - 1] = true;
+                }
+                remaining[a] = n;
+                minVertices[a] = isClosed ? 4 : 2;
+            }
+            k += n;
+        }
+
+        for (int v = 0; v < numVertices; v++) {
+            if (isRemoved[v]) {
+                continue;
+            }
+            if (isRing[partOf[v]] || v < partEnd[partOf[v]] - 1) {
+                segmentFrom[v] = new Segment(v, next[v]);
+                segments.insert(segmentFrom[v].getEnvelope(coords), segmentFrom[v]);
+            }
+            if (!isFixed[v] && remaining[partOf[v]] > minVertices[partOf[v]]) {
+                queue.add(new Candidate(v, triangleArea(coords[prev[v]], coords[v], coords[next[v]]), 0));
+            }
+        }
+
+        while (!queue.isEmpty()) {
+            Candidate c = queue.poll();
+            int v = c.vertex;
+            if (isRemoved[v] || c.version != version[v]) {
+                continue;
+            }
+            if (c.area >= areaThreshold) {
+                break;
+            }
+            int a = partOf[v];
+            int p = prev[v];
+            int n = next[v];
+            if (remaining[a] <= minVertices[a] || !canRemove(p, v, n, coords, segments, segmentFrom)) {
+                continue;
+            }
+
+            isRemoved[v] = true;
+            remaining[a]--;
+            next[p] = n;
+            prev[n] = p;
+            segments.remove(segmentFrom[p].getEnvelope(coords), segmentFrom[p]);
+            segments.remove(segmentFrom[v].getEnvelope(coords), segmentFrom[v]);
+            segmentFrom[v] = null;
+            segmentFrom[p] = new Segment(p, n);
+            segments.insert(segmentFrom[p].getEnvelope(coords), segmentFrom[p]);
+
+            // the effective area of a neighbour is never allowed to be less
+            // than that of the vertex just removed.
+            for (int w : new int[]{p, n}) {
+                if (!isFixed[w]) {
+                    version[w]++;
+                    double area = Math.max(c.area, triangleArea(coords[prev[w]], coords[w], coords[next[w]]));
+                    queue.add(new Candidate(w, area, version[w]));
+                }
+            }
+        }
+
+        Coordinate[][] ret = new Coordinate[numParts][];
+        for (int a = 0; a < numParts; a++) {
+            ArrayList<Coordinate> partCoords = new ArrayList<>();
+            if (isRing[a]) {
+                int first = partStart[a];
+                while (isRemoved[first]) {
+                    first++;
+                }
+                int v = first;
+                do {
+                    partCoords.add(coords[v]);
+                    v = next[v];
+                } while (v != first);
+                partCoords.add(coords[first]);
+            } else {
+                for (int v = partStart[a]; v < partEnd[a]; v = next[v]) {
+                    partCoords.add(coords[v]);
+                }
+            }
+            ret[a] = partCoords.toArray(new Coordinate[partCoords.size()]);
+        }
+        return ret;
+    }
+
+    /**
+     * Determines whether the vertex v can be removed, i.e. whether the
+     * segment joining its neighbours p and n would not cross any other
+     * segment and whether no other vertex lies within the triangle p-v-n.
+     */
+    private boolean canRemove(int p, int v, int n, Coordinate[] coords, Quadtree segments,
+            Segment[] segmentFrom) {
+        Envelope env = new Envelope(coords[p], coords[n]);
+        env.expandToInclude(coords[v]);
+        for (Object obj : segments.query(env)) {
+            Segment s = (Segment) obj;
+            if (s == segmentFrom[p] || s == segmentFrom[v]) {
+                continue;
+            }
+            if ((s.from != p && s.from != n && isInsideTriangle(coords[s.from], coords[p], coords[v], coords[n]))
+                    || (s.to != p && s.to != n && isInsideTriangle(coords[s.to], coords[p], coords[v], coords[n]))) {
+                return false;
+            }
+            if (s.from == p || s.to == p || s.from == n || s.to == n) {
+                continue;
+            }
+            lineIntersector.computeIntersection(coords[p], coords[n], coords[s.from], coords[s.to]);
+            if (lineIntersector.hasIntersection()) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    private static boolean isInsideTriangle(Coordinate q, Coordinate a, Coordinate b, Coordinate c) {
+        int o1 = CGAlgorithms.orientationIndex(a, b, q);
+        int o2 = CGAlgorithms.orientationIndex(b, c, q);
+        int o3 = CGAlgorithms.orientationIndex(c, a, q);
+        return o1 != 0 && o1 == o2 && o2 == o3;
+    }
+
+    private static double triangleArea(Coordinate a, Coordinate b, Coordinate c) {
+        return Math.abs((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.0;
+    }
+
+    private static class Segment {
+
+        int from, to;
+
+        Segment(int from, int to) {
+            this.from = from;
+            this.to = to;
+        }
+
+        Envelope getEnvelope(Coordinate[] coords) {
+            return new Envelope(coords[from], coords[to]);
+        }
+    }
+
+    private static class Candidate implements Comparable<Candidate> {
+
+        int vertex;
+        double area;
+        int version;
+
+        Candidate(int vertex, double area, int version) {
+            this.vertex = vertex;
+            this.area = area;
+            this.version = version;
+        }
+
+        @Override
+        public int compareTo(Candidate other) {
+            return Double.compare(area, other.area);
+        }
+    }
     
 //    /**
 //     * This method is only used during testing.
@@ -405,7 +591,7 @@ public class SimplifyLineOrPolygon implements WhiteboxPlugin {
 //        args[1] = "/Users/johnlindsay/Documents/Research/Contracts/NRCan 2012/Data/tmp1.shp";
 //
 //        args[2] = "15";
-//        args[3] = "true";
+//        args[3] = "Douglas-Peucker";
 //
 //        SimplifyLineOrPolygon slp = new SimplifyLineOrPolygon();
 //        slp.setArgs(args);
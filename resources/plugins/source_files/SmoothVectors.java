@@ -0,0 +1,436 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.shapefile.PointsList;
+import whitebox.geospatialfiles.shapefile.PolyLine;
+import whitebox.geospatialfiles.shapefile.Polygon;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool smooths the lines or polygons of a vector file using either 
+ * Chaikin's corner-cutting algorithm or a moving average of vertex positions, 
+ * applied for a number of iterations. The end points of lines are not moved 
+ * and feature attributes are copied to the output unchanged.
+ */
+public class SmoothVectors implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SmoothVectors";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Smooth Vectors";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Smooths lines or polygons using Chaikin or moving-average vertex smoothing";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "VectorTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+  
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+   
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        
+        amIActive = true;
+        String inputFile;
+        String outputFile;
+        int progress;
+        int i, part, start, end, iteration;
+        int featureNum, numFeatures;
+        int iterations;
+        int filterSize = 3;
+        boolean useChaikin;
+        ShapeType shapeType, outputShapeType;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        inputFile = args[0];
+        outputFile = args[1];
+        useChaikin = !args[2].toLowerCase().contains("moving");
+        iterations = (int) Double.parseDouble(args[3]);
+        if (args.length > 4 && !args[4].toLowerCase().equals("not specified")) {
+            filterSize = (int) Double.parseDouble(args[4]);
+        }
+        
+        if (iterations < 1) { iterations = 1; }
+        
+        if (filterSize < 3) { filterSize = 3; }
+        
+        if (filterSize % 2 == 0) { // the filter size must be an odd number
+            showFeedback("The filter size must be an odd number. The specified value "
+                    + "has been incremented by one.");
+            filterSize++;
+        }
+        
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            // set up the input shapefile.
+            ShapeFile input = new ShapeFile(inputFile);
+            shapeType = input.getShapeType();
+            
+            // make sure that the shapetype is either a flavour of polyline or polygon.
+            if (shapeType.getBaseType() == ShapeType.POLYGON) {
+                outputShapeType = ShapeType.POLYGON;
+            } else if (shapeType.getBaseType() == ShapeType.POLYLINE) {
+                outputShapeType = ShapeType.POLYLINE;
+            } else {
+                showFeedback("This tool only works with shapefiles of a polyline or polygon base shape type.");
+                return;
+            }
+            boolean isPolygon = (outputShapeType == ShapeType.POLYGON);
+            
+            // the attributes of each feature are copied to the output unchanged.
+            AttributeTable table = input.getAttributeTable();
+            ShapeFile output = new ShapeFile(outputFile, outputShapeType, table.getAllFields());
+            output.setProjectionStringFromOtherShapefile(input);
+            
+            numFeatures = input.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : input.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object[] attData = table.getRecord(record.getRecordNumber() - 1);
+                    double[][] points = record.getGeometry().getPoints();
+                    int[] parts = record.getGeometry().getParts();
+                    
+                    PointsList pl = new PointsList();
+                    int[] outputParts = new int[parts.length];
+                    for (part = 0; part < parts.length; part++) {
+                        start = parts[part];
+                        end = (part < parts.length - 1) ? parts[part + 1] : points.length;
+                        double[][] line = new double[end - start][];
+                        for (i = start; i < end; i++) {
+                            line[i - start] = points[i];
+                        }
+                        
+                        // only polygon rings are smoothed as closed loops; the end 
+                        // points of lines, even closed lines, are held fixed.
+                        boolean isRing = isPolygon && line.length >= 4
+                                && line[0][0] == line[line.length - 1][0]
+                                && line[0][1] == line[line.length - 1][1];
+                        if (isRing || line.length >= 3) {
+                            for (iteration = 0; iteration < iterations; iteration++) {
+                                if (useChaikin) {
+                                    line = isRing ? chaikinRing(line) : chaikinLine(line);
+                                } else {
+                                    line = isRing ? movingAverageRing(line, filterSize) : movingAverageLine(line, filterSize);
+                                }
+                            }
+                        }
+                        
+                        outputParts[part] = pl.size();
+                        for (double[] p : line) {
+                            pl.addPoint(p[0], p[1]);
+                        }
+                    }
+                    
+                    if (isPolygon) {
+                        output.addRecord(new Polygon(outputParts, pl.getPointsArray()), attData);
+                    } else {
+                        output.addRecord(new PolyLine(outputParts, pl.getPointsArray()), attData);
+                    }
+                }
+                
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+            
+            output.write();
+            
+            // returning a header file string displays the image.
+            returnData(outputFile);
+            
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+       
+    }
+    
+    /**
+     * Performs one iteration of Chaikin's corner cutting on a closed ring. 
+     * Each segment is replaced by points at one quarter and three quarters 
+     * of its length.
+     */
+    private double[][] chaikinRing(double[][] ring) {
+        int n = ring.length - 1; // the last point closes the ring
+        double[][] ret = new double[2 * n + 1][];
+        for (int i = 0; i < n; i++) {
+            ret[2 * i] = interpolate(ring[i], ring[i + 1], 0.25);
+            ret[2 * i + 1] = interpolate(ring[i], ring[i + 1], 0.75);
+        }
+        ret[2 * n] = ret[0];
+        return ret;
+    }
+    
+    /**
+     * Performs one iteration of Chaikin's corner cutting on a line, keeping
+     * its end points fixed. No point is inserted beside the end points of 
+     * the first and last segments.
+     */
+    private double[][] chaikinLine(double[][] line) {
+        int n = line.length - 1; // the number of segments
+        double[][] ret = new double[2 * n][];
+        int k = 0;
+        ret[k++] = line[0];
+        for (int i = 0; i < n; i++) {
+            if (i > 0) {
+                ret[k++] = interpolate(line[i], line[i + 1], 0.25);
+            }
+            if (i < n - 1) {
+                ret[k++] = interpolate(line[i], line[i + 1], 0.75);
+            }
+        }
+        ret[k] = line[n];
+        return ret;
+    }
+    
+    /**
+     * Replaces each vertex of a closed ring with the mean position of the 
+     * vertices in a window centred on it, wrapping around the ring.
+     */
+    private double[][] movingAverageRing(double[][] ring, int filterSize) {
+        int n = ring.length - 1; // the last point closes the ring
+        int halfFilterSize = filterSize / 2;
+        double[][] ret = new double[n + 1][];
+        for (int i = 0; i < n; i++) {
+            double sumX = 0;
+            double sumY = 0;
+            for (int j = i - halfFilterSize; j <= i + halfFilterSize; j++) {
+                int k = ((j % n) + n) % n;
+                sumX += ring[k][0];
+                sumY += ring[k][1];
+            }
+            ret[i] = new double[]{sumX / filterSize, sumY / filterSize};
+        }
+        ret[n] = ret[0];
+        return ret;
+    }
+    
+    /**
+     * Replaces each interior vertex of a line with the mean position of the 
+     * vertices in a window centred on it. The window is narrowed near the 
+     * ends of the line so that it remains centred and the end points are 
+     * not moved.
+     */
+    private double[][] movingAverageLine(double[][] line, int filterSize) {
+        int n = line.length;
+        double[][] ret = new double[n][];
+        ret[0] = line[0];
+        ret[n - 1] = line[n - 1];
+        for (int i = 1; i < n - 1; i++) {
+            int halfFilterSize = Math.min(filterSize / 2, Math.min(i, n - 1 - i));
+            double sumX = 0;
+            double sumY = 0;
+            for (int j = i - halfFilterSize; j <= i + halfFilterSize; j++) {
+                sumX += line[j][0];
+                sumY += line[j][1];
+            }
+            ret[i] = new double[]{sumX / (2 * halfFilterSize + 1), sumY / (2 * halfFilterSize + 1)};
+        }
+        return ret;
+    }
+    
+    private static double[] interpolate(double[] p1, double[] p2, double t) {
+        return new double[]{p1[0] + t * (p2[0] - p1[0]), p1[1] + t * (p2[1] - p1[1])};
+    }
+    
+//    // This method is only used during testing.
+//    public static void main(String[] args) {
+//        args = new String[5];
+//        args[0] = "/Users/johnlindsay/Documents/Data/tmp1.shp";
+//        args[1] = "/Users/johnlindsay/Documents/Data/tmp3.shp";
+//        args[2] = "Chaikin";
+//        args[3] = "3";
+//        args[4] = "not specified";
+//        
+//        SmoothVectors smooth = new SmoothVectors();
+//        smooth.setArgs(args);
+//        smooth.run();
+//    }
+}
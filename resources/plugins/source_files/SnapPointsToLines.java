@@ -0,0 +1,471 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import com.vividsolutions.jts.geom.Envelope;
+import com.vividsolutions.jts.index.strtree.STRtree;
+import java.util.List;
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.shapefile.Geometry;
+import whitebox.geospatialfiles.shapefile.PointM;
+import whitebox.geospatialfiles.shapefile.PointZ;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.geospatialfiles.shapefile.attributes.DBFField;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool moves each point in a vector file to the nearest location on any 
+ * line (or polygon boundary) in a second vector file that lies within a 
+ * search distance. The output records the snap distance, the ID of the 
+ * target line, and the distance along the target line of the snapped 
+ * location. Points without a line within the search distance are flagged 
+ * rather than dropped.
+ */
+public class SnapPointsToLines implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SnapPointsToLines";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Snap Points To Lines";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Moves points to the nearest location on a line within a search distance";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "VectorTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+  
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+   
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        
+        amIActive = true;
+        String pointsFile;
+        String linesFile;
+        String outputFile;
+        String lineIDField = "";
+        boolean outputOriginalPoints = false;
+        double searchDistance;
+        int progress;
+        int i, part, start, end;
+        int featureNum, numFeatures;
+        double x, y, dist, along;
+        ShapeType pointsShapeType, linesShapeType;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        pointsFile = args[0];
+        linesFile = args[1];
+        outputFile = args[2];
+        searchDistance = Double.parseDouble(args[3]);
+        if (args.length > 4) {
+            outputOriginalPoints = args[4].toLowerCase().contains("original");
+        }
+        if (args.length > 5 && !args[5].toLowerCase().contains("not specified")) {
+            lineIDField = args[5].trim();
+        }
+        
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((pointsFile == null) || (linesFile == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (searchDistance <= 0) {
+            showFeedback("The search distance must be greater than zero.");
+            return;
+        }
+
+        try {
+            ShapeFile points = new ShapeFile(pointsFile);
+            pointsShapeType = points.getShapeType();
+            if (pointsShapeType != ShapeType.POINT && pointsShapeType != ShapeType.POINTZ
+                    && pointsShapeType != ShapeType.POINTM) {
+                showFeedback("The input points file must be of a Point ShapeType.");
+                return;
+            }
+            
+            ShapeFile lines = new ShapeFile(linesFile);
+            linesShapeType = lines.getShapeType();
+            if (linesShapeType.getBaseType() != ShapeType.POLYLINE 
+                    && linesShapeType.getBaseType() != ShapeType.POLYGON) {
+                showFeedback("The input lines file must be of a polyline or polygon base shape type.");
+                return;
+            }
+            
+            AttributeTable linesTable = lines.getAttributeTable();
+            if (!lineIDField.isEmpty() && linesTable.getFieldColumnNumberFromName(lineIDField) < 0) {
+                showFeedback("The line ID field '" + lineIDField + "' could not be found in the lines attribute table.");
+                return;
+            }
+            
+            // Index every line segment in an R-tree. Each segment stores the
+            // ID of its line and the distance along the line to its start.
+            updateProgress("Indexing lines:", 0);
+            STRtree index = new STRtree();
+            numFeatures = lines.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : lines.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object lineID;
+                    if (lineIDField.isEmpty()) {
+                        lineID = (double) (record.getRecordNumber() - 1);
+                    } else {
+                        lineID = linesTable.getValue(record.getRecordNumber() - 1, lineIDField);
+                    }
+                    Geometry geometry = record.getGeometry();
+                    double[][] vertices = geometry.getPoints();
+                    int[] parts = geometry.getParts();
+                    along = 0;
+                    for (part = 0; part < parts.length; part++) {
+                        start = parts[part];
+                        end = (part < parts.length - 1) ? parts[part + 1] : vertices.length;
+                        for (i = start + 1; i < end; i++) {
+                            Segment segment = new Segment(lineID, vertices[i - 1][0], vertices[i - 1][1],
+                                    vertices[i][0], vertices[i][1], along);
+                            index.insert(new Envelope(segment.x1, segment.x2, segment.y1, segment.y2), segment);
+                            along += segment.length;
+                        }
+                    }
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+            index.build();
+            
+            // set up the output file. The point attributes are followed by 
+            // the snapping attributes.
+            AttributeTable pointsTable = points.getAttributeTable();
+            DBFField[] inputFields = pointsTable.getAllFields();
+            int numInputFields = inputFields.length;
+            DBFField[] fields = new DBFField[numInputFields + 4];
+            System.arraycopy(inputFields, 0, fields, 0, numInputFields);
+            
+            fields[numInputFields] = new DBFField();
+            fields[numInputFields].setName("SNAPPED");
+            fields[numInputFields].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields].setFieldLength(1);
+            fields[numInputFields].setDecimalCount(0);
+            
+            fields[numInputFields + 1] = new DBFField();
+            fields[numInputFields + 1].setName("SNAP_DIST");
+            fields[numInputFields + 1].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields + 1].setFieldLength(12);
+            fields[numInputFields + 1].setDecimalCount(3);
+            
+            if (lineIDField.isEmpty()) {
+                fields[numInputFields + 2] = new DBFField();
+                fields[numInputFields + 2].setName("LINE_ID");
+                fields[numInputFields + 2].setDataType(DBFField.DBFDataType.NUMERIC);
+                fields[numInputFields + 2].setFieldLength(10);
+                fields[numInputFields + 2].setDecimalCount(0);
+            } else {
+                // the line ID field keeps the type of the field it is copied from
+                DBFField idField = linesTable.getField(linesTable.getFieldColumnNumberFromName(lineIDField));
+                fields[numInputFields + 2] = new DBFField();
+                fields[numInputFields + 2].setName("LINE_ID");
+                fields[numInputFields + 2].setDataType(idField.getDataType());
+                fields[numInputFields + 2].setFieldLength(idField.getFieldLength());
+                fields[numInputFields + 2].setDecimalCount(idField.getDecimalCount());
+            }
+            
+            fields[numInputFields + 3] = new DBFField();
+            fields[numInputFields + 3].setName("ALONG_DIST");
+            fields[numInputFields + 3].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields + 3].setFieldLength(12);
+            fields[numInputFields + 3].setDecimalCount(3);
+            
+            ShapeFile output = new ShapeFile(outputFile, pointsShapeType, fields);
+            output.setProjectionStringFromOtherShapefile(points);
+            
+            updateProgress("Snapping points:", 0);
+            int numUnsnapped = 0;
+            numFeatures = points.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : points.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object[] attData = pointsTable.getRecord(record.getRecordNumber() - 1);
+                    Geometry geometry = record.getGeometry();
+                    x = geometry.getPoints()[0][0];
+                    y = geometry.getPoints()[0][1];
+                    
+                    // find the nearest segment within the search distance
+                    List<Segment> candidates = index.query(new Envelope(x - searchDistance,
+                            x + searchDistance, y - searchDistance, y + searchDistance));
+                    Segment nearest = null;
+                    double nearestDist = Double.POSITIVE_INFINITY;
+                    double nearestT = 0;
+                    for (Segment segment : candidates) {
+                        double t = segment.closestFraction(x, y);
+                        dist = Math.hypot(segment.x1 + t * (segment.x2 - segment.x1) - x,
+                                segment.y1 + t * (segment.y2 - segment.y1) - y);
+                        if (dist <= searchDistance && dist < nearestDist) {
+                            nearestDist = dist;
+                            nearest = segment;
+                            nearestT = t;
+                        }
+                    }
+                    
+                    Object[] rowData = new Object[numInputFields + 4];
+                    System.arraycopy(attData, 0, rowData, 0, numInputFields);
+                    double outputX = x;
+                    double outputY = y;
+                    if (nearest != null) {
+                        rowData[numInputFields] = 1.0;
+                        rowData[numInputFields + 1] = nearestDist;
+                        rowData[numInputFields + 2] = nearest.lineID;
+                        rowData[numInputFields + 3] = nearest.along + nearestT * nearest.length;
+                        if (!outputOriginalPoints) {
+                            outputX = nearest.x1 + nearestT * (nearest.x2 - nearest.x1);
+                            outputY = nearest.y1 + nearestT * (nearest.y2 - nearest.y1);
+                        }
+                    } else {
+                        // the point is kept at its original location and flagged
+                        rowData[numInputFields] = 0.0;
+                        numUnsnapped++;
+                    }
+                    
+                    switch (pointsShapeType) {
+                        case POINT:
+                            output.addRecord(new whitebox.geospatialfiles.shapefile.Point(outputX, outputY), rowData);
+                            break;
+                        case POINTZ:
+                            PointZ pointZ = (PointZ) geometry;
+                            output.addRecord(new PointZ(outputX, outputY, pointZ.getZ(), pointZ.getM()), rowData);
+                            break;
+                        case POINTM:
+                            PointM pointM = (PointM) geometry;
+                            output.addRecord(new PointM(outputX, outputY, pointM.getM()), rowData);
+                            break;
+                    }
+                }
+                
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+            
+            output.write();
+            
+            if (numUnsnapped > 0) {
+                showFeedback(numUnsnapped + " point(s) had no line within the search distance. "
+                        + "These have a SNAPPED value of 0 in the output attribute table.");
+            }
+            
+            // returning a header file string displays the image.
+            returnData(outputFile);
+            
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+       
+    }
+    
+    private class Segment {
+        
+        Object lineID;
+        double x1, y1, x2, y2;
+        double along;
+        double length;
+        
+        Segment(Object lineID, double x1, double y1, double x2, double y2, double along) {
+            this.lineID = lineID;
+            this.x1 = x1;
+            this.y1 = y1;
+            this.x2 = x2;
+            this.y2 = y2;
+            this.along = along;
+            this.length = Math.hypot(x2 - x1, y2 - y1);
+        }
+        
+        // returns the position of the point on the segment nearest to (x, y) 
+        // as a fraction of the segment length.
+        double closestFraction(double x, double y) {
+            if (length == 0) {
+                return 0;
+            }
+            double t = ((x - x1) * (x2 - x1) + (y - y1) * (y2 - y1)) / (length * length);
+            return Math.max(0, Math.min(1, t));
+        }
+    }
+}
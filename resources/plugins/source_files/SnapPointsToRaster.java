@@ -0,0 +1,426 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import whitebox.geospatialfiles.ShapeFile;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.shapefile.Geometry;
+import whitebox.geospatialfiles.shapefile.PointM;
+import whitebox.geospatialfiles.shapefile.PointZ;
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord;
+import whitebox.geospatialfiles.shapefile.ShapeType;
+import whitebox.geospatialfiles.shapefile.attributes.AttributeTable;
+import whitebox.geospatialfiles.shapefile.attributes.DBFField;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool moves each point in a vector file to the centre of the nearest 
+ * raster grid cell that meets a criterion (e.g. the nearest stream cell) 
+ * within a search distance. It generalizes the search used by the Jenson Snap 
+ * Pour Points tool to any set of points, measures the search distance from 
+ * the point itself rather than from the cell containing it, and records the 
+ * snap distance and the value of the target cell. Points without a suitable 
+ * cell within the search distance are flagged rather than dropped.
+ */
+public class SnapPointsToRaster implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SnapPointsToRaster";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Snap Points To Raster";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Moves points to the nearest grid cell meeting a criterion within a search distance";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "WatershedTools", "VectorTools" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+  
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+   
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+   
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        
+        amIActive = true;
+        String pointsFile;
+        String rasterHeader;
+        String outputFile;
+        String criterion = "greater than zero";
+        boolean outputOriginalPoints = false;
+        double searchDistance;
+        int progress;
+        int row, col, pointRow, pointCol;
+        int featureNum, numFeatures;
+        double x, y, z, dist, cellX, cellY;
+        ShapeType pointsShapeType;
+        
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        
+        pointsFile = args[0];
+        rasterHeader = args[1];
+        outputFile = args[2];
+        searchDistance = Double.parseDouble(args[3]);
+        if (args.length > 4) {
+            criterion = args[4].toLowerCase();
+        }
+        if (args.length > 5) {
+            outputOriginalPoints = args[5].toLowerCase().contains("original");
+        }
+        
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((pointsFile == null) || (rasterHeader == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        if (searchDistance <= 0) {
+            showFeedback("The search distance must be greater than zero.");
+            return;
+        }
+        
+        // 0 = greater than zero, 1 = not zero, 2 = not NoData
+        int criterionType;
+        if (criterion.contains("greater")) {
+            criterionType = 0;
+        } else if (criterion.contains("nodata")) {
+            criterionType = 2;
+        } else if (criterion.contains("not zero") || criterion.contains("non-zero")) {
+            criterionType = 1;
+        } else {
+            showFeedback("The snapping criterion is not recognized. It should be one of "
+                    + "'Greater Than Zero', 'Not Zero', or 'Not NoData'.");
+            return;
+        }
+
+        try {
+            ShapeFile points = new ShapeFile(pointsFile);
+            pointsShapeType = points.getShapeType();
+            if (pointsShapeType != ShapeType.POINT && pointsShapeType != ShapeType.POINTZ
+                    && pointsShapeType != ShapeType.POINTM) {
+                showFeedback("The input points file must be of a Point ShapeType.");
+                return;
+            }
+            
+            WhiteboxRaster raster = new WhiteboxRaster(rasterHeader, "r");
+            int rows = raster.getNumberRows();
+            int cols = raster.getNumberColumns();
+            double noData = raster.getNoDataValue();
+            
+            // the search window, in grid cells. Cells within the window but 
+            // beyond the search distance are not considered.
+            int windowCols = (int) Math.ceil(searchDistance / raster.getCellSizeX());
+            int windowRows = (int) Math.ceil(searchDistance / raster.getCellSizeY());
+            
+            // set up the output file. The point attributes are followed by 
+            // the snapping attributes.
+            AttributeTable pointsTable = points.getAttributeTable();
+            DBFField[] inputFields = pointsTable.getAllFields();
+            int numInputFields = inputFields.length;
+            DBFField[] fields = new DBFField[numInputFields + 3];
+            System.arraycopy(inputFields, 0, fields, 0, numInputFields);
+            
+            fields[numInputFields] = new DBFField();
+            fields[numInputFields].setName("SNAPPED");
+            fields[numInputFields].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields].setFieldLength(1);
+            fields[numInputFields].setDecimalCount(0);
+            
+            fields[numInputFields + 1] = new DBFField();
+            fields[numInputFields + 1].setName("SNAP_DIST");
+            fields[numInputFields + 1].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields + 1].setFieldLength(12);
+            fields[numInputFields + 1].setDecimalCount(3);
+            
+            fields[numInputFields + 2] = new DBFField();
+            fields[numInputFields + 2].setName("CELL_VALUE");
+            fields[numInputFields + 2].setDataType(DBFField.DBFDataType.NUMERIC);
+            fields[numInputFields + 2].setFieldLength(14);
+            fields[numInputFields + 2].setDecimalCount(4);
+            
+            ShapeFile output = new ShapeFile(outputFile, pointsShapeType, fields);
+            output.setProjectionStringFromOtherShapefile(points);
+            
+            int numUnsnapped = 0;
+            numFeatures = points.getNumberOfRecords();
+            featureNum = 0;
+            for (ShapeFileRecord record : points.records) {
+                featureNum++;
+                if (record.getShapeType() != ShapeType.NULLSHAPE) {
+                    Object[] attData = pointsTable.getRecord(record.getRecordNumber() - 1);
+                    Geometry geometry = record.getGeometry();
+                    x = geometry.getPoints()[0][0];
+                    y = geometry.getPoints()[0][1];
+                    pointRow = raster.getRowFromYCoordinate(y);
+                    pointCol = raster.getColumnFromXCoordinate(x);
+                    
+                    // find the nearest qualifying cell within the search distance
+                    double minDist = Double.POSITIVE_INFINITY;
+                    int minRow = -1;
+                    int minCol = -1;
+                    for (row = pointRow - windowRows; row <= pointRow + windowRows; row++) {
+                        if (row < 0 || row >= rows) {
+                            continue;
+                        }
+                        for (col = pointCol - windowCols; col <= pointCol + windowCols; col++) {
+                            if (col < 0 || col >= cols) {
+                                continue;
+                            }
+                            z = raster.getValue(row, col);
+                            if (!meetsCriterion(z, noData, criterionType)) {
+                                continue;
+                            }
+                            cellX = raster.getXCoordinateFromColumn(col);
+                            cellY = raster.getYCoordinateFromRow(row);
+                            dist = Math.hypot(cellX - x, cellY - y);
+                            if (dist <= searchDistance && dist < minDist) {
+                                minDist = dist;
+                                minRow = row;
+                                minCol = col;
+                            }
+                        }
+                    }
+                    
+                    Object[] rowData = new Object[numInputFields + 3];
+                    System.arraycopy(attData, 0, rowData, 0, numInputFields);
+                    double outputX = x;
+                    double outputY = y;
+                    if (minRow >= 0) {
+                        rowData[numInputFields] = 1.0;
+                        rowData[numInputFields + 1] = minDist;
+                        rowData[numInputFields + 2] = raster.getValue(minRow, minCol);
+                        if (!outputOriginalPoints) {
+                            outputX = raster.getXCoordinateFromColumn(minCol);
+                            outputY = raster.getYCoordinateFromRow(minRow);
+                        }
+                    } else {
+                        // the point is kept at its original location and flagged
+                        rowData[numInputFields] = 0.0;
+                        numUnsnapped++;
+                    }
+                    
+                    switch (pointsShapeType) {
+                        case POINT:
+                            output.addRecord(new whitebox.geospatialfiles.shapefile.Point(outputX, outputY), rowData);
+                            break;
+                        case POINTZ:
+                            PointZ pointZ = (PointZ) geometry;
+                            output.addRecord(new PointZ(outputX, outputY, pointZ.getZ(), pointZ.getM()), rowData);
+                            break;
+                        case POINTM:
+                            PointM pointM = (PointM) geometry;
+                            output.addRecord(new PointM(outputX, outputY, pointM.getM()), rowData);
+                            break;
+                    }
+                }
+                
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (featureNum * 100.0 / numFeatures);
+                updateProgress(progress);
+            }
+            
+            output.write();
+            raster.close();
+            
+            if (numUnsnapped > 0) {
+                showFeedback(numUnsnapped + " point(s) had no suitable grid cell within the search distance. "
+                        + "These have a SNAPPED value of 0 in the output attribute table.");
+            }
+            
+            // returning a header file string displays the image.
+            returnData(outputFile);
+            
+            
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+       
+    }
+    
+    private boolean meetsCriterion(double z, double noData, int criterionType) {
+        if (z == noData) {
+            return false;
+        }
+        switch (criterionType) {
+            case 0:
+                return z > 0;
+            case 1:
+                return z != 0;
+            default:
+                return true;
+        }
+    }
+}
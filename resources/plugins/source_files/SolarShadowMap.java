@@ -0,0 +1,499 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.text.DecimalFormat;
+import java.util.Calendar;
+import java.util.Date;
+import java.util.GregorianCalendar;
+import java.util.TimeZone;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+import whitebox.utilities.Parallel;
+
+/**
+ * This tool identifies the grid cells in a digital elevation model (DEM) that are in the shadow of the
+ * surrounding terrain at a specified date and time.
+ */
+public class SolarShadowMap implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SolarShadowMap";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Solar Shadow Map";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Maps the areas in the shadow of the terrain at a given date and time.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"TerrainAnalysis"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Estimates the value at a fractional grid position by bilinear
+     * interpolation between the centres of the four surrounding cells.
+     *
+     * @return the interpolated value, or NoData if the position is outside of
+     * the grid or any of the surrounding cells is NoData.
+     * @see WhiteboxRaster#getBilinearValue(double, double)
+     */
+    private static double getBilinearValue(double[][] data, double noData,
+            double row, double column) {
+        int rows = data.length;
+        int cols = data[0].length;
+        if (!(row >= -0.5 && row <= rows - 0.5
+                && column >= -0.5 && column <= cols - 0.5)) {
+            return noData;
+        }
+        double r = Math.min(Math.max(row, 0), rows - 1);
+        double c = Math.min(Math.max(column, 0), cols - 1);
+        int r0 = (int) Math.floor(r);
+        int c0 = (int) Math.floor(c);
+        double dr = r - r0;
+        double dc = c - c0;
+        int r1 = dr > 0 ? r0 + 1 : r0;
+        int c1 = dc > 0 ? c0 + 1 : c0;
+        double z00 = data[r0][c0];
+        double z01 = data[r0][c1];
+        double z10 = data[r1][c0];
+        double z11 = data[r1][c1];
+        if (z00 == noData || z01 == noData || z10 == noData || z11 == noData) {
+            return noData;
+        }
+        return z00 * (1 - dr) * (1 - dc) + z01 * (1 - dr) * dc
+                + z10 * dr * (1 - dc) + z11 * dr * dc;
+    }
+
+    /**
+     * Calculates the position of the sun using the NOAA general solar
+     * position equations (after Spencer, 1971).
+     *
+     * @param latitude the latitude in degrees, positive in the northern
+     * hemisphere.
+     * @param longitude the longitude in degrees, positive east of Greenwich.
+     * @param dayOfYear the day of the year, starting at 1 on January 1.
+     * @param hour the time of day in hours (UTC).
+     * @param daysInYear the number of days in the year.
+     * @return the solar azimuth, in degrees clockwise from north, and the
+     * solar altitude above the horizon, in degrees.
+     */
+    private static double[] getSolarPosition(double latitude, double longitude,
+            int dayOfYear, double hour, int daysInYear) {
+        double gamma = 2 * Math.PI / daysInYear * (dayOfYear - 1 + (hour - 12) / 24);
+        double eqTime = 229.18 * (0.000075 + 0.001868 * Math.cos(gamma)
+                - 0.032077 * Math.sin(gamma) - 0.014615 * Math.cos(2 * gamma)
+                - 0.040849 * Math.sin(2 * gamma));
+        double declination = 0.006918 - 0.399912 * Math.cos(gamma)
+                + 0.070257 * Math.sin(gamma) - 0.006758 * Math.cos(2 * gamma)
+                + 0.000907 * Math.sin(2 * gamma) - 0.002697 * Math.cos(3 * gamma)
+                + 0.00148 * Math.sin(3 * gamma);
+        // true solar time in minutes, and the hour angle
+        double solarTime = hour * 60 + eqTime + 4 * longitude;
+        double hourAngle = Math.toRadians(solarTime / 4 - 180);
+        double lat = Math.toRadians(latitude);
+        double cosZenith = Math.sin(lat) * Math.sin(declination)
+                + Math.cos(lat) * Math.cos(declination) * Math.cos(hourAngle);
+        cosZenith = Math.max(-1, Math.min(1, cosZenith));
+        double altitude = 90 - Math.toDegrees(Math.acos(cosZenith));
+        double azimuth = Math.toDegrees(Math.atan2(Math.sin(hourAngle),
+                Math.cos(hourAngle) * Math.sin(lat) - Math.tan(declination) * Math.cos(lat))) + 180;
+        if (azimuth >= 360) {
+            azimuth -= 360;
+        }
+        return new double[]{azimuth, altitude};
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        double latitude = Double.NaN;
+        double longitude = Double.NaN;
+        String dateTime = null;
+        int dayOfYear = -1;
+        double hour = Double.NaN;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2 && specified) {
+                latitude = Double.parseDouble(args[i]);
+            } else if (i == 3 && specified) {
+                longitude = Double.parseDouble(args[i]);
+            } else if (i == 4 && specified) {
+                dateTime = args[i].trim();
+            } else if (i == 5 && specified) {
+                dayOfYear = (int) Double.parseDouble(args[i]);
+            } else if (i == 6 && specified) {
+                hour = Double.parseDouble(args[i]);
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        // the date and time, in UTC
+        int daysInYear = 365;
+        if (dateTime != null) {
+            Matcher m = Pattern.compile("(\\d{4})-(\\d{2})-(\\d{2})(?:[T ](\\d{2}):(\\d{2})(?::(\\d{2}(?:\\.\\d+)?))?)?\\s*(Z|[+-]\\d{2}(?::?\\d{2})?)?").matcher(dateTime);
+            if (!m.matches()) {
+                showFeedback("The date and time must be in the ISO 8601 format, e.g. 2026-06-21T14:30:00Z.");
+                return;
+            }
+            GregorianCalendar calendar = new GregorianCalendar(TimeZone.getTimeZone("UTC"));
+            calendar.clear();
+            calendar.set(Integer.parseInt(m.group(1)), Integer.parseInt(m.group(2)) - 1,
+                    Integer.parseInt(m.group(3)));
+            double minutes = 0;
+            if (m.group(4) != null) {
+                minutes = Integer.parseInt(m.group(4)) * 60 + Integer.parseInt(m.group(5));
+                if (m.group(6) != null) {
+                    minutes += Double.parseDouble(m.group(6)) / 60;
+                }
+            }
+            if (m.group(7) != null && !m.group(7).equals("Z")) {
+                String offset = m.group(7).replace(":", "");
+                int sign = offset.startsWith("-") ? -1 : 1;
+                int offsetMinutes = Integer.parseInt(offset.substring(1, 3)) * 60;
+                if (offset.length() > 3) {
+                    offsetMinutes += Integer.parseInt(offset.substring(3));
+                }
+                minutes -= sign * offsetMinutes;
+            }
+            calendar.add(Calendar.MINUTE, (int) Math.floor(minutes));
+            minutes -= Math.floor(minutes);
+            dayOfYear = calendar.get(Calendar.DAY_OF_YEAR);
+            hour = calendar.get(Calendar.HOUR_OF_DAY) + (calendar.get(Calendar.MINUTE) + minutes) / 60;
+            daysInYear = calendar.getActualMaximum(Calendar.DAY_OF_YEAR);
+        } else if (dayOfYear < 1 || dayOfYear > 366 || Double.isNaN(hour)) {
+            showFeedback("Either the date and time, or the day of year and hour, must be specified.");
+            return;
+        } else if (hour < 0 || hour > 24) {
+            showFeedback("The hour must be between 0 and 24.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster DEM = new WhiteboxRaster(inputHeader, "r");
+            final int rows = DEM.getNumberRows();
+            final int cols = DEM.getNumberColumns();
+            final double noData = DEM.getNoDataValue();
+            final double cellSizeX = DEM.getCellSizeX();
+            final double cellSizeY = DEM.getCellSizeY();
+
+            double zFactor = 1;
+            if (DEM.getXYUnits().toLowerCase().contains("deg")
+                    || DEM.getProjection().toLowerCase().contains("geog")) {
+                double midLat = (DEM.getNorth() + DEM.getSouth()) / 2.0;
+                double midLon = (DEM.getEast() + DEM.getWest()) / 2.0;
+                if (midLat <= 90 && midLat >= -90) {
+                    zFactor = 1.0 / (113200 * Math.cos(Math.toRadians(midLat)));
+                    // default to the location of the centre of the DEM
+                    if (Double.isNaN(latitude)) {
+                        latitude = midLat;
+                    }
+                    if (Double.isNaN(longitude)) {
+                        longitude = midLon;
+                    }
+                }
+            }
+            if (Double.isNaN(latitude) || Double.isNaN(longitude)) {
+                showFeedback("The latitude and longitude must be specified for DEMs "
+                        + "that are not in geographic coordinates.");
+                return;
+            }
+
+            double[] sun = getSolarPosition(latitude, longitude, dayOfYear, hour, daysInYear);
+            final double azimuth = sun[0];
+            final double altitude = sun[1];
+
+            final double[][] elevations = new double[rows][];
+            double maxZ = Double.NEGATIVE_INFINITY;
+            for (int row = 0; row < rows; row++) {
+                elevations[row] = DEM.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (elevations[row][col] != noData) {
+                        elevations[row][col] *= zFactor;
+                        if (elevations[row][col] > maxZ) {
+                            maxZ = elevations[row][col];
+                        }
+                    }
+                }
+            }
+            final double maxElevation = maxZ;
+
+            // The rays are traced towards the sun in steps of half a cell,
+            // comparing the height of the ray with the interpolated elevation
+            // of the DEM, until the ray leaves the grid or rises above the
+            // highest point of the DEM.
+            final double step = Math.min(cellSizeX, cellSizeY) / 2;
+            final double tanAltitude = Math.tan(Math.toRadians(altitude));
+            final double dRow = -step * Math.cos(Math.toRadians(azimuth)) / cellSizeY;
+            final double dCol = step * Math.sin(Math.toRadians(azimuth)) / cellSizeX;
+            final WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.INTEGER, noData);
+            output.setPreferredPalette("black_white.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.BOOLEAN);
+
+            boolean completed = Parallel.ForRows(rows, cols, new Parallel.RowTask() {
+
+                @Override
+                public void run(int row, double[] data) {
+                    for (int col = 0; col < cols; col++) {
+                        double z = elevations[row][col];
+                        if (z == noData) {
+                            data[col] = noData;
+                            continue;
+                        }
+                        if (altitude <= 0) {
+                            // the sun is below the horizon
+                            data[col] = 1;
+                            continue;
+                        }
+                        int inShadow = 0;
+                        for (int k = 1;; k++) {
+                            double rayZ = z + k * step * tanAltitude;
+                            if (rayZ > maxElevation) {
+                                break;
+                            }
+                            double terrainZ = getBilinearValue(elevations, noData,
+                                    row + k * dRow, col + k * dCol);
+                            if (terrainZ == noData) {
+                                break;
+                            }
+                            if (terrainZ > rayZ) {
+                                inShadow = 1;
+                                break;
+                            }
+                        }
+                        data[col] = inShadow;
+                    }
+                }
+            }, new Parallel.RowSink() {
+
+                @Override
+                public boolean accept(int row, double[] data) {
+                    output.setRowValues(row, data);
+                    if (cancelOp) {
+                        return false;
+                    }
+                    updateProgress((int) (100f * (row + 1) / rows));
+                    return true;
+                }
+            });
+
+            if (!completed) {
+                DEM.close();
+                output.close();
+                cancelOperation();
+                return;
+            }
+
+            DecimalFormat df = new DecimalFormat("0.00");
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Location: latitude " + latitude + ", longitude " + longitude);
+            output.addMetadataEntry("Day of year: " + dayOfYear + ", hour (UTC): " + df.format(hour));
+            output.addMetadataEntry("Solar azimuth: " + df.format(azimuth)
+                    + " degrees, solar altitude: " + df.format(altitude) + " degrees");
+            output.addMetadataEntry("1 = in shadow, 0 = sunlit");
+
+            DEM.close();
+            output.close();
+
+            if (altitude <= 0) {
+                showFeedback("The sun is below the horizon at the specified time, "
+                        + "and so the entire DEM is in shadow.");
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,461 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedOutputStream;
+import java.io.FileOutputStream;
+import java.io.RandomAccessFile;
+import java.nio.ByteBuffer;
+import java.nio.ByteOrder;
+import java.nio.MappedByteBuffer;
+import java.nio.channels.FileChannel;
+import java.util.Arrays;
+import whitebox.geospatialfiles.LASReader;
+import whitebox.geospatialfiles.LasSpatialIndex;
+import whitebox.structures.BoundingBox;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool can be used to reorder the points in a LAS file, either spatially
+ * along a Morton (Z-order) curve or by GPS time, and to create a spatial index
+ * for the sorted file that speeds up bounding-box queries.
+ * 
+ */
+public class SortLidar implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+    
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "SortLidar";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Sort LiDAR Points";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Sorts the points in a LAS file spatially or by GPS time and creates a spatial index.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */    
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"LidarTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */    
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputFile = null;
+        String outputFile = null;
+        boolean sortByTime = false;
+        int blockSize = 10000;
+        int i, p, progress;
+        double x, y;
+        RandomAccessFile rIn = null;
+        BufferedOutputStream out = null;
+
+        // get the arguments
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputFile = args[i];
+            } else if (i == 1) {
+                outputFile = args[i];
+            } else if (i == 2) {
+                sortByTime = args[i].toLowerCase().contains("time");
+            } else if (i == 3) {
+                if (!args[i].trim().isEmpty() && !args[i].toLowerCase().equals("not specified")) {
+                    blockSize = Integer.parseInt(args[i].trim());
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputFile == null) || (outputFile == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (inputFile.equals(outputFile)) {
+            showFeedback("The output file must be different from the input file.");
+            return;
+        }
+        if (blockSize < 1) {
+            showFeedback("The number of points per index block must be greater than zero.");
+            return;
+        }
+
+        try {
+            LASReader las = new LASReader(inputFile);
+            if (las.getNumPointRecords() > Integer.MAX_VALUE) {
+                showFeedback("The LAS file contains too many points to be sorted.");
+                return;
+            }
+            int numPoints = (int) las.getNumPointRecords();
+            int recLength = las.getPointDataRecLength();
+            long offset = las.getOffsetToPointData();
+            short format = las.getPointDataFormatID();
+            double xScale = las.getXScale();
+            double yScale = las.getYScale();
+            double xOffset = las.getXOffset();
+            double yOffset = las.getYOffset();
+            if (sortByTime && format != 1 && format != 3 && format != 4 && format != 5) {
+                showFeedback("The points in this LAS file do not contain GPS time.");
+                return;
+            }
+
+            rIn = new RandomAccessFile(inputFile, "r");
+            FileChannel inChannel = rIn.getChannel();
+            long fileLength = inChannel.size();
+            long endOfPoints = offset + (long) numPoints * recLength;
+            if (endOfPoints > fileLength) {
+                showFeedback("The LAS file appears to be truncated.");
+                return;
+            }
+
+            // The point records are memory-mapped in chunks of whole records so
+            // that they can be read in sorted order without loading the file.
+            int recsPerChunk = Integer.MAX_VALUE / recLength;
+            int numChunks = (int) (((long) numPoints + recsPerChunk - 1) / recsPerChunk);
+            MappedByteBuffer[] chunks = new MappedByteBuffer[numChunks];
+            for (i = 0; i < numChunks; i++) {
+                long n = Math.min(recsPerChunk, numPoints - (long) i * recsPerChunk);
+                chunks[i] = inChannel.map(FileChannel.MapMode.READ_ONLY,
+                        offset + (long) i * recsPerChunk * recLength, n * recLength);
+                chunks[i].order(ByteOrder.LITTLE_ENDIAN);
+            }
+
+            /*
+             * Each point is given a 64-bit key with the quantized sort value
+             * in the upper 32 bits and the point number in the lower 32 bits,
+             * so that sorting the keys orders the points and keeps ties in
+             * their original order. The sort value is either the Morton code
+             * of the point's position on a 65536 x 65536 grid covering the
+             * file's extent, or the GPS time scaled to 32 bits.
+             */
+            long[] keys = new long[numPoints];
+            MappedByteBuffer buf;
+            int pos;
+            long q;
+            if (!sortByTime) {
+                double minX = las.getMinX();
+                double minY = las.getMinY();
+                double rangeX = Math.max(las.getMaxX() - minX, xScale);
+                double rangeY = Math.max(las.getMaxY() - minY, yScale);
+                for (p = 0; p < numPoints; p++) {
+                    buf = chunks[p / recsPerChunk];
+                    pos = (p % recsPerChunk) * recLength;
+                    x = buf.getInt(pos) * xScale + xOffset;
+                    y = buf.getInt(pos + 4) * yScale + yOffset;
+                    q = spreadBits(quantize((x - minX) / rangeX, 65535))
+                            | (spreadBits(quantize((y - minY) / rangeY, 65535)) << 1);
+                    keys[p] = makeKey(q, p);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (100f * p / numPoints);
+                    updateProgress("Loop 1 of 2:", progress);
+                }
+            } else {
+                double minTime = Double.POSITIVE_INFINITY;
+                double maxTime = Double.NEGATIVE_INFINITY;
+                double t;
+                for (p = 0; p < numPoints; p++) {
+                    t = chunks[p / recsPerChunk].getDouble((p % recsPerChunk) * recLength + 20);
+                    minTime = Math.min(minTime, t);
+                    maxTime = Math.max(maxTime, t);
+                }
+                double rangeTime = Math.max(maxTime - minTime, Double.MIN_NORMAL);
+                for (p = 0; p < numPoints; p++) {
+                    t = chunks[p / recsPerChunk].getDouble((p % recsPerChunk) * recLength + 20);
+                    keys[p] = makeKey(quantize((t - minTime) / rangeTime, 0xFFFFFFFFL), p);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                    progress = (int) (100f * p / numPoints);
+                    updateProgress("Loop 1 of 2:", progress);
+                }
+            }
+
+            updateProgress("Sorting points:", 0);
+            Arrays.sort(keys);
+
+            out = new BufferedOutputStream(new FileOutputStream(outputFile), 1 << 20);
+
+            // the header and variable length records are copied unchanged
+            ByteBuffer headerBuf = ByteBuffer.allocate((int) offset);
+            inChannel.read(headerBuf, 0);
+            out.write(headerBuf.array());
+
+            LasSpatialIndex index = new LasSpatialIndex(blockSize);
+            byte[] rec = new byte[recLength];
+            double blockMinX = Double.POSITIVE_INFINITY;
+            double blockMinY = Double.POSITIVE_INFINITY;
+            double blockMaxX = Double.NEGATIVE_INFINITY;
+            double blockMaxY = Double.NEGATIVE_INFINITY;
+            int blockStart = 0;
+            for (i = 0; i < numPoints; i++) {
+                p = (int) (keys[i] & 0xFFFFFFFFL);
+                buf = chunks[p / recsPerChunk];
+                pos = (p % recsPerChunk) * recLength;
+                buf.position(pos);
+                buf.get(rec);
+                out.write(rec);
+                x = buf.getInt(pos) * xScale + xOffset;
+                y = buf.getInt(pos + 4) * yScale + yOffset;
+                blockMinX = Math.min(blockMinX, x);
+                blockMinY = Math.min(blockMinY, y);
+                blockMaxX = Math.max(blockMaxX, x);
+                blockMaxY = Math.max(blockMaxY, y);
+                if (i - blockStart + 1 == blockSize || i == numPoints - 1) {
+                    index.addBlock(blockStart, i - blockStart + 1,
+                            new BoundingBox(blockMinX, blockMinY, blockMaxX, blockMaxY));
+                    blockStart = i + 1;
+                    blockMinX = Double.POSITIVE_INFINITY;
+                    blockMinY = Double.POSITIVE_INFINITY;
+                    blockMaxX = Double.NEGATIVE_INFINITY;
+                    blockMaxY = Double.NEGATIVE_INFINITY;
+                }
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                progress = (int) (100f * i / numPoints);
+                updateProgress("Loop 2 of 2:", progress);
+            }
+
+            // anything following the point records, e.g. waveform data, is
+            // copied unchanged and therefore keeps its offsets
+            ByteBuffer tailBuf = ByteBuffer.allocate(1 << 20);
+            long tailPos = endOfPoints;
+            while (tailPos < fileLength) {
+                tailBuf.clear();
+                int n = inChannel.read(tailBuf, tailPos);
+                if (n <= 0) {
+                    break;
+                }
+                out.write(tailBuf.array(), 0, n);
+                tailPos += n;
+            }
+
+            out.close();
+            out = null;
+            index.write(outputFile);
+
+            showFeedback("Sorted " + numPoints + " points. The spatial index contains "
+                    + index.getNumBlocks() + " blocks.");
+
+            returnData(outputFile);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            if (rIn != null) {
+                try {
+                    rIn.close();
+                } catch (Exception e) {
+                }
+            }
+            if (out != null) {
+                try {
+                    out.close();
+                } catch (Exception e) {
+                }
+            }
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    /**
+     * Scales a value in the range 0-1 to an integer between 0 and maxValue.
+     */
+    private static long quantize(double value, long maxValue) {
+        long q = (long) (value * maxValue);
+        return Math.max(0, Math.min(maxValue, q));
+    }
+
+    /**
+     * Spreads the lower 16 bits of a value into the even bits of a 32-bit
+     * value, for interleaving into a Morton code.
+     */
+    private static long spreadBits(long v) {
+        v &= 0xFFFFL;
+        v = (v | (v << 8)) & 0x00FF00FFL;
+        v = (v | (v << 4)) & 0x0F0F0F0FL;
+        v = (v | (v << 2)) & 0x33333333L;
+        v = (v | (v << 1)) & 0x55555555L;
+        return v;
+    }
+
+    /**
+     * Combines an unsigned 32-bit sort value and a point number into a key.
+     * The sign bit is flipped so that a signed sort orders the keys as
+     * unsigned values.
+     */
+    private static long makeKey(long sortValue, int pointNum) {
+        return ((sortValue << 32) | (pointNum & 0xFFFFFFFFL)) ^ Long.MIN_VALUE;
+    }
+}
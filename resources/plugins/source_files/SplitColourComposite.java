@@ -170,7 +170,7 @@ public class SplitColourComposite implements WhiteboxPlugin {
     		if (i == 0) {
                     inputHeader = args[i];
                 } else if (i == 1) {
-                    alphaChannelOutput = Boolean.getBoolean(args[i]);
+                    alphaChannelOutput = Boolean.parseBoolean(args[i]);
                 }
     	}
 
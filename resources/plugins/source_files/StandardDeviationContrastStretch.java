@@ -18,13 +18,11 @@
 package plugins;
 
 import java.util.Date;
-import java.io.*;
-import java.nio.*;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.interfaces.WhiteboxPlugin;
 import whitebox.interfaces.WhiteboxPluginHost;
 /**
- * Can't find
+ * This tool performs a standard deviation contrast stretch on a raster image. The lower and upper clip values are set to the image mean minus and plus a user-specified number of standard deviations (n), i.e. L = mean - n × sd and U = mean + n × sd, and each grid cell value (z) is then mapped onto the specified number of tonal values (t) such that zn = (z - L) / (U - L) × t, where zn is the output value.
  * @author Dr. John Lindsay email: jlindsay@uoguelph.ca
  */
 public class StandardDeviationContrastStretch implements WhiteboxPlugin {
@@ -160,131 +158,91 @@ public class StandardDeviationContrastStretch implements WhiteboxPlugin {
         String inputHeader = null;
         String outputHeader = null;
         int row, col;
-        int numCols;
-        int numRows;
         double z;
         double noData;
-        float progress;
-        int i;
-        int numReclassRanges;
-        int numReclassRangesMinusOne;
-        String[] reclassRangeStr = null;
-        double[][] reclassRange;
-        boolean blnAssignMode = false;
-        
+        int progress;
+        double numStdDevs = 2.0;
+        int numBins = 256;
+
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
             return;
         }
         
-        for (i = 0; i < args.length; i++) {
-            if (i == 0) {
-                inputHeader = args[i];
-            } else if (i == 1) {
-                outputHeader = args[i];
-            } else if (i == 2) {
-                reclassRangeStr = args[i].split("\t");
-                if (reclassRangeStr[2].toLowerCase().equals("not specified")) { blnAssignMode = true; }
-            }
-        }
-
+        inputHeader = args[0];
+        outputHeader = args[1];
+        numStdDevs = Double.parseDouble(args[2]);
+        numBins = Integer.parseInt(args[3]);
+        
         // check to see that the inputHeader are not null.
         if ((inputHeader == null) || (outputHeader == null)) {
             showFeedback("One or more of the input parameters have not been set properly.");
             return;
         }
         
+        if (numStdDevs <= 0) {
+            showFeedback("The number of standard deviations must be greater than zero.");
+            return;
+        }
+        
         try {
             
             WhiteboxRaster image = new WhiteboxRaster(inputHeader, "r");
-            int rows = image.getNumberRows();
-            int cols = image.getNumberColumns();
-            double[] data;
+            int nRows = image.getNumberRows();
+            int nCols = image.getNumberColumns();
             noData = image.getNoDataValue();
-            
-            //How many rows should there be in the ReclassRange array?
-            //There are three numbers in each range: New Value, From Value, To Just Less Than Value
-            numReclassRanges = reclassRangeStr.length / 3;
-            numReclassRangesMinusOne = numReclassRanges - 1;
-            reclassRange = new double[3][numReclassRanges];
-            i = 0;
-            for (int b = 0; b < reclassRangeStr.length; b++) {
-                if (!reclassRangeStr[b].toLowerCase().equals("not specified")) {
-                    if (!reclassRangeStr[b].toLowerCase().equals("nodata")) {
-                        reclassRange[i][b / 3] = Double.parseDouble(reclassRangeStr[b]);
-                    } else {
-                        reclassRange[i][b / 3] = noData;
-                    }
-                } else {
-                    reclassRange[i][b / 3] = 0;
-                }
-                i++;
-                if (i == 3) {
-                    i = 0;
-                }
+            StretchOutput stretch = new StretchOutput(numBins, args, 4, noData);
+            if (stretch.getError() != null) {
+                image.close();
+                showFeedback(stretch.getError());
+                return;
             }
-
-            if (numReclassRanges == 0) {
-                showFeedback("There is an error with the reclass ranges.");
+            double outputNoData = stretch.getNoData();
+            
+            double mean = image.getMean();
+            double stdDev = image.getStandardDeviation();
+            if (stdDev <= 0) {
+                showFeedback("The input image has no variation and cannot be stretched.");
+                image.close();
                 return;
             }
-
-            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            double minVal = mean - numStdDevs * stdDev;
+            double maxVal = mean + numStdDevs * stdDev;
+            
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader, stretch.getDataType(), outputNoData);
+            output.setNoDataValue(outputNoData);
             output.setPreferredPalette(image.getPreferredPalette());
-
-
-            if (blnAssignMode) {
-                for (row = 0; row < rows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < cols; col++) {
-                        for (i = 0; i < numReclassRanges; i++) {
-                            if (data[col] == reclassRange[1][i]) {
-                                output.setValue(row, col, reclassRange[0][i]);
-                                break;
-                            }
-                            if (i == numReclassRangesMinusOne) { //z was not in the reclass ranges; output value equals input value
-                                output.setValue(row, col, data[col]);
-                            }
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
+            
+            double[] data = null;
+            for (row = 0; row < nRows; row++) {
+                data = image.getRowValues(row);
+                for (col = 0; col < nCols; col++) {
+                    if (data[col] != noData) {
+                        z = stretch.stretch(data[col], minVal, maxVal);
+                        output.setValue(row, col, z);
+                    } else {
+                        output.setValue(row, col, outputNoData);
                     }
-                    progress = (float) (100f * row / (rows - 1));
-                    updateProgress((int) progress);
                 }
-
-            } else {
-                for (row = 0; row < rows; row++) {
-                    data = image.getRowValues(row);
-                    for (col = 0; col < cols; col++) {
-                        for (i = 0; i < numReclassRanges; i++) {
-                            if (data[col] >= reclassRange[1][i] && data[col] < reclassRange[2][i]) {
-                                output.setValue(row, col, reclassRange[0][i]);
-                                break;
-                            }
-                            if (i == numReclassRangesMinusOne) { //z was not in the reclass ranges; output value equals input value
-                                output.setValue(row, col, data[col]);
-                            }
-                        }
-
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    progress = (float) (100f * row / (rows - 1));
-                    updateProgress((int) progress);
+                if (cancelOp) {
+                    image.close();
+                    output.isTemporaryFile = true;
+                    output.close();
+                    cancelOperation();
+                    return;
                 }
-
+                progress = (int) (100f * row / (nRows - 1));
+                updateProgress(progress);
             }
-
+            
             image.close();
 
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Lower clip value: " + minVal);
+            output.addMetadataEntry("Upper clip value: " + maxVal);
+            output.addMetadataEntry("Output: " + stretch.getDescription());
             output.close();
 
             // returning a header file string displays the image.
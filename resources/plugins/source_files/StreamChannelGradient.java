@@ -0,0 +1,335 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the gradient of a stream channel at each stream cell,
+ * i.e. the drop in elevation along the channel over a reach of a specified
+ * number of grid cells downstream, divided by the along-channel length of the
+ * reach.
+ */
+public class StreamChannelGradient implements WhiteboxPlugin {
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    // Constants
+    private static final double LnOf2 = 0.693147180559945;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "StreamChannelGradient";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Stream Channel Gradient";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Calculates the gradient of a stream channel over a downstream reach.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"StreamAnalysis"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 6) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String demHeader = args[0];
+        String streamsHeader = args[1];
+        String pointerHeader = args[2];
+        String outputHeader = args[3];
+        int reachLength = Integer.parseInt(args[4]);
+        boolean localGradient = Boolean.parseBoolean(args[5]);
+        if (localGradient) {
+            reachLength = 1;
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((demHeader == null) || (streamsHeader == null) || (pointerHeader == null)
+                || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        if (reachLength < 1) {
+            showFeedback("The reach length must be at least one grid cell.");
+            return;
+        }
+
+        try {
+            int row, col, x, y, i, c;
+            double z, flowDir, length;
+            int progress, oldProgress = -1;
+            int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+            int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+
+            WhiteboxRaster dem = new WhiteboxRaster(demHeader, "r");
+            int rows = dem.getNumberRows();
+            int cols = dem.getNumberColumns();
+            double noData = dem.getNoDataValue();
+            double gridResX = dem.getCellSizeX();
+            double gridResY = dem.getCellSizeY();
+            double diagGridRes = Math.sqrt(gridResX * gridResX + gridResY * gridResY);
+            double[] gridLengths = new double[]{diagGridRes, gridResX, diagGridRes, gridResY,
+                diagGridRes, gridResX, diagGridRes, gridResY};
+
+            WhiteboxRaster streams = new WhiteboxRaster(streamsHeader, "r");
+            WhiteboxRaster pntr = new WhiteboxRaster(pointerHeader, "r");
+            if (streams.getNumberRows() != rows || streams.getNumberColumns() != cols
+                    || pntr.getNumberRows() != rows || pntr.getNumberColumns() != cols) {
+                showFeedback("The input images must be of the same dimensions.");
+                return;
+            }
+            double streamsNoData = streams.getNoDataValue();
+            double pntrNoData = pntr.getNoDataValue();
+
+            WhiteboxRasterStreamWriter output = new WhiteboxRasterStreamWriter(outputHeader,
+                    demHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette("spectrum.pal");
+            output.setZUnits("dimensionless");
+
+            double[] outputData = new double[cols];
+            double[] streamsData;
+            for (row = 0; row < rows; row++) {
+                streamsData = streams.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    outputData[col] = noData;
+                    z = dem.getValue(row, col);
+                    if (streamsData[col] == streamsNoData || streamsData[col] <= 0 || z == noData) {
+                        continue;
+                    }
+                    // follow the flow pointer downstream for the length of
+                    // the reach. The reach is incomplete if it leaves the
+                    // grid, enters NoData, or ends at a cell without flow.
+                    x = col;
+                    y = row;
+                    length = 0;
+                    for (i = 0; i < reachLength; i++) {
+                        flowDir = pntr.getValue(y, x);
+                        if (flowDir == pntrNoData || flowDir <= 0) {
+                            length = -1;
+                            break;
+                        }
+                        c = (int) Math.round(Math.log(flowDir) / LnOf2);
+                        if (c > 7 || (1 << c) != flowDir) {
+                            showFeedback("An unexpected value has "
+                                    + "been identified in the pointer "
+                                    + "image. This tool requires a "
+                                    + "pointer grid that has been "
+                                    + "created using either the D8 "
+                                    + "or Rho8 tools.");
+                            return;
+                        }
+                        x += dX[c];
+                        y += dY[c];
+                        length += gridLengths[c];
+                        if (x < 0 || x >= cols || y < 0 || y >= rows || dem.getValue(y, x) == noData) {
+                            length = -1;
+                            break;
+                        }
+                    }
+                    if (length > 0) {
+                        outputData[col] = (z - dem.getValue(y, x)) / length;
+                    }
+                }
+                output.writeRow(row, outputData);
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress(progress);
+                    if (cancelOp) {
+                        output.abort();
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Reach length: " + reachLength + " grid cells");
+
+            dem.close();
+            streams.close();
+            pntr.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType;
+
+/**
+ * The output range and data type of a linear contrast stretch. The stretched
+ * values are numTones equally spaced tones from the output minimum to the
+ * output maximum, which default to 0 and numTones - 1. The output is stored
+ * as the requested integer type unless the tones are not whole numbers, e.g.
+ * for a range of 0 to 1, in which case it is stored as floats. The NoData
+ * value is that of the input unless the output data type can't hold it or it
+ * falls within the output range.
+ */
+class StretchOutput {
+
+    private final int numTones;
+    private final double outputMin;
+    private final double outputMax;
+    private final DataType dataType;
+    private final double noData;
+    private final String error;
+
+    /**
+     * @param numTones The number of tones in the output.
+     * @param args The tool's arguments.
+     * @param firstArg The index of the output minimum argument, which is
+     * followed by the output maximum and the output data type. Missing or
+     * unspecified arguments take their default values.
+     * @param inputNoData The NoData value of the input image.
+     */
+    StretchOutput(int numTones, String[] args, int firstArg, double inputNoData) {
+        this.numTones = numTones;
+        String minArg = getArg(args, firstArg);
+        String maxArg = getArg(args, firstArg + 1);
+        String typeArg = getArg(args, firstArg + 2);
+        outputMin = (minArg != null) ? Double.parseDouble(minArg) : 0;
+        outputMax = (maxArg != null) ? Double.parseDouble(maxArg) : outputMin + numTones - 1;
+        String requested = (typeArg != null) ? typeArg.toLowerCase() : "integer";
+
+        double step = (numTones > 1) ? (outputMax - outputMin) / (numTones - 1) : 0;
+        boolean wholeTones = outputMin == Math.rint(outputMin) && step == Math.rint(step);
+        String message = null;
+        if (numTones < 2) {
+            message = "The number of tones must be at least two.";
+        } else if (!(outputMax > outputMin)) {
+            message = "The output maximum must be greater than the output minimum.";
+        }
+
+        if (requested.contains("float") || !wholeTones) {
+            dataType = DataType.FLOAT;
+            noData = inputNoData;
+        } else if (requested.contains("byte")) {
+            dataType = DataType.BYTE;
+            if (message == null && (outputMin < 0 || outputMax > 255)) {
+                message = "A byte output must range from 0 to 255 or less.";
+            }
+            if (inputNoData >= 0 && inputNoData <= 255 && inputNoData == Math.rint(inputNoData)
+                    && (inputNoData < outputMin || inputNoData > outputMax)) {
+                noData = inputNoData;
+            } else if (outputMax < 255) {
+                noData = 255;
+            } else if (outputMin > 0) {
+                noData = 0;
+            } else {
+                noData = inputNoData;
+                if (message == null) {
+                    message = "The output range fills the byte data type, leaving no value for NoData.";
+                }
+            }
+        } else {
+            dataType = DataType.INTEGER;
+            if (message == null && (outputMin < Short.MIN_VALUE + 1 || outputMax > Short.MAX_VALUE)) {
+                message = "An integer output must range from -32767 to 32767 or less.";
+            }
+            if (inputNoData >= Short.MIN_VALUE && inputNoData <= Short.MAX_VALUE
+                    && inputNoData == Math.rint(inputNoData)
+                    && (inputNoData < outputMin || inputNoData > outputMax)) {
+                noData = inputNoData;
+            } else {
+                noData = Short.MIN_VALUE;
+            }
+        }
+        error = message;
+    }
+
+    private static String getArg(String[] args, int i) {
+        if (i >= args.length || args[i].trim().isEmpty()
+                || args[i].toLowerCase().equals("not specified")) {
+            return null;
+        }
+        return args[i].trim();
+    }
+
+    /**
+     * @return A message describing why the output can't be created, or null
+     * if the range and data type are valid.
+     */
+    String getError() {
+        return error;
+    }
+
+    DataType getDataType() {
+        return dataType;
+    }
+
+    double getNoData() {
+        return noData;
+    }
+
+    /**
+     * Maps a value onto the output tones, given the input values that are
+     * mapped to the output minimum and maximum. Values beyond them saturate.
+     */
+    double stretch(double z, double minVal, double maxVal) {
+        double tone = Math.floor((z - minVal) * numTones / (maxVal - minVal));
+        if (tone < 0) {
+            tone = 0;
+        } else if (tone > numTones - 1) {
+            tone = numTones - 1;
+        }
+        return outputMin + tone * (outputMax - outputMin) / (numTones - 1);
+    }
+
+    /**
+     * @return A metadata entry describing the output range.
+     */
+    String getDescription() {
+        return numTones + " tones from " + outputMin + " to " + outputMax;
+    }
+}
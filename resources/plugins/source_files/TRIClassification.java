@@ -0,0 +1,377 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.io.BufferedWriter;
+import java.io.FileWriter;
+import java.io.PrintWriter;
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool classifies a topographic ruggedness index (TRI) raster into qualitative
+ * ruggedness classes, using either the Riley et al. (1999) classes or user-defined classes.
+ */
+public class TRIClassification implements WhiteboxPlugin {
+    
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "TRIClassification";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+    	return "Classify Topographic Ruggedness";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+    	return "Classifies a topographic ruggedness index raster into ruggedness classes.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+    	String[] ret = { "ElevResiduals" };
+    	return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+    
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress) || 
+                (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+    
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    
+    private boolean cancelOp = false;
+    
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+    
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    
+    private boolean amIActive = false;
+    
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+
+    // the upper bounds of the Riley et al. (1999) classes, in metres
+    private static final double[] rileyBreaks = {80, 116, 161, 239, 497, 958};
+    private static final String[] rileyClassNames = {"Level", "Nearly level",
+        "Slightly rugged", "Intermediately rugged", "Moderately rugged",
+        "Highly rugged", "Extremely rugged"};
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String outputHeader = null;
+        String scheme = "riley";
+        String breaksString = null;
+        String labelsString = null;
+        String csvFile = null;
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (int i = 0; i < args.length; i++) {
+            boolean specified = !args[i].trim().isEmpty()
+                    && !args[i].toLowerCase().equals("not specified");
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                outputHeader = args[i];
+            } else if (i == 2 && specified) {
+                scheme = args[i].toLowerCase();
+            } else if (i == 3 && specified) {
+                breaksString = args[i];
+            } else if (i == 4 && specified) {
+                labelsString = args[i];
+            } else if (i == 5 && specified) {
+                csvFile = args[i];
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        // the upper bound of each class but the last, and the class codes
+        double[] breaks;
+        int[] classCodes;
+        String[] classNames;
+        if (scheme.startsWith("user")) {
+            if (breaksString == null) {
+                showFeedback("The class breaks must be specified for a user-defined classification.");
+                return;
+            }
+            String[] str = breaksString.split(";");
+            breaks = new double[str.length];
+            for (int i = 0; i < str.length; i++) {
+                breaks[i] = Double.parseDouble(str[i].trim());
+                if (i > 0 && breaks[i] <= breaks[i - 1]) {
+                    showFeedback("The class breaks must be in increasing order.");
+                    return;
+                }
+            }
+            classCodes = new int[breaks.length + 1];
+            if (labelsString != null) {
+                str = labelsString.split(";");
+                if (str.length != classCodes.length) {
+                    showFeedback("There must be one more class label than there are class breaks.");
+                    return;
+                }
+                for (int i = 0; i < str.length; i++) {
+                    classCodes[i] = Integer.parseInt(str[i].trim());
+                }
+            } else {
+                for (int i = 0; i < classCodes.length; i++) {
+                    classCodes[i] = i + 1;
+                }
+            }
+            classNames = new String[classCodes.length];
+            for (int i = 0; i < classNames.length; i++) {
+                classNames[i] = "Class " + classCodes[i];
+            }
+        } else {
+            breaks = rileyBreaks;
+            classCodes = new int[breaks.length + 1];
+            for (int i = 0; i < classCodes.length; i++) {
+                classCodes[i] = i + 1;
+            }
+            classNames = rileyClassNames;
+        }
+
+        try {
+            WhiteboxRaster input = new WhiteboxRaster(inputHeader, "r");
+            int rows = input.getNumberRows();
+            int cols = input.getNumberColumns();
+            double noData = input.getNoDataValue();
+            double cellArea = Math.abs(input.getCellSizeX() * input.getCellSizeY());
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw", inputHeader,
+                    WhiteboxRaster.DataType.INTEGER, noData);
+            output.setPreferredPalette("qual.pal");
+            output.setDataScale(WhiteboxRaster.DataScale.CATEGORICAL);
+
+            long[] classCounts = new long[classCodes.length];
+            double[] outData = new double[cols];
+            for (int row = 0; row < rows; row++) {
+                double[] data = input.getRowValues(row);
+                for (int col = 0; col < cols; col++) {
+                    if (data[col] != noData) {
+                        // class bounds are inclusive of their upper bound, e.g. a TRI of 80 is level
+                        int c = 0;
+                        while (c < breaks.length && data[col] > breaks[c]) {
+                            c++;
+                        }
+                        outData[col] = classCodes[c];
+                        classCounts[c]++;
+                    } else {
+                        outData[col] = noData;
+                    }
+                }
+                output.setRowValues(row, outData);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
+                }
+                updateProgress((int) (100f * row / (rows - 1)));
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            for (int c = 0; c < classCodes.length; c++) {
+                output.addMetadataEntry("Class " + classCodes[c] + ": " + classNames[c]
+                        + " (" + getClassRange(breaks, c) + ")");
+            }
+
+            input.close();
+            output.close();
+
+            if (csvFile != null) {
+                long totalCount = 0;
+                for (long count : classCounts) {
+                    totalCount += count;
+                }
+                try (PrintWriter out = new PrintWriter(new BufferedWriter(new FileWriter(csvFile, false)))) {
+                    out.println("Class,Name,Lower Bound,Upper Bound,Cells,Area,Percent");
+                    for (int c = 0; c < classCounts.length; c++) {
+                        out.println(classCodes[c] + ",\"" + classNames[c] + "\","
+                                + (c > 0 ? breaks[c - 1] : "") + ","
+                                + (c < breaks.length ? breaks[c] : "") + ","
+                                + classCounts[c] + "," + classCounts[c] * cellArea + ","
+                                + (totalCount > 0 ? 100.0 * classCounts[c] / totalCount : 0));
+                    }
+                    out.println("Total,,,," + totalCount + "," + totalCount * cellArea + ","
+                            + (totalCount > 0 ? 100.0 : 0));
+                }
+            }
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+
+    private static String getClassRange(double[] breaks, int c) {
+        if (c == 0) {
+            return "up to " + breaks[0];
+        } else if (c == breaks.length) {
+            return "greater than " + breaks[c - 1];
+        } else {
+            return "greater than " + breaks[c - 1] + " up to " + breaks[c];
+        }
+    }
+}
@@ -0,0 +1,334 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool estimates the daily snowmelt from a snowpack using the temperature
+ * index (degree-day) method, in which melt is proportional to the amount by
+ * which the air temperature exceeds a threshold.
+ */
+public class TemperatureIndexMelt implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "TemperatureIndexMelt";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Temperature Index Snowmelt";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Estimates daily snowmelt from air temperature using a degree-day factor.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"HydroTools"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        if (args.length < 4) {
+            showFeedback("Plugin parameters have not been set properly.");
+            return;
+        }
+
+        String snowHeader = args[0];
+        String temperatureHeader = args[1];
+        String meltHeader = args[2];
+        String remainingHeader = null;
+        if (!args[3].toLowerCase().contains("not specified")) {
+            remainingHeader = args[3];
+        }
+        double meltFactor = 4.0;
+        if (args.length > 4 && !args[4].toLowerCase().contains("not specified")) {
+            meltFactor = Double.parseDouble(args[4].trim());
+        }
+        double thresholdTemp = 0.0;
+        if (args.length > 5 && !args[5].toLowerCase().contains("not specified")) {
+            thresholdTemp = Double.parseDouble(args[5].trim());
+        }
+        if (meltFactor < 0) {
+            showFeedback("The melt factor must not be negative.");
+            return;
+        }
+
+        // check to see that the input and output headers are not null.
+        if ((snowHeader == null) || (temperatureHeader == null) || (meltHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+
+        try {
+            int row, col;
+            double swe, temperature, melt;
+            int progress, oldProgress = -1;
+            double[] snowData, temperatureData;
+
+            WhiteboxRaster snowFile = new WhiteboxRaster(snowHeader, "r");
+            WhiteboxRaster temperatureFile = new WhiteboxRaster(temperatureHeader, "r");
+
+            int rows = snowFile.getNumberRows();
+            int cols = snowFile.getNumberColumns();
+            double snowNoData = snowFile.getNoDataValue();
+            double temperatureNoData = temperatureFile.getNoDataValue();
+
+            if (temperatureFile.getNumberRows() != rows || temperatureFile.getNumberColumns() != cols) {
+                showFeedback("The input files must have the same dimensions.");
+                snowFile.close();
+                temperatureFile.close();
+                return;
+            }
+
+            double noData = snowNoData;
+            WhiteboxRaster meltFile = new WhiteboxRaster(meltHeader, "rw",
+                    snowHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            meltFile.setPreferredPalette("water depth.pal");
+            meltFile.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            meltFile.setZUnits("mm");
+
+            WhiteboxRaster remainingFile = null;
+            if (remainingHeader != null) {
+                remainingFile = new WhiteboxRaster(remainingHeader, "rw",
+                        snowHeader, WhiteboxRaster.DataType.FLOAT, noData);
+                remainingFile.setPreferredPalette(snowFile.getPreferredPalette());
+                remainingFile.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+                remainingFile.setZUnits("mm");
+            }
+
+            for (row = 0; row < rows; row++) {
+                snowData = snowFile.getRowValues(row);
+                temperatureData = temperatureFile.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    swe = snowData[col];
+                    temperature = temperatureData[col];
+                    if (swe != snowNoData && temperature != temperatureNoData) {
+                        if (swe < 0) {
+                            swe = 0;
+                        }
+                        // the potential melt, limited by the snow that is
+                        // available to melt
+                        melt = 0;
+                        if (temperature > thresholdTemp) {
+                            melt = Math.min(meltFactor * (temperature - thresholdTemp), swe);
+                        }
+                        meltFile.setValue(row, col, melt);
+                        if (remainingFile != null) {
+                            remainingFile.setValue(row, col, swe - melt);
+                        }
+                    } else {
+                        meltFile.setValue(row, col, noData);
+                        if (remainingFile != null) {
+                            remainingFile.setValue(row, col, noData);
+                        }
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress != oldProgress) {
+                    oldProgress = progress;
+                    updateProgress((int) progress);
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            meltFile.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            meltFile.addMetadataEntry("Created on " + new Date());
+            meltFile.addMetadataEntry("Melt factor: " + meltFactor + " mm/\u00B0C/day");
+            meltFile.addMetadataEntry("Threshold temperature: " + thresholdTemp + " \u00B0C");
+            meltFile.close();
+            if (remainingFile != null) {
+                remainingFile.addMetadataEntry("Created by the "
+                        + getDescriptiveName() + " tool.");
+                remainingFile.addMetadataEntry("Created on " + new Date());
+                remainingFile.addMetadataEntry("Snow water equivalent remaining after melt, "
+                        + "with a melt factor of " + meltFactor + " mm/\u00B0C/day and a "
+                        + "threshold temperature of " + thresholdTemp + " \u00B0C");
+                remainingFile.close();
+            }
+
+            snowFile.close();
+            temperatureFile.close();
+
+            // returning a header file string displays the image.
+            returnData(meltHeader);
+            if (remainingHeader != null) {
+                returnData(remainingHeader);
+            }
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
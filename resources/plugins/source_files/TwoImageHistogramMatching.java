@@ -32,6 +32,8 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
     
     private WhiteboxPluginHost myHost = null;
     private String[] args;
+    // the minimum number of histogram bins used to estimate each cdf
+    private static final int MIN_NUM_BINS = 10000;
 
     /**
      * Used to retrieve the plugin tool's name. This is a short, unique name
@@ -236,10 +238,9 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
             
             double minValue1 = inputFile1.getMinimumValue();
             double maxValue1 = inputFile1.getMaximumValue();
-            int numBins1 = Math.max(2 * (int)Math.ceil(maxValue1 - minValue1 + 1), 
-                    (int)Math.ceil(Math.pow(rows1 * cols1, 1.0 / 3)));
-            double binSize = (maxValue1 - minValue1) / numBins1;
-            long[] histogram = new long[numBins1];
+            int numBins1 = Math.max(2 * (int)Math.ceil(maxValue1 - minValue1 + 1), MIN_NUM_BINS);
+            double binSize1 = (maxValue1 - minValue1) / numBins1;
+            long[] histogram1 = new long[numBins1];
             int binNum;
             int numBinsLessOne1 = numBins1 - 1;
             double[] data;
@@ -251,9 +252,8 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
                     z = data[col];
                     if (z != noData1) {
                         numCells1++;
-                        binNum = (int)((z - minValue1) / binSize);
-                        if (binNum > numBinsLessOne1) { binNum = numBinsLessOne1; }
-                        histogram[binNum]++;
+                        binNum = getBin(z, minValue1, binSize1, numBinsLessOne1);
+                        histogram1[binNum]++;
                     }
 
                 }
@@ -264,25 +264,12 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
             
             updateProgress("Loop 2 of 3: ", 0);
             
-            double[] cdf = new double[numBins1];
-            cdf[0] = histogram[0]; 
-            for (i = 1; i < numBins1; i++) {
-                cdf[i] = cdf[i - 1] + histogram[i];
-            }
-            
-            for (i = 0; i < numBins1; i++) {
-                cdf[i] = cdf[i] / numCells1;
-            }
- 
-            
             double minValue2 = inputFile2.getMinimumValue();
             double maxValue2 = inputFile2.getMaximumValue();
-            
-            int numBins2 = Math.max(2 * (int)Math.ceil(maxValue2 - minValue2 + 1), 
-                    (int)Math.ceil(Math.pow(rows2 * cols2, 1.0 / 3)));
-            int numBinsLessOne2 = numBins2 - 1;
+            int numBins2 = Math.max(2 * (int)Math.ceil(maxValue2 - minValue2 + 1), MIN_NUM_BINS);
+            double binSize2 = (maxValue2 - minValue2) / numBins2;
             long[] histogram2 = new long[numBins2];
-            double[][] referenceCDF = new double[numBins2][2];
+            int numBinsLessOne2 = numBins2 - 1;
             
             for (row = 0; row < rows2; row++) {
                 data = inputFile2.getRowValues(row);
@@ -290,95 +277,87 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
                     z = data[col];
                     if (z != noData2) {
                         numCells2++;
-                        binNum = (int)((z - minValue2) / binSize);
-                        if (binNum > numBinsLessOne2) { binNum = numBinsLessOne2; }
+                        binNum = getBin(z, minValue2, binSize2, numBinsLessOne2);
                         histogram2[binNum]++;
                     }
 
                 }
                 if (cancelOp) { cancelOperation(); return; }
-                progress = (float) (100f * row / (rows1 - 1));    
+                progress = (float) (100f * row / (rows2 - 1));    
                 updateProgress("Loop 2 of 3: ", (int)progress);
             }
             
-            // convert the reference histogram to a cdf.
-            referenceCDF[0][1] = histogram2[0]; 
-            for (i = 1; i < numBins2; i++) {
-                referenceCDF[i][1] = referenceCDF[i - 1][1] + histogram2[i];
+            if (numCells1 == 0 || numCells2 == 0) {
+                showFeedback("The input and reference images must contain valid (non-NoData) grid cells.");
+                return;
             }
             
-            for (i = 0; i < numBins2; i++) {
-                referenceCDF[i][0] = minValue2 + (i / (float)numBins2) * (maxValue2 - minValue2);
-                referenceCDF[i][1] = referenceCDF[i][1] / numCells2;
+            /* The cdf of each image is treated as piecewise linear, with
+             * breakpoints at the bin edges. cdf1[i] is the proportion of 
+             * the input cells that fall below the lower edge of bin i, and
+             * the reference cdf is stored as (x, p) pairs at the bin edges.
+             */
+            double[] cdf1 = new double[numBins1 + 1];
+            for (i = 0; i < numBins1; i++) {
+                cdf1[i + 1] = cdf1[i] + histogram1[i];
+            }
+            for (i = 0; i <= numBins1; i++) {
+                cdf1[i] = cdf1[i] / numCells1;
             }
             
-            int[] startingVals = new int[11];
-            double pVal = 0;
-            for (i = 0; i < numBins2; i++) {
-                pVal = referenceCDF[i][1];
-                if (pVal < 0.1) {
-                    startingVals[1] = i;
-                }
-                if (pVal < 0.2) {
-                    startingVals[2] = i;
-                }
-                if (pVal < 0.3) {
-                    startingVals[3] = i;
-                }
-                if (pVal < 0.4) {
-                    startingVals[4] = i;
-                }
-                if (pVal < 0.5) {
-                    startingVals[5] = i;
-                }
-                if (pVal < 0.6) {
-                    startingVals[6] = i;
-                }
-                if (pVal < 0.7) {
-                    startingVals[7] = i;
-                }
-                if (pVal < 0.8) {
-                    startingVals[8] = i;
-                }
-                if (pVal < 0.9) {
-                    startingVals[9] = i;
-                }
-                if (pVal <= 1) {
-                    startingVals[10] = i;
+            double[][] referenceCDF = new double[numBins2 + 1][2];
+            for (i = 0; i <= numBins2; i++) {
+                referenceCDF[i][0] = minValue2 + i * binSize2;
+                if (i > 0) {
+                    referenceCDF[i][1] = referenceCDF[i - 1][1] + histogram2[i - 1];
                 }
             }
-                
+            for (i = 0; i <= numBins2; i++) {
+                referenceCDF[i][1] = referenceCDF[i][1] / numCells2;
+            }
+            
             updateProgress("Loop 3 of 3: ", 0);
-            int j = 0;
-            double xVal = 0;
+            int lo, hi, mid;
+            double xVal, pVal;
             double x1, x2, p1, p2;
             for (row = 0; row < rows1; row++) {
                 data = inputFile1.getRowValues(row);
                 for (col = 0; col < cols1; col++) {
                     z = data[col];
                     if (z != noData1) {
-                        binNum = (int)((z - minValue1) / binSize);
-                        if (binNum > numBinsLessOne1) { binNum = numBinsLessOne1; }
-                        pVal = cdf[binNum];
-                        j = (int)(Math.floor(pVal * 10));
-                        for (i = startingVals[j]; i < numBins2; i++) {
-                            if (referenceCDF[i][1] > pVal) {
-                                if (i > 0) {
-                                    x1 = referenceCDF[i - 1][0];
-                                    x2 = referenceCDF[i][0];
-                                    p1 = referenceCDF[i - 1][1];
-                                    p2 = referenceCDF[i][1];
-                                    if (p1 != p2) {
-                                        xVal = x1 + ((x2 - x1) * ((pVal - p1) / (p2 - p1)));
-                                    } else {
-                                        xVal = x1;
-                                    }
+                        // find the cumulative proportion of the input value
+                        binNum = getBin(z, minValue1, binSize1, numBinsLessOne1);
+                        pVal = cdf1[binNum];
+                        if (binSize1 > 0) {
+                            pVal += (cdf1[binNum + 1] - cdf1[binNum]) 
+                                    * Math.min((z - (minValue1 + binNum * binSize1)) / binSize1, 1.0);
+                        } else {
+                            // a constant-valued input is mapped to the reference median
+                            pVal = 0.5;
+                        }
+                        
+                        // find the reference value with the same cumulative proportion
+                        if (pVal <= 0) {
+                            xVal = minValue2;
+                        } else if (pVal >= 1) {
+                            xVal = maxValue2;
+                        } else {
+                            // binary search for the first edge with a cdf >= pVal
+                            lo = 1;
+                            hi = numBins2;
+                            while (lo < hi) {
+                                mid = (lo + hi) / 2;
+                                if (referenceCDF[mid][1] >= pVal) {
+                                    hi = mid;
                                 } else {
-                                    xVal = referenceCDF[i][0];
+                                    lo = mid + 1;
                                 }
-                                break;
-                                
                             }
+                            x1 = referenceCDF[lo - 1][0];
+                            x2 = referenceCDF[lo][0];
+                            p1 = referenceCDF[lo - 1][1];
+                            p2 = referenceCDF[lo][1];
+                            xVal = x1 + ((x2 - x1) * ((pVal - p1) / (p2 - p1)));
                         }
                         
                         outputFile.setValue(row, col, xVal);
@@ -396,6 +375,7 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
             outputFile.addMetadataEntry("Created on " + new Date());
 
             inputFile1.close();
+            inputFile2.close();
             outputFile.close();
 
             // returning a header file string displays the image.
@@ -414,6 +394,20 @@ public class TwoImageHistogramMatching implements WhiteboxPlugin{
         }
     }
     
+    private static int getBin(double z, double minValue, double binSize, int numBinsLessOne) {
+        if (binSize <= 0) {
+            return 0;
+        }
+        int binNum = (int) ((z - minValue) / binSize);
+        if (binNum < 0) {
+            binNum = 0;
+        }
+        if (binNum > numBinsLessOne) {
+            binNum = numBinsLessOne;
+        }
+        return binNum;
+    }
+    
 //    /**
 //     * This method is only used for testing.
 //     * @param args 
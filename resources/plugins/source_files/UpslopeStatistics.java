@@ -0,0 +1,503 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+package plugins;
+
+import java.util.Date;
+import java.util.logging.Level;
+import whitebox.geospatialfiles.WhiteboxRaster;
+import whitebox.interfaces.WhiteboxPlugin;
+import whitebox.interfaces.WhiteboxPluginHost;
+
+/**
+ * This tool calculates the maximum, minimum, mean, or sum of an attribute over
+ * the upslope contributing area of each grid cell, using either D8 or FD8 flow
+ * routing.
+ */
+public class UpslopeStatistics implements WhiteboxPlugin {
+
+    private WhiteboxPluginHost myHost = null;
+    private String[] args;
+
+    // Constants
+    private static final double LnOf2 = 0.693147180559945;
+
+    /**
+     * Used to retrieve the plugin tool's name. This is a short, unique name
+     * containing no spaces.
+     *
+     * @return String containing plugin name.
+     */
+    @Override
+    public String getName() {
+        return "UpslopeStatistics";
+    }
+
+    /**
+     * Used to retrieve the plugin tool's descriptive name. This can be a longer
+     * name (containing spaces) and is used in the interface to list the tool.
+     *
+     * @return String containing the plugin descriptive name.
+     */
+    @Override
+    public String getDescriptiveName() {
+        return "Upslope Statistics";
+    }
+
+    /**
+     * Used to retrieve a short description of what the plugin tool does.
+     *
+     * @return String containing the plugin's description.
+     */
+    @Override
+    public String getToolDescription() {
+        return "Calculates statistics of an attribute over the upslope "
+                + "contributing area of each grid cell.";
+    }
+
+    /**
+     * Used to identify which toolboxes this plugin tool should be listed in.
+     *
+     * @return Array of Strings.
+     */
+    @Override
+    public String[] getToolbox() {
+        String[] ret = {"FlowAccum"};
+        return ret;
+    }
+
+    /**
+     * Sets the WhiteboxPluginHost to which the plugin tool is tied. This is the
+     * class that the plugin will send all feedback messages, progress updates,
+     * and return objects.
+     *
+     * @param host The WhiteboxPluginHost that called the plugin tool.
+     */
+    @Override
+    public void setPluginHost(WhiteboxPluginHost host) {
+        myHost = host;
+    }
+
+    /**
+     * Used to communicate feedback pop-up messages between a plugin tool and
+     * the main Whitebox user-interface.
+     *
+     * @param feedback String containing the text to display.
+     */
+    private void showFeedback(String message) {
+        if (myHost != null) {
+            myHost.showFeedback(message);
+        } else {
+            System.out.println(message);
+        }
+    }
+
+    /**
+     * Used to communicate a return object from a plugin tool to the main
+     * Whitebox user-interface.
+     *
+     * @return Object, such as an output WhiteboxRaster.
+     */
+    private void returnData(Object ret) {
+        if (myHost != null) {
+            myHost.returnData(ret);
+        }
+    }
+
+    private int previousProgress = 0;
+    private String previousProgressLabel = "";
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progressLabel A String to use for the progress label.
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(String progressLabel, int progress) {
+        if (myHost != null && ((progress != previousProgress)
+                || (!progressLabel.equals(previousProgressLabel)))) {
+            myHost.updateProgress(progressLabel, progress);
+        }
+        previousProgress = progress;
+        previousProgressLabel = progressLabel;
+    }
+
+    /**
+     * Used to communicate a progress update between a plugin tool and the main
+     * Whitebox user interface.
+     *
+     * @param progress Float containing the progress value (between 0 and 100).
+     */
+    private void updateProgress(int progress) {
+        if (myHost != null && progress != previousProgress) {
+            myHost.updateProgress(progress);
+        }
+        previousProgress = progress;
+    }
+
+    /**
+     * Sets the arguments (parameters) used by the plugin.
+     *
+     * @param args An array of string arguments.
+     */
+    @Override
+    public void setArgs(String[] args) {
+        this.args = args.clone();
+    }
+
+    private boolean cancelOp = false;
+
+    /**
+     * Used to communicate a cancel operation from the Whitebox GUI.
+     *
+     * @param cancel Set to true if the plugin should be canceled.
+     */
+    @Override
+    public void setCancelOp(boolean cancel) {
+        cancelOp = cancel;
+    }
+
+    private void cancelOperation() {
+        showFeedback("Operation cancelled.");
+        updateProgress("Progress: ", 0);
+    }
+
+    private boolean amIActive = false;
+
+    /**
+     * Used by the Whitebox GUI to tell if this plugin is still running.
+     *
+     * @return a boolean describing whether or not the plugin is actively being
+     * used.
+     */
+    @Override
+    public boolean isActive() {
+        return amIActive;
+    }
+
+    /**
+     * Used to execute this plugin tool.
+     */
+    @Override
+    public void run() {
+        amIActive = true;
+
+        String inputHeader = null;
+        String attributeHeader = null;
+        String outputHeader = null;
+        String statistic = "mean";
+        boolean useFD8 = false;
+        double exponent = 1.1;
+        int row, col, r, c, i;
+        int progress, oldProgress;
+        double z, proportion, totalRelief;
+        int[] dX = new int[]{1, 1, 1, 0, -1, -1, -1, 0};
+        int[] dY = new int[]{-1, 0, 1, 1, 1, 0, -1, -1};
+        double[] inflowingVals = new double[]{16, 32, 64, 128, 1, 2, 4, 8};
+
+        if (args.length <= 0) {
+            showFeedback("Plugin parameters have not been set.");
+            return;
+        }
+
+        for (i = 0; i < args.length; i++) {
+            if (i == 0) {
+                inputHeader = args[i];
+            } else if (i == 1) {
+                attributeHeader = args[i];
+            } else if (i == 2) {
+                outputHeader = args[i];
+            } else if (i == 3) {
+                statistic = args[i].toLowerCase().trim();
+            } else if (i == 4) {
+                useFD8 = args[i].toLowerCase().contains("fd8");
+            } else if (i == 5) {
+                if (!args[i].trim().isEmpty() 
+                        && !args[i].toLowerCase().contains("not specified")) {
+                    exponent = Double.parseDouble(args[i]);
+                }
+            }
+        }
+
+        // check to see that the inputHeader and outputHeader are not null.
+        if ((inputHeader == null) || (attributeHeader == null) 
+                || (outputHeader == null)) {
+            showFeedback("One or more of the input parameters have not been set properly.");
+            return;
+        }
+        
+        boolean isMax = statistic.startsWith("max");
+        boolean isMin = statistic.startsWith("min");
+        boolean isMean = statistic.startsWith("mean") || statistic.startsWith("average");
+        boolean isSum = statistic.startsWith("sum") || statistic.startsWith("total");
+        if (!isMax && !isMin && !isMean && !isSum) {
+            showFeedback("The statistic must be one of 'max', 'min', 'mean', or 'sum'.");
+            return;
+        }
+
+        try {
+            WhiteboxRaster input = new WhiteboxRaster(inputHeader, "r");
+            int rows = input.getNumberRows();
+            int cols = input.getNumberColumns();
+            double noData = input.getNoDataValue();
+
+            WhiteboxRaster attribute = new WhiteboxRaster(attributeHeader, "r");
+            if (attribute.getNumberRows() != rows 
+                    || attribute.getNumberColumns() != cols) {
+                showFeedback("The input files must have the same dimensions, i.e. number of "
+                        + "rows and columns.");
+                input.close();
+                attribute.close();
+                return;
+            }
+            double attNoData = attribute.getNoDataValue();
+            
+            // Read the flow directions (D8) or elevations (FD8) and the 
+            // attribute into memory.
+            double[][] data = new double[rows][cols];
+            double[][] value = new double[rows][cols];
+            double[][] weight = new double[rows][cols];
+            int[][] numInflowing = new int[rows][cols];
+            updateProgress("Reading data:", 0);
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                double[] inRow = input.getRowValues(row);
+                double[] attRow = attribute.getRowValues(row);
+                for (col = 0; col < cols; col++) {
+                    data[row][col] = inRow[col];
+                    z = attRow[col];
+                    if (inRow[col] != noData && z != attNoData) {
+                        value[row][col] = z;
+                        weight[row][col] = 1;
+                    } else if (isMax) {
+                        value[row][col] = Double.NEGATIVE_INFINITY;
+                    } else if (isMin) {
+                        value[row][col] = Double.POSITIVE_INFINITY;
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            attribute.close();
+
+            // Count the number of inflowing neighbours of each cell.
+            updateProgress("Counting inflowing neighbours:", 0);
+            oldProgress = -1;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    z = data[row][col];
+                    if (z == noData) {
+                        continue;
+                    }
+                    for (i = 0; i < 8; i++) {
+                        r = row + dY[i];
+                        c = col + dX[i];
+                        if (r < 0 || r >= rows || c < 0 || c >= cols 
+                                || data[r][c] == noData) {
+                            continue;
+                        }
+                        if ((!useFD8 && data[r][c] == inflowingVals[i])
+                                || (useFD8 && data[r][c] > z)) {
+                            numInflowing[row][col]++;
+                        }
+                    }
+                }
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            // Visit the cells in topological order, i.e. each cell after all 
+            // of the cells that drain to it, passing the running statistic 
+            // downslope. Under FD8, the sum and the weight are split among the 
+            // downslope neighbours in proportion to the flow that each 
+            // receives, while the max and min are passed to any neighbour that 
+            // receives flow.
+            int[] stack = new int[rows * cols];
+            int top = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (data[row][col] != noData && numInflowing[row][col] == 0) {
+                        stack[top++] = row * cols + col;
+                    }
+                }
+            }
+            long numSolved = 0;
+            long numValid = 0;
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    if (data[row][col] != noData) {
+                        numValid++;
+                    }
+                }
+            }
+            int[] receivers = new int[8];
+            double[] proportions = new double[8];
+            updateProgress("Accumulating:", 0);
+            oldProgress = -1;
+            while (top > 0) {
+                int cell = stack[--top];
+                row = cell / cols;
+                col = cell % cols;
+                z = data[row][col];
+                int numReceivers = 0;
+                if (!useFD8) {
+                    if (z > 0) {
+                        i = (int) (Math.log(z) / LnOf2);
+                        r = row + dY[i];
+                        c = col + dX[i];
+                        if (r >= 0 && r < rows && c >= 0 && c < cols 
+                                && data[r][c] != noData) {
+                            receivers[0] = r * cols + c;
+                            proportions[0] = 1.0;
+                            numReceivers = 1;
+                        }
+                    }
+                } else {
+                    totalRelief = 0;
+                    for (i = 0; i < 8; i++) {
+                        r = row + dY[i];
+                        c = col + dX[i];
+                        if (r >= 0 && r < rows && c >= 0 && c < cols 
+                                && data[r][c] != noData && data[r][c] < z) {
+                            receivers[numReceivers] = r * cols + c;
+                            proportions[numReceivers] = Math.pow(z - data[r][c], exponent);
+                            totalRelief += proportions[numReceivers];
+                            numReceivers++;
+                        }
+                    }
+                    for (i = 0; i < numReceivers; i++) {
+                        proportions[i] /= totalRelief;
+                    }
+                }
+                for (i = 0; i < numReceivers; i++) {
+                    r = receivers[i] / cols;
+                    c = receivers[i] % cols;
+                    if (isMax) {
+                        if (value[row][col] > value[r][c]) {
+                            value[r][c] = value[row][col];
+                        }
+                        weight[r][c] += weight[row][col];
+                    } else if (isMin) {
+                        if (value[row][col] < value[r][c]) {
+                            value[r][c] = value[row][col];
+                        }
+                        weight[r][c] += weight[row][col];
+                    } else {
+                        value[r][c] += value[row][col] * proportions[i];
+                        weight[r][c] += weight[row][col] * proportions[i];
+                    }
+                    numInflowing[r][c]--;
+                    if (numInflowing[r][c] == 0) {
+                        stack[top++] = receivers[i];
+                    }
+                }
+                numSolved++;
+                progress = (int) (100f * numSolved / numValid);
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+            stack = null;
+            
+            if (numSolved < numValid) {
+                String msg = "Warning: " + (numValid - numSolved) + " grid cells could "
+                        + "not be reached because the flow pointer contains loops. These "
+                        + "cells have been assigned NoData.";
+                showFeedback(msg);
+                myHost.logMessage(Level.WARNING, msg);
+            }
+
+            WhiteboxRaster output = new WhiteboxRaster(outputHeader, "rw",
+                    inputHeader, WhiteboxRaster.DataType.FLOAT, noData);
+            output.setPreferredPalette(attribute.getPreferredPalette());
+            output.setDataScale(WhiteboxRaster.DataScale.CONTINUOUS);
+            output.setZUnits(attribute.getZUnits());
+            
+            updateProgress("Saving data:", 0);
+            oldProgress = -1;
+            double[] outRow = new double[cols];
+            for (row = 0; row < rows; row++) {
+                for (col = 0; col < cols; col++) {
+                    // weight is zero where no upslope cell has an attribute 
+                    // value, and numInflowing is greater than zero where the 
+                    // cell was never reached.
+                    if (data[row][col] == noData || weight[row][col] <= 0 
+                            || numInflowing[row][col] > 0) {
+                        outRow[col] = noData;
+                    } else if (isMean) {
+                        outRow[col] = value[row][col] / weight[row][col];
+                    } else {
+                        outRow[col] = value[row][col];
+                    }
+                }
+                output.setRowValues(row, outRow);
+                progress = (int) (100f * row / (rows - 1));
+                if (progress > oldProgress) {
+                    updateProgress(progress);
+                    oldProgress = progress;
+                    if (cancelOp) {
+                        cancelOperation();
+                        return;
+                    }
+                }
+            }
+
+            output.addMetadataEntry("Created by the "
+                    + getDescriptiveName() + " tool.");
+            output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Attribute: " + attribute.getShortHeaderFile());
+            output.addMetadataEntry("Statistic: " + (isMax ? "max" : isMin ? "min" : isMean ? "mean" : "sum"));
+            output.addMetadataEntry("Flow routing: " + (useFD8 ? "FD8 (exponent = " + exponent + ")" : "D8"));
+
+            input.close();
+            output.close();
+
+            // returning a header file string displays the image.
+            returnData(outputHeader);
+
+        } catch (OutOfMemoryError oe) {
+            myHost.showFeedback("An out-of-memory error has occurred during operation.");
+        } catch (Exception e) {
+            myHost.showFeedback("An error has occurred during operation. See log file for details.");
+            myHost.logException("Error in " + getDescriptiveName(), e);
+        } finally {
+            updateProgress("Progress: ", 0);
+            // tells the main application that this process is completed.
+            amIActive = false;
+            myHost.pluginComplete();
+        }
+    }
+}
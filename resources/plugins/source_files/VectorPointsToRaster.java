@@ -17,6 +17,8 @@
 package plugins;
 
 import java.util.Date;
+import java.util.HashMap;
+import java.util.Map;
 import whitebox.geospatialfiles.ShapeFile;
 import whitebox.geospatialfiles.WhiteboxRaster;
 import whitebox.geospatialfiles.WhiteboxRasterBase;
@@ -218,6 +220,7 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
         DataType dataType = WhiteboxRasterBase.DataType.INTEGER;
         Object[] data;
         boolean useRecID = false;
+        int minPoints = 1;
         
         if (args.length <= 0) {
             showFeedback("Plugin parameters have not been set.");
@@ -237,6 +240,10 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
             cellSize = Double.parseDouble(args[5]);
         }
         baseFileHeader = args[6];
+        if (args.length > 7 && !args[7].trim().isEmpty()
+                && !args[7].toLowerCase().contains("not specified")) {
+            minPoints = Integer.parseInt(args[7].trim());
+        }
         
         // check to see that the inputHeader and outputHeader are not null.
         if ((inputFile == null) || (outputHeader == null)) {
@@ -285,6 +292,12 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
                 useRecID = true;
             }
             
+            if (assignmentType.equals("mean") || assignmentType.equals("standard deviation")) {
+                dataType = WhiteboxRasterBase.DataType.FLOAT;
+            } else if (assignmentType.equals("count")) {
+                dataType = WhiteboxRasterBase.DataType.INTEGER;
+            }
+            
             // initialize the output raster
             WhiteboxRaster output;
             if ((cellSize > 0) || 
@@ -314,71 +327,24 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
                 cols = output.getNumberColumns();
             }
             
+            // The points are binned into the grid cells that contain them. The
+            // statistics are held in a hash map keyed on the cell index, so that
+            // memory is only used for the cells that contain points.
+            HashMap<Long, CellStatistics> cells = new HashMap<>();
             double[][] geometry;
-            if (assignmentType.equals("minimum")) {
-                for (ShapeFileRecord record : input.records) {
-                    data = reader.nextRecord();
-                    geometry = getXYFromShapefileRecord(record);
-                    for (int i = 0; i < geometry.length; i++) {
-                        xCoord = geometry[i][0];
-                        yCoord = geometry[i][1];
-                        row = output.getRowFromYCoordinate(yCoord);
-                        col = output.getColumnFromXCoordinate(xCoord);
-                        if (row < rows && row >= 0 && col < cols && col >= 0) {
-                            // find the row and column number
-                            row = output.getRowFromYCoordinate(yCoord);
-                            col = output.getColumnFromXCoordinate(xCoord);
-                            value = Double.valueOf(data[fieldNum].toString());
-                            z = output.getValue(row, col);
-                            if (z == backgroundValue || z < value) {
-                                output.setValue(row, col, value);
-                            }
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    
-                    progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
-                    updateProgress(progress);
-                }
-                
-            } else if (assignmentType.equals("maximum")) {
-                for (ShapeFileRecord record : input.records) {
-                    data = reader.nextRecord();
-                    geometry = getXYFromShapefileRecord(record);
-                    for (int i = 0; i < geometry.length; i++) {
-                        xCoord = geometry[i][0];
-                        yCoord = geometry[i][1];
-                        row = output.getRowFromYCoordinate(yCoord);
-                        col = output.getColumnFromXCoordinate(xCoord);
-                        if (row < rows && row >= 0 && col < cols && col >= 0) {
-                            // find the row and column number
-                            row = output.getRowFromYCoordinate(yCoord);
-                            col = output.getColumnFromXCoordinate(xCoord);
-                            if (!useRecID) {
-                                value = Double.valueOf(data[fieldNum].toString());
-                            } else {
-                                value = record.getRecordNumber();
-                            }
-                            z = output.getValue(row, col);
-                            if (z == backgroundValue || z > value) {
-                                output.setValue(row, col, value);
-                            }
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    
-                    progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
-                    updateProgress(progress);
+            for (ShapeFileRecord record : input.records) {
+                data = reader.nextRecord();
+                boolean hasValue = true;
+                value = 0;
+                if (useRecID) {
+                    value = record.getRecordNumber();
+                } else if (data[fieldNum] != null) {
+                    value = Double.valueOf(data[fieldNum].toString());
+                } else if (!assignmentType.equals("count")) {
+                    // points with a null field value are ignored
+                    hasValue = false;
                 }
-            } else if (assignmentType.equals("sum")) {
-                for (ShapeFileRecord record : input.records) {
-                    data = reader.nextRecord();
+                if (hasValue) {
                     geometry = getXYFromShapefileRecord(record);
                     for (int i = 0; i < geometry.length; i++) {
                         xCoord = geometry[i][0];
@@ -386,100 +352,43 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
                         row = output.getRowFromYCoordinate(yCoord);
                         col = output.getColumnFromXCoordinate(xCoord);
                         if (row < rows && row >= 0 && col < cols && col >= 0) {
-                            // find the row and column number
-                            row = output.getRowFromYCoordinate(yCoord);
-                            col = output.getColumnFromXCoordinate(xCoord);
-                            if (!useRecID) {
-                                value = Double.valueOf(data[fieldNum].toString());
-                            } else {
-                                value = record.getRecordNumber();
-                            }
-                            z = output.getValue(row, col);
-                            if (z == backgroundValue) {
-                                output.setValue(row, col, value);
-                            } else {
-                                output.setValue(row, col, value + z);
+                            long key = (long) row * cols + col;
+                            CellStatistics stats = cells.get(key);
+                            if (stats == null) {
+                                stats = new CellStatistics();
+                                cells.put(key, stats);
                             }
+                            stats.add(value);
                         }
                     }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    
-                    progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
-                    updateProgress(progress);
                 }
-            } else if (assignmentType.equals("first")) {
-                for (ShapeFileRecord record : input.records) {
-                    data = reader.nextRecord();
-                    geometry = getXYFromShapefileRecord(record);
-                    for (int i = 0; i < geometry.length; i++) {
-                        xCoord = geometry[i][0];
-                        yCoord = geometry[i][1];
-                        row = output.getRowFromYCoordinate(yCoord);
-                        col = output.getColumnFromXCoordinate(xCoord);
-                        if (row < rows && row >= 0 && col < cols && col >= 0) {
-                            // find the row and column number
-                            row = output.getRowFromYCoordinate(yCoord);
-                            col = output.getColumnFromXCoordinate(xCoord);
-                            if (!useRecID) {
-                                value = Double.valueOf(data[fieldNum].toString());
-                            } else {
-                                value = record.getRecordNumber();
-                            }
-                            z = output.getValue(row, col);
-                            if (z == backgroundValue) {
-                                output.setValue(row, col, value);
-                            }
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    
-                    progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
-                    updateProgress(progress);
+                if (cancelOp) {
+                    cancelOperation();
+                    return;
                 }
-            } else if (assignmentType.equals("last")) {
-                for (ShapeFileRecord record : input.records) {
-                    data = reader.nextRecord();
-                    geometry = getXYFromShapefileRecord(record);
-                    for (int i = 0; i < geometry.length; i++) {
-                        xCoord = geometry[i][0];
-                        yCoord = geometry[i][1];
-                        row = output.getRowFromYCoordinate(yCoord);
-                        col = output.getColumnFromXCoordinate(xCoord);
-                        if (row < rows && row >= 0 && col < cols && col >= 0) {
-                            // find the row and column number
-                            row = output.getRowFromYCoordinate(yCoord);
-                            col = output.getColumnFromXCoordinate(xCoord);
-                            if (!useRecID) {
-                                value = Double.valueOf(data[fieldNum].toString());
-                            } else {
-                                value = record.getRecordNumber();
-                            }
-                            output.setValue(row, col, value);
-                        }
-                    }
-                    if (cancelOp) {
-                        cancelOperation();
-                        return;
-                    }
-                    
-                    progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
-                    updateProgress(progress);
+
+                progress = (int)((100.0 * record.getRecordNumber()) / input.getNumberOfRecords());
+                updateProgress(progress);
+            }
+
+            for (Map.Entry<Long, CellStatistics> entry : cells.entrySet()) {
+                row = (int) (entry.getKey() / cols);
+                col = (int) (entry.getKey() % cols);
+                CellStatistics stats = entry.getValue();
+                if (stats.n >= minPoints) {
+                    output.setValue(row, col, stats.getValue(assignmentType));
+                } else {
+                    output.setValue(row, col, noData);
                 }
-            } else if (assignmentType.equals("mean")) {
-                
-            } else if (assignmentType.equals("range")) {
-                
             }
             
             output.addMetadataEntry("Created by the "
                     + getDescriptiveName() + " tool.");
             output.addMetadataEntry("Created on " + new Date());
+            output.addMetadataEntry("Assignment operation: " + assignmentType);
+            if (minPoints > 1) {
+                output.addMetadataEntry("Minimum number of points: " + minPoints);
+            }
             
             output.flush();
             output.close();
@@ -500,6 +409,62 @@ public class VectorPointsToRaster implements WhiteboxPlugin {
         }
     }
     
+    /**
+     * The running statistics of the values of the points within a grid cell.
+     */
+    private static class CellStatistics {
+
+        int n = 0;
+        double first, last, min, max, sum;
+        double mean = 0;
+        double m2 = 0;
+
+        void add(double value) {
+            if (n == 0) {
+                first = value;
+                min = value;
+                max = value;
+            }
+            n++;
+            last = value;
+            sum += value;
+            if (value < min) {
+                min = value;
+            }
+            if (value > max) {
+                max = value;
+            }
+            // Welford's method, which is less prone to round-off than
+            // accumulating the sum of squares
+            double delta = value - mean;
+            mean += delta / n;
+            m2 += delta * (value - mean);
+        }
+
+        double getValue(String assignmentType) {
+            switch (assignmentType) {
+                case "minimum":
+                    return min;
+                case "maximum":
+                    return max;
+                case "sum":
+                    return sum;
+                case "first":
+                    return first;
+                case "mean":
+                    return mean;
+                case "count":
+                    return n;
+                case "standard deviation":
+                    return Math.sqrt(m2 / n);
+                case "range":
+                    return max - min;
+                default: // last
+                    return last;
+            }
+        }
+    }
+
     private double[][] getXYFromShapefileRecord(ShapeFileRecord record) {
         double[][] ret;
         ShapeType shapeType = record.getShapeType();
@@ -0,0 +1,274 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Tests for the cost-distance tools. The inputs are synthetic cost surfaces
+// with a single source cell and a single destination cell, or uniform cost
+// surfaces with two sources, created in a temporary directory by the tests.
+//
+// The tests are run against the compiled plugins with 'python build.py
+// runtests'.
+
+def outputDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+
+List<String> returned = []
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> false },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+// Creates a cost surface of the given size with smoothly varying costs and
+// three thick NoData barriers with gaps at alternate ends, so that the
+// least-cost path zigzags across the grid, together with
+// a source raster with a source cell near the top-left corner and a
+// destination raster with a destination cell near the bottom-right corner.
+def createInputs = { String prefix, int size ->
+    double noData = -32768.0
+    WhiteboxRaster cost = new WhiteboxRaster(prefix + "_cost.dep", (double)size, 0.0, (double)size, 0.0,
+        size, size, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, noData)
+    WhiteboxRaster source = new WhiteboxRaster(prefix + "_source.dep", "rw", prefix + "_cost.dep", DataType.FLOAT, 0.0)
+    WhiteboxRaster destination = new WhiteboxRaster(prefix + "_destination.dep", "rw", prefix + "_cost.dep", DataType.FLOAT, 0.0)
+    double[] data = new double[size]
+    for (int row = 0; row < size; row++) {
+        for (int col = 0; col < size; col++) {
+            double s = Math.sin(col * 20.0 / size) * Math.cos(row * 14.0 / size)
+            data[col] = 1 + 4 * s * s
+            int wall = (int)(row * 4 / size)
+            boolean inWall = (row * 4) % size < size.intdiv(10) && wall > 0
+            if (inWall && (wall % 2 == 1 ? col > size.intdiv(4) : col < size - size.intdiv(4))) {
+                data[col] = noData
+            }
+        }
+        cost.setRowValues(row, data)
+    }
+    source.setValue(5, 5, 1)
+    destination.setValue(size - 6, size - 6, 1)
+    cost.close()
+    source.close()
+    destination.close()
+}
+
+// runs the cost accumulation and returns the accumulated cost at the
+// destination and the run time in seconds
+def accumulateCost = { String prefix, String name, String pyramidLevels, String corridorWidth, int size ->
+    String outputFile = outputDir + name + ".dep"
+    long start = System.nanoTime()
+    runPlugin("CostAccumulation", [prefix + "_source.dep", prefix + "_cost.dep", outputFile,
+        outputDir + name + "_backlink.dep", "not specified", "not specified", prefix + "_destination.dep",
+        pyramidLevels, corridorWidth])
+    double seconds = (System.nanoTime() - start) / 1e9
+    WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+    double destinationCost = output.getValue(size - 6, size - 6)
+    List<String> metadata = output.getMetadata()
+    output.close()
+    [cost: destinationCost, seconds: seconds, metadata: metadata]
+}
+
+int numTests = 0
+int numFailed = 0
+def check = { String name, Closure test ->
+    numTests++
+    String failure
+    try {
+        failure = test()
+    } catch (Throwable e) {
+        failure = "unexpected exception: " + e
+    }
+    if (failure == null) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    " + failure
+    }
+}
+
+// The least-cost path found by pyramidal processing must cost no less than
+// the exact least-cost path, since it is restricted to a corridor, and only a
+// little more. The output must be flagged as approximate.
+check("costaccumulation_pyramid_accuracy", {
+    int size = 160
+    String prefix = outputDir + "medium"
+    createInputs(prefix, size)
+    def exact = accumulateCost(prefix, "medium_exact", "not specified", "not specified", size)
+    def pyramid = accumulateCost(prefix, "medium_pyramid", "3", "4", size)
+    if (pyramid.cost < exact.cost - 0.001 || pyramid.cost > exact.cost * 1.01) {
+        return "the pyramidal path costs " + pyramid.cost + " but the exact path costs " + exact.cost
+    }
+    if (exact.metadata.any { it.contains("Approximate") }) {
+        return "the exact output is flagged as approximate"
+    }
+    if (!pyramid.metadata.any { it.contains("Approximate") }) {
+        return "the pyramidal output is not flagged as approximate"
+    }
+    return null
+})
+
+// On a large grid, pyramidal processing must be several times faster.
+check("costaccumulation_pyramid_speed", {
+    int size = 1000
+    String prefix = outputDir + "large"
+    createInputs(prefix, size)
+    def exact = accumulateCost(prefix, "large_exact", "not specified", "not specified", size)
+    def pyramid = accumulateCost(prefix, "large_pyramid", "4", "4", size)
+    println "    exact: " + exact.seconds + " s, pyramidal: " + pyramid.seconds + " s"
+    if (pyramid.seconds * 3 > exact.seconds) {
+        return "the pyramidal run took " + pyramid.seconds + " s and the exact run took " + exact.seconds + " s"
+    }
+    if (pyramid.cost < exact.cost - 0.001 || pyramid.cost > exact.cost * 1.01) {
+        return "the pyramidal path costs " + pyramid.cost + " but the exact path costs " + exact.cost
+    }
+    return null
+})
+
+// returns the values of a raster in row-major order, with null for NoData
+def readValues = { String file ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+    List<Double> values = []
+    for (int row = 0; row < raster.getNumberRows(); row++) {
+        for (double z : raster.getRowValues(row)) {
+            values << (z == raster.getNoDataValue() ? null : z)
+        }
+    }
+    raster.close()
+    values
+}
+
+// compares two lists of raster values, which must have NoData in the same cells
+def compareValues = { List<Double> actual, List<Double> expected, String what ->
+    for (int i = 0; i < expected.size(); i++) {
+        if ((actual[i] == null) != (expected[i] == null) ||
+                (expected[i] != null && Math.abs(actual[i] - expected[i]) > 1e-6 * Math.max(1.0, expected[i]))) {
+            return "cell " + i + " of " + what + " is " + actual[i] + " rather than " + expected[i]
+        }
+    }
+    return null
+}
+
+// With costs per unit distance on a grid of unit cells, the accumulated cost
+// surface is the exact cost accumulation. Barriers in a barrier raster are
+// the same as NoData walls in the cost surface, and are NoData in the output.
+check("accumulated_cost_barriers", {
+    int size = 80
+    String prefix = outputDir + "barriers"
+    createInputs(prefix, size)
+    runPlugin("CostAccumulation", [prefix + "_source.dep", prefix + "_cost.dep", outputDir + "barriers_exact.dep",
+        outputDir + "barriers_backlink.dep", "not specified", "not specified", "not specified",
+        "not specified", "not specified"])
+    runPlugin("AccumulatedCostSurface", [prefix + "_cost.dep", prefix + "_source.dep", "not specified",
+        outputDir + "barriers_nodata.dep", "not specified", "true"])
+
+    // the same walls as a barrier raster, over a cost surface without NoData
+    WhiteboxRaster cost = new WhiteboxRaster(prefix + "_cost.dep", "r")
+    WhiteboxRaster filled = new WhiteboxRaster(prefix + "_filled.dep", "rw", prefix + "_cost.dep", DataType.FLOAT, 0.0)
+    WhiteboxRaster barriers = new WhiteboxRaster(prefix + "_barriers.dep", "rw", prefix + "_cost.dep", DataType.FLOAT, 0.0)
+    for (int row = 0; row < size; row++) {
+        double[] data = cost.getRowValues(row)
+        double[] walls = new double[size]
+        for (int col = 0; col < size; col++) {
+            if (data[col] == cost.getNoDataValue()) {
+                data[col] = 1.0
+                walls[col] = 1.0
+            }
+        }
+        filled.setRowValues(row, data)
+        barriers.setRowValues(row, walls)
+    }
+    cost.close()
+    filled.close()
+    barriers.close()
+    runPlugin("AccumulatedCostSurface", [prefix + "_filled.dep", prefix + "_source.dep", prefix + "_barriers.dep",
+        outputDir + "barriers_raster.dep", "not specified", "true"])
+
+    List<Double> expected = readValues(outputDir + "barriers_exact.dep")
+    return compareValues(readValues(outputDir + "barriers_nodata.dep"), expected, "the surface with NoData walls") ?:
+        compareValues(readValues(outputDir + "barriers_raster.dep"), expected, "the surface with a barrier raster")
+})
+
+// On a uniform cost surface with two sources, the accumulated cost is the
+// cost times the chessboard distance to the nearer source when the costs are
+// per cell, and times the octile distance in map units when they are per unit
+// distance. With a maximum cost, the cells that cost more are NoData and the
+// others are unchanged.
+check("accumulated_cost_distance_and_maximum", {
+    int rows = 30
+    int cols = 40
+    double cellSize = 2.5
+    double costValue = 3.0
+    String prefix = outputDir + "uniform"
+    List<List<Integer>> sources = [[4, 6], [22, 31]]
+    WhiteboxRaster cost = new WhiteboxRaster(prefix + "_cost.dep", rows * cellSize, 0.0, cols * cellSize, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, costValue, -32768.0)
+    WhiteboxRaster source = new WhiteboxRaster(prefix + "_source.dep", "rw", prefix + "_cost.dep", DataType.FLOAT, 0.0)
+    for (int row = 0; row < rows; row++) {
+        cost.setRowValues(row, ([costValue] * cols) as double[])
+        source.setRowValues(row, new double[cols])
+    }
+    sources.each { source.setValue(it[0], it[1], 1) }
+    cost.close()
+    source.close()
+
+    double maxCost = 40.0
+    for (boolean perUnitDistance : [false, true]) {
+        String name = outputDir + "uniform_" + perUnitDistance
+        runPlugin("AccumulatedCostSurface", [prefix + "_cost.dep", prefix + "_source.dep", "not specified",
+            name + ".dep", "not specified", perUnitDistance.toString()])
+        runPlugin("AccumulatedCostSurface", [prefix + "_cost.dep", prefix + "_source.dep", "not specified",
+            name + "_max.dep", maxCost.toString(), perUnitDistance.toString()])
+        List<Double> expected = []
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                expected << sources.collect {
+                    int dy = Math.abs(row - it[0])
+                    int dx = Math.abs(col - it[1])
+                    perUnitDistance ? costValue * cellSize * (Math.max(dx, dy) + (Math.sqrt(2) - 1) * Math.min(dx, dy))
+                        : costValue * Math.max(dx, dy)
+                }.min()
+            }
+        }
+        String failure = compareValues(readValues(name + ".dep"), expected,
+            "the surface with costs per " + (perUnitDistance ? "unit distance" : "cell"))
+        if (failure == null) {
+            failure = compareValues(readValues(name + "_max.dep"), expected.collect { it <= maxCost ? it : null },
+                "the surface with a maximum cost")
+        }
+        if (failure != null) {
+            return failure
+        }
+    }
+    return null
+})
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
@@ -0,0 +1,801 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import java.nio.file.StandardCopyOption
+import whitebox.algorithms.RasterResampler
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRaster.EdgeMode
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Tests for the filter, contrast stretch, colour and resampling tools. Most
+// run a tool on the small rasters in tests/data and compare its output with
+// the expected raster in tests/expected; the rest check values directly.
+//
+// The tests are run against the compiled plugins with 'python build.py 
+// runtests'. After an intended change to a tool's output, the expected 
+// rasters can be recreated by running this script with the argument 
+// 'regenerate'. To add a test, add an entry to the list of tests below and 
+// regenerate.
+
+def testDir = "tests" + File.separator
+def dataDir = testDir + "data" + File.separator
+def expectedDir = testDir + "expected" + File.separator
+boolean regenerate = args.length > 0 && args[0].toLowerCase().equals("regenerate")
+double tolerance = 0.0001
+
+def inputs = ["surface", "nodata", "row", "rows3", "uniform"]
+
+// name prefix, plugin, and the plugin arguments following the input and 
+// output files
+def filters = [
+    ["sobel", "FilterSobel", ["3 x 3", "false"]],
+    ["sobel5", "FilterSobel", ["5 x 5", "false"]],
+    ["sobel_replicate", "FilterSobel", ["3 x 3", "replicate"]],
+    ["sobel_wrap", "FilterSobel", ["3 x 3", "wrap"]],
+    ["prewitt", "FilterPrewitt", ["false"]],
+    ["laplacian", "FilterLaplacian", ["3 x 3 (1)"]],
+    ["userkernel", "FilterUserKernel", [dataDir + "kernel_directional.txt", "false", "0", "false"]]
+]
+
+// contrast stretches, which are only compared for the inputs that vary
+// because a uniform raster can't be stretched: name prefix, tool, whether
+// the tool is a Groovy script, and the arguments following the input and
+// output files
+def stretches = [
+    ["sigmoidal", "SigmoidalStretch", false, ["0.5", "10"]],
+    ["piecewise", "PiecewiseContrastStretch", true, ["0,0; 10,0.2; 20,0.9; 30.5,1"]],
+    ["minmax", "MinMaxContrastStretch", false, ["5", "25", "16"]],
+    ["minmax_unit", "MinMaxContrastStretch", false, ["5", "25", "5", "0", "1", "Integer"]],
+    ["minmax_byte", "MinMaxContrastStretch", false, ["5", "25", "255", "0", "254", "Byte"]],
+    ["stdev", "StandardDeviationContrastStretch", false, ["2", "16"]],
+    ["stdev1", "StandardDeviationContrastStretch", false, ["1", "16", "-1", "1", "Float"]]
+]
+// the tails clipped by the Percentage Contrast Stretch, which writes its
+// output beside its input
+def percentTails = ["both", "lower", "upper"]
+def stretchInputs = ["surface", "nodata", "row", "rows3"]
+
+// name, tool and the resampling method and NoData policy
+def resamplings = [
+    ["resample_bilinear", "Resample", ["bilinear", "renormalize"]],
+    ["resample_cubic", "Resample", ["cubic convolution", "renormalize"]],
+    ["resample_cubic_invalidate", "Resample", ["cubic convolution", "invalidate"]],
+    ["resample_lanczos", "Resample", ["lanczos", "renormalize"]],
+    ["mosaic_cubic", "Mosaic", ["cubic convolution", "renormalize"]]
+]
+
+List<String> returned = []
+List<String> feedback = []
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; feedback << a[0].toString(); 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> false },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+// runs one of the Groovy script tools in resources/plugins/Scripts
+def runScript = { String name, List<String> scriptArgs ->
+    Binding binding = new Binding()
+    binding.setVariable("pluginHost", host)
+    binding.setVariable("args", scriptArgs as String[])
+    new GroovyShell(binding).evaluate(new File("resources/plugins/Scripts/" + name + ".groovy").text, name + "Script")
+}
+
+def isAllZero = { String file ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+    boolean ret = true
+    for (int row = 0; row < raster.getNumberRows(); row++) {
+        for (double z : raster.getRowValues(row)) {
+            if (z != raster.getNoDataValue() && Math.abs(z) > tolerance) {
+                ret = false
+            }
+        }
+    }
+    raster.close()
+    return ret
+}
+
+def outputDir = regenerate ? expectedDir : Files.createTempDirectory("whitebox_tests").toString() + File.separator
+int numTests = 0
+int numFailed = 0
+
+// Filters on every input. A uniform raster has no edges, so the
+// edge-detection filters must output zero everywhere.
+filters.each { filter ->
+    inputs.each { input ->
+        String name = filter[0] + "_" + input
+        String outputFile = outputDir + name + ".dep"
+        numTests++
+        returned.clear()
+        runPlugin(filter[1], [dataDir + input + ".dep", outputFile] + filter[2])
+        if (regenerate) {
+            println "Regenerated " + name
+            return
+        }
+        
+        List<String> failures = []
+        if (!new File(outputFile).exists()) {
+            failures << "no output was created"
+        } else {
+            returned.clear()
+            runPlugin("CompareRasters", [expectedDir + name + ".dep", outputFile, tolerance.toString(), "5"])
+            String report = returned.isEmpty() ? "" : returned[0]
+            if (!report.contains("Result:\tIDENTICAL")) {
+                failures << "the output differs from the expected raster\n" + report
+            }
+            if (input == "uniform" && !isAllZero(outputFile)) {
+                failures << "a uniform raster did not produce all zeros"
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+// counts a test of a tool's output against the expected raster of the same
+// name, or reports the output as regenerated
+def compareWithExpected = { String name, String outputFile ->
+    numTests++
+    if (regenerate) {
+        println "Regenerated " + name
+        return
+    }
+    String failure = null
+    if (!new File(outputFile).exists()) {
+        failure = "no output was created"
+    } else {
+        returned.clear()
+        runPlugin("CompareRasters", [expectedDir + name + ".dep", outputFile, tolerance.toString(), "5"])
+        String report = returned.isEmpty() ? "" : returned[0]
+        if (!report.contains("Result:\tIDENTICAL")) {
+            failure = "the output differs from the expected raster\n" + report
+        }
+    }
+    if (failure == null) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    " + failure.replace("\n", "\n    ")
+    }
+}
+
+// The stretches read from copies of their inputs because computing an
+// image's statistics writes a .wstat file beside it.
+def stretchDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+def copyRaster = { String fromFile, String toFile ->
+    for (String ext : [".dep", ".tas"]) {
+        Files.copy(new File(fromFile.replace(".dep", ext)).toPath(), new File(toFile.replace(".dep", ext)).toPath())
+    }
+}
+stretchInputs.each { input -> copyRaster(dataDir + input + ".dep", stretchDir + input + ".dep") }
+
+// Contrast stretches on every input that varies.
+stretches.each { stretch ->
+    stretchInputs.each { input ->
+        String name = stretch[0] + "_" + input
+        String outputFile = outputDir + name + ".dep"
+        List<String> toolArgs = [stretchDir + input + ".dep", outputFile] + stretch[3]
+        if (stretch[2]) {
+            runScript(stretch[1], toolArgs)
+        } else {
+            runPlugin(stretch[1], toolArgs)
+        }
+        compareWithExpected(name, outputFile)
+    }
+}
+
+// The Percentage Contrast Stretch, clipping each tail in turn.
+percentTails.each { tails ->
+    stretchInputs.each { input ->
+        String name = "percent_" + tails + "_" + input
+        String inputFile = stretchDir + name + ".dep"
+        copyRaster(dataDir + input + ".dep", inputFile)
+        runPlugin("PercentageContrastStretch", [inputFile, "stretched", "10", tails, "16"])
+        String outputFile = inputFile.replace(".dep", " stretched.dep")
+        if (regenerate) {
+            for (String ext : [".dep", ".tas"]) {
+                Files.move(new File(outputFile.replace(".dep", ext)).toPath(),
+                    new File(expectedDir + name + ext).toPath(), StandardCopyOption.REPLACE_EXISTING)
+            }
+        }
+        compareWithExpected(name, outputFile)
+    }
+}
+
+// The surface with NoData cells is resampled onto a grid of half the cell
+// size, which the Resample tool requires to exist, and mosaicked with the
+// complete surface, which fills its NoData cells.
+resamplings.each { resampling ->
+    String name = resampling[0]
+    String outputFile = outputDir + name + ".dep"
+    if (resampling[1] == "Resample") {
+        new WhiteboxRaster(outputFile, 6.0, 0.0, 7.0, 0.0, 12, 14, DataScale.CONTINUOUS,
+            DataType.FLOAT, -32768.0, -32768.0).close()
+        runPlugin("Resample", [dataDir + "nodata.dep", outputFile] + resampling[2])
+    } else {
+        runPlugin("Mosaic", [dataDir + "nodata.dep;" + dataDir + "surface.dep", outputFile] + resampling[2])
+    }
+    compareWithExpected(name, outputFile)
+}
+
+// checks that do not compare outputs with expected rasters
+def check = { String name, Closure test ->
+    numTests++
+    String failure
+    try {
+        failure = test()
+    } catch (Exception e) {
+        failure = "unexpected exception: " + e
+    }
+    if (failure == null) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    " + failure
+    }
+}
+
+if (!regenerate) {
+    // Kernel files read by the User-Defined Kernel Filter.
+    Class kernelFilter = Class.forName("plugins.FilterUserKernel")
+    
+    check("kernel_normalize", {
+        double[][] kernel = kernelFilter.readKernel(dataDir + "kernel_smooth.txt")
+        kernelFilter.normalizeKernel(kernel)
+        double sum = kernel.flatten().sum()
+        if (Math.abs(sum - 1) > tolerance) {
+            return "the normalized weights sum to " + sum
+        }
+        if (Math.abs(kernel[1][1] - 0.25) > tolerance || Math.abs(kernel[0][0] - 0.0625) > tolerance) {
+            return "the normalized weights are not proportional to the original weights"
+        }
+        return null
+    })
+    
+    check("kernel_normalize_zero_sum", {
+        double[][] kernel = kernelFilter.readKernel(dataDir + "kernel_directional.txt")
+        try {
+            kernelFilter.normalizeKernel(kernel)
+            return "a kernel whose weights sum to zero was normalized"
+        } catch (IllegalArgumentException e) {
+            return null
+        }
+    })
+    
+    check("kernel_even_dimensions", {
+        try {
+            kernelFilter.readKernel(dataDir + "kernel_even.txt")
+            return "a kernel with an even number of columns was accepted"
+        } catch (IllegalArgumentException e) {
+            return null
+        }
+    })
+    
+    check("kernel_even_dimensions_no_output", {
+        String outputFile = outputDir + "userkernel_even.dep"
+        runPlugin("FilterUserKernel", [dataDir + "surface.dep", outputFile, dataDir + "kernel_even.txt", "false"])
+        return new File(outputFile).exists() ? "an output was created using an invalid kernel" : null
+    })
+    
+    // Values of the cells beyond the grid edges in each edge mode.
+
+    // surface.dep has 6 rows and 7 columns. Each off-grid cell is listed 
+    // with the in-grid cell whose value it should take in each edge mode.
+    check("edge_modes", {
+        WhiteboxRaster raster = new WhiteboxRaster(dataDir + "surface.dep", "r")
+        def cells = [[-1, -1], [-2, 3], [6, 7], [2, -3]]
+        def expected = [
+            (EdgeMode.REPLICATE): [[0, 0], [0, 3], [5, 6], [2, 0]],
+            (EdgeMode.REFLECT): [[0, 0], [1, 3], [5, 6], [2, 2]],
+            (EdgeMode.WRAP): [[5, 6], [4, 3], [0, 0], [2, 4]]
+        ]
+        try {
+            for (EdgeMode mode : EdgeMode.values()) {
+                raster.setEdgeMode(mode)
+                for (int i = 0; i < cells.size(); i++) {
+                    double value = raster.getValue(cells[i][0], cells[i][1])
+                    double expectedValue = (mode == EdgeMode.NODATA) ? raster.getNoDataValue()
+                        : raster.getValue(expected[mode][i][0], expected[mode][i][1])
+                    if (value != expectedValue) {
+                        return "cell " + cells[i] + " has a value of " + value + " in the " + mode + " edge mode rather than " + expectedValue
+                    }
+                }
+            }
+        } finally {
+            raster.close()
+        }
+        return null
+    })
+    
+    check("edge_mode_parameters", {
+        def modes = ["true": EdgeMode.REFLECT, "false": EdgeMode.NODATA, "NoData": EdgeMode.NODATA, 
+            "Replicate": EdgeMode.REPLICATE, "reflect": EdgeMode.REFLECT, "WRAP": EdgeMode.WRAP]
+        for (def entry : modes) {
+            if (EdgeMode.fromString(entry.key) != entry.value) {
+                return "'" + entry.key + "' is not converted to " + entry.value
+            }
+        }
+        try {
+            EdgeMode.fromString("mirror")
+            return "an unrecognized edge mode was accepted"
+        } catch (IllegalArgumentException e) {
+            return null
+        }
+    })
+
+    // Contrast stretches: output ranges, saturated tails, clip values,
+    // NoData and rejected parameters.
+
+    // returns the range of the valid values of a raster
+    def valueRange = { String file ->
+        WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+        double min = Double.POSITIVE_INFINITY
+        double max = Double.NEGATIVE_INFINITY
+        for (int row = 0; row < raster.getNumberRows(); row++) {
+            for (double z : raster.getRowValues(row)) {
+                if (z != raster.getNoDataValue()) {
+                    min = Math.min(min, z)
+                    max = Math.max(max, z)
+                }
+            }
+        }
+        raster.close()
+        [min, max]
+    }
+
+    check("sigmoidal_output_range", {
+        for (List<String> params : [["0.5", "10"], ["0.2", "3"], ["0.9", "25"]]) {
+            String outputFile = outputDir + "sigmoidal_range.dep"
+            runPlugin("SigmoidalStretch", [dataDir + "surface.dep", outputFile] + params)
+            def range = valueRange(outputFile)
+            if (Math.abs(range[0]) > tolerance || Math.abs(range[1] - 1) > tolerance) {
+                return "the output ranges from " + range[0] + " to " + range[1] + " rather than 0 to 1 with " + params
+            }
+        }
+        return null
+    })
+
+    check("sigmoidal_uniform_rejected", {
+        String outputFile = outputDir + "sigmoidal_uniform.dep"
+        feedback.clear()
+        runPlugin("SigmoidalStretch", [dataDir + "uniform.dep", outputFile, "0.5", "10"])
+        if (new File(outputFile).exists()) {
+            return "a raster with no variation was stretched"
+        }
+        if (!feedback.any { it.contains("no variation") }) {
+            return "the user wasn't told why the raster wasn't stretched: " + feedback
+        }
+        return null
+    })
+
+    // values beyond the first and last breakpoints take their output values
+    check("piecewise_clamped", {
+        String outputFile = outputDir + "piecewise_clamped.dep"
+        runScript("PiecewiseContrastStretch", [dataDir + "surface.dep", outputFile, "5,0.1; 25,0.9"])
+        WhiteboxRaster input = new WhiteboxRaster(dataDir + "surface.dep", "r")
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        try {
+            for (int row = 0; row < input.getNumberRows(); row++) {
+                for (int col = 0; col < input.getNumberColumns(); col++) {
+                    double z = input.getValue(row, col)
+                    double expected = z <= 5 ? 0.1 : (z >= 25 ? 0.9 : 0.1 + (z - 5) / 20 * 0.8)
+                    if (Math.abs(output.getValue(row, col) - expected) > tolerance) {
+                        return "an input of " + z + " was stretched to " + output.getValue(row, col) +
+                            " rather than " + expected
+                    }
+                }
+            }
+        } finally {
+            input.close()
+            output.close()
+        }
+        return null
+    })
+
+    check("piecewise_invalid_breakpoints", {
+        def invalid = [
+            "5,0.1": "At least two breakpoints",
+            "0,0; 10": "is not a pair",
+            "0,0; ten,1": "non-numeric",
+            "0,0; 10,0.5; 10,1": "must be increasing",
+            "10,0; 5,1": "must be increasing"
+        ]
+        for (def entry : invalid) {
+            String outputFile = outputDir + "piecewise_invalid.dep"
+            feedback.clear()
+            runScript("PiecewiseContrastStretch", [dataDir + "surface.dep", outputFile, entry.key])
+            if (new File(outputFile).exists()) {
+                return "an output was created with the breakpoints '" + entry.key + "'"
+            }
+            if (!feedback.any { it.contains(entry.value) }) {
+                return "the breakpoints '" + entry.key + "' were not rejected with the expected message: " + feedback
+            }
+        }
+        return null
+    })
+
+    // The clip values are the mean plus and minus the number of standard 
+    // deviations, with the sample standard deviation of the valid cells. 
+    // Cells beyond them take the lowest or highest tone.
+    check("stdev_bounds", {
+        String outputFile = outputDir + "stdev_bounds.dep"
+        runPlugin("StandardDeviationContrastStretch", [stretchDir + "nodata.dep", outputFile, "1", "16"])
+        WhiteboxRaster input = new WhiteboxRaster(stretchDir + "nodata.dep", "r")
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        try {
+            List<Double> values = []
+            for (int row = 0; row < input.getNumberRows(); row++) {
+                values.addAll(input.getRowValues(row).findAll { it != input.getNoDataValue() })
+            }
+            double mean = values.sum() / values.size()
+            double stdDev = Math.sqrt(values.sum { (it - mean) * (it - mean) } / (values.size() - 1))
+            double lower = mean - stdDev
+            double upper = mean + stdDev
+            def clipValue = { String entry ->
+                String line = output.getMetadata().find { it.startsWith(entry) }
+                line == null ? Double.NaN : Double.parseDouble(line.substring(entry.length()).trim())
+            }
+            if (Math.abs(clipValue("Lower clip value:") - lower) > tolerance
+                    || Math.abs(clipValue("Upper clip value:") - upper) > tolerance) {
+                return "the clip values are not " + lower + " and " + upper + ": " + output.getMetadata()
+            }
+            for (int row = 0; row < input.getNumberRows(); row++) {
+                for (int col = 0; col < input.getNumberColumns(); col++) {
+                    double z = input.getValue(row, col)
+                    double tone = output.getValue(row, col)
+                    if ((z != input.getNoDataValue() && z <= lower && tone != 0)
+                            || (z != input.getNoDataValue() && z >= upper && tone != 15)) {
+                        return "an input of " + z + " beyond the clip values was stretched to " + tone
+                    }
+                }
+            }
+        } finally {
+            input.close()
+            output.close()
+        }
+        return null
+    })
+
+    // Each stretch writes NoData in exactly the cells that are NoData in the 
+    // input, including when the output data type can't hold the input's 
+    // NoData value.
+    check("stretch_nodata_preserved", {
+        def runs = [
+            ["MinMaxContrastStretch", ["5", "25", "16"]],
+            ["StandardDeviationContrastStretch", ["2", "256", "0", "1", "Float"]],
+            ["MinMaxContrastStretch", ["5", "25", "255", "0", "254", "Byte"]],
+            ["StandardDeviationContrastStretch", ["2", "255", "1", "255", "Byte"]]
+        ]
+        WhiteboxRaster input = new WhiteboxRaster(stretchDir + "nodata.dep", "r")
+        try {
+            for (int i = 0; i < runs.size(); i++) {
+                def run = runs[i]
+                String outputFile = outputDir + "stretch_nodata" + i + ".dep"
+                runPlugin(run[0], [stretchDir + "nodata.dep", outputFile] + run[1])
+                if (!new File(outputFile).exists()) {
+                    return run[0] + " " + run[1] + " created no output"
+                }
+                WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+                try {
+                    for (int row = 0; row < input.getNumberRows(); row++) {
+                        for (int col = 0; col < input.getNumberColumns(); col++) {
+                            boolean inputNoData = input.getValue(row, col) == input.getNoDataValue()
+                            boolean outputNoData = output.getValue(row, col) == output.getNoDataValue()
+                            if (inputNoData != outputNoData) {
+                                return run[0] + " " + run[1] + " changed the NoData status of cell " + 
+                                    row + ", " + col
+                            }
+                        }
+                    }
+                } finally {
+                    output.close()
+                }
+            }
+        } finally {
+            input.close()
+        }
+        return null
+    })
+
+    check("stretch_output_range_rejected", {
+        def invalid = [
+            ["5", "25", "256", "0", "255", "Byte"]: "no value for NoData",
+            ["5", "25", "16", "0", "300", "Byte"]: "range from 0 to 255",
+            ["5", "25", "16", "10", "0", "Integer"]: "must be greater than the output minimum"
+        ]
+        for (def entry : invalid) {
+            String outputFile = outputDir + "stretch_invalid.dep"
+            feedback.clear()
+            runPlugin("MinMaxContrastStretch", [stretchDir + "surface.dep", outputFile] + entry.key)
+            if (new File(outputFile).exists()) {
+                return "an output was created with the output range " + entry.key
+            }
+            if (!feedback.any { it.contains(entry.value) }) {
+                return "the output range " + entry.key + " was not rejected with the expected message: " + feedback
+            }
+        }
+        return null
+    })
+
+    // Colour composites, colour spaces and band indices.
+
+    // writes a raster of 3 rows and 4 columns with the values listed by row
+    def writeRaster = { String file, List<Double> values ->
+        WhiteboxRaster raster = new WhiteboxRaster(file, 3.0, 0.0, 4.0, 0.0, 3, 4,
+            DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+        for (int i = 0; i < values.size(); i++) {
+            raster.setValue(i.intdiv(4), i % 4, values[i] as double)
+        }
+        raster.close()
+    }
+
+    def readValues = { String file ->
+        WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+        List<Double> values = []
+        for (int row = 0; row < 3; row++) {
+            for (int col = 0; col < 4; col++) {
+                values << raster.getValue(row, col)
+            }
+        }
+        raster.close()
+        values
+    }
+
+    // A composite of 8-bit bands, created without stretching the bands, is
+    // split into the same bands and re-created exactly, including partially
+    // transparent colours and NoData cells.
+    check("colour_composite_round_trip", {
+        double nd = -32768.0
+        def bands = [
+            Red: [0, 255, 128, 1, 17, nd, 200, 64, 99, 3, 250, 0],
+            Green: [0, 255, 127, 2, 34, 5, 100, 64, 98, 30, 251, 255],
+            Blue: [0, 255, 129, 3, 51, 6, 50, 64, 97, 33, 252, 0],
+            A: [255, 255, 0, 1, 128, 255, 254, 127, 255, 200, 10, 255]
+        ]
+        bands.each { name, values -> writeRaster(outputDir + "band_" + name + ".dep", values) }
+        String composite = outputDir + "composite.dep"
+        runPlugin("CreateColourComposite", [outputDir + "band_Red.dep", outputDir + "band_Green.dep",
+            outputDir + "band_Blue.dep", outputDir + "band_A.dep", composite, "false", "false"])
+        runPlugin("SplitColourComposite", [composite, "true"])
+        for (def band : bands) {
+            List<Double> expected = band.value.collect { it as double }
+            if (band.key != "Red") {
+                // the cell that is NoData in the red band is NoData in the composite
+                expected[5] = nd
+            }
+            List<Double> split = readValues(composite.replace(".dep", "_" + band.key + ".dep"))
+            if (split != expected) {
+                return "the split " + band.key + " band is " + split + " rather than " + expected
+            }
+        }
+        String recreated = outputDir + "recreated.dep"
+        runPlugin("CreateColourComposite", ["Red", "Green", "Blue", "A"].collect {
+            composite.replace(".dep", "_" + it + ".dep") } + [recreated, "false", "false"])
+        if (readValues(recreated) != readValues(composite)) {
+            return "the re-created composite is " + readValues(recreated) + " rather than " + readValues(composite)
+        }
+        return null
+    })
+
+    // black, white and grey cells have no hue or saturation rather than NaN
+    check("rgb_to_ihs_grey", {
+        // the first four cells are black, white and grey
+        writeRaster(outputDir + "grey_r.dep", [0, 255, 128, 7, 0, 255, 10, 20, 0, 0, 0, 0])
+        writeRaster(outputDir + "grey_g.dep", [0, 255, 128, 7, 30, 30, 30, 30, 30, 30, 30, 30])
+        writeRaster(outputDir + "grey_b.dep", [0, 255, 128, 7, 40, 40, 40, 40, 40, 40, 40, 40])
+        runPlugin("RGBtoIHS", [outputDir + "grey_r.dep", outputDir + "grey_g.dep", outputDir + "grey_b.dep",
+            outputDir + "grey_i.dep", outputDir + "grey_h.dep", outputDir + "grey_s.dep"])
+        List<Double> hue = readValues(outputDir + "grey_h.dep")
+        List<Double> saturation = readValues(outputDir + "grey_s.dep")
+        if (hue.any { Double.isNaN(it) } || saturation.any { Double.isNaN(it) }) {
+            return "the hue " + hue + " or saturation " + saturation + " contains NaN"
+        }
+        if (hue[0..3] != [0.0, 0.0, 0.0, 0.0] || saturation[0..3] != [0.0, 0.0, 0.0, 0.0]) {
+            return "grey cells have a hue of " + hue[0..3] + " and a saturation of " + saturation[0..3]
+        }
+        return null
+    })
+
+    // The normalized difference of two bands is NoData where either band is
+    // NoData or the bands sum to zero. With a soil-adjustment correction the
+    // sum only needs to differ from -L, and the index is scaled by 1 + L.
+    check("normalized_difference_index", {
+        writeRaster(outputDir + "ndi_a.dep", [0.5, 0.3, 0, 2, 0.4, -32768, 0.2, 1, 0.6, 0.1, 0.25, 0.8])
+        writeRaster(outputDir + "ndi_b.dep", [0.1, 0.3, 0, -2, 0.4, 0.5, -32768, 0, 0.2, 0.3, 0.25, 0.2])
+        double nd = -32768
+        Map<String, List<Double>> expected = [
+            "0": [0.4d / 0.6d, 0, nd, nd, 0, nd, nd, 1, 0.5, -0.5, 0, 0.6],
+            "0.5": [0.4d / 1.1d * 1.5d, 0, 0, 12, 0, nd, nd, 1, 0.4d / 1.3d * 1.5d, -0.2d / 0.9d * 1.5d, 0, 0.6]
+        ]
+        for (entry in expected) {
+            String outputFile = outputDir + "ndi_" + entry.key + ".dep"
+            runPlugin("NormalizedDifferenceIndex", [outputDir + "ndi_a.dep", outputDir + "ndi_b.dep",
+                outputFile, "not specified", entry.key, "0"])
+            List<Double> values = readValues(outputFile)
+            for (int i = 0; i < values.size(); i++) {
+                if (Math.abs(values[i] - (entry.value[i] as double)) > 1e-6) {
+                    return "with a correction of " + entry.key + " the index is " + values + " rather than " + entry.value
+                }
+            }
+        }
+        return null
+    })
+}
+
+// Resampling kernel weights and NoData policies, and filling gaps from a
+// reference surface.
+if (!regenerate) {
+    // writes a square raster with a cell size of one and the value of each
+    // cell given by a closure of its row and column
+    def writeSquareRaster = { String file, int size, Closure value ->
+        WhiteboxRaster raster = new WhiteboxRaster(file, size as double, 0.0, size as double, 0.0, size, size,
+            DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+        for (int row = 0; row < size; row++) {
+            for (int col = 0; col < size; col++) {
+                raster.setValue(row, col, value(row, col) as double)
+            }
+        }
+        raster.close()
+    }
+
+    // The value of an impulse sampled along its row at a distance d from its
+    // cell centre is the kernel weight at d, normalized over the taps.
+    String impulseFile = outputDir + "impulse.dep"
+    writeSquareRaster(impulseFile, 9, { row, col -> row == 4 && col == 4 ? 1 : 0 })
+    def sampleImpulse = { RasterResampler.Method method, double d ->
+        WhiteboxRaster impulse = new WhiteboxRaster(impulseFile, "r")
+        double z = new RasterResampler(impulse, method, RasterResampler.NoDataPolicy.INVALIDATE).getValue(4.5 + d, 4.5)
+        impulse.close()
+        z
+    }
+
+    check("resampler_cubic_weights", {
+        Map<Double, Double> expected = [0.25d: 0.8671875d, 0.5d: 0.5625d, 1.5d: -0.0625d, 2.5d: 0.0d]
+        for (entry in expected) {
+            double z = sampleImpulse(RasterResampler.Method.CUBIC_CONVOLUTION, entry.key)
+            if (Math.abs(z - entry.value) > 1e-9) {
+                return "the weight at " + entry.key + " is " + z + " rather than " + entry.value
+            }
+        }
+        return null
+    })
+
+    check("resampler_lanczos_weights", {
+        def lanczos = { double x -> 3 * Math.sin(Math.PI * x) * Math.sin(Math.PI * x / 3) / (Math.PI * x * Math.PI * x) }
+        // the six taps are 0.5, 1.5 and 2.5 cells away on either side
+        double sum = 2 * (lanczos(0.5) + lanczos(1.5) + lanczos(2.5))
+        Map<Double, Double> expected = [0.5d: lanczos(0.5) / sum, 1.5d: lanczos(1.5) / sum, 2.5d: lanczos(2.5) / sum]
+        for (entry in expected) {
+            double z = sampleImpulse(RasterResampler.Method.LANCZOS, entry.key)
+            if (Math.abs(z - entry.value) > 1e-9) {
+                return "the weight at " + entry.key + " is " + z + " rather than " + entry.value
+            }
+        }
+        if (Math.abs(sampleImpulse(RasterResampler.Method.LANCZOS, 0) - 1) > 1e-9) {
+            return "the weight at a cell centre isn't one"
+        }
+        return null
+    })
+
+    // A point next to a NoData cell, whose cubic kernel includes it, is NoData
+    // when invalidating and takes the value of its valid neighbours when
+    // renormalizing. A point within the NoData cell is NoData either way.
+    check("resampler_nodata_policies", {
+        String file = outputDir + "constant_nodata.dep"
+        writeSquareRaster(file, 5, { row, col -> row == 2 && col == 2 ? -32768 : 10 })
+        WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+        String failure = null
+        Map<RasterResampler.NoDataPolicy, Double> nearValues = [
+            (RasterResampler.NoDataPolicy.INVALIDATE): -32768d,
+            (RasterResampler.NoDataPolicy.RENORMALIZE): 10d]
+        for (entry in nearValues) {
+            RasterResampler resampler = new RasterResampler(raster, RasterResampler.Method.CUBIC_CONVOLUTION, entry.key)
+            double near = resampler.getValue(1.9, 2.5)
+            double within = resampler.getValue(2.6, 2.5)
+            if (Math.abs(near - entry.value) > 1e-9) {
+                failure = "a point next to the NoData cell is " + near + " when the policy is " + entry.key
+            } else if (within != -32768) {
+                failure = "a point within the NoData cell is " + within + " when the policy is " + entry.key
+            }
+        }
+        raster.close()
+        return failure
+    })
+
+    check("resampler_fit_to_data_type", {
+        // value, data type, NoData value and the expected stored value
+        def cases = [
+            [12.5d, DataType.BYTE, -32768d, 13d],
+            [255.7d, DataType.BYTE, -32768d, 255d],
+            [-3d, DataType.BYTE, -32768d, 0d],
+            [254.6d, DataType.BYTE, 255d, 254d],
+            [300d, DataType.BYTE, 255d, 254d],
+            [40000d, DataType.INTEGER, -32768d, 32767d],
+            [-40000d, DataType.INTEGER, -32768d, -32767d],
+            [-2.4d, DataType.INTEGER, -32768d, -2d],
+            [3e9d, DataType.I32, -32768d, Integer.MAX_VALUE as double],
+            [-3e9d, DataType.I32, -32768d, Integer.MIN_VALUE as double],
+            [1e39d, DataType.FLOAT, -32768d, Float.MAX_VALUE as double],
+            [-1e39d, DataType.FLOAT, -32768d, -Float.MAX_VALUE as double],
+            [1.23456d, DataType.DOUBLE, -32768d, 1.23456d],
+            [-32768d, DataType.BYTE, -32768d, -32768d]
+        ]
+        for (List c : cases) {
+            double z = RasterResampler.fitToDataType(c[0] as double, c[1] as DataType, c[2] as double)
+            if (z != (c[3] as double)) {
+                return c[0] + " is stored as " + z + " rather than " + c[3] + " in a " + c[1] + " raster with NoData " + c[2]
+            }
+        }
+        return null
+    })
+
+    // Voids cut from a known surface are recovered from a reference surface
+    // that is offset from it by a constant, whether the void is inside the
+    // grid or touches its edge. A void larger than the maximum size is left
+    // as NoData.
+    check("fill_gaps_with_reference", {
+        def surface = { row, col -> 2 * row + 0.5 * col + 0.1 * row * col }
+        def isVoid = { row, col -> (row in 2..3 && col in 2..4) || (row == 0 && col >= 6) || (row >= 6 && col <= 3) }
+        def isLargeVoid = { row, col -> row >= 6 && col <= 3 }
+        writeSquareRaster(outputDir + "voids.dep", 9, { row, col -> isVoid(row, col) ? -32768 : surface(row, col) })
+        writeSquareRaster(outputDir + "voids_reference.dep", 9, { row, col -> surface(row, col) - 5 })
+        String outputFile = outputDir + "voids_filled.dep"
+        runPlugin("FillGapsWithReference", [outputDir + "voids.dep", outputDir + "voids_reference.dep",
+            outputFile, "8", "12"])
+        WhiteboxRaster filled = new WhiteboxRaster(outputFile, "r")
+        String failure = null
+        for (int row = 0; row < 9 && failure == null; row++) {
+            for (int col = 0; col < 9 && failure == null; col++) {
+                double z = filled.getValue(row, col)
+                if (isLargeVoid(row, col)) {
+                    if (z != filled.getNoDataValue()) {
+                        failure = "cell (" + row + ", " + col + ") of the large void was filled with " + z
+                    }
+                } else if (Math.abs(z - surface(row, col)) > 1e-4) {
+                    failure = "cell (" + row + ", " + col + ") is " + z + " rather than " + surface(row, col)
+                }
+            }
+        }
+        filled.close()
+        return failure
+    })
+}
+
+if (!regenerate) {
+    println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+    System.exit(numFailed > 0 ? 1 : 0)
+}
@@ -0,0 +1,708 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import java.util.Arrays
+import groovy.json.JsonSlurper
+import whitebox.geospatialfiles.ShapeFile
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.shapefile.PolyLine
+import whitebox.geospatialfiles.shapefile.ShapeType
+import whitebox.geospatialfiles.shapefile.attributes.DBFField
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Tests for the hydrological tools. The inputs are synthetic flow-pointer
+// rasters, DEMs and stream networks that are created in a temporary directory by the tests, because
+// some of them are too large to keep in tests/data. Upslope statistics are
+// checked on planes, whose upslope areas are known, and contributing areas on
+// serpentine flowpaths.
+//
+// The tests are run against the compiled plugins with 'python build.py
+// runtests'.
+
+def outputDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+
+List<String> returned = []
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> false },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+// runs one of the Groovy scripts in resources/plugins/Scripts. The scripts
+// declare a class with the same name as the file, so the script itself is
+// given a different name.
+def runScript = { String name, List<String> scriptArgs ->
+    Binding binding = new Binding()
+    binding.setVariable("pluginHost", host)
+    binding.setVariable("args", scriptArgs as String[])
+    new GroovyShell(binding).evaluate(new File("resources/plugins/Scripts/" + name + ".groovy").text, name + "Script")
+}
+
+// creates a D8 pointer raster in which all cells drain to the last cell of
+// the last row along a single serpentine flowpath: flow runs east along the
+// even rows and west along the odd rows, passing south at the row ends.
+def createSerpentine = { String file, int rows, int cols ->
+    WhiteboxRaster pntr = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.INTEGER, 0.0, -32768.0)
+    double[] data = new double[cols]
+    for (int row = 0; row < rows; row++) {
+        boolean eastward = (row % 2 == 0)
+        Arrays.fill(data, eastward ? 2.0 : 32.0)
+        int rowEnd = eastward ? cols - 1 : 0
+        data[rowEnd] = (row < rows - 1) ? 8.0 : 0.0
+        pntr.setRowValues(row, data)
+    }
+    pntr.close()
+}
+
+int numTests = 0
+int numFailed = 0
+def check = { String name, Closure test ->
+    numTests++
+    String failure
+    try {
+        failure = test()
+    } catch (Throwable e) {
+        failure = "unexpected exception: " + e
+    }
+    if (failure == null) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    " + failure
+    }
+}
+
+// Each cell counts itself and every cell upstream of it, so the cells of a
+// serpentine flowpath are numbered 1, 2, 3, ... in the downstream direction.
+check("flowaccumd8_serpentine", {
+    int rows = 20
+    int cols = 50
+    String pntrFile = outputDir + "serpentine.dep"
+    String outputFile = outputDir + "serpentine_accum.dep"
+    createSerpentine(pntrFile, rows, cols)
+    runPlugin("FlowAccumD8", [pntrFile, outputFile, "number of upslope grid cells", "false"])
+    WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+    try {
+        for (int row = 0; row < rows; row++) {
+            double[] data = output.getRowValues(row)
+            for (int col = 0; col < cols; col++) {
+                double expected = row * cols + (row % 2 == 0 ? col : cols - 1 - col) + 1
+                if (data[col] != expected) {
+                    return "cell (" + row + ", " + col + ") has an accumulation of " + data[col] + " rather than " + expected
+                }
+            }
+        }
+    } finally {
+        output.close()
+    }
+    return null
+})
+
+// A 4097 x 4097 serpentine gives the outlet a count of 16,785,409, which is
+// greater than 2^24 and odd, and so cannot be represented by a float. The
+// count must be exact and must not have wrapped around.
+check("flowaccumd8_large_count", {
+    int rows = 4097
+    int cols = 4097
+    double numCells = (double)rows * cols
+    String pntrFile = outputDir + "large_serpentine.dep"
+    String outputFile = outputDir + "large_serpentine_accum.dep"
+    createSerpentine(pntrFile, rows, cols)
+    runPlugin("FlowAccumD8", [pntrFile, outputFile, "number of upslope grid cells", "false"])
+    WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+    double outletValue = output.getValue(rows - 1, (rows % 2 == 1) ? cols - 1 : 0)
+    double maximum = output.getMaximumValue()
+    output.close()
+    if (outletValue != numCells) {
+        return "the outlet has an accumulation of " + outletValue + " rather than " + numCells
+    }
+    if (maximum != numCells) {
+        return "the maximum accumulation is " + maximum + " rather than " + numCells
+    }
+    return null
+})
+
+// Each of the eight directions, a flat cell, a NoData cell, and an undefined
+// code in the Whitebox encoding, and the same cells in the other encodings.
+// Conversions between every pair of encodings must give the expected codes.
+check("d8pointerconversion_encodings", {
+    def encodings = [
+        Whitebox: [1, 2, 4, 8, 16, 32, 64, 128, 0, null, 0],
+        ESRI: [128, 1, 2, 4, 8, 16, 32, 64, 0, null, 0],
+        TauDEM: [2, 1, 8, 7, 6, 5, 4, 3, 0, null, 0],
+        GRASS: [1, 8, 7, 6, 5, 4, 3, 2, 0, null, 0]
+    ]
+    def inputs = [
+        Whitebox: [1, 2, 4, 8, 16, 32, 64, 128, 0, null, 3],
+        ESRI: [128, 1, 2, 4, 8, 16, 32, 64, 0, null, 3],
+        TauDEM: [2, 1, 8, 7, 6, 5, 4, 3, 0, null, 9],
+        GRASS: [-1, 8, 7, 6, 5, 4, 3, -2, 0, null, 9]
+    ]
+    for (String from : encodings.keySet()) {
+        String inputFile = outputDir + "pointer_" + from + ".dep"
+        List<Integer> values = inputs[from]
+        WhiteboxRaster input = new WhiteboxRaster(inputFile, 1.0, 0.0, (double)values.size(), 0.0,
+            1, values.size(), DataScale.CATEGORICAL, DataType.FLOAT, 0.0, -32768.0)
+        input.setRowValues(0, values.collect { it == null ? -32768.0 : (double)it } as double[])
+        input.close()
+        for (String to : encodings.keySet()) {
+            String outputFile = outputDir + "pointer_" + from + "_to_" + to + ".dep"
+            runPlugin("D8PointerConversion", [inputFile, outputFile, from, to])
+            WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+            List<Integer> converted = output.getRowValues(0).collect { it == -32768.0 ? null : (int)it }
+            output.close()
+            if (converted != encodings[to]) {
+                return "converting " + values + " from " + from + " to " + to + " gives " + converted
+            }
+        }
+    }
+    return null
+})
+
+// The conditioned DEM is a valley draining south along column 20, so the
+// modelled channel runs down the valley floor. The original DEM differs from
+// it at a pit, which was raised by 2, and a bump, which was lowered by 1. One
+// mapped stream follows the valley and the other crosses the hillslope, where
+// there is no modelled channel.
+check("hydroenforcementreport_valley", {
+    int rows = 50
+    int cols = 41
+    String originalFile = outputDir + "valley_original.dep"
+    String conditionedFile = outputDir + "valley_conditioned.dep"
+    String streamsFile = outputDir + "valley_streams.shp"
+    String reportFile = outputDir + "valley_report.html"
+    String sitesFile = outputDir + "valley_sites.geojson"
+    WhiteboxRaster original = new WhiteboxRaster(originalFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    WhiteboxRaster conditioned = new WhiteboxRaster(conditionedFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            double z = Math.abs(col - 20) + (rows - row) * 0.1
+            conditioned.setValue(row, col, z)
+            original.setValue(row, col, z - (row == 25 && col == 20 ? 2 : 0) + (row == 10 && col == 5 ? 1 : 0))
+        }
+    }
+    original.close()
+    conditioned.close()
+
+    DBFField idField = new DBFField()
+    idField.setName("FID")
+    idField.setDataType(DBFField.DBFDataType.NUMERIC)
+    idField.setFieldLength(10)
+    idField.setDecimalCount(0)
+    ShapeFile streams = new ShapeFile(streamsFile, ShapeType.POLYLINE, [idField] as DBFField[])
+    streams.addRecord(new PolyLine([0] as int[], [[20.5, 45.0], [20.5, 5.0]] as double[][]), [1.0] as Object[])
+    streams.addRecord(new PolyLine([0] as int[], [[5.5, 45.0], [5.5, 15.0]] as double[][]), [2.0] as Object[])
+    streams.write()
+
+    returned.clear()
+    runPlugin("HydroEnforcementReport", [originalFile, conditionedFile, streamsFile, "2.0", "50", "5", reportFile, sitesFile])
+    if (!returned.contains(new File(reportFile).getAbsolutePath())) {
+        return "the report was not returned"
+    }
+    String report = new File(reportFile).text
+    for (String expected : ["<td>Number of Cells</td><td class=\"numberCell\">1</td><td class=\"numberCell\">1</td><td class=\"numberCell\">2</td>",
+            "<td>Volume</td><td class=\"numberCell\">2.000</td><td class=\"numberCell\">1.000</td><td class=\"numberCell\">3.000</td>",
+            "<td>Followed by Modelled Channels</td><td class=\"numberCell\">40.000</td>",
+            "<td>Not Followed</td><td class=\"numberCell\">30.000</td>"]) {
+        if (!report.contains(expected)) {
+            return "the report does not contain " + expected
+        }
+    }
+    def sites = new JsonSlurper().parse(new File(sitesFile)).features
+    def changeSites = sites.findAll { it.properties.SITE_TYPE == "elevation change" }
+    def streamSites = sites.findAll { it.properties.SITE_TYPE == "stream disagreement" }
+    if (changeSites*.properties*.VALUE != [2.0, -1.0] || changeSites[0].geometry.coordinates != [20.5, 24.5]) {
+        return "the elevation change sites are " + changeSites
+    }
+    if (streamSites.size() != 1 || streamSites[0].properties.VALUE != 30.0
+            || streamSites[0].geometry.coordinates[0] != 5.5
+            || Math.abs(streamSites[0].geometry.coordinates[1] - 30.0) > 0.5) {
+        return "the stream disagreement sites are " + streamSites
+    }
+    return null
+})
+
+// On a depression-free DEM every D8 pointer is directed downslope, so the
+// channel gradient can never be negative. The DEM is a random surface with
+// its depressions filled with a small gradient, and every cell is a stream.
+check("streamchannelgradient_depression_free", {
+    int rows = 60
+    int cols = 60
+    String demFile = outputDir + "random_dem.dep"
+    String filledFile = outputDir + "random_filled.dep"
+    String pntrFile = outputDir + "random_pntr.dep"
+    String streamsFile = outputDir + "random_streams.dep"
+    Random random = new Random(42)
+    WhiteboxRaster dem = new WhiteboxRaster(demFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    WhiteboxRaster streams = new WhiteboxRaster(streamsFile, "rw", demFile, DataType.INTEGER, 0.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            dem.setValue(row, col, 100 + 10 * random.nextDouble() + 0.05 * row)
+            streams.setValue(row, col, 1.0)
+        }
+    }
+    dem.close()
+    streams.close()
+    runPlugin("FillDepressions", [demFile, filledFile, "0.001"])
+    runPlugin("FlowPointerD8", [filledFile, pntrFile])
+    for (String reach : ["1", "5"]) {
+        String outputFile = outputDir + "random_gradient_" + reach + ".dep"
+        runPlugin("StreamChannelGradient", [filledFile, streamsFile, pntrFile, outputFile, reach, "false"])
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        int numValid = 0
+        try {
+            for (int row = 0; row < rows; row++) {
+                for (double g : output.getRowValues(row)) {
+                    if (g != output.getNoDataValue()) {
+                        numValid++
+                        if (g < 0) {
+                            return "a reach of " + reach + " cells has a gradient of " + g
+                        }
+                    }
+                }
+            }
+        } finally {
+            output.close()
+        }
+        if (numValid < rows * cols / 2) {
+            return "only " + numValid + " cells have a gradient for a reach of " + reach + " cells"
+        }
+    }
+    return null
+})
+
+// Along the floor of the valley DEM used above, each step south drops by 0.1
+// over one cell, so the gradient is 0.1 over any reach. Reaches that would run
+// off of the bottom of the grid are NoData, as are cells that are not streams.
+check("streamchannelgradient_valley", {
+    int rows = 50
+    String demFile = outputDir + "valley_conditioned.dep"
+    String pntrFile = outputDir + "valley_pntr.dep"
+    String streamsFile = outputDir + "valley_floor.dep"
+    WhiteboxRaster streams = new WhiteboxRaster(streamsFile, "rw", demFile, DataType.INTEGER, 0.0)
+    for (int row = 0; row < rows; row++) {
+        streams.setValue(row, 20, 1.0)
+    }
+    streams.close()
+    runPlugin("FlowPointerD8", [demFile, pntrFile])
+    for (List<String> options : [["5", "false"], ["5", "true"]]) {
+        int reach = options[1] == "true" ? 1 : 5
+        String outputFile = outputDir + "valley_gradient_" + options[1] + ".dep"
+        runPlugin("StreamChannelGradient", [demFile, streamsFile, pntrFile, outputFile] + options)
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        double noData = output.getNoDataValue()
+        try {
+            for (int row = 0; row < rows; row++) {
+                double g = output.getValue(row, 20)
+                if (row < rows - reach && Math.abs(g - 0.1) > 1e-4) {
+                    return "the gradient of row " + row + " is " + g + " for a reach of " + reach + " cells"
+                }
+                if (row >= rows - reach && g != noData) {
+                    return "the reach from row " + row + " runs off of the grid but has a gradient of " + g
+                }
+                if (output.getValue(row, 10) != noData) {
+                    return "a cell that is not a stream has a gradient"
+                }
+            }
+        } finally {
+            output.close()
+        }
+    }
+    return null
+})
+
+// Every cell flows east, except the cells of the last column, which flow
+// south to an outlet in the last row. The flowpath from each seed runs along
+// its row and then down the last column, unless it reaches a stream first.
+check("tracedownslopeflowpaths_seeds", {
+    int rows = 10
+    int cols = 10
+    String pntrFile = outputDir + "east_pntr.dep"
+    String seedsFile = outputDir + "east_seeds.dep"
+    String streamsFile = outputDir + "east_streams.dep"
+    WhiteboxRaster pntr = new WhiteboxRaster(pntrFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.INTEGER, 0.0, -32768.0)
+    WhiteboxRaster seeds = new WhiteboxRaster(seedsFile, "rw", pntrFile, DataType.INTEGER, 0.0)
+    WhiteboxRaster streams = new WhiteboxRaster(streamsFile, "rw", pntrFile, DataType.INTEGER, 0.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            pntr.setValue(row, col, col < cols - 1 ? 2.0 : (row < rows - 1 ? 8.0 : 0.0))
+            seeds.setValue(row, col, 0.0)
+            streams.setValue(row, col, col == 6 ? 1.0 : 0.0)
+        }
+    }
+    seeds.setValue(1, 0, 3.0)
+    seeds.setValue(5, 2, 7.0)
+    pntr.close()
+    seeds.close()
+    streams.close()
+
+    // the second seed's flowpath joins the first seed's in the last column
+    String outputFile = outputDir + "east_paths.dep"
+    runScript("TraceDownslopeFlowpaths", [pntrFile, seedsFile, outputFile, "false"])
+    WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+    try {
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                double expected = 0.0
+                if (row == 1 || (col == cols - 1 && row > 1)) {
+                    expected = 3.0
+                } else if (row == 5 && col >= 2) {
+                    expected = 7.0
+                }
+                if (output.getValue(row, col) != expected) {
+                    return "cell (" + row + ", " + col + ") is " + output.getValue(row, col) + " rather than " + expected
+                }
+            }
+        }
+    } finally {
+        output.close()
+    }
+
+    // both flowpaths end at the stream in column 6
+    outputFile = outputDir + "east_paths_streams.dep"
+    runScript("TraceDownslopeFlowpaths", [pntrFile, seedsFile, outputFile, "true", streamsFile])
+    output = new WhiteboxRaster(outputFile, "r")
+    try {
+        double noData = output.getNoDataValue()
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                double expected = noData
+                if (row == 1 && col <= 6) {
+                    expected = 3.0
+                } else if (row == 5 && col >= 2 && col <= 6) {
+                    expected = 7.0
+                }
+                if (output.getValue(row, col) != expected) {
+                    return "with streams, cell (" + row + ", " + col + ") is " + output.getValue(row, col) + " rather than " + expected
+                }
+            }
+        }
+    } finally {
+        output.close()
+    }
+    return null
+})
+
+// The DEM is a plane that slopes to the south at a gradient of 0.1 and every
+// cell flows south. Basin 1 lies far enough inside of the grid that its
+// slopes and TPI values are unaffected by the edges; basin 2 is a strip along
+// the top of the grid.
+check("basingeomorphicmetrics_plane", {
+    int rows = 50
+    int cols = 41
+    String demFile = outputDir + "plane_dem.dep"
+    String pntrFile = outputDir + "plane_pntr.dep"
+    String basinsFile = outputDir + "plane_basins.dep"
+    String csvFile = outputDir + "plane_metrics.csv"
+    WhiteboxRaster dem = new WhiteboxRaster(demFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    WhiteboxRaster pntr = new WhiteboxRaster(pntrFile, "rw", demFile, DataType.INTEGER, 0.0)
+    WhiteboxRaster basins = new WhiteboxRaster(basinsFile, "rw", demFile, DataType.INTEGER, 0.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            dem.setValue(row, col, (rows - 1 - row) * 0.1)
+            pntr.setValue(row, col, row < rows - 1 ? 8.0 : 0.0)
+            double id = 0.0
+            if (row >= 5 && row <= 44 && col >= 5 && col <= 35) {
+                id = 1.0
+            } else if (row < 5) {
+                id = 2.0
+            }
+            basins.setValue(row, col, id)
+        }
+    }
+    dem.close()
+    pntr.close()
+    basins.close()
+
+    runPlugin("BasinGeomorphicMetrics", [demFile, pntrFile, basinsFile, csvFile, "10", "2"])
+    List<String> lines = new File(csvFile).readLines()
+    if (lines.size() != 3) {
+        return "the output has " + lines.size() + " lines rather than 3"
+    }
+    List<String> names = lines[0].split(",") as List
+    def value = { int line, String name -> Double.parseDouble(lines[line].split(",")[names.indexOf(name)]) }
+
+    // the stream cells have a flow accumulation of at least 10, i.e. rows 9 to 44 of basin 1
+    double area = 40 * 31
+    double relief = 3.9
+    double basinLength = Math.sqrt(39 * 39 + 30 * 30)
+    Map<String, Double> expected = ["Area": area, "Perimeter": 142.0, "Stream Length": 36.0 * 31,
+        "Drainage Density": 36.0 * 31 / area, "Relief": relief, "Basin Length": basinLength,
+        "Relief Ratio": relief / basinLength, "Ruggedness Number": 36.0 * 31 / area * Math.sqrt(relief),
+        "Mean Slope (degrees)": Math.toDegrees(Math.atan(0.1)), "Mean TPI": 0.0,
+        "Elongation Ratio": 2 * Math.sqrt(area / Math.PI) / basinLength,
+        "Circularity Ratio": 4 * Math.PI * area / (142.0 * 142.0)]
+    if (lines[1].split(",")[0] != "1" || lines[2].split(",")[0] != "2") {
+        return "the basins are not listed in order"
+    }
+    for (Map.Entry<String, Double> e : expected.entrySet()) {
+        if (Math.abs(value(1, e.getKey()) - e.getValue()) > 1e-3) {
+            return "the " + e.getKey() + " of basin 1 is " + value(1, e.getKey()) + " rather than " + e.getValue()
+        }
+    }
+    if (value(2, "Area") != 5 * 41 || Math.abs(value(2, "Relief") - 0.4) > 1e-3
+            || value(2, "Perimeter") != 92 || value(2, "Stream Length") != 0) {
+        return "the metrics of basin 2 are wrong: " + lines[2]
+    }
+    return null
+})
+
+check("temperatureindexmelt_daily_loop", {
+    // a snowpack of 0 to 45 mm SWE on a temperature gradient of -3 to 6
+    // degrees, with NoData in one corner of each raster
+    int rows = 10
+    int cols = 10
+    String sweFile = outputDir + "melt_swe_day0.dep"
+    String tempFile = outputDir + "melt_temperature.dep"
+    WhiteboxRaster swe = new WhiteboxRaster(sweFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    WhiteboxRaster temp = new WhiteboxRaster(tempFile, "rw", sweFile, DataType.FLOAT, -32768.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            swe.setValue(row, col, row == 0 && col == 0 ? -32768.0 : row * 5.0)
+            temp.setValue(row, col, row == rows - 1 && col == cols - 1 ? -32768.0 : col - 3.0)
+        }
+    }
+    swe.close()
+    temp.close()
+
+    // melt = 4 mm per degree above 0, capped at the available snow
+    def expectedMelt = { double z, double t -> Math.min(4.0 * Math.max(0.0, t), z) }
+    String meltFile = outputDir + "melt_day1.dep"
+    String remainingFile = outputDir + "melt_swe_day1.dep"
+    runPlugin("TemperatureIndexMelt", [sweFile, tempFile, meltFile, remainingFile, "not specified", "not specified"])
+    WhiteboxRaster melt = new WhiteboxRaster(meltFile, "r")
+    WhiteboxRaster remaining = new WhiteboxRaster(remainingFile, "r")
+    String failure = null
+    for (int row = 0; row < rows && failure == null; row++) {
+        for (int col = 0; col < cols; col++) {
+            double m = melt.getValue(row, col)
+            double r = remaining.getValue(row, col)
+            if ((row == 0 && col == 0) || (row == rows - 1 && col == cols - 1)) {
+                if (m != melt.getNoDataValue() || r != remaining.getNoDataValue()) {
+                    failure = "cell (" + row + ", " + col + ") is not NoData"
+                    break
+                }
+            } else if (Math.abs(m - expectedMelt(row * 5.0, col - 3.0)) > 1e-4
+                    || Math.abs(r - (row * 5.0 - m)) > 1e-4) {
+                failure = "cell (" + row + ", " + col + ") has a melt of " + m + " and " + r
+                    + " mm remaining rather than " + expectedMelt(row * 5.0, col - 3.0)
+                break
+            }
+        }
+    }
+    melt.close()
+    remaining.close()
+    if (failure != null) {
+        return failure
+    }
+
+    // chained daily, with a higher melt factor and threshold, the snowpack
+    // melts completely and the total melt equals the initial SWE
+    double[] totalMelt = new double[cols]
+    String currentFile = sweFile
+    for (int day = 1; day <= 10; day++) {
+        String nextFile = outputDir + "melt_loop_swe_" + day + ".dep"
+        String dayMeltFile = outputDir + "melt_loop_" + day + ".dep"
+        runPlugin("TemperatureIndexMelt", [currentFile, tempFile, dayMeltFile, nextFile, "6.0", "-1.0"])
+        WhiteboxRaster dayMelt = new WhiteboxRaster(dayMeltFile, "r")
+        for (int col = 0; col < cols; col++) {
+            totalMelt[col] += dayMelt.getValue(rows - 1, col) == dayMelt.getNoDataValue() ? 0 : dayMelt.getValue(rows - 1, col)
+        }
+        dayMelt.close()
+        currentFile = nextFile
+    }
+    WhiteboxRaster last = new WhiteboxRaster(currentFile, "r")
+    for (int col = 0; col < cols - 1; col++) {
+        double expected = col - 3.0 > -1.0 ? 45.0 : 0.0
+        if (Math.abs(totalMelt[col] - expected) > 1e-3 || Math.abs(last.getValue(rows - 1, col) - (45.0 - expected)) > 1e-3) {
+            last.close()
+            return "column " + col + " melted " + totalMelt[col] + " mm over 10 days rather than " + expected
+        }
+    }
+    last.close()
+    return null
+})
+
+// Every cell of a plane sloping to the south flows south, so the upslope area
+// of a cell is the part of its column above it. The attribute is 10 * row +
+// col, except for a NoData cell in column 2, which is left out of the
+// statistics of the cells below it but does not stop them from being found.
+check("upslopestatistics_d8_plane", {
+    int rows = 12
+    int cols = 7
+    String pntrFile = outputDir + "upslope_pntr.dep"
+    String attributeFile = outputDir + "upslope_attribute.dep"
+    WhiteboxRaster pntr = new WhiteboxRaster(pntrFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.INTEGER, 0.0, -32768.0)
+    WhiteboxRaster attribute = new WhiteboxRaster(attributeFile, "rw", pntrFile, DataType.FLOAT, 0.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            pntr.setValue(row, col, row < rows - 1 ? 8.0 : 0.0)
+            attribute.setValue(row, col, row == 3 && col == 2 ? -32768.0 : 10.0 * row + col)
+        }
+    }
+    pntr.close()
+    attribute.close()
+    Map<String, Closure> statistics = [
+        "max": { List<Double> v -> v.max() },
+        "min": { List<Double> v -> v.min() },
+        "mean": { List<Double> v -> v.sum() / v.size() },
+        "sum": { List<Double> v -> v.sum() }
+    ]
+    for (entry in statistics) {
+        String outputFile = outputDir + "upslope_d8_" + entry.key + ".dep"
+        runPlugin("UpslopeStatistics", [pntrFile, attributeFile, outputFile, entry.key, "D8", "not specified"])
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        try {
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    List<Double> upslope = (0..row).findAll { !(it == 3 && col == 2) }.collect { 10.0 * it + col }
+                    double expected = entry.value(upslope)
+                    double z = output.getValue(row, col)
+                    if (Math.abs(z - expected) > 1e-4) {
+                        return "the upslope " + entry.key + " of cell (" + row + ", " + col + ") is " + z + " rather than " + expected
+                    }
+                }
+            }
+        } finally {
+            output.close()
+        }
+    }
+    return null
+})
+
+// Under FD8 on the same plane, each cell sends equal shares of its flow to
+// the three cells below it, or two at the sides of the grid. The cells that
+// drain to a cell lie within a cone widening by a column on either side per
+// row upslope, so the upslope max and min of the column number are found from
+// its edges. The mean of a constant attribute is that constant, and the sum
+// of an attribute of one over the last row is the number of cells.
+check("upslopestatistics_fd8_plane", {
+    int rows = 12
+    int cols = 7
+    String demFile = outputDir + "upslope_dem.dep"
+    String columnFile = outputDir + "upslope_column.dep"
+    String onesFile = outputDir + "upslope_ones.dep"
+    WhiteboxRaster dem = new WhiteboxRaster(demFile, (double)rows, 0.0, (double)cols, 0.0,
+        rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+    WhiteboxRaster column = new WhiteboxRaster(columnFile, "rw", demFile, DataType.FLOAT, 0.0)
+    WhiteboxRaster ones = new WhiteboxRaster(onesFile, "rw", demFile, DataType.FLOAT, 0.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            dem.setValue(row, col, rows - row)
+            column.setValue(row, col, col)
+            ones.setValue(row, col, 1.0)
+        }
+    }
+    dem.close()
+    column.close()
+    ones.close()
+    def readOutput = { String attributeFile, String statistic ->
+        String outputFile = outputDir + "upslope_fd8_" + statistic + ".dep"
+        runPlugin("UpslopeStatistics", [demFile, attributeFile, outputFile, statistic, "FD8", "1.1"])
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        double[][] values = (0..<rows).collect { output.getRowValues(it) } as double[][]
+        output.close()
+        values
+    }
+    double[][] max = readOutput(columnFile, "max")
+    double[][] min = readOutput(columnFile, "min")
+    double[][] mean = readOutput(onesFile, "mean")
+    double[][] sum = readOutput(onesFile, "sum")
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            String cell = "cell (" + row + ", " + col + ")"
+            if (max[row][col] != Math.min(col + row, cols - 1) || min[row][col] != Math.max(col - row, 0)) {
+                return cell + " has an upslope range of " + min[row][col] + " to " + max[row][col]
+            }
+            if (Math.abs(mean[row][col] - 1) > 1e-6) {
+                return cell + " has an upslope mean of " + mean[row][col] + " rather than 1"
+            }
+        }
+    }
+    double total = (sum[rows - 1] as List<Double>).sum()
+    if (Math.abs(total - rows * cols) > 1e-3) {
+        return "the sums of the last row total " + total + " rather than " + (rows * cols)
+    }
+    return null
+})
+
+// Outlets at interior cells of a serpentine drain every cell upstream of them
+// along the flowpath, so the upper outlet, which lies 20 cells along the path,
+// is nested within the lower one, 45 cells along. The full areas are reported
+// for both outlets however the nested cells are labelled.
+check("contributingareafromoutlet_nested", {
+    int rows = 6
+    int cols = 10
+    String pntrFile = outputDir + "contributing_pntr.dep"
+    String outletFile = outputDir + "contributing_outlets.dep"
+    createSerpentine(pntrFile, rows, cols)
+    WhiteboxRaster outlets = new WhiteboxRaster(outletFile, "rw", pntrFile, DataType.INTEGER, 0.0)
+    outlets.setValue(2, 0, 5.0)
+    outlets.setValue(4, 5, 7.0)
+    outlets.close()
+    for (String rule in ["downstream outlet", "upstream outlet"]) {
+        String outputFile = outputDir + "contributing_" + rule.split(" ")[0] + ".dep"
+        returned.clear()
+        runPlugin("ContributingAreaFromOutlet", [pntrFile, outletFile, outputFile, rule])
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        try {
+            double noData = output.getNoDataValue()
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    int along = row * cols + (row % 2 == 0 ? col : cols - 1 - col)
+                    double expected = along > 45 ? noData : (along <= 20 && rule.startsWith("up") ? 5.0 : 7.0)
+                    if (output.getValue(row, col) != expected) {
+                        return "with the " + rule + " rule, cell (" + row + ", " + col + ") is labelled " + output.getValue(row, col) + " rather than " + expected
+                    }
+                }
+            }
+        } finally {
+            output.close()
+        }
+        String report = returned.find { it.startsWith("CONTRIBUTING AREA") }
+        if (report == null || !report.contains("5\t21\t21.0\t1\n") || !report.contains("7\t46\t46.0\t0\n")) {
+            return "with the " + rule + " rule, the report does not give the full areas: " + report
+        }
+    }
+    return null
+})
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
@@ -0,0 +1,653 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Tests for the raster math and statistics tools. Each test runs a plugin on the small
+// synthetic rasters in tests/data that are also used by the filter tests
+// and checks that the output values are related to the input values as
+// they should be, cell by cell. NoData cells must stay NoData. The Boolean
+// masks of the Threshold and IsNoData tools are compared with expected
+// rasters in tests/expected, which are regenerated by running this script
+// with the argument 'regenerate'.
+//
+// The tests are run against the compiled plugins with 'python build.py
+// runtests'.
+
+def testDir = "tests" + File.separator
+def dataDir = testDir + "data" + File.separator
+def outputDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+def expectedDir = testDir + "expected" + File.separator
+boolean regenerate = args.length > 0 && args[0].toLowerCase().equals("regenerate")
+double tolerance = 0.0001
+
+def inputs = ["surface", "nodata", "row", "uniform"]
+
+List<String> returned = []
+List<String> feedback = []
+boolean cancelRequested = false
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; feedback << a[0].toString(); 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> cancelRequested },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+// runs one of the Groovy script tools in resources/plugins/Scripts
+def runScript = { String name, List<String> scriptArgs ->
+    Binding binding = new Binding()
+    binding.setVariable("pluginHost", host)
+    binding.setVariable("args", scriptArgs as String[])
+    new GroovyShell(binding).evaluate(new File("resources/plugins/Scripts/" + name + ".groovy").text, name + "Script")
+}
+
+// returns the values of a raster in row-major order, with null for NoData
+def readValues = { String file ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+    List<Double> values = []
+    for (int row = 0; row < raster.getNumberRows(); row++) {
+        for (double z : raster.getRowValues(row)) {
+            values << (z == raster.getNoDataValue() ? null : z)
+        }
+    }
+    raster.close()
+    values
+}
+
+// checks that the output is NoData wherever the input is NoData
+def checkNoData = { List<Double> input, List<Double> output, List<String> failures ->
+    for (int i = 0; i < input.size(); i++) {
+        if ((input[i] == null) != (output[i] == null)) {
+            failures << "cell " + i + " is " + output[i] + " but the input is " + input[i]
+            return
+        }
+    }
+}
+
+// checks that each output value is the given function of the input value,
+// and that the output is NoData where the input is NoData or the function is
+// undefined
+def checkCellwise = { List<Double> input, List<Double> output, Closure expected, List<String> failures ->
+    for (int i = 0; i < input.size(); i++) {
+        Double z = input[i] == null ? null : expected(input[i])
+        if (z != null && (z.isNaN() || z.isInfinite())) {
+            z = null
+        }
+        if ((z == null) != (output[i] == null)) {
+            failures << "cell " + i + " is " + output[i] + " but the input is " + input[i]
+            return
+        }
+        if (z != null && Math.abs(output[i] - z) > tolerance) {
+            failures << "cell " + i + " is " + output[i] + " rather than " + z
+            return
+        }
+    }
+}
+
+// name, plugin, the plugin arguments following the input and output files,
+// and a check of the output values against the input values
+def tests = [
+    ["invert_linear", "InvertRaster", ["linear", "not specified", "not specified"], { List<Double> input, List<Double> output, List<String> failures ->
+        double sum = input.findAll { it != null }.min() + input.findAll { it != null }.max()
+        checkCellwise(input, output, { sum - it }, failures)
+    }],
+    ["invert_linear_rescaled", "InvertRaster", ["linear", "100", "200"], { List<Double> input, List<Double> output, List<String> failures ->
+        double min = input.findAll { it != null }.min()
+        double max = input.findAll { it != null }.max()
+        checkCellwise(input, output, { max > min ? 100 + (max - it) / (max - min) * 100 : 100 }, failures)
+    }],
+    ["invert_rank", "InvertRaster", ["rank", "not specified", "not specified"], { List<Double> input, List<Double> output, List<String> failures ->
+        // lower values have higher ranks and tied values have equal ranks
+        checkNoData(input, output, failures)
+        def valid = (0..<input.size()).findAll { input[it] != null }
+        if (valid.any { output[it] < 0 || output[it] > 1 }) {
+            failures << "the ranks are not between 0 and 1"
+        }
+        for (int i : valid) {
+            for (int j : valid) {
+                if (input[i] < input[j] && !(output[i] > output[j])) {
+                    failures << "cells " + i + " and " + j + " are not in inverse order"
+                    return
+                }
+                if (input[i] == input[j] && output[i] != output[j]) {
+                    failures << "cells " + i + " and " + j + " are tied but have different ranks"
+                    return
+                }
+            }
+        }
+        if (input.findAll { it != null }.unique().size() > 1 && (output.findAll { it != null }.min() != 0
+                || output.findAll { it != null }.max() != 1)) {
+            failures << "the ranks do not span the range 0 to 1"
+        }
+    }],
+    ["round", "Round", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.rint(it) }, failures)
+    }],
+    ["round_decimals", "Round", ["2", "not specified"], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.rint(it * 100) / 100 }, failures)
+    }],
+    ["round_multiple", "Round", ["not specified", "0.25"], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.rint(it / 0.25) * 0.25 }, failures)
+    }],
+    ["clamp", "Clamp", ["5", "20"], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.min(Math.max(it, 5), 20) }, failures)
+    }],
+    ["clamp_max", "Clamp", ["not specified", "7.5"], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.min(it, 7.5) }, failures)
+    }],
+    ["sign", "Sign", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.signum(it) }, failures)
+    }],
+    // the trigonometric tools take the input in degrees
+    ["sin", "Sin", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.sin(Math.toRadians(it)) }, failures)
+    }],
+    ["cos", "Cos", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.cos(Math.toRadians(it)) }, failures)
+    }],
+    ["tan", "Tan", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.tan(Math.toRadians(it)) }, failures)
+    }],
+    ["arctan", "ArcTan", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.atan(it) }, failures)
+    }],
+    // the inputs include zeros, whose logarithms are NoData
+    ["ln", "Ln", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.log(it) }, failures)
+    }],
+    ["log10", "Log10", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.log10(it) }, failures)
+    }],
+    ["sqrt", "SqrRt", [], { List<Double> input, List<Double> output, List<String> failures ->
+        checkCellwise(input, output, { Math.sqrt(it) }, failures)
+    }],
+    ["exp", "Exp", [], { List<Double> input, List<Double> output, List<String> failures ->
+        // the output is stored as floats, so large values are compared relatively
+        List<Double> ratios = (0..<output.size()).collect { int i ->
+            output[i] == null || input[i] == null ? output[i] : output[i] / Math.exp(input[i])
+        }
+        checkCellwise(input, ratios, { 1.0d }, failures)
+    }]
+]
+
+int numTests = 0
+int numFailed = 0
+tests.each { test ->
+    inputs.each { inputName ->
+        String name = test[0] + "_" + inputName
+        String inputFile = dataDir + inputName + ".dep"
+        String outputFile = outputDir + name + ".dep"
+        numTests++
+        runPlugin(test[1], [inputFile, outputFile] + test[2])
+
+        List<String> failures = []
+        if (!new File(outputFile).exists()) {
+            failures << "no output was created"
+        } else {
+            test[3](readValues(inputFile), readValues(outputFile), failures)
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it }
+        }
+    }
+}
+
+// The unary tools whose functions are undefined for some values, and Power,
+// are run on a raster of values from -2 to 2, including zero and a NoData
+// cell. The undefined cells must be NoData in the output rather than NaN.
+String signedFile = outputDir + "signed.dep"
+WhiteboxRaster signed = new WhiteboxRaster(signedFile, 3.0, 0.0, 7.0, 0.0, 3, 7,
+    DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, -32768.0)
+for (int i = 0; i < 21; i++) {
+    signed.setValue(i.intdiv(7), i % 7, i == 3 ? -32768.0 : -2.0 + 0.2 * i)
+}
+signed.close()
+[
+    ["domain_ln", "Ln", [signedFile], { Math.log(it) }],
+    ["domain_log10", "Log10", [signedFile], { Math.log10(it) }],
+    ["domain_log2", "Log2", [signedFile], { Math.log(it) / Math.log(2) }],
+    ["domain_sqrt", "SqrRt", [signedFile], { Math.sqrt(it) }],
+    ["domain_arcsin", "ArcSin", [signedFile], { Math.asin(it) }],
+    ["domain_arccos", "ArcCos", [signedFile], { Math.acos(it) }],
+    ["domain_power_half", "Power", [signedFile, "0.5"], { Math.pow(it, 0.5) }],
+    ["domain_power_square", "Power", [signedFile, "2"], { it * it }],
+    ["domain_power_reciprocal", "Power", [signedFile, "-1"], { Math.pow(it, -1) }],
+    ["domain_power_base", "Power", ["-2", signedFile], { Math.pow(-2, it) }]
+].each { test ->
+    String name = test[0]
+    String outputFile = outputDir + name + ".dep"
+    numTests++
+    runPlugin(test[1], test[2] + [outputFile])
+
+    List<String> failures = []
+    if (!new File(outputFile).exists()) {
+        failures << "no output was created"
+    } else {
+        List<Double> output = readValues(outputFile)
+        if (output.any { it != null && (it.isNaN() || it.isInfinite()) }) {
+            failures << "the output contains NaN or infinite values"
+        }
+        checkCellwise(readValues(signedFile), output, test[3], failures)
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+// The arithmetic and comparison tools are run on two co-registered rasters,
+// and on a raster and a constant in either order. The output is NoData where
+// either input is NoData and, for Divide, where the divisor is zero, and the
+// comparisons are 1 where true and 0 where false. Rasters that have the same
+// dimensions but not the same coordinates are refused.
+String otherFile = outputDir + "other.dep"
+String shiftedFile = outputDir + "shifted.dep"
+[[otherFile, 0.0], [shiftedFile, 1.0]].each { pair ->
+    WhiteboxRaster other = new WhiteboxRaster(pair[0], 3.0, 0.0, 7.0 + pair[1], pair[1], 3, 7,
+        DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, -9999.0)
+    for (int i = 0; i < 21; i++) {
+        other.setValue(i.intdiv(7), i % 7, i == 12 ? -9999.0 : (i % 5) - 1.0)
+    }
+    other.close()
+}
+[
+    ["arithmetic_add", "Add", [signedFile, otherFile], { x, y -> x + y }],
+    ["arithmetic_add_constant", "Add", [signedFile, "1.5"], { x, y -> x + y }],
+    ["arithmetic_subtract", "Subtract", [signedFile, otherFile], { x, y -> x - y }],
+    ["arithmetic_subtract_from_constant", "Subtract", ["10", signedFile], { x, y -> x - y }],
+    ["arithmetic_multiply", "Multiply", [signedFile, otherFile], { x, y -> x * y }],
+    ["arithmetic_multiply_constant", "Multiply", [signedFile, "-3"], { x, y -> x * y }],
+    ["arithmetic_divide", "Divide", [signedFile, otherFile], { x, y -> x / y }],
+    ["arithmetic_divide_by_zero", "Divide", [signedFile, "0"], { x, y -> x / y }],
+    ["arithmetic_divide_constant", "Divide", ["1", signedFile], { x, y -> x / y }],
+    ["comparison_greater_than", "GreaterThan", [signedFile, otherFile], { x, y -> x > y ? 1 : 0 }],
+    ["comparison_greater_than_constant", "GreaterThan", ["0.5", signedFile], { x, y -> x > y ? 1 : 0 }],
+    ["comparison_greater_than_equal_to", "GreaterThanEqualTo", [signedFile, "0"], { x, y -> x >= y ? 1 : 0 }],
+    ["comparison_less_than", "LessThan", [otherFile, signedFile], { x, y -> x < y ? 1 : 0 }],
+    ["comparison_less_than_constant", "LessThan", ["-1", signedFile], { x, y -> x < y ? 1 : 0 }],
+    ["comparison_less_than_equal_to", "LessThanEqualTo", ["0", signedFile], { x, y -> x <= y ? 1 : 0 }],
+    ["comparison_equal_to", "EqualTo", [otherFile, "1"], { x, y -> x == y ? 1 : 0 }],
+    ["comparison_equal_to_constant", "EqualTo", ["0", signedFile], { x, y -> x == y ? 1 : 0 }],
+    ["comparison_not_equal_to", "NotEqualTo", [signedFile, otherFile], { x, y -> x != y ? 1 : 0 }]
+].each { test ->
+    String name = test[0]
+    String outputFile = outputDir + name + ".dep"
+    numTests++
+    runPlugin(test[1], test[2] + [outputFile])
+
+    List<String> failures = []
+    if (!new File(outputFile).exists()) {
+        failures << "no output was created"
+    } else {
+        List<List<Double>> operands = test[2].collect { String arg ->
+            new File(arg).exists() ? readValues(arg) : [Double.parseDouble(arg)] * 21
+        }
+        List<Double> output = readValues(outputFile)
+        for (int i = 0; i < output.size() && failures.isEmpty(); i++) {
+            Double x = operands[0][i]
+            Double y = operands[1][i]
+            Double z = (x == null || y == null) ? null : (double) test[3](x, y)
+            if (z != null && (z.isNaN() || z.isInfinite())) {
+                z = null
+            }
+            if ((z == null) != (output[i] == null)) {
+                failures << "cell " + i + " is " + output[i] + " but the inputs are " + x + " and " + y
+            } else if (z != null && Math.abs(output[i] - z) > tolerance) {
+                failures << "cell " + i + " is " + output[i] + " rather than " + z
+            }
+        }
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+["Add", "Subtract", "Multiply", "Divide", "GreaterThan", "LessThan", "EqualTo"].each { plugin ->
+    String name = plugin.toLowerCase() + "_not_coregistered"
+    String outputFile = outputDir + name + ".dep"
+    numTests++
+    feedback.clear()
+    runPlugin(plugin, [signedFile, shiftedFile, outputFile])
+    if (!new File(outputFile).exists() && feedback.any { it.contains("same dimensions and coordinates") }) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    rasters with different coordinates were not refused"
+    }
+}
+
+// Geographically weighted statistics are compared with a direct calculation
+// of the weighted moments over the cells within the bandwidth of each cell.
+double bandwidth = 2.0
+def gwStatistics = { List<Double> xs, List<Double> ys, int rows, int cols, int row, int col ->
+    double sumW = 0, sumX = 0, sumY = 0
+    List<double[]> terms = []
+    for (int r = 0; r < rows; r++) {
+        for (int c = 0; c < cols; c++) {
+            double d = Math.sqrt((r - row) * (r - row) + (c - col) * (c - col))
+            Double x = xs[r * cols + c]
+            Double y = ys[r * cols + c]
+            if (d <= bandwidth && x != null && y != null) {
+                double w = Math.exp(-0.5 * (d / bandwidth) * (d / bandwidth))
+                terms << ([w, x, y] as double[])
+                sumW += w
+                sumX += w * x
+                sumY += w * y
+            }
+        }
+    }
+    double meanX = sumX / sumW
+    double meanY = sumY / sumW
+    double varX = terms.sum { it[0] * (it[1] - meanX) * (it[1] - meanX) } / sumW
+    double varY = terms.sum { it[0] * (it[2] - meanY) * (it[2] - meanY) } / sumW
+    double covariance = terms.sum { it[0] * (it[1] - meanX) * (it[2] - meanY) } / sumW
+    double third = terms.sum { it[0] * Math.pow(it[1] - meanX, 3) } / sumW
+    [mean: meanX, std_dev: Math.sqrt(varX),
+        skewness: varX > 1e-12 ? third / Math.pow(varX, 1.5) : null,
+        correlation: varX > 1e-12 && varY > 1e-12 ? covariance / Math.sqrt(varX * varY) : null]
+}
+
+[["surface", "nodata"], ["nodata", "surface"], ["uniform", "uniform"]].each { pair ->
+    String name = "gw_statistics_" + pair[0]
+    String prefix = outputDir + name
+    numTests++
+    runPlugin("GeographicallyWeightedStatistics", [dataDir + pair[0] + ".dep", dataDir + pair[1] + ".dep",
+        prefix + ".dep", bandwidth.toString(), "true", "true", "true", "true"])
+
+    List<String> failures = []
+    List<Double> xs = readValues(dataDir + pair[0] + ".dep")
+    List<Double> ys = readValues(dataDir + pair[1] + ".dep")
+    WhiteboxRaster input = new WhiteboxRaster(dataDir + pair[0] + ".dep", "r")
+    int rows = input.getNumberRows()
+    int cols = input.getNumberColumns()
+    input.close()
+    ["mean", "std_dev", "skewness", "correlation"].each { statistic ->
+        if (!failures.isEmpty()) {
+            return
+        }
+        String outputFile = prefix + "_" + statistic + ".dep"
+        if (!new File(outputFile).exists()) {
+            failures << "no " + statistic + " output was created"
+            return
+        }
+        List<Double> output = readValues(outputFile)
+        for (int i = 0; i < xs.size() && failures.isEmpty(); i++) {
+            Double expected = null
+            if (xs[i] != null && (statistic != "correlation" || ys[i] != null)) {
+                expected = gwStatistics(xs, statistic == "correlation" ? ys : xs, rows, cols, i.intdiv(cols), i % cols)[statistic]
+            }
+            if ((expected == null) != (output[i] == null)
+                    || (expected != null && Math.abs(output[i] - expected) > tolerance)) {
+                failures << "the " + statistic + " of cell " + i + " is " + output[i] + " rather than " + expected
+            }
+        }
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+// The standard error of a product of two rasters, a * b, with standard
+// errors sa and sb is sqrt(b^2 sa^2 + a^2 sb^2) to first order, and Monte
+// Carlo simulation gives nearly the same value. A NoData input cell is
+// NoData in the outputs.
+numTests++
+String name = "error_propagation_product"
+List<String> failures = []
+int rows = 5
+int cols = 6
+def createRaster = { String file, Closure value ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0, rows, cols,
+        DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, -32768.0)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            raster.setValue(row, col, value(row, col))
+        }
+    }
+    raster.close()
+}
+createRaster(outputDir + "a.dep", { int row, int col -> (row == 2 && col == 3) ? -32768.0 : row + 1.0 })
+createRaster(outputDir + "b.dep", { int row, int col -> col + 2.0 })
+createRaster(outputDir + "a_error.dep", { int row, int col -> 0.1 * (row + 1.0) })
+returned.clear()
+runScript("ErrorPropagation", [outputDir + "a.dep;" + outputDir + "b.dep", outputDir + "a_error.dep;0.2",
+    "[a] * x2", outputDir + "product.dep", outputDir + "product_error.dep", "20000", outputDir + "product_mc_error.dep"])
+if (!new File(outputDir + "product_mc_error.dep").exists()) {
+    failures << "no output was created"
+} else {
+    List<Double> mean = readValues(outputDir + "product.dep")
+    List<Double> error = readValues(outputDir + "product_error.dep")
+    List<Double> monteCarlo = readValues(outputDir + "product_mc_error.dep")
+    for (int i = 0; i < rows * cols && failures.isEmpty(); i++) {
+        double a = i.intdiv(cols) + 1.0
+        double b = i % cols + 2.0
+        double expected = Math.sqrt(b * b * 0.01 * a * a + a * a * 0.04)
+        if (i == 2 * cols + 3) {
+            if (mean[i] != null || error[i] != null || monteCarlo[i] != null) {
+                failures << "the NoData cell is not NoData in the outputs"
+            }
+        } else if (Math.abs(mean[i] - a * b) > tolerance) {
+            failures << "the mean of cell " + i + " is " + mean[i] + " rather than " + a * b
+        } else if (Math.abs(error[i] - expected) > tolerance) {
+            failures << "the standard error of cell " + i + " is " + error[i] + " rather than " + expected
+        } else if (Math.abs(monteCarlo[i] - expected) > 0.05 * expected) {
+            failures << "the Monte Carlo standard error of cell " + i + " is " + monteCarlo[i] + " rather than about " + expected
+        }
+    }
+    if (!returned.any { it.contains("Mean Monte Carlo standard error") }) {
+        failures << "the Monte Carlo comparison was not reported"
+    }
+}
+if (failures.isEmpty()) {
+    println "PASSED " + name
+} else {
+    numFailed++
+    println "FAILED " + name
+    failures.each { println "    " + it }
+}
+
+// A Threshold bound that is neither an existing raster nor a number is
+// reported by name, rather than as a failure of the tool, and no output is
+// created. A cancelled run discards its output.
+numTests++
+name = "threshold_bad_bound"
+failures = []
+String missingFile = outputDir + "missing" + File.separator + "bound.dep"
+for (List<String> bounds : [[missingFile, "not specified", ">"], ["0", missingFile, "between"]]) {
+    String outputFile = outputDir + name + ".dep"
+    feedback.clear()
+    runScript("Threshold", [outputDir + "a.dep", outputFile, bounds[2], bounds[0], bounds[1]])
+    if (new File(outputFile).exists()) {
+        failures << "an output was created for the " + bounds[2] + " operator"
+    } else if (!feedback.any { it == "'" + missingFile + "' is neither an existing raster nor a number." }) {
+        failures << "the missing bound was reported as " + feedback
+    }
+}
+// A cancelled Threshold run leaves an existing output untouched.
+String cancelledFile = outputDir + "threshold_cancelled.dep"
+runScript("Threshold", [outputDir + "a.dep", cancelledFile, ">", "2"])
+byte[] completedData = new File(cancelledFile.replace(".dep", ".tas")).bytes
+cancelRequested = true
+runScript("Threshold", [outputDir + "a.dep", cancelledFile, "<", "2"])
+cancelRequested = false
+if (new File(cancelledFile.replace(".dep", ".tas")).bytes != completedData) {
+    failures << "a cancelled run replaced the existing output"
+}
+if (failures.isEmpty()) {
+    println "PASSED " + name
+} else {
+    numFailed++
+    println "FAILED " + name
+    failures.each { println "    " + it }
+}
+
+// The Threshold masks cover each operator against a constant and against a
+// raster, 'between' with each combination of inclusive bounds, bounds given
+// as rasters, a NoData background, and inputs whose NoData value of 0 or 1
+// would be confused with the mask's values. The input has integer values
+// from -3 to 3, so that the constant bounds fall on cell values, and NoData
+// cells; the bound rasters have NoData cells of their own.
+String maskDir = regenerate ? expectedDir : outputDir
+int maskRows = 4
+int maskCols = 6
+def createMaskInput = { String file, double noData, Closure value ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, (double)maskRows, 0.0, (double)maskCols, 0.0,
+        maskRows, maskCols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, noData)
+    for (int i = 0; i < maskRows * maskCols; i++) {
+        raster.setValue(i.intdiv(maskCols), i % maskCols, value(i))
+    }
+    raster.close()
+    file
+}
+String maskInput = createMaskInput(outputDir + "mask_input.dep", -32768.0,
+    { int i -> i in [8, 17] ? -32768.0 : (i % 7) - 3.0 })
+String lowerBound = createMaskInput(outputDir + "mask_lower.dep", -9999.0,
+    { int i -> i == 10 ? -9999.0 : (i % 5) - 2.0 })
+String upperBound = createMaskInput(outputDir + "mask_upper.dep", -9999.0,
+    { int i -> i == 20 ? -9999.0 : (i % 5) * 1.0 })
+String noDataZeroInput = createMaskInput(outputDir + "mask_nodata_zero.dep", 0.0,
+    { int i -> i in [5, 13] ? 0.0 : (i % 4) + 1.0 })
+String noDataOneInput = createMaskInput(outputDir + "mask_nodata_one.dep", 1.0,
+    { int i -> i in [7, 19] ? 1.0 : (i % 4) + 2.0 })
+
+def compareWithExpected = { String maskName, String outputFile ->
+    numTests++
+    if (regenerate) {
+        println "Regenerated " + maskName
+        return
+    }
+    String failure = null
+    if (!new File(outputFile).exists()) {
+        failure = "no output was created"
+    } else {
+        returned.clear()
+        runPlugin("CompareRasters", [expectedDir + maskName + ".dep", outputFile, tolerance.toString(), "5"])
+        String report = returned.isEmpty() ? "" : returned[0]
+        if (!report.contains("Result:\tIDENTICAL")) {
+            failure = "the output differs from the expected raster\n" + report
+        }
+    }
+    if (failure == null) {
+        println "PASSED " + maskName
+    } else {
+        numFailed++
+        println "FAILED " + maskName
+        println "    " + failure.replace("\n", "\n    ")
+    }
+}
+
+// name, input, and the Threshold arguments following the input and output
+// files: operator, lower bound, upper bound, whether the lower and upper
+// bounds are inclusive, zero background and output data type
+List<List> masks = []
+[["lt", "<"], ["le", "<="], ["gt", ">"], ["ge", ">="], ["eq", "=="], ["ne", "!="]].each { op ->
+    masks << ["threshold_" + op[0] + "_constant", maskInput, [op[1], "0"]]
+    masks << ["threshold_" + op[0] + "_raster", maskInput, [op[1], lowerBound]]
+}
+[[true, true, "inclusive"], [false, true, "lower_exclusive"], [true, false, "upper_exclusive"],
+        [false, false, "exclusive"]].each { flags ->
+    masks << ["threshold_between_" + flags[2], maskInput,
+        ["between", "-1", "2", flags[0].toString(), flags[1].toString()]]
+}
+masks << ["threshold_between_rasters", maskInput, ["between", lowerBound, upperBound, "true", "false"]]
+masks << ["threshold_nodata_background", maskInput, [">=", "1", "not specified", "true", "true", "false"]]
+masks << ["threshold_nodata_zero", noDataZeroInput, [">", "2"]]
+masks << ["threshold_nodata_zero_background", noDataZeroInput, [">", "2", "not specified", "true", "true", "false"]]
+masks << ["threshold_nodata_one", noDataOneInput, ["<=", "3"]]
+masks.each { mask ->
+    String outputFile = maskDir + mask[0] + ".dep"
+    runScript("Threshold", [mask[1], outputFile] + mask[2])
+    compareWithExpected(mask[0], outputFile)
+}
+[["isnodata", maskInput], ["isnodata_nodata_zero", noDataZeroInput]].each { mask ->
+    String outputFile = maskDir + mask[0] + ".dep"
+    runPlugin("IsNoData", [mask[1], outputFile])
+    compareWithExpected(mask[0], outputFile)
+}
+
+// The mask is stored using the smallest integer type that holds 0, 1 and its
+// NoData value, which is that of the input unless it is 0 or 1, when it falls
+// back to -32768. Non-integer and large NoData values need floats, as does a
+// mask for which floats are requested.
+if (!regenerate) {
+    numTests++
+    name = "threshold_output_type"
+    failures = []
+    [
+        [-32768.0, "smallest integer", DataType.INTEGER, -32768.0],
+        [0.0, "smallest integer", DataType.INTEGER, -32768.0],
+        [1.0, "smallest integer", DataType.INTEGER, -32768.0],
+        [255.0, "smallest integer", DataType.BYTE, 255.0],
+        [-9999.0, "smallest integer", DataType.INTEGER, -9999.0],
+        [100000.0, "smallest integer", DataType.FLOAT, 100000.0],
+        [2.5, "smallest integer", DataType.FLOAT, 2.5],
+        [255.0, "float", DataType.FLOAT, 255.0]
+    ].each { List test ->
+        String inputFile = createMaskInput(outputDir + "type_input.dep", (double) test[0],
+            { int i -> i == 0 ? (double) test[0] : (double) ((i % 3) * 3 - 4) })
+        String outputFile = outputDir + "type_output.dep"
+        runScript("Threshold", [inputFile, outputFile, ">", "1", "not specified", "true", "true", "true", test[1]])
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        if (output.getDataType() != test[2] || output.getNoDataValue() != test[3]) {
+            failures << "an input NoData value of " + test[0] + " gave a " + output.getDataType() +
+                " mask with a NoData value of " + output.getNoDataValue()
+        } else if (output.getValue(0, 0) != output.getNoDataValue() || output.getValue(0, 2) != 1.0 ||
+                output.getValue(0, 1) != 0.0) {
+            failures << "the mask of an input with a NoData value of " + test[0] + " is wrong"
+        }
+        output.close()
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
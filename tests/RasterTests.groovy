@@ -0,0 +1,1041 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import java.util.Arrays
+import whitebox.geospatialfiles.GeoTiff
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.WhiteboxRasterStreamWriter
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+import whitebox.plugins.OutputFileGuard
+import whitebox.utilities.FileUtilities
+import whitebox.utilities.Parallel
+
+// Tests for the raster classes of the Whitebox API. New rasters are written
+// to temporary files that replace any existing raster of the same name when
+// they are closed, and two writers of the same raster are not allowed.
+// Memory-mapped rasters must return the same values as buffered ones and be
+// quicker to open and read sparsely. GeoTIFF files must be read exactly,
+// whatever their layout and compression, and percentiles found from
+// streaming histograms must match those of the sorted data. Only relative
+// file names are resolved against the working directory.
+//
+// The tests are run against the compiled API with 'python build.py
+// runtests'.
+
+def outputDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+
+int numTests = 0
+int numFailed = 0
+def check = { String name, Closure test ->
+    numTests++
+    String failure
+    try {
+        failure = test()
+    } catch (Throwable e) {
+        failure = "unexpected exception: " + e
+    }
+    if (failure == null) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        println "    " + failure
+    }
+}
+
+def createRaster = { String file, double value ->
+    WhiteboxRaster raster = new WhiteboxRaster(file, 10.0, 0.0, 10.0, 0.0, 10, 10,
+        DataScale.CONTINUOUS, DataType.FLOAT, value, -32768.0)
+    for (int row = 0; row < 10; row++) {
+        for (int col = 0; col < 10; col++) {
+            raster.setValue(row, col, value)
+        }
+    }
+    raster
+}
+
+// the names of the files in the output directory that belong to a raster,
+// other than its header and data files
+def leftoverFiles = { String file ->
+    String name = new File(file).getName().replace(".dep", "")
+    new File(outputDir).list().findAll { it.startsWith(name + ".") && !(it in [name + ".dep", name + ".tas"]) }
+}
+
+// The existing raster's files are unchanged until the new raster is closed.
+check("raster_replaced_on_close", {
+    String file = outputDir + "replaced.dep"
+    createRaster(file, 1.0).close()
+    byte[] header = new File(file).bytes
+    byte[] data = new File(file.replace(".dep", ".tas")).bytes
+    WhiteboxRaster raster = createRaster(file, 2.0)
+    raster.flush()
+    if (new File(file).bytes != header || new File(file.replace(".dep", ".tas")).bytes != data) {
+        return "the existing raster was changed before the new raster was closed"
+    }
+    raster.close()
+    WhiteboxRaster replaced = new WhiteboxRaster(file, "r")
+    double z = replaced.getValue(5, 5)
+    replaced.close()
+    if (z != 2.0) {
+        return "the closed raster has a value of " + z + " rather than 2.0"
+    }
+    if (!leftoverFiles(file).isEmpty()) {
+        return "temporary files remain: " + leftoverFiles(file)
+    }
+    return null
+})
+
+// A tool that fails and discards its output leaves the existing raster
+// untouched.
+check("raster_unchanged_by_discarded_writer", {
+    String file = outputDir + "unchanged.dep"
+    createRaster(file, 1.0).close()
+    WhiteboxRaster raster = createRaster(file, 2.0)
+    raster.flush()
+    raster.isTemporaryFile = true
+    raster.close()
+    WhiteboxRaster unchanged = new WhiteboxRaster(file, "r")
+    double z = unchanged.getValue(5, 5)
+    unchanged.close()
+    if (z != 1.0) {
+        return "the existing raster has a value of " + z + " rather than 1.0"
+    }
+    if (!leftoverFiles(file).isEmpty()) {
+        return "temporary files remain: " + leftoverFiles(file)
+    }
+    return null
+})
+
+check("raster_concurrent_writers", {
+    String file = outputDir + "concurrent.dep"
+    String baseFile = outputDir + "base.dep"
+    createRaster(baseFile, 0.0).close()
+    WhiteboxRaster first = createRaster(file, 1.0)
+    String failure = "a second raster writer of the same file was created"
+    try {
+        new WhiteboxRaster(file, "rw", baseFile, DataType.FLOAT, 0.0)
+    } catch (IllegalStateException e) {
+        failure = null
+    }
+    if (failure == null) {
+        failure = "a second stream writer of the same file was created"
+        try {
+            new WhiteboxRasterStreamWriter(file, baseFile, DataType.FLOAT, -32768.0)
+        } catch (IllegalStateException e) {
+            failure = null
+        }
+    }
+    first.close()
+    if (failure != null) {
+        return failure
+    }
+    // once the first writer has closed, the raster can be written again
+    WhiteboxRasterStreamWriter second = new WhiteboxRasterStreamWriter(file, baseFile, DataType.FLOAT, -32768.0)
+    second.close()
+    if (!leftoverFiles(file).isEmpty()) {
+        return "temporary files remain: " + leftoverFiles(file)
+    }
+    return null
+})
+
+// A closed writer releases its lock straight away, even while it is still
+// referenced, and the stats file of the raster it replaced is deleted so that
+// the statistics are found from the new data.
+check("raster_replaced_lock_and_stats", {
+    String file = outputDir + "restat.dep"
+    createRaster(file, 1.0).close()
+    WhiteboxRaster reader = new WhiteboxRaster(file, "r")
+    double mean = reader.getMean()
+    reader.close()
+    if (mean != 1.0 || !new File(file.replace(".dep", ".wstat")).exists()) {
+        return "the stats file of the first raster was not created"
+    }
+    WhiteboxRaster first = createRaster(file, 3.0)
+    first.close()
+    WhiteboxRaster second = createRaster(file, 5.0)
+    second.close()
+    if (first == null || new File(file + ".lock").exists()) {
+        return "the lock of a closed writer was kept"
+    }
+    if (new File(file.replace(".dep", ".wstat")).exists()) {
+        return "the stats file of the replaced raster remains"
+    }
+    reader = new WhiteboxRaster(file, "r")
+    mean = reader.getMean()
+    reader.close()
+    if (mean != 5.0) {
+        return "the mean of the new raster is " + mean + " rather than 5.0"
+    }
+    return null
+})
+
+// Changing the number of plugin processors while a ForRows call is running
+// on another thread replaces the shared pool for later calls without
+// stopping the running one.
+check("parallel_rows_pool_change", {
+    int processors = whitebox.parallel.Parallel.getPluginProcessors()
+    int rows = 200
+    def task = { int row, double[] data -> Arrays.fill(data, row * 2.0) } as Parallel.RowTask
+    def started = new java.util.concurrent.CountDownLatch(1)
+    def resume = new java.util.concurrent.CountDownLatch(1)
+    String failure = null
+    whitebox.parallel.Parallel.setPluginProcessors(4)
+    Thread running = Thread.start {
+        try {
+            Parallel.ForRows(rows, 3, task, { int row, double[] data ->
+                if (row == 0) {
+                    started.countDown()
+                    resume.await()
+                }
+                if (data[2] != row * 2.0) {
+                    failure = "row " + row + " of the running call has a value of " + data[2]
+                    return false
+                }
+                true
+            } as Parallel.RowSink)
+        } catch (Exception e) {
+            failure = "the running call failed: " + e
+        }
+    }
+    try {
+        started.await()
+        whitebox.parallel.Parallel.setPluginProcessors(2)
+        int received = 0
+        Parallel.ForRows(rows, 3, task, { int row, double[] data -> received++; true } as Parallel.RowSink)
+        if (received != rows) {
+            return "the call with the new pool received " + received + " rows"
+        }
+    } finally {
+        resume.countDown()
+        running.join()
+        whitebox.parallel.Parallel.setPluginProcessors(processors)
+    }
+    return failure
+})
+
+// Only relative file names, including those in a subdirectory, are resolved
+// against the working directory, e.g. the help files of the tool dialogs and
+// the hillshade raster of a layer. Absolute paths are kept as entered, as are
+// drive-letter paths where they are absolute, on Windows.
+check("resolve_relative_paths", {
+    String sep = File.separator
+    String dir = outputDir
+    Map<List<String>, String> cases = [
+        [["dem.dep", dir], dir + "dem.dep"],
+        [["  dem.dep ", dir], dir + "dem.dep"],
+        [["sub" + sep + "dem.dep", dir], dir + "sub" + sep + "dem.dep"],
+        [["dem.dep", dir.substring(0, dir.length() - 1)], dir + "dem.dep"],
+        [[dir + "dem.dep", "/elsewhere/"], dir + "dem.dep"],
+        [["", dir], ""],
+        [["dem.dep", ""], "dem.dep"]
+    ].collectEntries()
+    if (sep == "\\") {
+        cases[["C:\\data\\dem.dep", dir]] = "C:\\data\\dem.dep"
+        cases[["\\\\server\\share\\dem.dep", dir]] = "\\\\server\\share\\dem.dep"
+    } else {
+        cases[["/data/dem.dep", dir]] = "/data/dem.dep"
+    }
+    for (entry in cases) {
+        String resolved = FileUtilities.resolvePath(entry.key[0], entry.key[1])
+        if (resolved != entry.value) {
+            return "'" + entry.key[0] + "' in '" + entry.key[1] + "' resolves to '" + resolved + "' rather than '" + entry.value + "'"
+        }
+    }
+    return null
+})
+
+// A host won't run a tool whose output file exists, leaving the file as it
+// was, unless overwriting has been allowed on the calling thread.
+check("existing_output_guard", {
+    String input = outputDir + "guard_input.dep"
+    String output = outputDir + "guard_output.dep"
+    createRaster(input, 2.0).close()
+    createRaster(output, 7.0).close()
+    String feedback = null
+    def host = [
+        getResourcesDirectory: { -> "resources" + File.separator },
+        showFeedback: { Object[] a -> feedback = a[0]; 0 },
+        logException: { String s, Exception e -> println "    " + s + ": " + e },
+        isRequestForOperationCancelSet: { -> false }
+    ].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+    String[] args = [input, input, output] as String[]
+    // as the hosts' runPlugin methods do
+    def runPlugin = {
+        if (OutputFileGuard.canRun(host, "Add", args)) {
+            WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins.Add").newInstance()
+            plugin.setPluginHost(host)
+            plugin.setArgs(args)
+            plugin.run()
+        }
+    }
+    def outputValue = {
+        WhiteboxRaster raster = new WhiteboxRaster(output, "r")
+        double z = raster.getValue(5, 5)
+        raster.close()
+        z
+    }
+    if (OutputFileGuard.findExistingOutputs("resources", "Add", args) != [output]) {
+        return "the existing outputs are " + OutputFileGuard.findExistingOutputs("resources", "Add", args)
+    }
+    runPlugin()
+    if (outputValue() != 7.0) {
+        return "the existing output was replaced without the overwrite flag"
+    }
+    if (feedback == null || !feedback.contains(output)) {
+        return "the user wasn't told which output exists: " + feedback
+    }
+    OutputFileGuard.setOverwrite(true)
+    try {
+        runPlugin()
+    } finally {
+        OutputFileGuard.setOverwrite(false)
+    }
+    if (outputValue() != 4.0) {
+        return "the output is " + outputValue() + " rather than 4.0 with the overwrite flag"
+    }
+    return null
+})
+
+// Every cell, and cells beyond the edges, of a memory-mapped raster of each
+// data type has the same value as in a buffered raster.
+check("raster_memory_mapped_values", {
+    int rows = 7
+    int cols = 9
+    for (DataType dataType : [DataType.DOUBLE, DataType.FLOAT, DataType.INTEGER, DataType.BYTE]) {
+        String file = outputDir + "mapped_" + dataType.toString().toLowerCase() + ".dep"
+        double noData = (dataType == DataType.BYTE) ? 255.0 : -32768.0
+        WhiteboxRaster raster = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0, rows, cols,
+            DataScale.CONTINUOUS, dataType, 0.0, noData)
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                double z = (dataType == DataType.BYTE) ? (row * cols + col) * 3 : (row - 3) * 100.25 + col
+                raster.setValue(row, col, (row == 2 && col == 4) ? noData : z)
+            }
+        }
+        raster.close()
+        WhiteboxRaster buffered = new WhiteboxRaster(file, "r")
+        WhiteboxRaster mapped = WhiteboxRaster.openMemoryMapped(file)
+        buffered.setEdgeMode(WhiteboxRaster.EdgeMode.REFLECT)
+        mapped.setEdgeMode(WhiteboxRaster.EdgeMode.REFLECT)
+        try {
+            if (!mapped.isMemoryMapped()) {
+                return "the " + dataType + " raster was not mapped into memory"
+            }
+            for (int row = -2; row < rows + 2; row++) {
+                for (int col = -2; col < cols + 2; col++) {
+                    if (mapped.getValue(row, col) != buffered.getValue(row, col)) {
+                        return "cell (" + row + ", " + col + ") of the " + dataType + " raster is " +
+                            mapped.getValue(row, col) + " rather than " + buffered.getValue(row, col)
+                    }
+                }
+            }
+        } finally {
+            buffered.close()
+            mapped.close()
+        }
+    }
+    return null
+})
+
+// A tool that reads a few thousand cells from a band covering 5% of a large
+// raster, e.g. while tracing a flowpath, need not wait for the whole raster
+// to be read into memory.
+check("raster_memory_mapped_startup", {
+    int rows = 4000
+    int cols = 4000
+    String file = outputDir + "mapped_large.dep"
+    WhiteboxRaster raster = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0, rows, cols,
+        DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, -32768.0)
+    double[] data = new double[cols]
+    for (int row = 0; row < rows; row++) {
+        Arrays.fill(data, row)
+        raster.setRowValues(row, data)
+    }
+    raster.close()
+    def timeReads = { boolean useMapping ->
+        long start = System.nanoTime()
+        WhiteboxRaster r = useMapping ? WhiteboxRaster.openMemoryMapped(file) : new WhiteboxRaster(file, "r")
+        Random random = new Random(1)
+        double sum = 0
+        for (int i = 0; i < 2000; i++) {
+            sum += r.getValue(random.nextInt(rows.intdiv(20)), random.nextInt(cols))
+        }
+        r.close()
+        (System.nanoTime() - start) / 1e6
+    }
+    double bufferedTime = (1..3).collect { timeReads(false) }.min()
+    double mappedTime = (1..3).collect { timeReads(true) }.min()
+    println "    buffered: " + String.format("%.1f", bufferedTime) + " ms, memory-mapped: " +
+        String.format("%.1f", mappedTime) + " ms"
+    if (mappedTime * 3 > bufferedTime) {
+        return "the memory-mapped raster is not at least 3 times quicker"
+    }
+    return null
+})
+
+// GeoTIFF files of every layout, compression and predictor that the GeoTiff
+// reader supports are written with TestTiff. Strips of several rows, tiles
+// that extend beyond the image, and compressed files that are decoded ahead
+// on several threads must all be read exactly, in order and at random.
+def TestTiff = new GroovyClassLoader(getClass().getClassLoader()).parseClass(
+    new File("tests" + File.separator + "TestTiff.groovy"))
+
+check("geotiff_layouts", {
+    int rows = 37
+    int cols = 21
+    // bits, sample format and the value of each cell
+    def types = [
+        float32: [32, 3, { int r, int c -> (double) (float) ((r - 18) * 3.7 + c * 0.013) }],
+        float64: [64, 3, { int r, int c -> (r - 18) * 3.7 + c * 0.013 + r * 1e-9 }],
+        int8: [8, 2, { int r, int c -> (double) ((r * 5 + c) % 256 - 128) }],
+        uint8: [8, 1, { int r, int c -> (double) ((r * 7 + c * 11) % 256) }],
+        int16: [16, 2, { int r, int c -> (double) ((r - 18) * 1500 + c * 7) }],
+        uint16: [16, 1, { int r, int c -> (double) (r * 1700 + c * 13) }],
+        int32: [32, 2, { int r, int c -> (double) ((r - 18) * 100000 + c * 3) }],
+        uint32: [32, 1, { int r, int c -> (double) (4000000000L - r * 1000 - c) }]
+    ]
+    def layouts = [
+        ["float32", [rowsPerStrip: 5]],
+        ["float32", [littleEndian: false]],
+        ["float32", [tileWidth: 16, tileLength: 16]],
+        ["float32", [tileWidth: 16, tileLength: 16, compression: 8, predictor: 3]],
+        ["float32", [rowsPerStrip: 4, littleEndian: false, compression: 5, predictor: 3]],
+        ["float64", [rowsPerStrip: 3, compression: 8, predictor: 3]],
+        ["int8", [compression: 5]],
+        ["uint8", [rowsPerStrip: 2, compression: 32773]],
+        ["int16", [rowsPerStrip: 6, littleEndian: false, compression: 5, predictor: 2]],
+        ["uint16", [tileWidth: 16, tileLength: 16, compression: 32773, predictor: 2]],
+        ["int32", [rowsPerStrip: 7, compression: 8, predictor: 2]],
+        ["uint32", [rowsPerStrip: 1, littleEndian: false]]
+    ]
+    for (def layout : layouts) {
+        def type = types[layout[0]]
+        String name = layout[0] + " " + layout[1]
+        double[] values = new double[rows * cols]
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                values[row * cols + col] = type[2](row, col) as double
+            }
+        }
+        String file = outputDir + "layout.tif"
+        TestTiff.write(file, rows, cols, values, [bits: type[0], sampleFormat: type[1]] + layout[1])
+        GeoTiff gt = new GeoTiff(file)
+        gt.read()
+        try {
+            if (gt.getUnsupportedReason() != null) {
+                return name + " could not be read: " + gt.getUnsupportedReason()
+            }
+            double[] data = new double[cols]
+            for (int row = 0; row < rows; row++) {
+                gt.getRowValues(row, data)
+                for (int col = 0; col < cols; col++) {
+                    if (data[col] != values[row * cols + col]) {
+                        return "cell (" + row + ", " + col + ") of " + name + " is " + data[col] +
+                            " rather than " + values[row * cols + col]
+                    }
+                }
+            }
+            Random random = new Random(1)
+            for (int i = 0; i < 200; i++) {
+                int row = random.nextInt(rows)
+                int col = random.nextInt(cols)
+                if (gt.getValue(row, col) != values[row * cols + col]) {
+                    return "cell (" + row + ", " + col + ") of " + name + " is " + gt.getValue(row, col) +
+                        " rather than " + values[row * cols + col] + " when read at random"
+                }
+            }
+        } finally {
+            gt.close()
+        }
+    }
+    return null
+})
+
+check("geotiff_unsupported", {
+    String file = outputDir + "unsupported.tif"
+    TestTiff.write(file, 4, 4, new double[16], [bits: 64, sampleFormat: 1])
+    GeoTiff gt = new GeoTiff(file)
+    gt.read()
+    try {
+        if (gt.getUnsupportedReason() == null) {
+            return "unsigned 64-bit data were reported as supported"
+        }
+        gt.getRowValues(0, new double[4])
+        return "unsigned 64-bit data were read"
+    } catch (IOException e) {
+        return null
+    } finally {
+        gt.close()
+    }
+})
+
+// The ImportGeoTiff tool streams the rows of a compressed, tiled file to the
+// output raster and uses the file's NoData value.
+check("geotiff_import", {
+    int rows = 40
+    int cols = 35
+    double[] values = new double[rows * cols]
+    for (int i = 0; i < values.length; i++) {
+        values[i] = (i % 17 == 0) ? -9999.0 : (double) (float) (i * 0.25 - 100)
+    }
+    String file = outputDir + "import.tif"
+    TestTiff.write(file, rows, cols, values, [tileWidth: 16, tileLength: 16, compression: 5,
+        predictor: 3, noData: "-9999"])
+    def host = [
+        showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+        logException: { String s, Exception e -> println "    " + s + ": " + e },
+        isRequestForOperationCancelSet: { -> false }
+    ].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins.ImportGeoTiff").newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs([file] as String[])
+    plugin.run()
+    String outputFile = file.replace(".tif", ".dep")
+    if (!new File(outputFile).exists()) {
+        return "no output was created"
+    }
+    WhiteboxRaster raster = new WhiteboxRaster(outputFile, "r")
+    try {
+        if (raster.getNoDataValue() != -9999.0) {
+            return "the NoData value is " + raster.getNoDataValue() + " rather than -9999"
+        }
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                if (raster.getValue(row, col) != values[row * cols + col]) {
+                    return "cell (" + row + ", " + col + ") is " + raster.getValue(row, col) +
+                        " rather than " + values[row * cols + col]
+                }
+            }
+        }
+        if (raster.getMinimumValue() != -99.75 || raster.getMaximumValue() != values[values.length - 1]) {
+            return "the range of the output is " + raster.getMinimumValue() + " to " + raster.getMaximumValue()
+        }
+    } finally {
+        raster.close()
+    }
+    return null
+})
+
+// A raster stored as integers or bytes with a scale factor and offset reads
+// back real-world values within half of the scale factor of those written,
+// through buffered, memory-mapped, row and column reads and from the stream
+// writer. The scale and offset are kept in the header but are not passed on
+// to a raster based on the scaled one.
+check("raster_scaled_round_trip", {
+    int rows = 6
+    int cols = 8
+    def value = { int row, int col -> (0.37 * row - 0.11 * col + 0.0123 * row * col) as double }
+    for (DataType dataType : [DataType.INTEGER, DataType.BYTE]) {
+        double noData = (dataType == DataType.BYTE) ? 255.0 : -32768.0
+        double scale = (dataType == DataType.BYTE) ? 0.02 : 0.01
+        double offset = (dataType == DataType.BYTE) ? -1.0 : 0.5
+        String file = outputDir + "scaled_" + dataType.toString().toLowerCase() + ".dep"
+        String streamFile = outputDir + "scaled_stream_" + dataType.toString().toLowerCase() + ".dep"
+        WhiteboxRaster raster = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0, rows, cols,
+            DataScale.CONTINUOUS, dataType, noData, noData)
+        raster.setScaleAndOffset(scale, offset)
+        WhiteboxRasterStreamWriter writer = new WhiteboxRasterStreamWriter(streamFile, (double)rows, 0.0,
+            (double)cols, 0.0, rows, cols, DataScale.CONTINUOUS, dataType, noData)
+        writer.setScaleAndOffset(scale, offset)
+        for (int row = 0; row < rows; row++) {
+            double[] data = new double[cols]
+            for (int col = 0; col < cols; col++) {
+                data[col] = (row == 1 && col == 2) ? noData : value(row, col)
+                raster.setValue(row, col, data[col])
+            }
+            writer.writeRow(row, data)
+        }
+        raster.close()
+        writer.close()
+
+        for (String f : [file, streamFile]) {
+            WhiteboxRaster buffered = new WhiteboxRaster(f, "r")
+            WhiteboxRaster mapped = WhiteboxRaster.openMemoryMapped(f)
+            try {
+                if (buffered.getScaleFactor() != scale || buffered.getOffset() != offset) {
+                    return "the scale and offset of " + f + " are " + buffered.getScaleFactor() + " and " +
+                        buffered.getOffset()
+                }
+                double[] column = buffered.getColumnValues(3)
+                for (int row = 0; row < rows; row++) {
+                    double[] data = buffered.getRowValues(row)
+                    for (int col = 0; col < cols; col++) {
+                        List<Double> read = [buffered.getValue(row, col), mapped.getValue(row, col), data[col]]
+                        if (col == 3) {
+                            read << column[row]
+                        }
+                        for (double z : read) {
+                            if (row == 1 && col == 2) {
+                                if (z != noData) {
+                                    return "the NoData cell of " + f + " was read as " + z
+                                }
+                            } else if (Math.abs(z - value(row, col)) > scale / 2 + 1e-12) {
+                                return "cell (" + row + ", " + col + ") of " + f + " was read as " + z +
+                                    " rather than " + value(row, col)
+                            }
+                        }
+                    }
+                }
+                double expectedMax = (0..<rows).collect { int r -> (0..<cols).collect { int c -> value(r, c) }.max() }.max()
+                if (Math.abs(buffered.getMaximumValue() - expectedMax) > scale / 2 + 1e-12) {
+                    return "the maximum of " + f + " is " + buffered.getMaximumValue()
+                }
+            } finally {
+                buffered.close()
+                mapped.close()
+            }
+        }
+
+        String derivedFile = outputDir + "derived.dep"
+        WhiteboxRaster derived = new WhiteboxRaster(derivedFile, "rw", file, DataType.FLOAT, -32768.0)
+        derived.setValue(0, 0, 1.2345)
+        derived.close()
+        derived = new WhiteboxRaster(derivedFile, "r")
+        double z = derived.getValue(0, 0)
+        boolean scaled = derived.isScaled()
+        derived.close()
+        if (scaled || z != (double) (float) 1.2345) {
+            return "the raster based on a scaled raster is scaled too"
+        }
+    }
+    return null
+})
+
+// Header keys that merely contain the words of the scale factor and offset
+// keys, such as those that other software might add, leave the scale and
+// offset unchanged.
+check("raster_scale_header_keys", {
+    String file = outputDir + "scaled_keys.dep"
+    WhiteboxRaster raster = new WhiteboxRaster(file, 2.0, 0.0, 2.0, 0.0, 2, 2,
+        DataScale.CONTINUOUS, DataType.INTEGER, -32768.0, -32768.0)
+    raster.setScaleAndOffset(0.01, 0.5)
+    raster.setValue(0, 0, 1.23)
+    raster.close()
+    new File(file).append("Vertical Offset:\t100.0\nScale Factor Source:\tsurvey\n")
+    raster = new WhiteboxRaster(file, "r")
+    try {
+        if (raster.getScaleFactor() != 0.01d || raster.getOffset() != 0.5d) {
+            return "the scale and offset were read as " + raster.getScaleFactor() + " and " + raster.getOffset()
+        }
+        if (Math.abs(raster.getValue(0, 0) - 1.23d) > 0.005 + 1e-12) {
+            return "the scaled cell was read as " + raster.getValue(0, 0)
+        }
+    } finally {
+        raster.close()
+    }
+    return null
+})
+
+// A stream writer that is aborted, or closed before all of its rows have been
+// written, e.g. by a cancelled tool, leaves the existing raster untouched.
+check("stream_writer_incomplete_discarded", {
+    String file = outputDir + "stream_incomplete.dep"
+    createRaster(file, 1.0).close()
+    for (boolean abort : [true, false]) {
+        WhiteboxRasterStreamWriter writer = new WhiteboxRasterStreamWriter(file, 10.0, 0.0,
+            10.0, 0.0, 10, 10, DataScale.CONTINUOUS, DataType.FLOAT, -32768.0)
+        double[] data = new double[10]
+        Arrays.fill(data, 2.0)
+        for (int row = 0; row < 4; row++) {
+            writer.writeRow(row, data)
+        }
+        if (abort) {
+            writer.abort()
+        } else {
+            writer.close()
+        }
+        WhiteboxRaster unchanged = new WhiteboxRaster(file, "r")
+        double z = unchanged.getValue(0, 0)
+        unchanged.close()
+        String how = abort ? "aborted" : "incomplete"
+        if (z != 1.0) {
+            return "the " + how + " writer replaced the existing raster"
+        }
+        if (!leftoverFiles(file).isEmpty()) {
+            return "temporary files remain after the " + how + " writer: " + leftoverFiles(file)
+        }
+    }
+    return null
+})
+
+// The stream writer stores 32-bit integers, including values beyond the range
+// of the 16-bit integer type, and they read back exactly.
+check("stream_writer_i32", {
+    int rows = 4
+    int cols = 5
+    double noData = -32768.0
+    def value = { int row, int col -> ((row - 2) * 100000 + col * 7) as double }
+    String file = outputDir + "stream_i32.dep"
+    WhiteboxRasterStreamWriter writer = new WhiteboxRasterStreamWriter(file, (double)rows, 0.0,
+        (double)cols, 0.0, rows, cols, DataScale.CONTINUOUS, DataType.I32, noData)
+    for (int row = 0; row < rows; row++) {
+        double[] data = new double[cols]
+        for (int col = 0; col < cols; col++) {
+            data[col] = (row == 3 && col == 1) ? noData : value(row, col)
+        }
+        writer.writeRow(row, data)
+    }
+    writer.close()
+    WhiteboxRaster raster = new WhiteboxRaster(file, "r")
+    try {
+        if (raster.getDataType() != DataType.I32) {
+            return "the data type is " + raster.getDataType() + " rather than I32"
+        }
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                double expected = (row == 3 && col == 1) ? noData : value(row, col)
+                if (raster.getValue(row, col) != expected) {
+                    return "cell (" + row + ", " + col + ") is " + raster.getValue(row, col) +
+                        " rather than " + expected
+                }
+            }
+        }
+        if (raster.getMinimumValue() != -200000.0 || raster.getMaximumValue() != 100028.0) {
+            return "the range of the output is " + raster.getMinimumValue() + " to " + raster.getMaximumValue()
+        }
+    } finally {
+        raster.close()
+    }
+    return null
+})
+
+// The ScaleRaster tool chooses a scale and offset that cover the range of the
+// data, or uses those given, and reports the largest quantization error, which
+// is no more than half of the scale factor unless values are clipped.
+check("raster_scale_tool", {
+    int rows = 20
+    int cols = 30
+    String file = outputDir + "unscaled.dep"
+    WhiteboxRaster raster = new WhiteboxRaster(file, (double)rows, 0.0, (double)cols, 0.0, rows, cols,
+        DataScale.CONTINUOUS, DataType.FLOAT, -32768.0, -32768.0)
+    Random random = new Random(1)
+    for (int row = 0; row < rows; row++) {
+        for (int col = 0; col < cols; col++) {
+            raster.setValue(row, col, (row == 5 && col == 5) ? -32768.0 : random.nextDouble())
+        }
+    }
+    raster.close()
+    List<String> returned = []
+    def host = [
+        showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+        logException: { String s, Exception e -> println "    " + s + ": " + e },
+        isRequestForOperationCancelSet: { -> false },
+        returnData: { Object o -> returned << o.toString() }
+    ].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+    // the data type, scale and offset, and the expected number of clipped cells
+    def cases = [["integer", "not specified", "not specified", 0], ["byte", "not specified", "not specified", 0],
+                 ["byte", "0.002", "0", -1]]
+    for (def c : cases) {
+        String outputFile = outputDir + "scaled_tool.dep"
+        returned.clear()
+        WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins.ScaleRaster").newInstance()
+        plugin.setPluginHost(host)
+        plugin.setArgs([file, outputFile, c[0], c[1], c[2]] as String[])
+        plugin.run()
+        if (returned.size() < 2) {
+            return "no report was returned for " + c
+        }
+        Map<String, String> report = [:]
+        returned[1].split("\n").each { line ->
+            String[] parts = line.split("\t")
+            if (parts.length == 2) {
+                report[parts[0]] = parts[1]
+            }
+        }
+        double scale = Double.parseDouble(report["Scale factor:"])
+        double maxError = Double.parseDouble(report["Maximum quantization error:"])
+        int clipped = Integer.parseInt(report["Clipped cells:"].replace(",", ""))
+
+        WhiteboxRaster input = new WhiteboxRaster(file, "r")
+        WhiteboxRaster output = new WhiteboxRaster(outputFile, "r")
+        double measured = 0
+        int beyondBound = 0
+        try {
+            if (output.getDataType() != (c[0] == "byte" ? DataType.BYTE : DataType.INTEGER)) {
+                return "the output of " + c + " is of type " + output.getDataType()
+            }
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    double z = input.getValue(row, col)
+                    double s = output.getValue(row, col)
+                    if (z == input.getNoDataValue()) {
+                        if (s != output.getNoDataValue()) {
+                            return "the NoData cell is " + s + " in the output of " + c
+                        }
+                        continue
+                    }
+                    double error = Math.abs(s - z)
+                    measured = Math.max(measured, error)
+                    if (error > scale / 2 * (1 + 1e-9)) {
+                        beyondBound++
+                    }
+                }
+            }
+        } finally {
+            input.close()
+            output.close()
+        }
+        if (Math.abs(measured - maxError) > 1e-12) {
+            return "the reported maximum error of " + c + " is " + maxError + " but it is " + measured
+        }
+        if (clipped != beyondBound || (c[3] == 0 && clipped != 0) || (c[3] != 0 && clipped == 0)) {
+            return "the output of " + c + " has " + beyondBound + " clipped cells, but " + clipped + " were reported"
+        }
+    }
+    return null
+})
+
+// A GeoTIFF file with a scale and offset in its GDAL metadata holds stored
+// values, which GDAL reports as stored value * scale + offset. The imported
+// raster gives the same real-world values, keeping 8-bit unsigned and 16-bit
+// signed data as scaled BYTE and INTEGER rasters.
+check("geotiff_scaled", {
+    int rows = 9
+    int cols = 11
+    // bits, sample format, the output data type and the stored value of each cell
+    def types = [
+        [16, 2, DataType.INTEGER, { int r, int c -> (double) ((r - 4) * 3000 + c * 17) }],
+        [8, 1, DataType.BYTE, { int r, int c -> (double) ((r * 23 + c * 3) % 255) }],
+        [32, 3, DataType.FLOAT, { int r, int c -> (double) (float) (r * 1.5 + c * 0.25) }]
+    ]
+    double scale = 0.0025
+    double offset = 12.5
+    for (def type : types) {
+        double[] values = new double[rows * cols]
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                values[row * cols + col] = type[3](row, col) as double
+            }
+        }
+        double noData = values[2 * cols + 3]
+        String file = outputDir + "scaled.tif"
+        TestTiff.write(file, rows, cols, values, [bits: type[0], sampleFormat: type[1], compression: 8,
+            scale: scale, offset: offset, noData: noData.toString()])
+        GeoTiff gt = new GeoTiff(file)
+        gt.read()
+        double fileScale = gt.getScale()
+        double fileOffset = gt.getOffset()
+        gt.close()
+        if (fileScale != scale || fileOffset != offset) {
+            return "the scale and offset of the file are " + fileScale + " and " + fileOffset
+        }
+        def host = [
+            showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+            logException: { String s, Exception e -> println "    " + s + ": " + e },
+            isRequestForOperationCancelSet: { -> false }
+        ].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+        WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins.ImportGeoTiff").newInstance()
+        plugin.setPluginHost(host)
+        plugin.setArgs([file] as String[])
+        plugin.run()
+        WhiteboxRaster raster = new WhiteboxRaster(file.replace(".tif", ".dep"), "r")
+        try {
+            if (raster.getDataType() != type[2]) {
+                return "the " + type[0] + "-bit data were imported as " + raster.getDataType()
+            }
+            for (int row = 0; row < rows; row++) {
+                for (int col = 0; col < cols; col++) {
+                    double stored = values[row * cols + col]
+                    double expected = stored == noData ? raster.getNoDataValue() : stored * scale + offset
+                    double tolerance = type[2] == DataType.FLOAT ? Math.ulp((float) expected) : 1e-9
+                    if (Math.abs(raster.getValue(row, col) - expected) > tolerance) {
+                        return "cell (" + row + ", " + col + ") of the " + type[0] + "-bit data is " +
+                            raster.getValue(row, col) + " rather than " + expected
+                    }
+                }
+            }
+        } finally {
+            raster.close()
+        }
+    }
+    return null
+})
+
+// Percentiles found from streaming histograms are the same as those
+// interpolated between the values of a sorted copy of the data, whether the
+// values are few enough to be sorted in one pass or need finer histograms,
+// and with ties, NoData cells and a single value. The histogram counts every
+// valid cell in the bin it falls in.
+check("raster_percentiles", {
+    Random random = new Random(7)
+    def cases = [
+        ["large", 400, 300, { random.nextDouble() < 0.05 ? -32768.0 : Math.exp(random.nextGaussian() * 2) }],
+        ["ties", 350, 250, { random.nextDouble() < 0.05 ? -32768.0 : (double) random.nextInt(20) }],
+        ["small", 10, 10, { random.nextDouble() < 0.1 ? -32768.0 : random.nextGaussian() }],
+        ["constant", 10, 10, { 4.25d }]
+    ]
+    double[] percentiles = [0, 0.5, 1, 25, 50, 62.5, 99, 100] as double[]
+    for (def c : cases) {
+        String file = outputDir + "percentiles_" + c[0] + ".dep"
+        WhiteboxRaster raster = new WhiteboxRaster(file, (double) c[1], 0.0, (double) c[2], 0.0, c[1], c[2],
+            DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, -32768.0)
+        List<Double> valid = []
+        for (int row = 0; row < c[1]; row++) {
+            double[] data = new double[c[2]]
+            for (int col = 0; col < c[2]; col++) {
+                data[col] = c[3]() as double
+                if (data[col] != -32768.0) {
+                    valid << data[col]
+                }
+            }
+            raster.setRowValues(row, data)
+        }
+        raster.close()
+        double[] sorted = valid as double[]
+        Arrays.sort(sorted)
+        int n = sorted.length
+
+        raster = new WhiteboxRaster(file, "r")
+        try {
+            double[] found = raster.getPercentiles(percentiles)
+            for (int i = 0; i < percentiles.length; i++) {
+                double rank = percentiles[i] / 100 * (n - 1)
+                int lower = (int) Math.floor(rank)
+                double expected = sorted[lower] + (rank - lower) * (sorted[Math.min(lower + 1, n - 1)] - sorted[lower])
+                if (Math.abs(found[i] - expected) > 1e-12 * Math.max(1.0, Math.abs(expected))) {
+                    return "the " + percentiles[i] + " percentile of the " + c[0] + " raster is " + found[i] +
+                        " rather than " + expected
+                }
+            }
+            if (raster.getPercentile(50) != found[4]) {
+                return "the median of the " + c[0] + " raster differs when found on its own"
+            }
+            long[] histogram = raster.getHistogram(50)
+            long[] expected = new long[50]
+            double range = sorted[n - 1] - sorted[0]
+            for (double z : sorted) {
+                expected[range > 0 ? Math.min((int) ((z - sorted[0]) / range * 50), 49) : 0]++
+            }
+            if (!Arrays.equals(histogram, expected)) {
+                return "the histogram of the " + c[0] + " raster is " + histogram + " rather than " + expected
+            }
+        } finally {
+            raster.close()
+        }
+    }
+    return null
+})
+
+// The tools that clip the tails of a distribution find the same clip values
+// as before they used streaming histograms, apart from quantization. The
+// NormalizedDifferenceIndex tool sorted the output values and clipped them at
+// the values of rank k and n - 1 - k, where k = n * clip / 100, so the new clip
+// values lie between those of the neighbouring ranks. The FilterUserKernel
+// tool interpolated its display range within the bins of the statistics file,
+// so the new range is within a bin of the old one.
+check("percentile_clip_tools", {
+    def host = [
+        showFeedback: { Object[] a -> println "    " + a[0]; 0 },
+        logException: { String s, Exception e -> println "    " + s + ": " + e },
+        isRequestForOperationCancelSet: { -> false }
+    ].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+    def runPlugin = { String name, List<String> pluginArgs ->
+        WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+        plugin.setPluginHost(host)
+        plugin.setArgs(pluginArgs as String[])
+        plugin.run()
+    }
+    int rows = 320
+    int cols = 250
+    Random random = new Random(11)
+    ["band_a", "band_b"].each { band ->
+        WhiteboxRaster raster = new WhiteboxRaster(outputDir + band + ".dep", (double) rows, 0.0, (double) cols, 0.0,
+            rows, cols, DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                raster.setValue(row, col, (row == 5 && col < 40) ? -32768.0 : 100 + 50 * random.nextDouble() +
+                    (random.nextDouble() < 0.01 ? 2000 * random.nextDouble() : 0))
+            }
+        }
+        raster.close()
+    }
+    double clip = 2.0
+    runPlugin("NormalizedDifferenceIndex", [outputDir + "band_a.dep", outputDir + "band_b.dep",
+        outputDir + "ndi.dep", "not specified", "0", "0"])
+    runPlugin("NormalizedDifferenceIndex", [outputDir + "band_a.dep", outputDir + "band_b.dep",
+        outputDir + "ndi_clipped.dep", "not specified", "0", clip.toString()])
+    WhiteboxRaster unclipped = new WhiteboxRaster(outputDir + "ndi.dep", "r")
+    WhiteboxRaster clipped = new WhiteboxRaster(outputDir + "ndi_clipped.dep", "r")
+    try {
+        List<Double> valid = []
+        for (int row = 0; row < rows; row++) {
+            for (double z : unclipped.getRowValues(row)) {
+                if (z != unclipped.getNoDataValue()) {
+                    valid << z
+                }
+            }
+        }
+        double[] sorted = valid as double[]
+        Arrays.sort(sorted)
+        int n = sorted.length
+        int k = (int) (n * clip / 100)
+        double clipMin = Double.POSITIVE_INFINITY
+        double clipMax = Double.NEGATIVE_INFINITY
+        for (int row = 0; row < rows; row++) {
+            for (double z : clipped.getRowValues(row)) {
+                if (z != clipped.getNoDataValue()) {
+                    clipMin = Math.min(clipMin, z)
+                    clipMax = Math.max(clipMax, z)
+                }
+            }
+        }
+        if (clipMin < sorted[k - 1] || clipMin > sorted[k + 1]) {
+            return "the index was clipped below at " + clipMin + " rather than about " + sorted[k]
+        }
+        if (clipMax < sorted[n - 2 - k] || clipMax > sorted[n - k]) {
+            return "the index was clipped above at " + clipMax + " rather than about " + sorted[n - 1 - k]
+        }
+        for (int row = 0; row < rows; row++) {
+            double[] a = unclipped.getRowValues(row)
+            double[] b = clipped.getRowValues(row)
+            for (int col = 0; col < cols; col++) {
+                double expected = a[col] == unclipped.getNoDataValue() ? clipped.getNoDataValue() :
+                    Math.min(Math.max(a[col], clipMin), clipMax)
+                if (b[col] != expected) {
+                    return "cell (" + row + ", " + col + ") of the clipped index is " + b[col] + " rather than " + expected
+                }
+            }
+        }
+    } finally {
+        unclipped.close()
+        clipped.close()
+    }
+
+    runPlugin("FilterUserKernel", [outputDir + "band_a.dep", outputDir + "filtered.dep",
+        "tests" + File.separator + "data" + File.separator + "kernel_directional.txt", "false", clip.toString()])
+    WhiteboxRaster filtered = new WhiteboxRaster(outputDir + "filtered.dep", "r")
+    try {
+        double oldMin = filtered.getPercentileValue(clip)
+        double oldMax = filtered.getPercentileValue(100 - clip)
+        double binWidth = filtered.getHistoBinWidth()
+        if (Math.abs(filtered.getDisplayMinimum() - oldMin) > binWidth ||
+                Math.abs(filtered.getDisplayMaximum() - oldMax) > binWidth) {
+            return "the display range of the filtered raster is " + filtered.getDisplayMinimum() + " to " +
+                filtered.getDisplayMaximum() + " rather than about " + oldMin + " to " + oldMax
+        }
+    } finally {
+        filtered.close()
+    }
+    return null
+})
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
@@ -0,0 +1,369 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.file.Files
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Golden-output tests for the terrain analysis tools that process rows in
+// parallel. Each test runs a plugin on one of the small synthetic rasters in
+// tests/data and checks that its output is identical, cell for cell, to the
+// expected raster in tests/expected, so that the output does not depend on
+// the number of threads or the order in which rows are processed. The
+// inputs include rasters of a single row and of three rows. The flow path
+// curvatures are compared in the same way, using D8 and D-infinity pointers
+// created from the inputs, as is the SAGA wetness index of a slope above a
+// plateau, whose flat cells must be NoData. The shaded relief composites are
+// compared for each named palette and for a palette file, and palette files
+// that can't be used must be rejected.
+//
+// The tests are run against the compiled plugins with 'python build.py
+// runtests'. After an intended change to a tool's output, the expected
+// rasters can be recreated by running this script with the argument
+// 'regenerate'. The surface area ratio is also checked against its exact
+// value on a flat raster and on a plane sloping at 45 degrees, and the
+// horizon gradient against the directional derivative of a plane.
+
+def testDir = "tests" + File.separator
+def dataDir = testDir + "data" + File.separator
+def expectedDir = testDir + "expected" + File.separator
+boolean regenerate = args.length > 0 && args[0].toLowerCase().equals("regenerate")
+
+def inputs = ["surface", "nodata", "row", "rows3"]
+
+// name prefix, plugin, the plugin arguments following the input and output
+// files, and, for tools with several outputs, the suffixes of the output files
+def tools = [
+    ["relative_aspect", "RelativeAspect", ["45", "1"]],
+    ["fetch", "FetchAnalysis", ["135", "0.1", "not specified"]],
+    ["wind_shelter", "WindShelterIndex", ["0, 90", "not specified", "3", "not specified"], ["_0", "_90"]],
+    ["directional_relief", "DirectionalRelief", ["225", "not specified"]],
+    ["solar_shadow", "SolarShadowMap", ["45", "-80", "2026-06-21T14:30:00Z", "not specified", "not specified"]],
+    ["surface_area", "SurfaceArea", ["true", "1"]],
+    ["horizon_gradient", "HorizonGradient", ["30", "not specified"]],
+    ["saga_wetness", "SAGAWetnessIndex", ["5", "1"]],
+    ["shaded_relief_swiss_topo", "ShadedReliefComposite", ["swiss_topo", "315", "30", "1", "not specified"]],
+    ["shaded_relief_imhof", "ShadedReliefComposite", ["imhof", "135", "45", "2", "not specified"]],
+    ["shaded_relief_natural_earth", "ShadedReliefComposite", ["natural_earth", "225", "20", "0.5", "not specified"]],
+    ["shaded_relief_palette_file", "ShadedReliefComposite", ["not specified", "315", "30", "1", dataDir + "palette_three_stops.json"]]
+]
+
+List<String> returned = []
+List<String> feedback = []
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; feedback << a[0].toString(); 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> false },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+def outputDir = regenerate ? expectedDir : Files.createTempDirectory("whitebox_tests").toString() + File.separator
+int numTests = 0
+int numFailed = 0
+tools.each { tool ->
+    inputs.each { input ->
+        String name = tool[0] + "_" + input
+        String outputFile = outputDir + name + ".dep"
+        numTests++
+        returned.clear()
+        runPlugin(tool[1], [dataDir + input + ".dep", outputFile] + tool[2])
+        if (regenerate) {
+            println "Regenerated " + name
+            return
+        }
+
+        List<String> failures = []
+        for (String suffix : (tool.size() > 3 ? tool[3] : [""])) {
+            String output = outputDir + name + suffix + ".dep"
+            if (!new File(output).exists()) {
+                failures << "no output was created"
+                break
+            }
+            returned.clear()
+            runPlugin("CompareRasters", [expectedDir + name + suffix + ".dep", output, "0", "5"])
+            String report = returned.isEmpty() ? "" : returned[0]
+            if (!report.contains("Result:\tIDENTICAL")) {
+                failures << "the output differs from the expected raster\n" + report
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+// inputs created by the tests, which are never written to the expected
+// directory, even when regenerating
+def tempDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+
+// The flow path curvatures and convergence are compared for D8 pointers of
+// every input and for D-infinity pointers of the surfaces with and without
+// NoData cells. The pointers are created by the flow pointer tools.
+def flowPathInputs = [["d8", "FlowPointerD8", inputs], ["dinf", "FlowPointerDinf", ["surface", "nodata"]]]
+def flowPathSuffixes = ["_profile", "_tangential", "_convergence"]
+flowPathInputs.each { pointerType ->
+    pointerType[2].each { input ->
+        String name = "flow_path_curvature_" + pointerType[0] + "_" + input
+        String pointerFile = tempDir + pointerType[0] + "_" + input + ".dep"
+        numTests++
+        runPlugin(pointerType[1], [dataDir + input + ".dep", pointerFile])
+        runPlugin("FlowPathCurvature", [dataDir + input + ".dep", pointerFile, pointerType[0]] +
+            flowPathSuffixes.collect { outputDir + name + it + ".dep" } + ["1"])
+        if (regenerate) {
+            println "Regenerated " + name
+            return
+        }
+
+        List<String> failures = []
+        for (String suffix : flowPathSuffixes) {
+            String output = outputDir + name + suffix + ".dep"
+            if (!new File(output).exists()) {
+                failures << "no output was created"
+                break
+            }
+            returned.clear()
+            runPlugin("CompareRasters", [expectedDir + name + suffix + ".dep", output, "0", "5"])
+            String report = returned.isEmpty() ? "" : returned[0]
+            if (!report.contains("Result:\tIDENTICAL")) {
+                failures << "the " + suffix.substring(1) + " output differs from the expected raster\n" + report
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+// The SAGA wetness index of a slope running down onto a plateau. The cells
+// of the plateau whose neighbours are all flat have no slope and so are
+// NoData, while the others match the expected raster.
+numTests++
+String sagaFlatName = "saga_wetness_flat"
+WhiteboxRaster sagaFlatDem = new WhiteboxRaster(tempDir + sagaFlatName + "_dem.dep", 8.0, 0.0, 8.0, 0.0, 8, 8,
+    DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+for (int row = 0; row < 8; row++) {
+    for (int col = 0; col < 8; col++) {
+        sagaFlatDem.setValue(row, col, row < 4 ? 10.0 + 2 * (4 - row) + 0.5 * col : 10.0)
+    }
+}
+sagaFlatDem.close()
+runPlugin("SAGAWetnessIndex", [tempDir + sagaFlatName + "_dem.dep", outputDir + sagaFlatName + ".dep", "5", "1"])
+if (regenerate) {
+    println "Regenerated " + sagaFlatName
+} else {
+    List<String> failures = []
+    String output = outputDir + sagaFlatName + ".dep"
+    if (!new File(output).exists()) {
+        failures << "no output was created"
+    } else {
+        returned.clear()
+        runPlugin("CompareRasters", [expectedDir + sagaFlatName + ".dep", output, "0", "5"])
+        String report = returned.isEmpty() ? "" : returned[0]
+        if (!report.contains("Result:\tIDENTICAL")) {
+            failures << "the output differs from the expected raster\n" + report
+        }
+        WhiteboxRaster wetness = new WhiteboxRaster(output, "r")
+        for (int row = 0; row < 8; row++) {
+            for (int col = 0; col < 8; col++) {
+                boolean flat = row > 4
+                if (flat != (wetness.getValue(row, col) == wetness.getNoDataValue())) {
+                    failures << "cell (" + row + ", " + col + ") has a wetness index of " + wetness.getValue(row, col)
+                }
+            }
+        }
+        wetness.close()
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + sagaFlatName
+    } else {
+        numFailed++
+        println "FAILED " + sagaFlatName
+        failures.each { println "    " + it.replace("\n", "\n    ") }
+    }
+}
+
+if (!regenerate) {
+    // A palette file that can't be used, or an unknown palette name, is
+    // reported and no composite is created.
+    [["not json", "{ stops: [", "could not be read"],
+     ["no stops", "{\"colours\": []}", "must contain a 'stops' array"],
+     ["one stop", "{\"stops\": [{\"percentile\": 0, \"rgb\": [0, 0, 0]}]}", "at least two colour stops"],
+     ["two values", "{\"stops\": [{\"percentile\": 0, \"rgb\": [0, 0]}, {\"percentile\": 100, \"rgb\": [9, 9, 9]}]}", "an 'rgb' array of three values"],
+     ["colour range", "{\"stops\": [{\"percentile\": 0, \"rgb\": [0, 0, 256]}, {\"percentile\": 100, \"rgb\": [9, 9, 9]}]}", "between 0 and 255"],
+     ["decreasing", "{\"stops\": [{\"percentile\": 50, \"rgb\": [0, 0, 0]}, {\"percentile\": 20, \"rgb\": [9, 9, 9]}]}", "in increasing order"],
+     ["unknown name", null, "Unrecognized palette 'sepia'"]
+    ].each { test ->
+        String name = "shaded_relief_rejects_" + test[0].replace(" ", "_")
+        numTests++
+        String paletteFile = "not specified"
+        if (test[1] != null) {
+            paletteFile = tempDir + name + ".json"
+            new File(paletteFile).text = test[1]
+        }
+        String outputFile = outputDir + name + ".dep"
+        feedback.clear()
+        runPlugin("ShadedReliefComposite", [dataDir + "surface.dep", outputFile, "sepia", "315", "30", "1", paletteFile])
+        String failure = null
+        if (new File(outputFile).exists()) {
+            failure = "an output was created"
+        } else if (!feedback.any { it.contains(test[2]) }) {
+            failure = "the palette was not rejected with the expected message: " + feedback
+        }
+        if (failure == null) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            println "    " + failure
+        }
+    }
+
+    // The facets of a plane lie in the plane, so the ratio of every cell,
+    // including the edge cells, is exactly 1 on a flat raster and sqrt(2)
+    // on a plane sloping at 45 degrees. A NoData cell is NoData in the
+    // output and its neighbours are unaffected.
+    int rows = 6
+    int cols = 7
+    double cellSize = 2.5
+    double noData = -32768.0
+    [["surface_area_flat", { int row, int col -> 10.0 }, 1.0],
+     ["surface_area_45_degrees", { int row, int col -> col * cellSize }, Math.sqrt(2.0)],
+     ["surface_area_nodata", { int row, int col -> (row == 2 && col == 3) ? noData : row * cellSize }, Math.sqrt(2.0)]
+    ].each { test ->
+        String name = test[0]
+        numTests++
+        List<String> failures = []
+        WhiteboxRaster input = new WhiteboxRaster(outputDir + name + "_dem.dep", rows * cellSize, 0.0,
+            cols * cellSize, 0.0, rows, cols, DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, noData)
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                input.setValue(row, col, test[1](row, col))
+            }
+        }
+        input.close()
+        returned.clear()
+        runPlugin("SurfaceArea", [outputDir + name + "_dem.dep", outputDir + name + ".dep", "true", "1"])
+        if (!new File(outputDir + name + ".dep").exists()) {
+            failures << "no output was created"
+        } else {
+            double expected = test[2]
+            WhiteboxRaster output = new WhiteboxRaster(outputDir + name + ".dep", "r")
+            for (int row = 0; row < rows && failures.isEmpty(); row++) {
+                for (int col = 0; col < cols && failures.isEmpty(); col++) {
+                    double z = output.getValue(row, col)
+                    if (test[1](row, col) == noData) {
+                        if (z != output.getNoDataValue()) {
+                            failures << "the NoData cell has a ratio of " + z
+                        }
+                    } else if (Math.abs(z - expected) > 1e-6 || (expected == 1.0 && z != 1.0)) {
+                        failures << "the ratio of cell (" + row + ", " + col + ") is " + z + " rather than " + expected
+                    }
+                }
+            }
+            output.close()
+            String report = returned.size() > 1 ? returned[1] : ""
+            if (!report.contains("Surface area ratio:\t" + String.format("%.6f", expected))) {
+                failures << "the reported total ratio is wrong\n" + report
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+
+    // Every difference used by the horizon gradient is exact on a plane, as
+    // is bilinear interpolation, so the gradient of every cell, including
+    // the edge cells and the neighbours of a NoData cell, is the directional
+    // derivative of the plane, a sin(azimuth) + b cos(azimuth), times the
+    // weight.
+    double a = 0.3
+    double b = -0.45
+    def plane = { int row, int col -> a * col * cellSize + b * (rows - 1 - row) * cellSize }
+    [["horizon_gradient_north", "0", "not specified", 1.0],
+     ["horizon_gradient_30_degrees", "30", "1", 1.0],
+     ["horizon_gradient_135_degrees", "135", "2.5", 2.5],
+     ["horizon_gradient_250_degrees_nodata", "250", "-1", -1.0]
+    ].each { test ->
+        String name = test[0]
+        numTests++
+        List<String> failures = []
+        boolean hasNoData = name.endsWith("nodata")
+        WhiteboxRaster input = new WhiteboxRaster(outputDir + name + "_dem.dep", rows * cellSize, 0.0,
+            cols * cellSize, 0.0, rows, cols, DataScale.CONTINUOUS, DataType.DOUBLE, 0.0, noData)
+        for (int row = 0; row < rows; row++) {
+            for (int col = 0; col < cols; col++) {
+                input.setValue(row, col, (hasNoData && row == 3 && col == 2) ? noData : plane(row, col))
+            }
+        }
+        input.close()
+        runPlugin("HorizonGradient", [outputDir + name + "_dem.dep", outputDir + name + ".dep", test[1], test[2]])
+        if (!new File(outputDir + name + ".dep").exists()) {
+            failures << "no output was created"
+        } else {
+            double azimuth = Math.toRadians(Double.parseDouble(test[1]))
+            double expected = (test[3] as double) * (a * Math.sin(azimuth) + b * Math.cos(azimuth))
+            WhiteboxRaster output = new WhiteboxRaster(outputDir + name + ".dep", "r")
+            for (int row = 0; row < rows && failures.isEmpty(); row++) {
+                for (int col = 0; col < cols && failures.isEmpty(); col++) {
+                    double z = output.getValue(row, col)
+                    if (hasNoData && row == 3 && col == 2) {
+                        if (z != output.getNoDataValue()) {
+                            failures << "the NoData cell has a gradient of " + z
+                        }
+                    } else if (Math.abs(z - expected) > 1e-6) {
+                        failures << "the gradient of cell (" + row + ", " + col + ") is " + z + " rather than " + expected
+                    }
+                }
+            }
+            output.close()
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
@@ -0,0 +1,321 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import groovy.transform.CompileStatic
+import java.util.zip.DeflaterOutputStream
+
+// Writes single-band GeoTIFF files of any layout, compression and predictor
+// that the GeoTiff reader supports, for the raster tests and the GeoTIFF
+// benchmark. It is loaded by those scripts with parseClass rather than run.
+//
+// The options are the bits per sample (bits, default 32), the TIFF sample
+// format (sampleFormat: 1 unsigned, 2 signed or 3 floating point, default 3),
+// littleEndian (default true), either rowsPerStrip (default all rows) or
+// tileWidth and tileLength, compression (1 none, 5 LZW, 8 Deflate or 32773
+// PackBits, default 1), predictor (1 none, 2 horizontal differencing or 3
+// floating point, default 1), noData (the GDAL NoData string, if any) and
+// scale and offset, which are written to the GDAL metadata tag as GDAL writes
+// them.
+class TestTiff {
+
+    static void write(String file, int rows, int cols, double[] values, Map options) {
+        int bits = options.bits ?: 32
+        int sampleFormat = options.sampleFormat ?: 3
+        ByteOrder order = options.littleEndian != false ? ByteOrder.LITTLE_ENDIAN : ByteOrder.BIG_ENDIAN
+        int compression = options.compression ?: 1
+        int predictor = options.predictor ?: 1
+        boolean tiled = options.tileWidth != null
+        int blockWidth = tiled ? options.tileWidth : cols
+        int blockLength = tiled ? options.tileLength : (options.rowsPerStrip ?: rows)
+        int blocksAcross = tiled ? (int) Math.ceil(cols / (double) blockWidth) : 1
+        int blocksDown = (int) Math.ceil(rows / (double) blockLength)
+
+        List<byte[]> blocks = []
+        for (int by = 0; by < blocksDown; by++) {
+            for (int bx = 0; bx < blocksAcross; bx++) {
+                // the last strip holds only the remaining rows, while tiles
+                // on the edges are padded
+                int numRows = tiled ? blockLength : Math.min(blockLength, rows - by * blockLength)
+                long[] samples = getSamples(values, rows, cols, by * blockLength, bx * blockWidth,
+                    numRows, blockWidth, bits, sampleFormat)
+                byte[] bytes = encode(samples, blockWidth, numRows, bits, order, predictor)
+                blocks << compress(bytes, compression)
+            }
+        }
+
+        // the header, the blocks and then the IFD, with its values following it
+        List<List> entries = [
+            [256, 4, [cols]], [257, 4, [rows]], [258, 3, [bits]], [259, 3, [compression]], [262, 3, [1]],
+            [277, 3, [1]], [339, 3, [sampleFormat]],
+            [33550, 12, [1.0d, 1.0d, 0.0d]], [33922, 12, [0.0d, 0.0d, 0.0d, 0.0d, (double) rows, 0.0d]]
+        ]
+        if (predictor != 1) {
+            entries << [317, 3, [predictor]]
+        }
+        if (options.noData != null) {
+            entries << [42113, 2, options.noData.toString()]
+        }
+        if (options.scale != null || options.offset != null) {
+            entries << [42112, 2, "<GDALMetadata>\n" +
+                "  <Item name=\"OFFSET\" sample=\"0\" role=\"offset\">" + (options.offset ?: 0) + "</Item>\n" +
+                "  <Item name=\"SCALE\" sample=\"0\" role=\"scale\">" + (options.scale ?: 1) + "</Item>\n" +
+                "</GDALMetadata>\n"]
+        }
+        long offset = 8
+        List<Long> offsets = []
+        List<Long> byteCounts = []
+        for (byte[] block : blocks) {
+            offsets << offset
+            byteCounts << (long) block.length
+            offset += block.length
+        }
+        if (tiled) {
+            entries << [322, 4, [blockWidth]] << [323, 4, [blockLength]] << [324, 4, offsets] << [325, 4, byteCounts]
+        } else {
+            entries << [273, 4, offsets] << [278, 4, [blockLength]] << [279, 4, byteCounts]
+        }
+        entries.sort { it[0] }
+
+        int ifdOffset = (int) offset
+        int ifdSize = 2 + entries.size() * 12 + 4
+        ByteBuffer ifd = ByteBuffer.allocate(ifdSize).order(order)
+        ByteBuffer extra = ByteBuffer.allocate(65536 + 8 * offsets.size() * 2).order(order)
+        ifd.putShort((short) entries.size())
+        for (List entry : entries) {
+            int tag = entry[0]
+            int type = entry[1]
+            ByteBuffer v = ByteBuffer.allocate(8 * Math.max(1, entry[2].size()) + 8).order(order)
+            int count
+            if (type == 2) {
+                byte[] s = (entry[2] + "\0").getBytes("US-ASCII")
+                v.put(s)
+                count = s.length
+            } else {
+                count = entry[2].size()
+                for (def x : entry[2]) {
+                    if (type == 3) {
+                        v.putShort((short) x)
+                    } else if (type == 4) {
+                        v.putInt((int) (long) x)
+                    } else {
+                        v.putDouble((double) x)
+                    }
+                }
+            }
+            v.flip()
+            ifd.putShort((short) tag).putShort((short) type).putInt(count)
+            if (v.remaining() <= 4) {
+                byte[] inline = new byte[4]
+                v.get(inline, 0, v.remaining())
+                ifd.put(inline)
+            } else {
+                ifd.putInt(ifdOffset + ifdSize + extra.position())
+                extra.put(v)
+            }
+        }
+        ifd.putInt(0)
+
+        new File(file).withOutputStream { out ->
+            ByteBuffer header = ByteBuffer.allocate(8).order(order)
+            header.put((byte) (order == ByteOrder.LITTLE_ENDIAN ? 73 : 77))
+            header.put((byte) (order == ByteOrder.LITTLE_ENDIAN ? 73 : 77))
+            header.putShort((short) 42).putInt(ifdOffset)
+            out.write(header.array())
+            blocks.each { out.write(it) }
+            out.write(ifd.array())
+            out.write(extra.array(), 0, extra.position())
+        }
+    }
+
+    // The methods that handle every sample are compiled statically, so that
+    // large files are written quickly.
+
+    // the raw bits of the samples of a block, in the low bits of longs
+    @CompileStatic
+    static long[] getSamples(double[] values, int rows, int cols, int startRow, int startCol,
+            int numRows, int width, int bits, int sampleFormat) {
+        long[] samples = new long[numRows * width]
+        for (int r = 0; r < numRows; r++) {
+            for (int c = 0; c < width; c++) {
+                int row = startRow + r
+                int col = startCol + c
+                double z = (row < rows && col < cols) ? values[row * cols + col] : 0.0d
+                samples[r * width + c] = toBits(z, bits, sampleFormat)
+            }
+        }
+        return samples
+    }
+
+    @CompileStatic
+    static long toBits(double z, int bits, int sampleFormat) {
+        if (sampleFormat == 3) {
+            return bits == 32 ? Float.floatToRawIntBits((float) z) & 0xFFFFFFFFL : Double.doubleToRawLongBits(z)
+        }
+        long v = (long) z
+        return bits == 64 ? v : v & ((1L << bits) - 1)
+    }
+
+    @CompileStatic
+    static byte[] encode(long[] samples, int width, int numRows, int bits, ByteOrder order, int predictor) {
+        int bytesPerSample = (int) (bits / 8)
+        ByteBuffer buffer = ByteBuffer.allocate(samples.length * bytesPerSample)
+        if (predictor == 3) {
+            // big-endian bytes, in planes from the most significant, differenced
+            byte[] out = buffer.array()
+            int rowBytes = width * bytesPerSample
+            for (int r = 0; r < numRows; r++) {
+                int start = r * rowBytes
+                for (int i = 0; i < width; i++) {
+                    long v = samples[r * width + i]
+                    for (int b = 0; b < bytesPerSample; b++) {
+                        out[start + b * width + i] = (byte) (v >>> (8 * (bytesPerSample - 1 - b)))
+                    }
+                }
+                for (int i = rowBytes - 1; i > 0; i--) {
+                    out[start + i] = (byte) (out[start + i] - out[start + i - 1])
+                }
+            }
+            return out
+        }
+        buffer.order(order)
+        long mask = bits == 64 ? -1L : (1L << bits) - 1
+        for (int r = 0; r < numRows; r++) {
+            for (int i = 0; i < width; i++) {
+                long v = samples[r * width + i]
+                if (predictor == 2 && i > 0) {
+                    v = (v - samples[r * width + i - 1]) & mask
+                }
+                switch (bytesPerSample) {
+                    case 1: buffer.put((byte) v); break
+                    case 2: buffer.putShort((short) v); break
+                    case 4: buffer.putInt((int) v); break
+                    default: buffer.putLong(v)
+                }
+            }
+        }
+        return buffer.array()
+    }
+
+    @CompileStatic
+    static byte[] compress(byte[] data, int compression) {
+        switch (compression) {
+            case 1:
+                return data
+            case 5:
+                return lzw(data)
+            case 8:
+                ByteArrayOutputStream out = new ByteArrayOutputStream()
+                DeflaterOutputStream deflater = new DeflaterOutputStream(out)
+                deflater.write(data)
+                deflater.close()
+                return out.toByteArray()
+            case 32773:
+                return packBits(data)
+        }
+        throw new IllegalArgumentException("compression " + compression)
+    }
+
+    @CompileStatic
+    static byte[] lzw(byte[] data) {
+        ByteArrayOutputStream out = new ByteArrayOutputStream()
+        // the bit buffer, the number of bits in it and the code length
+        int[] state = [0, 0, 9] as int[]
+        Map<Integer, Integer> table = new HashMap<>()
+        int nextCode = 258
+        emit(out, state, 256)
+        int prefix = -1
+        for (byte b : data) {
+            int c = b & 0xFF
+            if (prefix < 0) {
+                prefix = c
+                continue
+            }
+            int key = (prefix << 8) | c
+            Integer code = table.get(key)
+            if (code != null) {
+                prefix = code
+                continue
+            }
+            emit(out, state, prefix)
+            table.put(key, nextCode++)
+            // the code length grows one code later than the table, since the
+            // decoder adds each string one code behind the encoder
+            if (nextCode == 512 || nextCode == 1024 || nextCode == 2048) {
+                state[2]++
+            } else if (nextCode == 4094) {
+                emit(out, state, 256)
+                table.clear()
+                nextCode = 258
+                state[2] = 9
+            }
+            prefix = c
+        }
+        if (prefix >= 0) {
+            emit(out, state, prefix)
+            nextCode++
+            if (nextCode == 512 || nextCode == 1024 || nextCode == 2048) {
+                state[2]++
+            }
+        }
+        emit(out, state, 257)
+        if (state[1] > 0) {
+            out.write((state[0] << (8 - state[1])) & 0xFF)
+        }
+        return out.toByteArray()
+    }
+
+    @CompileStatic
+    private static void emit(ByteArrayOutputStream out, int[] state, int code) {
+        state[0] = (state[0] << state[2]) | code
+        state[1] += state[2]
+        while (state[1] >= 8) {
+            out.write((state[0] >>> (state[1] - 8)) & 0xFF)
+            state[1] -= 8
+        }
+        state[0] &= (1 << state[1]) - 1
+    }
+
+    @CompileStatic
+    static byte[] packBits(byte[] data) {
+        ByteArrayOutputStream out = new ByteArrayOutputStream()
+        int i = 0
+        while (i < data.length) {
+            int run = 1
+            while (i + run < data.length && run < 128 && data[i + run] == data[i]) {
+                run++
+            }
+            if (run > 1) {
+                out.write(1 - run)
+                out.write(data[i])
+                i += run
+            } else {
+                int start = i
+                while (i < data.length && i - start < 128 && !(i + 1 < data.length && data[i + 1] == data[i])) {
+                    i++
+                }
+                if (i == start) {
+                    i++
+                }
+                out.write(i - start - 1)
+                out.write(data, start, i - start)
+            }
+        }
+        return out.toByteArray()
+    }
+}
@@ -0,0 +1,757 @@
+/*
+ * Copyright (C) 2026 Whitebox GAT contributors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+import com.vividsolutions.jts.geom.Coordinate
+import com.vividsolutions.jts.geom.GeometryFactory
+import java.nio.file.Files
+import whitebox.geospatialfiles.ShapeFile
+import whitebox.geospatialfiles.WhiteboxRaster
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataScale
+import whitebox.geospatialfiles.WhiteboxRasterBase.DataType
+import whitebox.geospatialfiles.shapefile.Geometry
+import whitebox.geospatialfiles.shapefile.Point
+import whitebox.geospatialfiles.shapefile.PolyLine
+import whitebox.geospatialfiles.shapefile.Polygon
+import whitebox.geospatialfiles.shapefile.ShapeFileRecord
+import whitebox.geospatialfiles.shapefile.ShapeType
+import whitebox.geospatialfiles.shapefile.attributes.DBFField
+import whitebox.interfaces.WhiteboxPlugin
+import whitebox.interfaces.WhiteboxPluginHost
+
+// Tests for the vector line and polygon cleanup tools. Each test runs a 
+// plugin on one of the noisy digitized coastline files in tests/data and 
+// checks properties of its output rather than comparing it with a golden 
+// file: the features and their attributes must pass through unchanged, 
+// polygon rings must stay closed with at least four points and must not 
+// become invalid, and tool-specific conditions must hold. coastline.shp 
+// holds two jittered shorelines; island.shp holds an island with a narrow 
+// inlet and a lake, and a small islet. The distance-to-feature rasters 
+// that are calculated from the same files are checked against distances 
+// measured by JTS, and the DEM interpolated from the contours of a cone 
+// against the cone. The poles of inaccessibility of polygons and raster 
+// patches, including concave ones whose centroids lie outside of them, are 
+// checked against their distances from the boundaries, and the shape 
+// metrics of labelled raster patches against hand-computed values. Points 
+// must be snapped to the nearest line or stream cell within the search 
+// distance, and flagged where there is none. Overlapping polygons 
+// rasterized by each method and overlap rule are compared with the expected 
+// rasters in tests/expected.
+//
+// The tests are run against the compiled plugins with 'python build.py 
+// runtests'. After an intended change to the rasterized polygons, the 
+// expected rasters can be recreated by running this script with the 
+// argument 'regenerate'.
+
+def testDir = "tests" + File.separator
+def dataDir = testDir + "data" + File.separator
+def expectedDir = testDir + "expected" + File.separator
+boolean regenerate = args.length > 0 && args[0].toLowerCase().equals("regenerate")
+def outputDir = Files.createTempDirectory("whitebox_tests").toString() + File.separator
+double tolerance = 0.000001
+
+List<String> returned = []
+List<String> feedback = []
+def host = [
+    showFeedback: { Object[] a -> println "    " + a[0]; feedback << a[0].toString(); 0 },
+    logException: { String s, Exception e -> println "    " + s + ": " + e },
+    logThrowable: { String s, Throwable e -> println "    " + s + ": " + e },
+    logMessage: { lvl, String s -> println "    " + s },
+    isRequestForOperationCancelSet: { -> false },
+    returnData: { Object o -> returned << o.toString() },
+].withDefault { k -> { Object[] a -> null } } as WhiteboxPluginHost
+
+def runPlugin = { String name, List<String> pluginArgs ->
+    WhiteboxPlugin plugin = (WhiteboxPlugin)Class.forName("plugins." + name).newInstance()
+    plugin.setPluginHost(host)
+    plugin.setArgs(pluginArgs as String[])
+    plugin.run()
+}
+
+// runs one of the Groovy script tools in resources/plugins/Scripts
+def runScript = { String name, List<String> scriptArgs ->
+    Binding binding = new Binding()
+    binding.setVariable("pluginHost", host)
+    binding.setVariable("args", scriptArgs as String[])
+    new GroovyShell(binding).evaluate(new File("resources/plugins/Scripts/" + name + ".groovy").text, name + "Script")
+}
+
+// returns the parts of a record as lists of [x, y] points
+def getParts = { ShapeFileRecord record ->
+    double[][] points = record.getGeometry().getPoints()
+    int[] parts = record.getGeometry().getParts()
+    (0..<parts.length).collect { int part ->
+        int end = part < parts.length - 1 ? parts[part + 1] : points.length
+        (parts[part]..<end).collect { points[it] }
+    }
+}
+
+def samePoint = { double[] p1, double[] p2 ->
+    Math.abs(p1[0] - p2[0]) < tolerance && Math.abs(p1[1] - p2[1]) < tolerance
+}
+
+def numVertices = { ShapeFile shapefile ->
+    shapefile.records.sum { getParts(it).sum { it.size() } }
+}
+
+// checks the conditions that all of the tools must satisfy
+def checkCommon = { ShapeFile input, ShapeFile output, List<String> failures ->
+    if (output.getNumberOfRecords() != input.getNumberOfRecords()) {
+        failures << "the output has " + output.getNumberOfRecords() + " features, expected " + input.getNumberOfRecords()
+        return
+    }
+    for (int i = 0; i < input.getNumberOfRecords(); i++) {
+        if (input.getAttributeTable().getRecord(i) != output.getAttributeTable().getRecord(i)) {
+            failures << "the attributes of feature " + (i + 1) + " were changed"
+        }
+        ShapeFileRecord inRecord = input.getRecord(i)
+        ShapeFileRecord outRecord = output.getRecord(i)
+        def inParts = getParts(inRecord)
+        def outParts = getParts(outRecord)
+        if (inParts.size() != outParts.size()) {
+            failures << "feature " + (i + 1) + " has " + outParts.size() + " parts, expected " + inParts.size()
+            continue
+        }
+        if (input.getShapeType().getBaseType().toString() == "POLYGON") {
+            outParts.eachWithIndex { part, j ->
+                if (part.size() < 4 || !samePoint(part[0], part[-1])) {
+                    failures << "ring " + j + " of feature " + (i + 1) + " is not closed or has fewer than four points"
+                }
+            }
+            def geom = new GeometryFactory().buildGeometry(outRecord.getGeometry().getJTSGeometries() as List)
+            if (!geom.isValid()) {
+                failures << "feature " + (i + 1) + " is not a valid polygon"
+            }
+        }
+    }
+}
+
+// checks that the end points of each line are unchanged
+def checkEndPoints = { ShapeFile input, ShapeFile output, List<String> failures ->
+    for (int i = 0; i < input.getNumberOfRecords(); i++) {
+        def inParts = getParts(input.getRecord(i))
+        def outParts = getParts(output.getRecord(i))
+        inParts.eachWithIndex { part, j ->
+            if (!samePoint(part[0], outParts[j][0]) || !samePoint(part[-1], outParts[j][-1])) {
+                failures << "the end points of part " + j + " of feature " + (i + 1) + " were moved"
+            }
+        }
+    }
+}
+
+// checks that simplification removed vertices and did not make a line 
+// cross itself
+def checkSimplified = { ShapeFile input, ShapeFile output, List<String> failures ->
+    if (numVertices(output) >= numVertices(input)) {
+        failures << "no vertices were removed"
+    }
+    if (input.getShapeType().getBaseType().toString() == "POLYLINE") {
+        output.records.eachWithIndex { record, i ->
+            def geom = new GeometryFactory().buildGeometry(record.getGeometry().getJTSGeometries() as List)
+            if (!geom.isSimple()) {
+                failures << "feature " + (i + 1) + " crosses itself"
+            }
+        }
+    }
+}
+
+// checks that no segment is longer than the maximum length and that every 
+// input vertex is retained
+def checkDensified = { double maxLength, ShapeFile input, ShapeFile output, List<String> failures ->
+    output.records.eachWithIndex { record, i ->
+        getParts(record).each { part ->
+            for (int j = 1; j < part.size(); j++) {
+                if (Math.hypot(part[j][0] - part[j - 1][0], part[j][1] - part[j - 1][1]) > maxLength + tolerance) {
+                    failures << "feature " + (i + 1) + " has a segment longer than " + maxLength
+                    return
+                }
+            }
+        }
+        def outParts = getParts(record)
+        getParts(input.getRecord(i)).eachWithIndex { part, j ->
+            if (!part.every { p -> outParts[j].any { samePoint(it, p) } }) {
+                failures << "part " + j + " of feature " + (i + 1) + " lost one of its vertices"
+            }
+        }
+    }
+}
+
+// name, plugin, the plugin arguments following the input and output files, 
+// and the tool-specific check
+def tools = [
+    ["simplify_dp", "SimplifyLineOrPolygon", ["5.0", "Douglas-Peucker"], checkSimplified],
+    ["simplify_vw", "SimplifyLineOrPolygon", ["5.0", "Visvalingam-Whyatt"], checkSimplified],
+    ["simplify_dp_coarse", "SimplifyLineOrPolygon", ["50.0", "Douglas-Peucker"], checkSimplified],
+    ["simplify_vw_coarse", "SimplifyLineOrPolygon", ["50.0", "Visvalingam-Whyatt"], checkSimplified],
+    ["smooth_chaikin", "SmoothVectors", ["Chaikin", "3", "not specified"], { i, o, f -> }],
+    ["smooth_average", "SmoothVectors", ["Moving Average", "2", "5"], { i, o, f -> }],
+    ["densify", "DensifyGeometry", ["1.0"], checkDensified.curry(1.0)]
+]
+
+def inputs = ["coastline", "island"]
+
+int numTests = 0
+int numFailed = 0
+tools.each { tool ->
+    inputs.each { inputName ->
+        String name = tool[0] + "_" + inputName
+        String outputFile = outputDir + name + ".shp"
+        numTests++
+        returned.clear()
+        runPlugin(tool[1], [dataDir + inputName + ".shp", outputFile] + tool[2])
+        
+        List<String> failures = []
+        if (!new File(outputFile).exists()) {
+            failures << "no output was created"
+        } else {
+            ShapeFile input = new ShapeFile(dataDir + inputName + ".shp")
+            ShapeFile output = new ShapeFile(outputFile)
+            checkCommon(input, output, failures)
+            if (failures.isEmpty()) {
+                tool[3](input, output, failures)
+                if (input.getShapeType().getBaseType().toString() == "POLYLINE") {
+                    checkEndPoints(input, output, failures)
+                }
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+// Checks the outputs of the Distance To Vector Features tool at every cell. 
+// The exact distances must match those measured by JTS, the direction must 
+// point at the nearest feature and the allocation must be its FID, unless 
+// two features are almost equally near. The rasterized distances must be 
+// within a cell and a half of the exact distances.
+def checkDistances = { String inputName, double cellSize, List<String> failures ->
+    String inputFile = dataDir + inputName + ".shp"
+    String prefix = outputDir + "distance_" + inputName
+    runPlugin("DistanceToVectorFeatures", [inputFile, prefix + ".dep", prefix + "_dir.dep", 
+        prefix + "_alloc.dep", "FID", cellSize.toString(), "not specified", "true"])
+    runPlugin("DistanceToVectorFeatures", [inputFile, prefix + "_rasterized.dep", "not specified",
+        "not specified", "not specified", cellSize.toString(), "not specified", "false"])
+    
+    ShapeFile input = new ShapeFile(inputFile)
+    def factory = new GeometryFactory()
+    def geoms = input.records.collect { factory.buildGeometry(it.getGeometry().getJTSGeometries() as List) }
+    def fids = (0..<input.getNumberOfRecords()).collect { input.getAttributeTable().getValue(it, "FID") as double }
+    WhiteboxRaster distance = new WhiteboxRaster(prefix + ".dep", "r")
+    WhiteboxRaster direction = new WhiteboxRaster(prefix + "_dir.dep", "r")
+    WhiteboxRaster allocation = new WhiteboxRaster(prefix + "_alloc.dep", "r")
+    WhiteboxRaster rasterized = new WhiteboxRaster(prefix + "_rasterized.dep", "r")
+    try {
+        for (int row = 0; row < distance.getNumberRows(); row++) {
+            double y = distance.getYCoordinateFromRow(row)
+            for (int col = 0; col < distance.getNumberColumns(); col++) {
+                double x = distance.getXCoordinateFromColumn(col)
+                def dists = geoms.collect { it.distance(factory.createPoint(new Coordinate(x, y))) }
+                double d = dists.min()
+                int nearest = dists.indexOf(d)
+                String cell = "cell (" + row + ", " + col + ")"
+                if (Math.abs(distance.getValue(row, col) - d) > 0.001) {
+                    failures << cell + " has a distance of " + distance.getValue(row, col) + " rather than " + d
+                    return
+                }
+                if (dists.count { it - d < 0.01 } == 1 && allocation.getValue(row, col) != fids[nearest]) {
+                    failures << cell + " is allocated to " + allocation.getValue(row, col) + " rather than " + fids[nearest]
+                    return
+                }
+                if (d > 0.01) {
+                    double az = Math.toRadians(direction.getValue(row, col))
+                    def target = factory.createPoint(new Coordinate(x + d * Math.sin(az), y + d * Math.cos(az)))
+                    if (geoms[nearest].distance(target) > 0.01) {
+                        failures << cell + " has a direction of " + direction.getValue(row, col) + " that does not point at the nearest feature"
+                        return
+                    }
+                } else if (d == 0 && direction.getValue(row, col) != direction.getNoDataValue()) {
+                    failures << cell + " is on a feature but has a direction of " + direction.getValue(row, col)
+                    return
+                }
+                if (Math.abs(rasterized.getValue(row, col) - d) > 1.5 * cellSize) {
+                    failures << cell + " has a rasterized distance of " + rasterized.getValue(row, col) + " rather than about " + d
+                    return
+                }
+            }
+        }
+    } finally {
+        [distance, direction, allocation, rasterized]*.close()
+    }
+}
+
+inputs.each { inputName ->
+    String name = "distance_" + inputName
+    numTests++
+    List<String> failures = []
+    checkDistances(inputName, 10.0, failures)
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+// writes a shapefile of the given geometries with an elevation attribute
+def writeShapes = { String file, ShapeType shapeType, List<Geometry> geometries, List<Double> elevations ->
+    DBFField[] fields = new DBFField[1]
+    fields[0] = new DBFField()
+    fields[0].setName("ELEV")
+    fields[0].setDataType(DBFField.DBFDataType.NUMERIC)
+    fields[0].setFieldLength(10)
+    fields[0].setDecimalCount(3)
+    ShapeFile output = new ShapeFile(file, shapeType, fields)
+    for (int i = 0; i < geometries.size(); i++) {
+        Object[] rowData = [elevations[i] as Double]
+        output.addRecord(geometries[i], rowData)
+    }
+    output.write()
+}
+
+// The DEM interpolated from the circular contours of a cone, z = 50 - r,
+// with a spot height at its apex honours the spot height and lies between
+// the contours. Between two contours the interpolated surface is harmonic,
+// i.e. it varies with the logarithm of r rather than with r, and so stays
+// within a unit of the cone. Without the spot height, the area within the
+// innermost contour is interpolated as flat and reported as such.
+int numContours = 4
+double contourInterval = 8
+def coneContours = (1..numContours).collect { int i ->
+    double r = i * contourInterval
+    double[][] points = (0..72).collect { int j -> [r * Math.sin(Math.toRadians(j * 5)), r * Math.cos(Math.toRadians(j * 5))] as double[] }
+    new PolyLine([0] as int[], points)
+}
+writeShapes(outputDir + "cone_contours.shp", ShapeType.POLYLINE, coneContours,
+    (1..numContours).collect { 50 - it * contourInterval })
+writeShapes(outputDir + "cone_apex.shp", ShapeType.POINT, [new Point(0, 0)], [50d])
+[["contours_to_dem_cone", outputDir + "cone_apex.shp"], ["contours_to_dem_cone_no_apex", "not specified"]].each { test ->
+    String name = test[0]
+    String outputFile = outputDir + name + ".dep"
+    boolean hasApex = test[1] != "not specified"
+    numTests++
+    feedback.clear()
+    runPlugin("ContoursToDEM", [outputDir + "cone_contours.shp", "ELEV", test[1], "not specified", outputFile,
+        "1", "not specified", "0.0001"])
+    List<String> failures = []
+    if (!new File(outputFile).exists()) {
+        failures << "no output was created"
+    } else {
+        WhiteboxRaster dem = new WhiteboxRaster(outputFile, "r")
+        double rMax = numContours * contourInterval
+        for (int row = 0; row < dem.getNumberRows() && failures.isEmpty(); row++) {
+            for (int col = 0; col < dem.getNumberColumns() && failures.isEmpty(); col++) {
+                double x = dem.getXCoordinateFromColumn(col)
+                double y = dem.getYCoordinateFromRow(row)
+                double r = Math.sqrt(x * x + y * y)
+                double z = dem.getValue(row, col)
+                String cell = "cell (" + row + ", " + col + ") at a distance of " + String.format("%.2f", r)
+                if (r < 0.5 && hasApex && Math.abs(z - 50) > 1e-6) {
+                    failures << cell + " contains the apex but is " + z
+                } else if (r < contourInterval - 1) {
+                    double innermost = 50 - contourInterval
+                    if (hasApex && (z <= innermost || z > 50)) {
+                        failures << cell + " is " + z + ", which is not between the innermost contour and the apex"
+                    } else if (!hasApex && Math.abs(z - innermost) > 1e-3) {
+                        failures << cell + " is " + z + " rather than flat at " + innermost
+                    }
+                } else if (r >= contourInterval && r <= rMax && Math.abs(z - (50 - r)) > 1) {
+                    failures << cell + " is " + z + " rather than about " + (50 - r)
+                }
+            }
+        }
+        dem.close()
+        boolean reportedFlat = feedback.any { it.contains("enclosed by a single contour") }
+        if (reportedFlat == hasApex) {
+            failures << (hasApex ? "a flat summit was reported: " : "the flat summit was not reported: ") + feedback
+        }
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+// The pole of inaccessibility of a U-shaped polygon, whose centroid lies in
+// the notch outside of it, is at one of the inner corners of the base, where
+// the inscribed circle touches the outer sides and the inner corner, at a
+// distance of 10 sqrt(2) / (1 + sqrt(2)) from each side. The pole of a square
+// is its centre. Each pole must be inside its polygon at the reported
+// distance from the boundary.
+numTests++
+List<String> poleFailures = []
+String poleInput = outputDir + "pole_polygons.shp"
+String poleOutput = outputDir + "pole_points.shp"
+double[][] uShape = [[0, 0], [0, 30], [10, 30], [10, 10], [20, 10], [20, 30], [30, 30], [30, 0], [0, 0]]
+double[][] square = [[40, 0], [40, 10], [50, 10], [50, 0], [40, 0]]
+writeShapes(poleInput, ShapeType.POLYGON, [new Polygon([0] as int[], uShape), new Polygon([0] as int[], square)], [1d, 2d])
+runScript("PoleOfInaccessibilityVector", [poleInput, poleOutput, "0.001", "false"])
+if (!new File(poleOutput).exists()) {
+    poleFailures << "no output was created"
+} else {
+    def factory = new GeometryFactory()
+    ShapeFile polygons = new ShapeFile(poleInput)
+    ShapeFile poles = new ShapeFile(poleOutput)
+    List<Double> expectedDistances = [10 * Math.sqrt(2) / (1 + Math.sqrt(2)), 5d]
+    if (poles.getNumberOfRecords() != 2) {
+        poleFailures << "the output has " + poles.getNumberOfRecords() + " points rather than 2"
+    }
+    for (int i = 0; i < Math.min(poles.getNumberOfRecords(), 2); i++) {
+        def polygon = factory.buildGeometry(polygons.getRecord(i).getGeometry().getJTSGeometries() as List)
+        double[] point = poles.getRecord(i).getGeometry().getPoints()[0]
+        def pole = factory.createPoint(new Coordinate(point[0], point[1]))
+        double reported = poles.getAttributeTable().getValue(i, "DIST") as double
+        double distance = polygon.getBoundary().distance(pole)
+        if (i == 0 && polygon.contains(polygon.getCentroid())) {
+            poleFailures << "the centroid of the U-shaped polygon is not outside of it"
+        }
+        if (!polygon.contains(pole)) {
+            poleFailures << "the pole of polygon " + (i + 1) + " at " + point + " is outside of it"
+        } else if (Math.abs(reported - distance) > 0.001 || Math.abs(distance - expectedDistances[i]) > 0.002) {
+            poleFailures << "the pole of polygon " + (i + 1) + " is " + distance + " from the boundary and reported as " +
+                reported + " rather than " + expectedDistances[i]
+        }
+    }
+}
+if (poleFailures.isEmpty()) {
+    println "PASSED pole_of_inaccessibility_vector"
+} else {
+    numFailed++
+    println "FAILED pole_of_inaccessibility_vector"
+    poleFailures.each { println "    " + it }
+}
+
+// The raster pole of each patch, one a U shape whose centroid falls outside
+// of it and the other a rectangle, is a cell at the greatest distance from
+// the cells outside of the patch, including those beyond the edges of the
+// grid, found by comparing every pair of cells.
+numTests++
+poleFailures = []
+int poleRows = 14
+int poleCols = 20
+def patchOf = { int row, int col ->
+    if (row >= 1 && row <= 12 && col >= 1 && col <= 9 && !(row <= 8 && col >= 4 && col <= 6)) {
+        return 1
+    } else if (row >= 3 && row <= 8 && col >= 12 && col <= 18) {
+        return 2
+    }
+    return 0
+}
+WhiteboxRaster patches = new WhiteboxRaster(outputDir + "pole_patches.dep", (double)poleRows, 0.0, (double)poleCols, 0.0,
+    poleRows, poleCols, DataScale.CATEGORICAL, DataType.INTEGER, 0.0, -32768.0)
+for (int row = 0; row < poleRows; row++) {
+    for (int col = 0; col < poleCols; col++) {
+        patches.setValue(row, col, patchOf(row, col))
+    }
+}
+patches.close()
+def distanceToOutside = { int row, int col ->
+    double minDist = Double.MAX_VALUE
+    for (int r = -1; r <= poleRows; r++) {
+        for (int c = -1; c <= poleCols; c++) {
+            boolean outside = r < 0 || r >= poleRows || c < 0 || c >= poleCols || patchOf(r, c) != patchOf(row, col)
+            if (outside) {
+                minDist = Math.min(minDist, Math.sqrt((r - row) * (r - row) + (c - col) * (c - col)))
+            }
+        }
+    }
+    minDist
+}
+returned.clear()
+runPlugin("PoleOfInaccessibility", [outputDir + "pole_patches.dep", outputDir + "pole_cells.dep", "true"])
+if (!new File(outputDir + "pole_cells.dep").exists()) {
+    poleFailures << "no output was created"
+} else {
+    WhiteboxRaster poleCells = new WhiteboxRaster(outputDir + "pole_cells.dep", "r")
+    for (int id = 1; id <= 2; id++) {
+        List<List<Integer>> marked = []
+        double maxDist = 0
+        for (int row = 0; row < poleRows; row++) {
+            for (int col = 0; col < poleCols; col++) {
+                if (patchOf(row, col) == id) {
+                    maxDist = Math.max(maxDist, distanceToOutside(row, col))
+                }
+                if (poleCells.getValue(row, col) == id) {
+                    marked << [row, col]
+                }
+            }
+        }
+        if (marked.size() != 1) {
+            poleFailures << "patch " + id + " has " + marked.size() + " pole cells"
+        } else if (patchOf(marked[0][0], marked[0][1]) != id) {
+            poleFailures << "the pole cell " + marked[0] + " of patch " + id + " is outside of it"
+        } else if (Math.abs(distanceToOutside(marked[0][0], marked[0][1]) - maxDist) > 1e-6) {
+            poleFailures << "the pole cell " + marked[0] + " of patch " + id + " is " +
+                distanceToOutside(marked[0][0], marked[0][1]) + " from the outside rather than " + maxDist
+        } else if (!returned.any { it.contains("\n" + id + "\t" + marked[0][1] + "\t" + marked[0][0] + "\t" + (float) maxDist) }) {
+            poleFailures << "the pole of patch " + id + " is not reported correctly: " + returned
+        }
+    }
+    poleCells.close()
+}
+if (poleFailures.isEmpty()) {
+    println "PASSED pole_of_inaccessibility_raster"
+} else {
+    numFailed++
+    println "FAILED pole_of_inaccessibility_raster"
+    poleFailures.each { println "    " + it }
+}
+
+// The shape metrics of three labelled patches on a grid of unit cells are
+// compared with values worked out by hand: a 3 x 3 square, a 1 x 5 strip and
+// an L shape of six cells, four cells down and two more across the bottom.
+// Zero-valued cells and one NoData cell are background. The perimeters are
+// 12, 12 and 14 and the fractal dimensions are 2 ln(P / 4) / ln(A). The
+// principal second moments, including the 1/12 moment of each cell, are
+// equal for the square, 25/12 and 1/12 for the strip and 5/3 and 5/12 for
+// the L, so the elongations are 0, 0.8 and 0.5.
+numTests++
+List<String> shapeFailures = []
+int shapeRows = 6
+int shapeCols = 8
+def shapeClassOf = { int row, int col ->
+    if (row <= 2 && col <= 2) {
+        return 1
+    } else if (row == 5 && col <= 4) {
+        return 2
+    } else if ((col == 5 && row <= 3) || (row == 3 && col >= 6)) {
+        return 3
+    }
+    return 0
+}
+WhiteboxRaster shapeClasses = new WhiteboxRaster(outputDir + "shape_classes.dep", (double)shapeRows, 0.0,
+    (double)shapeCols, 0.0, shapeRows, shapeCols, DataScale.CATEGORICAL, DataType.INTEGER, 0.0, -32768.0)
+for (int row = 0; row < shapeRows; row++) {
+    for (int col = 0; col < shapeCols; col++) {
+        shapeClasses.setValue(row, col, shapeClassOf(row, col))
+    }
+}
+shapeClasses.setValue(5, 7, -32768.0)
+shapeClasses.close()
+// by class: PERIM_AREA_RATIO, COMPACTNESS, ELONGATION and FRACTAL_DIM
+def expectedShapes = [
+    1: [12 / 9d, Math.PI / 4, 0d, 1d],
+    2: [12 / 5d, 4 * Math.PI * 5 / 144, 0.8d, 2 * Math.log(3) / Math.log(5)],
+    3: [14 / 6d, 4 * Math.PI * 6 / 196, 0.5d, 2 * Math.log(3.5) / Math.log(6)]
+]
+def shapeMetrics = ["PERIM_AREA_RATIO", "COMPACTNESS", "ELONGATION", "FRACTAL_DIM"]
+String shapeTable = outputDir + "shape_metrics.csv"
+runScript("PatchShapeMetrics", [outputDir + "shape_classes.dep", shapeTable, "true", "true", "patch", "1"])
+if (!new File(shapeTable).exists()) {
+    shapeFailures << "no table was created"
+} else {
+    List<String> lines = new File(shapeTable).readLines()
+    List<String> header = lines[0].split(",") as List
+    if (lines.size() != 4) {
+        shapeFailures << "the table has " + (lines.size() - 1) + " patches rather than 3"
+    }
+    for (String line : lines.drop(1)) {
+        List<String> values = line.split(",", -1) as List
+        int classValue = values[header.indexOf("CLASS")] as int
+        for (int m = 0; m < shapeMetrics.size(); m++) {
+            String value = values[header.indexOf(shapeMetrics[m])]
+            double expected = expectedShapes[classValue][m]
+            if (value.isEmpty() || Math.abs(Double.parseDouble(value) - expected) > 1e-6) {
+                shapeFailures << "the " + shapeMetrics[m] + " of class " + classValue + " is '" + value +
+                    "' rather than " + expected
+            }
+        }
+    }
+}
+for (int m = 0; m < shapeMetrics.size(); m++) {
+    String rasterFile = outputDir + "shape_metrics_" + shapeMetrics[m].toLowerCase() + ".dep"
+    if (!new File(rasterFile).exists()) {
+        shapeFailures << "no " + shapeMetrics[m] + " raster was created"
+        continue
+    }
+    WhiteboxRaster metricRaster = new WhiteboxRaster(rasterFile, "r")
+    for (int row = 0; row < shapeRows; row++) {
+        for (int col = 0; col < shapeCols; col++) {
+            int classValue = (row == 5 && col == 7) ? 0 : shapeClassOf(row, col)
+            double value = metricRaster.getValue(row, col)
+            double expected = classValue == 0 ? metricRaster.getNoDataValue() : expectedShapes[classValue][m]
+            if (Math.abs(value - expected) > 1e-5) {
+                shapeFailures << "cell " + row + ", " + col + " of the " + shapeMetrics[m] + " raster is " + value +
+                    " rather than " + expected
+                break
+            }
+        }
+    }
+    metricRaster.close()
+}
+if (shapeFailures.isEmpty()) {
+    println "PASSED patch_shape_metrics"
+} else {
+    numFailed++
+    println "FAILED patch_shape_metrics"
+    shapeFailures.each { println "    " + it }
+}
+
+// runs a snapping tool on points numbered by their ELEV attribute and checks
+// each output point against its expected location and attributes, given as
+// [x, y, SNAP_DIST, and the tool-specific attributes] or as null for a point
+// that should be flagged as not snapped
+def checkSnapped = { String name, List<String> toolArgs, List<List<Double>> expected, List<String> fieldNames,
+        double[][] inputPoints ->
+    numTests++
+    List<String> failures = []
+    String outputFile = toolArgs[2]
+    boolean original = toolArgs.any { it.toLowerCase().contains("original") }
+    feedback.clear()
+    runPlugin(name.startsWith("snap_points_to_lines") ? "SnapPointsToLines" : "SnapPointsToRaster", toolArgs)
+    if (!new File(outputFile).exists()) {
+        failures << "no output was created"
+    } else {
+        ShapeFile output = new ShapeFile(outputFile)
+        if (output.getNumberOfRecords() != expected.size()) {
+            failures << "the output has " + output.getNumberOfRecords() + " points rather than " + expected.size()
+        }
+        def table = output.getAttributeTable()
+        for (int i = 0; i < Math.min(output.getNumberOfRecords(), expected.size()); i++) {
+            double[] point = output.getRecord(i).getGeometry().getPoints()[0]
+            List<Double> e = expected[i]
+            double[] location = (e == null || original) ? inputPoints[i] : [e[0], e[1]] as double[]
+            String pointName = "point " + (table.getValue(i, "ELEV") as int)
+            if (Math.abs(point[0] - location[0]) > 1e-9 || Math.abs(point[1] - location[1]) > 1e-9) {
+                failures << pointName + " is at " + point + " rather than " + location
+            }
+            double snapped = table.getValue(i, "SNAPPED") as double
+            if (snapped != (e == null ? 0 : 1)) {
+                failures << pointName + " has a SNAPPED value of " + snapped
+            } else if (e == null) {
+                if (table.getValue(i, "SNAP_DIST") != null || fieldNames.any { table.getValue(i, it) != null }) {
+                    failures << pointName + " was not snapped but has snapping attributes " + table.getRecord(i)
+                }
+            } else {
+                List<Double> values = [table.getValue(i, "SNAP_DIST") as double] + fieldNames.collect { table.getValue(i, it) as double }
+                for (int j = 0; j < values.size(); j++) {
+                    if (Math.abs(values[j] - e[j + 2]) > 0.0005 + 1e-9) {
+                        failures << pointName + " has attributes " + values + " rather than " + e[2..-1]
+                        break
+                    }
+                }
+            }
+        }
+        int numUnsnapped = expected.count { it == null }
+        if (numUnsnapped > 0 && !feedback.any { it.contains(numUnsnapped + " ") }) {
+            failures << "the " + numUnsnapped + " points that were not snapped were not reported: " + feedback
+        }
+    }
+    if (failures.isEmpty()) {
+        println "PASSED " + name
+    } else {
+        numFailed++
+        println "FAILED " + name
+        failures.each { println "    " + it }
+    }
+}
+
+// Points are snapped to the nearest location on either of two lines, the
+// first of which bends, within a search distance of 5. The distance along a
+// line is measured from its first vertex. A point just beyond the search
+// distance and a distant point are kept where they are and flagged.
+String snapLines = outputDir + "snap_lines.shp"
+writeShapes(snapLines, ShapeType.POLYLINE, [new PolyLine([0] as int[], [[0, 0], [10, 0], [10, 10]] as double[][]),
+    new PolyLine([0] as int[], [[20, 0], [20, 20]] as double[][])], [1d, 2d])
+double[][] snapPoints = [[4, 1.5], [11, 7], [16, 5], [4, 5.5], [50, 50]]
+String snapPointsFile = outputDir + "snap_points.shp"
+writeShapes(snapPointsFile, ShapeType.POINT, snapPoints.collect { new Point(it[0], it[1]) }, [1d, 2d, 3d, 4d, 5d])
+// x, y, SNAP_DIST, LINE_ID and ALONG_DIST
+List<List<Double>> snappedToLines = [[4d, 0d, 1.5d, 0d, 4d], [10d, 7d, 1d, 0d, 17d], [20d, 5d, 4d, 1d, 5d], null, null]
+["snapped", "original"].each { mode ->
+    String name = "snap_points_to_lines_" + mode
+    checkSnapped(name, [snapPointsFile, snapLines, outputDir + name + ".shp", "5", mode + " points", "not specified"],
+        snappedToLines, ["LINE_ID", "ALONG_DIST"], snapPoints)
+}
+
+// Points are snapped to the centre of the nearest stream cell within a search
+// distance of 4. The streams are a column of cells with a link ID of 3 and a
+// corner cell with a negative value, so that the point near that corner is
+// only snapped when any value other than zero is accepted. The point in
+// another corner is more than 4 from any stream, and a NoData cell next to
+// the last point is never snapped to.
+String snapStreams = outputDir + "snap_streams.dep"
+WhiteboxRaster streams = new WhiteboxRaster(snapStreams, 10.0, 0.0, 10.0, 0.0, 10, 10,
+    DataScale.CONTINUOUS, DataType.FLOAT, 0.0, -32768.0)
+for (int row = 0; row < 10; row++) {
+    streams.setValue(row, 5, 3.0)
+}
+streams.setValue(9, 0, -1.0)
+streams.setValue(2, 8, -32768.0)
+streams.close()
+double[][] rasterPoints = [[2.2, 4.5], [0.6, 0.4], [0.2, 9.8], [8.4, 7.6]]
+String rasterPointsFile = outputDir + "snap_raster_points.shp"
+writeShapes(rasterPointsFile, ShapeType.POINT, rasterPoints.collect { new Point(it[0], it[1]) }, [1d, 2d, 3d, 4d])
+// x, y, SNAP_DIST and CELL_VALUE
+[["greater than zero", [[5.5d, 4.5d, 3.3d, 3d], null, null, [5.5d, 7.5d, Math.hypot(2.9, 0.1), 3d]]],
+ ["not zero", [[5.5d, 4.5d, 3.3d, 3d], [0.5d, 0.5d, Math.hypot(0.1, 0.1), -1d], null, [5.5d, 7.5d, Math.hypot(2.9, 0.1), 3d]]]
+].each { test ->
+    String name = "snap_points_to_raster_" + test[0].replace(" ", "_")
+    checkSnapped(name, [rasterPointsFile, snapStreams, outputDir + name + ".shp", "4", test[0], "snapped points"],
+        test[1], ["CELL_VALUE"], rasterPoints)
+}
+
+// Three overlapping polygons, whose edges do not follow the grid, are
+// rasterized by each method under each overlap rule and compared with the
+// expected rasters in tests/expected.
+String overlapInput = outputDir + "overlapping_polygons.shp"
+double[][] squareA = [[0, 0], [0, 6.3], [6.3, 6.3], [6.3, 0], [0, 0]]
+double[][] squareB = [[3.5, 2.2], [3.5, 8.6], [9.7, 8.6], [9.7, 2.2], [3.5, 2.2]]
+double[][] triangle = [[1.2, 7.9], [8.4, 4.1], [2.6, 0.4], [1.2, 7.9]]
+writeShapes(overlapInput, ShapeType.POLYGON, [squareA, squareB, triangle].collect { new Polygon([0] as int[], it) },
+    [1d, 10d, 100d])
+for (String method : ["centroid", "majority area", "any part"]) {
+    for (String overlapRule : ["first", "last", "sum"]) {
+        String name = "polygon_to_raster_" + method.replace(" ", "_") + "_" + overlapRule
+        String outputFile = (regenerate ? expectedDir : outputDir) + name + ".dep"
+        numTests++
+        runPlugin("PolygonToRaster", [overlapInput, outputFile, "ELEV", "nodata", "0.5", "not specified",
+            method, "4", overlapRule])
+        if (regenerate) {
+            println "Regenerated " + name
+            continue
+        }
+        List<String> failures = []
+        if (!new File(outputFile).exists()) {
+            failures << "no output was created"
+        } else {
+            returned.clear()
+            runPlugin("CompareRasters", [expectedDir + name + ".dep", outputFile, "0", "5"])
+            String report = returned.isEmpty() ? "" : returned[0]
+            if (!report.contains("Result:\tIDENTICAL")) {
+                failures << "the output differs from the expected raster\n" + report
+            }
+        }
+        if (failures.isEmpty()) {
+            println "PASSED " + name
+        } else {
+            numFailed++
+            println "FAILED " + name
+            failures.each { println "    " + it.replace("\n", "\n    ") }
+        }
+    }
+}
+
+println "\n" + (numTests - numFailed) + " of " + numTests + " tests passed."
+System.exit(numFailed > 0 ? 1 : 0)
@@ -0,0 +1,162 @@
+# Exports a JSON manifest describing the Whitebox GAT plugin tools
+#
+# Copyright (C) 2026 Whitebox GAT contributors
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+# The manifest is a JSON array with one object for each plugin registered in a
+# META-INF/services file, sorted by tool name:
+#
+#   {"name": ..., "descriptive_name": ..., "description": ...,
+#    "toolbox": [{"name": ..., "label": ...}, ...], "parameters": [...]}
+#
+# The toolboxes are those returned by the plugin's getToolbox() method, with
+# the labels used in the tool tree (resources/toolbox.xml). The parameters are
+# read from the tool's dialog file and are listed in the order in which the
+# plugin expects its arguments. Tools that provide their own dialog, or that
+# have no dialog file, have null parameters. This lets front-ends and
+# documentation builders discover the tools without running them. Run with
+# 'python build.py manifest' or 'python tool_manifest.py [output file]'.
+
+import json
+import os
+import re
+import sys
+import xml.etree.ElementTree as ET
+
+from python_api import findPluginSources, getReturnedString, getText, isTrue, dialogsDir
+
+toolboxFile = os.path.join('resources', 'toolbox.xml')
+outputFile = os.path.join('resources', 'plugins', 'tool_manifest.json')
+
+
+# reads the string literals in the body of a plugin's getToolbox() method
+def getToolboxes(source):
+    m = re.search(r'public\s+String\s*\[\]\s+getToolbox\s*\(\s*\)\s*\{(.*?)\n\s*\}', source, re.S)
+    if not m:
+        return []
+    return re.findall(r'"((?:[^"\\]|\\.)*)"', m.group(1))
+
+
+# maps each toolbox name in toolbox.xml to its label
+def readToolboxLabels():
+    labels = {}
+    if not os.path.exists(toolboxFile):
+        return labels
+    for el in ET.parse(toolboxFile).getroot().iter('toolbox'):
+        if el.get('name'):
+            labels[el.get('name')] = el.get('label') or el.get('name')
+    return labels
+
+
+# converts a dialog component into a parameter description, or None for
+# components, such as labels, that aren't arguments
+def readComponent(el):
+    componentType = el.get('type')
+    param = {
+        'name': getText(el, 'Name'),
+        'label': getText(el, 'LabelText'),
+        'description': getText(el, 'Description') or getText(el, 'LabelText'),
+        'component': componentType,
+        'optional': isTrue(el, 'MakeOptional'),
+        'default': None
+    }
+    if componentType == 'DialogFile':
+        mode = getText(el, 'DialogMode')
+        param['type'] = 'output file' if mode.lower().startswith('save') else 'input file'
+        param['filter'] = getText(el, 'Filter')
+    elif componentType == 'DialogMultiFile':
+        param['type'] = 'file list'
+        param['filter'] = getText(el, 'Filter')
+    elif componentType == 'DialogCheckBox':
+        param['type'] = 'boolean'
+        param['default'] = isTrue(el, 'InitialState')
+    elif componentType == 'DialogComboBox':
+        choices = [c.strip() for c in getText(el, 'ListItems').split(',')]
+        param['type'] = 'choice'
+        param['choices'] = choices
+        try:
+            param['default'] = choices[int(getText(el, 'DefaultItem', '0'))]
+        except (ValueError, IndexError):
+            param['default'] = choices[0]
+    elif componentType == 'DialogOption':
+        param['type'] = 'choice'
+        param['choices'] = [getText(el, 'Button1Label'), getText(el, 'Button2Label')]
+        param['default'] = param['choices'][0]
+    elif componentType == 'DialogDataInput':
+        numerical = isTrue(el, 'NumericalInputOnly')
+        param['type'] = 'number' if numerical else 'text'
+        initialText = getText(el, 'InitialText')
+        if initialText:
+            param['default'] = initialText
+            if numerical:
+                try:
+                    param['default'] = float(initialText)
+                except ValueError:
+                    pass
+    elif componentType in ('DialogFieldSelector', 'DialogWeightedMultiFile', 'DialogReclassGrid'):
+        param['type'] = 'text'
+    else:
+        return None
+    return param
+
+
+# reads the parameters of a tool from its dialog file, or returns None if the
+# tool has no dialog file or provides its own dialog
+def readParameters(toolName):
+    dialogFile = os.path.join(dialogsDir, toolName + '.xml')
+    if not os.path.exists(dialogFile):
+        return None
+    try:
+        root = ET.parse(dialogFile).getroot()
+    except ET.ParseError as e:
+        print("Unable to read " + dialogFile + ": " + str(e))
+        return None
+    params = []
+    for el in root.iter('DialogComponent'):
+        if el.get('type') == 'CustomDialogProvidedByPlugin':
+            return None
+        p = readComponent(el)
+        if p is not None:
+            params.append(p)
+    return params
+
+
+def buildManifest():
+    labels = readToolboxLabels()
+    manifest = []
+    for name, source in sorted(findPluginSources().items()):
+        manifest.append({
+            'name': name,
+            'descriptive_name': getReturnedString(source, 'getDescriptiveName') or name,
+            'description': getReturnedString(source, 'getToolDescription') or '',
+            'toolbox': [{'name': t, 'label': labels.get(t, t)} for t in getToolboxes(source)],
+            'parameters': readParameters(name)
+        })
+    return manifest
+
+
+def generate(fileName=outputFile):
+    print("Generating tool manifest: " + fileName)
+    manifest = buildManifest()
+    with open(fileName, 'wb') as f:
+        f.write(json.dumps(manifest, indent=2, sort_keys=True).encode('utf-8'))
+    print("Wrote " + str(len(manifest)) + " tools")
+
+
+if __name__ == '__main__':
+    if len(sys.argv) > 1:
+        generate(sys.argv[1])
+    else:
+        generate()